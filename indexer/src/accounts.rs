@@ -0,0 +1,36 @@
+use anchor_lang::{prelude::*, AccountDeserialize, Discriminator};
+use m2::states::*;
+
+/// An M2 account, trade states of every version are normalized into a `Bid` or a `Listing`
+pub enum M2Account {
+    Bid(BidArgs),
+    Listing(SellArgs),
+    AuctionHouse(Box<AuctionHouse>),
+    Other([u8; 8]),
+}
+
+/// Decodes the data of an account owned by the M2 program, None for closed accounts (empty or
+/// with a zeroed discriminator)
+pub fn decode_account(data: &[u8]) -> Result<Option<M2Account>> {
+    if data.len() < 8 || data[..8] == [0; 8] {
+        return Ok(None);
+    }
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&data[..8]);
+    let account = if discriminator == BuyerTradeState::discriminator()
+        || discriminator == BuyerTradeStateV2::discriminator()
+        || discriminator == BuyerTradeStateV3::discriminator()
+    {
+        M2Account::Bid(BidArgs::from_data(data)?)
+    } else if discriminator == SellerTradeState::discriminator()
+        || discriminator == SellerTradeStateV2::discriminator()
+        || discriminator == SellerTradeStateV3::discriminator()
+    {
+        M2Account::Listing(SellArgs::from_data(data)?)
+    } else if discriminator == AuctionHouse::discriminator() {
+        M2Account::AuctionHouse(Box::new(AuctionHouse::try_deserialize(&mut &data[..])?))
+    } else {
+        M2Account::Other(discriminator)
+    };
+    Ok(Some(account))
+}
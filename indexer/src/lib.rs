@@ -0,0 +1,12 @@
+//! Decoders for M2 accounts and transaction logs, for indexers fed by RPC subscriptions
+//! (accountSubscribe / logsSubscribe) or a Geyser plugin. Both hand over raw account data and
+//! log messages, which is all this crate takes, so it has no transport of its own.
+//!
+//! Trade states are decoded with the program's own `BidArgs::from_data` and
+//! `SellArgs::from_data`, the same code the instructions read them with.
+
+pub mod accounts;
+pub mod logs;
+
+pub use accounts::*;
+pub use logs::*;
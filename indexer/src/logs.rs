@@ -0,0 +1,214 @@
+use serde_json::{Map, Value};
+
+const PROGRAM_LOG: &str = "Program log: ";
+const INSTRUCTION_LOG: &str = "Program log: Instruction: ";
+
+/// A fill, from the JSON line the execute instructions log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sale {
+    pub instruction: String,
+    pub price: u64,
+    pub seller_expiry: i64,
+    pub buyer_expiry: i64,
+    pub royalty: Option<u64>,
+    /// Units sold, price is per unit. 1 for NFTs and for sales logged before SFTs could trade.
+    pub token_size: u64,
+}
+
+/// A listing placed or repriced
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListingEvent {
+    pub instruction: String,
+    pub price: u64,
+    pub seller_expiry: i64,
+}
+
+/// A bid placed or updated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BidEvent {
+    pub instruction: String,
+    pub price: u64,
+    pub buyer_expiry: i64,
+}
+
+/// A creator's royalty from a sale, logged once per creator paid, or by distribute_royalties for
+/// royalties a sale left pending
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoyaltyPayment {
+    pub instruction: String,
+    pub creator: String,
+    /// The default pubkey for SOL
+    pub payment_mint: String,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum M2Event {
+    Sale(Sale),
+    Listing(ListingEvent),
+    Bid(BidEvent),
+    RoyaltyPayment(RoyaltyPayment),
+    /// Any other JSON line the program logged, e.g. a deposit receipt or an order memo
+    Other {
+        instruction: String,
+        fields: Map<String, Value>,
+    },
+}
+
+/// Decodes the JSON lines M2 logged in a transaction, given its log messages as returned by
+/// getTransaction, logsSubscribe or Geyser. Each event carries the name of the instruction that
+/// logged it, as Anchor logs it (e.g. "ExecuteSaleV2"). Lines of other programs are skipped.
+pub fn decode_logs(program_id: &str, logs: &[String]) -> Vec<M2Event> {
+    let invoke = format!("Program {} invoke", program_id);
+    // the programs on the invoke stack, and the instruction M2 is running where it is on top
+    let mut stack: Vec<Option<String>> = vec![];
+    let mut events = vec![];
+    for line in logs {
+        if let Some(rest) = line.strip_prefix("Program ") {
+            if rest.contains(" invoke [") {
+                stack.push(if line.starts_with(&invoke) {
+                    Some(String::new())
+                } else {
+                    None
+                });
+                continue;
+            }
+            if rest.ends_with(" success") || rest.contains(" failed: ") {
+                stack.pop();
+                continue;
+            }
+        }
+        let instruction = match stack.last_mut() {
+            Some(Some(instruction)) => instruction,
+            _ => continue,
+        };
+        if let Some(name) = line.strip_prefix(INSTRUCTION_LOG) {
+            *instruction = name.to_string();
+        } else if let Some(Value::Object(fields)) = line
+            .strip_prefix(PROGRAM_LOG)
+            .and_then(|json| serde_json::from_str(json).ok())
+        {
+            events.push(to_event(instruction.clone(), fields));
+        }
+    }
+    events
+}
+
+fn to_event(instruction: String, fields: Map<String, Value>) -> M2Event {
+    let u64_field = |name: &str| fields.get(name).and_then(Value::as_u64);
+    let i64_field = |name: &str| fields.get(name).and_then(Value::as_i64);
+    let str_field = |name: &str| fields.get(name).and_then(Value::as_str).map(str::to_string);
+    if let (Some(amount), Some(creator), Some(payment_mint)) = (
+        u64_field("royalty_paid"),
+        str_field("creator"),
+        str_field("payment_mint"),
+    ) {
+        return M2Event::RoyaltyPayment(RoyaltyPayment {
+            instruction,
+            creator,
+            payment_mint,
+            amount,
+        });
+    }
+    match (
+        u64_field("price"),
+        i64_field("seller_expiry"),
+        i64_field("buyer_expiry"),
+    ) {
+        (Some(price), Some(seller_expiry), Some(buyer_expiry)) => M2Event::Sale(Sale {
+            royalty: u64_field("royalty"),
+            token_size: u64_field("token_size").unwrap_or(1),
+            instruction,
+            price,
+            seller_expiry,
+            buyer_expiry,
+        }),
+        (Some(price), Some(seller_expiry), None) => M2Event::Listing(ListingEvent {
+            instruction,
+            price,
+            seller_expiry,
+        }),
+        (Some(price), None, Some(buyer_expiry)) => M2Event::Bid(BidEvent {
+            instruction,
+            price,
+            buyer_expiry,
+        }),
+        _ => M2Event::Other {
+            instruction,
+            fields,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const M2: &str = "M2mx93ekt1fmXSVkTrUL9xVFHkmME8HTUi5Cyc5aF7K";
+
+    fn logs(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn decode_logs_returns_sale_of_execute_sale_v2() {
+        let events = decode_logs(
+            M2,
+            &logs(&[
+                "Program M2mx93ekt1fmXSVkTrUL9xVFHkmME8HTUi5Cyc5aF7K invoke [1]",
+                "Program log: Instruction: ExecuteSaleV2",
+                "Program 11111111111111111111111111111111 invoke [2]",
+                "Program log: {\"price\":1}",
+                "Program 11111111111111111111111111111111 success",
+                "Program log: {\"price\":100,\"seller_expiry\":-1,\"buyer_expiry\":5,\"royalty\":2}",
+                "Program M2mx93ekt1fmXSVkTrUL9xVFHkmME8HTUi5Cyc5aF7K success",
+            ]),
+        );
+        assert_eq!(
+            events,
+            vec![M2Event::Sale(Sale {
+                instruction: "ExecuteSaleV2".to_string(),
+                price: 100,
+                seller_expiry: -1,
+                buyer_expiry: 5,
+                royalty: Some(2),
+                token_size: 1,
+            })]
+        );
+    }
+
+    #[test]
+    fn decode_logs_returns_royalty_payments() {
+        let events = decode_logs(
+            M2,
+            &logs(&[
+                "Program M2mx93ekt1fmXSVkTrUL9xVFHkmME8HTUi5Cyc5aF7K invoke [1]",
+                "Program log: Instruction: Mip1ExecuteSaleV2",
+                "Program log: {\"royalty_paid\":30,\"creator\":\"Creator1111111111111111111111111111111111111\",\"payment_mint\":\"11111111111111111111111111111111\"}",
+                "Program M2mx93ekt1fmXSVkTrUL9xVFHkmME8HTUi5Cyc5aF7K success",
+            ]),
+        );
+        assert_eq!(
+            events,
+            vec![M2Event::RoyaltyPayment(RoyaltyPayment {
+                instruction: "Mip1ExecuteSaleV2".to_string(),
+                creator: "Creator1111111111111111111111111111111111111".to_string(),
+                payment_mint: "11111111111111111111111111111111".to_string(),
+                amount: 30,
+            })]
+        );
+    }
+
+    #[test]
+    fn decode_logs_skips_other_programs() {
+        let events = decode_logs(
+            M2,
+            &logs(&[
+                "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [1]",
+                "Program log: {\"price\":100,\"seller_expiry\":0}",
+                "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+            ]),
+        );
+        assert!(events.is_empty());
+    }
+}
@@ -0,0 +1,92 @@
+#![no_main]
+
+use anchor_lang::{prelude::*, Discriminator};
+use libfuzzer_sys::fuzz_target;
+use m2::{
+    states::*,
+    utils::{create_or_realloc_buyer_trade_state, create_or_realloc_seller_trade_state},
+};
+use m2_fuzz::{set_fuzz_syscall_stubs, AccountsInput, FuzzAccount};
+
+// data[0] picks the trade state side and what the account starts as, data[1..9] are its lamports
+// and the rest its data
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 9 {
+        return;
+    }
+    set_fuzz_syscall_stubs();
+    let is_seller = data[0] & 1 == 1;
+    let mut lamports = [0; 8];
+    lamports.copy_from_slice(&data[1..9]);
+    let mut account_data = data[9..].to_vec();
+    match data[0] >> 1 & 0b11 {
+        // keep the fuzzer's bytes, lengths and discriminator as they are
+        0 => {}
+        // an old trade state that gets migrated
+        1 => account_data.resize(
+            if is_seller {
+                SellerTradeState::LEN
+            } else {
+                BuyerTradeState::LEN
+            },
+            0,
+        ),
+        // the current version, possibly truncated or padded
+        2 => {
+            let discriminator = if is_seller {
+                SellerTradeStateV3::discriminator()
+            } else {
+                BuyerTradeStateV3::discriminator()
+            };
+            account_data.splice(0..account_data.len().min(8), discriminator.iter().copied());
+        }
+        // a brand new account
+        _ => account_data.clear(),
+    }
+
+    let mut input = AccountsInput::new(&[
+        FuzzAccount {
+            key: Pubkey::new_unique(),
+            owner: m2::ID,
+            lamports: u64::from_le_bytes(lamports),
+            data: account_data,
+            is_signer: false,
+            is_writable: true,
+        },
+        FuzzAccount {
+            key: Pubkey::new_unique(),
+            owner: solana_program::system_program::ID,
+            lamports: u64::MAX / 2,
+            data: vec![],
+            is_signer: true,
+            is_writable: true,
+        },
+    ]);
+    let account_infos = input.account_infos();
+    let (trade_state, payer) = (&account_infos[0], &account_infos[1]);
+
+    let seeds: &[&[u8]] = &[b"fuzz"];
+    let (result, len, discriminator) = if is_seller {
+        (
+            create_or_realloc_seller_trade_state(trade_state, payer, None, seeds),
+            SellerTradeStateV3::LEN,
+            SellerTradeStateV3::discriminator(),
+        )
+    } else {
+        (
+            create_or_realloc_buyer_trade_state(trade_state, payer, None, seeds),
+            BuyerTradeStateV3::LEN,
+            BuyerTradeStateV3::discriminator(),
+        )
+    };
+    // garbage is rejected, anything accepted is a current version trade state
+    if result.is_ok() {
+        // seller trade states may keep the alt prices tail of a listing that had them
+        assert!(
+            trade_state.data_len() == len
+                || is_seller
+                    && trade_state.data_len() == len + SellerTradeStateV3::ALT_PRICES_LEN
+        );
+        assert_eq!(trade_state.try_borrow_data().unwrap()[..8], discriminator);
+    }
+});
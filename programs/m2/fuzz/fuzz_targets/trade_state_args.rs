@@ -0,0 +1,42 @@
+#![no_main]
+
+use anchor_lang::{prelude::*, Discriminator};
+use libfuzzer_sys::fuzz_target;
+use m2::states::*;
+use m2_fuzz::{AccountsInput, FuzzAccount};
+
+fuzz_target!(|data: &[u8]| {
+    // the raw bytes, then behind each trade state discriminator so the decoders get past the
+    // version check and into the body
+    let discriminators = [
+        BuyerTradeState::discriminator(),
+        BuyerTradeStateV2::discriminator(),
+        BuyerTradeStateV3::discriminator(),
+        SellerTradeState::discriminator(),
+        SellerTradeStateV2::discriminator(),
+        SellerTradeStateV3::discriminator(),
+    ];
+    let mut inputs = vec![data.to_vec()];
+    inputs.extend(
+        discriminators
+            .iter()
+            .map(|discriminator| [&discriminator[..], data].concat()),
+    );
+
+    for account_data in inputs {
+        let _ = BidArgs::from_data(&account_data);
+        let _ = SellArgs::from_data(&account_data);
+
+        let mut input = AccountsInput::new(&[FuzzAccount {
+            key: Pubkey::new_unique(),
+            owner: m2::ID,
+            lamports: 0,
+            data: account_data,
+            is_signer: false,
+            is_writable: false,
+        }]);
+        let account_infos = input.account_infos();
+        let _ = BidArgs::from_account_info(&account_infos[0]);
+        let _ = SellArgs::from_account_info(&account_infos[0]);
+    }
+});
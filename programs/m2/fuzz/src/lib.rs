@@ -0,0 +1,140 @@
+//! Helpers shared by the fuzz targets: accounts laid out the way the runtime passes them to a
+//! program, and syscall stubs for the sysvars and system program calls the helpers under test make.
+
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::{
+        deserialize, ProgramResult, BPF_ALIGN_OF_U128, MAX_PERMITTED_DATA_INCREASE, SUCCESS,
+    },
+    instruction::Instruction,
+    program_error::ProgramError,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::SystemInstruction,
+};
+use std::sync::Once;
+
+const NON_DUP_MARKER: u8 = u8::MAX;
+
+pub struct FuzzAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// Serialized program input, AccountInfo::realloc needs the length prefix in front of the data and
+/// the spare room after it that only the runtime's layout has
+pub struct AccountsInput {
+    // u64s so the buffer is aligned like the runtime's
+    buffer: Vec<u64>,
+}
+
+impl AccountsInput {
+    pub fn new(accounts: &[FuzzAccount]) -> Self {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&(accounts.len() as u64).to_le_bytes());
+        for account in accounts {
+            bytes.push(NON_DUP_MARKER);
+            bytes.push(account.is_signer as u8);
+            bytes.push(account.is_writable as u8);
+            bytes.push(0); // executable
+            bytes.extend_from_slice(&[0; 4]); // original data len, filled in by deserialize
+            bytes.extend_from_slice(account.key.as_ref());
+            bytes.extend_from_slice(account.owner.as_ref());
+            bytes.extend_from_slice(&account.lamports.to_le_bytes());
+            bytes.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&account.data);
+            bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+            let padding = (BPF_ALIGN_OF_U128 - bytes.len() % BPF_ALIGN_OF_U128) % BPF_ALIGN_OF_U128;
+            bytes.resize(bytes.len() + padding, 0);
+            bytes.extend_from_slice(&0u64.to_le_bytes()); // rent epoch
+        }
+        bytes.extend_from_slice(&0u64.to_le_bytes()); // instruction data
+        bytes.extend_from_slice(m2::ID.as_ref());
+
+        let mut buffer = vec![0u64; (bytes.len() + 7) / 8];
+        for (word, chunk) in buffer.iter_mut().zip(bytes.chunks(8)) {
+            let mut le = [0; 8];
+            le[..chunk.len()].copy_from_slice(chunk);
+            *word = u64::from_le_bytes(le);
+        }
+        AccountsInput { buffer }
+    }
+
+    pub fn account_infos(&mut self) -> Vec<AccountInfo<'_>> {
+        // the buffer was serialized above in the layout deserialize reads
+        unsafe { deserialize(self.buffer.as_mut_ptr() as *mut u8).1 }
+    }
+}
+
+struct FuzzSyscallStubs;
+
+impl SyscallStubs for FuzzSyscallStubs {
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+
+    // the trade state helpers only call the system program
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let find = |i: usize| {
+            let key = instruction
+                .accounts
+                .get(i)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?
+                .pubkey;
+            account_infos
+                .iter()
+                .find(|ai| *ai.key == key)
+                .ok_or(ProgramError::NotEnoughAccountKeys)
+        };
+        match bincode::deserialize(&instruction.data)
+            .map_err(|_| ProgramError::InvalidInstructionData)?
+        {
+            SystemInstruction::CreateAccount {
+                lamports, space, ..
+            } => {
+                let to = find(1)?;
+                if !to.data_is_empty() {
+                    return Err(ProgramError::AccountAlreadyInitialized);
+                }
+                move_lamports(find(0)?, to, lamports)?;
+                to.realloc(space as usize, true)
+            }
+            SystemInstruction::Transfer { lamports } => move_lamports(find(0)?, find(1)?, lamports),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    let from_lamports = from
+        .lamports()
+        .checked_sub(lamports)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    let to_lamports = to
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::InvalidArgument)?;
+    **from.try_borrow_mut_lamports()? = from_lamports;
+    **to.try_borrow_mut_lamports()? = to_lamports;
+    Ok(())
+}
+
+pub fn set_fuzz_syscall_stubs() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        set_syscall_stubs(Box::new(FuzzSyscallStubs));
+    });
+}
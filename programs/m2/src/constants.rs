@@ -3,34 +3,81 @@ use solana_program::{pubkey, pubkey::Pubkey};
 pub const PREFIX: &str = "m2";
 pub const TREASURY: &str = "treasury";
 pub const SIGNER: &str = "signer";
+pub const ESCROW_CONFIG: &str = "escrow_config";
+pub const RENT_VAULT: &str = "rent_vault";
+pub const OPEN_BID_INDEX: &str = "open_bid_index";
+pub const LISTING_INDEX: &str = "listing_index";
+pub const AUTHORIZATION_NONCE: &str = "authorization_nonce";
+pub const EDITION_SALE: &str = "edition_sale";
+pub const DEPOSIT_RECEIPT: &str = "deposit_receipt";
+pub const HOUSE_METRICS: &str = "house_metrics";
+pub const REFERRER: &str = "referrer";
+pub const PROGRAM_CONFIG: &str = "program_config";
+pub const CANCEL_AUTHORITY_REGISTRY: &str = "cancel_authority_registry";
+pub const MAKER_REWARDS: &str = "maker_rewards";
+pub const MAKER_REWARDS_VAULT: &str = "maker_rewards_vault";
+pub const ESCROW_LEDGER: &str = "escrow_ledger";
+pub const SPENDING_ALLOWANCE: &str = "spending_allowance";
+pub const PENDING_HOUSE_UPDATE: &str = "pending_house_update";
+pub const ATTESTATION: &str = "attestation";
+pub const CPI_ALLOWLIST: &str = "cpi_allowlist";
+pub const LISTING_SNAPSHOT: &str = "listing_snapshot";
+pub const WALLET_LIMITS: &str = "wallet_limits";
+pub const ESCROW_LOCK: &str = "escrow_lock";
+pub const RATE_LIMIT: &str = "rate_limit";
+pub const LISTING_INSURANCE: &str = "listing_insurance";
+pub const PROCEEDS_SPLIT: &str = "proceeds_split";
+pub const CO_OWNER_APPROVAL: &str = "co_owner_approval";
+pub const GUARDIAN: &str = "guardian";
+pub const ESCROW_TOP_UP: &str = "escrow_top_up";
+pub const NOTARY_SET: &str = "notary_set";
+pub const PENDING_ROYALTIES: &str = "pending_royalties";
+pub const BID_GROUP: &str = "bid_group";
+pub const MINT_MARKET: &str = "mint_market";
+pub const ROYALTY_OVERRIDE: &str = "royalty_override";
+pub const LOAN_VAULT: &str = "loan_vault"; // seed of the loan program's vault PDA
 pub const MAX_PRICE: u64 = 8000000 * 1000000000;
 pub const CANCEL_AUTHORITY: Pubkey = pubkey!("CNTuB1JiQD8Xh5SoRcEmF61yivN9F7uzdSaGnRex36wi");
 pub const DEFAULT_MAKER_FEE_BP: i16 = 0;
 pub const DEFAULT_TAKER_FEE_BP: u16 = 250;
 pub const MAX_MAKER_FEE_BP: i16 = 500;
 pub const MAX_TAKER_FEE_BP: u16 = 500;
+pub const MAX_INDEXED_BIDS: usize = 256;
+pub const MAX_SWEEPABLE_ESCROW_LAMPORTS: u64 = 10_000_000; // 0.01 SOL
+pub const SWEEP_CRANKER_INCENTIVE_BP: u16 = 500;
+pub const MATCH_BOUNTY_BP: u16 = 1000; // cranker's share of the taker fee of a sale it matched
+pub const MAX_SWAP_SLIPPAGE_BP: u16 = 1000; // most a bid can let a swap fill spend over its price
+pub const MAX_CANCEL_BUY_BATCH: usize = 16;
+pub const MAX_UPGRADE_STS_BATCH: usize = 16;
+pub const MAX_SWEEP_BUY: usize = 8; // listings a sweep_buy fills at most, bounded by compute
+pub const MAX_CONFIG_PAYMENT_MINTS: usize = 8;
+pub const MAX_CANCEL_AUTHORITIES: usize = 8;
+pub const MAX_CPI_CALLERS: usize = 8;
+pub const MAX_ALT_PRICES: usize = 3; // payment mints a listing accepts besides its own
+pub const MAX_ESCROW_LEDGER_MINTS: usize = 8; // SOL and up to 7 SPL payment mints
+pub const MAX_PROCEEDS_SPLIT_RECIPIENTS: usize = 5;
+pub const MAX_CO_OWNERS: usize = 8; // approvals are a bitmask over them
+pub const MAX_NOTARIES: usize = 5;
+pub const MAX_PENDING_ROYALTIES: usize = 16; // creators owed royalty on a mint at once
+pub const MAX_ROYALTY_RECIPIENTS: usize = 16; // at most MAX_PENDING_ROYALTIES, so one sale can owe all of them
+pub const MAX_BID_GROUP_BIDS: usize = 8;
+pub const MAX_SPENDING_ALLOWANCE_LOCKS: usize = 8; // open bids a spending allowance funds at once
+pub const METADATA_PIN_LEN: usize = 24; // sha256 prefix a bid pins the metadata with
+pub const HOUSE_METRICS_DAYS: usize = 30;
 pub const DEFAULT_BID_EXPIRY_SECONDS_AFTER_NOW: i64 = 60 * 60 * 24 * 7; // 7 days
+pub const HOUSE_UPDATE_TIMELOCK_SECONDS: i64 = 60 * 60 * 24 * 2; // 2 days
+pub const MAX_ESCROW_LOCK_SLOTS: u64 = 1500; // ~10 minutes
+pub const LISTING_INSURANCE_WITHDRAW_DELAY_SECONDS: i64 = 60 * 60 * 24 * 3; // 3 days
+pub const REMAINING_ACCOUNTS_SCHEMA_OFFSET: usize = 256; // extra args bytes reserved before the schema
 
-pub const VALID_PAYMENT_MINTS: [Pubkey; 8] = if cfg!(feature = "anchor-test") {
-    [
-        pubkey!("BJqwwqWHcA5pXAnsAnG6mMiRqKzNcg36LG4bvcqbi3PP"),
-        pubkey!("CmmV5QXtqiaGQoca58HsKM2tP9qGQcAAh7gHwzZ68MBS"),
-        pubkey!("11111111111111111111111111111111"),
-        pubkey!("11111111111111111111111111111111"),
-        pubkey!("11111111111111111111111111111111"),
-        pubkey!("11111111111111111111111111111111"),
-        pubkey!("11111111111111111111111111111111"),
-        pubkey!("11111111111111111111111111111111"),
-    ]
-} else {
-    [
-        pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"), // USDC
-        pubkey!("mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So"),  // marinade staked SOL
-        pubkey!("J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yYac6Y7kGCPn"), // Jito staked SOL
-        pubkey!("3dgCCb15HMQSA4Pn3Tfii5vRk7aRqTH95LJjxzsG2Mug"), // HXD
-        pubkey!("2taiJMsZVH9UWG31DASLE3qZdpiq7BsmAVRoC8kNKgyR"), // xHXD (devnet HXD)
-        pubkey!("4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU"), // devnet USDC
-        pubkey!("DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263"), // Bonk
-        pubkey!("11111111111111111111111111111111"),
-    ]
-};
+// built into every deployment, a cluster adds its own through ProgramConfig
+pub const VALID_PAYMENT_MINTS: [Pubkey; 8] = [
+    pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"), // USDC
+    pubkey!("mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So"),  // marinade staked SOL
+    pubkey!("J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yYac6Y7kGCPn"), // Jito staked SOL
+    pubkey!("3dgCCb15HMQSA4Pn3Tfii5vRk7aRqTH95LJjxzsG2Mug"), // HXD
+    pubkey!("2taiJMsZVH9UWG31DASLE3qZdpiq7BsmAVRoC8kNKgyR"), // xHXD (devnet HXD)
+    pubkey!("4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU"), // devnet USDC
+    pubkey!("DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263"), // Bonk
+    pubkey!("11111111111111111111111111111111"),
+];
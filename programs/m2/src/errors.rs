@@ -95,7 +95,7 @@ pub enum ErrorCode {
     // 330
     #[msg("Invalid create auction house nonce")]
     InvalidCreateAuctionHouseNonce,
-    // 331
+    // 331, only kept so the codes after it don't shift, failures return a specific variant
     #[msg("Invalid account state")]
     InvalidAccountState,
     // 332
@@ -113,6 +113,249 @@ pub enum ErrorCode {
     // 336
     #[msg("Deprecated")]
     Deprecated,
+    // 337
     #[msg("Missing remaining account")]
     MissingRemainingAccount,
+    // 338
+    #[msg("Another instruction in this transaction modifies the same trade state")]
+    ConflictingInstructionInTransaction,
+    // 339
+    #[msg("No free escrow balance to withdraw")]
+    NoFreeEscrowBalance,
+    // 340
+    #[msg("Open bid index is full")]
+    OpenBidIndexFull,
+    // 341
+    #[msg("Missing or invalid off-chain signed authorization")]
+    InvalidSignedAuthorization,
+    // 342
+    #[msg("Authorization nonce was already used")]
+    StaleAuthorizationNonce,
+    // 343
+    #[msg("All prints of this edition sale have been sold")]
+    EditionSaleSoldOut,
+    // 344
+    #[msg("Too many accounts in batch")]
+    BatchTooLarge,
+    // 345
+    #[msg("Escrow adapter is not allowed by the auction house")]
+    EscrowAdapterNotAllowed,
+    // 346
+    #[msg("Escrow adapter paid less than the bid price")]
+    EscrowAdapterUnderpaid,
+    // 347
+    #[msg("Token account delegate is not the program signer")]
+    DelegateMismatch,
+    // 348
+    #[msg("Trade state belongs to another auction house")]
+    WrongAuctionHouseForState,
+    // 349
+    #[msg("Token is not escrowed by the program signer")]
+    TokenNotEscrowed,
+    // 350
+    #[msg("Token is already escrowed by another listing")]
+    TokenAlreadyEscrowed,
+    // 351
+    #[msg("Migration trade state doesn't match the token")]
+    StaleMigrationState,
+    // 352
+    #[msg("Escrow balance is lower than the price")]
+    EscrowBalanceTooLow,
+    // 353
+    #[msg("Trade state doesn't match the given accounts or args")]
+    TradeStateMismatch,
+    // 354
+    #[msg("Trade state already exists")]
+    TradeStateAlreadyExists,
+    // 355
+    #[msg("Escrow adapter doesn't match the bid")]
+    EscrowAdapterMismatch,
+    // 356
+    #[msg("Unexpected remaining accounts")]
+    UnexpectedRemainingAccounts,
+    // 357
+    #[msg("Wallet still has open bids")]
+    OpenBidsRemaining,
+    // 358
+    #[msg("Payment mint doesn't match the expected payment mint")]
+    PaymentMintMismatch,
+    // 359
+    #[msg("Bid is below the minimum share of the listing price")]
+    BidTooLowForListing,
+    // 360
+    #[msg("Listing can't be auto relisted")]
+    AutoRelistNotAllowed,
+    // 361
+    #[msg("Trade state was left without a discriminator, repair it with repair_trade_state")]
+    StrandedTradeState,
+    // 362
+    #[msg("Signer is not the program's upgrade authority")]
+    NotUpgradeAuthority,
+    // 363
+    #[msg("Cancel authority registry is full")]
+    CancelAuthorityRegistryFull,
+    // 364
+    #[msg("Listing requires bids to pay the full creator royalty")]
+    ListingRequiresFullRoyalty,
+    // 365
+    #[msg("Auction house enforces the full creator royalty")]
+    AuctionHouseEnforcesRoyalty,
+    // 366
+    #[msg("No maker rewards to claim, or the rewards vault is empty")]
+    NoMakerRewardsToClaim,
+    // 367
+    #[msg("Escrow ledger has no free slot for another payment mint")]
+    EscrowLedgerFull,
+    // 368
+    #[msg("Withdrawal would leave the escrow below what its open bids lock")]
+    EscrowBalanceLocked,
+    // 369
+    #[msg("Metadata no longer matches the hash the bid pinned")]
+    MetadataPinMismatch,
+    // 370
+    #[msg("Spending allowance limit exceeded")]
+    SpendingAllowanceExceeded,
+    // 371
+    #[msg("Scheduled auction house update is not effective yet")]
+    HouseUpdateNotEffective,
+    // 372
+    #[msg("Listing requires a valid attestation for the buyer")]
+    BuyerNotAttested,
+    // 373
+    #[msg("Missing the account of a creator owed royalty")]
+    MissingCreatorAccount,
+    // 374
+    #[msg("Program is not allowed to CPI into sales of this auction house")]
+    CpiCallerNotAllowed,
+    // 375
+    #[msg("CPI allowlist is full")]
+    CpiAllowlistFull,
+    // 376
+    #[msg("Invalid bid escalation")]
+    InvalidBidEscalation,
+    // 377
+    #[msg("Loan repayment only works for SOL sales paid to the seller's wallet")]
+    LoanRepaymentNotSupported,
+    // 378
+    #[msg("Loan program took from the seller")]
+    LoanProgramTookFromSeller,
+    // 379
+    #[msg("Wallet limit exceeded")]
+    WalletLimitExceeded,
+    // 380
+    #[msg("Escrow lock deadline must be in the future and within MAX_ESCROW_LOCK_SLOTS")]
+    InvalidEscrowLockDeadline,
+    // 381
+    #[msg("Escrow lock is active until its deadline, only the notary can release it early")]
+    EscrowLockActive,
+    // 382
+    #[msg("Token account is frozen, unstake it first or pass the auction house's staking adapter")]
+    TokenAccountFrozen,
+    // 383
+    #[msg("Both the listing and the bid must opt into auto matching")]
+    AutoMatchNotEnabled,
+    // 384
+    #[msg("Wallet exceeded the auction house's instructions per slot")]
+    RateLimitExceeded,
+    // 385
+    #[msg("Listing was left half migrated, recover it with recover_seller_trade_state")]
+    HalfMigratedTradeState,
+    // 386
+    #[msg("Listing insurance is still open to claims, withdraw again once the delay passed")]
+    ListingInsuranceLocked,
+    // 387
+    #[msg("Token is still escrowed or delegated by the seller, the listing isn't broken")]
+    ListingNotBroken,
+    // 388
+    #[msg("Remaining accounts schema is malformed or doesn't match the remaining accounts")]
+    InvalidRemainingAccountsSchema,
+    // 389
+    #[msg("Strict invariant violated")]
+    InvariantViolated,
+    // 390
+    #[msg("Proceeds split has too many recipients, an empty one or more than 10000 bp in total")]
+    InvalidProceedsSplit,
+    // 391
+    #[msg("Proceeds split only applies to SOL proceeds paid to the seller's wallet")]
+    ProceedsSplitNotSupported,
+    // 392
+    #[msg("Co-owners must be 1 to 8 distinct keys with a threshold between 1 and their count")]
+    InvalidCoOwners,
+    // 393
+    #[msg("Co-owners haven't approved this listing action, or the signer isn't one of them")]
+    CoOwnerApprovalMissing,
+    // 394
+    #[msg("Guardian didn't sign or isn't the wallet's guardian")]
+    InvalidGuardian,
+    // 395
+    #[msg("Swap program isn't the program config's swap adapter")]
+    SwapAdapterNotAllowed,
+    // 396
+    #[msg("Swap spent more than the bid's max slippage allows or paid less than the sale needs")]
+    SwapSlippageExceeded,
+    // 397
+    #[msg("Escrow top-up's funding token account doesn't delegate enough to cover the shortfall")]
+    EscrowTopUpExceeded,
+    // 398
+    #[msg("Notary set must be 1 to 5 distinct keys with a threshold between 1 and their count")]
+    InvalidNotarySet,
+    // 399
+    #[msg("Trade state was closed but holds lamports again, drain it with defund_zombie_state")]
+    ZombieTradeState,
+    // 400
+    #[msg("Pending royalties have no free slot for another creator, distribute them first")]
+    PendingRoyaltiesFull,
+    // 401
+    #[msg("Bid group must have 1 to 8 distinct ungrouped bids of the buyer with max_fills between 1 and their count")]
+    InvalidBidGroup,
+    // 402
+    #[msg("Bid group already had its max fills, its other bids can't fill")]
+    BidGroupConsumed,
+    // 403
+    #[msg("Listing is hidden until its reveal time")]
+    ListingHidden,
+    // 404
+    #[msg("Spending allowance has too many open bids")]
+    SpendingAllowanceFull,
+    // 405
+    #[msg("Fee changes must be scheduled with schedule_house_update")]
+    FeeChangeNotScheduled,
+    // 406
+    #[msg("Loan accounts must be passed for listings that repay a loan, with the seller's loan vault, and only for them")]
+    LoanRepaymentMismatch,
+    // 407
+    #[msg(
+        "Auction house enforces wallet limits, the wallet's WalletLimits address must be passed"
+    )]
+    WalletLimitsRequired,
+    // 408
+    #[msg("Royalty override must have 1 to 16 distinct recipients with shares adding up to 100")]
+    InvalidRoyaltyOverride,
+    // 409
+    #[msg("Escrow holds no dust to sweep, it's empty or above the sweepable amount")]
+    EscrowNotSweepable,
+    // 410
+    #[msg("Open bid index was created after the wallet's escrow backed bids, it can't vouch for all of them")]
+    OpenBidIndexIncomplete,
+    // 411
+    #[msg("Bid only fills while its collection's floor is at or below its max floor price")]
+    FloorConditionNotMet,
+    // 412
+    #[msg("Master edition account can't be read as a master edition")]
+    InvalidMasterEdition,
+    // 413
+    #[msg("Treasury must keep its minimum leftover balance")]
+    TreasuryBalanceTooLow,
+    // 414
+    #[msg("Trade state still holds a valid order, it isn't a zombie")]
+    NotZombieTradeState,
+    // 415
+    #[msg("Current instruction isn't the one its trailing args were read for")]
+    TrailingArgsMismatch,
+    // 416
+    #[msg("Spending allowance is for another auction house or spender")]
+    SpendingAllowanceMismatch,
+    // 417
+    #[msg("Seller trade state has a length this program never wrote")]
+    InvalidTradeStateLength,
 }
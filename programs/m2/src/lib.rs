@@ -6,12 +6,14 @@ mod m2_ins;
 pub mod mip1_ins;
 mod ocp_ins;
 pub mod states;
-mod utils;
+pub mod utils;
 
 use crate::m2_ins::*;
 use crate::mip1_ins::*;
 use crate::ocp_ins::*;
+use crate::states::{CpiPolicy, PaymentMintFees, ProceedsSplitRecipient, RoyaltyRecipient};
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
 
 anchor_lang::declare_id!("M2mx93ekt1fmXSVkTrUL9xVFHkmME8HTUi5Cyc5aF7K");
 
@@ -26,6 +28,7 @@ pub mod m2 {
         m2_ins::withdraw_from_treasury::handle(ctx, amount)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn update_auction_house<'info>(
         ctx: Context<'_, '_, '_, 'info, UpdateAuctionHouse<'info>>,
         seller_fee_basis_points: Option<u16>,
@@ -33,6 +36,21 @@ pub mod m2 {
         seller_referral_bp: Option<u16>,
         requires_notary: Option<bool>,
         nprob: Option<u8>,
+        sandwich_guard: Option<bool>,
+        protocol_fee_recipient: Option<Pubkey>,
+        protocol_fee_share_bp: Option<u16>,
+        escrow_adapters: Option<[Pubkey; 2]>,
+        min_bid_bp_of_list_price: Option<u16>,
+        delist_grace_seconds: Option<u32>,
+        enforce_royalties: Option<bool>,
+        maker_rewards_lamports_per_day: Option<u64>,
+        maker_rewards_volume_bp: Option<u16>,
+        attestation_authority: Option<Pubkey>,
+        loan_program: Option<Pubkey>,
+        staking_adapter: Option<Pubkey>,
+        max_ixs_per_slot: Option<u8>,
+        allow_sfts: Option<bool>,
+        enforce_wallet_limits: Option<bool>,
     ) -> Result<()> {
         m2_ins::update_auction_house::handle(
             ctx,
@@ -41,6 +59,21 @@ pub mod m2 {
             seller_referral_bp,
             requires_notary,
             nprob,
+            sandwich_guard,
+            protocol_fee_recipient,
+            protocol_fee_share_bp,
+            escrow_adapters,
+            min_bid_bp_of_list_price,
+            delist_grace_seconds,
+            enforce_royalties,
+            maker_rewards_lamports_per_day,
+            maker_rewards_volume_bp,
+            attestation_authority,
+            loan_program,
+            staking_adapter,
+            max_ixs_per_slot,
+            allow_sfts,
+            enforce_wallet_limits,
         )
     }
 
@@ -49,7 +82,17 @@ pub mod m2 {
         escrow_payment_bump: u8,
         amount: u64,
     ) -> Result<()> {
-        m2_ins::withdraw::handle(ctx, escrow_payment_bump, amount)
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::Withdraw::DISCRIMINATOR,
+            WITHDRAW_ARGS_LEN,
+            &WITHDRAW_ORDER,
+        )?;
+        m2_ins::withdraw::handle(
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
+            escrow_payment_bump,
+            amount,
+        )
     }
 
     pub fn deposit<'info>(
@@ -57,23 +100,46 @@ pub mod m2 {
         _escrow_payment_bump: u8,
         amount: u64,
     ) -> Result<()> {
-        m2_ins::deposit::handle(ctx, amount)
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::Deposit::DISCRIMINATOR,
+            DEPOSIT_ARGS_LEN,
+            &DEPOSIT_ORDER,
+        )?;
+        m2_ins::deposit::handle(
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
+            amount,
+        )
     }
 
     pub fn sell<'info>(
-        ctx: Context<'_, '_, '_, 'info, Sell<'info>>,
+        mut ctx: Context<'_, '_, '_, 'info, Sell<'info>>,
         _seller_state_bump: u8,
         program_as_signer_bump: u8,
         buyer_price: u64,
         token_size: u64,
         seller_state_expiry: i64,
     ) -> Result<()> {
+        // SellExtraArgs are appended to the instruction data, read through the instructions
+        // sysvar passed as the last remaining account
+        let mut extra_args = vec![];
+        if let Some((last, rest)) = ctx.remaining_accounts.split_last() {
+            if last.key == &anchor_lang::solana_program::sysvar::instructions::id() {
+                extra_args = utils::get_trailing_instruction_data(
+                    last,
+                    &instruction::Sell::DISCRIMINATOR,
+                    SELL_ARGS_LEN,
+                )?;
+                ctx.remaining_accounts = rest;
+            }
+        }
         m2_ins::sell::handle(
             ctx,
             program_as_signer_bump,
             buyer_price,
             token_size,
             seller_state_expiry,
+            &extra_args,
         )
     }
 
@@ -83,7 +149,18 @@ pub mod m2 {
         token_size: u64,
         seller_state_expiry: i64,
     ) -> Result<()> {
-        m2_ins::cancel_sell::handle(ctx, buyer_price, token_size, seller_state_expiry)
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::CancelSell::DISCRIMINATOR,
+            CANCEL_SELL_ARGS_LEN,
+            &CANCEL_SELL_ORDER,
+        )?;
+        m2_ins::cancel_sell::handle(
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
+            buyer_price,
+            token_size,
+            seller_state_expiry,
+        )
     }
 
     pub fn buy<'info>(
@@ -127,27 +204,59 @@ pub mod m2 {
         token_size: u64,
         buyer_state_expiry: i64,
     ) -> Result<()> {
-        m2_ins::cancel_buy::handle(ctx, buyer_price, token_size, buyer_state_expiry)
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::CancelBuy::DISCRIMINATOR,
+            CANCEL_BUY_ARGS_LEN,
+            &CANCEL_BUY_ORDER,
+        )?;
+        m2_ins::cancel_buy::handle(
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
+            buyer_price,
+            token_size,
+            buyer_state_expiry,
+        )
     }
 
     pub fn ocp_sell<'info>(
         ctx: Context<'_, '_, '_, 'info, OCPSell<'info>>,
         args: OCPSellArgs,
     ) -> Result<()> {
-        ocp_ins::ocp_sell::handle(ctx, args)
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::OcpSell::DISCRIMINATOR,
+            OCP_SELL_ARGS_LEN,
+            &OCP_SELL_ORDER,
+        )?;
+        ocp_ins::ocp_sell::handle(utils::with_ordered_remaining_accounts(ctx, &ordered), args)
     }
 
     pub fn ocp_cancel_sell<'info>(
         ctx: Context<'_, '_, '_, 'info, OCPCancelSell<'info>>,
     ) -> Result<()> {
-        ocp_ins::ocp_cancel_sell::handle(ctx)
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::OcpCancelSell::DISCRIMINATOR,
+            OCP_CANCEL_SELL_ARGS_LEN,
+            &OCP_CANCEL_SELL_ORDER,
+        )?;
+        ocp_ins::ocp_cancel_sell::handle(utils::with_ordered_remaining_accounts(ctx, &ordered))
     }
 
     pub fn ocp_execute_sale_v2<'info>(
         ctx: Context<'_, '_, '_, 'info, OCPExecuteSaleV2<'info>>,
         args: OCPExecuteSaleV2Args,
     ) -> Result<()> {
-        ocp_ins::ocp_execute_sale_v2::handle(ctx, args)
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::OcpExecuteSaleV2::DISCRIMINATOR,
+            OCP_EXECUTE_SALE_V2_ARGS_LEN,
+            &OCP_EXECUTE_SALE_V2_ORDER,
+        )?;
+        ocp_ins::ocp_execute_sale_v2::handle(
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
+            args,
+        )
     }
 
     pub fn execute_sale_v2<'info>(
@@ -160,15 +269,23 @@ pub mod m2 {
         _seller_state_expiry: i64,
         maker_fee_bp: i16,
         taker_fee_bp: u16,
+        expected_payment_mint: Pubkey,
     ) -> Result<()> {
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::ExecuteSaleV2::DISCRIMINATOR,
+            EXECUTE_SALE_V2_ARGS_LEN,
+            &EXECUTE_SALE_V2_ORDER,
+        )?;
         m2_ins::execute_sale_v2::handle(
-            ctx,
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
             escrow_payment_bump,
             program_as_signer_bump,
             buyer_price,
             token_size,
             maker_fee_bp,
             taker_fee_bp,
+            expected_payment_mint,
         )
     }
 
@@ -176,19 +293,630 @@ pub mod m2 {
         ctx: Context<'_, '_, '_, 'info, MIP1Sell<'info>>,
         args: MIP1SellArgs,
     ) -> Result<()> {
-        mip1_ins::mip1_sell::handle_mip1_sell(ctx, &args)
+        // SellExtraArgs are appended to the instruction data after args
+        let extra_args = utils::get_trailing_instruction_data(
+            &ctx.accounts.instructions,
+            &instruction::Mip1Sell::DISCRIMINATOR,
+            MIP1_SELL_ARGS_LEN,
+        )?;
+        let ordered =
+            utils::order_remaining_accounts(ctx.remaining_accounts, &extra_args, &MIP1_SELL_ORDER)?;
+        mip1_ins::mip1_sell::handle_mip1_sell(
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
+            &args,
+            &extra_args,
+        )
     }
 
     pub fn mip1_execute_sale_v2<'info>(
         ctx: Context<'_, '_, '_, 'info, MIP1ExecuteSaleV2<'info>>,
         args: MIP1ExecuteSaleV2Args,
     ) -> Result<()> {
-        mip1_ins::mip1_execute_sale_v2::handle_mip1_execute_sale(ctx, args)
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::Mip1ExecuteSaleV2::DISCRIMINATOR,
+            MIP1_EXECUTE_SALE_V2_ARGS_LEN,
+            &MIP1_EXECUTE_SALE_V2_ORDER,
+        )?;
+        mip1_ins::mip1_execute_sale_v2::handle_mip1_execute_sale(
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
+            args,
+        )
     }
 
     pub fn mip1_cancel_sell<'info>(
         ctx: Context<'_, '_, '_, 'info, MIP1CancelSell<'info>>,
     ) -> Result<()> {
-        mip1_ins::mip1_cancel_sell::handle_mip1_cancel_sell(ctx)
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::Mip1CancelSell::DISCRIMINATOR,
+            MIP1_CANCEL_SELL_ARGS_LEN,
+            &MIP1_CANCEL_SELL_ORDER,
+        )?;
+        mip1_ins::mip1_cancel_sell::handle_mip1_cancel_sell(utils::with_ordered_remaining_accounts(
+            ctx, &ordered,
+        ))
+    }
+
+    pub fn set_escrow_config<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetEscrowConfig<'info>>,
+        keep_open: bool,
+        min_resident_balance: u64,
+    ) -> Result<()> {
+        m2_ins::set_escrow_config::handle(ctx, keep_open, min_resident_balance)
+    }
+
+    pub fn withdraw_from_rent_vault<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawFromRentVault<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        m2_ins::withdraw_from_rent_vault::handle(ctx, amount)
+    }
+
+    pub fn withdraw_all<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawAll<'info>>) -> Result<()> {
+        m2_ins::withdraw_all::handle(ctx)
+    }
+
+    pub fn sell_with_authorization<'info>(
+        ctx: Context<'_, '_, '_, 'info, SellWithAuthorization<'info>>,
+        price: u64,
+        expiry: i64,
+        nonce: u64,
+    ) -> Result<()> {
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::SellWithAuthorization::DISCRIMINATOR,
+            SELL_WITH_AUTHORIZATION_ARGS_LEN,
+            &SELL_WITH_AUTHORIZATION_ORDER,
+        )?;
+        m2_ins::sell_with_authorization::handle(
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
+            price,
+            expiry,
+            nonce,
+        )
+    }
+
+    pub fn buy_with_authorization<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyWithAuthorization<'info>>,
+        price: u64,
+        expiry: i64,
+        buyer_creator_royalty_bp: u16,
+        nonce: u64,
+    ) -> Result<()> {
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::BuyWithAuthorization::DISCRIMINATOR,
+            BUY_WITH_AUTHORIZATION_ARGS_LEN,
+            &BUY_WITH_AUTHORIZATION_ORDER,
+        )?;
+        m2_ins::buy_with_authorization::handle(
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
+            price,
+            expiry,
+            buyer_creator_royalty_bp,
+            nonce,
+        )
+    }
+
+    pub fn sell_edition<'info>(
+        ctx: Context<'_, '_, '_, 'info, SellEdition<'info>>,
+        price: u64,
+        max_prints: u64,
+    ) -> Result<()> {
+        m2_ins::sell_edition::handle(ctx, price, max_prints)
+    }
+
+    pub fn execute_edition_sale<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteEditionSale<'info>>,
+        price: u64,
+    ) -> Result<()> {
+        m2_ins::execute_edition_sale::handle(ctx, price)
+    }
+
+    pub fn cancel_edition_sale<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelEditionSale<'info>>,
+    ) -> Result<()> {
+        m2_ins::cancel_edition_sale::handle(ctx)
+    }
+
+    pub fn sweep_abandoned_escrow<'info>(
+        ctx: Context<'_, '_, '_, 'info, SweepAbandonedEscrow<'info>>,
+    ) -> Result<()> {
+        m2_ins::sweep_abandoned_escrow::handle(ctx)
+    }
+
+    pub fn mip1_update_listing<'info>(
+        ctx: Context<'_, '_, '_, 'info, MIP1UpdateListing<'info>>,
+        args: MIP1UpdateListingArgs,
+    ) -> Result<()> {
+        let ordered = utils::order_remaining_accounts(
+            ctx.remaining_accounts,
+            &args.extra_args,
+            &MIP1_UPDATE_LISTING_ORDER,
+        )?;
+        mip1_ins::mip1_update_listing::handle_mip1_update_listing(
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
+            &args,
+        )
+    }
+
+    pub fn cancel_buy_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelBuyBatch<'info>>,
+    ) -> Result<()> {
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::CancelBuyBatch::DISCRIMINATOR,
+            CANCEL_BUY_BATCH_ARGS_LEN,
+            &CANCEL_BUY_BATCH_ORDER,
+        )?;
+        m2_ins::cancel_buy_batch::handle(utils::with_ordered_remaining_accounts(ctx, &ordered))
+    }
+
+    pub fn mip1_bid_and_execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, MIP1ExecuteSaleV2<'info>>,
+        args: MIP1ExecuteSaleV2Args,
+    ) -> Result<()> {
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::Mip1BidAndExecute::DISCRIMINATOR,
+            MIP1_EXECUTE_SALE_V2_ARGS_LEN,
+            &MIP1_EXECUTE_SALE_V2_ORDER,
+        )?;
+        mip1_ins::mip1_execute_sale_v2::handle_mip1_bid_and_execute(
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
+            args,
+        )
+    }
+
+    pub fn sell_into_bid<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteSaleV2<'info>>,
+        escrow_payment_bump: u8,
+        program_as_signer_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+        _buyer_state_expiry: i64,
+        _seller_state_expiry: i64,
+        maker_fee_bp: i16,
+        taker_fee_bp: u16,
+        expected_payment_mint: Pubkey,
+    ) -> Result<()> {
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::SellIntoBid::DISCRIMINATOR,
+            EXECUTE_SALE_V2_ARGS_LEN,
+            &EXECUTE_SALE_V2_ORDER,
+        )?;
+        m2_ins::execute_sale_v2::handle_sell_into_bid(
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
+            escrow_payment_bump,
+            program_as_signer_bump,
+            buyer_price,
+            token_size,
+            maker_fee_bp,
+            taker_fee_bp,
+            expected_payment_mint,
+        )
+    }
+
+    pub fn deposit_with_reference<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositWithReference<'info>>,
+        amount: u64,
+        reference: [u8; 32],
+    ) -> Result<()> {
+        m2_ins::deposit_with_reference::handle(ctx, amount, reference)
+    }
+
+    pub fn init_house_metrics(ctx: Context<InitHouseMetrics>, payment_mint: Pubkey) -> Result<()> {
+        m2_ins::init_house_metrics::handle(ctx, payment_mint)
+    }
+
+    pub fn auto_relist<'info>(ctx: Context<'_, '_, '_, 'info, AutoRelist<'info>>) -> Result<()> {
+        m2_ins::auto_relist::handle(ctx)
+    }
+
+    pub fn sell_frozen<'info>(
+        ctx: Context<'_, '_, '_, 'info, SellFrozen<'info>>,
+        price: u64,
+        expiry: i64,
+        extra_args: Vec<u8>,
+    ) -> Result<()> {
+        let ordered = utils::order_remaining_accounts(
+            ctx.remaining_accounts,
+            &extra_args,
+            &SELL_FROZEN_ORDER,
+        )?;
+        m2_ins::sell_frozen::handle(
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
+            price,
+            expiry,
+            &extra_args,
+        )
+    }
+
+    pub fn quote_ocp_royalty(ctx: Context<QuoteOCPRoyalty>, price: u64) -> Result<u16> {
+        ocp_ins::quote_ocp_royalty::handle(ctx, price)
+    }
+
+    pub fn repair_trade_state(ctx: Context<RepairTradeState>) -> Result<()> {
+        m2_ins::repair_trade_state::handle(ctx)
+    }
+
+    pub fn register_referrer(
+        ctx: Context<RegisterReferrer>,
+        payout: Pubkey,
+        attribution_window_seconds: u32,
+    ) -> Result<()> {
+        m2_ins::register_referrer::handle(ctx, payout, attribution_window_seconds)
+    }
+
+    pub fn init_program_config(
+        ctx: Context<InitProgramConfig>,
+        config_authority: Pubkey,
+        payment_mints: [Pubkey; 8],
+        cancel_authority: Pubkey,
+    ) -> Result<()> {
+        m2_ins::init_program_config::handle(ctx, config_authority, payment_mints, cancel_authority)
+    }
+
+    pub fn update_program_config(
+        ctx: Context<UpdateProgramConfig>,
+        new_authority: Option<Pubkey>,
+        payment_mints: Option<[Pubkey; 8]>,
+        cancel_authority: Option<Pubkey>,
+        disabled_instructions: Option<u32>,
+        swap_adapter: Option<Pubkey>,
+        payment_mint_fees: Option<[PaymentMintFees; 8]>,
+    ) -> Result<()> {
+        m2_ins::update_program_config::handle(
+            ctx,
+            new_authority,
+            payment_mints,
+            cancel_authority,
+            disabled_instructions,
+            swap_adapter,
+            payment_mint_fees,
+        )
+    }
+
+    pub fn set_cancel_authority(
+        ctx: Context<SetCancelAuthority>,
+        key: Pubkey,
+        enabled: bool,
+    ) -> Result<()> {
+        m2_ins::set_cancel_authority::handle(ctx, key, enabled)
+    }
+
+    pub fn close_stale_sts<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseStaleSts<'info>>,
+    ) -> Result<()> {
+        m2_ins::close_stale_sts::handle(ctx)
+    }
+
+    pub fn init_maker_rewards(ctx: Context<InitMakerRewards>) -> Result<()> {
+        m2_ins::init_maker_rewards::handle(ctx)
+    }
+
+    pub fn claim_maker_rewards<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimMakerRewards<'info>>,
+    ) -> Result<()> {
+        m2_ins::claim_maker_rewards::handle(ctx)
+    }
+
+    pub fn init_escrow_ledger(ctx: Context<InitEscrowLedger>) -> Result<()> {
+        m2_ins::init_escrow_ledger::handle(ctx)
+    }
+
+    pub fn upgrade_legacy_sts<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpgradeLegacySts<'info>>,
+    ) -> Result<()> {
+        m2_ins::upgrade_legacy_sts::handle(ctx)
+    }
+
+    pub fn set_spending_allowance(ctx: Context<SetSpendingAllowance>, limit: u64) -> Result<()> {
+        m2_ins::set_spending_allowance::handle(ctx, limit)
+    }
+
+    pub fn schedule_house_update(
+        ctx: Context<ScheduleHouseUpdate>,
+        seller_fee_basis_points: Option<u16>,
+        buyer_referral_bp: Option<u16>,
+        seller_referral_bp: Option<u16>,
+        protocol_fee_share_bp: Option<u16>,
+    ) -> Result<()> {
+        m2_ins::schedule_house_update::handle(
+            ctx,
+            seller_fee_basis_points,
+            buyer_referral_bp,
+            seller_referral_bp,
+            protocol_fee_share_bp,
+        )
+    }
+
+    pub fn apply_house_update(ctx: Context<ApplyHouseUpdate>) -> Result<()> {
+        m2_ins::apply_house_update::handle(ctx)
+    }
+
+    pub fn issue_attestation(ctx: Context<IssueAttestation>, expiry: i64) -> Result<()> {
+        m2_ins::issue_attestation::handle(ctx, expiry)
+    }
+
+    pub fn revoke_attestation(ctx: Context<RevokeAttestation>) -> Result<()> {
+        m2_ins::revoke_attestation::handle(ctx)
+    }
+
+    pub fn set_cpi_policy(
+        ctx: Context<SetCpiPolicy>,
+        cpi_policy: CpiPolicy,
+        program: Option<Pubkey>,
+        allowed: bool,
+    ) -> Result<()> {
+        m2_ins::set_cpi_policy::handle(ctx, cpi_policy, program, allowed)
+    }
+
+    pub fn snapshot_listing(ctx: Context<SnapshotListing>, snapshot_id: u64) -> Result<()> {
+        m2_ins::snapshot_listing::handle(ctx, snapshot_id)
+    }
+
+    pub fn get_config(ctx: Context<GetConfig>) -> Result<ProgramConstants> {
+        m2_ins::get_config::handle(ctx)
+    }
+
+    pub fn set_wallet_limits(
+        ctx: Context<SetWalletLimits>,
+        max_daily_buy_volume: u64,
+        max_open_bids: u32,
+    ) -> Result<()> {
+        m2_ins::set_wallet_limits::handle(ctx, max_daily_buy_volume, max_open_bids)
+    }
+
+    pub fn cancel_listing_auto<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelListingAuto<'info>>,
+    ) -> Result<()> {
+        m2_ins::cancel_listing_auto::handle(ctx)
+    }
+
+    pub fn list_auto<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ListAuto<'info>>,
+        args: ListAutoArgs,
+    ) -> Result<()> {
+        m2_ins::list_auto::handle(ctx, args)
+    }
+
+    pub fn lock_escrow_for_quote<'info>(
+        ctx: Context<'_, '_, '_, 'info, LockEscrowForQuote<'info>>,
+        order_hash: [u8; 32],
+        amount: u64,
+        deadline_slot: u64,
+    ) -> Result<()> {
+        m2_ins::lock_escrow_for_quote::handle(ctx, order_hash, amount, deadline_slot)
+    }
+
+    pub fn release_escrow_lock<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReleaseEscrowLock<'info>>,
+    ) -> Result<()> {
+        m2_ins::release_escrow_lock::handle(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn match_orders<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteSaleV2<'info>>,
+        escrow_payment_bump: u8,
+        program_as_signer_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+        _buyer_state_expiry: i64,
+        _seller_state_expiry: i64,
+        maker_fee_bp: i16,
+        taker_fee_bp: u16,
+        expected_payment_mint: Pubkey,
+    ) -> Result<()> {
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::MatchOrders::DISCRIMINATOR,
+            EXECUTE_SALE_V2_ARGS_LEN,
+            &EXECUTE_SALE_V2_ORDER,
+        )?;
+        m2_ins::execute_sale_v2::handle_match_orders(
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
+            escrow_payment_bump,
+            program_as_signer_bump,
+            buyer_price,
+            token_size,
+            maker_fee_bp,
+            taker_fee_bp,
+            expected_payment_mint,
+        )
+    }
+
+    pub fn init_rate_limit(ctx: Context<InitRateLimit>) -> Result<()> {
+        m2_ins::init_rate_limit::handle(ctx)
+    }
+
+    pub fn recover_seller_trade_state(ctx: Context<RecoverSellerTradeState>) -> Result<()> {
+        m2_ins::recover_seller_trade_state::handle(ctx)
+    }
+
+    pub fn quote_list(
+        ctx: Context<QuoteList>,
+        price: u64,
+        maker_fee_bp: i16,
+        taker_fee_bp: u16,
+        buyer_creator_royalty_bp: u16,
+    ) -> Result<ListQuote> {
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::QuoteList::DISCRIMINATOR,
+            QUOTE_LIST_ARGS_LEN,
+            &QUOTE_LIST_ORDER,
+        )?;
+        m2_ins::quote_list::handle(
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
+            price,
+            maker_fee_bp,
+            taker_fee_bp,
+            buyer_creator_royalty_bp,
+        )
+    }
+
+    pub fn deposit_listing_insurance(
+        ctx: Context<DepositListingInsurance>,
+        amount: u64,
+    ) -> Result<()> {
+        m2_ins::deposit_listing_insurance::handle(ctx, amount)
+    }
+
+    pub fn withdraw_listing_insurance(ctx: Context<WithdrawListingInsurance>) -> Result<()> {
+        m2_ins::withdraw_listing_insurance::handle(ctx)
+    }
+
+    pub fn claim_listing_insurance(ctx: Context<ClaimListingInsurance>) -> Result<()> {
+        m2_ins::claim_listing_insurance::handle(ctx)
+    }
+
+    pub fn set_proceeds_split(
+        ctx: Context<SetProceedsSplit>,
+        recipients: Vec<ProceedsSplitRecipient>,
+    ) -> Result<()> {
+        m2_ins::set_proceeds_split::handle(ctx, recipients)
+    }
+
+    pub fn close_proceeds_split(ctx: Context<CloseProceedsSplit>) -> Result<()> {
+        m2_ins::close_proceeds_split::handle(ctx)
+    }
+
+    pub fn set_co_owners(
+        ctx: Context<SetCoOwners>,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        m2_ins::set_co_owners::handle(ctx, owners, threshold)
+    }
+
+    pub fn approve_co_owner_action(
+        ctx: Context<ApproveCoOwnerAction>,
+        price: u64,
+        expiry: i64,
+        payment_mint: Pubkey,
+        extra_args: Vec<u8>,
+    ) -> Result<()> {
+        m2_ins::approve_co_owner_action::handle(ctx, price, expiry, payment_mint, &extra_args)
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>) -> Result<()> {
+        m2_ins::set_guardian::handle(ctx)
+    }
+
+    pub fn remove_guardian(ctx: Context<RemoveGuardian>) -> Result<()> {
+        m2_ins::remove_guardian::handle(ctx)
+    }
+
+    pub fn sweep_buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, SweepBuy<'info>>,
+        max_budget: u64,
+        maker_fee_bp: i16,
+        taker_fee_bp: u16,
+    ) -> Result<SweepBuyResult> {
+        m2_ins::sweep_buy::handle(ctx, max_budget, maker_fee_bp, taker_fee_bp)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn dry_run_execute_sale<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteSaleV2<'info>>,
+        _escrow_payment_bump: u8,
+        _program_as_signer_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+        _buyer_state_expiry: i64,
+        _seller_state_expiry: i64,
+        maker_fee_bp: i16,
+        taker_fee_bp: u16,
+        expected_payment_mint: Pubkey,
+    ) -> Result<DryRunResult> {
+        let ordered = utils::order_remaining_accounts_by_instruction_data(
+            ctx.remaining_accounts,
+            &instruction::DryRunExecuteSale::DISCRIMINATOR,
+            EXECUTE_SALE_V2_ARGS_LEN,
+            &EXECUTE_SALE_V2_ORDER,
+        )?;
+        m2_ins::execute_sale_v2::handle_dry_run(
+            utils::with_ordered_remaining_accounts(ctx, &ordered),
+            buyer_price,
+            token_size,
+            maker_fee_bp,
+            taker_fee_bp,
+            expected_payment_mint,
+        )
+    }
+
+    pub fn set_escrow_top_up(ctx: Context<SetEscrowTopUp>, cap: u64) -> Result<()> {
+        m2_ins::set_escrow_top_up::handle(ctx, cap)
+    }
+
+    pub fn remove_escrow_top_up(ctx: Context<RemoveEscrowTopUp>) -> Result<()> {
+        m2_ins::remove_escrow_top_up::handle(ctx)
+    }
+
+    pub fn set_notary_set(
+        ctx: Context<SetNotarySet>,
+        notaries: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        m2_ins::set_notary_set::handle(ctx, notaries, threshold)
+    }
+
+    pub fn close_notary_set(ctx: Context<CloseNotarySet>) -> Result<()> {
+        m2_ins::close_notary_set::handle(ctx)
+    }
+
+    pub fn defund_zombie_state(ctx: Context<DefundZombieState>) -> Result<()> {
+        m2_ins::defund_zombie_state::handle(ctx)
+    }
+
+    pub fn distribute_royalties<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeRoyalties<'info>>,
+    ) -> Result<()> {
+        m2_ins::distribute_royalties::handle(ctx)
+    }
+
+    pub fn create_bid_group<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateBidGroup<'info>>,
+        id: u64,
+        max_fills: u8,
+    ) -> Result<()> {
+        m2_ins::create_bid_group::handle(ctx, id, max_fills)
+    }
+
+    pub fn close_bid_group<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseBidGroup<'info>>,
+    ) -> Result<()> {
+        m2_ins::close_bid_group::handle(ctx)
+    }
+
+    pub fn init_mint_market(ctx: Context<InitMintMarket>) -> Result<()> {
+        m2_ins::init_mint_market::handle(ctx)
+    }
+
+    pub fn refresh_mint_market<'info>(
+        ctx: Context<'_, '_, '_, 'info, RefreshMintMarket<'info>>,
+    ) -> Result<()> {
+        m2_ins::refresh_mint_market::handle(ctx)
+    }
+
+    pub fn invalidate_broken_listing<'info>(
+        ctx: Context<'_, '_, '_, 'info, InvalidateBrokenListing<'info>>,
+    ) -> Result<()> {
+        m2_ins::invalidate_broken_listing::handle(ctx)
+    }
+
+    pub fn set_royalty_override(
+        ctx: Context<SetRoyaltyOverride>,
+        recipients: Vec<RoyaltyRecipient>,
+    ) -> Result<()> {
+        m2_ins::set_royalty_override::handle(ctx, recipients)
+    }
+
+    pub fn close_royalty_override(ctx: Context<CloseRoyaltyOverride>) -> Result<()> {
+        m2_ins::close_royalty_override::handle(ctx)
     }
 }
@@ -0,0 +1,54 @@
+use {crate::constants::*, crate::errors::ErrorCode, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct ApplyHouseUpdate<'info> {
+    /// CHECK: authority, receives the pending update's rent
+    #[account(mut, address = auction_house.authority)]
+    authority: UncheckedAccount<'info>,
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        close=authority,
+        seeds=[
+            PREFIX.as_bytes(),
+            PENDING_HOUSE_UPDATE.as_bytes(),
+            auction_house.key().as_ref(),
+        ],
+        bump=pending_house_update.bump,
+    )]
+    pending_house_update: Box<Account<'info, PendingHouseUpdate>>,
+}
+
+// Permissionless, applies the scheduled fee changes once their timelock has passed
+pub fn handle(ctx: Context<ApplyHouseUpdate>) -> Result<()> {
+    let pending_house_update = &ctx.accounts.pending_house_update;
+    let auction_house = &mut ctx.accounts.auction_house;
+
+    if Clock::get()?.unix_timestamp < pending_house_update.effective_at {
+        return Err(ErrorCode::HouseUpdateNotEffective.into());
+    }
+
+    if let Some(sfbp) = pending_house_update.seller_fee_basis_points {
+        auction_house.seller_fee_basis_points = sfbp;
+    }
+    if let Some(bbp) = pending_house_update.buyer_referral_bp {
+        auction_house.buyer_referral_bp = bbp;
+    }
+    if let Some(sbp) = pending_house_update.seller_referral_bp {
+        auction_house.seller_referral_bp = sbp;
+    }
+    if let Some(pbp) = pending_house_update.protocol_fee_share_bp {
+        auction_house.protocol_fee_share_bp = pbp;
+    }
+    auction_house.assert_referral_bp()?;
+
+    msg!(
+        "{{\"seller_fee_basis_points\":{},\"buyer_referral_bp\":{},\"seller_referral_bp\":{},\"protocol_fee_share_bp\":{}}}",
+        auction_house.seller_fee_basis_points,
+        auction_house.buyer_referral_bp,
+        auction_house.seller_referral_bp,
+        auction_house.protocol_fee_share_bp
+    );
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct ApproveCoOwnerAction<'info> {
+    co_owner: Signer<'info>,
+    /// CHECK: seller, the co-owned wallet, only used to derive the approval
+    seller: UncheckedAccount<'info>,
+    /// CHECK: token_mint, only used to derive the approval
+    token_mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            CO_OWNER_APPROVAL.as_bytes(),
+            seller.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump=co_owner_approval.bump,
+    )]
+    co_owner_approval: Box<Account<'info, CoOwnerApproval>>,
+}
+
+// Approves listing the token with sell_frozen at price and expiry, in payment_mint (default for
+// SOL) and with extra_args, or cancelling its listing with price 0 and no other terms. Approving
+// another action than the pending one starts over from this approval.
+pub fn handle(
+    ctx: Context<ApproveCoOwnerAction>,
+    price: u64,
+    expiry: i64,
+    payment_mint: Pubkey,
+    extra_args: &[u8],
+) -> Result<()> {
+    let action_hash = if price == 0 {
+        CoOwnerApproval::cancel_action_hash()
+    } else {
+        CoOwnerApproval::action_hash(price, expiry, &payment_mint, extra_args)
+    };
+    let co_owner_approval = &mut ctx.accounts.co_owner_approval;
+    co_owner_approval.approve(ctx.accounts.co_owner.key, action_hash)?;
+
+    msg!(
+        "{{\"price\":{},\"expiry\":{},\"payment_mint\":\"{}\",\"approvals\":{},\"threshold\":{}}}",
+        price,
+        expiry,
+        payment_mint,
+        co_owner_approval.approved.count_ones(),
+        co_owner_approval.threshold
+    );
+    Ok(())
+}
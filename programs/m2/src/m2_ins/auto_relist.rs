@@ -0,0 +1,115 @@
+use mpl_token_metadata::accounts::Metadata;
+
+use {
+    crate::constants::*, crate::errors::ErrorCode, crate::states::*, crate::utils::*,
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct AutoRelist<'info> {
+    #[account(mut)]
+    cranker: Signer<'info>,
+    #[account(mut)]
+    seller_trade_state: Box<Account<'info, SellerTradeStateV3>>,
+    // remaining accounts:
+    // metadata (optional) - needed to update the listing's verified collection ListingIndex
+    // listing_index (optional, writable) - seller's and verified collection's ListingIndex, their floor follows the new price
+    // mint_market (optional, writable) - token mint's MintMarket, its best ask follows the new price
+}
+
+// Permissionless crank for listings that opted into auto relisting: once expired, the listing
+// gets the same duration again at auto_relist_price_bp above its price, up to auto_relist_max times.
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, AutoRelist<'info>>) -> Result<()> {
+    let sts = &mut ctx.accounts.seller_trade_state;
+    let now = Clock::get()?.unix_timestamp;
+
+    let expiry = Expiry::from(sts.expiry);
+    if sts.auto_relist_price_bp == 0
+        || sts.auto_relist_count >= sts.auto_relist_max
+        || !expiry.is_expired(now)
+    {
+        return Err(ErrorCode::AutoRelistNotAllowed.into());
+    }
+    let duration = expiry
+        .timestamp()
+        .ok_or(ErrorCode::AutoRelistNotAllowed)?
+        .checked_sub(sts.last_updated_at)
+        .filter(|d| *d > 0)
+        .ok_or(ErrorCode::AutoRelistNotAllowed)?;
+    let new_expiry = now
+        .checked_add(duration)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+    let relist_price = |price: u64| -> Result<u64> {
+        let new_price = (price as u128)
+            .checked_mul(10000 + sts.auto_relist_price_bp as u128)
+            .ok_or(ErrorCode::NumericalOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::NumericalOverflow)? as u64;
+        if new_price > MAX_PRICE {
+            return Err(ErrorCode::InvalidPrice.into());
+        }
+        Ok(new_price)
+    };
+    let new_price = relist_price(sts.buyer_price)?;
+
+    // alt prices go up by the same bp, they live after the V3 body so they're written directly
+    let sts_info = sts.to_account_info();
+    let mut alt_prices = SellerTradeStateV3::alt_prices_from_data(&sts_info.try_borrow_data()?)?;
+    if alt_prices.iter().any(|p| p.price != 0) {
+        for alt_price in alt_prices.iter_mut().filter(|p| p.price != 0) {
+            alt_price.price = relist_price(alt_price.price)?;
+        }
+        let serialized = alt_prices.try_to_vec()?;
+        sts_info.try_borrow_mut_data()?
+            [SellerTradeStateV3::LEN..SellerTradeStateV3::LEN + serialized.len()]
+            .copy_from_slice(&serialized);
+    }
+
+    // keep escrow or delegate mode
+    sts.expiry = expiry.with_timestamp(new_expiry).into();
+    sts.buyer_price = new_price;
+    sts.last_updated_at = now;
+    sts.auto_relist_count += 1;
+
+    // the indexes are written through the account data, so persist the relist first and pick
+    // up the listing_indexes they record after
+    sts.exit(&crate::ID)?;
+    let sell_args = SellArgs::from_account_info(&sts_info)?;
+    let sol_price = sell_args.visible_sol_price(now);
+    let metadata = find_remaining_account(
+        ctx.remaining_accounts,
+        &Metadata::find_pda(&sell_args.token_mint).0,
+    );
+    let listing_indexes: Vec<_> = get_listing_index_keys(&sell_args.seller, metadata)
+        .into_iter()
+        .filter_map(|key| {
+            let (address, bump) = get_listing_index_address(&sell_args.auction_house_key, &key);
+            find_remaining_account(ctx.remaining_accounts, &address)
+                .map(|listing_index| (listing_index, key, bump))
+        })
+        .collect();
+    record_listing(
+        &listing_indexes,
+        &ctx.accounts.cranker.to_account_info(),
+        &sell_args.auction_house_key,
+        &sts_info,
+        sol_price,
+    )?;
+    update_mint_market(
+        find_remaining_account(
+            ctx.remaining_accounts,
+            &get_mint_market_address(&sell_args.auction_house_key, &sell_args.token_mint),
+        ),
+        |market| market.offer_ask(&sts_info.key(), sol_price, sell_args.token_size),
+    )?;
+    let sts = &mut ctx.accounts.seller_trade_state;
+    sts.reload()?;
+
+    msg!(
+        "{{\"price\":{},\"seller_expiry\":{},\"auto_relist_count\":{}}}",
+        sts.buyer_price,
+        sts.expiry,
+        sts.auto_relist_count
+    );
+    Ok(())
+}
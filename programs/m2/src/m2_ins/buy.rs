@@ -57,6 +57,9 @@ pub struct Buy<'info> {
     token_program: Program<'info, Token>,
     system_program: Program<'info, System>,
     rent: Sysvar<'info, Rent>,
+    // remaining accounts:
+    // program_config (optional) - the deployment's ProgramConfig, buy fails with Deprecated if it disabled it
+    // referrer (optional) - Referrer registration of buyer_referral, the referral is only stored if it is registered
 }
 
 pub fn handle<'info>(
@@ -71,11 +74,18 @@ pub fn handle<'info>(
     let token_mint = &ctx.accounts.token_mint;
     let escrow_payment_account = &ctx.accounts.escrow_payment_account;
     let auction_house = &ctx.accounts.auction_house;
-    let buyer_referral = &ctx.accounts.buyer_referral;
     let buyer_trade_state_clone = &ctx.accounts.buyer_trade_state.to_account_info();
     let buyer_trade_state = &mut ctx.accounts.buyer_trade_state;
     let system_program = &ctx.accounts.system_program;
     let auction_house_key = auction_house.key();
+    assert_instruction_enabled(
+        find_program_config(ctx.remaining_accounts)?.as_ref(),
+        GatedInstruction::Buy,
+    )?;
+    // legacy bids can't be counted in WalletLimits, buy_v2 places limited bids
+    if auction_house.enforce_wallet_limits {
+        return Err(ErrorCode::WalletLimitsRequired.into());
+    }
 
     let discriminator_ai = buyer_trade_state_clone.try_borrow_data()?;
     if discriminator_ai[..8] != BuyerTradeState::discriminator() && discriminator_ai[..8] != [0; 8]
@@ -115,7 +125,16 @@ pub fn handle<'info>(
     assert_metadata_valid(metadata, &token_mint_key)?;
     buyer_trade_state.auction_house_key = auction_house_key;
     buyer_trade_state.buyer = wallet.key();
-    buyer_trade_state.buyer_referral = buyer_referral.key();
+    buyer_trade_state.buyer_referral = get_registered_referral(
+        find_remaining_account(
+            ctx.remaining_accounts,
+            &get_referrer_address(
+                &ctx.accounts.auction_house.key(),
+                ctx.accounts.buyer_referral.key,
+            ),
+        ),
+        ctx.accounts.buyer_referral.key,
+    )?;
     buyer_trade_state.buyer_price = buyer_price;
     buyer_trade_state.token_mint = token_mint_key;
     buyer_trade_state.token_size = token_size;
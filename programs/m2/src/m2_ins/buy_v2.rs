@@ -1,7 +1,7 @@
 use anchor_lang::Discriminator;
 use solana_program::{program::invoke, system_instruction};
 
-use crate::index_ra;
+use crate::{index_ra, strict_invariant};
 
 use {
     crate::constants::*,
@@ -18,10 +18,7 @@ pub struct BuyV2<'info> {
     wallet: Signer<'info>,
     /// CHECK: notary is not dangerous because we don't read or write from this account
     notary: UncheckedAccount<'info>,
-    #[account(
-        constraint = token_mint.supply == 1 @ ErrorCode::InvalidTokenMint,
-        constraint = token_mint.decimals == 0 @ ErrorCode::InvalidTokenMint
-    )]
+    #[account(constraint = auction_house.is_tradable_mint(&token_mint) @ ErrorCode::InvalidTokenMint)]
     token_mint: Account<'info, Mint>,
     /// CHECK: metadata
     #[account(
@@ -56,45 +53,164 @@ pub struct BuyV2<'info> {
     buyer_referral: UncheckedAccount<'info>,
     token_program: Program<'info, Token>,
     system_program: Program<'info, System>,
-    // remaining accounts:
+    // remaining accounts, in this order, or in any order if extra_args has a remaining accounts schema (see RemainingAccountsTag):
+    // notary_set, then notaries (signer) (optional) - auction house's NotarySet and threshold of its notaries, cosign in place of auction_house.notary for the rent vault, placed first
+    // program_config (optional) - the deployment's ProgramConfig, placed before the accounts below, its payment mints are accepted too
+    // rate_limit (required if the auction house rate limits wallets, writable) - wallet's RateLimit, placed after program_config and before the accounts below
     // 0. payment_mint (optional) - if the buyer is paying in a token, this is the mint of that token
     // 1. payment_source_token_account (optional) - if the buyer is paying in a token, this is the source token account, we need to verify sufficient balance
     // ...
+    // escrow adapter program, then its accounts (required if extra_args names an escrow adapter) - SOL bids only, placed after the accounts above
+    // -10. mint_market (optional, writable) - token mint's MintMarket, SOL bids are offered as its best bid
+    // -9. wallet_limits (required if the wallet's previous bid on the token counts in it or auction_house.enforce_wallet_limits, writable) - wallet's WalletLimits, its uninitialized address if there is none, the bid counts as an open bid in it
+    // -8. treasury escrow_payment_account (optional, writable) - escrow of the spending_allowance's treasury
+    // -7. spending_allowance (optional, writable) - a SpendingAllowance for the wallet, SOL bids top up the escrow from the treasury's escrow instead of the payer, requires the escrow_ledger, which locks the draw to the bid
+    // -6. escrow_ledger (optional, required if the bid being updated is ledgered, writable) - buyer's EscrowLedger, the bid's full cost is locked in it unless an escrow adapter funds the bid
+    // -5. seller_trade_state (optional) - a listing of the token, the bid must be at least auction_house.min_bid_bp_of_list_price of its price
+    // -4. open_bid_index (optional) - buyer's open bid index, lists the bid and counts SOL bids as locked escrow funds
+    // -3. referrer (optional) - Referrer registration of buyer_referral, the referral is only stored if it is registered
+    // -2. rent_vault (optional) - auction house rent vault, pays for bts rent instead of the wallet if the notary signs and there's no payer
     // -1. payer (optional) - this wallet will try to subsidize SOL for the buyer if bidding in SOL, and will pay for bts rent
 }
 
+/// Where buy_v2 expects each group of remaining accounts
+const BUY_V2_ORDER: [RemainingAccountsTag; 14] = [
+    RemainingAccountsTag::NotarySet,
+    RemainingAccountsTag::ProgramConfig,
+    RemainingAccountsTag::RateLimit,
+    RemainingAccountsTag::Payment,
+    RemainingAccountsTag::EscrowAdapter,
+    RemainingAccountsTag::MintMarket,
+    RemainingAccountsTag::WalletLimits,
+    RemainingAccountsTag::SpendingAllowance,
+    RemainingAccountsTag::EscrowLedger,
+    RemainingAccountsTag::Listing,
+    RemainingAccountsTag::OpenBidIndex,
+    RemainingAccountsTag::Referrer,
+    RemainingAccountsTag::RentVault,
+    RemainingAccountsTag::Payer,
+];
+
 pub fn handle<'info>(
     ctx: Context<'_, '_, '_, 'info, BuyV2<'info>>,
     buyer_price: u64,
     token_size: u64,
     buyer_state_expiry: i64,
     buyer_creator_royalty_bp: u16,
-    _extra_args: &[u8],
+    extra_args: &[u8],
 ) -> Result<()> {
+    let ordered_remaining_accounts =
+        order_remaining_accounts(ctx.remaining_accounts, extra_args, &BUY_V2_ORDER)?;
+    let remaining_accounts = ordered_remaining_accounts
+        .as_deref()
+        .unwrap_or(ctx.remaining_accounts);
+    let extra_args = BuyExtraArgs::parse(extra_args);
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house,
+        &ctx.accounts.notary,
+    )?;
+    let (remaining_accounts, program_config) =
+        split_program_config_from_remaining_accounts(remaining_accounts)?;
+    let remaining_accounts = split_rate_limit_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &ctx.accounts.auction_house,
+        ctx.accounts.wallet.key,
+    )?;
     let (remaining_accounts, possible_payer) =
-        split_payer_from_remaining_accounts(ctx.remaining_accounts);
+        split_payer_from_remaining_accounts(remaining_accounts);
     let payer = if let Some(p) = possible_payer {
         p
     } else {
         &ctx.accounts.wallet
     };
+    let (remaining_accounts, rent_vault) = split_rent_vault_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &notary_cosign,
+    );
+    let rent_vault_seeds = rent_vault.as_ref().map(|v| v.seeds());
+    let (rent_payer, rent_payer_seeds) = match (possible_payer, &rent_vault) {
+        (None, Some(vault)) => (vault.info, rent_vault_seeds.as_ref().map(|s| &s[..])),
+        _ => (payer, None),
+    };
+    let (remaining_accounts, referrer) = split_referrer_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        ctx.accounts.buyer_referral.key,
+    );
+    let buyer_referral = get_registered_referral(referrer, ctx.accounts.buyer_referral.key)?;
+    let (remaining_accounts, open_bid_index) = split_open_bid_index_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        ctx.accounts.wallet.key,
+    );
+    let (remaining_accounts, listing) = split_listing_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &ctx.accounts.token_mint.key(),
+    )?;
+    let (remaining_accounts, escrow_ledger) = split_escrow_ledger_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        ctx.accounts.wallet.key,
+    )?;
+    // an escrow adapter funds the bid, so there's nothing in the escrow to lock for it
+    let ledgered = escrow_ledger.is_some() && extra_args.escrow_adapter == Pubkey::default();
+    let (remaining_accounts, spending_allowance) =
+        split_spending_allowance_from_remaining_accounts(
+            remaining_accounts,
+            &ctx.accounts.auction_house.key(),
+            ctx.accounts.wallet.key,
+        )?;
+    let (remaining_accounts, wallet_limits) = split_wallet_limits_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &ctx.accounts.auction_house,
+        ctx.accounts.wallet.key,
+    )?;
+    let (remaining_accounts, mint_market) = split_mint_market_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &ctx.accounts.token_mint.key(),
+    );
+    let (remaining_accounts, escrow_adapter_accounts) =
+        if extra_args.escrow_adapter != Pubkey::default() {
+            assert_escrow_adapter_allowed(&ctx.accounts.auction_house, &extra_args.escrow_adapter)?;
+            let adapter_accounts =
+                get_escrow_adapter_accounts(remaining_accounts, &extra_args.escrow_adapter)?;
+            (
+                &remaining_accounts[..remaining_accounts.len() - adapter_accounts.len()],
+                Some(adapter_accounts),
+            )
+        } else {
+            (remaining_accounts, None)
+        };
     let metadata = &ctx.accounts.metadata;
     let token_mint = &ctx.accounts.token_mint;
     let escrow_payment_account = &ctx.accounts.escrow_payment_account;
     let auction_house = &ctx.accounts.auction_house;
-    let buyer_referral = &ctx.accounts.buyer_referral;
     let buyer_trade_state = &ctx.accounts.buyer_trade_state;
     let system_program = &ctx.accounts.system_program;
     let is_spl = remaining_accounts.len() == 2;
 
     if buyer_trade_state.data_len() > 0 {
-        let discriminator_data = &buyer_trade_state.try_borrow_data()?[0..8];
+        let discriminator_data = get_discriminator(buyer_trade_state)?;
         if discriminator_data != BuyerTradeState::discriminator()
             && discriminator_data != BuyerTradeStateV2::discriminator()
+            && discriminator_data != BuyerTradeStateV3::discriminator()
         {
             return Err(ErrorCode::InvalidDiscriminator.into());
         }
     }
+    let previous_bid = if buyer_trade_state.data_len() > 0 {
+        Some(BidArgs::from_account_info(buyer_trade_state)?)
+    } else {
+        None
+    };
+    // no bid is backed by an empty escrow, so an index created on it tracks all the wallet's bids
+    let tracks_all_bids = previous_bid.is_none() && escrow_payment_account.lamports() == 0;
 
     if buyer_creator_royalty_bp > 10_000 {
         return Err(ErrorCode::InvalidBasisPoints.into());
@@ -103,45 +219,116 @@ pub fn handle<'info>(
     if buyer_price > MAX_PRICE || buyer_price == 0 {
         return Err(ErrorCode::InvalidPrice.into());
     }
+    if token_size == 0 {
+        return Err(ErrorCode::InvalidTokenAmount.into());
+    }
+    let escalation = assert_bid_escalation(
+        &extra_args.escalation,
+        buyer_price,
+        Clock::get()?.unix_timestamp,
+    )?;
+    // an escrow adapter locks the bid's price up front, it can't follow an escalating bid
+    if escalation.is_set() && extra_args.escrow_adapter != Pubkey::default() {
+        return Err(ErrorCode::InvalidBidEscalation.into());
+    }
+    // a swap converts an SPL escrow into SOL when the bid fills, an all-in bid's cost isn't in its mint
+    if extra_args.swap_max_slippage_bp > 0
+        && (!is_spl
+            || extra_args.price_is_all_in
+            || extra_args.swap_max_slippage_bp > MAX_SWAP_SLIPPAGE_BP)
+    {
+        return Err(ErrorCode::InvalidBasisPoints.into());
+    }
+    // an escalating bid is funded and locked at the most it can pay, for every unit it bids on
+    let locked_price = get_total_price(buyer_price.max(escalation.max_price), token_size)?;
+    // the funding source of a bid can't change, cancel and bid again instead
+    if previous_bid
+        .as_ref()
+        .map_or(false, |b| b.escrow_adapter != extra_args.escrow_adapter)
+    {
+        return Err(ErrorCode::EscrowAdapterMismatch.into());
+    }
+
+    if spending_allowance.is_some() && (escrow_adapter_accounts.is_some() || is_spl) {
+        return Err(ErrorCode::UnexpectedRemainingAccounts.into());
+    }
+    if let Some(loaded) = &spending_allowance {
+        // the draw is locked in the escrow ledger so the spender can't withdraw it
+        if !ledgered {
+            return Err(ErrorCode::MissingRemainingAccount.into());
+        }
+        // a bid draws from one allowance only, so it's refunded to the treasury that funded it
+        if previous_bid.as_ref().map_or(false, |b| b.allowance_funded)
+            && !loaded.allowance.has_lock(buyer_trade_state.key)
+        {
+            return Err(ErrorCode::TradeStateMismatch.into());
+        }
+    }
+    let mut allowance_funded = previous_bid.as_ref().map_or(false, |b| b.allowance_funded);
 
-    if remaining_accounts.is_empty() {
+    let escrow_balance = if escrow_adapter_accounts.is_some() {
+        // SOL from an external escrow program, locked once the trade state is written
+        if !remaining_accounts.is_empty() {
+            return Err(ErrorCode::UnexpectedRemainingAccounts.into());
+        }
+        0
+    } else if remaining_accounts.is_empty() {
         // SOL
-        if escrow_payment_account.lamports() < buyer_price {
-            let diff = buyer_price
+        if escrow_payment_account.lamports() < locked_price {
+            let diff = locked_price
                 .checked_sub(escrow_payment_account.lamports())
                 .ok_or(ErrorCode::NumericalOverflow)?;
-            invoke(
-                &system_instruction::transfer(payer.key, &escrow_payment_account.key(), diff),
-                &[
-                    payer.to_account_info(),
-                    escrow_payment_account.to_account_info(),
-                    system_program.to_account_info(),
-                ],
-            )?;
+            if let Some(mut loaded) = spending_allowance {
+                draw_spending_allowance(
+                    &mut loaded,
+                    escrow_payment_account,
+                    buyer_trade_state.key,
+                    diff,
+                )?;
+                allowance_funded = true;
+            } else {
+                invoke(
+                    &system_instruction::transfer(payer.key, &escrow_payment_account.key(), diff),
+                    &[
+                        payer.to_account_info(),
+                        escrow_payment_account.to_account_info(),
+                        system_program.to_account_info(),
+                    ],
+                )?;
+            }
         }
+        escrow_payment_account.lamports()
     } else if is_spl {
         // SPL
-        assert_payment_mint(index_ra!(remaining_accounts, 0))?;
+        assert_payment_mint(index_ra!(remaining_accounts, 0), program_config.as_ref())?;
         let payment_token_account_parsed = assert_is_ata(
             index_ra!(remaining_accounts, 1),
             escrow_payment_account.key,
             index_ra!(remaining_accounts, 0).key,
             escrow_payment_account.key,
         )?;
-        if payment_token_account_parsed.amount < buyer_price {
-            return Err(ErrorCode::InvalidTokenAmount.into());
+        if payment_token_account_parsed.amount < locked_price {
+            return Err(ErrorCode::EscrowBalanceTooLow.into());
         }
+        payment_token_account_parsed.amount
     } else {
-        return Err(ErrorCode::InvalidAccountState.into());
-    }
+        return Err(ErrorCode::UnexpectedRemainingAccounts.into());
+    };
 
     assert_metadata_valid(metadata, &token_mint.key())?;
+    // a pin that doesn't match already can never fill
+    assert_metadata_pin(&extra_args.metadata_pin, metadata)?;
+    // a floor condition is checked against the verified collection's floor, without one it can't fill
+    if extra_args.max_floor_price > 0 && get_verified_collection(metadata).is_none() {
+        return Err(ErrorCode::FloorConditionNotMet.into());
+    }
     let bts_bump = ctx.bumps.buyer_trade_state;
     // create or reallocate the buyer trade state
     // after this call the correct size should be allocated and discriminator should be written
     create_or_realloc_buyer_trade_state(
         buyer_trade_state,
-        payer,
+        rent_payer,
+        rent_payer_seeds,
         &[
             PREFIX.as_bytes(),
             ctx.accounts.wallet.key().as_ref(),
@@ -151,10 +338,10 @@ pub fn handle<'info>(
         ],
     )?;
 
-    let bts_v2 = BuyerTradeStateV2 {
+    let bts_v3 = BuyerTradeStateV3 {
         auction_house_key: auction_house.key(),
         buyer: ctx.accounts.wallet.key(),
-        buyer_referral: buyer_referral.key(),
+        buyer_referral,
         buyer_price,
         token_mint: token_mint.key(),
         token_size,
@@ -166,16 +353,133 @@ pub fn handle<'info>(
         } else {
             Pubkey::default()
         },
+        indexed: open_bid_index.is_some(),
+        escrow_adapter: extra_args.escrow_adapter,
+        order_memo: extra_args.order_memo,
+        receipt_owner: extra_args.receipt_owner,
+        ledgered,
+        metadata_pin: extra_args.metadata_pin,
+        escalation,
+        limited: wallet_limits.is_some(),
+        price_is_all_in: extra_args.price_is_all_in,
+        auto_match: extra_args.auto_match,
+        swap_max_slippage_bp: extra_args.swap_max_slippage_bp,
+        // an updated bid stays in its BidGroup
+        bid_group: previous_bid
+            .as_ref()
+            .map_or(Pubkey::default(), |b| b.bid_group),
+        allowance_funded,
+        max_floor_price: extra_args.max_floor_price,
     };
 
+    // offers on a listing in a currency it accepts can't be dust relative to its price in it
+    if let Some(list_price) = listing.and_then(|l| l.price_for(&bts_v3.payment_mint)) {
+        let min_bid = (list_price as u128)
+            .checked_mul(auction_house.min_bid_bp_of_list_price as u128)
+            .ok_or(ErrorCode::NumericalOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        if (buyer_price as u128) < min_bid {
+            return Err(ErrorCode::BidTooLowForListing.into());
+        }
+    }
+
+    let locked_cost = get_bid_locked_cost(
+        locked_price,
+        bts_v3.price_is_all_in,
+        bts_v3.buyer_creator_royalty_bp,
+    );
+    if let Some((open_bid_index, bump)) = open_bid_index {
+        let mut index = load_or_create_open_bid_index(
+            open_bid_index,
+            payer,
+            &auction_house.key(),
+            ctx.accounts.wallet.key,
+            bump,
+            tracks_all_bids,
+        )?;
+        // an updated bid replaces the previous one in the index
+        if let Some(previous_bid) = previous_bid.as_ref().filter(|b| b.indexed) {
+            index.remove_bid(
+                buyer_trade_state.key,
+                previous_bid.locked_cost(),
+                &previous_bid.payment_mint,
+            );
+        }
+        index.add_bid(buyer_trade_state.key, locked_cost, &bts_v3.payment_mint)?;
+        save_open_bid_index(open_bid_index, Some(payer), &index)?;
+    }
+
+    // an updated bid was already counted, unless it wasn't placed with the limits
+    match (
+        wallet_limits,
+        previous_bid.as_ref().map_or(false, |b| b.limited),
+    ) {
+        (Some((wallet_limits, mut limits)), previously_limited) => {
+            if !previously_limited {
+                limits.open_bid()?;
+            }
+            save_wallet_limits(wallet_limits, &limits)?;
+        }
+        (None, true) => return Err(ErrorCode::MissingRemainingAccount.into()),
+        (None, false) => {}
+    }
+
+    match escrow_ledger {
+        Some((escrow_ledger, mut ledger)) => {
+            // an updated bid replaces the previous one's lock
+            if let Some(previous_bid) = previous_bid.as_ref().filter(|b| b.ledgered) {
+                ledger.unlock(&previous_bid.payment_mint, previous_bid.locked_cost())?;
+            }
+            if ledgered {
+                ledger.lock(&bts_v3.payment_mint, locked_cost)?;
+            }
+            ledger.sync(&bts_v3.payment_mint, escrow_balance)?;
+            save_escrow_ledger(escrow_ledger, &ledger)?;
+        }
+        // the previous bid's lock is only released with the ledger
+        None if previous_bid.as_ref().map_or(false, |b| b.ledgered) => {
+            return Err(ErrorCode::MissingRemainingAccount.into());
+        }
+        None => {}
+    }
+
     // serialize
-    let bts_v2_serialized = bts_v2.try_to_vec()?;
-    buyer_trade_state.try_borrow_mut_data()?[8..8 + bts_v2_serialized.len()]
-        .copy_from_slice(&bts_v2_serialized);
+    let bts_v3_serialized = bts_v3.try_to_vec()?;
+    buyer_trade_state.try_borrow_mut_data()?[8..8 + bts_v3_serialized.len()]
+        .copy_from_slice(&bts_v3_serialized);
+    strict_invariant!(assert_buyer_trade_state_consistent(
+        buyer_trade_state,
+        &bts_v3
+    ));
+    update_mint_market(mint_market, |market| {
+        market.offer_bid(
+            buyer_trade_state.key,
+            (bts_v3.payment_mint == Pubkey::default()).then_some(bts_v3.buyer_price),
+            bts_v3.token_size,
+        )
+    })?;
+    if let Some(adapter_accounts) = escrow_adapter_accounts {
+        invoke_escrow_adapter(
+            EscrowAdapterInstruction::Lock {
+                amount: locked_price,
+            },
+            adapter_accounts,
+            auction_house.as_ref(),
+            &[
+                PREFIX.as_bytes(),
+                auction_house.creator.as_ref(),
+                &[auction_house.bump],
+            ],
+            buyer_trade_state,
+            ctx.accounts.wallet.as_ref(),
+            None,
+        )?;
+    }
     msg!(
         "{{\"price\":{},\"buyer_expiry\":{}}}",
-        bts_v2.buyer_price,
-        bts_v2.expiry
+        bts_v3.buyer_price,
+        bts_v3.expiry
     );
     Ok(())
 }
@@ -0,0 +1,357 @@
+use anchor_lang::Discriminator;
+use solana_program::{program::invoke, system_instruction, sysvar};
+
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    crate::utils::*,
+    anchor_lang::{prelude::*, AnchorDeserialize},
+    anchor_spl::token::Mint,
+};
+
+/// The message the buyer signs off-chain, Borsh serialized
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct BuyAuthorization {
+    pub auction_house: Pubkey,
+    pub token_mint: Pubkey,
+    pub price: u64,
+    pub expiry: i64,
+    pub buyer_creator_royalty_bp: u16,
+    pub nonce: u64,
+}
+
+#[derive(Accounts)]
+pub struct BuyWithAuthorization<'info> {
+    /// CHECK: buyer doesn't sign the transaction, the ed25519 instruction carries the buyer's signature
+    wallet: UncheckedAccount<'info>,
+    notary: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(
+        constraint = token_mint.supply == 1 @ ErrorCode::InvalidTokenMint,
+        constraint = token_mint.decimals == 0 @ ErrorCode::InvalidTokenMint
+    )]
+    token_mint: Account<'info, Mint>,
+    /// CHECK: metadata
+    metadata: UncheckedAccount<'info>,
+    /// CHECK: escrow_payment_account
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), wallet.key().as_ref()], bump)]
+    escrow_payment_account: UncheckedAccount<'info>,
+    /// CHECK: authority
+    authority: UncheckedAccount<'info>,
+    #[account(
+        seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()],
+        bump=auction_house.bump,
+        has_one=authority,
+    )]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+    /// CHECK: seeds check + discriminator check
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump)]
+    buyer_trade_state: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        seeds=[
+            PREFIX.as_bytes(),
+            AUTHORIZATION_NONCE.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        space=AuthorizationNonce::LEN,
+        bump,
+    )]
+    authorization_nonce: Box<Account<'info, AuthorizationNonce>>,
+    /// CHECK: buyer_referral
+    buyer_referral: UncheckedAccount<'info>,
+    /// CHECK: checked by address
+    #[account(address = sysvar::instructions::id())]
+    instructions: UncheckedAccount<'info>,
+    system_program: Program<'info, System>,
+    // remaining accounts, in this order, or in any order with a remaining accounts schema appended to the instruction data (see RemainingAccountsTag):
+    // notary_set, then notaries (signer) (optional) - auction house's NotarySet and threshold of its notaries, cosign in place of auction_house.notary, placed first
+    // rate_limit (required if the auction house rate limits wallets, writable) - wallet's RateLimit, placed before the accounts below
+    // 0. open_bid_index (optional) - buyer's open bid index, lists the bid and counts it as locked escrow funds
+    // referrer (optional) - Referrer registration of buyer_referral, the referral is only stored if it is registered
+    // escrow_ledger (optional, required if the bid being updated is ledgered or with a spending_allowance, writable) - buyer's EscrowLedger, the bid's full cost is locked in it
+    // wallet_limits (required if auction_house.enforce_wallet_limits, writable) - buyer's WalletLimits, its uninitialized address if there is none, the bid counts as an open bid in it
+    // mint_market (optional, writable) - token mint's MintMarket, the bid is offered as its best bid
+    // -2. treasury escrow_payment_account (optional, writable) - escrow of the spending_allowance's treasury
+    // -1. spending_allowance (optional, writable) - a SpendingAllowance for the wallet, the escrow is topped up from the treasury's escrow instead of the payer
+    // instructions sysvar (optional) - placed last if a remaining accounts schema is appended to the instruction data
+}
+
+/// Length of the buy_with_authorization instruction data up to the end of its args
+pub const BUY_WITH_AUTHORIZATION_ARGS_LEN: usize = 8 + 8 + 8 + 2 + 8;
+
+/// Where buy_with_authorization expects each group of remaining accounts
+pub const BUY_WITH_AUTHORIZATION_ORDER: [RemainingAccountsTag; 4] = [
+    RemainingAccountsTag::NotarySet,
+    RemainingAccountsTag::RateLimit,
+    RemainingAccountsTag::Keyed,
+    RemainingAccountsTag::SpendingAllowance,
+];
+
+// Bids in SOL without a buyer transaction: the buyer signs the bid terms off-chain, the notary
+// relays it and the payer funds the trade state rent and whatever the escrow is short of the price.
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, BuyWithAuthorization<'info>>,
+    price: u64,
+    expiry: i64,
+    buyer_creator_royalty_bp: u16,
+    nonce: u64,
+) -> Result<()> {
+    let wallet = &ctx.accounts.wallet;
+    let payer = &ctx.accounts.payer;
+    let token_mint = &ctx.accounts.token_mint;
+    let escrow_payment_account = &ctx.accounts.escrow_payment_account;
+    let auction_house = &ctx.accounts.auction_house;
+    let buyer_trade_state = &ctx.accounts.buyer_trade_state;
+    let system_program = &ctx.accounts.system_program;
+    let authorization_nonce = &mut ctx.accounts.authorization_nonce;
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        ctx.remaining_accounts,
+        auction_house,
+        &ctx.accounts.notary,
+    )?;
+    notary_cosign.assert_signed()?;
+
+    if buyer_creator_royalty_bp > 10_000 {
+        return Err(ErrorCode::InvalidBasisPoints.into());
+    }
+    if price > MAX_PRICE || price == 0 {
+        return Err(ErrorCode::InvalidPrice.into());
+    }
+
+    let message = BuyAuthorization {
+        auction_house: auction_house.key(),
+        token_mint: token_mint.key(),
+        price,
+        expiry,
+        buyer_creator_royalty_bp,
+        nonce,
+    }
+    .try_to_vec()?;
+    assert_ed25519_signed(&ctx.accounts.instructions, wallet.key, &message)?;
+
+    consume_authorization_nonce(
+        authorization_nonce,
+        &auction_house.key(),
+        wallet.key,
+        ctx.bumps.authorization_nonce,
+        nonce,
+    )?;
+
+    let previous_bid = if buyer_trade_state.data_len() > 0 {
+        let discriminator = get_discriminator(buyer_trade_state)?;
+        if discriminator != BuyerTradeState::discriminator()
+            && discriminator != BuyerTradeStateV2::discriminator()
+            && discriminator != BuyerTradeStateV3::discriminator()
+        {
+            return Err(ErrorCode::InvalidDiscriminator.into());
+        }
+        Some(BidArgs::from_account_info(buyer_trade_state)?)
+    } else {
+        None
+    };
+    // no bid is backed by an empty escrow, so an index created on it tracks all the wallet's bids
+    let tracks_all_bids = previous_bid.is_none() && escrow_payment_account.lamports() == 0;
+    if previous_bid
+        .as_ref()
+        .map_or(false, |b| b.escrow_adapter != Pubkey::default())
+    {
+        return Err(ErrorCode::EscrowAdapterMismatch.into());
+    }
+
+    let remaining_accounts = split_rate_limit_from_remaining_accounts(
+        remaining_accounts,
+        &auction_house.key(),
+        auction_house,
+        wallet.key,
+    )?;
+    let (remaining_accounts, spending_allowance) =
+        split_spending_allowance_from_remaining_accounts(
+            remaining_accounts,
+            &auction_house.key(),
+            wallet.key,
+        )?;
+    let escrow_ledger = find_remaining_account(
+        remaining_accounts,
+        &get_escrow_ledger_address(&auction_house.key(), wallet.key),
+    );
+    let mut ledger = match escrow_ledger {
+        Some(ai) => {
+            assert_owned_by(ai, &crate::ID)?;
+            Some(EscrowLedger::try_deserialize(
+                &mut &ai.try_borrow_data()?[..],
+            )?)
+        }
+        None => None,
+    };
+    // the previous bid's lock is only released with the ledger, and a draw is locked in it so the
+    // buyer can't withdraw it
+    if ledger.is_none()
+        && (spending_allowance.is_some() || previous_bid.as_ref().map_or(false, |b| b.ledgered))
+    {
+        return Err(ErrorCode::MissingRemainingAccount.into());
+    }
+    // a bid draws from one allowance only, so it's refunded to the treasury that funded it
+    if let Some(loaded) = &spending_allowance {
+        if previous_bid.as_ref().map_or(false, |b| b.allowance_funded)
+            && !loaded.allowance.has_lock(buyer_trade_state.key)
+        {
+            return Err(ErrorCode::TradeStateMismatch.into());
+        }
+    }
+    let mut allowance_funded = previous_bid.as_ref().map_or(false, |b| b.allowance_funded);
+    let wallet_limits = find_wallet_limits(
+        remaining_accounts,
+        &auction_house.key(),
+        auction_house,
+        wallet.key,
+    )?;
+    let previously_limited = previous_bid.as_ref().map_or(false, |b| b.limited);
+
+    if escrow_payment_account.lamports() < price {
+        let diff = price
+            .checked_sub(escrow_payment_account.lamports())
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        if let Some(mut loaded) = spending_allowance {
+            draw_spending_allowance(
+                &mut loaded,
+                escrow_payment_account,
+                buyer_trade_state.key,
+                diff,
+            )?;
+            allowance_funded = true;
+        } else {
+            invoke(
+                &system_instruction::transfer(payer.key, &escrow_payment_account.key(), diff),
+                &[
+                    payer.to_account_info(),
+                    escrow_payment_account.to_account_info(),
+                    system_program.to_account_info(),
+                ],
+            )?;
+        }
+    }
+
+    assert_metadata_valid(&ctx.accounts.metadata, &token_mint.key())?;
+    create_or_realloc_buyer_trade_state(
+        buyer_trade_state,
+        payer,
+        None,
+        &[
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_mint.key().as_ref(),
+            &[ctx.bumps.buyer_trade_state],
+        ],
+    )?;
+
+    let (index_key, index_bump) = get_open_bid_index_address(&auction_house.key(), wallet.key);
+    let open_bid_index = find_remaining_account(remaining_accounts, &index_key);
+    let bts_v3 = BuyerTradeStateV3 {
+        auction_house_key: auction_house.key(),
+        buyer: wallet.key(),
+        buyer_referral: get_registered_referral(
+            find_remaining_account(
+                remaining_accounts,
+                &get_referrer_address(&auction_house.key(), ctx.accounts.buyer_referral.key),
+            ),
+            ctx.accounts.buyer_referral.key,
+        )?,
+        buyer_price: price,
+        token_mint: token_mint.key(),
+        token_size: 1,
+        bump: ctx.bumps.buyer_trade_state,
+        buyer_creator_royalty_bp,
+        expiry: get_default_buyer_state_expiry(expiry),
+        payment_mint: Pubkey::default(),
+        indexed: open_bid_index.is_some(),
+        escrow_adapter: Pubkey::default(),
+        order_memo: [0; 32],
+        receipt_owner: Pubkey::default(),
+        ledgered: ledger.is_some(),
+        metadata_pin: [0; METADATA_PIN_LEN],
+        escalation: BidEscalation::default(),
+        // a bid counted in the buyer's WalletLimits stays counted until it's cancelled or filled
+        limited: previously_limited || wallet_limits.is_some(),
+        price_is_all_in: false,
+        auto_match: false,
+        swap_max_slippage_bp: 0,
+        // an updated bid stays in its BidGroup
+        bid_group: previous_bid
+            .as_ref()
+            .map_or(Pubkey::default(), |b| b.bid_group),
+        allowance_funded,
+        max_floor_price: 0,
+    };
+    let locked_cost = get_bid_locked_cost(
+        price,
+        bts_v3.price_is_all_in,
+        bts_v3.buyer_creator_royalty_bp,
+    );
+
+    if let Some(open_bid_index) = open_bid_index {
+        let mut index = load_or_create_open_bid_index(
+            open_bid_index,
+            payer,
+            &auction_house.key(),
+            wallet.key,
+            index_bump,
+            tracks_all_bids,
+        )?;
+        if let Some(previous_bid) = previous_bid.as_ref().filter(|b| b.indexed) {
+            index.remove_bid(
+                buyer_trade_state.key,
+                previous_bid.locked_cost(),
+                &previous_bid.payment_mint,
+            );
+        }
+        index.add_bid(buyer_trade_state.key, locked_cost, &bts_v3.payment_mint)?;
+        save_open_bid_index(open_bid_index, Some(payer), &index)?;
+    }
+    if let Some((wallet_limits, mut limits)) = wallet_limits {
+        if !previously_limited {
+            limits.open_bid()?;
+            save_wallet_limits(wallet_limits, &limits)?;
+        }
+    }
+    if let (Some(escrow_ledger), Some(ledger)) = (escrow_ledger, ledger.as_mut()) {
+        // an updated bid replaces the previous one's lock
+        if let Some(previous_bid) = previous_bid.as_ref().filter(|b| b.ledgered) {
+            ledger.unlock(&previous_bid.payment_mint, previous_bid.locked_cost())?;
+        }
+        ledger.lock(&bts_v3.payment_mint, locked_cost)?;
+        ledger.sync(&bts_v3.payment_mint, escrow_payment_account.lamports())?;
+        save_escrow_ledger(escrow_ledger, ledger)?;
+    }
+
+    let bts_v3_serialized = bts_v3.try_to_vec()?;
+    buyer_trade_state.try_borrow_mut_data()?[8..8 + bts_v3_serialized.len()]
+        .copy_from_slice(&bts_v3_serialized);
+    update_mint_market(
+        find_remaining_account(
+            remaining_accounts,
+            &get_mint_market_address(&auction_house.key(), &token_mint.key()),
+        ),
+        |market| market.offer_bid(buyer_trade_state.key, Some(price), 1),
+    )?;
+    msg!(
+        "{{\"price\":{},\"buyer_expiry\":{},\"nonce\":{}}}",
+        bts_v3.buyer_price,
+        bts_v3.expiry,
+        nonce
+    );
+    Ok(())
+}
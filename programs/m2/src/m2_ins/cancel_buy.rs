@@ -1,11 +1,21 @@
 use {
-    crate::constants::*, crate::errors::ErrorCode, crate::states::*,
-    crate::utils::close_account_anchor, anchor_lang::prelude::*, anchor_spl::token::Mint,
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    crate::utils::{
+        close_account_anchor, get_escrow_adapter_accounts, invoke_escrow_adapter,
+        is_cancel_authority, release_escrow_ledger_bid, release_open_bid,
+        release_spending_allowance_bid, release_wallet_limits_bid, remove_from_mint_market,
+        split_guardian_from_remaining_accounts, split_program_config_from_remaining_accounts,
+        EscrowAdapterInstruction, RemainingAccountsTag,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::Mint,
 };
 
 #[derive(Accounts)]
 pub struct CancelBuy<'info> {
-    /// CHECK: wallet
+    /// CHECK: wallet must sign, otherwise its guardian or delist authority (notary) must sign
     #[account(mut)]
     wallet: UncheckedAccount<'info>,
     /// CHECK: notary is not dangerous because we don't read or write from this account
@@ -30,17 +40,43 @@ pub struct CancelBuy<'info> {
     buyer_trade_state: AccountInfo<'info>,
     /// CHECK: buyer_referral
     buyer_referral: UncheckedAccount<'info>,
+    // remaining accounts, in this order, or in any order with a remaining accounts schema appended to the instruction data (see RemainingAccountsTag):
+    // program_config (optional) - the deployment's ProgramConfig, placed before the accounts below, its cancel authority may cancel too
+    // guardian_account, then guardian (signer) (optional) - wallet's Guardian and its guardian, cancels for the wallet, placed before the accounts below
+    // cancel_authority_registry (required if auction_house.cancel_authority_registry and the notary cancels for the wallet)
+    // 0. open_bid_index (required if the bid is indexed, writable) - buyer's OpenBidIndex, the cancelled bid is removed from it
+    // escrow_ledger (required if the bid is ledgered, writable) - buyer's EscrowLedger, the cancelled bid is unlocked in it
+    // spending_allowance, its treasury's escrow_payment_account and the buyer's escrow_payment_account (required if the bid drew from a spending allowance, writable) - what it drew goes back to the treasury
+    // wallet_limits (optional, writable) - buyer's WalletLimits, the cancelled bid is closed in it
+    // mint_market (optional, writable) - token mint's MintMarket, the cancelled bid is dropped from it
+    // escrow adapter program, then its accounts (required if the bid is funded by an escrow adapter) - placed last
+    // instructions sysvar (optional) - placed last if a remaining accounts schema is appended to the instruction data
 }
 
+/// Length of the cancel_buy instruction data up to the end of its args
+pub const CANCEL_BUY_ARGS_LEN: usize = 8 + 8 + 8 + 8;
+
+/// Where cancel_buy expects each group of remaining accounts
+pub const CANCEL_BUY_ORDER: [RemainingAccountsTag; 4] = [
+    RemainingAccountsTag::ProgramConfig,
+    RemainingAccountsTag::Guardian,
+    RemainingAccountsTag::Keyed,
+    RemainingAccountsTag::EscrowAdapter,
+];
+
 pub fn handle<'info>(
     ctx: Context<'_, '_, '_, 'info, CancelBuy<'info>>,
     buyer_price: u64,
     token_size: u64,
     buyer_state_expiry: i64,
 ) -> Result<()> {
+    let (remaining_accounts, program_config) =
+        split_program_config_from_remaining_accounts(ctx.remaining_accounts)?;
     let wallet = &ctx.accounts.wallet;
     let notary = &ctx.accounts.notary;
     let buyer_trade_state = &mut ctx.accounts.buyer_trade_state;
+    let (remaining_accounts, guardian) =
+        split_guardian_from_remaining_accounts(remaining_accounts, wallet)?;
 
     if buyer_trade_state.data_is_empty() {
         return Err(ErrorCode::EmptyTradeState.into());
@@ -58,14 +94,58 @@ pub fn handle<'info>(
         return Err(ErrorCode::InvalidExpiry.into());
     }
 
-    // If wallet doesn't sign, notary must be CANCEL_AUTHORITY and also sign.
-    let cancel_authority_signed = notary.is_signer && *notary.key == CANCEL_AUTHORITY;
+    // If neither the wallet nor its guardian sign, notary must be a cancel authority and also sign.
+    let cancel_authority_signed = notary.is_signer
+        && is_cancel_authority(
+            notary.key,
+            &ctx.accounts.auction_house,
+            program_config.as_ref(),
+            remaining_accounts,
+        )?;
 
-    if !wallet.is_signer && !cancel_authority_signed {
+    if !wallet.is_signer && guardian.is_none() && !cancel_authority_signed {
         return Err(ErrorCode::NoValidSignerPresent.into());
     }
 
-    close_account_anchor(buyer_trade_state, wallet)?;
+    release_open_bid(remaining_accounts, buyer_trade_state.key, &bid_args)?;
+    release_escrow_ledger_bid(remaining_accounts, &bid_args)?;
+    release_wallet_limits_bid(
+        remaining_accounts,
+        &ctx.accounts.auction_house,
+        &bid_args,
+        None,
+    )?;
+    release_spending_allowance_bid(
+        remaining_accounts,
+        buyer_trade_state.key,
+        &bid_args,
+        None,
+        0,
+    )?;
+    remove_from_mint_market(
+        remaining_accounts,
+        &bid_args.auction_house_key,
+        &bid_args.token_mint,
+        &[buyer_trade_state.key],
+    )?;
+    if bid_args.escrow_adapter != Pubkey::default() {
+        // released even if the adapter was removed from the auction house since
+        let auction_house = &ctx.accounts.auction_house;
+        invoke_escrow_adapter(
+            EscrowAdapterInstruction::Release,
+            get_escrow_adapter_accounts(remaining_accounts, &bid_args.escrow_adapter)?,
+            auction_house.as_ref(),
+            &[
+                PREFIX.as_bytes(),
+                auction_house.creator.as_ref(),
+                &[auction_house.bump],
+            ],
+            buyer_trade_state,
+            wallet,
+            None,
+        )?;
+    }
+    close_account_anchor(buyer_trade_state, wallet, true)?;
 
     Ok(())
 }
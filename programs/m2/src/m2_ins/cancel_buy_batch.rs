@@ -0,0 +1,188 @@
+use anchor_lang::Discriminator;
+
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    crate::utils::{
+        assert_keys_equal, assert_owned_by, close_account_anchor, find_remaining_account,
+        get_cancel_authority_registry_address, get_escrow_ledger_address,
+        get_open_bid_index_address, get_wallet_limits_address, is_cancel_authority,
+        remove_from_mint_market, save_escrow_ledger, save_open_bid_index, save_wallet_limits,
+        split_guardian_from_remaining_accounts, split_program_config_from_remaining_accounts,
+        RemainingAccountsTag,
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct CancelBuyBatch<'info> {
+    /// CHECK: wallet must sign, otherwise its guardian or delist authority (notary) must sign
+    #[account(mut)]
+    wallet: UncheckedAccount<'info>,
+    /// CHECK: notary is not dangerous because we don't read or write from this account
+    notary: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    // remaining accounts, in this order, or in any order with a remaining accounts schema appended to the instruction data (see RemainingAccountsTag):
+    // program_config (optional) - the deployment's ProgramConfig, placed before the accounts below, its cancel authority may cancel too
+    // guardian_account, then guardian (signer) (optional) - wallet's Guardian and its guardian, cancels for the wallet, placed before the accounts below
+    // cancel_authority_registry (required if auction_house.cancel_authority_registry and the notary cancels for the wallet)
+    // 0..MAX_CANCEL_BUY_BATCH. buyer_trade_state (writable) - the wallet's bids to cancel, already closed ones are skipped, bids funded by an escrow adapter or a spending allowance are cancelled with cancel_buy
+    // open_bid_index (required if any of the bids is indexed, writable) - wallet's OpenBidIndex, the cancelled bids are removed from it
+    // escrow_ledger (required if a cancelled bid is ledgered, writable) - wallet's EscrowLedger, the cancelled bids are unlocked in it
+    // wallet_limits (optional, writable) - wallet's WalletLimits, the cancelled bids are closed in it
+    // mint_market (optional, writable) - any of the bids' mints' MintMarkets, the cancelled bids are dropped from them
+    // instructions sysvar (optional) - placed last if a remaining accounts schema is appended to the instruction data
+}
+
+/// Length of the cancel_buy_batch instruction data, it has no args
+pub const CANCEL_BUY_BATCH_ARGS_LEN: usize = 8;
+
+/// Where cancel_buy_batch expects each group of remaining accounts
+pub const CANCEL_BUY_BATCH_ORDER: [RemainingAccountsTag; 3] = [
+    RemainingAccountsTag::ProgramConfig,
+    RemainingAccountsTag::Guardian,
+    RemainingAccountsTag::Keyed,
+];
+
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, CancelBuyBatch<'info>>) -> Result<()> {
+    let (remaining_accounts, program_config) =
+        split_program_config_from_remaining_accounts(ctx.remaining_accounts)?;
+    let wallet = &ctx.accounts.wallet;
+    let notary = &ctx.accounts.notary;
+    let auction_house_key = ctx.accounts.auction_house.key();
+    let (remaining_accounts, guardian) =
+        split_guardian_from_remaining_accounts(remaining_accounts, wallet)?;
+
+    // If neither the wallet nor its guardian sign, notary must be a cancel authority and also sign.
+    let cancel_authority_signed = notary.is_signer
+        && is_cancel_authority(
+            notary.key,
+            &ctx.accounts.auction_house,
+            program_config.as_ref(),
+            remaining_accounts,
+        )?;
+    if !wallet.is_signer && guardian.is_none() && !cancel_authority_signed {
+        return Err(ErrorCode::NoValidSignerPresent.into());
+    }
+
+    let (index_key, _) = get_open_bid_index_address(&auction_house_key, wallet.key);
+    let open_bid_index = find_remaining_account(remaining_accounts, &index_key);
+    let mut index = match open_bid_index {
+        Some(ai) => {
+            assert_owned_by(ai, &crate::ID)?;
+            Some(OpenBidIndex::try_deserialize(
+                &mut &ai.try_borrow_data()?[..],
+            )?)
+        }
+        None => None,
+    };
+
+    let ledger_key = get_escrow_ledger_address(&auction_house_key, wallet.key);
+    let escrow_ledger = find_remaining_account(remaining_accounts, &ledger_key);
+    let mut ledger = match escrow_ledger {
+        Some(ai) => {
+            assert_owned_by(ai, &crate::ID)?;
+            Some(EscrowLedger::try_deserialize(
+                &mut &ai.try_borrow_data()?[..],
+            )?)
+        }
+        None => None,
+    };
+
+    let limits_key = get_wallet_limits_address(&auction_house_key, wallet.key);
+    let wallet_limits = find_remaining_account(remaining_accounts, &limits_key);
+    let mut limits = match wallet_limits {
+        Some(ai) => {
+            assert_owned_by(ai, &crate::ID)?;
+            Some(WalletLimits::try_deserialize(
+                &mut &ai.try_borrow_data()?[..],
+            )?)
+        }
+        None => None,
+    };
+
+    let registry_key = get_cancel_authority_registry_address(&auction_house_key);
+    let buyer_trade_states = remaining_accounts.iter().filter(|ai| {
+        *ai.key != index_key
+            && *ai.key != registry_key
+            && *ai.key != ledger_key
+            && *ai.key != limits_key
+            && !matches!(ai.try_borrow_data(), Ok(data) if data.starts_with(&MintMarket::discriminator()))
+    });
+    if buyer_trade_states.clone().count() > MAX_CANCEL_BUY_BATCH {
+        return Err(ErrorCode::BatchTooLarge.into());
+    }
+
+    let mut cancelled = 0u32;
+    for buyer_trade_state in buyer_trade_states {
+        if buyer_trade_state.data_is_empty() {
+            continue;
+        }
+        let bid_args = BidArgs::from_account_info(buyer_trade_state)?;
+        if bid_args.auction_house_key != auction_house_key {
+            return Err(ErrorCode::WrongAuctionHouseForState.into());
+        }
+        if bid_args.buyer != wallet.key() {
+            return Err(ErrorCode::TradeStateMismatch.into());
+        }
+        // bids funded by an escrow adapter need its accounts, cancel them with cancel_buy
+        if bid_args.escrow_adapter != Pubkey::default() {
+            return Err(ErrorCode::EscrowAdapterMismatch.into());
+        }
+        // so do bids that drew from a spending allowance, for its refund
+        if bid_args.allowance_funded {
+            return Err(ErrorCode::MissingRemainingAccount.into());
+        }
+        let expected_key = Pubkey::create_program_address(
+            &[
+                PREFIX.as_bytes(),
+                wallet.key.as_ref(),
+                auction_house_key.as_ref(),
+                bid_args.token_mint.as_ref(),
+                &[bid_args.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::DerivedKeyInvalid)?;
+        assert_keys_equal(&expected_key, buyer_trade_state.key)?;
+
+        if bid_args.indexed {
+            let index = index.as_mut().ok_or(ErrorCode::MissingRemainingAccount)?;
+            index.remove_bid(
+                buyer_trade_state.key,
+                bid_args.locked_cost(),
+                &bid_args.payment_mint,
+            );
+        }
+        if bid_args.ledgered {
+            let ledger = ledger.as_mut().ok_or(ErrorCode::MissingRemainingAccount)?;
+            ledger.unlock(&bid_args.payment_mint, bid_args.locked_cost())?;
+        }
+        if let Some(limits) = limits.as_mut().filter(|_| bid_args.limited) {
+            limits.close_bid();
+        }
+        remove_from_mint_market(
+            remaining_accounts,
+            &auction_house_key,
+            &bid_args.token_mint,
+            &[buyer_trade_state.key],
+        )?;
+        close_account_anchor(buyer_trade_state, wallet, true)?;
+        cancelled += 1;
+    }
+
+    if let (Some(ai), Some(index)) = (open_bid_index, index) {
+        save_open_bid_index(ai, None, &index)?;
+    }
+    if let (Some(ai), Some(ledger)) = (escrow_ledger, ledger) {
+        save_escrow_ledger(ai, &ledger)?;
+    }
+    if let (Some(ai), Some(limits)) = (wallet_limits, limits) {
+        save_wallet_limits(ai, &limits)?;
+    }
+
+    msg!("{{\"cancelled\":{}}}", cancelled);
+    Ok(())
+}
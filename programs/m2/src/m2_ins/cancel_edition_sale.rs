@@ -0,0 +1,62 @@
+use {
+    crate::constants::*,
+    crate::states::*,
+    anchor_lang::prelude::*,
+    anchor_spl::token::{Mint, SetAuthority, Token, TokenAccount},
+    spl_token::instruction::AuthorityType,
+};
+
+#[derive(Accounts)]
+pub struct CancelEditionSale<'info> {
+    #[account(mut)]
+    wallet: Signer<'info>,
+    #[account(mut, address = edition_sale_state.token_account)]
+    token_account: Account<'info, TokenAccount>,
+    token_mint: Account<'info, Mint>,
+    /// CHECK: authority
+    authority: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=authority)]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+    #[account(
+        mut,
+        close=wallet,
+        seeds=[
+            PREFIX.as_bytes(),
+            EDITION_SALE.as_bytes(),
+            auction_house.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump=edition_sale_state.bump,
+        constraint = edition_sale_state.seller == wallet.key(),
+    )]
+    edition_sale_state: Box<Account<'info, EditionSaleState>>,
+    /// CHECK: program_as_signer
+    #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
+    program_as_signer: UncheckedAccount<'info>,
+    token_program: Program<'info, Token>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, CancelEditionSale<'info>>) -> Result<()> {
+    anchor_spl::token::set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SetAuthority {
+                account_or_mint: ctx.accounts.token_account.to_account_info(),
+                current_authority: ctx.accounts.program_as_signer.to_account_info(),
+            },
+            &[&[
+                PREFIX.as_bytes(),
+                SIGNER.as_bytes(),
+                &[ctx.bumps.program_as_signer],
+            ]],
+        ),
+        AuthorityType::AccountOwner,
+        Some(ctx.accounts.wallet.key()),
+    )?;
+
+    msg!(
+        "{{\"printed\":{}}}",
+        ctx.accounts.edition_sale_state.printed
+    );
+    Ok(())
+}
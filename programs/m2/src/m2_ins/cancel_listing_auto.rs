@@ -0,0 +1,83 @@
+use std::collections::BTreeSet;
+
+use {
+    crate::m2_ins::*, crate::mip1_ins::*, crate::ocp_ins::*, crate::states::*, crate::utils::*,
+    anchor_lang::prelude::*, anchor_lang::Bumps, anchor_spl::token::Mint,
+};
+
+#[derive(Accounts)]
+pub struct CancelListingAuto<'info> {
+    token_mint: Account<'info, Mint>,
+    /// CHECK: metadata, may be empty for tokens without metadata
+    #[account(
+    seeds = [
+        "metadata".as_bytes(),
+        mpl_token_metadata::ID.as_ref(),
+        token_mint.key().as_ref(),
+    ],
+    bump,
+    seeds::program = mpl_token_metadata::ID,
+    )]
+    metadata: UncheckedAccount<'info>,
+    // remaining accounts:
+    // 0.. accounts of cancel_sell, mip1_cancel_sell or ocp_cancel_sell for the token, in their
+    //     order and followed by their remaining accounts. the OCP mint state is in the
+    //     ocp_cancel_sell accounts, which is how OCP tokens are told apart.
+}
+
+// Routes to cancel_sell, mip1_cancel_sell or ocp_cancel_sell by the token's standard, so clients
+// don't have to pick one. The routed flow validates its accounts and signers as if it was called
+// directly.
+pub fn handle<'info>(ctx: Context<'_, '_, 'info, 'info, CancelListingAuto<'info>>) -> Result<()> {
+    let program_id = ctx.program_id;
+    let token_mint = ctx.accounts.token_mint.key();
+    let flow = get_listing_flow(&token_mint, &ctx.accounts.metadata, ctx.remaining_accounts)?;
+    msg!("cancel_listing_auto: {:?}", flow);
+
+    let mut accounts = ctx.remaining_accounts;
+    let mut reallocs = BTreeSet::new();
+    match flow {
+        ListingFlow::Legacy => {
+            let mut bumps = <CancelSell as Bumps>::Bumps::default();
+            let mut cancel = CancelSell::try_accounts(
+                program_id,
+                &mut accounts,
+                &[],
+                &mut bumps,
+                &mut reallocs,
+            )?;
+            let sell_args = SellArgs::from_account_info(cancel.seller_trade_state())?;
+            cancel_sell::handle(
+                Context::new(program_id, &mut cancel, accounts, bumps),
+                sell_args.buyer_price,
+                sell_args.token_size,
+                sell_args.expiry,
+            )?;
+            cancel.exit(program_id)
+        }
+        ListingFlow::Mip1 => {
+            let mut bumps = <MIP1CancelSell as Bumps>::Bumps::default();
+            let mut cancel = MIP1CancelSell::try_accounts(
+                program_id,
+                &mut accounts,
+                &[],
+                &mut bumps,
+                &mut reallocs,
+            )?;
+            handle_mip1_cancel_sell(Context::new(program_id, &mut cancel, accounts, bumps))?;
+            cancel.exit(program_id)
+        }
+        ListingFlow::Ocp => {
+            let mut bumps = <OCPCancelSell as Bumps>::Bumps::default();
+            let mut cancel = OCPCancelSell::try_accounts(
+                program_id,
+                &mut accounts,
+                &[],
+                &mut bumps,
+                &mut reallocs,
+            )?;
+            ocp_cancel_sell::handle(Context::new(program_id, &mut cancel, accounts, bumps))?;
+            cancel.exit(program_id)
+        }
+    }
+}
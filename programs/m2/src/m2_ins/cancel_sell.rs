@@ -1,3 +1,4 @@
+use mpl_token_metadata::accounts::Metadata;
 use solana_program::program::invoke;
 use spl_token::instruction::revoke;
 
@@ -13,7 +14,7 @@ use {
 
 #[derive(Accounts)]
 pub struct CancelSell<'info> {
-    /// CHECK: wallet must sign, otherwise delist authority (notary) must sign
+    /// CHECK: wallet must sign, otherwise delist authority (notary), its guardian or a seller_authority its co-owners approved the cancel for must sign
     #[account(mut)]
     wallet: UncheckedAccount<'info>,
     /// CHECK: notary is not dangerous because we don't read or write from this account
@@ -41,17 +42,71 @@ pub struct CancelSell<'info> {
     /// CHECK: seller_referral
     seller_referral: UncheckedAccount<'info>,
     token_program: Program<'info, Token>,
+    // remaining accounts, in this order, or in any order with a remaining accounts schema appended to the instruction data (see RemainingAccountsTag):
+    // notary_set, then notaries (signer) (optional) - auction house's NotarySet and threshold of its notaries, cosign in place of auction_house.notary, placed first
+    // program_config (optional) - the deployment's ProgramConfig, placed before the accounts below, its cancel authority may cancel too
+    // co_owner_approval (writable), then seller_authority (signer) (optional) - the token's CoOwnerApproval for wallet and an approving co-owner, cancels for a wallet that can't sign, placed before the accounts below
+    // guardian_account, then guardian (signer) (optional) - wallet's Guardian and its guardian, cancels for the wallet, placed before the accounts below
+    // cancel_authority_registry (required if auction_house.cancel_authority_registry and the notary cancels for the wallet)
+    // 0. program_as_signer (required if seller_state_expiry < 0)
+    // metadata (optional) - needed to remove the listing from its verified collection's ListingIndex
+    // listing_index (optional, writable) - seller's and verified collection's ListingIndex, the listing is removed from them
+    // program_as_signer, master edition and token metadata program (required if the listing is frozen) - the token account is thawed
+    // maker_rewards (required if the listing is enrolled in maker rewards, writable) - seller's MakerRewards, an enrolled listing accrues the time it was live until it expired
+    // mint_market (optional, writable) - token mint's MintMarket, the cancelled listing is dropped from it
+    // instructions sysvar (optional) - placed last if a remaining accounts schema is appended to the instruction data
 }
 
+impl<'info> CancelSell<'info> {
+    pub(crate) fn seller_trade_state(&self) -> &AccountInfo<'info> {
+        &self.seller_trade_state
+    }
+}
+
+/// Length of the cancel_sell instruction data up to the end of its args
+pub const CANCEL_SELL_ARGS_LEN: usize = 8 + 8 + 8 + 8;
+
+/// Where cancel_sell expects each group of remaining accounts
+pub const CANCEL_SELL_ORDER: [RemainingAccountsTag; 6] = [
+    RemainingAccountsTag::NotarySet,
+    RemainingAccountsTag::ProgramConfig,
+    RemainingAccountsTag::CoOwnerApproval,
+    RemainingAccountsTag::Guardian,
+    RemainingAccountsTag::ProgramAsSigner,
+    RemainingAccountsTag::Keyed,
+];
+
 pub fn handle<'info>(
     ctx: Context<'_, '_, '_, 'info, CancelSell<'info>>,
     _buyer_price: u64,
     token_size: u64,
     seller_state_expiry: i64,
 ) -> Result<()> {
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        ctx.remaining_accounts,
+        &ctx.accounts.auction_house,
+        &ctx.accounts.notary,
+    )?;
+    let (remaining_accounts, program_config) =
+        split_program_config_from_remaining_accounts(remaining_accounts)?;
     let wallet = &ctx.accounts.wallet;
     let token_account = &ctx.accounts.token_account;
     let token_mint = ctx.accounts.token_mint.as_ref() as &AccountInfo;
+    let (remaining_accounts, seller_authority) = split_co_owner_authority_from_remaining_accounts(
+        remaining_accounts,
+        wallet,
+        token_mint.key,
+    )?;
+    if let Some((co_owner_approval, seller_authority)) = seller_authority {
+        use_co_owner_approval(
+            co_owner_approval,
+            seller_authority,
+            &CoOwnerApproval::cancel_action_hash(),
+        )?;
+    }
+    let (remaining_accounts, guardian) =
+        split_guardian_from_remaining_accounts(remaining_accounts, wallet)?;
+    let seller_signed = wallet.is_signer || seller_authority.is_some() || guardian.is_some();
     let seller_trade_state = &ctx.accounts.seller_trade_state;
     let token_program = &ctx.accounts.token_program;
     let notary = &ctx.accounts.notary;
@@ -69,17 +124,30 @@ pub fn handle<'info>(
         return Err(ErrorCode::InvalidExpiry.into());
     }
 
-    // If wallet doesn't sign, notary must be CANCEL_AUTHORITY and also sign.
-    let cancel_authority_signed = notary.is_signer && *notary.key == CANCEL_AUTHORITY;
+    // If neither the wallet, its co-owners nor its guardian sign, notary must be a cancel authority
+    // and also sign.
+    let cancel_authority_signed = notary.is_signer
+        && is_cancel_authority(
+            notary.key,
+            &ctx.accounts.auction_house,
+            program_config.as_ref(),
+            remaining_accounts,
+        )?;
 
-    if !wallet.is_signer && !cancel_authority_signed {
+    if !seller_signed && !cancel_authority_signed {
         return Err(ErrorCode::NoValidSignerPresent.into());
     }
 
-    if !cancel_authority_signed {
+    // a seller can pull a fresh listing on their own, e.g. after a price typo. Repricing doesn't
+    // restart the grace period, or a seller could keep one open by relisting.
+    let seller_in_grace_period = seller_signed
+        && sell_args.created_at > 0
+        && Clock::get()?.unix_timestamp - sell_args.created_at
+            <= auction_house.delist_grace_seconds as i64;
+    if !cancel_authority_signed && !seller_in_grace_period {
         assert_valid_notary(
             auction_house,
-            notary,
+            &notary_cosign,
             100u8, // 100% enforced cosign
         )?;
     }
@@ -90,14 +158,15 @@ pub fn handle<'info>(
 
     // If seller_state_expiry is negative, we treat it that program_as_signer is the authority
     // For max compatibility, we derive the authority from the first remaining accounts.
-    if seller_state_expiry < 0 {
-        if ctx.remaining_accounts.is_empty() {
+    let escrowed = Expiry::from(seller_state_expiry).is_escrowed();
+    if escrowed {
+        if remaining_accounts.is_empty() {
             return Err(ErrorCode::InvalidRemainingAccountsWithoutProgramAsSigner.into());
         }
 
         let (program_as_signer, wallet_bump) =
             Pubkey::find_program_address(&[PREFIX.as_bytes(), SIGNER.as_bytes()], ctx.program_id);
-        if ctx.remaining_accounts[0].key() != program_as_signer {
+        if remaining_accounts[0].key() != program_as_signer {
             return Err(ErrorCode::InvalidRemainingAccountsWithoutProgramAsSigner.into());
         }
         let seeds = &[PREFIX.as_bytes(), SIGNER.as_bytes(), &[wallet_bump][..]];
@@ -106,7 +175,7 @@ pub fn handle<'info>(
                 token_program.to_account_info(),
                 SetAuthority {
                     account_or_mint: token_account.to_account_info(),
-                    current_authority: ctx.remaining_accounts[0].clone(),
+                    current_authority: remaining_accounts[0].clone(),
                 },
             )
             .with_signer(&[&seeds[..]]),
@@ -115,7 +184,35 @@ pub fn handle<'info>(
         )?;
     }
 
-    if seller_state_expiry >= 0 && token_account.owner == wallet.key() {
+    if sell_args.frozen {
+        let (program_as_signer, program_as_signer_bump) =
+            Pubkey::find_program_address(&[PREFIX.as_bytes(), SIGNER.as_bytes()], ctx.program_id);
+        let program_as_signer = find_remaining_account(remaining_accounts, &program_as_signer)
+            .ok_or(ErrorCode::InvalidRemainingAccountsWithoutProgramAsSigner)?;
+        let (edition, token_metadata_program) =
+            get_frozen_listing_accounts(remaining_accounts, token_mint.key)?;
+        set_delegated_account_frozen(
+            false,
+            program_as_signer,
+            token_account.as_ref(),
+            edition,
+            token_mint,
+            token_program,
+            token_metadata_program,
+            &[&[
+                PREFIX.as_bytes(),
+                SIGNER.as_bytes(),
+                &[program_as_signer_bump],
+            ]],
+        )?;
+    }
+
+    // co-owners and guardians can't revoke for the wallet, it revokes the delegate itself
+    if !escrowed
+        && token_account.owner == wallet.key()
+        && seller_authority.is_none()
+        && guardian.is_none()
+    {
         invoke(
             &revoke(
                 &token_program.key(),
@@ -131,7 +228,23 @@ pub fn handle<'info>(
             ],
         )?;
     }
-    close_account_anchor(seller_trade_state, wallet)?;
+    // the metadata is only needed to find the collection's listing index, so it's optional here
+    let metadata =
+        find_remaining_account(remaining_accounts, &Metadata::find_pda(token_mint.key).0);
+    release_listing(
+        remaining_accounts,
+        &auction_house.key(),
+        &get_listing_index_keys(wallet.key, metadata),
+        seller_trade_state,
+    )?;
+    accrue_maker_rewards(remaining_accounts, auction_house, &sell_args, 0)?;
+    remove_from_mint_market(
+        remaining_accounts,
+        &auction_house.key(),
+        token_mint.key,
+        &[seller_trade_state.key],
+    )?;
+    close_account_anchor(seller_trade_state, wallet, true)?;
 
     Ok(())
 }
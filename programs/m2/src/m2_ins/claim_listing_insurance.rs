@@ -0,0 +1,243 @@
+use {
+    crate::constants::*, crate::errors::ErrorCode, crate::states::*, crate::utils::*,
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ClaimListingInsurance<'info> {
+    notary: Signer<'info>,
+    /// CHECK: seller, checked against the insurance and gets its rent back
+    #[account(mut)]
+    seller: UncheckedAccount<'info>,
+    /// CHECK: buyer whose fill failed, receives the deposit
+    #[account(mut, constraint = buyer.key() != seller.key() @ ErrorCode::PublicKeysShouldBeUnique)]
+    buyer: UncheckedAccount<'info>,
+    /// CHECK: token_account the broken listing was delegated from, the listing's own, may be closed by now
+    token_account: UncheckedAccount<'info>,
+    /// CHECK: the seller's broken listing of the insured mint, checked in the handler
+    seller_trade_state: UncheckedAccount<'info>,
+    /// CHECK: the buyer's bid that would have filled the listing, checked in the handler
+    buyer_trade_state: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=notary)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        close=seller,
+        seeds=[
+            PREFIX.as_bytes(),
+            LISTING_INSURANCE.as_bytes(),
+            auction_house.key().as_ref(),
+            seller.key().as_ref(),
+            listing_insurance.token_mint.as_ref(),
+        ],
+        bump=listing_insurance.bump,
+        has_one=seller,
+    )]
+    listing_insurance: Box<Account<'info, ListingInsurance>>,
+    /// CHECK: program_as_signer
+    #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
+    program_as_signer: UncheckedAccount<'info>,
+}
+
+// The notary decides off chain that buyer's fill failed because the seller broke their listing.
+// The program checks what it can of that: the seller's listing of the insured mint is still open
+// and backed by token_account, which no longer backs it, and the buyer has a live bid that would
+// have filled it. The whole deposit goes to the buyer, the rent back to the seller.
+pub fn handle(ctx: Context<ClaimListingInsurance>) -> Result<()> {
+    let listing_insurance = &ctx.accounts.listing_insurance;
+    let token_account = &ctx.accounts.token_account;
+    let buyer = &ctx.accounts.buyer;
+
+    assert_claim_proof(
+        listing_insurance,
+        &SellArgs::from_account_info(&ctx.accounts.seller_trade_state)?,
+        &BidArgs::from_account_info(&ctx.accounts.buyer_trade_state)?,
+        buyer.key,
+        token_account.key,
+        Clock::get()?.unix_timestamp,
+    )?;
+    if !token_account.data_is_empty()
+        && unpack_token_account(token_account)?.owner != ctx.accounts.seller.key()
+    {
+        return Err(ErrorCode::IncorrectOwner.into());
+    }
+    if is_listing_token_live(
+        token_account,
+        &listing_insurance.token_mint,
+        ctx.accounts.program_as_signer.key,
+    )? {
+        return Err(ErrorCode::ListingNotBroken.into());
+    }
+
+    // the insurance is ours, so its lamports can be moved without the system program
+    let amount = listing_insurance.amount;
+    let listing_insurance_info = listing_insurance.to_account_info();
+    **listing_insurance_info.try_borrow_mut_lamports()? = listing_insurance_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+    **buyer.try_borrow_mut_lamports()? = buyer
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+
+    msg!(
+        "{{\"listing_insurance\":\"{}\",\"claimed\":{},\"buyer\":\"{}\"}}",
+        listing_insurance.key(),
+        amount,
+        buyer.key()
+    );
+    Ok(())
+}
+
+/// Checks that sell_args is the seller's unexpired listing of the insured mint, delegated from
+/// token_account, and bid_args the buyer's unexpired bid on the mint at or above the listing's
+/// price in the bid's payment mint, so a fill between them only failed on the token
+fn assert_claim_proof(
+    listing_insurance: &ListingInsurance,
+    sell_args: &SellArgs,
+    bid_args: &BidArgs,
+    buyer: &Pubkey,
+    token_account: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    if sell_args.auction_house_key != listing_insurance.auction_house_key
+        || sell_args.seller != listing_insurance.seller
+        || sell_args.token_mint != listing_insurance.token_mint
+        || sell_args.token_account != *token_account
+        || bid_args.auction_house_key != listing_insurance.auction_house_key
+        || bid_args.buyer != *buyer
+        || bid_args.token_mint != listing_insurance.token_mint
+    {
+        return Err(ErrorCode::TradeStateMismatch.into());
+    }
+    assert_not_expired(sell_args.expiry, now)?;
+    assert_not_expired(bid_args.expiry, now)?;
+    match sell_args.price_for(&bid_args.payment_mint) {
+        Some(price) if price <= bid_args.price_at(now) => Ok(()),
+        _ => Err(ErrorCode::TradeStateMismatch.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claim(buyer: Pubkey, token_account: Pubkey) -> (ListingInsurance, SellArgs, BidArgs) {
+        let (auction_house_key, seller, token_mint) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        (
+            ListingInsurance {
+                auction_house_key,
+                seller,
+                token_mint,
+                amount: 1_000_000,
+                ..Default::default()
+            },
+            SellArgs {
+                auction_house_key,
+                seller,
+                token_mint,
+                token_account,
+                token_size: 1,
+                buyer_price: 10_000_000,
+                expiry: 1_000,
+                ..Default::default()
+            },
+            BidArgs {
+                auction_house_key,
+                buyer,
+                token_mint,
+                token_size: 1,
+                buyer_price: 10_000_000,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn claim_needs_the_listing_and_a_bid_that_would_fill_it() {
+        let (buyer, token_account) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (insurance, sell_args, bid_args) = claim(buyer, token_account);
+        assert!(assert_claim_proof(
+            &insurance,
+            &sell_args,
+            &bid_args,
+            &buyer,
+            &token_account,
+            10
+        )
+        .is_ok());
+
+        // another token account of the seller doesn't show the listing is broken
+        let other_token_account = Pubkey::new_unique();
+        assert!(assert_claim_proof(
+            &insurance,
+            &sell_args,
+            &bid_args,
+            &buyer,
+            &other_token_account,
+            10
+        )
+        .is_err());
+
+        // another seller's listing, or another buyer's bid, isn't proof
+        let other_listing = SellArgs {
+            seller: Pubkey::new_unique(),
+            ..sell_args.clone()
+        };
+        assert!(assert_claim_proof(
+            &insurance,
+            &other_listing,
+            &bid_args,
+            &buyer,
+            &token_account,
+            10
+        )
+        .is_err());
+        let other_buyer = Pubkey::new_unique();
+        assert!(assert_claim_proof(
+            &insurance,
+            &sell_args,
+            &bid_args,
+            &other_buyer,
+            &token_account,
+            10
+        )
+        .is_err());
+
+        // a bid below the listing's price wouldn't have filled it
+        let low_bid = BidArgs {
+            buyer_price: 9_999_999,
+            ..bid_args.clone()
+        };
+        assert!(
+            assert_claim_proof(&insurance, &sell_args, &low_bid, &buyer, &token_account, 10)
+                .is_err()
+        );
+
+        // nor a bid in a payment mint the listing doesn't take
+        let spl_bid = BidArgs {
+            payment_mint: Pubkey::new_unique(),
+            ..bid_args.clone()
+        };
+        assert!(
+            assert_claim_proof(&insurance, &sell_args, &spl_bid, &buyer, &token_account, 10)
+                .is_err()
+        );
+
+        // an expired listing couldn't have been filled anyway
+        assert!(assert_claim_proof(
+            &insurance,
+            &sell_args,
+            &bid_args,
+            &buyer,
+            &token_account,
+            1_001
+        )
+        .is_err());
+    }
+}
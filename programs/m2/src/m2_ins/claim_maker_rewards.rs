@@ -0,0 +1,79 @@
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    anchor_lang::{
+        prelude::*,
+        solana_program::{program::invoke_signed, system_instruction},
+    },
+};
+
+// The maker rewards vault is funded by the operator with plain SOL transfers to the PDA, like the
+// rent vault. Claims pay out what the vault can cover and leave the rest unclaimed.
+#[derive(Accounts)]
+pub struct ClaimMakerRewards<'info> {
+    #[account(mut)]
+    wallet: Signer<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            MAKER_REWARDS.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        bump=maker_rewards.bump,
+        has_one=wallet,
+    )]
+    maker_rewards: Box<Account<'info, MakerRewards>>,
+    /// CHECK: maker_rewards_vault
+    #[account(
+      mut,
+      seeds=[PREFIX.as_bytes(), MAKER_REWARDS_VAULT.as_bytes(), auction_house.key().as_ref()],
+      bump,
+    )]
+    maker_rewards_vault: UncheckedAccount<'info>,
+    system_program: Program<'info, System>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, ClaimMakerRewards<'info>>) -> Result<()> {
+    let maker_rewards_vault = &ctx.accounts.maker_rewards_vault;
+    let wallet = &ctx.accounts.wallet;
+    let maker_rewards = &mut ctx.accounts.maker_rewards;
+    let ah_key = ctx.accounts.auction_house.key();
+
+    let amount = maker_rewards.unclaimed.min(maker_rewards_vault.lamports());
+    if amount == 0 {
+        return Err(ErrorCode::NoMakerRewardsToClaim.into());
+    }
+
+    invoke_signed(
+        &system_instruction::transfer(&maker_rewards_vault.key(), &wallet.key(), amount),
+        &[
+            maker_rewards_vault.to_account_info(),
+            wallet.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[&[
+            PREFIX.as_bytes(),
+            MAKER_REWARDS_VAULT.as_bytes(),
+            ah_key.as_ref(),
+            &[ctx.bumps.maker_rewards_vault],
+        ]],
+    )?;
+
+    maker_rewards.unclaimed -= amount;
+    maker_rewards.claimed = maker_rewards
+        .claimed
+        .checked_add(amount)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+
+    msg!(
+        "{{\"maker_rewards_claimed\":{},\"unclaimed\":{}}}",
+        amount,
+        maker_rewards.unclaimed
+    );
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use {
+    crate::constants::*, crate::states::*, crate::utils::*, anchor_lang::prelude::*,
+    anchor_lang::Discriminator,
+};
+
+#[derive(Accounts)]
+pub struct CloseBidGroup<'info> {
+    #[account(mut)]
+    wallet: Signer<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            BID_GROUP.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+            &bid_group.id.to_le_bytes(),
+        ],
+        bump=bid_group.bump,
+        close=wallet,
+    )]
+    bid_group: Box<Account<'info, BidGroup>>,
+}
+
+// Closes the wallet's bid group. Its open bids passed as writable remaining accounts leave the
+// group and fill on their own again, the others still point at the closed group and can't fill
+// until they're cancelled.
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, CloseBidGroup<'info>>) -> Result<()> {
+    let bid_group = &ctx.accounts.bid_group;
+    let bid_group_key = bid_group.key();
+
+    for bts in ctx.remaining_accounts {
+        if !bid_group.bids.contains(bts.key)
+            || bts.owner != &crate::ID
+            || bts.data_is_empty()
+            || get_discriminator(bts)? != BuyerTradeStateV3::discriminator()
+        {
+            continue;
+        }
+        let mut bid = BuyerTradeStateV3::try_deserialize(&mut &bts.try_borrow_data()?[..])?;
+        if bid.bid_group == bid_group_key {
+            bid.bid_group = Pubkey::default();
+            bid.try_serialize(&mut &mut bts.try_borrow_mut_data()?[..])?;
+        }
+    }
+
+    msg!(
+        "{{\"closed_bid_group\":\"{}\",\"fills\":{}}}",
+        bid_group_key,
+        bid_group.fills
+    );
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct CloseNotarySet<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(
+        seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()],
+        bump=auction_house.bump,
+        has_one=authority,
+    )]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            NOTARY_SET.as_bytes(),
+            auction_house.key().as_ref(),
+        ],
+        bump=notary_set.bump,
+        close=authority,
+    )]
+    notary_set: Box<Account<'info, NotarySet>>,
+}
+
+// Closes the auction house's notary set, only auction_house.notary can cosign again
+pub fn handle(_ctx: Context<CloseNotarySet>) -> Result<()> {
+    Ok(())
+}
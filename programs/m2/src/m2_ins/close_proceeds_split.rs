@@ -0,0 +1,23 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct CloseProceedsSplit<'info> {
+    #[account(mut)]
+    seller: Signer<'info>,
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            PROCEEDS_SPLIT.as_bytes(),
+            seller.key().as_ref(),
+        ],
+        bump=proceeds_split.bump,
+        close=seller,
+    )]
+    proceeds_split: Box<Account<'info, ProceedsSplit>>,
+}
+
+// Closes the seller's proceeds split, their sales pay them in full again
+pub fn handle(_ctx: Context<CloseProceedsSplit>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use mpl_token_metadata::accounts::Metadata;
+
+use {crate::constants::*, crate::errors::ErrorCode, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct CloseRoyaltyOverride<'info> {
+    #[account(mut)]
+    update_authority: Signer<'info>,
+    /// CHECK: token_mint, only used to derive the metadata and royalty_override
+    token_mint: UncheckedAccount<'info>,
+    /// CHECK: metadata
+    #[account(
+    seeds = [
+        "metadata".as_bytes(),
+        mpl_token_metadata::ID.as_ref(),
+        token_mint.key().as_ref(),
+    ],
+    bump,
+    seeds::program = mpl_token_metadata::ID,
+    )]
+    metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            ROYALTY_OVERRIDE.as_bytes(),
+            token_mint.key().as_ref(),
+        ],
+        bump=royalty_override.bump,
+        close=update_authority,
+    )]
+    royalty_override: Box<Account<'info, RoyaltyOverride>>,
+}
+
+// Closes the mint's royalty override, sales pay the metadata creators again
+pub fn handle(ctx: Context<CloseRoyaltyOverride>) -> Result<()> {
+    let metadata = Metadata::safe_deserialize(&ctx.accounts.metadata.data.borrow())?;
+    if metadata.update_authority != ctx.accounts.update_authority.key() {
+        return Err(ErrorCode::PublicKeyMismatch.into());
+    }
+    Ok(())
+}
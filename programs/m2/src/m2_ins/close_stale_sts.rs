@@ -0,0 +1,91 @@
+use mpl_token_metadata::accounts::Metadata;
+
+use {
+    crate::constants::*, crate::errors::ErrorCode, crate::states::*, crate::utils::*,
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct CloseStaleSts<'info> {
+    /// CHECK: seller, checked against the trade state and receives its rent
+    #[account(mut)]
+    seller: UncheckedAccount<'info>,
+    /// CHECK: token_account the stale trade state was made for, may be closed by now
+    token_account: UncheckedAccount<'info>,
+    /// CHECK: token_mint, only used to derive the trade state
+    token_mint: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    /// CHECK: seeds check and check sell_args
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            seller.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    seller_trade_state: AccountInfo<'info>,
+    /// CHECK: program_as_signer
+    #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
+    program_as_signer: UncheckedAccount<'info>,
+    // remaining accounts:
+    // metadata (optional) - needed to remove the listing from its verified collection's ListingIndex
+    // listing_index (optional, writable) - seller's and verified collection's ListingIndex, the listing is removed from them
+    // mint_market (optional, writable) - token mint's MintMarket, the closed listing is dropped from it
+}
+
+// Closes a seller trade state whose token is gone: neither escrowed in token_account nor
+// delegated to program_as_signer from it, e.g. the half of a mip1_sell migration pair that was
+// left behind. Nothing can fill such a listing, so anyone can close it, the rent goes back to
+// the seller.
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, CloseStaleSts<'info>>) -> Result<()> {
+    let seller = &ctx.accounts.seller;
+    let token_account = &ctx.accounts.token_account;
+    let token_mint = &ctx.accounts.token_mint;
+    let auction_house = &ctx.accounts.auction_house;
+    let seller_trade_state = &ctx.accounts.seller_trade_state;
+    let program_as_signer = &ctx.accounts.program_as_signer;
+
+    if seller_trade_state.data_is_empty() {
+        return Err(ErrorCode::EmptyTradeState.into());
+    }
+    let sell_args = SellArgs::from_account_info(seller_trade_state)?;
+    if sell_args.auction_house_key != auction_house.key() {
+        return Err(ErrorCode::WrongAuctionHouseForState.into());
+    }
+    if sell_args.seller != seller.key()
+        || sell_args.token_account != token_account.key()
+        || sell_args.token_mint != token_mint.key()
+    {
+        return Err(ErrorCode::TradeStateMismatch.into());
+    }
+
+    if is_listing_token_live(token_account, token_mint.key, program_as_signer.key)? {
+        return Err(ErrorCode::TokenAlreadyEscrowed.into());
+    }
+
+    let metadata = find_remaining_account(
+        ctx.remaining_accounts,
+        &Metadata::find_pda(&token_mint.key()).0,
+    );
+    release_listing(
+        ctx.remaining_accounts,
+        &auction_house.key(),
+        &get_listing_index_keys(seller.key, metadata),
+        seller_trade_state,
+    )?;
+    remove_from_mint_market(
+        ctx.remaining_accounts,
+        &auction_house.key(),
+        token_mint.key,
+        &[seller_trade_state.key],
+    )?;
+    close_account_anchor(seller_trade_state, seller, true)?;
+
+    msg!("{{\"closed_stale_sts\":\"{}\"}}", seller_trade_state.key());
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use {
+    crate::constants::*, crate::errors::ErrorCode, crate::states::*, crate::utils::*,
+    anchor_lang::prelude::*, anchor_lang::Discriminator,
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CreateBidGroup<'info> {
+    #[account(mut)]
+    wallet: Signer<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init,
+        payer=wallet,
+        seeds=[
+            PREFIX.as_bytes(),
+            BID_GROUP.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+            &id.to_le_bytes(),
+        ],
+        space=BidGroup::LEN,
+        bump,
+    )]
+    bid_group: Box<Account<'info, BidGroup>>,
+    system_program: Program<'info, System>,
+}
+
+// Groups the wallet's bids passed as writable remaining accounts, in its order of priority, so at
+// most max_fills of them fill. A bid can be in one group only.
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateBidGroup<'info>>,
+    id: u64,
+    max_fills: u8,
+) -> Result<()> {
+    let wallet = &ctx.accounts.wallet;
+    let auction_house_key = ctx.accounts.auction_house.key();
+    let bid_group = &mut ctx.accounts.bid_group;
+    let bid_group_key = bid_group.key();
+
+    let bids: Vec<Pubkey> = ctx.remaining_accounts.iter().map(|ai| ai.key()).collect();
+    bid_group.auction_house_key = auction_house_key;
+    bid_group.buyer = wallet.key();
+    bid_group.id = id;
+    bid_group.bump = ctx.bumps.bid_group;
+    bid_group.set_bids(&bids, max_fills)?;
+
+    for bts in ctx.remaining_accounts {
+        assert_not_zombie_trade_state(bts)?;
+        assert_owned_by(bts, &crate::ID)?;
+        if get_discriminator(bts)? != BuyerTradeStateV3::discriminator() {
+            return Err(ErrorCode::InvalidDiscriminator.into());
+        }
+        let mut bid = BuyerTradeStateV3::try_deserialize(&mut &bts.try_borrow_data()?[..])?;
+        if bid.auction_house_key != auction_house_key
+            || bid.buyer != wallet.key()
+            || bid.bid_group != Pubkey::default()
+        {
+            return Err(ErrorCode::InvalidBidGroup.into());
+        }
+        bid.bid_group = bid_group_key;
+        bid.try_serialize(&mut &mut bts.try_borrow_mut_data()?[..])?;
+    }
+
+    msg!(
+        "{{\"bid_group\":\"{}\",\"bids\":{},\"max_fills\":{}}}",
+        bid_group_key,
+        bids.len(),
+        max_fills
+    );
+    Ok(())
+}
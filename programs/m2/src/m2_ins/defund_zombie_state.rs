@@ -0,0 +1,97 @@
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    crate::utils::*,
+    anchor_lang::{
+        prelude::*,
+        solana_program::{program::invoke_signed, system_instruction},
+    },
+};
+
+#[derive(Accounts)]
+pub struct DefundZombieState<'info> {
+    /// CHECK: wallet that owned the trade state, receives its lamports
+    #[account(mut)]
+    wallet: UncheckedAccount<'info>,
+    /// CHECK: token_mint, only used to derive the trade state
+    token_mint: UncheckedAccount<'info>,
+    /// CHECK: token_account a listing was made for, only passed for seller trade states
+    token_account: Option<UncheckedAccount<'info>>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    /// CHECK: derived from the accounts above in the handler, and must be a zombie
+    #[account(mut)]
+    trade_state: UncheckedAccount<'info>,
+    /// CHECK: program_as_signer
+    #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
+    program_as_signer: UncheckedAccount<'info>,
+    system_program: Program<'info, System>,
+}
+
+// Permissionless: drains a trade state that was closed but got lamports sent back to it, see
+// is_zombie_trade_state, to the wallet that owned it. A buyer trade state is derived without
+// token_account, a seller one with it. A seller trade state whose token is still escrowed or
+// delegated to the program is a broken listing rather than a zombie, recover_seller_trade_state
+// hands its token back.
+pub fn handle(ctx: Context<DefundZombieState>) -> Result<()> {
+    let wallet = &ctx.accounts.wallet;
+    let token_mint = &ctx.accounts.token_mint;
+    let auction_house_key = ctx.accounts.auction_house.key();
+    let trade_state = &ctx.accounts.trade_state;
+    let program_as_signer = &ctx.accounts.program_as_signer;
+
+    let wallet_key = wallet.key();
+    let token_mint_key = token_mint.key();
+    let token_account_key = ctx.accounts.token_account.as_ref().map(|ai| ai.key());
+    let mut seeds: Vec<&[u8]> = vec![
+        PREFIX.as_bytes(),
+        wallet_key.as_ref(),
+        auction_house_key.as_ref(),
+    ];
+    if let Some(token_account_key) = token_account_key.as_ref() {
+        seeds.push(token_account_key.as_ref());
+    }
+    seeds.push(token_mint_key.as_ref());
+    let (trade_state_key, bump) = Pubkey::find_program_address(&seeds, &crate::ID);
+    assert_keys_equal(&trade_state_key, trade_state.key)?;
+
+    if !is_zombie_trade_state(trade_state)? {
+        return Err(ErrorCode::NotZombieTradeState.into());
+    }
+    let lamports = trade_state.lamports();
+
+    if trade_state.data_is_empty() {
+        // closed with realloc_to_zero, only the trade state's seeds can move its lamports
+        let bump = [bump];
+        seeds.push(&bump);
+        invoke_signed(
+            &system_instruction::transfer(trade_state.key, wallet.key, lamports),
+            &[
+                trade_state.to_account_info(),
+                wallet.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&seeds],
+        )?;
+    } else {
+        if let Some(token_account) = ctx.accounts.token_account.as_ref() {
+            if !token_account.data_is_empty() {
+                let account = unpack_token_account(token_account)?;
+                if account.owner == program_as_signer.key()
+                    || account.delegate == Some(program_as_signer.key()).into()
+                {
+                    return Err(ErrorCode::HalfMigratedTradeState.into());
+                }
+            }
+        }
+        close_account_anchor(trade_state, wallet, true)?;
+    }
+
+    msg!(
+        "{{\"defunded_zombie_state\":\"{}\",\"lamports\":{}}}",
+        trade_state.key(),
+        lamports
+    );
+    Ok(())
+}
@@ -3,14 +3,21 @@ use std::cmp;
 
 use crate::{
     index_ra,
-    utils::{split_payer_from_remaining_accounts, DestinationSpecifier},
+    utils::{
+        get_balance_from_token_account, save_escrow_ledger,
+        split_escrow_ledger_from_remaining_accounts, split_payer_from_remaining_accounts,
+        DestinationSpecifier, RemainingAccountsTag,
+    },
 };
 
 use {
     crate::constants::*,
     crate::errors::ErrorCode,
     crate::states::*,
-    crate::utils::{assert_keys_equal, assert_payment_mint, transfer_token},
+    crate::utils::{
+        assert_keys_equal, assert_payment_mint, split_program_config_from_remaining_accounts,
+        transfer_token,
+    },
     anchor_lang::{prelude::*, solana_program::system_instruction},
 };
 
@@ -29,19 +36,35 @@ pub struct Deposit<'info> {
     #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=authority)]
     auction_house: Account<'info, AuctionHouse>,
     system_program: Program<'info, System>,
-    // remaining accounts:
+    // remaining accounts, in this order, or in any order with a remaining accounts schema appended to the instruction data (see RemainingAccountsTag):
+    // program_config (optional) - the deployment's ProgramConfig, placed before the accounts below, its payment mints are accepted too
     // 0. payment_mint (optional) - if included, must be a valid token mint
     // 1. deposit_source_token_account (optional)
     // 2. deposit_destination_token_account (optional)
     // 3. token_program (optional)
     // 4. associated_token_program (optional)
     // ...
+    // -2. escrow_ledger (optional, writable) - wallet's EscrowLedger, the deposit is recorded as free in it
     // -1. payer (optional) - but either payer or wallet must be signer
+    // instructions sysvar (optional) - placed last if a remaining accounts schema is appended to the instruction data
 }
 
+/// Length of the deposit instruction data up to the end of its args
+pub const DEPOSIT_ARGS_LEN: usize = 8 + 1 + 8;
+
+/// Where deposit expects each group of remaining accounts
+pub const DEPOSIT_ORDER: [RemainingAccountsTag; 4] = [
+    RemainingAccountsTag::ProgramConfig,
+    RemainingAccountsTag::Payment,
+    RemainingAccountsTag::EscrowLedger,
+    RemainingAccountsTag::Payer,
+];
+
 pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, Deposit<'info>>, amount: u64) -> Result<()> {
+    let (remaining_accounts, program_config) =
+        split_program_config_from_remaining_accounts(ctx.remaining_accounts)?;
     let (remaining_accounts, possible_payer) =
-        split_payer_from_remaining_accounts(ctx.remaining_accounts);
+        split_payer_from_remaining_accounts(remaining_accounts);
     if !ctx.accounts.wallet.is_signer && possible_payer.is_none() {
         return Err(ErrorCode::NoValidSignerPresent.into());
     }
@@ -50,10 +73,15 @@ pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, Deposit<'info>>, amount: u6
     } else {
         &ctx.accounts.wallet
     };
+    let (remaining_accounts, escrow_ledger) = split_escrow_ledger_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        ctx.accounts.wallet.key,
+    )?;
     let escrow_payment_account = &ctx.accounts.escrow_payment_account;
     let system_program = &ctx.accounts.system_program;
 
-    if remaining_accounts.is_empty() {
+    let (payment_mint, escrow_balance) = if remaining_accounts.is_empty() {
         invoke(
             &system_instruction::transfer(
                 payer.key,
@@ -66,9 +94,10 @@ pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, Deposit<'info>>, amount: u6
                 system_program.to_account_info(),
             ],
         )?;
+        (Pubkey::default(), escrow_payment_account.lamports())
     } else {
         assert_keys_equal(index_ra!(remaining_accounts, 3).key, &spl_token::id())?;
-        assert_payment_mint(index_ra!(remaining_accounts, 0))?;
+        assert_payment_mint(index_ra!(remaining_accounts, 0), program_config.as_ref())?;
         transfer_token(
             &amount,
             payer,
@@ -84,7 +113,15 @@ pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, Deposit<'info>>, amount: u6
             None,
             &[],
         )?;
-    }
+        (
+            index_ra!(remaining_accounts, 0).key(),
+            get_balance_from_token_account(index_ra!(remaining_accounts, 2))?,
+        )
+    };
 
+    if let Some((escrow_ledger, mut ledger)) = escrow_ledger {
+        ledger.sync(&payment_mint, escrow_balance)?;
+        save_escrow_ledger(escrow_ledger, &ledger)?;
+    }
     Ok(())
 }
@@ -0,0 +1,62 @@
+use solana_program::{program::invoke, system_instruction};
+
+use {crate::constants::*, crate::errors::ErrorCode, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct DepositListingInsurance<'info> {
+    #[account(mut)]
+    seller: Signer<'info>,
+    /// CHECK: token_mint, only used to derive the insurance
+    token_mint: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init_if_needed,
+        payer=seller,
+        seeds=[
+            PREFIX.as_bytes(),
+            LISTING_INSURANCE.as_bytes(),
+            auction_house.key().as_ref(),
+            seller.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        space=ListingInsurance::LEN,
+        bump,
+    )]
+    listing_insurance: Box<Account<'info, ListingInsurance>>,
+    system_program: Program<'info, System>,
+}
+
+// Adds amount to the seller's insurance for their delegate listings of the token. Depositing
+// also stops a withdrawal the seller started.
+pub fn handle(ctx: Context<DepositListingInsurance>, amount: u64) -> Result<()> {
+    let seller = &ctx.accounts.seller;
+    let listing_insurance = &mut ctx.accounts.listing_insurance;
+    listing_insurance.auction_house_key = ctx.accounts.auction_house.key();
+    listing_insurance.seller = seller.key();
+    listing_insurance.token_mint = ctx.accounts.token_mint.key();
+    listing_insurance.bump = ctx.bumps.listing_insurance;
+    listing_insurance.withdrawable_at = 0;
+
+    if amount > 0 {
+        invoke(
+            &system_instruction::transfer(&seller.key(), &listing_insurance.key(), amount),
+            &[
+                seller.to_account_info(),
+                listing_insurance.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+        listing_insurance.amount = listing_insurance
+            .amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+    }
+
+    msg!(
+        "{{\"listing_insurance\":\"{}\",\"amount\":{}}}",
+        listing_insurance.key(),
+        listing_insurance.amount
+    );
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use solana_program::program::invoke;
+use std::cmp;
+
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    anchor_lang::{prelude::*, solana_program::system_instruction},
+};
+
+#[derive(Accounts)]
+#[instruction(amount: u64, reference: [u8; 32])]
+pub struct DepositWithReference<'info> {
+    /// CHECK: seeds check, this is the beneficiary of the deposit
+    wallet: UncheckedAccount<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    /// CHECK: notary is not dangerous because we don't read or write from this account
+    notary: UncheckedAccount<'info>,
+    /// CHECK: escrow_payment_account
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), wallet.key().as_ref()], bump)]
+    escrow_payment_account: UncheckedAccount<'info>,
+    /// CHECK: authority
+    authority: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=authority)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init,
+        payer=payer,
+        seeds=[
+            PREFIX.as_bytes(),
+            DEPOSIT_RECEIPT.as_bytes(),
+            auction_house.key().as_ref(),
+            reference.as_ref(),
+        ],
+        space=DepositReceipt::LEN,
+        bump,
+    )]
+    deposit_receipt: Account<'info, DepositReceipt>,
+    system_program: Program<'info, System>,
+}
+
+// SOL deposit made on the wallet's behalf by a payer, typically a fiat on-ramp, that records
+// its external payment reference in a DepositReceipt. The receipt is keyed by the reference,
+// so one external payment can only ever fund one deposit.
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, DepositWithReference<'info>>,
+    amount: u64,
+    reference: [u8; 32],
+) -> Result<()> {
+    let payer = &ctx.accounts.payer;
+    let escrow_payment_account = &ctx.accounts.escrow_payment_account;
+    let system_program = &ctx.accounts.system_program;
+    let deposit_receipt = &mut ctx.accounts.deposit_receipt;
+
+    if amount == 0 || amount > MAX_PRICE {
+        return Err(ErrorCode::InvalidPrice.into());
+    }
+
+    let amount = cmp::max(amount, Rent::get()?.minimum_balance(0));
+    invoke(
+        &system_instruction::transfer(payer.key, &escrow_payment_account.key(), amount),
+        &[
+            escrow_payment_account.to_account_info(),
+            payer.to_account_info(),
+            system_program.to_account_info(),
+        ],
+    )?;
+
+    deposit_receipt.set_inner(DepositReceipt {
+        auction_house_key: ctx.accounts.auction_house.key(),
+        wallet: ctx.accounts.wallet.key(),
+        payer: payer.key(),
+        reference,
+        amount,
+        created_at: Clock::get()?.unix_timestamp,
+        bump: ctx.bumps.deposit_receipt,
+    });
+
+    msg!(
+        "{{\"deposit_receipt\":\"{}\",\"amount\":{}}}",
+        deposit_receipt.key(),
+        amount
+    );
+    Ok(())
+}
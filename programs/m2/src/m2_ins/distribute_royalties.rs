@@ -0,0 +1,61 @@
+use {
+    crate::constants::*, crate::errors::ErrorCode, crate::states::*, crate::utils::*,
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct DistributeRoyalties<'info> {
+    /// CHECK: token_mint, only used to derive pending_royalties
+    token_mint: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            PENDING_ROYALTIES.as_bytes(),
+            auction_house.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump=pending_royalties.bump,
+    )]
+    pending_royalties: Box<Account<'info, PendingRoyalties>>,
+}
+
+// Permissionless: pays the pending royalties of the creators passed as writable remaining
+// accounts, as many as fit in the transaction. A creator the payout wouldn't make rent exempt
+// keeps its claim. Once every claim is paid, the account is closed to its rent_payer if that is
+// passed in remaining accounts too.
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, DistributeRoyalties<'info>>) -> Result<()> {
+    let pending_royalties = &mut ctx.accounts.pending_royalties;
+    let pending_royalties_info = pending_royalties.to_account_info();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+
+    for creator in ctx.remaining_accounts {
+        let amount = pending_royalties.amount_owed(creator.key);
+        if amount == 0 || amount.saturating_add(creator.lamports()) < rent_exempt_minimum {
+            continue;
+        }
+        pending_royalties.take_claim(creator.key);
+        let pending_lamports = pending_royalties_info
+            .lamports()
+            .checked_sub(amount)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        let creator_lamports = creator
+            .lamports()
+            .checked_add(amount)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        **pending_royalties_info.try_borrow_mut_lamports()? = pending_lamports;
+        **creator.try_borrow_mut_lamports()? = creator_lamports;
+        log_royalty_paid(creator.key, &Pubkey::default(), amount);
+    }
+
+    if pending_royalties.is_empty() {
+        if let Some(rent_payer) =
+            find_remaining_account(ctx.remaining_accounts, &pending_royalties.rent_payer)
+        {
+            pending_royalties.close(rent_payer.clone())?;
+        }
+    }
+    Ok(())
+}
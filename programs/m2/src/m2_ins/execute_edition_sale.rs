@@ -0,0 +1,223 @@
+use mpl_token_metadata::{
+    accounts::{MasterEdition, Metadata},
+    instructions::MintNewEditionFromMasterEditionViaTokenBuilder,
+    types::MintNewEditionFromMasterEditionViaTokenArgs,
+};
+use solana_program::{
+    program::{invoke, invoke_signed},
+    system_instruction,
+};
+
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    crate::utils::{find_wallet_limits, save_wallet_limits},
+    anchor_lang::prelude::*,
+    anchor_spl::token::{Mint, Token, TokenAccount},
+};
+
+#[derive(Accounts)]
+pub struct ExecuteEditionSale<'info> {
+    #[account(mut)]
+    buyer: Signer<'info>,
+    /// CHECK: seller, checked against the edition sale state
+    #[account(mut)]
+    seller: UncheckedAccount<'info>,
+    #[account(address = edition_sale_state.token_account)]
+    token_account: Box<Account<'info, TokenAccount>>,
+    token_mint: Box<Account<'info, Mint>>,
+    /// CHECK: metadata of the master edition
+    #[account(
+    seeds = [
+        "metadata".as_bytes(),
+        mpl_token_metadata::ID.as_ref(),
+        token_mint.key().as_ref(),
+    ],
+    bump,
+    seeds::program = mpl_token_metadata::ID,
+    )]
+    metadata: UncheckedAccount<'info>,
+    /// CHECK: master_edition, checked in cpi
+    #[account(
+        mut,
+        seeds = [
+            "metadata".as_bytes(),
+            mpl_token_metadata::ID.as_ref(),
+            token_mint.key().as_ref(),
+            "edition".as_bytes(),
+        ],
+        bump,
+        seeds::program = mpl_token_metadata::ID,
+    )]
+    master_edition: UncheckedAccount<'info>,
+    /// CHECK: edition marker pda, checked in cpi
+    #[account(mut)]
+    edition_marker: UncheckedAccount<'info>,
+    /// CHECK: new mint, initialized by the buyer with one token minted to the buyer, checked in cpi
+    #[account(mut)]
+    new_mint: UncheckedAccount<'info>,
+    /// CHECK: new metadata, checked in cpi
+    #[account(mut)]
+    new_metadata: UncheckedAccount<'info>,
+    /// CHECK: new edition, checked in cpi
+    #[account(mut)]
+    new_edition: UncheckedAccount<'info>,
+    /// CHECK: update authority of the master edition metadata, checked in the handler
+    new_metadata_update_authority: UncheckedAccount<'info>,
+    /// CHECK: authority
+    authority: UncheckedAccount<'info>,
+    #[account(
+        seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()],
+        bump=auction_house.bump,
+        has_one=authority,
+        has_one=auction_house_treasury,
+    )]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+    /// CHECK: auction_house_treasury
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), TREASURY.as_bytes()], bump=auction_house.treasury_bump)]
+    auction_house_treasury: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            EDITION_SALE.as_bytes(),
+            auction_house.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump=edition_sale_state.bump,
+        has_one=seller,
+    )]
+    edition_sale_state: Box<Account<'info, EditionSaleState>>,
+    /// CHECK: program_as_signer
+    #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
+    program_as_signer: UncheckedAccount<'info>,
+    /// CHECK: token metadata program
+    #[account(address = mpl_token_metadata::ID)]
+    token_metadata_program: UncheckedAccount<'info>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+    // remaining accounts:
+    // wallet_limits (required if auction_house.enforce_wallet_limits, writable) - buyer's WalletLimits, its uninitialized address if there is none, the print adds to the day's buy volume
+}
+
+// Sells one print: the buyer pays the listed price to the seller plus the auction house's fee to
+// the treasury, then a new edition is printed from the master edition onto the buyer's new mint.
+// Prints are primary sales of the lister, so no royalties are paid.
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteEditionSale<'info>>,
+    price: u64,
+) -> Result<()> {
+    let buyer = &ctx.accounts.buyer;
+    let seller = &ctx.accounts.seller;
+    let system_program = &ctx.accounts.system_program;
+    let program_as_signer = &ctx.accounts.program_as_signer;
+    let edition_sale_state = &mut ctx.accounts.edition_sale_state;
+
+    if edition_sale_state.printed >= edition_sale_state.max_prints {
+        return Err(ErrorCode::EditionSaleSoldOut.into());
+    }
+    // the seller can reprice at any time, so the buyer states the price they agreed to
+    if price != edition_sale_state.price {
+        return Err(ErrorCode::InvalidPrice.into());
+    }
+    let metadata = Metadata::safe_deserialize(&ctx.accounts.metadata.data.borrow())?;
+    if metadata.update_authority != ctx.accounts.new_metadata_update_authority.key() {
+        return Err(ErrorCode::PublicKeyMismatch.into());
+    }
+    let master_edition = MasterEdition::from_bytes(&ctx.accounts.master_edition.data.borrow())
+        .map_err(|_| ErrorCode::InvalidMasterEdition)?;
+    let edition = master_edition
+        .supply
+        .checked_add(1)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+
+    if let Some((wallet_limits, mut limits)) = find_wallet_limits(
+        ctx.remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &ctx.accounts.auction_house,
+        buyer.key,
+    )? {
+        limits.record_buy(price, Clock::get()?.unix_timestamp)?;
+        save_wallet_limits(wallet_limits, &limits)?;
+    }
+
+    let fee = (price as u128)
+        .checked_mul(ctx.accounts.auction_house.seller_fee_basis_points as u128)
+        .and_then(|v| v.checked_div(10_000))
+        .ok_or(ErrorCode::NumericalOverflow)? as u64;
+    invoke(
+        &system_instruction::transfer(buyer.key, seller.key, price),
+        &[
+            buyer.to_account_info(),
+            seller.to_account_info(),
+            system_program.to_account_info(),
+        ],
+    )?;
+    if fee > 0 {
+        invoke(
+            &system_instruction::transfer(buyer.key, ctx.accounts.auction_house_treasury.key, fee),
+            &[
+                buyer.to_account_info(),
+                ctx.accounts.auction_house_treasury.to_account_info(),
+                system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    let ins = MintNewEditionFromMasterEditionViaTokenBuilder::new()
+        .new_metadata(ctx.accounts.new_metadata.key())
+        .new_edition(ctx.accounts.new_edition.key())
+        .master_edition(ctx.accounts.master_edition.key())
+        .new_mint(ctx.accounts.new_mint.key())
+        .edition_mark_pda(ctx.accounts.edition_marker.key())
+        .new_mint_authority(buyer.key())
+        .payer(buyer.key())
+        .token_account_owner(program_as_signer.key())
+        .token_account(ctx.accounts.token_account.key())
+        .new_metadata_update_authority(ctx.accounts.new_metadata_update_authority.key())
+        .metadata(ctx.accounts.metadata.key())
+        .token_program(ctx.accounts.token_program.key())
+        .system_program(system_program.key())
+        .mint_new_edition_from_master_edition_via_token_args(
+            MintNewEditionFromMasterEditionViaTokenArgs { edition },
+        )
+        .instruction();
+    invoke_signed(
+        &ins,
+        &[
+            ctx.accounts.new_metadata.to_account_info(),
+            ctx.accounts.new_edition.to_account_info(),
+            ctx.accounts.master_edition.to_account_info(),
+            ctx.accounts.new_mint.to_account_info(),
+            ctx.accounts.edition_marker.to_account_info(),
+            buyer.to_account_info(),
+            program_as_signer.to_account_info(),
+            ctx.accounts.token_account.to_account_info(),
+            ctx.accounts.new_metadata_update_authority.to_account_info(),
+            ctx.accounts.metadata.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            system_program.to_account_info(),
+            ctx.accounts.token_metadata_program.to_account_info(),
+        ],
+        &[&[
+            PREFIX.as_bytes(),
+            SIGNER.as_bytes(),
+            &[ctx.bumps.program_as_signer],
+        ]],
+    )?;
+
+    edition_sale_state.printed = edition_sale_state
+        .printed
+        .checked_add(1)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+
+    msg!(
+        "{{\"price\":{},\"edition\":{},\"printed\":{},\"max_prints\":{}}}",
+        price,
+        edition,
+        edition_sale_state.printed,
+        edition_sale_state.max_prints
+    );
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use mpl_token_metadata::accounts::Metadata;
 
-use crate::index_ra;
+use crate::{index_ra, strict_invariant};
 
 use {
     crate::constants::*,
@@ -8,8 +8,12 @@ use {
     crate::states::*,
     crate::utils::*,
     anchor_lang::{prelude::*, AnchorDeserialize},
-    anchor_spl::{associated_token::AssociatedToken, token::Token},
-    solana_program::program_option::COption,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{approve, Approve, Token},
+    },
+    solana_program::{program_option::COption, sysvar},
+    spl_associated_token_account::get_associated_token_address_with_program_id,
 };
 
 #[derive(Accounts)]
@@ -72,7 +76,6 @@ pub struct ExecuteSaleV2<'info> {
         bump=auction_house.bump,
         has_one=authority,
         has_one=auction_house_treasury,
-        constraint = auction_house.notary == notary.key() @ ErrorCode::InvalidNotary,
     )]
     auction_house: Account<'info, AuctionHouse>,
     /// CHECK: auction_house_treasury
@@ -116,7 +119,8 @@ pub struct ExecuteSaleV2<'info> {
     #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
     program_as_signer: UncheckedAccount<'info>,
     rent: Sysvar<'info, Rent>,
-    // remaining accounts:
+    // remaining accounts, in this order, or in any order with a remaining accounts schema appended to the instruction data (see RemainingAccountsTag):
+    // notary_set, then notaries (signer) (optional) - auction house's NotarySet and the notaries cosigning with it, notary may be one of them instead of auction_house.notary, placed first
     // ** IF USING NATIVE SOL **
     // 0..=4. creators (optional) - if the buyer is paying in SOL, these are the creators of the token
     //
@@ -124,14 +128,59 @@ pub struct ExecuteSaleV2<'info> {
     // 0. payment_mint (required) - if the buyer is paying in a token, this is the mint of that token
     // 1. payment_source_token_account (required) - escrow token account controlled by escrow_payment_account
     // 2. payment_seller_token_account (required) - token account controlled by seller
-    // 3. payment_treausry_token_account (required) - token account controlled by auction_house_treasury
+    // 3. payment_treausry_token_account (required) - auction_house_treasury's ATA for the payment mint, created if needed
     // 4..=13. creator_token_account (optional) - if the buyer is paying in a SPL token, these are the creator token accounts,
     //                                            if the creator token accounts are not initialized, the creator itself needs to be
     //                                            included, in the format of creator_1_ATA, creator_1, creator_2_ATA, creator_2, ...
+    //                  or creator (optional) - alternatively the creator wallets in metadata order, starting with creator_1, the program
+    //                                            then derives each creator ATA and finds it among the accounts after the creators
     // ...
-    // -1. payer (optional) - this wallet will try to pay for rent
+    // instructions sysvar (required if auction_house.sandwich_guard, or if the sale is a CPI and auction_house.cpi_policy is Allowlist) - placed after the accounts above
+    // protocol fee recipient or its payment mint ATA (required if auction_house.protocol_fee_share_bp > 0) - placed after the accounts above
+    // escrow adapter program, then its accounts (required if the bid is funded by an escrow adapter) - placed after the accounts above
+    // receipt_owner (required if the bid names one) - receives the NFT instead of the buyer, buyer_receipt_token_account is its ATA
+    // escrow_config (optional) - buyer's EscrowConfig, honored when deciding whether to close the escrow
+    // open_bid_index (required if the bid is indexed, writable) - buyer's OpenBidIndex, the filled bid is removed from it
+    // escrow_ledger (required if the bid is ledgered, writable) - buyer's EscrowLedger, the filled bid is unlocked in it
+    // spending_allowance and its treasury's escrow_payment_account (required if the bid drew from a spending allowance, writable) - what the fill didn't use of the draw goes back to the treasury
+    // wallet_limits (required if the bid counts in the buyer's WalletLimits or auction_house.enforce_wallet_limits, writable) - buyer's WalletLimits, its uninitialized address if there is none, the filled bid is closed in it and, in SOL, the sale adds to the day's buy volume
+    // pending_royalties (optional, writable, SOL only) - PendingRoyalties of the auction house and mint, creators owed royalty but left out of the creator accounts get a claim in it, created if needed
+    // royalty_override (optional) - token mint's RoyaltyOverride, its recipients are paid the royalty in place of the metadata creators, the creator accounts follow its order
+    // bid_group (required if the bid is in a BidGroup, writable) - the fill is counted in it, its other bids can't fill once it had its max fills
+    // bid group bids (optional, writable) - the BidGroup's other bids, the fill that consumes the group closes them to the buyer and unlocks their escrow funds
+    // mint_market (optional, writable) - token mint's MintMarket, the filled bid and listing are dropped from it
+    // listing_index (optional, writable, the collection's is required if the bid is floor conditional) - seller's and verified collection's ListingIndex, the filled listing is removed from them
+    // house_metrics (optional, writable) - auction house's HouseMetrics for the payment mint, the sale is added to it
+    // program_config (optional) - its fee override for the payment mint applies unless the notary signed for the fees
+    // seller wSOL ATA (writable) and native mint (required if the listing takes proceeds as wSOL) - the ATA is created if needed
+    // proceeds_split (optional), then its recipients (writable) - seller's ProceedsSplit, the seller's SOL proceeds fan out to the recipients
+    // master edition and token metadata program (required if the listing is frozen) - the seller's token account is thawed for the transfer
+    // maker_rewards (required if the listing is enrolled in maker rewards, writable) - seller's MakerRewards, an enrolled listing accrues the time it was live until it expired and, if the buyer takes it in SOL, its volume
+    // attestation (required if the listing requires one) - buyer's Attestation
+    // cpi_allowlist (required if the sale is a CPI and auction_house.cpi_policy is Allowlist) - auction house's CpiAllowlist
+    // program_config, then swap adapter program, payment_mint, payment_source_token_account (writable) and the adapter's accounts (required if a bid in a mint the listing doesn't take fills it through a swap) - ProgramConfig.swap_adapter swaps the bid's escrow into the SOL the sale needs, placed after the accounts above
+    // escrow_top_up, then its funding_token_account (writable), the payment mint and the escrow's ATA of it (writable) (optional) - buyer's EscrowTopUp, pulls what the escrow is short of the sale from the funding account, the native mint and the escrow's wSOL ATA for SOL bids
+    // loan program, then its loan_vault (writable) and accounts (required if the listing repays a loan, SOL only) - auction_house.loan_program, the seller's proceeds repay their loan against the NFT through it, the loan vault is the program's PDA of [LOAN_VAULT, seller, token_mint], placed after the accounts above
+    // cranker's ATA of the payment mint (required for match_orders in SPL, writable) - the match bounty is paid to it, created if needed
+    // -1. payer (optional, required for match_orders) - this wallet will try to pay for rent, match_orders pays it the match bounty
+    // instructions sysvar (optional) - placed last if a remaining accounts schema is appended to the instruction data, a sale that reads the sysvar takes it among the schema's keyed accounts too
 }
 
+/// Length of the instruction data of execute_sale_v2, sell_into_bid, match_orders and
+/// dry_run_execute_sale up to the end of their args
+pub const EXECUTE_SALE_V2_ARGS_LEN: usize = 8 + 1 + 1 + 8 + 8 + 8 + 8 + 2 + 2 + 32;
+
+/// Where execute_sale_v2 expects each group of remaining accounts
+pub const EXECUTE_SALE_V2_ORDER: [RemainingAccountsTag; 6] = [
+    RemainingAccountsTag::NotarySet,
+    RemainingAccountsTag::Payment,
+    RemainingAccountsTag::Creators,
+    RemainingAccountsTag::Keyed,
+    RemainingAccountsTag::LoanRepayment,
+    RemainingAccountsTag::Payer,
+];
+
+#[allow(clippy::too_many_arguments)]
 pub fn handle<'info>(
     ctx: Context<'_, '_, '_, 'info, ExecuteSaleV2<'info>>,
     escrow_payment_bump: u8,
@@ -140,12 +189,80 @@ pub fn handle<'info>(
     token_size: u64,
     maker_fee_bp: i16,
     taker_fee_bp: u16,
+    expected_payment_mint: Pubkey,
 ) -> Result<()> {
+    execute_sale(
+        ctx,
+        escrow_payment_bump,
+        program_as_signer_bump,
+        buyer_price,
+        token_size,
+        maker_fee_bp,
+        taker_fee_bp,
+        expected_payment_mint,
+        false,
+    )
+}
+
+// Settles a crossed book: an auto_match bid at or above an auto_match listing is filled at the
+// listing's price by anyone, signing as the payer instead of the buyer or the seller. The bid is
+// the taker, the cranker earns MATCH_BOUNTY_BP of the taker fee from the treasury, in the payment
+// mint.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_match_orders<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteSaleV2<'info>>,
+    escrow_payment_bump: u8,
+    program_as_signer_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+    maker_fee_bp: i16,
+    taker_fee_bp: u16,
+    expected_payment_mint: Pubkey,
+) -> Result<()> {
+    execute_sale(
+        ctx,
+        escrow_payment_bump,
+        program_as_signer_bump,
+        buyer_price,
+        token_size,
+        maker_fee_bp,
+        taker_fee_bp,
+        expected_payment_mint,
+        true,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_sale<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteSaleV2<'info>>,
+    escrow_payment_bump: u8,
+    program_as_signer_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+    maker_fee_bp: i16,
+    taker_fee_bp: u16,
+    expected_payment_mint: Pubkey,
+    is_match: bool,
+) -> Result<()> {
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        ctx.remaining_accounts,
+        &ctx.accounts.auction_house,
+        &ctx.accounts.notary,
+    )?;
+    notary_cosign.assert_known()?;
     let (remaining_accounts, possible_payer) =
-        split_payer_from_remaining_accounts(ctx.remaining_accounts);
+        split_payer_from_remaining_accounts(remaining_accounts);
+    let cranker = if is_match {
+        Some(possible_payer.ok_or(ErrorCode::SaleRequiresSigner)?)
+    } else {
+        None
+    };
+    let (remaining_accounts, loan_repayment) = split_loan_repayment_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house,
+    )?;
     let buyer = &ctx.accounts.buyer;
     let seller = &ctx.accounts.seller;
-    let notary = &ctx.accounts.notary;
     let token_account = &ctx.accounts.token_account;
     let token_mint = &ctx.accounts.token_mint;
     let metadata = &ctx.accounts.metadata;
@@ -169,67 +286,70 @@ pub fn handle<'info>(
         escrow_payment_bump,
     )?;
 
-    if !buyer.is_signer && !seller.is_signer {
+    if cranker.is_none() && !buyer.is_signer && !seller.is_signer {
         return Err(ErrorCode::SaleRequiresSigner.into());
     }
+    let seller_is_taker = cranker.is_none() && !buyer.is_signer;
 
     if buyer_trade_state.data_is_empty() || seller_trade_state.to_account_info().data_is_empty() {
         return Err(ErrorCode::BothPartiesNeedToAgreeToSale.into());
     }
     let bid_args = BidArgs::from_account_info(buyer_trade_state)?;
-    let is_spl = bid_args.payment_mint != Pubkey::default();
-
-    bid_args.check_args(
-        ctx.accounts.buyer_referral.key,
-        buyer_price,
-        token_mint.key,
-        token_size,
-        if is_spl {
-            index_ra!(remaining_accounts, 0).key // mint account
-        } else {
-            &bid_args.payment_mint
-        },
-    )?;
     let sell_args = SellArgs::from_account_info(seller_trade_state)?;
-    sell_args.check_args(
-        ctx.accounts.seller_referral.key,
-        &buyer_price,
+    assert_loan_repayment(
+        loan_repayment.as_ref(),
+        sell_args.repay_loan,
+        seller.key,
         token_mint.key,
-        &token_size,
-        &bid_args.payment_mint, // check that mints match, equality is transitive
     )?;
-
+    // a bid escrowed in a mint the listing doesn't take swaps into SOL and settles in it
+    let swap_fill = cranker.is_none() && is_swap_fill(&bid_args, &sell_args);
+    let payment_mint = if swap_fill {
+        Pubkey::default()
+    } else {
+        bid_args.payment_mint
+    };
+    let is_spl = payment_mint != Pubkey::default();
+    let (actual_maker_fee_bp, actual_taker_fee_bp) = get_actual_maker_taker_fee_bp(
+        &notary_cosign,
+        maker_fee_bp,
+        taker_fee_bp,
+        get_payment_mint_fees(remaining_accounts, &payment_mint)?,
+    );
     let clock = Clock::get()?;
-    if bid_args.expiry.abs() > 1 && clock.unix_timestamp > bid_args.expiry.abs() {
-        return Err(ErrorCode::InvalidExpiry.into());
-    }
-    if sell_args.expiry.abs() > 1 && clock.unix_timestamp > sell_args.expiry.abs() {
-        return Err(ErrorCode::InvalidExpiry.into());
-    }
 
-    let taker = if buyer.is_signer { buyer } else { seller };
+    let checks = SaleChecks {
+        accounts: ctx.accounts,
+        remaining_accounts,
+        bid_args,
+        sell_args,
+        buyer_price,
+        token_size,
+        actual_maker_fee_bp,
+        actual_taker_fee_bp,
+        seller_is_taker,
+        swap_fill,
+        is_match: cranker.is_some(),
+        payment_mint,
+        now: clock.unix_timestamp,
+    };
+    checks.check(&expected_payment_mint)?;
+    // prices are per unit, a sale of token_size units pays for all of them. An all-in bid pays
+    // exactly its price, the sale is priced at what that buys after the fees and royalty on top.
+    let all_in_total = checks.all_in_total()?;
+    let total_price = checks.total_price()?;
+    let SaleChecks {
+        bid_args,
+        sell_args,
+        ..
+    } = checks;
+
+    let taker = if seller_is_taker { seller } else { buyer };
     let payer = if let Some(p) = possible_payer {
         p
     } else {
         taker
     };
-
-    let delegate = get_delegate_from_token_account(token_account)?;
-    if let Some(d) = delegate {
-        assert_keys_equal(program_as_signer.key, &d)?;
-    } else if !is_token_owner(token_account, &program_as_signer.key())? {
-        return Err(ErrorCode::IncorrectOwner.into());
-    }
-
-    assert_is_ata(
-        &token_account.to_account_info(),
-        &seller.key(),
-        token_mint.key,
-        &program_as_signer.key(),
-    )?;
-
-    assert_metadata_valid(metadata, token_mint.key)?;
-
     let auction_house_key = auction_house.key();
     let escrow_signer_seeds: &[&[&[u8]]] = &[&[
         PREFIX.as_bytes(),
@@ -237,6 +357,84 @@ pub fn handle<'info>(
         buyer.key.as_ref(),
         &[escrow_payment_bump],
     ]];
+    let treasury_signer_seeds: &[&[&[u8]]] = &[&[
+        PREFIX.as_bytes(),
+        auction_house_key.as_ref(),
+        TREASURY.as_bytes(),
+        &[auction_house.treasury_bump],
+    ]];
+
+    fund_escrow_from_adapter(
+        remaining_accounts,
+        auction_house,
+        &bid_args,
+        buyer_trade_state,
+        buyer,
+        escrow_payment_account,
+    )?;
+    // what the escrow has to hold for the sale, only worked out when it's swapped or topped up into it
+    let buyer_total_cost = || -> Result<u64> {
+        if let Some(all_in_total) = all_in_total {
+            return Ok(all_in_total);
+        }
+        get_buyer_total_cost(
+            total_price,
+            actual_maker_fee_bp,
+            actual_taker_fee_bp,
+            seller_is_taker,
+            get_royalty_bp(
+                None,
+                &sell_args.royalty_locked(Metadata::safe_deserialize(&metadata.data.borrow())?),
+                total_price / token_size.max(1),
+            ),
+            bid_args.buyer_creator_royalty_bp,
+        )
+    };
+    let swap_amount_in = if swap_fill {
+        swap_into_escrow(
+            remaining_accounts,
+            &bid_args,
+            buyer_total_cost()?,
+            clock.unix_timestamp,
+            escrow_payment_account,
+            escrow_signer_seeds,
+        )?
+    } else {
+        0
+    };
+    let escrow_top_up_args = if swap_fill || bid_args.escrow_adapter != Pubkey::default() {
+        None
+    } else {
+        get_escrow_top_up_args(
+            &auction_house_key,
+            buyer.key,
+            escrow_payment_account.key,
+            remaining_accounts,
+            &payment_mint,
+        )?
+    };
+    if let Some(escrow_top_up_args) = &escrow_top_up_args {
+        let balance = if is_spl {
+            unpack_token_account(index_ra!(remaining_accounts, 1))?.amount
+        } else {
+            escrow_payment_account.lamports()
+        };
+        let shortfall = buyer_total_cost()?.saturating_sub(balance);
+        if shortfall > 0 {
+            top_up_escrow(
+                escrow_top_up_args,
+                shortfall,
+                &auction_house_key,
+                buyer.key,
+                escrow_payment_account,
+                payer,
+                token_program,
+                system_program,
+                escrow_signer_seeds,
+            )?;
+        }
+    }
+    let escrow_lamports_before = escrow_payment_account.lamports();
 
     let royalty = if bid_args.buyer_creator_royalty_bp == 0 {
         0
@@ -248,10 +446,15 @@ pub fn handle<'info>(
                 remaining_accounts.iter()
             }),
             None,
-            &Metadata::safe_deserialize(&metadata.data.borrow())?,
+            &get_royalty_override_metadata(
+                sell_args.royalty_locked(Metadata::safe_deserialize(&metadata.data.borrow())?),
+                token_mint.key,
+                remaining_accounts,
+            )?,
             &escrow_payment_account.to_account_info(),
+            buyer_trade_state,
             escrow_signer_seeds,
-            buyer_price,
+            total_price,
             bid_args.buyer_creator_royalty_bp,
             if is_spl {
                 Some(TransferCreatorSplArgs {
@@ -265,43 +468,167 @@ pub fn handle<'info>(
             } else {
                 None
             },
+            get_pending_royalties_args(
+                &auction_house_key,
+                token_mint.key,
+                remaining_accounts,
+                payer,
+            ),
         )?
     };
 
-    let (actual_maker_fee_bp, actual_taker_fee_bp) =
-        get_actual_maker_taker_fee_bp(notary, maker_fee_bp, taker_fee_bp);
-    transfer_listing_payment(
-        buyer_price,
+    let loan_vault_lamports = loan_repayment.as_ref().map(|l| l.loan_vault.lamports());
+    let listing_spl_args = if is_spl {
+        Some(TransferListingPaymentSplArgs {
+            payer,
+            buyer,
+            mint: index_ra!(remaining_accounts, 0),
+            payment_source_token_account: index_ra!(remaining_accounts, 1),
+            payment_seller_token_account: index_ra!(remaining_accounts, 2),
+            payment_treasury_token_account: index_ra!(remaining_accounts, 3),
+            system_program,
+            token_program,
+            treasury_signer_seeds,
+        })
+    } else {
+        None
+    };
+    let (_, taker_fee) = transfer_listing_payment(
+        total_price,
         actual_maker_fee_bp,
         actual_taker_fee_bp,
         taker,
         seller,
         escrow_payment_account,
         auction_house_treasury,
+        listing_spl_args,
+        get_protocol_fee_args(
+            auction_house,
+            remaining_accounts,
+            if is_spl {
+                Some(&bid_args.payment_mint)
+            } else {
+                None
+            },
+        )?,
+        get_house_metrics_args(
+            &auction_house.key(),
+            remaining_accounts,
+            &payment_mint,
+            royalty,
+        ),
+        get_wsol_proceeds_args(
+            &sell_args,
+            &payment_mint,
+            remaining_accounts,
+            payer,
+            system_program,
+            token_program,
+        )?,
+        get_proceeds_split_args(seller.key, remaining_accounts)?,
+        loan_repayment.as_ref().map(|l| l.loan_vault),
+        escrow_signer_seeds,
+    )?;
+    // an SPL sale pays out of the escrow's token account, its lamports don't move
+    strict_invariant!(assert_escrow_conserved(
+        escrow_lamports_before,
+        escrow_payment_account.lamports(),
         if is_spl {
-            Some(TransferListingPaymentSplArgs {
-                payer,
-                buyer,
-                mint: index_ra!(remaining_accounts, 0),
-                payment_source_token_account: index_ra!(remaining_accounts, 1),
-                payment_seller_token_account: index_ra!(remaining_accounts, 2),
-                payment_treasury_token_account: index_ra!(remaining_accounts, 3),
-                system_program,
-                token_program,
-            })
+            0
         } else {
-            None
+            expected_escrow_outflow(
+                total_price,
+                actual_maker_fee_bp,
+                actual_taker_fee_bp,
+                seller_is_taker,
+                royalty,
+            )?
         },
-        escrow_signer_seeds,
-    )?;
+    ));
+    if let Some(all_in_total) = all_in_total {
+        pay_all_in_remainder(
+            get_all_in_remainder(
+                all_in_total,
+                total_price,
+                actual_maker_fee_bp,
+                actual_taker_fee_bp,
+                seller_is_taker,
+                royalty,
+            )?,
+            escrow_payment_account,
+            auction_house_treasury,
+            listing_spl_args,
+            escrow_signer_seeds,
+        )?;
+    }
+    let match_bounty = match cranker {
+        Some(cranker) => pay_match_bounty(
+            taker_fee,
+            auction_house_treasury,
+            cranker,
+            system_program,
+            match listing_spl_args {
+                Some(spl_args) => Some((
+                    spl_args,
+                    find_remaining_account(
+                        remaining_accounts,
+                        &get_associated_token_address_with_program_id(
+                            cranker.key,
+                            spl_args.mint.key,
+                            token_program.key,
+                        ),
+                    )
+                    .ok_or(ErrorCode::MissingRemainingAccount)?,
+                )),
+                None => None,
+            },
+            treasury_signer_seeds,
+        )?,
+        None => 0,
+    };
+    if let (Some(loan_repayment), Some(lamports_before)) = (&loan_repayment, loan_vault_lamports) {
+        repay_loan(
+            loan_repayment,
+            loan_repayment.loan_vault.lamports() - lamports_before,
+            auction_house.as_ref(),
+            &[
+                PREFIX.as_bytes(),
+                auction_house.creator.as_ref(),
+                &[auction_house.bump],
+            ],
+            &token_mint.to_account_info(),
+            seller,
+        )?;
+    }
 
+    if sell_args.frozen {
+        let (edition, token_metadata_program) =
+            get_frozen_listing_accounts(remaining_accounts, token_mint.key)?;
+        set_delegated_account_frozen(
+            false,
+            program_as_signer,
+            token_account,
+            edition,
+            token_mint,
+            token_program,
+            token_metadata_program,
+            &[&[
+                PREFIX.as_bytes(),
+                SIGNER.as_bytes(),
+                &[program_as_signer_bump],
+            ]],
+        )?;
+    }
+
+    let receipt_owner = get_receipt_owner(remaining_accounts, buyer, &bid_args)?;
+    // units of an escrowed listing that weren't sold go back to the seller
     let buyer_rec_acct = transfer_token(
         &token_size,
         payer,
         program_as_signer,
         seller,
-        None,
-        DestinationSpecifier::Ai(buyer),
+        Some(seller),
+        DestinationSpecifier::Ai(&receipt_owner),
         token_mint,
         token_account,
         buyer_receipt_token_account,
@@ -324,25 +651,589 @@ pub fn handle<'info>(
             // do nothing
         }
     }
+    strict_invariant!(assert_ata_derivation(
+        buyer_receipt_token_account,
+        receipt_owner.key,
+        token_mint.key
+    ));
 
     // we don't need to zero out buyer_trade_state, just copy zero discriminator to it and then close
-    close_account_anchor(buyer_trade_state, buyer)?;
-    close_account_anchor(seller_trade_state, seller)?;
+    release_open_bid(ctx.remaining_accounts, buyer_trade_state.key, &bid_args)?;
+    release_escrow_ledger_bid(ctx.remaining_accounts, &bid_args)?;
+    release_spending_allowance_bid(
+        ctx.remaining_accounts,
+        buyer_trade_state.key,
+        &bid_args,
+        Some(escrow_payment_account),
+        escrow_lamports_before.saturating_sub(escrow_payment_account.lamports()),
+    )?;
+    release_wallet_limits_bid(
+        ctx.remaining_accounts,
+        &ctx.accounts.auction_house,
+        &bid_args,
+        Some(total_price),
+    )?;
+    fill_bid_group_bid(
+        ctx.remaining_accounts,
+        auction_house,
+        &bid_args,
+        buyer_trade_state.key,
+        buyer,
+    )?;
+    close_account_anchor(buyer_trade_state, buyer, true)?;
+    settle_filled_listing(
+        ctx.remaining_accounts,
+        auction_house,
+        seller_trade_state,
+        &sell_args,
+        metadata,
+        &token_mint.key(),
+        &[buyer_trade_state.key],
+        if !seller_is_taker && !is_spl {
+            total_price
+        } else {
+            0
+        },
+    )?;
+    close_account_anchor(seller_trade_state, seller, true)?;
 
     try_close_buyer_escrow(
         escrow_payment_account,
         buyer,
         system_program,
+        get_escrow_config(remaining_accounts, &auction_house.key(), buyer.key)?.as_ref(),
         escrow_signer_seeds,
     )?;
 
     msg!(
-        "{{\"price\":{},\"seller_expiry\":{},\"buyer_expiry\":{},\"royalty\":{}}}",
-        buyer_price,
+        "{{\"price\":{},\"seller_expiry\":{},\"buyer_expiry\":{},\"royalty\":{},\"token_size\":{}}}",
+        total_price / token_size.max(1),
         sell_args.expiry,
         bid_args.expiry,
         royalty,
+        token_size,
     );
+    if cranker.is_some() {
+        msg!("{{\"match_bounty\":{}}}", match_bounty);
+    }
+    if swap_fill {
+        msg!("{{\"swap_amount_in\":{}}}", swap_amount_in);
+    }
+    log_order_memos(&bid_args, &sell_args);
 
     Ok(())
 }
+
+// Accepts a bid without a prior listing: the seller delegates the token to program_as_signer,
+// a delegate mode listing at the bid's price is written and settled against the bid in the same
+// instruction, so nothing can land between the listing and the fill.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_sell_into_bid<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteSaleV2<'info>>,
+    escrow_payment_bump: u8,
+    program_as_signer_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+    maker_fee_bp: i16,
+    taker_fee_bp: u16,
+    expected_payment_mint: Pubkey,
+) -> Result<()> {
+    let seller = &ctx.accounts.seller;
+    let token_account = &ctx.accounts.token_account;
+    let token_mint = &ctx.accounts.token_mint;
+    let auction_house = &ctx.accounts.auction_house;
+    let buyer_trade_state = &ctx.accounts.buyer_trade_state;
+    let seller_trade_state = &ctx.accounts.seller_trade_state;
+
+    if !seller.is_signer {
+        return Err(ErrorCode::SaleRequiresSigner.into());
+    }
+    // the listing it writes is a sell listing, retired with sell
+    assert_instruction_enabled_in(ctx.remaining_accounts, GatedInstruction::Sell)?;
+    if buyer_trade_state.data_is_empty() {
+        return Err(ErrorCode::BothPartiesNeedToAgreeToSale.into());
+    }
+    // an existing listing is filled with execute_sale_v2 instead
+    if !seller_trade_state.data_is_empty() {
+        return Err(ErrorCode::TradeStateAlreadyExists.into());
+    }
+    let bid_args = BidArgs::from_account_info(buyer_trade_state)?;
+    if bid_args.auction_house_key != auction_house.key() {
+        return Err(ErrorCode::WrongAuctionHouseForState.into());
+    }
+
+    let token_account_parsed =
+        assert_is_ata(token_account, seller.key, token_mint.key, seller.key)?;
+    if token_size > token_account_parsed.amount || token_size == 0 {
+        return Err(ErrorCode::InvalidTokenAmount.into());
+    }
+    approve(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Approve {
+                to: token_account.to_account_info(),
+                delegate: ctx.accounts.program_as_signer.to_account_info(),
+                authority: seller.to_account_info(),
+            },
+        ),
+        token_size,
+    )?;
+
+    // the seller funds the trade state rent, it's refunded to the seller when the sale closes it
+    create_or_realloc_seller_trade_state(
+        seller_trade_state,
+        seller,
+        None,
+        &[
+            PREFIX.as_bytes(),
+            seller.key.as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key.as_ref(),
+            token_mint.key.as_ref(),
+            &[ctx.bumps.seller_trade_state],
+        ],
+    )?;
+    write_seller_trade_state(
+        seller_trade_state,
+        SellerTradeStateV3 {
+            auction_house_key: auction_house.key(),
+            seller: seller.key(),
+            seller_referral: ctx.accounts.seller_referral.key(),
+            buyer_price,
+            token_mint: token_mint.key(),
+            token_account: token_account.key(),
+            token_size,
+            bump: ctx.bumps.seller_trade_state,
+            expiry: 0,
+            payment_mint: bid_args.payment_mint,
+            ..Default::default()
+        },
+    )?;
+
+    handle(
+        ctx,
+        escrow_payment_bump,
+        program_as_signer_bump,
+        buyer_price,
+        token_size,
+        maker_fee_bp,
+        taker_fee_bp,
+        expected_payment_mint,
+    )
+}
+
+/// The outcome of each check dry_run_execute_sale runs on a prospective fill, 0 if it passes,
+/// otherwise the error code execute_sale_v2 would fail the check with. Checks that need the trade
+/// states carry trade_states' code when they can't be read.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DryRunResult {
+    pub signer: u32,
+    pub trade_states: u32,
+    pub bid: u32,            // price, size, mint, referral and royalty match the fill
+    pub listing: u32, // price, size, mint, referral, payment mint and attestation match the fill
+    pub expiry: u32,  // neither expired, and the listing isn't hidden
+    pub escrow_balance: u32, // 0 for adapter funded bids and swap fills, only known once they pay
+    pub delegation: u32, // token escrowed by or delegated to program_as_signer
+    pub metadata: u32, // valid for the token and matching the bid's pin
+    pub guards: u32,  // the auction house's CPI policy and sandwich guard allow the sale
+    pub wallet_limits: u32, // the buyer's WalletLimits allow the fill
+}
+
+impl DryRunResult {
+    pub fn is_fillable(&self) -> bool {
+        *self == DryRunResult::default()
+    }
+}
+
+fn check_code(result: Result<()>) -> u32 {
+    match result {
+        Ok(()) => 0,
+        Err(Error::AnchorError(e)) => e.error_code_number,
+        Err(Error::ProgramError(e)) => u64::from(e.program_error.clone()) as u32,
+    }
+}
+
+/// The checks execute_sale_v2 runs on a fill before anything moves, shared with
+/// dry_run_execute_sale so a dry run reports exactly what the sale would fail on
+struct SaleChecks<'a, 'info> {
+    accounts: &'a ExecuteSaleV2<'info>,
+    remaining_accounts: &'a [AccountInfo<'info>],
+    bid_args: BidArgs,
+    sell_args: SellArgs,
+    buyer_price: u64,
+    token_size: u64,
+    actual_maker_fee_bp: i16,
+    actual_taker_fee_bp: u16,
+    seller_is_taker: bool,
+    swap_fill: bool,
+    is_match: bool,
+    payment_mint: Pubkey,
+    now: i64,
+}
+
+impl<'a, 'info> SaleChecks<'a, 'info> {
+    /// Every check of the sale, failing with the first that doesn't pass
+    fn check(&self, expected_payment_mint: &Pubkey) -> Result<()> {
+        self.check_bid()?;
+        self.check_listing(expected_payment_mint)?;
+        self.check_guards()?;
+        self.check_expiry()?;
+        check_delegation(self.accounts)?;
+        self.check_metadata()?;
+        self.check_wallet_limits()
+    }
+
+    fn is_spl(&self) -> bool {
+        self.payment_mint != Pubkey::default()
+    }
+
+    fn metadata(&self) -> Result<Metadata> {
+        let metadata = &self.accounts.metadata;
+        assert_metadata_valid(metadata, self.accounts.token_mint.key)?;
+        Ok(self
+            .sell_args
+            .royalty_locked(Metadata::safe_deserialize(&metadata.data.borrow())?))
+    }
+
+    fn royalty_bp(&self) -> Result<u16> {
+        Ok(get_royalty_bp(None, &self.metadata()?, self.buyer_price))
+    }
+
+    /// What an all-in bid pays for the sale, none for other bids
+    fn all_in_total(&self) -> Result<Option<u64>> {
+        if self.bid_args.price_is_all_in && !self.swap_fill {
+            Ok(Some(get_total_price(
+                self.bid_args.price_at(self.now),
+                self.token_size,
+            )?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// The price of all token_size units, for an all-in bid what its price buys after the fees and
+    /// royalty on top
+    fn total_price(&self) -> Result<u64> {
+        let all_in_total = match self.all_in_total()? {
+            Some(all_in_total) => all_in_total,
+            None => return get_total_price(self.buyer_price, self.token_size),
+        };
+        let metadata = self.metadata()?;
+        let total_price = get_all_in_total_price(
+            all_in_total,
+            self.token_size,
+            self.actual_maker_fee_bp,
+            self.actual_taker_fee_bp,
+            self.seller_is_taker,
+            |unit_price| Ok(get_royalty_bp(None, &metadata, unit_price)),
+            self.bid_args.buyer_creator_royalty_bp,
+        )?;
+        if total_price < get_total_price(self.buyer_price, self.token_size)? {
+            return Err(ErrorCode::TradeStateMismatch.into());
+        }
+        Ok(total_price)
+    }
+
+    fn check_bid(&self) -> Result<()> {
+        let remaining_accounts = self.remaining_accounts;
+        let bid_fill_price = get_bid_fill_price(
+            &self.bid_args,
+            self.buyer_price,
+            self.actual_maker_fee_bp,
+            self.actual_taker_fee_bp,
+            self.seller_is_taker,
+            || self.royalty_bp(),
+            self.bid_args.buyer_creator_royalty_bp,
+        )?;
+        self.bid_args.check_args(
+            self.accounts.buyer_referral.key,
+            // a swap fill is priced in the bid's own mint, the swap checks what it spends, and a
+            // crossed bid fills at the listing's lower price
+            if self.swap_fill
+                || (self.is_match && bid_fill_price <= self.bid_args.price_at(self.now))
+            {
+                self.bid_args.buyer_price
+            } else {
+                bid_fill_price
+            },
+            self.accounts.token_mint.key,
+            self.token_size,
+            if self.is_spl() {
+                index_ra!(remaining_accounts, 0).key // mint account
+            } else {
+                &self.bid_args.payment_mint
+            },
+        )?;
+        assert_bid_royalty_allowed(
+            &self.accounts.auction_house,
+            self.bid_args.buyer_creator_royalty_bp,
+            self.sell_args.royalty_mode,
+        )
+    }
+
+    fn check_listing(&self, expected_payment_mint: &Pubkey) -> Result<()> {
+        self.sell_args.check_args(
+            self.accounts.seller_referral.key,
+            &self.buyer_price,
+            self.accounts.token_mint.key,
+            &self.token_size,
+            &self.payment_mint, // check that mints match, equality is transitive
+        )?;
+        if self.is_match && !(self.bid_args.auto_match && self.sell_args.auto_match) {
+            return Err(ErrorCode::AutoMatchNotEnabled.into());
+        }
+        if self.swap_fill {
+            if *expected_payment_mint != self.payment_mint {
+                return Err(ErrorCode::PaymentMintMismatch.into());
+            }
+        } else {
+            assert_expected_payment_mint(
+                expected_payment_mint,
+                &self.bid_args,
+                &self.sell_args,
+                self.remaining_accounts,
+            )?;
+        }
+        assert_buyer_attested(
+            &self.accounts.auction_house,
+            &self.sell_args,
+            &self.bid_args.buyer,
+            self.remaining_accounts,
+        )
+    }
+
+    /// The CPI policy and the sandwich guard of the auction house
+    fn check_guards(&self) -> Result<()> {
+        let auction_house = &self.accounts.auction_house;
+        let instructions =
+            find_remaining_account(self.remaining_accounts, &sysvar::instructions::id());
+        assert_cpi_caller_allowed(auction_house, instructions, self.remaining_accounts)?;
+        if auction_house.sandwich_guard {
+            assert_no_conflicting_instructions(
+                instructions.ok_or(ErrorCode::MissingRemainingAccount)?,
+                &[
+                    self.accounts.buyer_trade_state.key,
+                    self.accounts.seller_trade_state.key,
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn check_expiry(&self) -> Result<()> {
+        assert_not_expired(self.bid_args.expiry, self.now)?;
+        assert_not_expired(self.sell_args.expiry, self.now)?;
+        assert_listing_visible(self.sell_args.hidden_until, self.now)
+    }
+
+    fn check_metadata(&self) -> Result<()> {
+        assert_metadata_valid(&self.accounts.metadata, self.accounts.token_mint.key)?;
+        assert_metadata_pin(&self.bid_args.metadata_pin, &self.accounts.metadata)?;
+        assert_floor_condition(
+            self.bid_args.max_floor_price,
+            get_verified_collection(&self.accounts.metadata),
+            self.remaining_accounts,
+            &self.accounts.auction_house.key(),
+        )
+    }
+
+    /// The buyer's wallet limits still allow the bid to fill at the sale's price
+    fn check_wallet_limits(&self) -> Result<()> {
+        get_released_wallet_limits(
+            self.remaining_accounts,
+            &self.accounts.auction_house,
+            &self.bid_args,
+            Some(self.total_price()?),
+        )
+        .map(|_| ())
+    }
+
+    fn check_escrow_balance(&self) -> Result<()> {
+        if self.bid_args.escrow_adapter != Pubkey::default() || self.swap_fill {
+            return Ok(());
+        }
+        let remaining_accounts = self.remaining_accounts;
+        // an all-in bid pays exactly its price
+        let cost = match self.all_in_total()? {
+            Some(all_in_total) => all_in_total,
+            None => get_buyer_total_cost(
+                get_total_price(self.buyer_price, self.token_size)?,
+                self.actual_maker_fee_bp,
+                self.actual_taker_fee_bp,
+                self.seller_is_taker,
+                self.royalty_bp()?,
+                self.bid_args.buyer_creator_royalty_bp,
+            )?,
+        };
+        let balance = if self.is_spl() {
+            unpack_token_account(index_ra!(remaining_accounts, 1))?.amount
+        } else {
+            self.accounts.escrow_payment_account.lamports()
+        };
+        if balance >= cost {
+            return Ok(());
+        }
+        // the escrow top-up covers the shortfall if it's still delegated enough
+        match get_escrow_top_up_args(
+            &self.accounts.auction_house.key(),
+            self.accounts.buyer.key,
+            self.accounts.escrow_payment_account.key,
+            remaining_accounts,
+            &self.payment_mint,
+        )? {
+            Some(args) => args.assert_covers(cost - balance),
+            None => Err(ErrorCode::EscrowBalanceTooLow.into()),
+        }
+    }
+}
+
+fn check_delegation(accounts: &ExecuteSaleV2) -> Result<()> {
+    let token_account = unpack_token_account(&accounts.token_account)?;
+    match token_account.delegate {
+        COption::Some(delegate) => assert_keys_equal(accounts.program_as_signer.key, &delegate)?,
+        COption::None if token_account.owner != *accounts.program_as_signer.key => {
+            return Err(ErrorCode::IncorrectOwner.into());
+        }
+        COption::None => {}
+    }
+    assert_is_ata(
+        &accounts.token_account,
+        accounts.seller.key,
+        accounts.token_mint.key,
+        accounts.program_as_signer.key,
+    )?;
+    Ok(())
+}
+
+// View only, nothing is written: runs the checks of execute_sale_v2 on a prospective fill with
+// the same accounts and args, each reporting its own outcome instead of the first failure
+// aborting the rest, so bots can pre-validate candidate fills by simulating it.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_dry_run<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteSaleV2<'info>>,
+    buyer_price: u64,
+    token_size: u64,
+    maker_fee_bp: i16,
+    taker_fee_bp: u16,
+    expected_payment_mint: Pubkey,
+) -> Result<DryRunResult> {
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        ctx.remaining_accounts,
+        &ctx.accounts.auction_house,
+        &ctx.accounts.notary,
+    )?;
+    notary_cosign.assert_known()?;
+    let (remaining_accounts, _) = split_payer_from_remaining_accounts(remaining_accounts);
+    let (remaining_accounts, _) = split_loan_repayment_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house,
+    )?;
+    let accounts = &ctx.accounts;
+    let mut result = DryRunResult {
+        signer: check_code(if accounts.buyer.is_signer || accounts.seller.is_signer {
+            Ok(())
+        } else {
+            Err(ErrorCode::SaleRequiresSigner.into())
+        }),
+        delegation: check_code(check_delegation(accounts)),
+        metadata: check_code(assert_metadata_valid(
+            &accounts.metadata,
+            accounts.token_mint.key,
+        )),
+        ..Default::default()
+    };
+
+    let trade_states = if accounts.buyer_trade_state.data_is_empty()
+        || accounts.seller_trade_state.data_is_empty()
+    {
+        Err(ErrorCode::BothPartiesNeedToAgreeToSale.into())
+    } else {
+        BidArgs::from_account_info(&accounts.buyer_trade_state).and_then(|bid_args| {
+            Ok((
+                bid_args,
+                SellArgs::from_account_info(&accounts.seller_trade_state)?,
+            ))
+        })
+    };
+    let (bid_args, sell_args) = match trade_states {
+        Ok(trade_states) => trade_states,
+        Err(e) => {
+            let code = check_code(Err(e));
+            return Ok(DryRunResult {
+                trade_states: code,
+                bid: code,
+                listing: code,
+                expiry: code,
+                escrow_balance: code,
+                guards: code,
+                wallet_limits: code,
+                ..result
+            });
+        }
+    };
+
+    let swap_fill = is_swap_fill(&bid_args, &sell_args);
+    let payment_mint = if swap_fill {
+        Pubkey::default()
+    } else {
+        bid_args.payment_mint
+    };
+    let (actual_maker_fee_bp, actual_taker_fee_bp) = get_actual_maker_taker_fee_bp(
+        &notary_cosign,
+        maker_fee_bp,
+        taker_fee_bp,
+        get_payment_mint_fees(remaining_accounts, &payment_mint)?,
+    );
+    let checks = SaleChecks {
+        accounts,
+        remaining_accounts,
+        bid_args,
+        sell_args,
+        buyer_price,
+        token_size,
+        actual_maker_fee_bp,
+        actual_taker_fee_bp,
+        seller_is_taker: !accounts.buyer.is_signer,
+        swap_fill,
+        is_match: false,
+        payment_mint,
+        now: Clock::get()?.unix_timestamp,
+    };
+    if result.metadata == 0 {
+        result.metadata = check_code(checks.check_metadata());
+    }
+    result.expiry = check_code(checks.check_expiry());
+    result.bid = check_code(checks.check_bid());
+    result.listing = check_code(checks.check_listing(&expected_payment_mint));
+    result.guards = check_code(checks.check_guards());
+    result.wallet_limits = check_code(checks.check_wallet_limits());
+    result.escrow_balance = check_code(checks.check_escrow_balance());
+
+    msg!("{{\"dry_run\":true,\"fillable\":{}}}", result.is_fillable());
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dry_run_checks_report_the_error_code_the_sale_would_fail_with() {
+        assert_eq!(check_code(Ok(())), 0);
+        assert_eq!(
+            check_code(Err(ErrorCode::EscrowBalanceTooLow.into())),
+            ErrorCode::EscrowBalanceTooLow as u32 + 6000
+        );
+        assert_eq!(
+            check_code(Err(ProgramError::InvalidAccountData.into())),
+            u64::from(ProgramError::InvalidAccountData) as u32
+        );
+
+        assert!(DryRunResult::default().is_fillable());
+        assert!(!DryRunResult {
+            expiry: check_code(Err(ErrorCode::InvalidExpiry.into())),
+            ..Default::default()
+        }
+        .is_fillable());
+    }
+}
@@ -0,0 +1,89 @@
+use {crate::constants::*, anchor_lang::prelude::*};
+
+/// The program's compiled constants, as returned by get_config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ProgramConstants {
+    pub max_price: u64,
+    pub default_maker_fee_bp: i16,
+    pub default_taker_fee_bp: u16,
+    pub max_maker_fee_bp: i16,
+    pub max_taker_fee_bp: u16,
+    pub default_bid_expiry_seconds: i64,
+    pub cancel_authority: Pubkey,
+    pub payment_mints: Vec<Pubkey>, // VALID_PAYMENT_MINTS, without what a ProgramConfig adds
+    pub seeds: Vec<String>,         // PDA seed strings, PREFIX first
+}
+
+#[derive(Accounts)]
+pub struct GetConfig {}
+
+// View only, nothing is written: returns the constants of the deployed build so clients don't
+// have to hardcode them. Fits in return data, which is capped at 1024 bytes.
+pub fn handle(_ctx: Context<GetConfig>) -> Result<ProgramConstants> {
+    Ok(program_constants())
+}
+
+fn program_constants() -> ProgramConstants {
+    ProgramConstants {
+        max_price: MAX_PRICE,
+        default_maker_fee_bp: DEFAULT_MAKER_FEE_BP,
+        default_taker_fee_bp: DEFAULT_TAKER_FEE_BP,
+        max_maker_fee_bp: MAX_MAKER_FEE_BP,
+        max_taker_fee_bp: MAX_TAKER_FEE_BP,
+        default_bid_expiry_seconds: DEFAULT_BID_EXPIRY_SECONDS_AFTER_NOW,
+        cancel_authority: CANCEL_AUTHORITY,
+        payment_mints: VALID_PAYMENT_MINTS.to_vec(),
+        seeds: [
+            PREFIX,
+            TREASURY,
+            SIGNER,
+            ESCROW_CONFIG,
+            RENT_VAULT,
+            OPEN_BID_INDEX,
+            LISTING_INDEX,
+            AUTHORIZATION_NONCE,
+            EDITION_SALE,
+            DEPOSIT_RECEIPT,
+            HOUSE_METRICS,
+            REFERRER,
+            PROGRAM_CONFIG,
+            CANCEL_AUTHORITY_REGISTRY,
+            MAKER_REWARDS,
+            MAKER_REWARDS_VAULT,
+            ESCROW_LEDGER,
+            SPENDING_ALLOWANCE,
+            PENDING_HOUSE_UPDATE,
+            ATTESTATION,
+            CPI_ALLOWLIST,
+            LISTING_SNAPSHOT,
+            WALLET_LIMITS,
+            ESCROW_LOCK,
+            RATE_LIMIT,
+            LISTING_INSURANCE,
+            PROCEEDS_SPLIT,
+            CO_OWNER_APPROVAL,
+            GUARDIAN,
+            ESCROW_TOP_UP,
+            NOTARY_SET,
+            PENDING_ROYALTIES,
+            BID_GROUP,
+            MINT_MARKET,
+            LOAN_VAULT,
+        ]
+        .iter()
+        .map(|seed| seed.to_string())
+        .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::program::MAX_RETURN_DATA;
+
+    #[test]
+    fn program_constants_fit_in_return_data() {
+        let constants = program_constants().try_to_vec().unwrap();
+        assert!(constants.len() <= MAX_RETURN_DATA);
+    }
+}
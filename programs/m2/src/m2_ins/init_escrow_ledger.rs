@@ -0,0 +1,37 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct InitEscrowLedger<'info> {
+    #[account(mut)]
+    wallet: Signer<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init,
+        payer=wallet,
+        seeds=[
+            PREFIX.as_bytes(),
+            ESCROW_LEDGER.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        space=EscrowLedger::LEN,
+        bump,
+    )]
+    escrow_ledger: Box<Account<'info, EscrowLedger>>,
+    /// CHECK: escrow_payment_account
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), wallet.key().as_ref()], bump)]
+    escrow_payment_account: UncheckedAccount<'info>,
+    system_program: Program<'info, System>,
+}
+
+// Opts the wallet into the escrow ledger, starting with its SOL balance as free. Only bids placed
+// after this, with the ledger passed in, lock their price in it.
+pub fn handle(ctx: Context<InitEscrowLedger>) -> Result<()> {
+    let escrow_lamports = ctx.accounts.escrow_payment_account.lamports();
+    let escrow_ledger = &mut ctx.accounts.escrow_ledger;
+    escrow_ledger.auction_house_key = ctx.accounts.auction_house.key();
+    escrow_ledger.wallet = ctx.accounts.wallet.key();
+    escrow_ledger.bump = ctx.bumps.escrow_ledger;
+    escrow_ledger.sync(&Pubkey::default(), escrow_lamports)
+}
@@ -0,0 +1,47 @@
+use {
+    crate::constants::*, crate::errors::ErrorCode, crate::states::*,
+    crate::utils::split_program_config_from_remaining_accounts, anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(payment_mint: Pubkey)]
+pub struct InitHouseMetrics<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=authority)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init,
+        payer=authority,
+        seeds=[
+            PREFIX.as_bytes(),
+            HOUSE_METRICS.as_bytes(),
+            auction_house.key().as_ref(),
+            payment_mint.as_ref(),
+        ],
+        space=HouseMetrics::LEN,
+        bump,
+    )]
+    house_metrics: Box<Account<'info, HouseMetrics>>,
+    system_program: Program<'info, System>,
+    // remaining accounts:
+    // 0. program_config (optional) - the deployment's ProgramConfig, its payment mints are accepted too
+}
+
+// Opts the auction house into on-chain sale metrics for one payment mint, the default pubkey
+// being SOL. Only sales executed after this, with the account passed in, are counted.
+pub fn handle(ctx: Context<InitHouseMetrics>, payment_mint: Pubkey) -> Result<()> {
+    let (_, program_config) = split_program_config_from_remaining_accounts(ctx.remaining_accounts)?;
+    if payment_mint != Pubkey::default()
+        && !VALID_PAYMENT_MINTS.contains(&payment_mint)
+        && !program_config.map_or(false, |c| c.is_payment_mint(&payment_mint))
+    {
+        return Err(ErrorCode::InvalidTokenMint.into());
+    }
+    let house_metrics = &mut ctx.accounts.house_metrics;
+    house_metrics.auction_house_key = ctx.accounts.auction_house.key();
+    house_metrics.payment_mint = payment_mint;
+    house_metrics.bump = ctx.bumps.house_metrics;
+    house_metrics.last_day = Clock::get()?.unix_timestamp.div_euclid(86400);
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct InitMakerRewards<'info> {
+    #[account(mut)]
+    wallet: Signer<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init,
+        payer=wallet,
+        seeds=[
+            PREFIX.as_bytes(),
+            MAKER_REWARDS.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        space=MakerRewards::LEN,
+        bump,
+    )]
+    maker_rewards: Box<Account<'info, MakerRewards>>,
+    system_program: Program<'info, System>,
+}
+
+// Opts the wallet into maker rewards. Only listings made after this, with the account passed in
+// to sell or sell_frozen, are enrolled and accrue when they close.
+pub fn handle(ctx: Context<InitMakerRewards>) -> Result<()> {
+    let maker_rewards = &mut ctx.accounts.maker_rewards;
+    maker_rewards.auction_house_key = ctx.accounts.auction_house.key();
+    maker_rewards.wallet = ctx.accounts.wallet.key();
+    maker_rewards.bump = ctx.bumps.maker_rewards;
+    Ok(())
+}
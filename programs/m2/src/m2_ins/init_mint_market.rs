@@ -0,0 +1,35 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*, anchor_spl::token::Mint};
+
+#[derive(Accounts)]
+pub struct InitMintMarket<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    token_mint: Account<'info, Mint>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init,
+        payer=payer,
+        seeds=[
+            PREFIX.as_bytes(),
+            MINT_MARKET.as_bytes(),
+            auction_house.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        space=MintMarket::LEN,
+        bump,
+    )]
+    mint_market: Box<Account<'info, MintMarket>>,
+    system_program: Program<'info, System>,
+}
+
+// Permissionless: creates the token mint's MintMarket, empty until orders are placed, or
+// refresh_mint_market is called, with it passed in
+pub fn handle(ctx: Context<InitMintMarket>) -> Result<()> {
+    let mint_market = &mut ctx.accounts.mint_market;
+    mint_market.auction_house_key = ctx.accounts.auction_house.key();
+    mint_market.token_mint = ctx.accounts.token_mint.key();
+    mint_market.bump = ctx.bumps.mint_market;
+    mint_market.last_updated_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use {
+    crate::constants::*, crate::errors::ErrorCode, crate::program::M2, crate::states::*,
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct InitProgramConfig<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    program: Program<'info, M2>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ErrorCode::NotUpgradeAuthority)]
+    program_data: Account<'info, ProgramData>,
+    #[account(
+        init,
+        payer=authority,
+        seeds=[PREFIX.as_bytes(), PROGRAM_CONFIG.as_bytes()],
+        space=ProgramConfig::LEN,
+        bump,
+    )]
+    program_config: Box<Account<'info, ProgramConfig>>,
+    system_program: Program<'info, System>,
+}
+
+// Set up once per deployment by the upgrade authority, which then hands it to the authority
+// that maintains it, e.g. the devnet ops key.
+pub fn handle(
+    ctx: Context<InitProgramConfig>,
+    config_authority: Pubkey,
+    payment_mints: [Pubkey; MAX_CONFIG_PAYMENT_MINTS],
+    cancel_authority: Pubkey,
+) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.authority = config_authority;
+    program_config.payment_mints = payment_mints;
+    program_config.cancel_authority = cancel_authority;
+    program_config.bump = ctx.bumps.program_config;
+    Ok(())
+}
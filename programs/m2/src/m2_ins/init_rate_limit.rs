@@ -0,0 +1,33 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct InitRateLimit<'info> {
+    #[account(mut)]
+    wallet: Signer<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init,
+        payer=wallet,
+        seeds=[
+            PREFIX.as_bytes(),
+            RATE_LIMIT.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        space=RateLimit::LEN,
+        bump,
+    )]
+    rate_limit: Box<Account<'info, RateLimit>>,
+    system_program: Program<'info, System>,
+}
+
+// Creates the wallet's RateLimit, which sell, mip1_sell and buy_v2 count the wallet's orders in
+// when the auction house doesn't require the notary and sets max_ixs_per_slot
+pub fn handle(ctx: Context<InitRateLimit>) -> Result<()> {
+    let rate_limit = &mut ctx.accounts.rate_limit;
+    rate_limit.auction_house_key = ctx.accounts.auction_house.key();
+    rate_limit.wallet = ctx.accounts.wallet.key();
+    rate_limit.bump = ctx.bumps.rate_limit;
+    Ok(())
+}
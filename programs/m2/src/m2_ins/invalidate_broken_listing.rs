@@ -0,0 +1,107 @@
+use mpl_token_metadata::accounts::Metadata;
+
+use {
+    crate::constants::*, crate::errors::ErrorCode, crate::states::*, crate::utils::*,
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct InvalidateBrokenListing<'info> {
+    /// CHECK: seller, checked against the trade state and receives its rent
+    #[account(mut)]
+    seller: UncheckedAccount<'info>,
+    /// CHECK: token_account the listing delegates from, may be closed by now
+    token_account: UncheckedAccount<'info>,
+    /// CHECK: token_mint, only used to derive the trade state
+    token_mint: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    /// CHECK: seeds check and check sell_args
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            seller.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    seller_trade_state: AccountInfo<'info>,
+    /// CHECK: program_as_signer
+    #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
+    program_as_signer: UncheckedAccount<'info>,
+    // remaining accounts:
+    // metadata (optional) - needed to remove the listing from its verified collection's ListingIndex
+    // listing_index (optional, writable) - seller's and verified collection's ListingIndex, the listing is removed from them
+    // mint_market (optional, writable) - token mint's MintMarket, the listing is dropped from it
+}
+
+// Closes an escrowless listing the seller broke by moving the token or revoking the delegation
+// outside of M2, e.g. a transfer on another marketplace. The token account isn't the seller's any
+// more, or doesn't delegate the listed size to program_as_signer, so nothing can fill the listing
+// and anyone can close it to keep the book honest, the rent goes back to the seller. Escrowed
+// listings are left to close_stale_sts.
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, InvalidateBrokenListing<'info>>,
+) -> Result<()> {
+    let seller = &ctx.accounts.seller;
+    let token_account = &ctx.accounts.token_account;
+    let token_mint = &ctx.accounts.token_mint;
+    let auction_house = &ctx.accounts.auction_house;
+    let seller_trade_state = &ctx.accounts.seller_trade_state;
+    let program_as_signer = &ctx.accounts.program_as_signer;
+
+    if seller_trade_state.data_is_empty() {
+        return Err(ErrorCode::EmptyTradeState.into());
+    }
+    let sell_args = SellArgs::from_account_info(seller_trade_state)?;
+    if sell_args.auction_house_key != auction_house.key() {
+        return Err(ErrorCode::WrongAuctionHouseForState.into());
+    }
+    if sell_args.seller != seller.key()
+        || sell_args.token_account != token_account.key()
+        || sell_args.token_mint != token_mint.key()
+    {
+        return Err(ErrorCode::TradeStateMismatch.into());
+    }
+
+    // a closed token account breaks the listing too
+    if !token_account.data_is_empty() {
+        let account = unpack_token_account(token_account)?;
+        if account.mint != token_mint.key() {
+            return Err(ErrorCode::InvalidTokenMint.into());
+        }
+        if account.owner == program_as_signer.key() && account.amount > 0 {
+            return Err(ErrorCode::TokenAlreadyEscrowed.into());
+        }
+        if is_delegate_listing_intact(&account, &sell_args, program_as_signer.key) {
+            return Err(ErrorCode::ListingNotBroken.into());
+        }
+    }
+
+    let metadata = find_remaining_account(
+        ctx.remaining_accounts,
+        &Metadata::find_pda(&token_mint.key()).0,
+    );
+    release_listing(
+        ctx.remaining_accounts,
+        &auction_house.key(),
+        &get_listing_index_keys(seller.key, metadata),
+        seller_trade_state,
+    )?;
+    remove_from_mint_market(
+        ctx.remaining_accounts,
+        &auction_house.key(),
+        token_mint.key,
+        &[seller_trade_state.key],
+    )?;
+    close_account_anchor(seller_trade_state, seller, true)?;
+
+    msg!(
+        "{{\"invalidated_broken_listing\":\"{}\"}}",
+        seller_trade_state.key()
+    );
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct IssueAttestation<'info> {
+    #[account(mut)]
+    attestation_authority: Signer<'info>,
+    /// CHECK: wallet
+    wallet: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=attestation_authority)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init_if_needed,
+        payer=attestation_authority,
+        seeds=[
+            PREFIX.as_bytes(),
+            ATTESTATION.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        space=Attestation::LEN,
+        bump,
+    )]
+    attestation: Box<Account<'info, Attestation>>,
+    system_program: Program<'info, System>,
+}
+
+// Issues or renews the wallet's attestation, expiry 0 for one that doesn't expire
+pub fn handle(ctx: Context<IssueAttestation>, expiry: i64) -> Result<()> {
+    let attestation = &mut ctx.accounts.attestation;
+    attestation.auction_house_key = ctx.accounts.auction_house.key();
+    attestation.wallet = ctx.accounts.wallet.key();
+    attestation.issuer = ctx.accounts.attestation_authority.key();
+    attestation.expiry = expiry;
+    attestation.bump = ctx.bumps.attestation;
+
+    msg!("{{\"attestation_expiry\":{}}}", expiry);
+    Ok(())
+}
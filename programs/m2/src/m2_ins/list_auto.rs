@@ -0,0 +1,105 @@
+use std::collections::BTreeSet;
+
+use {
+    crate::m2_ins::*, crate::mip1_ins::*, crate::ocp_ins::*, crate::utils::*,
+    anchor_lang::prelude::*, anchor_lang::Bumps, anchor_spl::token::Mint,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ListAutoArgs {
+    pub price: u64,
+    pub expiry: i64,
+    pub extra_args: Vec<u8>, // SellExtraArgs of sell and mip1_sell, ignored by ocp_sell
+}
+
+#[derive(Accounts)]
+pub struct ListAuto<'info> {
+    token_mint: Account<'info, Mint>,
+    /// CHECK: metadata, may be empty for tokens without metadata
+    #[account(
+    seeds = [
+        "metadata".as_bytes(),
+        mpl_token_metadata::ID.as_ref(),
+        token_mint.key().as_ref(),
+    ],
+    bump,
+    seeds::program = mpl_token_metadata::ID,
+    )]
+    metadata: UncheckedAccount<'info>,
+    // remaining accounts:
+    // 0.. accounts of sell, mip1_sell or ocp_sell for the token, in their order and followed by
+    //     their remaining accounts. the OCP mint state is in the ocp_sell accounts, which is how
+    //     OCP tokens are told apart.
+}
+
+// Routes to sell, mip1_sell or ocp_sell by the token's standard, so integrators keep one listing
+// code path. The routed flow validates its accounts, signers and args as if it was called
+// directly, a sell listing is of a single token.
+pub fn handle<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ListAuto<'info>>,
+    args: ListAutoArgs,
+) -> Result<()> {
+    let program_id = ctx.program_id;
+    let flow = get_listing_flow(
+        &ctx.accounts.token_mint.key(),
+        &ctx.accounts.metadata,
+        ctx.remaining_accounts,
+    )?;
+    msg!("list_auto: {:?}", flow);
+    if let Some(ix) = flow.gate() {
+        assert_instruction_enabled_in(ctx.remaining_accounts, ix)?;
+    }
+
+    let mut accounts = ctx.remaining_accounts;
+    let mut reallocs = BTreeSet::new();
+    match flow {
+        ListingFlow::Legacy => {
+            let mut bumps = <Sell as Bumps>::Bumps::default();
+            let mut sell =
+                Sell::try_accounts(program_id, &mut accounts, &[], &mut bumps, &mut reallocs)?;
+            let program_as_signer_bump = bumps.program_as_signer;
+            sell::handle(
+                Context::new(program_id, &mut sell, accounts, bumps),
+                program_as_signer_bump,
+                args.price,
+                1,
+                args.expiry,
+                &args.extra_args,
+            )?;
+            sell.exit(program_id)
+        }
+        ListingFlow::Mip1 => {
+            let mut bumps = <MIP1Sell as Bumps>::Bumps::default();
+            let mut sell =
+                MIP1Sell::try_accounts(program_id, &mut accounts, &[], &mut bumps, &mut reallocs)?;
+            handle_mip1_sell(
+                Context::new(program_id, &mut sell, accounts, bumps),
+                &MIP1SellArgs {
+                    price: args.price,
+                    expiry: args.expiry,
+                },
+                &args.extra_args,
+            )?;
+            sell.exit(program_id)
+        }
+        ListingFlow::Ocp => {
+            let ocp_args = OCPSellArgs {
+                price: args.price,
+                expiry: args.expiry,
+            };
+            let mut bumps = <OCPSell as Bumps>::Bumps::default();
+            let mut sell = OCPSell::try_accounts(
+                program_id,
+                &mut accounts,
+                &ocp_args.try_to_vec()?,
+                &mut bumps,
+                &mut reallocs,
+            )?;
+            ocp_sell::handle(
+                Context::new(program_id, &mut sell, accounts, bumps),
+                ocp_args,
+            )?;
+            sell.exit(program_id)
+        }
+    }
+}
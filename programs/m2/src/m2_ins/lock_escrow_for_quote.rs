@@ -0,0 +1,127 @@
+use crate::index_ra;
+
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    crate::utils::*,
+    anchor_lang::{
+        prelude::*,
+        solana_program::{program::invoke_signed, system_instruction},
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(order_hash: [u8; 32])]
+pub struct LockEscrowForQuote<'info> {
+    #[account(mut)]
+    wallet: Signer<'info>,
+    /// CHECK: escrow_payment_account
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), wallet.key().as_ref()], bump)]
+    escrow_payment_account: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init,
+        payer=wallet,
+        seeds=[
+            PREFIX.as_bytes(),
+            ESCROW_LOCK.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+            order_hash.as_ref(),
+        ],
+        space=EscrowLock::LEN,
+        bump,
+    )]
+    escrow_lock: Box<Account<'info, EscrowLock>>,
+    system_program: Program<'info, System>,
+    // remaining accounts:
+    // 0. payment_mint (optional) - if included, locks the token of this mint instead of SOL
+    // 1. escrow_token_account (optional) - escrow_payment_account's ATA of payment_mint
+    // 2. lock_token_account (optional) - escrow_lock's ATA of payment_mint, created if needed
+    // 3. token_program (optional)
+    // 4. associated_token_program (optional)
+}
+
+// Moves amount out of the wallet's escrow into a lock for order_hash, until deadline_slot. Before
+// the deadline only the notary can release it, e.g. to settle the order, after it anyone can.
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, LockEscrowForQuote<'info>>,
+    order_hash: [u8; 32],
+    amount: u64,
+    deadline_slot: u64,
+) -> Result<()> {
+    let wallet = &ctx.accounts.wallet;
+    let escrow_payment_account = &ctx.accounts.escrow_payment_account;
+    let auction_house_key = ctx.accounts.auction_house.key();
+    let remaining_accounts = ctx.remaining_accounts;
+
+    let slot = Clock::get()?.slot;
+    if deadline_slot <= slot || deadline_slot - slot > MAX_ESCROW_LOCK_SLOTS {
+        return Err(ErrorCode::InvalidEscrowLockDeadline.into());
+    }
+    if amount == 0 {
+        return Err(ErrorCode::InvalidPrice.into());
+    }
+
+    let escrow_signer_seeds: &[&[&[u8]]] = &[&[
+        PREFIX.as_bytes(),
+        auction_house_key.as_ref(),
+        wallet.key.as_ref(),
+        &[ctx.bumps.escrow_payment_account],
+    ]];
+    let escrow_lock_info = ctx.accounts.escrow_lock.to_account_info();
+    let payment_mint = if remaining_accounts.is_empty() {
+        invoke_signed(
+            &system_instruction::transfer(
+                &escrow_payment_account.key(),
+                &escrow_lock_info.key(),
+                amount,
+            ),
+            &[
+                escrow_payment_account.to_account_info(),
+                escrow_lock_info.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            escrow_signer_seeds,
+        )?;
+        Pubkey::default()
+    } else {
+        assert_keys_equal(index_ra!(remaining_accounts, 3).key, &spl_token::id())?;
+        transfer_token(
+            &amount,
+            wallet,
+            escrow_payment_account,
+            wallet,
+            None,
+            DestinationSpecifier::Ai(&escrow_lock_info),
+            index_ra!(remaining_accounts, 0),
+            index_ra!(remaining_accounts, 1),
+            index_ra!(remaining_accounts, 2),
+            index_ra!(remaining_accounts, 3),
+            &ctx.accounts.system_program,
+            None,
+            escrow_signer_seeds,
+        )?;
+        index_ra!(remaining_accounts, 0).key()
+    };
+
+    let escrow_lock = &mut ctx.accounts.escrow_lock;
+    escrow_lock.auction_house_key = auction_house_key;
+    escrow_lock.wallet = wallet.key();
+    escrow_lock.bump = ctx.bumps.escrow_lock;
+    escrow_lock.order_hash = order_hash;
+    escrow_lock.payment_mint = payment_mint;
+    escrow_lock.amount = amount;
+    escrow_lock.deadline_slot = deadline_slot;
+
+    msg!(
+        "{{\"escrow_lock\":\"{}\",\"payment_mint\":\"{}\",\"amount\":{},\"deadline_slot\":{}}}",
+        escrow_lock.key(),
+        payment_mint,
+        amount,
+        deadline_slot
+    );
+    Ok(())
+}
@@ -27,3 +27,183 @@ pub use execute_sale_v2::*;
 
 pub mod buy_v2;
 pub use buy_v2::*;
+
+pub mod set_escrow_config;
+pub use set_escrow_config::*;
+
+pub mod withdraw_from_rent_vault;
+pub use withdraw_from_rent_vault::*;
+
+pub mod withdraw_all;
+pub use withdraw_all::*;
+
+pub mod sell_with_authorization;
+pub use sell_with_authorization::*;
+
+pub mod buy_with_authorization;
+pub use buy_with_authorization::*;
+
+pub mod sell_edition;
+pub use sell_edition::*;
+
+pub mod execute_edition_sale;
+pub use execute_edition_sale::*;
+
+pub mod cancel_edition_sale;
+pub use cancel_edition_sale::*;
+
+pub mod sweep_abandoned_escrow;
+pub use sweep_abandoned_escrow::*;
+
+pub mod cancel_buy_batch;
+pub use cancel_buy_batch::*;
+
+pub mod deposit_with_reference;
+pub use deposit_with_reference::*;
+
+pub mod init_house_metrics;
+pub use init_house_metrics::*;
+
+pub mod auto_relist;
+pub use auto_relist::*;
+
+pub mod sell_frozen;
+pub use sell_frozen::*;
+
+pub mod repair_trade_state;
+pub use repair_trade_state::*;
+
+pub mod register_referrer;
+pub use register_referrer::*;
+
+pub mod init_program_config;
+pub use init_program_config::*;
+
+pub mod update_program_config;
+pub use update_program_config::*;
+
+pub mod set_cancel_authority;
+pub use set_cancel_authority::*;
+
+pub mod close_stale_sts;
+pub use close_stale_sts::*;
+
+pub mod init_maker_rewards;
+pub use init_maker_rewards::*;
+
+pub mod claim_maker_rewards;
+pub use claim_maker_rewards::*;
+
+pub mod init_escrow_ledger;
+pub use init_escrow_ledger::*;
+
+pub mod upgrade_legacy_sts;
+pub use upgrade_legacy_sts::*;
+
+pub mod set_spending_allowance;
+pub use set_spending_allowance::*;
+
+pub mod schedule_house_update;
+pub use schedule_house_update::*;
+
+pub mod apply_house_update;
+pub use apply_house_update::*;
+
+pub mod issue_attestation;
+pub use issue_attestation::*;
+
+pub mod revoke_attestation;
+pub use revoke_attestation::*;
+
+pub mod set_cpi_policy;
+pub use set_cpi_policy::*;
+
+pub mod snapshot_listing;
+pub use snapshot_listing::*;
+
+pub mod get_config;
+pub use get_config::*;
+
+pub mod set_wallet_limits;
+pub use set_wallet_limits::*;
+
+pub mod cancel_listing_auto;
+pub use cancel_listing_auto::*;
+
+pub mod list_auto;
+pub use list_auto::*;
+
+pub mod lock_escrow_for_quote;
+pub use lock_escrow_for_quote::*;
+
+pub mod release_escrow_lock;
+pub use release_escrow_lock::*;
+
+pub mod init_rate_limit;
+pub use init_rate_limit::*;
+
+pub mod recover_seller_trade_state;
+pub use recover_seller_trade_state::*;
+
+pub mod quote_list;
+pub use quote_list::*;
+
+pub mod deposit_listing_insurance;
+pub use deposit_listing_insurance::*;
+
+pub mod withdraw_listing_insurance;
+pub use withdraw_listing_insurance::*;
+
+pub mod claim_listing_insurance;
+pub use claim_listing_insurance::*;
+
+pub mod set_proceeds_split;
+pub use set_proceeds_split::*;
+
+pub mod close_proceeds_split;
+pub use close_proceeds_split::*;
+
+pub mod set_co_owners;
+pub use set_co_owners::*;
+
+pub mod approve_co_owner_action;
+pub use approve_co_owner_action::*;
+
+pub mod set_guardian;
+pub use set_guardian::*;
+
+pub mod remove_guardian;
+pub use remove_guardian::*;
+
+pub mod sweep_buy;
+pub use sweep_buy::*;
+
+pub mod set_escrow_top_up;
+pub use set_escrow_top_up::*;
+
+pub mod remove_escrow_top_up;
+pub use remove_escrow_top_up::*;
+
+pub mod set_notary_set;
+pub use set_notary_set::*;
+
+pub mod close_notary_set;
+pub use close_notary_set::*;
+pub mod defund_zombie_state;
+pub use defund_zombie_state::*;
+pub mod distribute_royalties;
+pub use distribute_royalties::*;
+pub mod create_bid_group;
+pub use create_bid_group::*;
+pub mod close_bid_group;
+pub use close_bid_group::*;
+pub mod init_mint_market;
+pub use init_mint_market::*;
+pub mod refresh_mint_market;
+pub use refresh_mint_market::*;
+pub mod invalidate_broken_listing;
+pub use invalidate_broken_listing::*;
+pub mod set_royalty_override;
+pub use set_royalty_override::*;
+pub mod close_royalty_override;
+pub use close_royalty_override::*;
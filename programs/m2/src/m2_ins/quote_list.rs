@@ -0,0 +1,204 @@
+use mpl_token_metadata::accounts::Metadata;
+
+use crate::index_ra;
+
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    crate::utils::{
+        assert_bid_royalty_allowed, assert_payment_mint, get_actual_maker_taker_fee_bp,
+        get_buyer_total_cost, get_creator_royalty, get_listing_payment_amounts, get_royalty_bp,
+        split_notary_set_from_remaining_accounts, split_program_config_from_remaining_accounts,
+        ListingPaymentAmounts, RemainingAccountsTag,
+    },
+    anchor_lang::prelude::*,
+};
+
+/// What a listing at a price nets the seller, as returned by quote_list
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListQuote {
+    pub seller_proceeds: u64, // price less the maker fee, or plus the maker rebate
+    pub maker_fee: i64,
+    pub taker_fee: u64,
+    pub royalty: u64, // paid by the buyer on top of the price
+    pub buyer_total_cost: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(price: u64, maker_fee_bp: i16, taker_fee_bp: u16)]
+pub struct QuoteList<'info> {
+    /// CHECK: the house's notary, the fee bps only apply if it signs, as in execute_sale_v2
+    notary: UncheckedAccount<'info>,
+    /// CHECK: token_mint, only used to derive the metadata
+    token_mint: UncheckedAccount<'info>,
+    /// CHECK: metadata
+    #[account(
+    seeds = [
+        "metadata".as_bytes(),
+        mpl_token_metadata::ID.as_ref(),
+        token_mint.key().as_ref(),
+    ],
+    bump,
+    seeds::program = mpl_token_metadata::ID,
+    )]
+    metadata: UncheckedAccount<'info>,
+    #[account(
+        seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()],
+        bump=auction_house.bump,
+        constraint = maker_fee_bp <= MAX_MAKER_FEE_BP @ ErrorCode::InvalidPlatformFeeBp,
+        constraint = maker_fee_bp >= -(taker_fee_bp as i16) @ ErrorCode::InvalidPlatformFeeBp,
+        constraint = taker_fee_bp <= MAX_TAKER_FEE_BP @ ErrorCode::InvalidPlatformFeeBp,
+    )]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+    // remaining accounts, in this order, or in any order with a remaining accounts schema appended to the instruction data (see RemainingAccountsTag):
+    // notary_set, then notaries (signer) (optional) - auction house's NotarySet and the notaries cosigning with it, notary may be one of them instead of auction_house.notary, placed first
+    // program_config (optional) - the deployment's ProgramConfig, placed before the account below, its payment mints are accepted too
+    // payment_mint (optional) - the listing's SPL payment mint, SOL without it
+    // seller_trade_state (optional) - the listing of token_mint being quoted, placed last, its royalty_mode and royalty lock apply as they do in execute_sale_v2
+    // instructions sysvar (optional) - placed last if a remaining accounts schema is appended to the instruction data
+}
+
+/// Length of the quote_list instruction data up to the end of its args
+pub const QUOTE_LIST_ARGS_LEN: usize = 8 + 8 + 2 + 2 + 2;
+
+/// Where quote_list expects each group of remaining accounts
+pub const QUOTE_LIST_ORDER: [RemainingAccountsTag; 4] = [
+    RemainingAccountsTag::NotarySet,
+    RemainingAccountsTag::ProgramConfig,
+    RemainingAccountsTag::Payment,
+    RemainingAccountsTag::Listing,
+];
+
+// View only, nothing is written: returns what execute_sale_v2 would pay the seller of a listing
+// at price filled by a taker bid, so every listing UI shows the program's own numbers. OCP
+// listings with a dynamic royalty curve can pass the royalty from quote_ocp_royalty instead.
+pub fn handle(
+    ctx: Context<QuoteList>,
+    price: u64,
+    maker_fee_bp: i16,
+    taker_fee_bp: u16,
+    buyer_creator_royalty_bp: u16,
+) -> Result<ListQuote> {
+    if price > MAX_PRICE || price == 0 {
+        return Err(ErrorCode::InvalidPrice.into());
+    }
+    let auction_house = &ctx.accounts.auction_house;
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        ctx.remaining_accounts,
+        auction_house,
+        &ctx.accounts.notary,
+    )?;
+    notary_cosign.assert_known()?;
+    let (remaining_accounts, program_config) =
+        split_program_config_from_remaining_accounts(remaining_accounts)?;
+    // the listing is told apart by its owner, a payment mint is owned by a token program
+    let (remaining_accounts, sell_args) = match remaining_accounts.split_last() {
+        Some((last, rest)) if last.owner == &crate::ID => {
+            let sell_args = SellArgs::from_account_info(last)?;
+            if sell_args.token_mint != ctx.accounts.token_mint.key() {
+                return Err(ErrorCode::InvalidTokenMint.into());
+            }
+            (rest, Some(sell_args))
+        }
+        _ => (remaining_accounts, None),
+    };
+    assert_bid_royalty_allowed(
+        auction_house,
+        buyer_creator_royalty_bp,
+        sell_args
+            .as_ref()
+            .map_or(RoyaltyMode::Optional, |s| s.royalty_mode),
+    )?;
+    match remaining_accounts.len() {
+        0 => {}
+        1 => assert_payment_mint(index_ra!(remaining_accounts, 0), program_config.as_ref())?,
+        _ => return Err(ErrorCode::UnexpectedRemainingAccounts.into()),
+    }
+
+    let payment_mint_fees = match (remaining_accounts.first(), program_config.as_ref()) {
+        (Some(payment_mint), Some(config)) => config.fees_for(payment_mint.key),
+        _ => None,
+    };
+    let (actual_maker_fee_bp, actual_taker_fee_bp) = get_actual_maker_taker_fee_bp(
+        &notary_cosign,
+        maker_fee_bp,
+        taker_fee_bp,
+        payment_mint_fees,
+    );
+    let metadata = Metadata::safe_deserialize(&ctx.accounts.metadata.data.borrow())?;
+    let metadata = match &sell_args {
+        Some(sell_args) => sell_args.royalty_locked(metadata),
+        None => metadata,
+    };
+    let quote = list_quote(
+        price,
+        actual_maker_fee_bp,
+        actual_taker_fee_bp,
+        get_royalty_bp(None, &metadata, price),
+        buyer_creator_royalty_bp,
+    )?;
+    msg!(
+        "{{\"price\":{},\"seller_proceeds\":{}}}",
+        price,
+        quote.seller_proceeds
+    );
+    Ok(quote)
+}
+
+fn list_quote(
+    price: u64,
+    actual_maker_fee_bp: i16,
+    actual_taker_fee_bp: u16,
+    royalty_bp: u16,
+    buyer_creator_royalty_bp: u16,
+) -> Result<ListQuote> {
+    // the listing is the maker, so the seller only ever pays or gets the maker side
+    let ListingPaymentAmounts {
+        maker_fee,
+        taker_fee,
+        seller_will_get_from_buyer,
+        ..
+    } = get_listing_payment_amounts(price, actual_maker_fee_bp, actual_taker_fee_bp, false, 0)?;
+    Ok(ListQuote {
+        seller_proceeds: seller_will_get_from_buyer,
+        maker_fee,
+        taker_fee,
+        royalty: get_creator_royalty(royalty_bp, price, buyer_creator_royalty_bp)?,
+        buyer_total_cost: get_buyer_total_cost(
+            price,
+            actual_maker_fee_bp,
+            actual_taker_fee_bp,
+            false,
+            royalty_bp,
+            buyer_creator_royalty_bp,
+        )?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_quote_nets_the_maker_fee_out_of_the_price() -> Result<()> {
+        let quote = list_quote(10_000_000, 100, 200, 500, 10_000)?;
+        assert_eq!(
+            quote,
+            ListQuote {
+                seller_proceeds: 9_900_000,
+                maker_fee: 100_000,
+                taker_fee: 200_000,
+                royalty: 500_000,
+                buyer_total_cost: 10_700_000,
+            }
+        );
+
+        // a maker rebate adds to the proceeds, the royalty never takes from them
+        let quote = list_quote(10_000_000, -100, 200, 500, 0)?;
+        assert_eq!(quote.seller_proceeds, 10_100_000);
+        assert_eq!(quote.royalty, 0);
+        assert_eq!(quote.buyer_total_cost, 10_200_000);
+        Ok(())
+    }
+}
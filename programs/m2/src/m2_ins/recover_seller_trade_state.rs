@@ -0,0 +1,101 @@
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    crate::utils::*,
+    anchor_lang::prelude::*,
+    anchor_spl::token::{revoke, set_authority, Revoke, SetAuthority, Token},
+    spl_token::instruction::AuthorityType,
+};
+
+#[derive(Accounts)]
+pub struct RecoverSellerTradeState<'info> {
+    #[account(mut)]
+    seller: Signer<'info>,
+    /// CHECK: token_account the listing was made for, checked as a token account of token_mint
+    #[account(mut)]
+    token_account: UncheckedAccount<'info>,
+    /// CHECK: token_mint, only used to derive the trade state
+    token_mint: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    /// CHECK: seeds check, and must be half migrated
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            seller.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    seller_trade_state: AccountInfo<'info>,
+    /// CHECK: program_as_signer
+    #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
+    program_as_signer: UncheckedAccount<'info>,
+    token_program: Program<'info, Token>,
+}
+
+// Closes a seller trade state left half migrated, see is_half_migrated_seller_trade_state, which
+// neither cancel_sell nor a relist can read any more. The token goes back to the seller as
+// cancel_sell would return it: escrowed tokens are handed back, delegated ones revoked.
+pub fn handle(ctx: Context<RecoverSellerTradeState>) -> Result<()> {
+    let seller = &ctx.accounts.seller;
+    let token_account = &ctx.accounts.token_account;
+    let token_mint = &ctx.accounts.token_mint;
+    let seller_trade_state = &ctx.accounts.seller_trade_state;
+    let program_as_signer = &ctx.accounts.program_as_signer;
+    let token_program = &ctx.accounts.token_program;
+
+    if seller_trade_state.data_is_empty() {
+        return Err(ErrorCode::EmptyTradeState.into());
+    }
+    assert_owned_by(seller_trade_state, &crate::ID)?;
+    if !is_half_migrated_seller_trade_state(seller_trade_state)? {
+        return Err(ErrorCode::InvalidDiscriminator.into());
+    }
+
+    if !token_account.data_is_empty() {
+        let account = unpack_token_account(token_account)?;
+        if account.mint != token_mint.key() {
+            return Err(ErrorCode::InvalidTokenMint.into());
+        }
+        if account.owner == program_as_signer.key() {
+            set_authority(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    SetAuthority {
+                        account_or_mint: token_account.to_account_info(),
+                        current_authority: program_as_signer.to_account_info(),
+                    },
+                    &[&[
+                        PREFIX.as_bytes(),
+                        SIGNER.as_bytes(),
+                        &[ctx.bumps.program_as_signer],
+                    ]],
+                ),
+                AuthorityType::AccountOwner,
+                Some(seller.key()),
+            )?;
+        } else if account.owner == seller.key()
+            && account.delegate == Some(program_as_signer.key()).into()
+        {
+            revoke(CpiContext::new(
+                token_program.to_account_info(),
+                Revoke {
+                    source: token_account.to_account_info(),
+                    authority: seller.to_account_info(),
+                },
+            ))?;
+        }
+    }
+
+    close_account_anchor(seller_trade_state, seller, true)?;
+    msg!(
+        "{{\"recovered_seller_trade_state\":\"{}\"}}",
+        seller_trade_state.key()
+    );
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct RefreshMintMarket<'info> {
+    /// CHECK: token_mint, only used to derive mint_market
+    token_mint: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            MINT_MARKET.as_bytes(),
+            auction_house.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump=mint_market.bump,
+    )]
+    mint_market: Box<Account<'info, MintMarket>>,
+    // remaining accounts:
+    // 0.. trade states (optional) - bids and listings of the token mint to rank, and its current best orders to drop if they were closed, hidden or repriced
+}
+
+/// An open order of the token mint as MintMarket ranks it, its SOL price, if any, and token size
+enum OpenOrder {
+    Bid(Option<u64>, u64),
+    Ask(Option<u64>, u64),
+}
+
+fn open_order(
+    trade_state: &AccountInfo,
+    auction_house_key: &Pubkey,
+    token_mint: &Pubkey,
+    now: i64,
+) -> Option<OpenOrder> {
+    if trade_state.owner != &crate::ID || trade_state.data_is_empty() {
+        return None;
+    }
+    if let Ok(bid) = BidArgs::from_account_info(trade_state) {
+        return (bid.auction_house_key == *auction_house_key
+            && bid.token_mint == *token_mint
+            && !Expiry::from(bid.expiry).is_expired(now))
+        .then(|| {
+            OpenOrder::Bid(
+                (bid.payment_mint == Pubkey::default()).then_some(bid.buyer_price),
+                bid.token_size,
+            )
+        });
+    }
+    let listing = SellArgs::from_account_info(trade_state).ok()?;
+    (listing.auction_house_key == *auction_house_key
+        && listing.token_mint == *token_mint
+        && !Expiry::from(listing.expiry).is_expired(now)
+        && now >= listing.hidden_until)
+        .then(|| OpenOrder::Ask(listing.price_for(&Pubkey::default()), listing.token_size))
+}
+
+// Permissionless: keepers pass the mint's open orders to repair its top of book after the best
+// order was cancelled or filled without the MintMarket, or repriced. Every trade state passed is
+// dropped before the open ones are ranked, so the best of those passed takes the place of a stale
+// or repriced best order.
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, RefreshMintMarket<'info>>) -> Result<()> {
+    let auction_house_key = ctx.accounts.auction_house.key();
+    let token_mint = ctx.accounts.token_mint.key();
+    let now = Clock::get()?.unix_timestamp;
+    let mint_market = &mut ctx.accounts.mint_market;
+
+    let orders: Vec<(&Pubkey, Option<OpenOrder>)> = ctx
+        .remaining_accounts
+        .iter()
+        .map(|ts| (ts.key, open_order(ts, &auction_house_key, &token_mint, now)))
+        .collect();
+    for (trade_state, _) in orders.iter() {
+        mint_market.remove(trade_state);
+    }
+    for (trade_state, order) in orders {
+        match order {
+            Some(OpenOrder::Bid(sol_price, token_size)) => {
+                mint_market.offer_bid(trade_state, sol_price, token_size)
+            }
+            Some(OpenOrder::Ask(sol_price, token_size)) => {
+                mint_market.offer_ask(trade_state, sol_price, token_size)
+            }
+            None => {}
+        }
+    }
+    mint_market.last_updated_at = now;
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=authority)]
+    auction_house: Account<'info, AuctionHouse>,
+    /// CHECK: referral, the key orders pass as buyer_referral or seller_referral
+    referral: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer=authority,
+        seeds=[
+            PREFIX.as_bytes(),
+            REFERRER.as_bytes(),
+            auction_house.key().as_ref(),
+            referral.key().as_ref(),
+        ],
+        space=Referrer::LEN,
+        bump,
+    )]
+    referrer: Box<Account<'info, Referrer>>,
+    system_program: Program<'info, System>,
+}
+
+// Registers a referral key, or updates its registration. Registering again restarts the
+// attribution window.
+pub fn handle(
+    ctx: Context<RegisterReferrer>,
+    payout: Pubkey,
+    attribution_window_seconds: u32,
+) -> Result<()> {
+    let referrer = &mut ctx.accounts.referrer;
+    referrer.auction_house_key = ctx.accounts.auction_house.key();
+    referrer.referral = ctx.accounts.referral.key();
+    referrer.payout = payout;
+    referrer.registered_at = Clock::get()?.unix_timestamp;
+    referrer.attribution_window_seconds = attribution_window_seconds;
+    referrer.bump = ctx.bumps.referrer;
+    msg!(
+        "{{\"referral\":\"{}\",\"payout\":\"{}\",\"attribution_window_seconds\":{}}}",
+        referrer.referral,
+        payout,
+        attribution_window_seconds
+    );
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use crate::index_ra;
+
+use {
+    crate::constants::*, crate::errors::ErrorCode, crate::states::*, crate::utils::*,
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ReleaseEscrowLock<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    /// CHECK: wallet, gets the lock's rent back
+    #[account(mut)]
+    wallet: UncheckedAccount<'info>,
+    /// CHECK: notary, has to sign to release the lock before its deadline
+    notary: UncheckedAccount<'info>,
+    /// CHECK: escrow_payment_account
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), wallet.key().as_ref()], bump)]
+    escrow_payment_account: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        close=wallet,
+        seeds=[
+            PREFIX.as_bytes(),
+            ESCROW_LOCK.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+            escrow_lock.order_hash.as_ref(),
+        ],
+        bump=escrow_lock.bump,
+    )]
+    escrow_lock: Box<Account<'info, EscrowLock>>,
+    system_program: Program<'info, System>,
+    // remaining accounts:
+    // notary_set, then notaries (signer) (optional) - auction house's NotarySet and threshold of its notaries, cosign in place of auction_house.notary, placed first
+    // then, if the lock holds a SPL payment mint:
+    // 0. payment_mint
+    // 1. lock_token_account - escrow_lock's ATA of payment_mint, closed to the wallet
+    // 2. escrow_token_account - escrow_payment_account's ATA of payment_mint, created if needed
+    // 3. token_program
+    // 4. associated_token_program
+}
+
+// Returns the locked funds to the wallet's escrow and closes the lock.
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, ReleaseEscrowLock<'info>>) -> Result<()> {
+    let escrow_lock = &ctx.accounts.escrow_lock;
+    let auction_house = &ctx.accounts.auction_house;
+    let wallet = &ctx.accounts.wallet;
+    let escrow_payment_account = &ctx.accounts.escrow_payment_account;
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        ctx.remaining_accounts,
+        auction_house,
+        &ctx.accounts.notary,
+    )?;
+
+    let notary_signed = notary_cosign.signed;
+    if escrow_lock.is_active(Clock::get()?.slot) && !notary_signed {
+        return Err(ErrorCode::EscrowLockActive.into());
+    }
+
+    let escrow_lock_info = escrow_lock.to_account_info();
+    if escrow_lock.payment_mint == Pubkey::default() {
+        // the lock is ours, so its lamports can be moved without the system program
+        **escrow_lock_info.try_borrow_mut_lamports()? = escrow_lock_info
+            .lamports()
+            .checked_sub(escrow_lock.amount)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        **escrow_payment_account.try_borrow_mut_lamports()? = escrow_payment_account
+            .lamports()
+            .checked_add(escrow_lock.amount)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+    } else {
+        assert_keys_equal(
+            index_ra!(remaining_accounts, 0).key,
+            &escrow_lock.payment_mint,
+        )?;
+        assert_keys_equal(index_ra!(remaining_accounts, 3).key, &spl_token::id())?;
+        let auction_house_key = auction_house.key();
+        transfer_token(
+            &escrow_lock.amount,
+            &ctx.accounts.payer,
+            &escrow_lock_info,
+            wallet,
+            None,
+            DestinationSpecifier::Ai(escrow_payment_account),
+            index_ra!(remaining_accounts, 0),
+            index_ra!(remaining_accounts, 1),
+            index_ra!(remaining_accounts, 2),
+            index_ra!(remaining_accounts, 3),
+            &ctx.accounts.system_program,
+            None,
+            &[&[
+                PREFIX.as_bytes(),
+                ESCROW_LOCK.as_bytes(),
+                auction_house_key.as_ref(),
+                wallet.key.as_ref(),
+                escrow_lock.order_hash.as_ref(),
+                &[escrow_lock.bump],
+            ]],
+        )?;
+    }
+
+    msg!(
+        "{{\"escrow_lock\":\"{}\",\"amount\":{},\"early\":{}}}",
+        escrow_lock.key(),
+        escrow_lock.amount,
+        notary_signed
+    );
+    Ok(())
+}
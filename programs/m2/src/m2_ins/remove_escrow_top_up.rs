@@ -0,0 +1,50 @@
+use {
+    crate::constants::*,
+    crate::states::*,
+    crate::utils::*,
+    anchor_lang::prelude::*,
+    anchor_spl::token::{revoke, Revoke, Token},
+};
+
+#[derive(Accounts)]
+pub struct RemoveEscrowTopUp<'info> {
+    #[account(mut)]
+    wallet: Signer<'info>,
+    /// CHECK: funding_token_account, revoked if it still delegates to escrow_top_up
+    #[account(mut, address=escrow_top_up.funding_token_account)]
+    funding_token_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            ESCROW_TOP_UP.as_bytes(),
+            escrow_top_up.auction_house_key.as_ref(),
+            wallet.key().as_ref(),
+        ],
+        bump=escrow_top_up.bump,
+        close=wallet,
+    )]
+    escrow_top_up: Box<Account<'info, EscrowTopUp>>,
+    token_program: Program<'info, Token>,
+}
+
+// Removes the wallet's escrow top-up, revoking what's left of the cap. The funding account may
+// already be closed or delegated elsewhere, then there's nothing to revoke.
+pub fn handle(ctx: Context<RemoveEscrowTopUp>) -> Result<()> {
+    let funding_token_account = &ctx.accounts.funding_token_account;
+    let delegated_to_top_up =
+        unpack_token_account(funding_token_account).map_or(false, |account| {
+            account.owner == ctx.accounts.wallet.key()
+                && account.delegate == Some(ctx.accounts.escrow_top_up.key()).into()
+        });
+    if delegated_to_top_up {
+        revoke(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Revoke {
+                source: funding_token_account.to_account_info(),
+                authority: ctx.accounts.wallet.to_account_info(),
+            },
+        ))?;
+    }
+    Ok(())
+}
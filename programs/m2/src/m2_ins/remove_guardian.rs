@@ -0,0 +1,19 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct RemoveGuardian<'info> {
+    #[account(mut)]
+    wallet: Signer<'info>,
+    #[account(
+        mut,
+        seeds=[PREFIX.as_bytes(), GUARDIAN.as_bytes(), wallet.key().as_ref()],
+        bump=guardian_account.bump,
+        close=wallet,
+    )]
+    guardian_account: Box<Account<'info, Guardian>>,
+}
+
+// Removes the wallet's guardian, only the wallet can cancel its orders again
+pub fn handle(_ctx: Context<RemoveGuardian>) -> Result<()> {
+    Ok(())
+}
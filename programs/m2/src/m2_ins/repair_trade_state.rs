@@ -0,0 +1,55 @@
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    crate::utils::{assert_owned_by, close_account_anchor, get_discriminator},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct RepairTradeState<'info> {
+    /// CHECK: wallet, receives the rent of the stranded trade state
+    #[account(mut)]
+    wallet: UncheckedAccount<'info>,
+    /// CHECK: token_mint, only used to derive the trade state
+    token_mint: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    /// CHECK: seeds check, and must be left without a discriminator
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    buyer_trade_state: AccountInfo<'info>,
+}
+
+// Closes a buyer trade state that was left allocated but without a discriminator, which no bid
+// instruction can use or cancel, and refunds its rent to the wallet so the bid can be placed again.
+// There is no bid in it to lose, so anyone can call this.
+pub fn handle(ctx: Context<RepairTradeState>) -> Result<()> {
+    let wallet = &ctx.accounts.wallet;
+    let buyer_trade_state = &ctx.accounts.buyer_trade_state;
+
+    if buyer_trade_state.data_is_empty() {
+        return Err(ErrorCode::EmptyTradeState.into());
+    }
+    assert_owned_by(buyer_trade_state, &crate::ID)?;
+    if get_discriminator(buyer_trade_state)? != [0; 8] {
+        return Err(ErrorCode::InvalidDiscriminator.into());
+    }
+
+    let lamports = buyer_trade_state.lamports();
+    close_account_anchor(buyer_trade_state, wallet, true)?;
+    msg!(
+        "{{\"repaired_trade_state\":\"{}\",\"lamports\":{}}}",
+        buyer_trade_state.key(),
+        lamports
+    );
+    Ok(())
+}
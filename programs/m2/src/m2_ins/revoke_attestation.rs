@@ -0,0 +1,27 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct RevokeAttestation<'info> {
+    #[account(mut)]
+    attestation_authority: Signer<'info>,
+    /// CHECK: wallet
+    wallet: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=attestation_authority)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        close=attestation_authority,
+        seeds=[
+            PREFIX.as_bytes(),
+            ATTESTATION.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        bump=attestation.bump,
+    )]
+    attestation: Box<Account<'info, Attestation>>,
+}
+
+pub fn handle(_ctx: Context<RevokeAttestation>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use {crate::constants::*, crate::errors::ErrorCode, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct ScheduleHouseUpdate<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    authority: Signer<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=authority)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        seeds=[
+            PREFIX.as_bytes(),
+            PENDING_HOUSE_UPDATE.as_bytes(),
+            auction_house.key().as_ref(),
+        ],
+        space=PendingHouseUpdate::LEN,
+        bump,
+    )]
+    pending_house_update: Box<Account<'info, PendingHouseUpdate>>,
+    system_program: Program<'info, System>,
+}
+
+// Schedules fee changes HOUSE_UPDATE_TIMELOCK_SECONDS from now. Scheduling again replaces the
+// pending values and restarts the timelock.
+pub fn handle(
+    ctx: Context<ScheduleHouseUpdate>,
+    seller_fee_basis_points: Option<u16>,
+    buyer_referral_bp: Option<u16>,
+    seller_referral_bp: Option<u16>,
+    protocol_fee_share_bp: Option<u16>,
+) -> Result<()> {
+    if [
+        seller_fee_basis_points,
+        buyer_referral_bp,
+        seller_referral_bp,
+        protocol_fee_share_bp,
+    ]
+    .iter()
+    .any(|bp| bp.map_or(false, |bp| bp > 10000))
+    {
+        return Err(ErrorCode::InvalidBasisPoints.into());
+    }
+
+    let effective_at = Clock::get()?
+        .unix_timestamp
+        .checked_add(HOUSE_UPDATE_TIMELOCK_SECONDS)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+    let pending_house_update = &mut ctx.accounts.pending_house_update;
+    pending_house_update.auction_house_key = ctx.accounts.auction_house.key();
+    pending_house_update.bump = ctx.bumps.pending_house_update;
+    pending_house_update.effective_at = effective_at;
+    pending_house_update.seller_fee_basis_points = seller_fee_basis_points;
+    pending_house_update.buyer_referral_bp = buyer_referral_bp;
+    pending_house_update.seller_referral_bp = seller_referral_bp;
+    pending_house_update.protocol_fee_share_bp = protocol_fee_share_bp;
+
+    msg!("{{\"effective_at\":{}}}", effective_at);
+    Ok(())
+}
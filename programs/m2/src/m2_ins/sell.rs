@@ -27,10 +27,7 @@ pub struct Sell<'info> {
     /// CHECK: token_ata is the account that will hold the token after ata creation and setAuthority from wallet to program_as_signer
     #[account(mut)]
     token_ata: UncheckedAccount<'info>,
-    #[account(
-        constraint = token_mint.supply == 1 @ ErrorCode::InvalidTokenMint,
-        constraint = token_mint.decimals == 0 @ ErrorCode::InvalidTokenMint,
-    )]
+    #[account(constraint = auction_house.is_tradable_mint(&token_mint) @ ErrorCode::InvalidTokenMint)]
     token_mint: Account<'info, Mint>,
     /// CHECK: metadata
     metadata: UncheckedAccount<'info>,
@@ -64,31 +61,116 @@ pub struct Sell<'info> {
     #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
     program_as_signer: UncheckedAccount<'info>,
     rent: Sysvar<'info, Rent>,
-    // remaining accounts:
+    // remaining accounts, in this order, or in any order if extra_args has a remaining accounts schema (see RemainingAccountsTag):
+    // notary_set, then notaries (signer) (optional) - auction house's NotarySet and threshold of its notaries, cosign in place of auction_house.notary for the rent vault, placed first
+    // program_config (optional) - the deployment's ProgramConfig, placed before the accounts below, its payment mints are accepted too, sell fails with Deprecated if it disabled it
+    // rate_limit (required if the auction house rate limits wallets, writable) - wallet's RateLimit, placed after program_config and before the accounts below
     // 0. payment_mint (optional) - if the seller wants payment in a SPL token, this is the mint of that token
     // ...
+    // mint_market (optional, writable) - token mint's MintMarket, the listing is offered as its best ask, placed before listing_index
+    // -5.. listing_index (optional) - seller's and verified collection's ListingIndex, the listing is recorded in them
+    // -4. maker_rewards (optional) - seller's MakerRewards, the listing is enrolled in maker rewards
+    // -3. referrer (optional) - Referrer registration of seller_referral, the referral is only stored if it is registered
+    // -2. rent_vault (optional) - auction house rent vault, pays for sts rent instead of the wallet if the notary signs and there's no payer
     // -1. payer (optional) - this wallet will try to pay for sts rent
+    // staking adapter, then its accounts (optional) - auction_house.staking_adapter, unstakes token_account if it's frozen, placed after the accounts above
+    // instructions sysvar (optional) - placed last if SellExtraArgs are appended to the instruction data
 }
 
+/// Length of the sell instruction data up to the end of its args, SellExtraArgs follow
+pub const SELL_ARGS_LEN: usize = 8 + 1 + 1 + 8 + 8 + 8;
+
+/// Where sell expects each group of remaining accounts
+const SELL_ORDER: [RemainingAccountsTag; 11] = [
+    RemainingAccountsTag::NotarySet,
+    RemainingAccountsTag::ProgramConfig,
+    RemainingAccountsTag::RateLimit,
+    RemainingAccountsTag::Payment,
+    RemainingAccountsTag::MintMarket,
+    RemainingAccountsTag::ListingIndex,
+    RemainingAccountsTag::MakerRewards,
+    RemainingAccountsTag::Referrer,
+    RemainingAccountsTag::RentVault,
+    RemainingAccountsTag::Payer,
+    RemainingAccountsTag::StakingAdapter,
+];
+
 pub fn handle<'info>(
     ctx: Context<'_, '_, '_, 'info, Sell<'info>>,
     _program_as_signer_bump: u8,
     buyer_price: u64,
     token_size: u64,
     seller_state_expiry: i64,
+    extra_args: &[u8],
 ) -> Result<()> {
+    let ordered_remaining_accounts =
+        order_remaining_accounts(ctx.remaining_accounts, extra_args, &SELL_ORDER)?;
+    let remaining_accounts = ordered_remaining_accounts
+        .as_deref()
+        .unwrap_or(ctx.remaining_accounts);
+    let extra_args = SellExtraArgs::parse(extra_args);
     let wallet = &ctx.accounts.wallet;
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house,
+        &ctx.accounts.notary,
+    )?;
+    let (remaining_accounts, program_config) =
+        split_program_config_from_remaining_accounts(remaining_accounts)?;
+    assert_instruction_enabled(program_config.as_ref(), GatedInstruction::Sell)?;
+    let remaining_accounts = split_rate_limit_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &ctx.accounts.auction_house,
+        wallet.key,
+    )?;
+    let (remaining_accounts, staking_adapter) = split_staking_adapter_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house,
+    );
     let (remaining_accounts, possible_payer) =
-        split_payer_from_remaining_accounts(ctx.remaining_accounts);
+        split_payer_from_remaining_accounts(remaining_accounts);
     let payer = if let Some(p) = possible_payer {
         p
     } else {
         wallet
     };
+    let (remaining_accounts, rent_vault) = split_rent_vault_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &notary_cosign,
+    );
+    let rent_vault_seeds = rent_vault.as_ref().map(|v| v.seeds());
+    let (rent_payer, rent_payer_seeds) = match (possible_payer, &rent_vault) {
+        (None, Some(vault)) => (vault.info, rent_vault_seeds.as_ref().map(|s| &s[..])),
+        _ => (payer, None),
+    };
+    let (remaining_accounts, referrer) = split_referrer_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        ctx.accounts.seller_referral.key,
+    );
+    let seller_referral = get_registered_referral(referrer, ctx.accounts.seller_referral.key)?;
+    let (remaining_accounts, maker_rewards) = split_maker_rewards_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        wallet.key,
+    )?;
+    let listing_index_keys =
+        get_listing_index_keys(wallet.key, Some(ctx.accounts.metadata.as_ref()));
+    let (remaining_accounts, listing_indexes) = split_listing_indexes_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &listing_index_keys,
+    );
+    let (remaining_accounts, mint_market) = split_mint_market_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &ctx.accounts.token_mint.key(),
+    );
     let token_mint = &ctx.accounts.token_mint;
     let metadata = &ctx.accounts.metadata;
     let seller_trade_state = &ctx.accounts.seller_trade_state;
-    let seller_referral = &ctx.accounts.seller_referral;
     let auction_house = &ctx.accounts.auction_house;
     let token_program = &ctx.accounts.token_program;
     let system_program = &ctx.accounts.system_program;
@@ -96,19 +178,25 @@ pub fn handle<'info>(
     let token_ata = &ctx.accounts.token_ata;
     let token_account = &ctx.accounts.token_account;
     let payment_mint = if remaining_accounts.len() == 1 {
-        assert_payment_mint(index_ra!(remaining_accounts, 0))?;
+        assert_payment_mint(index_ra!(remaining_accounts, 0), program_config.as_ref())?;
         Some(index_ra!(remaining_accounts, 0))
     } else {
         None
     };
+    assert_alt_prices(
+        &extra_args.alt_prices,
+        payment_mint.map_or(&Pubkey::default(), |m| m.key),
+        program_config.as_ref(),
+    )?;
 
     let token_ata_ai = token_ata.as_ref() as &AccountInfo;
     let token_account_ai = token_account.as_ref() as &AccountInfo;
 
     if !seller_trade_state.data_is_empty() {
-        let discriminator_ai = seller_trade_state.try_borrow_data()?;
-        if discriminator_ai[..8] != SellerTradeState::discriminator()
-            && discriminator_ai[..8] != SellerTradeStateV2::discriminator()
+        let discriminator = get_discriminator(seller_trade_state)?;
+        if discriminator != SellerTradeState::discriminator()
+            && discriminator != SellerTradeStateV2::discriminator()
+            && discriminator != SellerTradeStateV3::discriminator()
         {
             return Err(ErrorCode::InvalidDiscriminator.into());
         }
@@ -119,9 +207,16 @@ pub fn handle<'info>(
     if buyer_price > MAX_PRICE || buyer_price == 0 {
         return Err(ErrorCode::InvalidPrice.into());
     }
+    // a frozen account would fail the transfer or set_authority below with an opaque token error
+    assert_not_frozen_or_unstake(
+        token_account_ai,
+        token_mint.as_ref(),
+        wallet,
+        staking_adapter.as_ref(),
+    )?;
     if token_account_ai.key != token_ata_ai.key {
         transfer_token(
-            &1,
+            &token_size,
             payer,
             wallet,
             wallet,
@@ -141,9 +236,7 @@ pub fn handle<'info>(
     // seller_state_expiry < 0, non-movable listing mode
     //   - with program_as_signer to hold the authority
     //   - the sts will be closed when delist
-    if seller_state_expiry >= 0 {
-        return Err(ErrorCode::InvalidExpiry.into());
-    }
+    assert_expiry_mode(seller_state_expiry, true)?;
     if !is_token_owner(token_ata_ai, program_as_signer.key)? {
         anchor_spl::token::set_authority(
             CpiContext::new(
@@ -159,12 +252,13 @@ pub fn handle<'info>(
     } else if seller_trade_state.data_is_empty() {
         // so token owner is already program_as_signer, but token_size is 0
         // this is likely a relist from other auction house, not change sell price, we should simply block it
-        return Err(ErrorCode::InvalidAccountState.into());
+        return Err(ErrorCode::TokenAlreadyEscrowed.into());
     }
 
     create_or_realloc_seller_trade_state(
         seller_trade_state,
-        payer,
+        rent_payer,
+        rent_payer_seeds,
         &[
             PREFIX.as_bytes(),
             wallet.key().as_ref(),
@@ -174,25 +268,57 @@ pub fn handle<'info>(
             &[ctx.bumps.seller_trade_state],
         ],
     )?;
-    let sts = SellerTradeStateV2 {
-        auction_house_key: auction_house.key(),
-        seller: wallet.key(),
-        seller_referral: seller_referral.key(),
-        buyer_price,
-        token_mint: token_mint.key(),
-        token_account: token_ata_ai.key(),
-        token_size,
-        bump: ctx.bumps.seller_trade_state,
-        expiry: seller_state_expiry,
-        payment_mint: if let Some(m) = payment_mint {
-            *m.key
-        } else {
-            Pubkey::default()
+    write_seller_trade_state(
+        seller_trade_state,
+        SellerTradeStateV3 {
+            auction_house_key: auction_house.key(),
+            seller: wallet.key(),
+            seller_referral,
+            buyer_price,
+            token_mint: token_mint.key(),
+            token_account: token_ata_ai.key(),
+            token_size,
+            bump: ctx.bumps.seller_trade_state,
+            expiry: seller_state_expiry,
+            payment_mint: if let Some(m) = payment_mint {
+                *m.key
+            } else {
+                Pubkey::default()
+            },
+            origin: extra_args.origin,
+            order_memo: extra_args.order_memo,
+            proceeds_as_wsol: extra_args.proceeds_as_wsol,
+            auto_relist_price_bp: extra_args.auto_relist_price_bp,
+            auto_relist_max: extra_args.auto_relist_max,
+            royalty_mode: extra_args.royalty_mode,
+            requires_attestation: extra_args.requires_attestation,
+            auto_match: extra_args.auto_match,
+            royalty_lock: extra_args.royalty_lock,
+            hidden_until: extra_args.hidden_until,
+            repay_loan: extra_args.repay_loan,
+            royalty_bp_snapshot: get_royalty_bp_snapshot(extra_args.royalty_lock, metadata)?,
+            maker_rewards,
+            ..Default::default()
         },
-    };
-    let sts_v2_serialized = sts.try_to_vec()?;
-    seller_trade_state.try_borrow_mut_data()?[8..8 + sts_v2_serialized.len()]
-        .copy_from_slice(&sts_v2_serialized);
+    )?;
+    write_seller_alt_prices(
+        seller_trade_state,
+        rent_payer,
+        rent_payer_seeds,
+        &extra_args.alt_prices,
+    )?;
+    let sell_args = SellArgs::from_account_info(seller_trade_state)?;
+    let sol_price = sell_args.visible_sol_price(Clock::get()?.unix_timestamp);
+    record_listing(
+        &listing_indexes,
+        payer,
+        &auction_house.key(),
+        seller_trade_state,
+        sol_price,
+    )?;
+    update_mint_market(mint_market, |market| {
+        market.offer_ask(seller_trade_state.key, sol_price, token_size)
+    })?;
 
     msg!(
         "{{\"price\":{},\"seller_expiry\":{}}}",
@@ -0,0 +1,157 @@
+use mpl_token_metadata::accounts::MasterEdition;
+
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    crate::utils::*,
+    anchor_lang::prelude::*,
+    anchor_spl::token::{Mint, SetAuthority, Token, TokenAccount},
+    spl_token::instruction::AuthorityType,
+};
+
+#[derive(Accounts)]
+pub struct SellEdition<'info> {
+    #[account(mut)]
+    wallet: Signer<'info>,
+    #[account(mut, constraint = token_account.mint == token_mint.key())]
+    token_account: Account<'info, TokenAccount>,
+    #[account(
+        constraint = token_mint.supply == 1 @ ErrorCode::InvalidTokenMint,
+        constraint = token_mint.decimals == 0 @ ErrorCode::InvalidTokenMint,
+    )]
+    token_mint: Account<'info, Mint>,
+    /// CHECK: metadata
+    metadata: UncheckedAccount<'info>,
+    /// CHECK: master_edition, deserialized in the handler
+    #[account(
+        seeds = [
+            "metadata".as_bytes(),
+            mpl_token_metadata::ID.as_ref(),
+            token_mint.key().as_ref(),
+            "edition".as_bytes(),
+        ],
+        bump,
+        seeds::program = mpl_token_metadata::ID,
+    )]
+    master_edition: UncheckedAccount<'info>,
+    /// CHECK: authority
+    authority: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=authority)]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+    #[account(
+        init_if_needed,
+        payer=wallet,
+        seeds=[
+            PREFIX.as_bytes(),
+            EDITION_SALE.as_bytes(),
+            auction_house.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        space=EditionSaleState::LEN,
+        bump,
+    )]
+    edition_sale_state: Box<Account<'info, EditionSaleState>>,
+    /// CHECK: seeds check, a listing of the master edition token must not exist
+    #[account(
+        seeds=[
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    seller_trade_state: UncheckedAccount<'info>,
+    /// CHECK: program_as_signer
+    #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
+    program_as_signer: UncheckedAccount<'info>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+// Lists prints of a master edition: the master edition token stays in the seller's ATA with
+// program_as_signer as its owner, so execute_edition_sale can print from it. Calling this again
+// updates the price and the number of prints for sale. The token can't be listed itself meanwhile:
+// a listing of it blocks the edition sale, and sell can't list a token program_as_signer already
+// owns without one.
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, SellEdition<'info>>,
+    price: u64,
+    max_prints: u64,
+) -> Result<()> {
+    let wallet = &ctx.accounts.wallet;
+    let token_account = &ctx.accounts.token_account;
+    let token_mint = &ctx.accounts.token_mint;
+    let program_as_signer = &ctx.accounts.program_as_signer;
+    let edition_sale_state = &mut ctx.accounts.edition_sale_state;
+
+    if price > MAX_PRICE || price == 0 {
+        return Err(ErrorCode::InvalidPrice.into());
+    }
+    let is_new_sale = edition_sale_state.seller == Pubkey::default();
+    if !is_new_sale && edition_sale_state.seller != wallet.key() {
+        return Err(ErrorCode::TradeStateMismatch.into());
+    }
+    if max_prints <= edition_sale_state.printed {
+        return Err(ErrorCode::InvalidTokenAmount.into());
+    }
+
+    assert_metadata_valid(&ctx.accounts.metadata, &token_mint.key())?;
+    assert_owned_by(&ctx.accounts.master_edition, &mpl_token_metadata::ID)?;
+    let master_edition = MasterEdition::from_bytes(&ctx.accounts.master_edition.data.borrow())
+        .map_err(|_| ErrorCode::InvalidMasterEdition)?;
+    if let Some(max_supply) = master_edition.max_supply {
+        let prints_left = max_supply.saturating_sub(master_edition.supply);
+        if max_prints - edition_sale_state.printed > prints_left {
+            return Err(ErrorCode::InvalidTokenAmount.into());
+        }
+    }
+
+    assert_is_ata(
+        token_account.as_ref(),
+        wallet.key,
+        &token_mint.key(),
+        program_as_signer.key,
+    )?;
+    // a listing of the master token, or a delegate anything else holds on it, could still move it
+    if !ctx.accounts.seller_trade_state.data_is_empty() {
+        return Err(ErrorCode::TokenAlreadyEscrowed.into());
+    }
+    if token_account.delegate.is_some() {
+        return Err(ErrorCode::DelegateMismatch.into());
+    }
+    if token_account.owner == wallet.key() {
+        anchor_spl::token::set_authority(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    account_or_mint: token_account.to_account_info(),
+                    current_authority: wallet.to_account_info(),
+                },
+            ),
+            AuthorityType::AccountOwner,
+            Some(program_as_signer.key()),
+        )?;
+    } else if is_new_sale {
+        // program_as_signer already owns the token, it's listed somewhere else
+        return Err(ErrorCode::TokenAlreadyEscrowed.into());
+    }
+
+    edition_sale_state.auction_house_key = ctx.accounts.auction_house.key();
+    edition_sale_state.seller = wallet.key();
+    edition_sale_state.master_mint = token_mint.key();
+    edition_sale_state.token_account = token_account.key();
+    edition_sale_state.price = price;
+    edition_sale_state.max_prints = max_prints;
+    edition_sale_state.bump = ctx.bumps.edition_sale_state;
+
+    msg!(
+        "{{\"price\":{},\"max_prints\":{},\"printed\":{}}}",
+        price,
+        max_prints,
+        edition_sale_state.printed
+    );
+    Ok(())
+}
@@ -0,0 +1,311 @@
+use anchor_lang::Discriminator;
+use mpl_token_metadata::{accounts::Metadata, types::TokenStandard};
+
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    crate::utils::*,
+    anchor_lang::prelude::*,
+    anchor_spl::token::{approve, Approve, Mint, Token, TokenAccount},
+};
+
+#[derive(Accounts)]
+pub struct SellFrozen<'info> {
+    /// CHECK: wallet must sign, otherwise a seller_authority its co-owners approved the listing for
+    #[account(mut)]
+    wallet: UncheckedAccount<'info>,
+    /// CHECK: notary is not dangerous because we don't read or write from this account
+    notary: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = token_account.mint == token_mint.key(),
+        constraint = token_account.owner == wallet.key() @ ErrorCode::IncorrectOwner,
+    )]
+    token_account: Account<'info, TokenAccount>,
+    #[account(
+        constraint = token_mint.supply == 1 @ ErrorCode::InvalidTokenMint,
+        constraint = token_mint.decimals == 0 @ ErrorCode::InvalidTokenMint,
+    )]
+    token_mint: Account<'info, Mint>,
+    /// CHECK: metadata
+    metadata: UncheckedAccount<'info>,
+    /// CHECK: edition, checked when freezing
+    edition: UncheckedAccount<'info>,
+    /// CHECK: authority
+    authority: UncheckedAccount<'info>,
+    #[account(
+      seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()],
+      has_one=authority,
+      bump,
+    )]
+    auction_house: Account<'info, AuctionHouse>,
+    /// CHECK: checked in seeds
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    seller_trade_state: UncheckedAccount<'info>,
+    /// CHECK: seller_referral
+    seller_referral: UncheckedAccount<'info>,
+    /// CHECK: program_as_signer
+    #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
+    program_as_signer: UncheckedAccount<'info>,
+    /// CHECK: checked by address
+    #[account(address = mpl_token_metadata::ID)]
+    token_metadata_program: UncheckedAccount<'info>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+    // remaining accounts, in this order, or in any order if extra_args has a remaining accounts schema (see RemainingAccountsTag):
+    // program_config (optional) - the deployment's ProgramConfig, placed before the accounts below, its payment mints are accepted too
+    // rate_limit (required if the auction house rate limits wallets, writable) - wallet's RateLimit, placed after program_config and before the accounts below
+    // co_owner_approval (writable), then seller_authority (signer, writable) (required if wallet doesn't sign) - the token's CoOwnerApproval for wallet, the seller_authority pays the rent, placed before the accounts below
+    // 0. payment_mint (optional) - if the seller wants payment in a SPL token, this is the mint of that token
+    // mint_market (optional, writable) - token mint's MintMarket, the listing is offered as its best ask, placed before listing_index
+    // -3.. listing_index (optional) - seller's and verified collection's ListingIndex, the listing is recorded in them
+    // -2. maker_rewards (optional) - seller's MakerRewards, the listing is enrolled in maker rewards
+    // -1. referrer (optional) - Referrer registration of seller_referral, the referral is only stored if it is registered
+}
+
+/// Where sell_frozen expects each group of remaining accounts
+pub const SELL_FROZEN_ORDER: [RemainingAccountsTag; 8] = [
+    RemainingAccountsTag::ProgramConfig,
+    RemainingAccountsTag::RateLimit,
+    RemainingAccountsTag::CoOwnerApproval,
+    RemainingAccountsTag::Payment,
+    RemainingAccountsTag::MintMarket,
+    RemainingAccountsTag::ListingIndex,
+    RemainingAccountsTag::MakerRewards,
+    RemainingAccountsTag::Referrer,
+];
+
+// Lists a vanilla NFT without moving it: program_as_signer becomes the delegate and freezes the
+// seller's ATA through Token Metadata, so the NFT stays visible in the seller's wallet. The sale
+// and cancel_sell thaw it again. Calling this again on a frozen listing reprices it. A wallet
+// that can't sign, e.g. a multisig PDA, lists through a co-owner once it delegated the token to
+// program_as_signer itself.
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, SellFrozen<'info>>,
+    price: u64,
+    expiry: i64,
+    extra_args: &[u8],
+) -> Result<()> {
+    let terms = extra_args;
+    let extra_args = SellExtraArgs::parse(extra_args);
+    let wallet = &ctx.accounts.wallet;
+    let token_account = &ctx.accounts.token_account;
+    let token_mint = &ctx.accounts.token_mint;
+    let auction_house = &ctx.accounts.auction_house;
+    let seller_trade_state = &ctx.accounts.seller_trade_state;
+    let program_as_signer = &ctx.accounts.program_as_signer;
+    let listing_index_keys = get_listing_index_keys(wallet.key, Some(&ctx.accounts.metadata));
+    let (remaining_accounts, program_config) =
+        split_program_config_from_remaining_accounts(ctx.remaining_accounts)?;
+    let remaining_accounts = split_rate_limit_from_remaining_accounts(
+        remaining_accounts,
+        &auction_house.key(),
+        auction_house,
+        wallet.key,
+    )?;
+    let (remaining_accounts, seller_authority) = split_co_owner_authority_from_remaining_accounts(
+        remaining_accounts,
+        wallet,
+        &token_mint.key(),
+    )?;
+    let payer = match seller_authority {
+        Some((_, seller_authority)) => seller_authority,
+        None if wallet.is_signer => wallet.as_ref(),
+        None => return Err(ErrorCode::NoValidSignerPresent.into()),
+    };
+    let (remaining_accounts, referrer) = split_referrer_from_remaining_accounts(
+        remaining_accounts,
+        &auction_house.key(),
+        ctx.accounts.seller_referral.key,
+    );
+    let seller_referral = get_registered_referral(referrer, ctx.accounts.seller_referral.key)?;
+    let (remaining_accounts, maker_rewards) = split_maker_rewards_from_remaining_accounts(
+        remaining_accounts,
+        &auction_house.key(),
+        wallet.key,
+    )?;
+    let (remaining_accounts, listing_indexes) = split_listing_indexes_from_remaining_accounts(
+        remaining_accounts,
+        &auction_house.key(),
+        &listing_index_keys,
+    );
+    let (remaining_accounts, mint_market) = split_mint_market_from_remaining_accounts(
+        remaining_accounts,
+        &auction_house.key(),
+        &token_mint.key(),
+    );
+    let payment_mint = if remaining_accounts.len() == 1 {
+        assert_payment_mint(&remaining_accounts[0], program_config.as_ref())?;
+        remaining_accounts[0].key()
+    } else {
+        Pubkey::default()
+    };
+    if let Some((co_owner_approval, seller_authority)) = seller_authority {
+        use_co_owner_approval(
+            co_owner_approval,
+            seller_authority,
+            &CoOwnerApproval::action_hash(price, expiry, &payment_mint, terms),
+        )?;
+    }
+
+    if price > MAX_PRICE || price == 0 {
+        return Err(ErrorCode::InvalidPrice.into());
+    }
+    // frozen listings are always delegate mode
+    assert_expiry_mode(expiry, false)?;
+    assert_is_ata(
+        token_account.as_ref(),
+        wallet.key,
+        &token_mint.key(),
+        wallet.key,
+    )?;
+    assert_metadata_valid(&ctx.accounts.metadata, &token_mint.key())?;
+    let metadata = Metadata::safe_deserialize(&ctx.accounts.metadata.data.borrow())?;
+    if !matches!(
+        metadata.token_standard,
+        None | Some(TokenStandard::NonFungible)
+    ) {
+        return Err(ErrorCode::InvalidTokenStandard.into());
+    }
+
+    let is_new_listing = seller_trade_state.data_is_empty();
+    if !is_new_listing {
+        let discriminator = get_discriminator(seller_trade_state)?;
+        if discriminator != SellerTradeState::discriminator()
+            && discriminator != SellerTradeStateV2::discriminator()
+            && discriminator != SellerTradeStateV3::discriminator()
+        {
+            return Err(ErrorCode::InvalidDiscriminator.into());
+        }
+    }
+
+    if token_account.is_frozen() {
+        // only a frozen listing of ours can be repriced, anything else froze it for its own reasons
+        if is_new_listing || !SellArgs::from_account_info(seller_trade_state)?.frozen {
+            return Err(ErrorCode::TokenAlreadyEscrowed.into());
+        }
+        // frozen listings are of a supply 1 mint, the repriced listing is rewritten for 1 token
+        if SellArgs::from_account_info(seller_trade_state)?.token_size > 1 {
+            return Err(ErrorCode::InvalidTokenAmount.into());
+        }
+        if token_account.delegate != Some(program_as_signer.key()).into() {
+            return Err(ErrorCode::DelegateMismatch.into());
+        }
+    } else {
+        if seller_authority.is_some() {
+            // the co-owned wallet delegates the token itself, its co-owners can't approve for it
+            if token_account.delegate != Some(program_as_signer.key()).into()
+                || token_account.delegated_amount == 0
+            {
+                return Err(ErrorCode::DelegateMismatch.into());
+            }
+        } else {
+            approve(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Approve {
+                        to: token_account.to_account_info(),
+                        delegate: program_as_signer.to_account_info(),
+                        authority: wallet.to_account_info(),
+                    },
+                ),
+                1,
+            )?;
+        }
+        set_delegated_account_frozen(
+            true,
+            program_as_signer,
+            token_account.as_ref(),
+            &ctx.accounts.edition,
+            token_mint.as_ref(),
+            &ctx.accounts.token_program,
+            &ctx.accounts.token_metadata_program,
+            &[&[
+                PREFIX.as_bytes(),
+                SIGNER.as_bytes(),
+                &[ctx.bumps.program_as_signer],
+            ]],
+        )?;
+    }
+
+    assert_alt_prices(
+        &extra_args.alt_prices,
+        &payment_mint,
+        program_config.as_ref(),
+    )?;
+
+    create_or_realloc_seller_trade_state(
+        seller_trade_state,
+        payer,
+        None,
+        &[
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            token_mint.key().as_ref(),
+            &[ctx.bumps.seller_trade_state],
+        ],
+    )?;
+    write_seller_trade_state(
+        seller_trade_state,
+        SellerTradeStateV3 {
+            auction_house_key: auction_house.key(),
+            seller: wallet.key(),
+            seller_referral,
+            buyer_price: price,
+            token_mint: token_mint.key(),
+            token_account: token_account.key(),
+            token_size: 1,
+            bump: ctx.bumps.seller_trade_state,
+            expiry,
+            payment_mint,
+            origin: extra_args.origin,
+            order_memo: extra_args.order_memo,
+            proceeds_as_wsol: extra_args.proceeds_as_wsol,
+            auto_relist_price_bp: extra_args.auto_relist_price_bp,
+            auto_relist_max: extra_args.auto_relist_max,
+            royalty_mode: extra_args.royalty_mode,
+            requires_attestation: extra_args.requires_attestation,
+            auto_match: extra_args.auto_match,
+            royalty_lock: extra_args.royalty_lock,
+            hidden_until: extra_args.hidden_until,
+            repay_loan: extra_args.repay_loan,
+            royalty_bp_snapshot: get_royalty_bp_snapshot(
+                extra_args.royalty_lock,
+                &ctx.accounts.metadata,
+            )?,
+            maker_rewards,
+            frozen: true,
+            ..Default::default()
+        },
+    )?;
+    write_seller_alt_prices(seller_trade_state, payer, None, &extra_args.alt_prices)?;
+
+    let sell_args = SellArgs::from_account_info(seller_trade_state)?;
+    let sol_price = sell_args.visible_sol_price(Clock::get()?.unix_timestamp);
+    record_listing(
+        &listing_indexes,
+        payer,
+        &auction_house.key(),
+        seller_trade_state,
+        sol_price,
+    )?;
+    update_mint_market(mint_market, |market| {
+        market.offer_ask(seller_trade_state.key, sol_price, sell_args.token_size)
+    })?;
+
+    msg!("{{\"price\":{},\"seller_expiry\":{}}}", price, expiry);
+    Ok(())
+}
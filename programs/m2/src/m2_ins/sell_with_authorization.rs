@@ -0,0 +1,247 @@
+use anchor_lang::Discriminator;
+use solana_program::sysvar;
+
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    crate::utils::*,
+    anchor_lang::{prelude::*, AnchorDeserialize},
+    anchor_spl::token::{Mint, Token, TokenAccount},
+};
+
+/// The message the seller signs off-chain, Borsh serialized. It covers every account the relayer
+/// could otherwise pick for the seller.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SellAuthorization {
+    pub auction_house: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_account: Pubkey,
+    pub seller_referral: Pubkey,
+    pub price: u64,
+    pub expiry: i64,
+    pub nonce: u64,
+}
+
+#[derive(Accounts)]
+pub struct SellWithAuthorization<'info> {
+    /// CHECK: seller doesn't sign the transaction, the ed25519 instruction carries the seller's signature
+    wallet: UncheckedAccount<'info>,
+    notary: Signer<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(
+        constraint = token_account.mint == token_mint.key(),
+        constraint = token_account.owner == wallet.key() @ ErrorCode::IncorrectOwner,
+    )]
+    token_account: Account<'info, TokenAccount>,
+    #[account(
+        constraint = token_mint.supply == 1 @ ErrorCode::InvalidTokenMint,
+        constraint = token_mint.decimals == 0 @ ErrorCode::InvalidTokenMint,
+    )]
+    token_mint: Account<'info, Mint>,
+    /// CHECK: metadata
+    metadata: UncheckedAccount<'info>,
+    /// CHECK: authority
+    authority: UncheckedAccount<'info>,
+    #[account(
+      seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()],
+      has_one=authority,
+      bump,
+    )]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+    /// CHECK: checked in seeds
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    seller_trade_state: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        seeds=[
+            PREFIX.as_bytes(),
+            AUTHORIZATION_NONCE.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        space=AuthorizationNonce::LEN,
+        bump,
+    )]
+    authorization_nonce: Box<Account<'info, AuthorizationNonce>>,
+    /// CHECK: seller_referral
+    seller_referral: UncheckedAccount<'info>,
+    /// CHECK: program_as_signer
+    #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
+    program_as_signer: UncheckedAccount<'info>,
+    /// CHECK: checked by address
+    #[account(address = sysvar::instructions::id())]
+    instructions: UncheckedAccount<'info>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+    // remaining accounts, in this order, or in any order with a remaining accounts schema appended to the instruction data (see RemainingAccountsTag):
+    // notary_set, then notaries (signer) (optional) - auction house's NotarySet and threshold of its notaries, cosign in place of auction_house.notary, placed first
+    // program_config (optional) - the deployment's ProgramConfig, the listing fails with Deprecated if it disabled sell
+    // 0.. listing_index (optional) - seller's and verified collection's ListingIndex, the listing is recorded in them
+    // referrer (optional) - Referrer registration of seller_referral, the referral is only stored if it is registered
+    // mint_market (optional, writable) - token mint's MintMarket, the listing is offered as its best ask, placed before listing_index
+    // instructions sysvar (optional) - placed last if a remaining accounts schema is appended to the instruction data
+}
+
+/// Length of the sell_with_authorization instruction data up to the end of its args
+pub const SELL_WITH_AUTHORIZATION_ARGS_LEN: usize = 8 + 8 + 8 + 8;
+
+/// Where sell_with_authorization expects each group of remaining accounts
+pub const SELL_WITH_AUTHORIZATION_ORDER: [RemainingAccountsTag; 3] = [
+    RemainingAccountsTag::NotarySet,
+    RemainingAccountsTag::Keyed,
+    RemainingAccountsTag::ListingIndex,
+];
+
+// Lists without a seller transaction: the seller has approved program_as_signer as the delegate
+// of the token account beforehand, and signs the listing terms off-chain. The notary relays it and
+// the payer funds the trade state rent, so this is always a delegate mode listing (expiry >= 0).
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, SellWithAuthorization<'info>>,
+    price: u64,
+    expiry: i64,
+    nonce: u64,
+) -> Result<()> {
+    let wallet = &ctx.accounts.wallet;
+    let token_account = &ctx.accounts.token_account;
+    let token_mint = &ctx.accounts.token_mint;
+    let auction_house = &ctx.accounts.auction_house;
+    let seller_trade_state = &ctx.accounts.seller_trade_state;
+    let authorization_nonce = &mut ctx.accounts.authorization_nonce;
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        ctx.remaining_accounts,
+        auction_house,
+        &ctx.accounts.notary,
+    )?;
+    notary_cosign.assert_signed()?;
+    assert_instruction_enabled(
+        find_program_config(remaining_accounts)?.as_ref(),
+        GatedInstruction::Sell,
+    )?;
+
+    if price > MAX_PRICE || price == 0 {
+        return Err(ErrorCode::InvalidPrice.into());
+    }
+    assert_expiry_mode(expiry, false)?;
+
+    let message = SellAuthorization {
+        auction_house: auction_house.key(),
+        token_mint: token_mint.key(),
+        token_account: token_account.key(),
+        seller_referral: ctx.accounts.seller_referral.key(),
+        price,
+        expiry,
+        nonce,
+    }
+    .try_to_vec()?;
+    assert_ed25519_signed(&ctx.accounts.instructions, wallet.key, &message)?;
+
+    consume_authorization_nonce(
+        authorization_nonce,
+        &auction_house.key(),
+        wallet.key,
+        ctx.bumps.authorization_nonce,
+        nonce,
+    )?;
+
+    assert_is_ata(
+        token_account.as_ref(),
+        wallet.key,
+        &token_mint.key(),
+        wallet.key,
+    )?;
+    if token_account.delegate != Some(ctx.accounts.program_as_signer.key()).into()
+        || token_account.delegated_amount < 1
+    {
+        return Err(ErrorCode::DelegateMismatch.into());
+    }
+    assert_metadata_valid(&ctx.accounts.metadata, &token_mint.key())?;
+
+    let is_new_listing = seller_trade_state.data_is_empty();
+    if !is_new_listing {
+        let discriminator = get_discriminator(seller_trade_state)?;
+        if discriminator != SellerTradeState::discriminator()
+            && discriminator != SellerTradeStateV2::discriminator()
+            && discriminator != SellerTradeStateV3::discriminator()
+        {
+            return Err(ErrorCode::InvalidDiscriminator.into());
+        }
+    }
+
+    create_or_realloc_seller_trade_state(
+        seller_trade_state,
+        &ctx.accounts.payer,
+        None,
+        &[
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            token_mint.key().as_ref(),
+            &[ctx.bumps.seller_trade_state],
+        ],
+    )?;
+    write_seller_trade_state(
+        seller_trade_state,
+        SellerTradeStateV3 {
+            auction_house_key: auction_house.key(),
+            seller: wallet.key(),
+            seller_referral: get_registered_referral(
+                find_remaining_account(
+                    remaining_accounts,
+                    &get_referrer_address(&auction_house.key(), ctx.accounts.seller_referral.key),
+                ),
+                ctx.accounts.seller_referral.key,
+            )?,
+            buyer_price: price,
+            token_mint: token_mint.key(),
+            token_account: token_account.key(),
+            token_size: 1,
+            bump: ctx.bumps.seller_trade_state,
+            expiry,
+            payment_mint: Pubkey::default(),
+            ..Default::default()
+        },
+    )?;
+
+    let listing_index_keys = get_listing_index_keys(wallet.key, Some(&ctx.accounts.metadata));
+    let (_, listing_indexes) = split_listing_indexes_from_remaining_accounts(
+        remaining_accounts,
+        &auction_house.key(),
+        &listing_index_keys,
+    );
+    record_listing(
+        &listing_indexes,
+        &ctx.accounts.payer,
+        &auction_house.key(),
+        seller_trade_state,
+        Some(price),
+    )?;
+    update_mint_market(
+        find_remaining_account(
+            remaining_accounts,
+            &get_mint_market_address(&auction_house.key(), &token_mint.key()),
+        ),
+        |market| market.offer_ask(seller_trade_state.key, Some(price), 1),
+    )?;
+
+    msg!(
+        "{{\"price\":{},\"seller_expiry\":{},\"nonce\":{}}}",
+        price,
+        expiry,
+        nonce
+    );
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct SetCancelAuthority<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()],
+        bump=auction_house.bump,
+        has_one=authority,
+    )]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init_if_needed,
+        payer=authority,
+        seeds=[
+            PREFIX.as_bytes(),
+            CANCEL_AUTHORITY_REGISTRY.as_bytes(),
+            auction_house.key().as_ref(),
+        ],
+        space=CancelAuthorityRegistry::LEN,
+        bump,
+    )]
+    cancel_authority_registry: Box<Account<'info, CancelAuthorityRegistry>>,
+    system_program: Program<'info, System>,
+}
+
+// Enables or disables a key in the auction house's CancelAuthorityRegistry. The first call
+// creates the registry and switches the house over to it, so CANCEL_AUTHORITY stops working
+// there unless it is added as well.
+pub fn handle(ctx: Context<SetCancelAuthority>, key: Pubkey, enabled: bool) -> Result<()> {
+    let auction_house = &mut ctx.accounts.auction_house;
+    let registry = &mut ctx.accounts.cancel_authority_registry;
+    registry.auction_house_key = auction_house.key();
+    registry.bump = ctx.bumps.cancel_authority_registry;
+    registry.set(key, enabled)?;
+    auction_house.cancel_authority_registry = true;
+    msg!(
+        "{{\"cancel_authority\":\"{}\",\"enabled\":{}}}",
+        key,
+        enabled
+    );
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct SetCoOwners<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    /// the co-owned wallet, e.g. a multisig PDA signing through its program
+    seller: Signer<'info>,
+    /// CHECK: token_mint, only used to derive the approval
+    token_mint: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        seeds=[
+            PREFIX.as_bytes(),
+            CO_OWNER_APPROVAL.as_bytes(),
+            seller.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        space=CoOwnerApproval::LEN,
+        bump,
+    )]
+    co_owner_approval: Box<Account<'info, CoOwnerApproval>>,
+    system_program: Program<'info, System>,
+}
+
+// Sets who can list and cancel the seller's token for it and how many of them have to approve
+// each action. Replacing the co-owners drops the approvals collected so far.
+pub fn handle(ctx: Context<SetCoOwners>, owners: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    let co_owner_approval = &mut ctx.accounts.co_owner_approval;
+    co_owner_approval.seller = ctx.accounts.seller.key();
+    co_owner_approval.token_mint = ctx.accounts.token_mint.key();
+    co_owner_approval.bump = ctx.bumps.co_owner_approval;
+    co_owner_approval.set_owners(&owners, threshold)?;
+
+    msg!(
+        "{{\"owners\":{},\"threshold\":{}}}",
+        owners.len(),
+        threshold
+    );
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct SetCpiPolicy<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()],
+        bump=auction_house.bump,
+        has_one=authority,
+    )]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init_if_needed,
+        payer=authority,
+        seeds=[
+            PREFIX.as_bytes(),
+            CPI_ALLOWLIST.as_bytes(),
+            auction_house.key().as_ref(),
+        ],
+        space=CpiAllowlist::LEN,
+        bump,
+    )]
+    cpi_allowlist: Box<Account<'info, CpiAllowlist>>,
+    system_program: Program<'info, System>,
+}
+
+// Sets which programs may CPI into the auction house's sales, and allows or disallows program in
+// its CpiAllowlist if one is given. Under CpiPolicy::Allowlist an empty allowlist rejects every CPI.
+pub fn handle(
+    ctx: Context<SetCpiPolicy>,
+    cpi_policy: CpiPolicy,
+    program: Option<Pubkey>,
+    allowed: bool,
+) -> Result<()> {
+    let auction_house = &mut ctx.accounts.auction_house;
+    let allowlist = &mut ctx.accounts.cpi_allowlist;
+    allowlist.auction_house_key = auction_house.key();
+    allowlist.bump = ctx.bumps.cpi_allowlist;
+    if let Some(program) = program {
+        allowlist.set(program, allowed)?;
+    }
+    auction_house.cpi_policy = cpi_policy;
+    msg!(
+        "{{\"cpi_policy\":\"{:?}\",\"program\":\"{}\",\"allowed\":{}}}",
+        cpi_policy,
+        program.unwrap_or_default(),
+        allowed
+    );
+    Ok(())
+}
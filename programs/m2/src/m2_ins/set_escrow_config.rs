@@ -0,0 +1,43 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct SetEscrowConfig<'info> {
+    #[account(mut)]
+    wallet: Signer<'info>,
+    /// CHECK: authority
+    authority: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=authority)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init_if_needed,
+        payer=wallet,
+        seeds=[
+            PREFIX.as_bytes(),
+            ESCROW_CONFIG.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        space=EscrowConfig::LEN,
+        bump)]
+    escrow_config: Box<Account<'info, EscrowConfig>>,
+    system_program: Program<'info, System>,
+}
+
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, SetEscrowConfig<'info>>,
+    keep_open: bool,
+    min_resident_balance: u64,
+) -> Result<()> {
+    let escrow_config = &mut ctx.accounts.escrow_config;
+    escrow_config.auction_house_key = ctx.accounts.auction_house.key();
+    escrow_config.wallet = ctx.accounts.wallet.key();
+    escrow_config.keep_open = keep_open;
+    escrow_config.min_resident_balance = min_resident_balance;
+    escrow_config.bump = ctx.bumps.escrow_config;
+    msg!(
+        "{{\"keep_open\":{},\"min_resident_balance\":{}}}",
+        keep_open,
+        min_resident_balance
+    );
+    Ok(())
+}
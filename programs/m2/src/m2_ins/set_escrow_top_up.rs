@@ -0,0 +1,65 @@
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    anchor_lang::prelude::*,
+    anchor_spl::token::{approve, Approve, Token, TokenAccount},
+};
+
+#[derive(Accounts)]
+pub struct SetEscrowTopUp<'info> {
+    #[account(mut)]
+    wallet: Signer<'info>,
+    #[account(
+        mut,
+        constraint = funding_token_account.owner == wallet.key() @ ErrorCode::IncorrectOwner,
+    )]
+    funding_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+    #[account(
+        init_if_needed,
+        payer=wallet,
+        seeds=[
+            PREFIX.as_bytes(),
+            ESCROW_TOP_UP.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        space=EscrowTopUp::LEN,
+        bump,
+    )]
+    escrow_top_up: Box<Account<'info, EscrowTopUp>>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+// Authorizes execute to top up the wallet's escrow from funding_token_account by up to cap in
+// total, replacing the previous funding account and cap. The cap is the delegated amount, so it
+// shrinks as top-ups are pulled and the wallet can revoke it on the token account at any time.
+pub fn handle(ctx: Context<SetEscrowTopUp>, cap: u64) -> Result<()> {
+    approve(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Approve {
+                to: ctx.accounts.funding_token_account.to_account_info(),
+                delegate: ctx.accounts.escrow_top_up.to_account_info(),
+                authority: ctx.accounts.wallet.to_account_info(),
+            },
+        ),
+        cap,
+    )?;
+
+    let escrow_top_up = &mut ctx.accounts.escrow_top_up;
+    escrow_top_up.auction_house_key = ctx.accounts.auction_house.key();
+    escrow_top_up.wallet = ctx.accounts.wallet.key();
+    escrow_top_up.funding_token_account = ctx.accounts.funding_token_account.key();
+    escrow_top_up.bump = ctx.bumps.escrow_top_up;
+
+    msg!(
+        "{{\"funding_token_account\":\"{}\",\"cap\":{}}}",
+        escrow_top_up.funding_token_account,
+        cap
+    );
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(mut)]
+    wallet: Signer<'info>,
+    /// CHECK: guardian, any key the wallet trusts to cancel for it
+    guardian: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer=wallet,
+        seeds=[PREFIX.as_bytes(), GUARDIAN.as_bytes(), wallet.key().as_ref()],
+        space=Guardian::LEN,
+        bump,
+    )]
+    guardian_account: Box<Account<'info, Guardian>>,
+    system_program: Program<'info, System>,
+}
+
+// Registers the guardian that can cancel the wallet's listings and bids, replacing the previous one
+pub fn handle(ctx: Context<SetGuardian>) -> Result<()> {
+    let guardian_account = &mut ctx.accounts.guardian_account;
+    guardian_account.wallet = ctx.accounts.wallet.key();
+    guardian_account.guardian = ctx.accounts.guardian.key();
+    guardian_account.bump = ctx.bumps.guardian_account;
+
+    msg!("{{\"guardian\":\"{}\"}}", guardian_account.guardian);
+    Ok(())
+}
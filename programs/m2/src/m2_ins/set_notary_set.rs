@@ -0,0 +1,42 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct SetNotarySet<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(
+        seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()],
+        bump=auction_house.bump,
+        has_one=authority,
+    )]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init_if_needed,
+        payer=authority,
+        seeds=[
+            PREFIX.as_bytes(),
+            NOTARY_SET.as_bytes(),
+            auction_house.key().as_ref(),
+        ],
+        space=NotarySet::LEN,
+        bump,
+    )]
+    notary_set: Box<Account<'info, NotarySet>>,
+    system_program: Program<'info, System>,
+}
+
+// Sets the notaries that can cosign for the auction house when auction_house.notary doesn't, and
+// how many of them have to sign. auction_house.notary keeps cosigning on its own.
+pub fn handle(ctx: Context<SetNotarySet>, notaries: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    let notary_set = &mut ctx.accounts.notary_set;
+    notary_set.auction_house_key = ctx.accounts.auction_house.key();
+    notary_set.bump = ctx.bumps.notary_set;
+    notary_set.set_notaries(&notaries, threshold)?;
+
+    msg!(
+        "{{\"notaries\":{},\"threshold\":{}}}",
+        notaries.len(),
+        threshold
+    );
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct SetProceedsSplit<'info> {
+    #[account(mut)]
+    seller: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer=seller,
+        seeds=[
+            PREFIX.as_bytes(),
+            PROCEEDS_SPLIT.as_bytes(),
+            seller.key().as_ref(),
+        ],
+        space=ProceedsSplit::LEN,
+        bump,
+    )]
+    proceeds_split: Box<Account<'info, ProceedsSplit>>,
+    system_program: Program<'info, System>,
+}
+
+// Replaces the recipients of the seller's proceeds split. It applies to the seller's SOL sales on
+// every auction house, from the next execute that is passed the split and its recipients.
+pub fn handle(
+    ctx: Context<SetProceedsSplit>,
+    recipients: Vec<ProceedsSplitRecipient>,
+) -> Result<()> {
+    let proceeds_split = &mut ctx.accounts.proceeds_split;
+    proceeds_split.seller = ctx.accounts.seller.key();
+    proceeds_split.bump = ctx.bumps.proceeds_split;
+    proceeds_split.set_recipients(&recipients)?;
+
+    msg!(
+        "{{\"recipients\":{},\"total_bp\":{}}}",
+        recipients.len(),
+        recipients.iter().map(|r| r.bp as u32).sum::<u32>()
+    );
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use mpl_token_metadata::accounts::Metadata;
+
+use {crate::constants::*, crate::errors::ErrorCode, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct SetRoyaltyOverride<'info> {
+    #[account(mut)]
+    update_authority: Signer<'info>,
+    /// CHECK: token_mint, only used to derive the metadata and royalty_override
+    token_mint: UncheckedAccount<'info>,
+    /// CHECK: metadata
+    #[account(
+    seeds = [
+        "metadata".as_bytes(),
+        mpl_token_metadata::ID.as_ref(),
+        token_mint.key().as_ref(),
+    ],
+    bump,
+    seeds::program = mpl_token_metadata::ID,
+    )]
+    metadata: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer=update_authority,
+        seeds=[
+            PREFIX.as_bytes(),
+            ROYALTY_OVERRIDE.as_bytes(),
+            token_mint.key().as_ref(),
+        ],
+        space=RoyaltyOverride::LEN,
+        bump,
+    )]
+    royalty_override: Box<Account<'info, RoyaltyOverride>>,
+    system_program: Program<'info, System>,
+}
+
+// Replaces the recipients the mint's royalty is paid to, on every auction house, from the next
+// execute that is passed the override. The royalty bp stays the metadata's.
+pub fn handle(ctx: Context<SetRoyaltyOverride>, recipients: Vec<RoyaltyRecipient>) -> Result<()> {
+    let metadata = Metadata::safe_deserialize(&ctx.accounts.metadata.data.borrow())?;
+    if metadata.update_authority != ctx.accounts.update_authority.key() {
+        return Err(ErrorCode::PublicKeyMismatch.into());
+    }
+    let royalty_override = &mut ctx.accounts.royalty_override;
+    royalty_override.token_mint = ctx.accounts.token_mint.key();
+    royalty_override.bump = ctx.bumps.royalty_override;
+    royalty_override.set_recipients(&recipients)?;
+
+    msg!("{{\"recipients\":{}}}", recipients.len());
+    Ok(())
+}
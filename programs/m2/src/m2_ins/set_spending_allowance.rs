@@ -0,0 +1,45 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct SetSpendingAllowance<'info> {
+    #[account(mut)]
+    treasury: Signer<'info>,
+    /// CHECK: spender
+    spender: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init_if_needed,
+        payer=treasury,
+        seeds=[
+            PREFIX.as_bytes(),
+            SPENDING_ALLOWANCE.as_bytes(),
+            auction_house.key().as_ref(),
+            treasury.key().as_ref(),
+            spender.key().as_ref(),
+        ],
+        space=SpendingAllowance::LEN,
+        bump,
+    )]
+    spending_allowance: Box<Account<'info, SpendingAllowance>>,
+    system_program: Program<'info, System>,
+}
+
+// Sets the lifetime limit of what the spender may draw from the treasury's escrow, spent carries
+// over and drops by what closed bids hand back. The treasury keeps its escrow funded, e.g. with
+// deposit.
+pub fn handle(ctx: Context<SetSpendingAllowance>, limit: u64) -> Result<()> {
+    let spending_allowance = &mut ctx.accounts.spending_allowance;
+    spending_allowance.auction_house_key = ctx.accounts.auction_house.key();
+    spending_allowance.treasury = ctx.accounts.treasury.key();
+    spending_allowance.spender = ctx.accounts.spender.key();
+    spending_allowance.bump = ctx.bumps.spending_allowance;
+    spending_allowance.limit = limit;
+
+    msg!(
+        "{{\"limit\":{},\"spent\":{}}}",
+        spending_allowance.limit,
+        spending_allowance.spent
+    );
+    Ok(())
+}
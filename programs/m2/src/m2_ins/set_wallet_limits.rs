@@ -0,0 +1,48 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct SetWalletLimits<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    notary: Signer<'info>,
+    /// CHECK: wallet
+    wallet: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=notary)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        init_if_needed,
+        payer=payer,
+        seeds=[
+            PREFIX.as_bytes(),
+            WALLET_LIMITS.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+        ],
+        space=WalletLimits::LEN,
+        bump,
+    )]
+    wallet_limits: Box<Account<'info, WalletLimits>>,
+    system_program: Program<'info, System>,
+}
+
+// Sets the wallet's limits, 0 for no limit. The counters carry over, a limit lowered below them
+// only blocks new bids and fills until they drop below it.
+pub fn handle(
+    ctx: Context<SetWalletLimits>,
+    max_daily_buy_volume: u64,
+    max_open_bids: u32,
+) -> Result<()> {
+    let wallet_limits = &mut ctx.accounts.wallet_limits;
+    wallet_limits.auction_house_key = ctx.accounts.auction_house.key();
+    wallet_limits.wallet = ctx.accounts.wallet.key();
+    wallet_limits.bump = ctx.bumps.wallet_limits;
+    wallet_limits.max_daily_buy_volume = max_daily_buy_volume;
+    wallet_limits.max_open_bids = max_open_bids;
+
+    msg!(
+        "{{\"max_daily_buy_volume\":{},\"max_open_bids\":{}}}",
+        max_daily_buy_volume,
+        max_open_bids
+    );
+    Ok(())
+}
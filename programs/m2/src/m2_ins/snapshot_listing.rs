@@ -0,0 +1,57 @@
+use {crate::constants::*, crate::errors::ErrorCode, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+#[instruction(snapshot_id: u64)]
+pub struct SnapshotListing<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    notary: Signer<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=notary)]
+    auction_house: Account<'info, AuctionHouse>,
+    /// CHECK: owner and auction house checked when decoded
+    seller_trade_state: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer=payer,
+        seeds=[
+            PREFIX.as_bytes(),
+            LISTING_SNAPSHOT.as_bytes(),
+            seller_trade_state.key().as_ref(),
+            &snapshot_id.to_le_bytes(),
+        ],
+        space=ListingSnapshot::LEN,
+        bump,
+    )]
+    listing_snapshot: Box<Account<'info, ListingSnapshot>>,
+    system_program: Program<'info, System>,
+}
+
+// Copies the listing's current terms into a new ListingSnapshot. Snapshots can't be overwritten or
+// closed, each snapshot_id of a listing is written once.
+pub fn handle(ctx: Context<SnapshotListing>, snapshot_id: u64) -> Result<()> {
+    let sell_args = SellArgs::from_account_info(&ctx.accounts.seller_trade_state)?;
+    if sell_args.auction_house_key != ctx.accounts.auction_house.key() {
+        return Err(ErrorCode::WrongAuctionHouseForState.into());
+    }
+
+    let snapshot = &mut ctx.accounts.listing_snapshot;
+    snapshot.auction_house_key = sell_args.auction_house_key;
+    snapshot.seller_trade_state = ctx.accounts.seller_trade_state.key();
+    snapshot.snapshot_id = snapshot_id;
+    snapshot.bump = ctx.bumps.listing_snapshot;
+    snapshot.taken_at = Clock::get()?.unix_timestamp;
+    snapshot.seller = sell_args.seller;
+    snapshot.token_mint = sell_args.token_mint;
+    snapshot.payment_mint = sell_args.payment_mint;
+    snapshot.price = sell_args.buyer_price;
+    snapshot.expiry = sell_args.expiry;
+    snapshot.last_updated_at = sell_args.last_updated_at;
+
+    msg!(
+        "{{\"snapshot_id\":{},\"price\":{},\"seller_expiry\":{}}}",
+        snapshot_id,
+        snapshot.price,
+        snapshot.expiry
+    );
+    Ok(())
+}
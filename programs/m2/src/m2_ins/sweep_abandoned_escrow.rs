@@ -0,0 +1,97 @@
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    anchor_lang::{
+        prelude::*,
+        solana_program::{
+            program::{invoke, invoke_signed},
+            system_instruction, system_program,
+        },
+    },
+};
+
+#[derive(Accounts)]
+pub struct SweepAbandonedEscrow<'info> {
+    #[account(mut)]
+    cranker: Signer<'info>,
+    /// CHECK: wallet that owns the escrow, receives the swept lamports
+    #[account(mut)]
+    wallet: UncheckedAccount<'info>,
+    /// CHECK: escrow_payment_account
+    #[account(
+        mut,
+        seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), wallet.key().as_ref()],
+        bump,
+        owner = system_program::ID,
+        constraint = escrow_payment_account.data_is_empty() @ ErrorCode::EscrowNotSweepable,
+    )]
+    escrow_payment_account: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+    #[account(
+        seeds=[PREFIX.as_bytes(), OPEN_BID_INDEX.as_bytes(), auction_house.key().as_ref(), wallet.key().as_ref()],
+        bump=open_bid_index.bump,
+        constraint = open_bid_index.tracks_all_bids @ ErrorCode::OpenBidIndexIncomplete,
+    )]
+    open_bid_index: Box<Account<'info, OpenBidIndex>>,
+    system_program: Program<'info, System>,
+}
+
+// Permissionless: closes a small escrow whose wallet has no open bids, sending the lamports back to
+// the wallet minus a small incentive for the cranker. Only an OpenBidIndex created while the escrow
+// was empty can vouch that the wallet has no open bids, a bid placed before it may still be backed
+// by the escrow.
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, SweepAbandonedEscrow<'info>>) -> Result<()> {
+    let cranker = &ctx.accounts.cranker;
+    let wallet = &ctx.accounts.wallet;
+    let escrow_payment_account = &ctx.accounts.escrow_payment_account;
+    let open_bid_index = &ctx.accounts.open_bid_index;
+    let system_program = &ctx.accounts.system_program;
+    let auction_house_key = ctx.accounts.auction_house.key();
+
+    if open_bid_index.open_bids > 0 || !open_bid_index.bids.is_empty() {
+        return Err(ErrorCode::OpenBidsRemaining.into());
+    }
+    let escrow_lamports = escrow_payment_account.lamports();
+    if escrow_lamports == 0 || escrow_lamports > MAX_SWEEPABLE_ESCROW_LAMPORTS {
+        return Err(ErrorCode::EscrowNotSweepable.into());
+    }
+
+    let incentive = (escrow_lamports as u128)
+        .checked_mul(SWEEP_CRANKER_INCENTIVE_BP as u128)
+        .and_then(|v| v.checked_div(10_000))
+        .ok_or(ErrorCode::NumericalOverflow)? as u64;
+    let escrow_signer_seeds: &[&[&[u8]]] = &[&[
+        PREFIX.as_bytes(),
+        auction_house_key.as_ref(),
+        wallet.key.as_ref(),
+        &[ctx.bumps.escrow_payment_account],
+    ]];
+
+    // the escrow is emptied in one transfer, a partial transfer would leave it not rent exempt
+    invoke_signed(
+        &system_instruction::transfer(escrow_payment_account.key, cranker.key, escrow_lamports),
+        &[
+            escrow_payment_account.to_account_info(),
+            cranker.to_account_info(),
+            system_program.to_account_info(),
+        ],
+        escrow_signer_seeds,
+    )?;
+    invoke(
+        &system_instruction::transfer(cranker.key, wallet.key, escrow_lamports - incentive),
+        &[
+            cranker.to_account_info(),
+            wallet.to_account_info(),
+            system_program.to_account_info(),
+        ],
+    )?;
+
+    msg!(
+        "{{\"swept\":{},\"incentive\":{}}}",
+        escrow_lamports,
+        incentive
+    );
+    Ok(())
+}
@@ -0,0 +1,502 @@
+use anchor_lang::Discriminator;
+use mpl_token_metadata::accounts::Metadata;
+
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    crate::utils::*,
+    anchor_lang::prelude::*,
+    anchor_spl::{associated_token::AssociatedToken, token::Token},
+    solana_program::{program_option::COption, program_pack::Pack, sysvar},
+};
+
+/// What sweep_buy did with a listing
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SweepFillStatus {
+    Filled,
+    AlreadyFilled, // filled or cancelled before the sweep got to it
+    OverBudget, // costs more than what's left of max_budget, the buyer's escrow or its daily buy volume
+    Unavailable, // expired, not priced in SOL, frozen, or the token isn't escrowed or delegated anymore
+}
+
+/// What sweep_buy returns, one status per listing in the order they were passed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SweepBuyResult {
+    pub statuses: Vec<SweepFillStatus>,
+    pub spent: u64, // drawn from the buyer's escrow, fees and royalties included
+}
+
+#[derive(Accounts)]
+#[instruction(max_budget: u64, maker_fee_bp: i16, taker_fee_bp: u16)]
+pub struct SweepBuy<'info> {
+    #[account(mut)]
+    buyer: Signer<'info>,
+    /// CHECK: optional
+    notary: UncheckedAccount<'info>,
+    /// CHECK: escrow_payment_account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump,
+    )]
+    escrow_payment_account: UncheckedAccount<'info>,
+    /// CHECK: authority
+    authority: UncheckedAccount<'info>,
+    #[account(
+        seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()],
+        bump=auction_house.bump,
+        has_one=authority,
+        has_one=auction_house_treasury,
+        constraint = maker_fee_bp <= MAX_MAKER_FEE_BP @ ErrorCode::InvalidPlatformFeeBp,
+        constraint = maker_fee_bp >= -(taker_fee_bp as i16) @ ErrorCode::InvalidPlatformFeeBp,
+        constraint = taker_fee_bp <= MAX_TAKER_FEE_BP @ ErrorCode::InvalidPlatformFeeBp,
+    )]
+    auction_house: Account<'info, AuctionHouse>,
+    /// CHECK: auction_house_treasury
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), TREASURY.as_bytes()], bump=auction_house.treasury_bump)]
+    auction_house_treasury: UncheckedAccount<'info>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+    ata_program: Program<'info, AssociatedToken>,
+    /// CHECK: program_as_signer
+    #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
+    program_as_signer: UncheckedAccount<'info>,
+    // remaining accounts:
+    // notary_set, then notaries (signer) (optional) - auction house's NotarySet and the notaries cosigning with it, notary may be one of them instead of auction_house.notary, placed first
+    // instructions sysvar (required if auction_house.sandwich_guard, or if the sweep is a CPI and auction_house.cpi_policy is Allowlist) - placed before the listings
+    // protocol fee recipient (required if auction_house.protocol_fee_share_bp > 0) - placed before the listings
+    // cpi_allowlist (required if the sweep is a CPI and auction_house.cpi_policy is Allowlist) - placed before the listings
+    // wallet_limits (required if auction_house.enforce_wallet_limits, writable) - buyer's WalletLimits, its uninitialized address if there is none, the listings bought add to the day's buy volume, placed before the listings
+    // then up to MAX_SWEEP_BUY listings, cheapest first, each as:
+    //   0. seller (writable)
+    //   1. seller_trade_state (writable) - filled or cancelled ones are skipped
+    //   2. token_account (writable) - escrowed by or delegated to program_as_signer
+    //   3. token_mint
+    //   4. metadata
+    //   5. buyer_receipt_token_account (writable) - buyer's ATA for token_mint, created if needed
+    //   6.. creators (writable) - every creator of the metadata, in metadata order
+    //   then, in any order (optional): listing_index (writable) - seller's and verified collection's ListingIndex, mint_market (writable) - token mint's MintMarket,
+    //   maker_rewards (required if the listing is enrolled in maker rewards, writable) - seller's MakerRewards, proceeds_split, then its recipients (writable) - seller's ProceedsSplit
+}
+
+// Buys SOL listings of any sellers out of the buyer's escrow in one instruction, as a taker paying
+// the full creator royalty, until max_budget runs out. Listings that were filled or can't be
+// bought anymore are skipped instead of failing the sweep, so a client can sweep the N cheapest
+// listings of a collection and retry with the same accounts. Listings that repay a loan are
+// skipped. A listing of several tokens is bought whole, at its unit price times its token_size.
+// Each fill is settled like execute_sale_v2 settles it, with the accounts passed after the
+// listing's creators.
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, SweepBuy<'info>>,
+    max_budget: u64,
+    maker_fee_bp: i16,
+    taker_fee_bp: u16,
+) -> Result<SweepBuyResult> {
+    let auction_house = &ctx.accounts.auction_house;
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        ctx.remaining_accounts,
+        auction_house,
+        &ctx.accounts.notary,
+    )?;
+    notary_cosign.assert_known()?;
+    let limits_key = get_wallet_limits_address(&auction_house.key(), ctx.accounts.buyer.key);
+    let prefix_len = remaining_accounts
+        .iter()
+        .take_while(|ai| {
+            *ai.key == sysvar::instructions::id()
+                || *ai.key == get_cpi_allowlist_address(&auction_house.key())
+                || *ai.key == limits_key
+                || (auction_house.protocol_fee_share_bp > 0
+                    && *ai.key == auction_house.protocol_fee_recipient)
+        })
+        .count();
+    let (prefix, mut listings) = remaining_accounts.split_at(prefix_len);
+    let instructions = find_remaining_account(prefix, &sysvar::instructions::id());
+    assert_cpi_caller_allowed(auction_house, instructions, prefix)?;
+    let mut wallet_limits = find_wallet_limits(
+        prefix,
+        &auction_house.key(),
+        auction_house,
+        ctx.accounts.buyer.key,
+    )?;
+
+    let mut groups = Vec::with_capacity(MAX_SWEEP_BUY);
+    while !listings.is_empty() {
+        if groups.len() == MAX_SWEEP_BUY {
+            return Err(ErrorCode::BatchTooLarge.into());
+        }
+        let metadata = listings.get(4).ok_or(ErrorCode::MissingRemainingAccount)?;
+        let token_mint = &listings[3];
+        assert_metadata_valid(metadata, token_mint.key)?;
+        let metadata = Metadata::safe_deserialize(&metadata.data.borrow())?;
+        let group_len = 6 + metadata.creators.as_ref().map_or(0, |c| c.len());
+        if listings.len() < group_len {
+            return Err(ErrorCode::MissingRemainingAccount.into());
+        }
+        let group_len = group_len + get_settlement_accounts_len(&listings[group_len..])?;
+        let (group, rest) = listings.split_at(group_len);
+        groups.push((group, metadata));
+        listings = rest;
+    }
+
+    if auction_house.sandwich_guard {
+        let instructions = instructions.ok_or(ErrorCode::MissingRemainingAccount)?;
+        let guarded: Vec<&Pubkey> = groups.iter().map(|(group, _)| group[1].key).collect();
+        assert_no_conflicting_instructions(instructions, &guarded)?;
+    }
+
+    // sweeps pay in SOL, which has no fee override
+    let (actual_maker_fee_bp, actual_taker_fee_bp) =
+        get_actual_maker_taker_fee_bp(&notary_cosign, maker_fee_bp, taker_fee_bp, None);
+    let now = Clock::get()?.unix_timestamp;
+    let mut result = SweepBuyResult::default();
+    for (group, metadata) in &groups {
+        let (status, spent) = fill_listing(
+            &ctx,
+            group,
+            metadata,
+            max_budget.saturating_sub(result.spent),
+            actual_maker_fee_bp,
+            actual_taker_fee_bp,
+            now,
+            prefix,
+            wallet_limits.as_mut().map(|(_, limits)| limits),
+        )?;
+        result.spent = result
+            .spent
+            .checked_add(spent)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        result.statuses.push(status);
+    }
+    if let Some((wallet_limits, limits)) = &wallet_limits {
+        save_wallet_limits(wallet_limits, limits)?;
+    }
+
+    let auction_house_key = auction_house.key();
+    try_close_buyer_escrow(
+        &ctx.accounts.escrow_payment_account,
+        &ctx.accounts.buyer,
+        &ctx.accounts.system_program,
+        None,
+        &[&[
+            PREFIX.as_bytes(),
+            auction_house_key.as_ref(),
+            ctx.accounts.buyer.key.as_ref(),
+            &[ctx.bumps.escrow_payment_account],
+        ]],
+    )?;
+
+    msg!(
+        "{{\"swept\":{},\"spent\":{}}}",
+        result
+            .statuses
+            .iter()
+            .filter(|s| **s == SweepFillStatus::Filled)
+            .count(),
+        result.spent
+    );
+    Ok(result)
+}
+
+/// How many of the accounts after a listing's creators are the accounts its fill is settled with:
+/// ListingIndex, MintMarket and MakerRewards accounts, and a ProceedsSplit followed by its
+/// recipients. The next listing starts with its seller, a wallet, so it never reads as one.
+fn get_settlement_accounts_len(accounts: &[AccountInfo]) -> Result<usize> {
+    let mut len = 0;
+    while let Some(ai) = accounts.get(len) {
+        if ai.owner != &crate::ID || ai.data_len() < 8 {
+            break;
+        }
+        let discriminator = get_discriminator(ai)?;
+        if discriminator == ProceedsSplit::discriminator() {
+            let proceeds_split = ProceedsSplit::try_deserialize(&mut &ai.try_borrow_data()?[..])?;
+            len += 1 + proceeds_split.used_recipients().count();
+        } else if discriminator == ListingIndex::discriminator()
+            || discriminator == MintMarket::discriminator()
+            || discriminator == MakerRewards::discriminator()
+        {
+            len += 1;
+        } else {
+            break;
+        }
+    }
+    if len > accounts.len() {
+        return Err(ErrorCode::MissingRemainingAccount.into());
+    }
+    Ok(len)
+}
+
+/// Fills one listing of the sweep if it's still available and fits in budget, returning its
+/// status and what the buyer spent on it
+#[allow(clippy::too_many_arguments)]
+fn fill_listing<'info>(
+    ctx: &Context<'_, '_, '_, 'info, SweepBuy<'info>>,
+    group: &[AccountInfo<'info>],
+    metadata: &Metadata,
+    budget: u64,
+    actual_maker_fee_bp: i16,
+    actual_taker_fee_bp: u16,
+    now: i64,
+    prefix: &[AccountInfo<'info>],
+    wallet_limits: Option<&mut WalletLimits>,
+) -> Result<(SweepFillStatus, u64)> {
+    let (seller, seller_trade_state, token_account, token_mint, buyer_receipt_token_account) =
+        (&group[0], &group[1], &group[2], &group[3], &group[5]);
+    let (creators, settlement_accounts) =
+        group[6..].split_at(metadata.creators.as_ref().map_or(0, |c| c.len()));
+    let buyer = &ctx.accounts.buyer.to_account_info();
+    let escrow_payment_account = &ctx.accounts.escrow_payment_account.to_account_info();
+    let auction_house = &ctx.accounts.auction_house;
+    let program_as_signer = &ctx.accounts.program_as_signer.to_account_info();
+
+    if seller_trade_state.data_is_empty() || seller_trade_state.owner != &crate::ID {
+        return Ok((SweepFillStatus::AlreadyFilled, 0));
+    }
+    let sell_args = SellArgs::from_account_info(seller_trade_state)?;
+    if sell_args.auction_house_key != auction_house.key()
+        || sell_args.seller != *seller.key
+        || sell_args.token_account != *token_account.key
+        || sell_args.token_mint != *token_mint.key
+    {
+        return Err(ErrorCode::TradeStateMismatch.into());
+    }
+    let metadata = &sell_args.royalty_locked(metadata.clone());
+
+    let price = match sell_args.price_for(&Pubkey::default()) {
+        Some(price)
+            if !Expiry::from(sell_args.expiry).is_expired(now)
+                && assert_listing_visible(sell_args.hidden_until, now).is_ok()
+                && !sell_args.frozen
+                && !sell_args.requires_attestation
+                && !sell_args.proceeds_as_wsol
+                && !sell_args.repay_loan
+                && sell_args.token_size > 0 =>
+        {
+            price
+        }
+        _ => return Ok((SweepFillStatus::Unavailable, 0)),
+    };
+    let token_account_parsed = match token_account.owner == &spl_token::ID {
+        true => spl_token::state::Account::unpack(&token_account.try_borrow_data()?)?,
+        false => return Ok((SweepFillStatus::Unavailable, 0)),
+    };
+    let escrowed = token_account_parsed.owner == *program_as_signer.key;
+    let delegated = token_account_parsed.delegate == COption::Some(*program_as_signer.key)
+        && token_account_parsed.delegated_amount >= sell_args.token_size;
+    if token_account_parsed.is_frozen()
+        || !(escrowed || delegated)
+        || token_account_parsed.amount < sell_args.token_size
+    {
+        return Ok((SweepFillStatus::Unavailable, 0));
+    }
+    assert_is_ata(
+        token_account,
+        seller.key,
+        token_mint.key,
+        program_as_signer.key,
+    )?;
+
+    let total_price = get_total_price(price, sell_args.token_size)?;
+    let cost = get_buyer_total_cost(
+        total_price,
+        actual_maker_fee_bp,
+        actual_taker_fee_bp,
+        false,
+        get_royalty_bp(None, metadata, total_price),
+        10_000,
+    )?;
+    if cost > budget || cost > escrow_payment_account.lamports() {
+        return Ok((SweepFillStatus::OverBudget, 0));
+    }
+    // the sale counts towards the buyer's daily buy volume like any SOL fill
+    if let Some(limits) = wallet_limits {
+        let mut recorded = *limits;
+        if recorded.record_buy(total_price, now).is_err() {
+            return Ok((SweepFillStatus::OverBudget, 0));
+        }
+        *limits = recorded;
+    }
+
+    let auction_house_key = auction_house.key();
+    let escrow_signer_seeds: &[&[&[u8]]] = &[&[
+        PREFIX.as_bytes(),
+        auction_house_key.as_ref(),
+        buyer.key.as_ref(),
+        &[ctx.bumps.escrow_payment_account],
+    ]];
+    let lamports_before = escrow_payment_account.lamports();
+    // the trade state is the royalty conduit, it's closed to the seller right after
+    pay_creator_fees(
+        &mut creators.iter(),
+        None,
+        metadata,
+        escrow_payment_account,
+        seller_trade_state,
+        escrow_signer_seeds,
+        total_price,
+        10_000,
+        None,
+        None,
+    )?;
+    transfer_listing_payment(
+        total_price,
+        actual_maker_fee_bp,
+        actual_taker_fee_bp,
+        buyer,
+        seller,
+        escrow_payment_account,
+        &ctx.accounts.auction_house_treasury,
+        None,
+        get_protocol_fee_args(auction_house, prefix, None)?,
+        None,
+        None,
+        get_proceeds_split_args(seller.key, settlement_accounts)?,
+        None,
+        escrow_signer_seeds,
+    )?;
+
+    let buyer_rec_acct = transfer_token(
+        &sell_args.token_size,
+        buyer,
+        program_as_signer,
+        seller,
+        Some(seller),
+        DestinationSpecifier::Ai(buyer),
+        token_mint,
+        token_account,
+        buyer_receipt_token_account,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        None,
+        &[&[
+            PREFIX.as_bytes(),
+            SIGNER.as_bytes(),
+            &[ctx.bumps.program_as_signer],
+        ]],
+    )?;
+    if matches!(buyer_rec_acct.delegate, COption::Some(d) if d != *program_as_signer.key) {
+        return Err(ErrorCode::BuyerATACannotHaveDelegate.into());
+    }
+    settle_filled_listing(
+        settlement_accounts,
+        auction_house,
+        seller_trade_state,
+        &sell_args,
+        &group[4],
+        token_mint.key,
+        &[],
+        total_price,
+    )?;
+    close_account_anchor(seller_trade_state, seller, true)?;
+
+    msg!(
+        "{{\"price\":{},\"seller_expiry\":{},\"token_size\":{}}}",
+        price,
+        sell_args.expiry,
+        sell_args.token_size,
+    );
+    Ok((
+        SweepFillStatus::Filled,
+        lamports_before.saturating_sub(escrow_payment_account.lamports()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_listing_takes_the_settlement_accounts_up_to_the_next_seller() {
+        let mut proceeds_split = ProceedsSplit::default();
+        proceeds_split.recipients[0].address = Pubkey::new_unique();
+        proceeds_split.recipients[1].address = Pubkey::new_unique();
+        let mut split_data = Vec::new();
+        proceeds_split.try_serialize(&mut split_data).unwrap();
+        let mut index_data = ListingIndex::discriminator().to_vec();
+        let mut market_data = MintMarket::discriminator().to_vec();
+        let keys: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        let mut lamports = [0u64; 6];
+        let [split_lamports, index_lamports, market_lamports, a_lamports, b_lamports, seller_lamports] =
+            &mut lamports;
+        let mut empty = [[0u8; 0]; 3];
+        let [a_data, b_data, seller_data] = &mut empty;
+        let system_program = anchor_lang::system_program::ID;
+        let accounts = [
+            AccountInfo::new(
+                &keys[0],
+                false,
+                false,
+                index_lamports,
+                &mut index_data,
+                &crate::ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &keys[1],
+                false,
+                false,
+                split_lamports,
+                &mut split_data,
+                &crate::ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &keys[2],
+                false,
+                true,
+                a_lamports,
+                a_data,
+                &system_program,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &keys[3],
+                false,
+                true,
+                b_lamports,
+                b_data,
+                &system_program,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &keys[4],
+                false,
+                true,
+                market_lamports,
+                &mut market_data,
+                &crate::ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &keys[5],
+                false,
+                true,
+                seller_lamports,
+                seller_data,
+                &system_program,
+                false,
+                0,
+            ),
+        ];
+
+        // the split's two recipients are wallets but belong to it, the next wallet is a seller
+        assert_eq!(get_settlement_accounts_len(&accounts).unwrap(), 5);
+        assert_eq!(get_settlement_accounts_len(&accounts[5..]).unwrap(), 0);
+        assert_eq!(get_settlement_accounts_len(&[]).unwrap(), 0);
+        // a split whose recipients weren't passed
+        assert_eq!(
+            get_settlement_accounts_len(&accounts[1..3]).unwrap_err(),
+            ErrorCode::MissingRemainingAccount.into()
+        );
+    }
+}
@@ -16,6 +16,7 @@ pub struct UpdateAuctionHouse<'info> {
     system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handle<'info>(
     ctx: Context<'_, '_, '_, 'info, UpdateAuctionHouse<'info>>,
     seller_fee_basis_points: Option<u16>,
@@ -23,17 +24,41 @@ pub fn handle<'info>(
     seller_referral_bp: Option<u16>,
     requires_notary: Option<bool>,
     nprob: Option<u8>,
+    sandwich_guard: Option<bool>,
+    protocol_fee_recipient: Option<Pubkey>,
+    protocol_fee_share_bp: Option<u16>,
+    escrow_adapters: Option<[Pubkey; 2]>,
+    min_bid_bp_of_list_price: Option<u16>,
+    delist_grace_seconds: Option<u32>,
+    enforce_royalties: Option<bool>,
+    maker_rewards_lamports_per_day: Option<u64>,
+    maker_rewards_volume_bp: Option<u16>,
+    attestation_authority: Option<Pubkey>,
+    loan_program: Option<Pubkey>,
+    staking_adapter: Option<Pubkey>,
+    max_ixs_per_slot: Option<u8>,
+    allow_sfts: Option<bool>,
+    enforce_wallet_limits: Option<bool>,
 ) -> Result<()> {
     let new_authority = &ctx.accounts.new_authority;
     let auction_house = &mut ctx.accounts.auction_house;
     let treasury_withdrawal_destination = &ctx.accounts.treasury_withdrawal_destination;
 
-    if let Some(sfbp) = seller_fee_basis_points {
-        if sfbp > 10000 {
-            return Err(ErrorCode::InvalidBasisPoints.into());
-        }
-
-        auction_house.seller_fee_basis_points = sfbp;
+    // fee changes apply to open listings, so they only take effect through the timelock of
+    // schedule_house_update, passing the current value is a no-op
+    if [
+        (
+            seller_fee_basis_points,
+            auction_house.seller_fee_basis_points,
+        ),
+        (buyer_referral_bp, auction_house.buyer_referral_bp),
+        (seller_referral_bp, auction_house.seller_referral_bp),
+        (protocol_fee_share_bp, auction_house.protocol_fee_share_bp),
+    ]
+    .iter()
+    .any(|(bp, current)| bp.map_or(false, |bp| bp != *current))
+    {
+        return Err(ErrorCode::FeeChangeNotScheduled.into());
     }
 
     if let Some(require_notary) = requires_notary {
@@ -41,23 +66,59 @@ pub fn handle<'info>(
         auction_house.notary = ctx.accounts.notary.key();
     }
 
-    if let Some(bbp) = buyer_referral_bp {
-        auction_house.buyer_referral_bp = bbp;
-    }
-    if let Some(sbp) = seller_referral_bp {
-        auction_house.seller_referral_bp = sbp;
-    }
     if let Some(_nprob) = nprob {
         auction_house.nprob = _nprob;
     }
-
-    let referral_bp = auction_house
-        .buyer_referral_bp
-        .checked_add(auction_house.seller_referral_bp)
-        .ok_or(ErrorCode::NumericalOverflow)?;
-    if referral_bp > auction_house.seller_fee_basis_points {
-        return Err(ErrorCode::InvalidBasisPoints.into());
+    if let Some(guard) = sandwich_guard {
+        auction_house.sandwich_guard = guard;
+    }
+    if let Some(recipient) = protocol_fee_recipient {
+        auction_house.protocol_fee_recipient = recipient;
+    }
+    if let Some(adapters) = escrow_adapters {
+        auction_house.escrow_adapters = adapters;
+    }
+    if let Some(min_bid_bp) = min_bid_bp_of_list_price {
+        if min_bid_bp > 10000 {
+            return Err(ErrorCode::InvalidBasisPoints.into());
+        }
+        auction_house.min_bid_bp_of_list_price = min_bid_bp;
+    }
+    if let Some(grace) = delist_grace_seconds {
+        auction_house.delist_grace_seconds = grace;
     }
+    if let Some(enforce) = enforce_royalties {
+        auction_house.enforce_royalties = enforce;
+    }
+    if let Some(per_day) = maker_rewards_lamports_per_day {
+        auction_house.maker_rewards_lamports_per_day = per_day;
+    }
+    if let Some(volume_bp) = maker_rewards_volume_bp {
+        if volume_bp > 10000 {
+            return Err(ErrorCode::InvalidBasisPoints.into());
+        }
+        auction_house.maker_rewards_volume_bp = volume_bp;
+    }
+    if let Some(attestation_authority) = attestation_authority {
+        auction_house.attestation_authority = attestation_authority;
+    }
+    if let Some(loan_program) = loan_program {
+        auction_house.loan_program = loan_program;
+    }
+    if let Some(staking_adapter) = staking_adapter {
+        auction_house.staking_adapter = staking_adapter;
+    }
+    if let Some(max_ixs_per_slot) = max_ixs_per_slot {
+        auction_house.max_ixs_per_slot = max_ixs_per_slot;
+    }
+    if let Some(allow_sfts) = allow_sfts {
+        auction_house.allow_sfts = allow_sfts;
+    }
+    if let Some(enforce_wallet_limits) = enforce_wallet_limits {
+        auction_house.enforce_wallet_limits = enforce_wallet_limits;
+    }
+
+    auction_house.assert_referral_bp()?;
 
     auction_house.authority = new_authority.key();
     auction_house.treasury_withdrawal_destination = treasury_withdrawal_destination.key();
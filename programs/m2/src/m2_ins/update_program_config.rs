@@ -0,0 +1,44 @@
+use {crate::constants::*, crate::states::*, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct UpdateProgramConfig<'info> {
+    authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds=[PREFIX.as_bytes(), PROGRAM_CONFIG.as_bytes()],
+        bump=program_config.bump,
+        has_one=authority,
+    )]
+    program_config: Box<Account<'info, ProgramConfig>>,
+}
+
+pub fn handle(
+    ctx: Context<UpdateProgramConfig>,
+    new_authority: Option<Pubkey>,
+    payment_mints: Option<[Pubkey; MAX_CONFIG_PAYMENT_MINTS]>,
+    cancel_authority: Option<Pubkey>,
+    disabled_instructions: Option<u32>,
+    swap_adapter: Option<Pubkey>,
+    payment_mint_fees: Option<[PaymentMintFees; MAX_CONFIG_PAYMENT_MINTS]>,
+) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    if let Some(new_authority) = new_authority {
+        program_config.authority = new_authority;
+    }
+    if let Some(payment_mints) = payment_mints {
+        program_config.payment_mints = payment_mints;
+    }
+    if let Some(cancel_authority) = cancel_authority {
+        program_config.cancel_authority = cancel_authority;
+    }
+    if let Some(disabled_instructions) = disabled_instructions {
+        program_config.disabled_instructions = disabled_instructions;
+    }
+    if let Some(swap_adapter) = swap_adapter {
+        program_config.swap_adapter = swap_adapter;
+    }
+    if let Some(payment_mint_fees) = payment_mint_fees {
+        program_config.set_payment_mint_fees(payment_mint_fees)?;
+    }
+    Ok(())
+}
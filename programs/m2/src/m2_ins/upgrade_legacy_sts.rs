@@ -0,0 +1,75 @@
+use anchor_lang::Discriminator;
+
+use {
+    crate::constants::*, crate::errors::ErrorCode, crate::states::*, crate::utils::*,
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct UpgradeLegacySts<'info> {
+    /// pays the rent the bigger layout needs
+    #[account(mut)]
+    payer: Signer<'info>,
+    notary: Signer<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=notary)]
+    auction_house: Account<'info, AuctionHouse>,
+    system_program: Program<'info, System>,
+    // remaining accounts:
+    // 0..MAX_UPGRADE_STS_BATCH. seller_trade_state (writable) - V1 listings to upgrade, already upgraded or closed ones are skipped
+}
+
+// Crank that rewrites V1 listings (SellerTradeState) in the current layout, as sell would when the
+// seller reprices, keeping their terms. Once no V1 listing is left, handlers can drop V1 support.
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, UpgradeLegacySts<'info>>) -> Result<()> {
+    let payer = &ctx.accounts.payer;
+    let auction_house_key = ctx.accounts.auction_house.key();
+    if ctx.remaining_accounts.len() > MAX_UPGRADE_STS_BATCH {
+        return Err(ErrorCode::BatchTooLarge.into());
+    }
+
+    let mut upgraded = 0u32;
+    for seller_trade_state in ctx.remaining_accounts {
+        if seller_trade_state.data_len() != SellerTradeState::LEN
+            || get_discriminator(seller_trade_state)? != SellerTradeState::discriminator()
+        {
+            continue;
+        }
+        let sell_args = SellArgs::from_account_info(seller_trade_state)?;
+        if sell_args.auction_house_key != auction_house_key {
+            return Err(ErrorCode::WrongAuctionHouseForState.into());
+        }
+        let seeds: &[&[u8]] = &[
+            PREFIX.as_bytes(),
+            sell_args.seller.as_ref(),
+            auction_house_key.as_ref(),
+            sell_args.token_account.as_ref(),
+            sell_args.token_mint.as_ref(),
+            &[sell_args.bump],
+        ];
+        let expected_key = Pubkey::create_program_address(seeds, &crate::ID)
+            .map_err(|_| ErrorCode::DerivedKeyInvalid)?;
+        assert_keys_equal(&expected_key, seller_trade_state.key)?;
+
+        create_or_realloc_seller_trade_state(seller_trade_state, payer, None, seeds)?;
+        write_seller_trade_state(
+            seller_trade_state,
+            SellerTradeStateV3 {
+                auction_house_key,
+                seller: sell_args.seller,
+                seller_referral: sell_args.seller_referral,
+                buyer_price: sell_args.buyer_price,
+                token_mint: sell_args.token_mint,
+                token_account: sell_args.token_account,
+                token_size: sell_args.token_size,
+                bump: sell_args.bump,
+                expiry: sell_args.expiry,
+                payment_mint: Pubkey::default(),
+                ..Default::default()
+            },
+        )?;
+        upgraded += 1;
+    }
+
+    msg!("{{\"upgraded\":{}}}", upgraded);
+    Ok(())
+}
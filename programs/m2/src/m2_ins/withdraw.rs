@@ -26,14 +26,25 @@ pub struct Withdraw<'info> {
     #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=authority)]
     auction_house: Account<'info, AuctionHouse>,
     system_program: Program<'info, System>,
-    // remaining accounts:
+    // remaining accounts, in this order, or in any order with a remaining accounts schema appended to the instruction data (see RemainingAccountsTag):
     // 0. payment_mint (optional) - if included, will try to withdraw the token of this mint
     // 1. payment_source_token_account (optional) - token account controlled by escrow_payment_account that is source of tokens
     // 2. payment_destination_token_account (optional) - token account controlled by wallet that is destination of tokens
     // 3. token_program (optional)
     // 4. associated_token_program (optional)
+    // -1. escrow_ledger (optional, writable) - wallet's EscrowLedger, the withdrawal can't dip into what its bids lock
+    // instructions sysvar (optional) - placed last if a remaining accounts schema is appended to the instruction data
 }
 
+/// Length of the withdraw instruction data up to the end of its args
+pub const WITHDRAW_ARGS_LEN: usize = 8 + 1 + 8;
+
+/// Where withdraw expects each group of remaining accounts
+pub const WITHDRAW_ORDER: [RemainingAccountsTag; 2] = [
+    RemainingAccountsTag::Payment,
+    RemainingAccountsTag::EscrowLedger,
+];
+
 pub fn handle<'info>(
     ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
     escrow_payment_bump: u8,
@@ -45,7 +56,11 @@ pub fn handle<'info>(
     let auction_house = &ctx.accounts.auction_house;
     let system_program = &ctx.accounts.system_program;
     let auction_house_key = auction_house.key();
-    let remaining_accounts = ctx.remaining_accounts;
+    let (remaining_accounts, escrow_ledger) = split_escrow_ledger_from_remaining_accounts(
+        ctx.remaining_accounts,
+        &auction_house_key,
+        wallet.key,
+    )?;
 
     assert_bump(
         &[
@@ -68,7 +83,23 @@ pub fn handle<'info>(
         &[escrow_payment_bump],
     ]];
 
-    if ctx.remaining_accounts.is_empty() {
+    let escrow_balance = || -> Result<u64> {
+        if remaining_accounts.is_empty() {
+            Ok(escrow_payment_account.lamports())
+        } else {
+            get_balance_from_token_account(index_ra!(remaining_accounts, 1))
+        }
+    };
+    let payment_mint = if remaining_accounts.is_empty() {
+        Pubkey::default()
+    } else {
+        index_ra!(remaining_accounts, 0).key()
+    };
+    if let Some((_, ledger)) = &escrow_ledger {
+        ledger.assert_withdrawable(&payment_mint, amount, escrow_balance()?)?;
+    }
+
+    if remaining_accounts.is_empty() {
         invoke_signed(
             &system_instruction::transfer(&escrow_payment_account.key(), &wallet.key(), amount),
             &[
@@ -97,5 +128,9 @@ pub fn handle<'info>(
         )?;
     }
 
+    if let Some((escrow_ledger, mut ledger)) = escrow_ledger {
+        ledger.sync(&payment_mint, escrow_balance()?)?;
+        save_escrow_ledger(escrow_ledger, &ledger)?;
+    }
     Ok(())
 }
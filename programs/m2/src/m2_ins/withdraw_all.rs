@@ -0,0 +1,106 @@
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    crate::utils::{
+        assert_owned_by, save_escrow_ledger, split_escrow_ledger_from_remaining_accounts,
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::{program::invoke_signed, system_instruction},
+    },
+    std::cmp,
+};
+
+#[derive(Accounts)]
+pub struct WithdrawAll<'info> {
+    #[account(mut)]
+    wallet: Signer<'info>,
+    /// CHECK: escrow_payment_account
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), wallet.key().as_ref()], bump)]
+    escrow_payment_account: UncheckedAccount<'info>,
+    /// CHECK: authority
+    authority: UncheckedAccount<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=authority)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        seeds=[PREFIX.as_bytes(), OPEN_BID_INDEX.as_bytes(), auction_house.key().as_ref(), wallet.key().as_ref()],
+        bump=open_bid_index.bump,
+    )]
+    open_bid_index: Box<Account<'info, OpenBidIndex>>,
+    system_program: Program<'info, System>,
+    // remaining accounts:
+    // 0..n. buyer_trade_states (optional) - expired indexed bids of the wallet, their locks are released from the index
+    // -1. escrow_ledger (optional, writable) - wallet's EscrowLedger, what it locks in SOL stays in the escrow too
+}
+
+// Withdraws the escrow's free balance, i.e. everything not backing the wallet's indexed SOL bids.
+// Expired bids can't fill anymore, passing them in releases their locks without cancelling them.
+// Bids placed without the open bid index or the escrow ledger aren't counted, those still need to be
+// cancelled or kept in mind.
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawAll<'info>>) -> Result<()> {
+    let wallet = &ctx.accounts.wallet;
+    let escrow_payment_account = &ctx.accounts.escrow_payment_account;
+    let auction_house_key = ctx.accounts.auction_house.key();
+    let (expired_bids, escrow_ledger) = split_escrow_ledger_from_remaining_accounts(
+        ctx.remaining_accounts,
+        &auction_house_key,
+        wallet.key,
+    )?;
+    let now = Clock::get()?.unix_timestamp;
+    for buyer_trade_state in expired_bids {
+        assert_owned_by(buyer_trade_state, &crate::ID)?;
+        let bid_args = BidArgs::from_account_info(buyer_trade_state)?;
+        if bid_args.auction_house_key != auction_house_key || bid_args.buyer != wallet.key() {
+            return Err(ErrorCode::TradeStateMismatch.into());
+        }
+        if !Expiry::from(bid_args.expiry).is_expired(now) {
+            return Err(ErrorCode::InvalidExpiry.into());
+        }
+        ctx.accounts.open_bid_index.remove_bid(
+            buyer_trade_state.key,
+            bid_args.locked_cost(),
+            &bid_args.payment_mint,
+        );
+    }
+    let locked_amount = cmp::max(
+        ctx.accounts.open_bid_index.locked_amount,
+        escrow_ledger.map_or(0, |(_, l)| l.balance(&Pubkey::default()).locked),
+    );
+
+    let escrow_lamports = escrow_payment_account.lamports();
+    let mut amount = escrow_lamports.saturating_sub(locked_amount);
+    // whatever stays behind has to keep the escrow rent exempt
+    let min_rent = Rent::get()?.minimum_balance(0);
+    let left = escrow_lamports - amount;
+    if left > 0 && left < min_rent {
+        amount = escrow_lamports.saturating_sub(min_rent);
+    }
+    if amount == 0 {
+        return Err(ErrorCode::NoFreeEscrowBalance.into());
+    }
+
+    invoke_signed(
+        &system_instruction::transfer(&escrow_payment_account.key(), &wallet.key(), amount),
+        &[
+            escrow_payment_account.to_account_info(),
+            wallet.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[&[
+            PREFIX.as_bytes(),
+            auction_house_key.as_ref(),
+            wallet.key().as_ref(),
+            &[ctx.bumps.escrow_payment_account],
+        ]],
+    )?;
+
+    if let Some((escrow_ledger, mut ledger)) = escrow_ledger {
+        ledger.sync(&Pubkey::default(), escrow_payment_account.lamports())?;
+        save_escrow_ledger(escrow_ledger, &ledger)?;
+    }
+
+    msg!("{{\"withdrawn\":{},\"locked\":{}}}", amount, locked_amount);
+    Ok(())
+}
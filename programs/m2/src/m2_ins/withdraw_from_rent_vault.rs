@@ -0,0 +1,54 @@
+use {
+    crate::constants::*,
+    crate::states::*,
+    anchor_lang::{
+        prelude::*,
+        solana_program::{program::invoke_signed, system_instruction},
+    },
+};
+
+// The rent vault is funded by the operator with plain SOL transfers to the PDA,
+// and pays for trade state rent when the notary co-signs. Only the authority can take funds out.
+#[derive(Accounts)]
+pub struct WithdrawFromRentVault<'info> {
+    authority: Signer<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump, has_one=authority)]
+    auction_house: Account<'info, AuctionHouse>,
+    /// CHECK: rent_vault
+    #[account(
+      mut,
+      seeds=[PREFIX.as_bytes(), RENT_VAULT.as_bytes(), auction_house.key().as_ref()],
+      bump,
+    )]
+    rent_vault: UncheckedAccount<'info>,
+    /// CHECK: destination of the withdrawn lamports, chosen by the authority
+    #[account(mut)]
+    destination: UncheckedAccount<'info>,
+    system_program: Program<'info, System>,
+}
+
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawFromRentVault<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let rent_vault = &ctx.accounts.rent_vault;
+    let destination = &ctx.accounts.destination;
+    let ah_key = ctx.accounts.auction_house.key();
+
+    invoke_signed(
+        &system_instruction::transfer(&rent_vault.key(), &destination.key(), amount),
+        &[
+            rent_vault.to_account_info(),
+            destination.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[&[
+            PREFIX.as_bytes(),
+            RENT_VAULT.as_bytes(),
+            ah_key.as_ref(),
+            &[ctx.bumps.rent_vault],
+        ]],
+    )?;
+
+    Ok(())
+}
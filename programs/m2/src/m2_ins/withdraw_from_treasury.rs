@@ -52,7 +52,7 @@ pub fn handle<'info>(
             .checked_sub(MIN_LEFTOVER)
             .ok_or(ErrorCode::NumericalOverflow)?)
     {
-        return Err(ErrorCode::InvalidAccountState.into());
+        return Err(ErrorCode::TreasuryBalanceTooLow.into());
     }
 
     let ah_key = auction_house.key();
@@ -0,0 +1,60 @@
+use {
+    crate::constants::*, crate::errors::ErrorCode, crate::states::*,
+    crate::utils::close_account_anchor, anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct WithdrawListingInsurance<'info> {
+    #[account(mut)]
+    seller: Signer<'info>,
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()], bump=auction_house.bump)]
+    auction_house: Account<'info, AuctionHouse>,
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            LISTING_INSURANCE.as_bytes(),
+            auction_house.key().as_ref(),
+            seller.key().as_ref(),
+            listing_insurance.token_mint.as_ref(),
+        ],
+        bump=listing_insurance.bump,
+        has_one=seller,
+    )]
+    listing_insurance: Box<Account<'info, ListingInsurance>>,
+}
+
+// Withdrawing takes two calls: the first starts LISTING_INSURANCE_WITHDRAW_DELAY_SECONDS in which
+// buyers whose fills the seller broke can still be paid, the second closes the insurance to the
+// seller once it passed. Otherwise a seller could withdraw in the same transaction that moves the
+// token.
+pub fn handle(ctx: Context<WithdrawListingInsurance>) -> Result<()> {
+    let listing_insurance = &mut ctx.accounts.listing_insurance;
+    let now = Clock::get()?.unix_timestamp;
+
+    if listing_insurance.withdrawable_at == 0 {
+        listing_insurance.withdrawable_at = now
+            .checked_add(LISTING_INSURANCE_WITHDRAW_DELAY_SECONDS)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        msg!(
+            "{{\"listing_insurance\":\"{}\",\"withdrawable_at\":{}}}",
+            listing_insurance.key(),
+            listing_insurance.withdrawable_at
+        );
+        return Ok(());
+    }
+    if now < listing_insurance.withdrawable_at {
+        return Err(ErrorCode::ListingInsuranceLocked.into());
+    }
+
+    msg!(
+        "{{\"listing_insurance\":\"{}\",\"withdrawn\":{}}}",
+        listing_insurance.key(),
+        listing_insurance.amount
+    );
+    close_account_anchor(
+        &listing_insurance.to_account_info(),
+        &ctx.accounts.seller.to_account_info(),
+        true,
+    )
+}
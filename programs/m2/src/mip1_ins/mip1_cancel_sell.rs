@@ -8,7 +8,12 @@ use mpl_token_metadata::{
 use solana_program::sysvar;
 use spl_associated_token_account::get_associated_token_address;
 
-use crate::utils::{assert_is_ata, check_programmable, close_account_anchor};
+use crate::utils::{
+    assert_is_ata, assert_keys_equal, check_programmable, close_account_anchor,
+    get_listing_index_keys, release_listing, remove_from_mint_market,
+    split_guardian_from_remaining_accounts, split_notary_set_from_remaining_accounts,
+    RemainingAccountsTag,
+};
 use {
     crate::constants::*,
     crate::errors::ErrorCode,
@@ -22,8 +27,9 @@ use {
 
 #[derive(Accounts)]
 pub struct MIP1CancelSell<'info> {
+    /// CHECK: wallet must sign, otherwise its guardian must sign
     #[account(mut)]
-    wallet: Signer<'info>,
+    wallet: UncheckedAccount<'info>,
     notary: Signer<'info>,
     /// CHECK: program_as_signer
     #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
@@ -53,7 +59,6 @@ pub struct MIP1CancelSell<'info> {
     metadata: UncheckedAccount<'info>,
     #[account(
         seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()],
-        constraint = auction_house.notary == notary.key(),
         bump,
     )]
     auction_house: Box<Account<'info, AuctionHouse>>,
@@ -71,8 +76,8 @@ pub struct MIP1CancelSell<'info> {
     )]
     seller_trade_state: AccountInfo<'info>,
     /// CHECK: checked in CPI - account that will end up with the token
-    /// should always be ATA of (mint, wallet)
-    #[account(mut, address = get_associated_token_address(wallet.key, &token_mint.key()))]
+    /// should always be ATA of (mint, wallet), or of (mint, recipient) when delisting into another wallet
+    #[account(mut)]
     token_account: UncheckedAccount<'info>,
     /// CHECK: checked in CPI - temporary token account to facilitate MIP0 -> MIP1 migration
     /// should always be ATA of (mint, program_as_signer)
@@ -104,8 +109,26 @@ pub struct MIP1CancelSell<'info> {
     associated_token_program: Program<'info, AssociatedToken>,
     token_program: Program<'info, Token>,
     system_program: Program<'info, System>,
+    // remaining accounts, in this order, or in any order with a remaining accounts schema appended to the instruction data (see RemainingAccountsTag):
+    // notary_set, then notaries (signer) (optional) - auction house's NotarySet and threshold of its notaries, cosign in place of auction_house.notary, placed first
+    // guardian_account, then guardian (signer, writable) (required if wallet doesn't sign) - wallet's Guardian and its guardian, cancels back into the wallet and pays for the token record, placed before the accounts below
+    // 0. recipient (required if token_account isn't the wallet's ATA) - wallet migrating sellers delist into, the notary cosigns it
+    // 0.. listing_index (optional, writable) - seller's and verified collection's ListingIndex, the listing is removed from them
+    // mint_market (optional, writable) - token mint's MintMarket, the cancelled listing is dropped from it
+    // instructions sysvar (optional) - placed last if a remaining accounts schema is appended to the instruction data
 }
 
+/// Length of the mip1_cancel_sell instruction data, it has no args
+pub const MIP1_CANCEL_SELL_ARGS_LEN: usize = 8;
+
+/// Where mip1_cancel_sell expects each group of remaining accounts
+pub const MIP1_CANCEL_SELL_ORDER: [RemainingAccountsTag; 4] = [
+    RemainingAccountsTag::NotarySet,
+    RemainingAccountsTag::Guardian,
+    RemainingAccountsTag::Recipient,
+    RemainingAccountsTag::Keyed,
+];
+
 pub fn handle_mip1_cancel_sell<'info>(
     ctx: Context<'_, '_, '_, 'info, MIP1CancelSell<'info>>,
 ) -> Result<()> {
@@ -139,12 +162,46 @@ pub fn handle_mip1_cancel_sell<'info>(
 
     check_programmable(&Metadata::safe_deserialize(&metadata.data.borrow()).unwrap())?;
 
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        ctx.remaining_accounts,
+        ctx.accounts.auction_house.as_ref(),
+        &ctx.accounts.notary,
+    )?;
+    notary_cosign.assert_signed()?;
+    let (remaining_accounts, guardian) =
+        split_guardian_from_remaining_accounts(remaining_accounts, wallet)?;
+    let payer = match guardian {
+        Some(guardian) => guardian.clone(),
+        None if wallet.is_signer => wallet.to_account_info(),
+        None => return Err(ErrorCode::NoValidSignerPresent.into()),
+    };
+
+    // the token goes back to the wallet unless token_account is another wallet's ATA, e.g. when
+    // the seller migrates wallets. the notary has to sign for this instruction either way, and a
+    // guardian only cancels back into the wallet.
+    let wallet_token_account = get_associated_token_address(wallet.key, token_mint.key);
+    let (recipient, remaining_accounts) = if token_account.key() == wallet_token_account {
+        (wallet.to_account_info(), remaining_accounts)
+    } else {
+        if guardian.is_some() {
+            return Err(ErrorCode::NoValidSignerPresent.into());
+        }
+        let (recipient, remaining_accounts) = remaining_accounts
+            .split_first()
+            .ok_or(ErrorCode::MissingRemainingAccount)?;
+        assert_keys_equal(
+            token_account.key,
+            &get_associated_token_address(recipient.key, token_mint.key),
+        )?;
+        (recipient.clone(), remaining_accounts)
+    };
+
     let program_as_signer_seeds = &[
         PREFIX.as_bytes(),
         SIGNER.as_bytes(),
         &[ctx.bumps.program_as_signer],
     ];
-    let source_token_account = if token_ata.key().eq(token_account.key) {
+    let source_token_account = if token_ata.key() == wallet_token_account {
         // mip0 -> mip1 migration, need to move to temp token account
         let mut payload_map = HashMap::new();
         payload_map.insert(
@@ -171,7 +228,7 @@ pub fn handle_mip1_cancel_sell<'info>(
             .token_record(Some(owner_token_record.key()))
             .destination_token_record(Some(temp_token_record.key()))
             .authority(program_as_signer.key())
-            .payer(wallet.key())
+            .payer(payer.key())
             .system_program(system_program.key())
             .sysvar_instructions(instructions.key())
             .spl_token_program(token_program.key())
@@ -188,6 +245,7 @@ pub fn handle_mip1_cancel_sell<'info>(
             &ins,
             &[
                 wallet.to_account_info(),
+                payer.clone(),
                 program_as_signer.to_account_info(),
                 token_ata.to_account_info(),
                 token_account_temp.to_account_info(),
@@ -210,7 +268,7 @@ pub fn handle_mip1_cancel_sell<'info>(
             CpiContext::new(
                 token_program.to_account_info(),
                 SetAuthority {
-                    account_or_mint: token_account.to_account_info(),
+                    account_or_mint: token_ata.to_account_info(),
                     current_authority: program_as_signer.to_account_info(),
                 },
             )
@@ -235,14 +293,14 @@ pub fn handle_mip1_cancel_sell<'info>(
         .token(source_token_account.key())
         .token_owner(program_as_signer.key())
         .destination_token(token_account.key())
-        .destination_owner(wallet.key())
+        .destination_owner(recipient.key())
         .mint(token_mint.key())
         .metadata(metadata.key())
         .edition(Some(edition.key()))
         .token_record(Some(temp_token_record.key()))
         .destination_token_record(Some(destination_token_record.key()))
         .authority(program_as_signer.key())
-        .payer(wallet.key())
+        .payer(payer.key())
         .system_program(system_program.key())
         .sysvar_instructions(instructions.key())
         .spl_token_program(token_program.key())
@@ -262,6 +320,8 @@ pub fn handle_mip1_cancel_sell<'info>(
             token_account.to_account_info(),
             source_token_account.clone(),
             wallet.to_account_info(),
+            payer.clone(),
+            recipient.clone(),
             program_as_signer.to_account_info(),
             token_mint.to_account_info(),
             metadata.to_account_info(),
@@ -297,14 +357,27 @@ pub fn handle_mip1_cancel_sell<'info>(
         )?;
     }
 
-    assert_is_ata(token_account, wallet.key, token_mint.key, wallet.key)?;
+    assert_is_ata(token_account, recipient.key, token_mint.key, recipient.key)?;
 
-    close_account_anchor(seller_trade_state, wallet)?;
+    release_listing(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &get_listing_index_keys(wallet.key, Some(metadata.as_ref())),
+        seller_trade_state,
+    )?;
+    remove_from_mint_market(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        token_mint.key,
+        &[seller_trade_state.key],
+    )?;
+    close_account_anchor(seller_trade_state, wallet, true)?;
 
     msg!(
-        "mip1_cancel_sell: {{\"seller_trade_state\":\"{}\",\"token_account\":\"{}\"}}",
+        "mip1_cancel_sell: {{\"seller_trade_state\":\"{}\",\"token_account\":\"{}\",\"recipient\":\"{}\"}}",
         seller_trade_state.key(),
-        token_ata.key()
+        token_ata.key(),
+        recipient.key()
     );
     msg!(
         "{{\"price\":{},\"seller_expiry\":{}}}",
@@ -1,13 +1,11 @@
-use std::collections::HashMap;
-
-use mpl_token_metadata::{
-    accounts::Metadata,
-    instructions::TransferBuilder,
-    types::{AuthorizationData, Payload, PayloadType, SeedsVec, TransferArgs},
+use mpl_token_metadata::{accounts::Metadata, types::ProgrammableConfig};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::{invoke, invoke_signed},
+    system_instruction, sysvar,
 };
-use solana_program::{program::invoke_signed, sysvar};
 
-use crate::index_ra;
+use crate::{index_ra, strict_invariant};
 
 use {
     crate::constants::*,
@@ -21,11 +19,78 @@ use {
     },
 };
 
+/// Token metadata `Transfer` data moving one token with program_as_signer's `SourceSeeds`
+/// as the authorization payload, i.e. `TransferArgs::V1 { amount: 1, authorization_data }`.
+/// Pre-encoded so a sale doesn't build the `HashMap` backed `Payload` and serialize it.
+const PROGRAM_AS_SIGNER_TRANSFER_DATA: [u8; 51] = [
+    49, // Transfer discriminator
+    0,  // TransferArgs::V1
+    1, 0, 0, 0, 0, 0, 0, 0, // amount
+    1, // Some(AuthorizationData)
+    1, 0, 0, 0, // payload map len
+    11, 0, 0, 0, b'S', b'o', b'u', b'r', b'c', b'e', b'S', b'e', b'e', b'd', b's',
+    1, // PayloadType::Seeds
+    2, 0, 0, 0, // seeds len
+    2, 0, 0, 0, b'm', b'2', // PREFIX
+    6, 0, 0, 0, b's', b'i', b'g', b'n', b'e', b'r', // SIGNER
+];
+
+/// (is_writable, is_signer) of the token metadata `Transfer` accounts, in instruction order:
+/// token, token_owner, destination_token, destination_owner, mint, metadata, edition,
+/// token_record, destination_token_record, authority, payer, system_program,
+/// sysvar_instructions, spl_token_program, spl_ata_program, authorization_rules_program,
+/// authorization_rules.
+const TRANSFER_ACCOUNT_FLAGS: [(bool, bool); 17] = [
+    (true, false),
+    (false, false),
+    (true, false),
+    (false, false),
+    (false, false),
+    (true, false),
+    (false, false),
+    (true, false),
+    (true, false),
+    (false, true),
+    (true, true),
+    (false, false),
+    (false, false),
+    (false, false),
+    (false, false),
+    (false, false),
+    (false, false),
+];
+
+/// Builds the pNFT transfer signed by program_as_signer, `keys` are in `Transfer` account order.
+fn program_as_signer_transfer_instruction(keys: [Pubkey; 17]) -> Instruction {
+    let mut accounts = Vec::with_capacity(keys.len());
+    for (pubkey, &(is_writable, is_signer)) in keys.iter().zip(TRANSFER_ACCOUNT_FLAGS.iter()) {
+        accounts.push(AccountMeta {
+            pubkey: *pubkey,
+            is_signer,
+            is_writable,
+        });
+    }
+    Instruction {
+        program_id: mpl_token_metadata::ID,
+        accounts,
+        data: PROGRAM_AS_SIGNER_TRANSFER_DATA.to_vec(),
+    }
+}
+
+/// Rule set a transfer of the pNFT is authorized against, if it has one.
+fn rule_set(metadata: &Metadata) -> Option<Pubkey> {
+    match metadata.programmable_config {
+        Some(ProgrammableConfig::V1 { rule_set }) => rule_set,
+        None => None,
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct MIP1ExecuteSaleV2Args {
     pub price: u64,
     pub maker_fee_bp: i16,
     pub taker_fee_bp: u16,
+    pub expected_payment_mint: Pubkey,
 }
 
 #[derive(Accounts)]
@@ -72,7 +137,6 @@ pub struct MIP1ExecuteSaleV2<'info> {
     pub metadata: UncheckedAccount<'info>,
     #[account(
         seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()],
-        constraint = auction_house.notary == notary.key() @ ErrorCode::InvalidNotary,
         bump,
     )]
     pub auction_house: Box<Account<'info, AuctionHouse>>,
@@ -128,10 +192,10 @@ pub struct MIP1ExecuteSaleV2<'info> {
     token_metadata_program: UncheckedAccount<'info>,
     /// CHECK: checked in CPI
     edition: UncheckedAccount<'info>,
-    /// CHECK: checked in CPI
-    authorization_rules_program: UncheckedAccount<'info>,
-    /// CHECK: checked in CPI
-    authorization_rules: UncheckedAccount<'info>,
+    /// CHECK: checked in CPI - only needed if the pNFT has a rule set
+    authorization_rules_program: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked against the metadata's rule set and in CPI - only needed if the pNFT has one
+    authorization_rules: Option<UncheckedAccount<'info>>,
     /// CHECK: checked in CPI
     #[account(mut)]
     owner_token_record: UncheckedAccount<'info>,
@@ -146,7 +210,8 @@ pub struct MIP1ExecuteSaleV2<'info> {
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
-    // remaining accounts:
+    // remaining accounts, in this order, or in any order with a remaining accounts schema appended to the instruction data (see RemainingAccountsTag):
+    // notary_set, then notaries (signer) (optional) - auction house's NotarySet and the notaries cosigning with it, notary may be one of them instead of auction_house.notary, placed first
     // ** IF USING NATIVE SOL **
     // 0..=4. creators (optional) - if the buyer is paying in SOL, these are the creators of the token
     //
@@ -154,12 +219,50 @@ pub struct MIP1ExecuteSaleV2<'info> {
     // 0. payment_mint (required) - if the buyer is paying in a token, this is the mint of that token
     // 1. payment_source_token_account (required) - escrow token account controlled by escrow_payment_account
     // 2. payment_seller_token_account (required) - token account controlled by seller
-    // 3. payment_treausry_token_account (required) - token account controlled by auction_house_treasury
+    // 3. payment_treausry_token_account (required) - auction_house_treasury's ATA for the payment mint, created if needed
     // 4..=13. creator_token_account (optional) - if the buyer is paying in a SPL token, these are the creator token accounts,
     //                                            if the creator token accounts are not initialized, the creator itself needs to be
     //                                            included, in the format of creator_1_ATA, creator_1, creator_2_ATA, creator_2, ...
+    //                  or creator (optional) - alternatively the creator wallets in metadata order, starting with creator_1, the program
+    //                                            then derives each creator ATA and finds it among the accounts after the creators
+    // ...
+    // protocol fee recipient or its payment mint ATA (required if auction_house.protocol_fee_share_bp > 0) - placed after the accounts above
+    // escrow adapter program, then its accounts (required if the bid is funded by an escrow adapter) - placed after the accounts above
+    // receipt_owner (required if the bid names one) - receives the NFT instead of the buyer, buyer_receipt_token_account is its ATA
+    // escrow_config (optional) - buyer's EscrowConfig, honored when deciding whether to close the escrow
+    // open_bid_index (required if the bid is indexed, writable) - buyer's OpenBidIndex, the filled bid is removed from it
+    // escrow_ledger (required if the bid is ledgered, writable) - buyer's EscrowLedger, the filled bid is unlocked in it
+    // spending_allowance and its treasury's escrow_payment_account (required if the bid drew from a spending allowance, writable) - what the fill didn't use of the draw goes back to the treasury
+    // wallet_limits (required if the bid counts in the buyer's WalletLimits or auction_house.enforce_wallet_limits, writable) - buyer's WalletLimits, its uninitialized address if there is none, the filled bid is closed in it and, in SOL, the sale adds to the day's buy volume
+    // pending_royalties (optional, writable, SOL only) - PendingRoyalties of the auction house and mint, creators owed royalty but left out of the creator accounts get a claim in it, created if needed
+    // royalty_override (optional) - token mint's RoyaltyOverride, its recipients are paid the royalty in place of the metadata creators, the creator accounts follow its order
+    // bid_group (required if the bid is in a BidGroup, writable) - the fill is counted in it, its other bids can't fill once it had its max fills
+    // bid group bids (optional, writable) - the BidGroup's other bids, the fill that consumes the group closes them to the buyer and unlocks their escrow funds
+    // mint_market (optional, writable) - token mint's MintMarket, the filled bid and listing are dropped from it
+    // listing_index (optional, writable, the collection's is required if the bid is floor conditional) - seller's and verified collection's ListingIndex, the filled listing is removed from them
+    // house_metrics (optional, writable) - auction house's HouseMetrics for the payment mint, the sale is added to it
+    // program_config (optional) - its fee override for the payment mint applies unless the notary signed for the fees
+    // seller wSOL ATA (writable) and native mint (required if the listing takes proceeds as wSOL) - the ATA is created if needed
+    // proceeds_split (optional), then its recipients (writable) - seller's ProceedsSplit, the seller's SOL proceeds fan out to the recipients
+    // attestation (required if the listing requires one) - buyer's Attestation
+    // cpi_allowlist (required if the sale is a CPI and auction_house.cpi_policy is Allowlist) - auction house's CpiAllowlist
+    // loan program, then its loan_vault (writable) and accounts (required if the listing repays a loan, SOL only) - auction_house.loan_program, the seller's proceeds repay their loan against the NFT through it, the loan vault is the program's PDA of [LOAN_VAULT, seller, token_mint], placed after the accounts above
+    // instructions sysvar (optional) - placed last if a remaining accounts schema is appended to the instruction data
 }
 
+/// Length of the instruction data of mip1_execute_sale_v2 and mip1_bid_and_execute up to
+/// the end of their args, a remaining accounts schema may follow
+pub const MIP1_EXECUTE_SALE_V2_ARGS_LEN: usize = 8 + 8 + 2 + 2 + 32;
+
+/// Where mip1_execute_sale_v2 expects each group of remaining accounts
+pub const MIP1_EXECUTE_SALE_V2_ORDER: [RemainingAccountsTag; 5] = [
+    RemainingAccountsTag::NotarySet,
+    RemainingAccountsTag::Payment,
+    RemainingAccountsTag::Creators,
+    RemainingAccountsTag::Keyed,
+    RemainingAccountsTag::LoanRepayment,
+];
+
 pub fn handle_mip1_execute_sale<'info>(
     ctx: Context<'_, '_, '_, 'info, MIP1ExecuteSaleV2<'info>>,
     args: MIP1ExecuteSaleV2Args,
@@ -169,7 +272,6 @@ pub fn handle_mip1_execute_sale<'info>(
     let seller = &ctx.accounts.seller;
     let token_mint = &ctx.accounts.token_mint;
     let metadata = &ctx.accounts.metadata;
-    let notary = &ctx.accounts.notary;
     let seller_trade_state = &ctx.accounts.seller_trade_state;
     let buyer_trade_state = &ctx.accounts.buyer_trade_state;
     let buyer_escrow_payment_account = &ctx.accounts.buyer_escrow_payment_account;
@@ -190,7 +292,16 @@ pub fn handle_mip1_execute_sale<'info>(
     let token_program = &ctx.accounts.token_program;
     let system_program = &ctx.accounts.system_program;
     let instructions = &ctx.accounts.instructions;
-    let remaining_accounts = ctx.remaining_accounts;
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        ctx.remaining_accounts,
+        auction_house,
+        &ctx.accounts.notary,
+    )?;
+    notary_cosign.assert_known()?;
+    let (remaining_accounts, loan_repayment) = split_loan_repayment_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house,
+    )?;
 
     if !buyer.is_signer && !seller.is_signer {
         return Err(ErrorCode::SaleRequiresSigner.into());
@@ -198,10 +309,39 @@ pub fn handle_mip1_execute_sale<'info>(
     let taker = if buyer.is_signer { buyer } else { seller };
 
     let bid_args = BidArgs::from_account_info(buyer_trade_state)?;
+    let sell_args = SellArgs::from_account_info(seller_trade_state)?;
+    assert_loan_repayment(
+        loan_repayment.as_ref(),
+        sell_args.repay_loan,
+        seller.key,
+        &token_mint.key(),
+    )?;
     let is_spl = bid_args.payment_mint != Pubkey::default();
+    let (actual_maker_fee_bp, actual_taker_fee_bp) = get_actual_maker_taker_fee_bp(
+        &notary_cosign,
+        args.maker_fee_bp,
+        args.taker_fee_bp,
+        get_payment_mint_fees(ctx.remaining_accounts, &bid_args.payment_mint)?,
+    );
+    // pNFT sales pay the creators' full royalty whatever share the bid agreed to, all-in bids are
+    // checked and priced with the royalty the sale pays
+    let buyer_creator_royalty_bp = 10_000;
     bid_args.check_args(
         ctx.accounts.buyer_referral.key,
-        args.price,
+        get_bid_fill_price(
+            &bid_args,
+            args.price,
+            actual_maker_fee_bp,
+            actual_taker_fee_bp,
+            !buyer.is_signer,
+            || {
+                assert_metadata_valid(metadata, &token_mint.key())?;
+                let metadata =
+                    sell_args.royalty_locked(Metadata::safe_deserialize(&metadata.data.borrow())?);
+                Ok(get_royalty_bp(None, &metadata, args.price))
+            },
+            buyer_creator_royalty_bp,
+        )?,
         &token_mint.key(),
         1,
         if is_spl {
@@ -210,85 +350,138 @@ pub fn handle_mip1_execute_sale<'info>(
             &bid_args.payment_mint
         },
     )?;
-    let sell_args = SellArgs::from_account_info(seller_trade_state)?;
     sell_args.check_args(
         ctx.accounts.seller_referral.key,
-        &bid_args.buyer_price,
+        &args.price,
         &bid_args.token_mint,
         &1,
         &bid_args.payment_mint,
     )?;
+    assert_expected_payment_mint(
+        &args.expected_payment_mint,
+        &bid_args,
+        &sell_args,
+        remaining_accounts,
+    )?;
+    assert_buyer_attested(
+        auction_house,
+        &sell_args,
+        &bid_args.buyer,
+        remaining_accounts,
+    )?;
+    assert_cpi_caller_allowed(auction_house, Some(instructions), remaining_accounts)?;
 
-    let clock = Clock::get()?;
-    if bid_args.expiry.abs() > 1 && clock.unix_timestamp > bid_args.expiry.abs() {
-        return Err(ErrorCode::InvalidExpiry.into());
-    }
-    if sell_args.expiry.abs() > 1 && clock.unix_timestamp > sell_args.expiry.abs() {
-        return Err(ErrorCode::InvalidExpiry.into());
+    if auction_house.sandwich_guard {
+        assert_no_conflicting_instructions(
+            &ctx.accounts.instructions,
+            &[buyer_trade_state.key, seller_trade_state.key],
+        )?;
     }
 
+    let clock = Clock::get()?;
+    assert_not_expired(bid_args.expiry, clock.unix_timestamp)?;
+    assert_not_expired(sell_args.expiry, clock.unix_timestamp)?;
+    assert_listing_visible(sell_args.hidden_until, clock.unix_timestamp)?;
+
     assert_metadata_valid(metadata, &token_mint.key())?;
+    assert_metadata_pin(&bid_args.metadata_pin, metadata)?;
+    assert_floor_condition(
+        bid_args.max_floor_price,
+        get_verified_collection(metadata),
+        ctx.remaining_accounts,
+        &auction_house.key(),
+    )?;
 
     let program_as_signer_seeds = &[
         PREFIX.as_bytes(),
         SIGNER.as_bytes(),
         &[ctx.bumps.program_as_signer],
     ];
-    let payload = Payload {
-        map: HashMap::from([(
-            "SourceSeeds".to_owned(),
-            PayloadType::Seeds(SeedsVec {
-                seeds: vec![PREFIX.as_bytes().to_vec(), SIGNER.as_bytes().to_vec()],
-            }),
-        )]),
+    let receipt_owner = get_receipt_owner(remaining_accounts, buyer, &bid_args)?;
+    let metadata_parsed =
+        &sell_args.royalty_locked(Metadata::safe_deserialize(&metadata.data.borrow()).unwrap());
+    // an all-in bid pays exactly its price, the sale is priced at what that buys after the fees and
+    // royalty on top
+    let all_in_total = if bid_args.price_is_all_in {
+        Some(bid_args.price_at(clock.unix_timestamp))
+    } else {
+        None
     };
-    let ins = TransferBuilder::new()
-        .token(token_account.key())
-        .token_owner(token_account.owner)
-        .destination_token(buyer_receipt_token_account.key())
-        .destination_owner(buyer.key())
-        .mint(token_mint.key())
-        .metadata(metadata.key())
-        .edition(Some(edition.key()))
-        .token_record(Some(owner_token_record.key()))
-        .destination_token_record(Some(destination_token_record.key()))
-        .authority(program_as_signer.key())
-        .payer(payer.key())
-        .system_program(system_program.key())
-        .sysvar_instructions(instructions.key())
-        .spl_token_program(token_program.key())
-        .spl_ata_program(associated_token_program.key())
-        .authorization_rules_program(Some(authorization_rules_program.key()))
-        .authorization_rules(Some(authorization_rules.key()))
-        .transfer_args(TransferArgs::V1 {
-            authorization_data: Some(AuthorizationData { payload }),
-            amount: 1,
-        })
-        .instruction();
+    let price = match all_in_total {
+        Some(all_in_total) => {
+            let price = get_all_in_total_price(
+                all_in_total,
+                1,
+                actual_maker_fee_bp,
+                actual_taker_fee_bp,
+                taker.key == seller.key,
+                |unit_price| Ok(get_royalty_bp(None, metadata_parsed, unit_price)),
+                buyer_creator_royalty_bp,
+            )?;
+            if price < args.price {
+                return Err(ErrorCode::TradeStateMismatch.into());
+            }
+            price
+        }
+        None => args.price,
+    };
+    // pNFTs without a rule set skip the rules accounts, token metadata takes its own program id
+    // in place of an optional account
+    let mut transfer_account_infos = vec![
+        program_as_signer.to_account_info(),
+        token_account.to_account_info(),
+        buyer_receipt_token_account.to_account_info(),
+        receipt_owner.to_account_info(),
+        payer.to_account_info(),
+        token_mint.to_account_info(),
+        metadata.to_account_info(),
+        edition.to_account_info(),
+        token_program.to_account_info(),
+        associated_token_program.to_account_info(),
+        system_program.to_account_info(),
+        instructions.to_account_info(),
+        ctx.accounts.token_metadata_program.to_account_info(),
+        owner_token_record.to_account_info(),
+        destination_token_record.to_account_info(),
+        seller.to_account_info(), // might not be needed, but skips an unnecessary branch
+    ];
+    let (authorization_rules_program_key, authorization_rules_key) = match rule_set(metadata_parsed)
+    {
+        Some(rule_set) => {
+            let authorization_rules_program = authorization_rules_program
+                .as_ref()
+                .ok_or(ErrorCode::MissingRemainingAccount)?;
+            let authorization_rules = authorization_rules
+                .as_ref()
+                .ok_or(ErrorCode::MissingRemainingAccount)?;
+            assert_keys_equal(authorization_rules.key, &rule_set)?;
+            transfer_account_infos.push(authorization_rules_program.to_account_info());
+            transfer_account_infos.push(authorization_rules.to_account_info());
+            (authorization_rules_program.key(), rule_set)
+        }
+        None => (mpl_token_metadata::ID, mpl_token_metadata::ID),
+    };
+    let ins = program_as_signer_transfer_instruction([
+        token_account.key(),
+        token_account.owner,
+        buyer_receipt_token_account.key(),
+        receipt_owner.key(),
+        token_mint.key(),
+        metadata.key(),
+        edition.key(),
+        owner_token_record.key(),
+        destination_token_record.key(),
+        program_as_signer.key(),
+        payer.key(),
+        system_program.key(),
+        instructions.key(),
+        token_program.key(),
+        associated_token_program.key(),
+        authorization_rules_program_key,
+        authorization_rules_key,
+    ]);
 
-    invoke_signed(
-        &ins,
-        &[
-            program_as_signer.to_account_info(),
-            token_account.to_account_info(),
-            buyer_receipt_token_account.to_account_info(),
-            buyer.to_account_info(),
-            payer.to_account_info(),
-            token_mint.to_account_info(),
-            metadata.to_account_info(),
-            edition.to_account_info(),
-            token_program.to_account_info(),
-            associated_token_program.to_account_info(),
-            system_program.to_account_info(),
-            instructions.to_account_info(),
-            authorization_rules_program.to_account_info(),
-            authorization_rules.to_account_info(),
-            owner_token_record.to_account_info(),
-            destination_token_record.to_account_info(),
-            seller.to_account_info(), // might not be needed, but skips an unnecessary branch
-        ],
-        &[program_as_signer_seeds],
-    )?;
+    invoke_signed(&ins, &transfer_account_infos, &[program_as_signer_seeds])?;
 
     let buyer_escrow_signer_seeds: &[&[&[u8]]] = &[&[
         PREFIX.as_bytes(),
@@ -296,9 +489,24 @@ pub fn handle_mip1_execute_sale<'info>(
         buyer.key.as_ref(),
         &[ctx.bumps.buyer_escrow_payment_account],
     ]];
+    let treasury_signer_seeds: &[&[&[u8]]] = &[&[
+        PREFIX.as_bytes(),
+        auction_house_key.as_ref(),
+        TREASURY.as_bytes(),
+        &[auction_house.treasury_bump],
+    ]];
+
+    fund_escrow_from_adapter(
+        remaining_accounts,
+        auction_house,
+        &bid_args,
+        buyer_trade_state,
+        buyer,
+        buyer_escrow_payment_account,
+    )?;
+    let escrow_lamports_before = buyer_escrow_payment_account.lamports();
 
     // buyer pays creator royalties
-    let metadata_parsed = &Metadata::safe_deserialize(&metadata.data.borrow()).unwrap();
     let royalty = pay_creator_fees(
         &mut (if is_spl {
             remaining_accounts[4..].iter()
@@ -306,11 +514,16 @@ pub fn handle_mip1_execute_sale<'info>(
             remaining_accounts.iter()
         }),
         None,
-        metadata_parsed,
+        &get_royalty_override_metadata(
+            metadata_parsed.clone(),
+            &token_mint.key(),
+            remaining_accounts,
+        )?,
         &buyer_escrow_payment_account.to_account_info(),
+        buyer_trade_state,
         buyer_escrow_signer_seeds,
-        args.price,
-        10_000,
+        price,
+        buyer_creator_royalty_bp,
         if is_spl {
             Some(TransferCreatorSplArgs {
                 buyer,
@@ -323,35 +536,113 @@ pub fn handle_mip1_execute_sale<'info>(
         } else {
             None
         },
+        get_pending_royalties_args(
+            &auction_house_key,
+            &token_mint.key(),
+            remaining_accounts,
+            payer,
+        ),
     )?;
     check_programmable(metadata_parsed)?;
 
-    let (actual_maker_fee_bp, actual_taker_fee_bp) =
-        get_actual_maker_taker_fee_bp(notary, args.maker_fee_bp, args.taker_fee_bp);
+    let loan_vault_lamports = loan_repayment.as_ref().map(|l| l.loan_vault.lamports());
+    let listing_spl_args = if is_spl {
+        Some(TransferListingPaymentSplArgs {
+            payer,
+            buyer,
+            mint: index_ra!(remaining_accounts, 0),
+            payment_source_token_account: index_ra!(remaining_accounts, 1),
+            payment_seller_token_account: index_ra!(remaining_accounts, 2),
+            payment_treasury_token_account: index_ra!(remaining_accounts, 3),
+            system_program,
+            token_program,
+            treasury_signer_seeds,
+        })
+    } else {
+        None
+    };
     let (maker_fee, taker_fee) = transfer_listing_payment(
-        args.price,
+        price,
         actual_maker_fee_bp,
         actual_taker_fee_bp,
         taker,
         seller,
         buyer_escrow_payment_account,
         auction_house_treasury,
+        listing_spl_args,
+        get_protocol_fee_args(
+            auction_house,
+            remaining_accounts,
+            if is_spl {
+                Some(&bid_args.payment_mint)
+            } else {
+                None
+            },
+        )?,
+        get_house_metrics_args(
+            &auction_house.key(),
+            remaining_accounts,
+            &bid_args.payment_mint,
+            royalty,
+        ),
+        get_wsol_proceeds_args(
+            &sell_args,
+            &bid_args.payment_mint,
+            remaining_accounts,
+            payer,
+            system_program,
+            token_program,
+        )?,
+        get_proceeds_split_args(seller.key, remaining_accounts)?,
+        loan_repayment.as_ref().map(|l| l.loan_vault),
+        buyer_escrow_signer_seeds,
+    )?;
+    // an SPL sale pays out of the escrow's token account, its lamports don't move
+    strict_invariant!(assert_escrow_conserved(
+        escrow_lamports_before,
+        buyer_escrow_payment_account.lamports(),
         if is_spl {
-            Some(TransferListingPaymentSplArgs {
-                payer,
-                buyer,
-                mint: index_ra!(remaining_accounts, 0),
-                payment_source_token_account: index_ra!(remaining_accounts, 1),
-                payment_seller_token_account: index_ra!(remaining_accounts, 2),
-                payment_treasury_token_account: index_ra!(remaining_accounts, 3),
-                system_program,
-                token_program,
-            })
+            0
         } else {
-            None
+            expected_escrow_outflow(
+                price,
+                actual_maker_fee_bp,
+                actual_taker_fee_bp,
+                taker.key == seller.key,
+                royalty,
+            )?
         },
-        buyer_escrow_signer_seeds,
-    )?;
+    ));
+    if let Some(all_in_total) = all_in_total {
+        pay_all_in_remainder(
+            get_all_in_remainder(
+                all_in_total,
+                price,
+                actual_maker_fee_bp,
+                actual_taker_fee_bp,
+                taker.key == seller.key,
+                royalty,
+            )?,
+            buyer_escrow_payment_account,
+            auction_house_treasury,
+            listing_spl_args,
+            buyer_escrow_signer_seeds,
+        )?;
+    }
+    if let (Some(loan_repayment), Some(lamports_before)) = (&loan_repayment, loan_vault_lamports) {
+        repay_loan(
+            loan_repayment,
+            loan_repayment.loan_vault.lamports() - lamports_before,
+            &auction_house.to_account_info(),
+            &[
+                PREFIX.as_bytes(),
+                auction_house.creator.as_ref(),
+                &[auction_house.bump],
+            ],
+            &token_mint.to_account_info(),
+            seller,
+        )?;
+    }
 
     // close token account
     if token_account.amount == 1 && token_account.owner == program_as_signer.key() {
@@ -375,30 +666,261 @@ pub fn handle_mip1_execute_sale<'info>(
 
     assert_is_ata(
         buyer_receipt_token_account,
-        &buyer.key(),
+        receipt_owner.key,
         &token_mint.key(),
-        &buyer.key(),
+        receipt_owner.key,
     )?;
+    strict_invariant!(assert_ata_derivation(
+        buyer_receipt_token_account,
+        receipt_owner.key,
+        &token_mint.key()
+    ));
 
     try_close_buyer_escrow(
         buyer_escrow_payment_account,
         buyer,
         system_program,
+        get_escrow_config(remaining_accounts, &auction_house.key(), buyer.key)?.as_ref(),
         buyer_escrow_signer_seeds,
     )?;
 
     // we don't need to zero out buyer_trade_state, just copy zero discriminator to it and then close
-    close_account_anchor(buyer_trade_state, buyer)?;
-    close_account_anchor(seller_trade_state, seller)?;
+    release_open_bid(ctx.remaining_accounts, buyer_trade_state.key, &bid_args)?;
+    release_escrow_ledger_bid(ctx.remaining_accounts, &bid_args)?;
+    release_spending_allowance_bid(
+        ctx.remaining_accounts,
+        buyer_trade_state.key,
+        &bid_args,
+        Some(buyer_escrow_payment_account),
+        escrow_lamports_before.saturating_sub(buyer_escrow_payment_account.lamports()),
+    )?;
+    release_wallet_limits_bid(
+        ctx.remaining_accounts,
+        &ctx.accounts.auction_house,
+        &bid_args,
+        Some(price),
+    )?;
+    fill_bid_group_bid(
+        ctx.remaining_accounts,
+        auction_house,
+        &bid_args,
+        buyer_trade_state.key,
+        buyer,
+    )?;
+    close_account_anchor(buyer_trade_state, buyer, true)?;
+    release_listing(
+        ctx.remaining_accounts,
+        &auction_house.key(),
+        &get_listing_index_keys(seller.key, Some(metadata.as_ref())),
+        seller_trade_state,
+    )?;
+    remove_from_mint_market(
+        ctx.remaining_accounts,
+        &auction_house.key(),
+        &token_mint.key(),
+        &[buyer_trade_state.key, seller_trade_state.key],
+    )?;
+    close_account_anchor(seller_trade_state, seller, true)?;
     msg!(
         "{{\"maker_fee\":{},\"taker_fee\":{},\"royalty\":{},\"price\":{},\"seller_expiry\":{},\"buyer_expiry\":{}}}",
         maker_fee,
         taker_fee,
         royalty,
-        args.price,
+        price,
         sell_args.expiry,
         bid_args.expiry,
     );
+    log_order_memos(&bid_args, &sell_args);
 
     Ok(())
 }
+
+// Buy now for a listed pNFT in one instruction: the buyer tops up the escrow, the bid is written
+// and then settled against the existing listing right away, so there is no separate deposit or
+// buy_v2 instruction. SOL listings only, and the buyer must not have a bid on the mint already.
+pub fn handle_mip1_bid_and_execute<'info>(
+    ctx: Context<'_, '_, '_, 'info, MIP1ExecuteSaleV2<'info>>,
+    args: MIP1ExecuteSaleV2Args,
+) -> Result<()> {
+    let buyer = &ctx.accounts.buyer;
+    let seller_trade_state = &ctx.accounts.seller_trade_state;
+    let buyer_trade_state = &ctx.accounts.buyer_trade_state;
+    let buyer_escrow_payment_account = &ctx.accounts.buyer_escrow_payment_account;
+    let auction_house = &ctx.accounts.auction_house;
+    let token_mint = &ctx.accounts.token_mint;
+
+    if !buyer.is_signer {
+        return Err(ErrorCode::SaleRequiresSigner.into());
+    }
+    if seller_trade_state.data_is_empty() {
+        return Err(ErrorCode::BothPartiesNeedToAgreeToSale.into());
+    }
+    if !buyer_trade_state.data_is_empty() {
+        return Err(ErrorCode::TradeStateAlreadyExists.into());
+    }
+    let sell_args = SellArgs::from_account_info(seller_trade_state)?;
+    if sell_args.payment_mint != Pubkey::default() {
+        return Err(ErrorCode::ExpectedSolAccount.into());
+    }
+    // the bid is written for the single token of the mint, it can only fill a listing of it
+    if sell_args.token_size != 1 {
+        return Err(ErrorCode::InvalidTokenAmount.into());
+    }
+    if args.price > MAX_PRICE {
+        return Err(ErrorCode::InvalidPrice.into());
+    }
+
+    // the buyer is the taker, the escrow is topped up to all execute debits from it: the price,
+    // the taker fee and the royalty
+    let (_, notary_cosign) = split_notary_set_from_remaining_accounts(
+        ctx.remaining_accounts,
+        auction_house,
+        &ctx.accounts.notary,
+    )?;
+    let (actual_maker_fee_bp, actual_taker_fee_bp) = get_actual_maker_taker_fee_bp(
+        &notary_cosign,
+        args.maker_fee_bp,
+        args.taker_fee_bp,
+        get_payment_mint_fees(ctx.remaining_accounts, &sell_args.payment_mint)?,
+    );
+    assert_metadata_valid(&ctx.accounts.metadata, &token_mint.key())?;
+    let metadata = sell_args.royalty_locked(Metadata::safe_deserialize(
+        &ctx.accounts.metadata.data.borrow(),
+    )?);
+    let total_cost = get_buyer_total_cost(
+        args.price,
+        actual_maker_fee_bp,
+        actual_taker_fee_bp,
+        false,
+        get_royalty_bp(None, &metadata, args.price),
+        10_000,
+    )?;
+    if buyer_escrow_payment_account.lamports() < total_cost {
+        let diff = total_cost - buyer_escrow_payment_account.lamports();
+        invoke(
+            &system_instruction::transfer(buyer.key, buyer_escrow_payment_account.key, diff),
+            &[
+                buyer.to_account_info(),
+                buyer_escrow_payment_account.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    // the buyer funds the trade state rent, it's refunded to the buyer when the sale closes it
+    create_or_realloc_buyer_trade_state(
+        buyer_trade_state,
+        buyer,
+        None,
+        &[
+            PREFIX.as_bytes(),
+            buyer.key.as_ref(),
+            auction_house.key().as_ref(),
+            token_mint.key().as_ref(),
+            &[ctx.bumps.buyer_trade_state],
+        ],
+    )?;
+    let bts_v3 = BuyerTradeStateV3 {
+        auction_house_key: auction_house.key(),
+        buyer: buyer.key(),
+        buyer_referral: ctx.accounts.buyer_referral.key(),
+        buyer_price: args.price,
+        token_mint: token_mint.key(),
+        token_size: 1,
+        bump: ctx.bumps.buyer_trade_state,
+        expiry: 0,
+        buyer_creator_royalty_bp: 10_000,
+        ..Default::default()
+    };
+    let bts_v3_serialized = bts_v3.try_to_vec()?;
+    buyer_trade_state.try_borrow_mut_data()?[8..8 + bts_v3_serialized.len()]
+        .copy_from_slice(&bts_v3_serialized);
+
+    handle_mip1_execute_sale(ctx, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mpl_token_metadata::{
+        instructions::TransferBuilder,
+        types::{AuthorizationData, Payload, PayloadType, SeedsVec, TransferArgs},
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn program_as_signer_transfer_instruction_matches_transfer_builder() {
+        let keys: [Pubkey; 17] = std::array::from_fn(|_| Pubkey::new_unique());
+        let payload = Payload {
+            map: HashMap::from([(
+                "SourceSeeds".to_owned(),
+                PayloadType::Seeds(SeedsVec {
+                    seeds: vec![PREFIX.as_bytes().to_vec(), SIGNER.as_bytes().to_vec()],
+                }),
+            )]),
+        };
+        let expected = TransferBuilder::new()
+            .token(keys[0])
+            .token_owner(keys[1])
+            .destination_token(keys[2])
+            .destination_owner(keys[3])
+            .mint(keys[4])
+            .metadata(keys[5])
+            .edition(Some(keys[6]))
+            .token_record(Some(keys[7]))
+            .destination_token_record(Some(keys[8]))
+            .authority(keys[9])
+            .payer(keys[10])
+            .system_program(keys[11])
+            .sysvar_instructions(keys[12])
+            .spl_token_program(keys[13])
+            .spl_ata_program(keys[14])
+            .authorization_rules_program(Some(keys[15]))
+            .authorization_rules(Some(keys[16]))
+            .transfer_args(TransferArgs::V1 {
+                authorization_data: Some(AuthorizationData { payload }),
+                amount: 1,
+            })
+            .instruction();
+
+        assert_eq!(program_as_signer_transfer_instruction(keys), expected);
+    }
+
+    #[test]
+    fn program_as_signer_transfer_instruction_without_rule_set_matches_transfer_builder() {
+        let mut keys: [Pubkey; 17] = std::array::from_fn(|_| Pubkey::new_unique());
+        keys[15] = mpl_token_metadata::ID;
+        keys[16] = mpl_token_metadata::ID;
+        let payload = Payload {
+            map: HashMap::from([(
+                "SourceSeeds".to_owned(),
+                PayloadType::Seeds(SeedsVec {
+                    seeds: vec![PREFIX.as_bytes().to_vec(), SIGNER.as_bytes().to_vec()],
+                }),
+            )]),
+        };
+        let expected = TransferBuilder::new()
+            .token(keys[0])
+            .token_owner(keys[1])
+            .destination_token(keys[2])
+            .destination_owner(keys[3])
+            .mint(keys[4])
+            .metadata(keys[5])
+            .edition(Some(keys[6]))
+            .token_record(Some(keys[7]))
+            .destination_token_record(Some(keys[8]))
+            .authority(keys[9])
+            .payer(keys[10])
+            .system_program(keys[11])
+            .sysvar_instructions(keys[12])
+            .spl_token_program(keys[13])
+            .spl_ata_program(keys[14])
+            .transfer_args(TransferArgs::V1 {
+                authorization_data: Some(AuthorizationData { payload }),
+                amount: 1,
+            })
+            .instruction();
+
+        assert_eq!(program_as_signer_transfer_instruction(keys), expected);
+    }
+}
@@ -18,9 +18,15 @@ use {
     crate::errors::ErrorCode,
     crate::states::*,
     crate::utils::{
-        assert_is_ata, assert_payment_mint, check_programmable, close_account_anchor,
-        create_or_realloc_seller_trade_state, get_delegate_info_and_token_state_from_token_record,
-        split_payer_from_remaining_accounts,
+        assert_expiry_mode, assert_is_ata, assert_payment_mint, check_programmable,
+        close_account_anchor, create_or_realloc_seller_trade_state,
+        get_delegate_info_and_token_state_from_token_record, get_listing_index_keys,
+        get_registered_referral, get_royalty_bp_snapshot, record_listing, release_listing,
+        split_listing_indexes_from_remaining_accounts, split_mint_market_from_remaining_accounts,
+        split_notary_set_from_remaining_accounts, split_payer_from_remaining_accounts,
+        split_program_config_from_remaining_accounts, split_rate_limit_from_remaining_accounts,
+        split_referrer_from_remaining_accounts, split_rent_vault_from_remaining_accounts,
+        update_mint_market, write_seller_trade_state, RemainingAccountsTag,
     },
     anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize},
     anchor_spl::{
@@ -35,6 +41,9 @@ pub struct MIP1SellArgs {
     pub expiry: i64,
 }
 
+/// Length of the mip1_sell instruction data up to the end of MIP1SellArgs, SellExtraArgs follow
+pub const MIP1_SELL_ARGS_LEN: usize = 8 + 8 + 8;
+
 #[derive(Accounts)]
 pub struct MIP1Sell<'info> {
     #[account(mut)]
@@ -68,7 +77,6 @@ pub struct MIP1Sell<'info> {
     metadata: UncheckedAccount<'info>,
     #[account(
         seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()],
-        constraint = auction_house.notary == notary.key(),
         bump,
     )]
     auction_house: Box<Account<'info, AuctionHouse>>,
@@ -118,7 +126,7 @@ pub struct MIP1Sell<'info> {
     authorization_rules: UncheckedAccount<'info>,
     /// CHECK: check in cpi
     #[account(address = sysvar::instructions::id())]
-    instructions: UncheckedAccount<'info>,
+    pub instructions: UncheckedAccount<'info>,
     /// CHECK: checked in CPI
     #[account(mut, address = TokenRecord::find_pda(&token_mint.key(), &token_account.key()).0)]
     owner_token_record: UncheckedAccount<'info>,
@@ -130,24 +138,90 @@ pub struct MIP1Sell<'info> {
     token_program: Program<'info, Token>,
     system_program: Program<'info, System>,
     rent: Sysvar<'info, Rent>,
-    // remaining accounts:
+    // remaining accounts, in this order, or in any order if extra_args has a remaining accounts schema (see RemainingAccountsTag):
+    // program_config (optional) - the deployment's ProgramConfig, placed before the accounts below, its payment mints are accepted too
+    // rate_limit (required if the auction house rate limits wallets, writable) - wallet's RateLimit, placed after program_config and before the accounts below
     // 0. payment_mint (optional) - if the seller wants payment in a SPL token, this is the mint of that token
     // ...
+    // mint_market (optional, writable) - token mint's MintMarket, the listing is offered as its best ask, placed before listing_index
+    // -4.. listing_index (optional) - seller's and verified collection's ListingIndex, the listing is recorded in them
+    // -3. referrer (optional) - Referrer registration of seller_referral, the referral is only stored if it is registered
+    // notary_set, then notaries (signer) (optional) - auction house's NotarySet and threshold of its notaries, cosign in place of auction_house.notary for the rent vault, placed first
+    // -2. rent_vault (optional) - auction house rent vault, pays for sts rent instead of the wallet if the notary signs and there's no payer
     // -1. payer (optional) - this wallet will try to pay for sts rent
 }
 
+/// Where mip1_sell expects each group of remaining accounts
+pub const MIP1_SELL_ORDER: [RemainingAccountsTag; 9] = [
+    RemainingAccountsTag::NotarySet,
+    RemainingAccountsTag::ProgramConfig,
+    RemainingAccountsTag::RateLimit,
+    RemainingAccountsTag::Payment,
+    RemainingAccountsTag::MintMarket,
+    RemainingAccountsTag::ListingIndex,
+    RemainingAccountsTag::Referrer,
+    RemainingAccountsTag::RentVault,
+    RemainingAccountsTag::Payer,
+];
+
 pub fn handle_mip1_sell<'info>(
     ctx: Context<'_, '_, '_, 'info, MIP1Sell<'info>>,
     args: &MIP1SellArgs,
+    extra_args: &[u8],
 ) -> Result<()> {
+    let extra_args = SellExtraArgs::parse(extra_args);
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        ctx.remaining_accounts,
+        ctx.accounts.auction_house.as_ref(),
+        &ctx.accounts.notary,
+    )?;
+    notary_cosign.assert_known()?;
+    let (remaining_accounts, program_config) =
+        split_program_config_from_remaining_accounts(remaining_accounts)?;
+    let remaining_accounts = split_rate_limit_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &ctx.accounts.auction_house,
+        ctx.accounts.wallet.key,
+    )?;
     let (remaining_accounts, possible_payer) =
-        split_payer_from_remaining_accounts(ctx.remaining_accounts);
+        split_payer_from_remaining_accounts(remaining_accounts);
     let wallet = &ctx.accounts.wallet;
     let payer = if let Some(p) = possible_payer {
         p
     } else {
         wallet
     };
+    let (remaining_accounts, rent_vault) = split_rent_vault_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &notary_cosign,
+    );
+    let rent_vault_seeds = rent_vault.as_ref().map(|v| v.seeds());
+    let (rent_payer, rent_payer_seeds) = match (possible_payer, &rent_vault) {
+        (None, Some(vault)) => (vault.info, rent_vault_seeds.as_ref().map(|s| &s[..])),
+        _ => (payer, None),
+    };
+    let (remaining_accounts, referrer) = split_referrer_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        ctx.accounts.seller_referral.key,
+    );
+    let seller_referral = get_registered_referral(referrer, ctx.accounts.seller_referral.key)?;
+    let listing_index_keys = get_listing_index_keys(
+        ctx.accounts.wallet.key,
+        Some(ctx.accounts.metadata.as_ref()),
+    );
+    let (remaining_accounts, listing_indexes) = split_listing_indexes_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &listing_index_keys,
+    );
+    let (remaining_accounts, mint_market) = split_mint_market_from_remaining_accounts(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &ctx.accounts.token_mint.key(),
+    );
     let token_mint = ctx.accounts.token_mint.as_ref().as_ref() as &AccountInfo;
     let token_program = &ctx.accounts.token_program;
     let associated_token_program = &ctx.accounts.associated_token_program;
@@ -158,7 +232,6 @@ pub fn handle_mip1_sell<'info>(
     let escrow_ata = &ctx.accounts.token_ata;
 
     let seller_trade_state = &ctx.accounts.seller_trade_state;
-    let seller_referral = &ctx.accounts.seller_referral;
     let auction_house = ctx.accounts.auction_house.as_ref().as_ref() as &AccountInfo;
 
     let metadata = &ctx.accounts.metadata;
@@ -175,9 +248,7 @@ pub fn handle_mip1_sell<'info>(
     if args.price > MAX_PRICE || args.price == 0 {
         return Err(ErrorCode::InvalidPrice.into());
     }
-    if args.expiry >= 0 {
-        return Err(ErrorCode::InvalidExpiry.into());
-    }
+    assert_expiry_mode(args.expiry, true)?;
 
     // not too pretty, but needed to preserve original init_if_needed behavior
     let (sell_args, migration_sell_args) =
@@ -196,7 +267,12 @@ pub fn handle_mip1_sell<'info>(
                 token_mint.key.as_ref(),
                 &[ctx.bumps.seller_trade_state],
             ];
-            create_or_realloc_seller_trade_state(seller_trade_state, payer, sts_seeds)?;
+            create_or_realloc_seller_trade_state(
+                seller_trade_state,
+                rent_payer,
+                rent_payer_seeds,
+                sts_seeds,
+            )?;
             ret
         } else {
             let sell_args = if seller_trade_state.data_is_empty() {
@@ -218,7 +294,12 @@ pub fn handle_mip1_sell<'info>(
                 token_mint.key.as_ref(),
                 &[ctx.bumps.migration_seller_trade_state],
             ];
-            create_or_realloc_seller_trade_state(migration_seller_trade_state, payer, sts_seeds)?;
+            create_or_realloc_seller_trade_state(
+                migration_seller_trade_state,
+                rent_payer,
+                rent_payer_seeds,
+                sts_seeds,
+            )?;
             let sts_seeds: &[&[u8]] = &[
                 PREFIX.as_bytes(),
                 wallet.key.as_ref(),
@@ -227,7 +308,12 @@ pub fn handle_mip1_sell<'info>(
                 token_mint.key.as_ref(),
                 &[ctx.bumps.seller_trade_state],
             ];
-            create_or_realloc_seller_trade_state(seller_trade_state, payer, sts_seeds)?;
+            create_or_realloc_seller_trade_state(
+                seller_trade_state,
+                rent_payer,
+                rent_payer_seeds,
+                sts_seeds,
+            )?;
             (sell_args, migration_sell_args)
         };
 
@@ -237,7 +323,7 @@ pub fn handle_mip1_sell<'info>(
         || migration_sell_args.auction_house_key.ne(&Pubkey::default())
             && migration_sell_args.auction_house_key.ne(auction_house.key)
     {
-        return Err(ErrorCode::InvalidAccountState.into());
+        return Err(ErrorCode::WrongAuctionHouseForState.into());
     }
 
     check_programmable(&Metadata::safe_deserialize(&metadata.data.borrow()).unwrap())?;
@@ -251,7 +337,7 @@ pub fn handle_mip1_sell<'info>(
                     token_account.amount,
                     migration_sell_args.seller
                 );
-                return Err(ErrorCode::InvalidAccountState.into());
+                return Err(ErrorCode::StaleMigrationState.into());
             }
             (
                 migration_seller_trade_state,
@@ -358,12 +444,12 @@ pub fn handle_mip1_sell<'info>(
                 Some(delegate_key) => {
                     if delegate_key != program_as_signer.key() {
                         msg!("unexpected delegate: {}", delegate_key);
-                        return Err(ErrorCode::InvalidAccountState.into());
+                        return Err(ErrorCode::DelegateMismatch.into());
                     }
                     if let Some(role) = delegate_role {
                         if role != TokenDelegateRole::Migration {
                             msg!("unexpected delegate role {:?}", role);
-                            return Err(ErrorCode::InvalidAccountState.into());
+                            return Err(ErrorCode::DelegateMismatch.into());
                         }
                         // modify a previous escrowless listing - likely resulting from migration ocp -> mip1
                         (
@@ -374,33 +460,70 @@ pub fn handle_mip1_sell<'info>(
                         )
                     } else {
                         msg!("Delegate must have a role!");
-                        return Err(ErrorCode::InvalidAccountState.into());
+                        return Err(ErrorCode::DelegateMismatch.into());
                     }
                 }
             }
         };
 
     let payment_mint = if remaining_accounts.len() == 1 {
-        assert_payment_mint(index_ra!(remaining_accounts, 0))?;
+        assert_payment_mint(index_ra!(remaining_accounts, 0), program_config.as_ref())?;
         index_ra!(remaining_accounts, 0).key()
     } else {
         Pubkey::default()
     };
-    let sts = SellerTradeStateV2 {
-        auction_house_key: auction_house.key(),
-        seller: wallet_key,
-        seller_referral: seller_referral.key(),
-        buyer_price: args.price,
-        token_mint: token_mint.key(),
-        token_account: escrow_account_key,
-        token_size: 1,
-        bump: sts_to_modify_bump,
-        expiry: args.expiry,
-        payment_mint,
-    };
-    let sts_v2_serialized = sts.try_to_vec()?;
-    sts_to_modify.try_borrow_mut_data()?[8..8 + sts_v2_serialized.len()]
-        .copy_from_slice(&sts_v2_serialized);
+    let sts = write_seller_trade_state(
+        sts_to_modify,
+        SellerTradeStateV3 {
+            auction_house_key: auction_house.key(),
+            seller: wallet_key,
+            seller_referral,
+            buyer_price: args.price,
+            token_mint: token_mint.key(),
+            token_account: escrow_account_key,
+            token_size: 1,
+            bump: sts_to_modify_bump,
+            expiry: args.expiry,
+            payment_mint,
+            origin: extra_args.origin,
+            order_memo: extra_args.order_memo,
+            proceeds_as_wsol: extra_args.proceeds_as_wsol,
+            auto_relist_price_bp: extra_args.auto_relist_price_bp,
+            auto_relist_max: extra_args.auto_relist_max,
+            // pNFT sales always pay full royalties
+            royalty_mode: RoyaltyMode::Full,
+            requires_attestation: extra_args.requires_attestation,
+            auto_match: extra_args.auto_match,
+            royalty_lock: extra_args.royalty_lock,
+            hidden_until: extra_args.hidden_until,
+            repay_loan: extra_args.repay_loan,
+            royalty_bp_snapshot: get_royalty_bp_snapshot(extra_args.royalty_lock, metadata)?,
+            ..Default::default()
+        },
+    )?;
+    // a migrated listing is released under its old trade state and recorded under the new one
+    if sts_to_close.key != sts_to_modify.key {
+        release_listing(
+            ctx.remaining_accounts,
+            auction_house.key,
+            &listing_index_keys,
+            sts_to_close,
+        )?;
+    }
+    let sell_args = SellArgs::from_account_info(sts_to_modify)?;
+    let sol_price = sell_args.visible_sol_price(Clock::get()?.unix_timestamp);
+    record_listing(
+        &listing_indexes,
+        payer,
+        auction_house.key,
+        sts_to_modify,
+        sol_price,
+    )?;
+    update_mint_market(mint_market, |market| {
+        // a migrated listing moves to its new trade state
+        market.remove(sts_to_close.key);
+        market.offer_ask(sts_to_modify.key, sol_price, sts.token_size)
+    })?;
 
     msg!(
         "mip1_sell: {{\"seller_trade_state\":\"{}\",\"token_account\":\"{}\"}}",
@@ -414,7 +537,7 @@ pub fn handle_mip1_sell<'info>(
     );
 
     if sts_to_close.key != sts_to_modify.key {
-        close_account_anchor(sts_to_close, wallet)?;
+        close_account_anchor(sts_to_close, wallet, true)?;
     }
     Ok(())
 }
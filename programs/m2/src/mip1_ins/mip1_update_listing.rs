@@ -0,0 +1,211 @@
+use mpl_token_metadata::accounts::Metadata;
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::index_ra;
+
+use {
+    crate::constants::*,
+    crate::errors::ErrorCode,
+    crate::states::*,
+    crate::utils::{
+        assert_payment_mint, check_programmable, create_or_realloc_seller_trade_state,
+        get_royalty_bp_snapshot, split_mint_market_from_remaining_accounts,
+        split_notary_set_from_remaining_accounts, split_program_config_from_remaining_accounts,
+        update_mint_market, write_seller_trade_state, RemainingAccountsTag,
+    },
+    anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize},
+    anchor_spl::token::{Mint, TokenAccount},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MIP1UpdateListingArgs {
+    pub price: u64,
+    pub extra_args: Vec<u8>,
+}
+
+#[derive(Accounts)]
+pub struct MIP1UpdateListing<'info> {
+    #[account(mut)]
+    wallet: Signer<'info>,
+    /// CHECK: optional
+    notary: UncheckedAccount<'info>,
+    /// CHECK: program_as_signer
+    #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
+    program_as_signer: UncheckedAccount<'info>,
+    #[account(
+        token::mint = token_mint,
+        constraint = token_ata.amount == 1 @ ErrorCode::TokenNotEscrowed,
+        constraint = token_ata.owner == program_as_signer.key() @ ErrorCode::TokenNotEscrowed,
+        address = get_associated_token_address(&program_as_signer.key(), &token_mint.key()),
+    )]
+    token_ata: Box<Account<'info, TokenAccount>>,
+    #[account(
+        constraint = token_mint.supply == 1 && token_mint.decimals == 0,
+    )]
+    token_mint: Box<Account<'info, Mint>>,
+    /// CHECK: metadata
+    #[account(
+    seeds = [
+        "metadata".as_bytes(),
+        mpl_token_metadata::ID.as_ref(),
+        token_mint.key().as_ref(),
+    ],
+    bump,
+    seeds::program = mpl_token_metadata::ID,
+    )]
+    metadata: UncheckedAccount<'info>,
+    #[account(
+        seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()],
+        bump,
+    )]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+    /// CHECK: seeds check and args check
+    #[account(
+        mut,
+        seeds=[
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_ata.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump
+    )]
+    seller_trade_state: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+    // remaining accounts, in this order, or in any order if extra_args has a remaining accounts schema (see RemainingAccountsTag):
+    // notary_set, then notaries (signer) (optional) - auction house's NotarySet and the notaries cosigning with it, notary may be one of them instead of auction_house.notary, placed first
+    // program_config (optional) - the deployment's ProgramConfig, placed before the accounts below, its payment mints are accepted too
+    // 0. payment_mint (optional) - if the seller wants payment in a SPL token, this is the mint of that token
+    // mint_market (optional, writable) - token mint's MintMarket, the repriced listing is offered as its best ask, placed last
+}
+
+/// Where mip1_update_listing expects each group of remaining accounts
+pub const MIP1_UPDATE_LISTING_ORDER: [RemainingAccountsTag; 4] = [
+    RemainingAccountsTag::NotarySet,
+    RemainingAccountsTag::ProgramConfig,
+    RemainingAccountsTag::Payment,
+    RemainingAccountsTag::MintMarket,
+];
+
+// Reprices an escrowed pNFT listing, the token already sits in the program_as_signer ATA so
+// there is no Token Metadata transfer, only the trade state is rewritten. Leaving out the
+// payment_mint switches the listing back to SOL.
+pub fn handle_mip1_update_listing<'info>(
+    ctx: Context<'_, '_, '_, 'info, MIP1UpdateListing<'info>>,
+    args: &MIP1UpdateListingArgs,
+) -> Result<()> {
+    let extra_args = SellExtraArgs::parse(&args.extra_args);
+    let wallet = &ctx.accounts.wallet;
+    let token_ata = &ctx.accounts.token_ata;
+    let token_mint = &ctx.accounts.token_mint;
+    let auction_house = &ctx.accounts.auction_house;
+    let seller_trade_state = &ctx.accounts.seller_trade_state;
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        ctx.remaining_accounts,
+        auction_house,
+        &ctx.accounts.notary,
+    )?;
+    notary_cosign.assert_known()?;
+    let (remaining_accounts, program_config) =
+        split_program_config_from_remaining_accounts(remaining_accounts)?;
+    let (remaining_accounts, mint_market) = split_mint_market_from_remaining_accounts(
+        remaining_accounts,
+        &auction_house.key(),
+        &token_mint.key(),
+    );
+
+    if args.price > MAX_PRICE || args.price == 0 {
+        return Err(ErrorCode::InvalidPrice.into());
+    }
+
+    let sell_args = SellArgs::from_account_info(seller_trade_state)?;
+    if sell_args.auction_house_key != auction_house.key() {
+        return Err(ErrorCode::WrongAuctionHouseForState.into());
+    }
+    if sell_args.seller != wallet.key()
+        || sell_args.token_account != token_ata.key()
+        || sell_args.token_mint != token_mint.key()
+    {
+        return Err(ErrorCode::TradeStateMismatch.into());
+    }
+    // the mint's supply is 1, a listing of any other size isn't one this instruction wrote
+    if sell_args.token_size != 1 {
+        return Err(ErrorCode::InvalidTokenAmount.into());
+    }
+
+    check_programmable(
+        &Metadata::safe_deserialize(&ctx.accounts.metadata.data.borrow())
+            .map_err(|_| ErrorCode::InvalidTokenStandard)?,
+    )?;
+
+    let payment_mint = if remaining_accounts.len() == 1 {
+        assert_payment_mint(index_ra!(remaining_accounts, 0), program_config.as_ref())?;
+        index_ra!(remaining_accounts, 0).key()
+    } else {
+        Pubkey::default()
+    };
+
+    create_or_realloc_seller_trade_state(
+        seller_trade_state,
+        wallet,
+        None,
+        &[
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_ata.key().as_ref(),
+            token_mint.key().as_ref(),
+            &[ctx.bumps.seller_trade_state],
+        ],
+    )?;
+    let sts = write_seller_trade_state(
+        seller_trade_state,
+        SellerTradeStateV3 {
+            auction_house_key: sell_args.auction_house_key,
+            seller: sell_args.seller,
+            seller_referral: sell_args.seller_referral,
+            buyer_price: args.price,
+            token_mint: sell_args.token_mint,
+            token_account: sell_args.token_account,
+            token_size: sell_args.token_size,
+            bump: ctx.bumps.seller_trade_state,
+            expiry: sell_args.expiry,
+            payment_mint,
+            origin: extra_args.origin,
+            order_memo: extra_args.order_memo,
+            proceeds_as_wsol: extra_args.proceeds_as_wsol,
+            auto_relist_price_bp: extra_args.auto_relist_price_bp,
+            auto_relist_max: extra_args.auto_relist_max,
+            // pNFT sales always pay full royalties
+            royalty_mode: RoyaltyMode::Full,
+            requires_attestation: extra_args.requires_attestation,
+            auto_match: extra_args.auto_match,
+            royalty_lock: extra_args.royalty_lock,
+            hidden_until: extra_args.hidden_until,
+            repay_loan: extra_args.repay_loan,
+            royalty_bp_snapshot: get_royalty_bp_snapshot(
+                extra_args.royalty_lock,
+                &ctx.accounts.metadata,
+            )?,
+            ..Default::default()
+        },
+    )?;
+    let sol_price = SellArgs::from_account_info(seller_trade_state)?
+        .visible_sol_price(Clock::get()?.unix_timestamp);
+    update_mint_market(mint_market, |market| {
+        market.offer_ask(seller_trade_state.key, sol_price, sts.token_size)
+    })?;
+
+    msg!(
+        "mip1_update_listing: {{\"seller_trade_state\":\"{}\",\"payment_mint\":\"{}\"}}",
+        seller_trade_state.key(),
+        sts.payment_mint
+    );
+    msg!(
+        "{{\"price\":{},\"seller_expiry\":{}}}",
+        sts.buyer_price,
+        sts.expiry
+    );
+    Ok(())
+}
@@ -6,3 +6,6 @@ pub use ocp_cancel_sell::*;
 
 pub mod ocp_execute_sale_v2;
 pub use ocp_execute_sale_v2::*;
+
+pub mod quote_ocp_royalty;
+pub use quote_ocp_royalty::*;
@@ -4,6 +4,11 @@ use {
     crate::constants::*,
     crate::errors::ErrorCode,
     crate::states::*,
+    crate::utils::{
+        get_listing_index_keys, is_cancel_authority, release_listing, remove_from_mint_market,
+        split_guardian_from_remaining_accounts, split_notary_set_from_remaining_accounts,
+        split_program_config_from_remaining_accounts, RemainingAccountsTag,
+    },
     anchor_lang::prelude::*,
     anchor_spl::token::{Mint, Token, TokenAccount},
 };
@@ -77,20 +82,52 @@ pub struct OCPCancelSell<'info> {
     token_program: Program<'info, Token>,
     system_program: Program<'info, System>,
     rent: Sysvar<'info, Rent>,
+    // remaining accounts, in this order, or in any order with a remaining accounts schema appended to the instruction data (see RemainingAccountsTag):
+    // notary_set, then notaries (signer) (optional) - auction house's NotarySet and threshold of its notaries, cosign in place of auction_house.notary, placed first
+    // program_config (optional) - the deployment's ProgramConfig, placed before the accounts below, its cancel authority may cancel too
+    // guardian_account, then guardian (signer) (optional) - wallet's Guardian and its guardian, cancels for the wallet, placed before the accounts below
+    // cancel_authority_registry (required if auction_house.cancel_authority_registry and the notary cancels for the wallet)
+    // 0.. listing_index (optional, writable) - seller's and verified collection's ListingIndex, the listing is removed from them
+    // mint_market (optional, writable) - token mint's MintMarket, the cancelled listing is dropped from it
+    // instructions sysvar (optional) - placed last if a remaining accounts schema is appended to the instruction data
 }
 
+/// Length of the ocp_cancel_sell instruction data, it has no args
+pub const OCP_CANCEL_SELL_ARGS_LEN: usize = 8;
+
+/// Where ocp_cancel_sell expects each group of remaining accounts
+pub const OCP_CANCEL_SELL_ORDER: [RemainingAccountsTag; 4] = [
+    RemainingAccountsTag::NotarySet,
+    RemainingAccountsTag::ProgramConfig,
+    RemainingAccountsTag::Guardian,
+    RemainingAccountsTag::Keyed,
+];
+
 pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, OCPCancelSell<'info>>) -> Result<()> {
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        ctx.remaining_accounts,
+        &ctx.accounts.auction_house,
+        &ctx.accounts.notary,
+    )?;
+    let (remaining_accounts, program_config) =
+        split_program_config_from_remaining_accounts(remaining_accounts)?;
     let notary = &ctx.accounts.notary;
     let wallet = &ctx.accounts.wallet;
+    let (remaining_accounts, guardian) =
+        split_guardian_from_remaining_accounts(remaining_accounts, wallet)?;
 
-    let cancel_authority_signed = *notary.key == CANCEL_AUTHORITY;
-    let auction_house_notary_signed = *notary.key == ctx.accounts.auction_house.notary;
+    let cancel_authority_signed = is_cancel_authority(
+        notary.key,
+        &ctx.accounts.auction_house,
+        program_config.as_ref(),
+        remaining_accounts,
+    )?;
 
-    if !wallet.is_signer && !cancel_authority_signed {
+    if !wallet.is_signer && guardian.is_none() && !cancel_authority_signed {
         return Err(ErrorCode::NoValidSignerPresent.into());
     }
 
-    if wallet.is_signer && !auction_house_notary_signed {
+    if (wallet.is_signer || guardian.is_some()) && !notary_cosign.signed {
         return Err(ErrorCode::NoValidSignerPresent.into());
     }
 
@@ -132,6 +169,19 @@ pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, OCPCancelSell<'info>>) -> R
         ))?;
     }
 
+    release_listing(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &get_listing_index_keys(wallet.key, Some(&ctx.accounts.metadata)),
+        &seller_trade_state.to_account_info(),
+    )?;
+    remove_from_mint_market(
+        remaining_accounts,
+        &ctx.accounts.auction_house.key(),
+        &ctx.accounts.token_mint.key(),
+        &[&seller_trade_state.key()],
+    )?;
+
     msg!(
         "{{\"price\":{},\"seller_expiry\":{}}}",
         seller_trade_state.buyer_price,
@@ -3,6 +3,8 @@ use mpl_token_metadata::accounts::Metadata;
 use open_creator_protocol::state::Policy;
 use solana_program::sysvar;
 
+use crate::strict_invariant;
+
 use {
     crate::constants::*,
     crate::errors::ErrorCode,
@@ -65,7 +67,6 @@ pub struct OCPExecuteSaleV2<'info> {
     pub metadata: UncheckedAccount<'info>,
     #[account(
         seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()],
-        constraint = auction_house.notary == notary.key() @ ErrorCode::InvalidNotary,
         bump,
     )]
     pub auction_house: Box<Account<'info, AuctionHouse>>,
@@ -137,8 +138,34 @@ pub struct OCPExecuteSaleV2<'info> {
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+    // remaining accounts, in this order, or in any order with a remaining accounts schema appended to the instruction data (see RemainingAccountsTag):
+    // notary_set, then notaries (signer) (optional) - auction house's NotarySet and the notaries cosigning with it, notary may be one of them instead of auction_house.notary, placed first
+    // cpi_allowlist (required if the sale is a CPI and auction_house.cpi_policy is Allowlist) - auction house's CpiAllowlist
+    // wallet_limits (required if the bid counts in the buyer's WalletLimits or auction_house.enforce_wallet_limits, writable) - buyer's WalletLimits, its uninitialized address if there is none, the filled bid is closed in it and the sale adds to the day's buy volume
+    // open_bid_index (required if the bid is indexed, writable) - buyer's OpenBidIndex, the filled bid is removed from it
+    // escrow_ledger (required if the bid is ledgered, writable) - buyer's EscrowLedger, the filled bid is unlocked in it
+    // spending_allowance and its treasury's escrow_payment_account (required if the bid drew from a spending allowance, writable) - what the fill didn't use of the draw goes back to the treasury
+    // pending_royalties (optional, writable, SOL only) - PendingRoyalties of the auction house and mint, creators owed royalty but left out of the creator accounts get a claim in it, created if needed
+    // royalty_override (optional) - token mint's RoyaltyOverride, its recipients are paid the royalty in place of the metadata creators, the creator accounts follow its order
+    // bid_group (required if the bid is in a BidGroup, writable) - the fill is counted in it, its other bids can't fill once it had its max fills
+    // bid group bids (optional, writable) - the BidGroup's other bids, the fill that consumes the group closes them to the buyer and unlocks their escrow funds
+    // mint_market (optional, writable) - token mint's MintMarket, the filled bid and listing are dropped from it
+    // listing_index (optional, writable, the collection's is required if the bid is floor conditional) - seller's and verified collection's ListingIndex, the filled listing is removed from them
+    // program_config (optional) - its fee override for the payment mint applies unless the notary signed for the fees
+    // proceeds_split (optional), then its recipients (writable) - seller's ProceedsSplit, the seller's SOL proceeds fan out to the recipients
+    // instructions sysvar (optional) - placed last if a remaining accounts schema is appended to the instruction data
 }
 
+/// Length of the ocp_execute_sale_v2 instruction data up to the end of its args
+pub const OCP_EXECUTE_SALE_V2_ARGS_LEN: usize = 8 + 8 + 2 + 2;
+
+/// Where ocp_execute_sale_v2 expects each group of remaining accounts
+pub const OCP_EXECUTE_SALE_V2_ORDER: [RemainingAccountsTag; 3] = [
+    RemainingAccountsTag::NotarySet,
+    RemainingAccountsTag::Creators,
+    RemainingAccountsTag::Keyed,
+];
+
 pub fn handle<'info>(
     ctx: Context<'_, '_, '_, 'info, OCPExecuteSaleV2<'info>>,
     args: OCPExecuteSaleV2Args,
@@ -148,7 +175,6 @@ pub fn handle<'info>(
     let seller = &ctx.accounts.seller;
     let token_mint = &ctx.accounts.token_mint;
     let metadata = &ctx.accounts.metadata;
-    let notary = &ctx.accounts.notary;
     let seller_trade_state = &ctx.accounts.seller_trade_state;
     let buyer_trade_state = &ctx.accounts.buyer_trade_state;
     let buyer_escrow_payment_account = &ctx.accounts.buyer_escrow_payment_account;
@@ -157,10 +183,41 @@ pub fn handle<'info>(
     let auction_house_treasury = &ctx.accounts.auction_house_treasury;
     let system_program = &ctx.accounts.system_program;
 
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        ctx.remaining_accounts,
+        auction_house,
+        &ctx.accounts.notary,
+    )?;
+    notary_cosign.assert_known()?;
     let bid_args = BidArgs::from_account_info(buyer_trade_state)?;
+    let (actual_maker_fee_bp, actual_taker_fee_bp) = get_actual_maker_taker_fee_bp(
+        &notary_cosign,
+        args.maker_fee_bp,
+        args.taker_fee_bp,
+        get_payment_mint_fees(remaining_accounts, &bid_args.payment_mint)?,
+    );
+    // OCP sales pay the creators' full royalty whatever share the bid agreed to, all-in bids are
+    // checked and priced with the royalty the sale pays
+    let buyer_creator_royalty_bp = 10_000;
     bid_args.check_args(
         &bid_args.buyer_referral,
-        args.price,
+        get_bid_fill_price(
+            &bid_args,
+            args.price,
+            actual_maker_fee_bp,
+            actual_taker_fee_bp,
+            !buyer.is_signer,
+            || {
+                assert_metadata_valid(metadata, &token_mint.key())?;
+                let metadata = Metadata::safe_deserialize(&metadata.data.borrow())?;
+                Ok(get_royalty_bp(
+                    Some(&ctx.accounts.ocp_policy),
+                    &metadata,
+                    args.price,
+                ))
+            },
+            buyer_creator_royalty_bp,
+        )?,
         &token_mint.key(),
         1,
         &Pubkey::default(),
@@ -173,16 +230,32 @@ pub fn handle<'info>(
         &1,
         &Pubkey::default(),
     )?;
+    assert_cpi_caller_allowed(
+        auction_house,
+        Some(&ctx.accounts.instructions),
+        remaining_accounts,
+    )?;
 
-    let clock = Clock::get()?;
-    if bid_args.expiry.abs() > 1 && clock.unix_timestamp > bid_args.expiry.abs() {
-        return Err(ErrorCode::InvalidExpiry.into());
-    }
-    if sell_args.expiry.abs() > 1 && clock.unix_timestamp > sell_args.expiry.abs() {
-        return Err(ErrorCode::InvalidExpiry.into());
+    if auction_house.sandwich_guard {
+        assert_no_conflicting_instructions(
+            &ctx.accounts.instructions,
+            &[buyer_trade_state.key, seller_trade_state.key],
+        )?;
     }
 
+    let clock = Clock::get()?;
+    assert_not_expired(bid_args.expiry, clock.unix_timestamp)?;
+    assert_not_expired(sell_args.expiry, clock.unix_timestamp)?;
+    assert_listing_visible(sell_args.hidden_until, clock.unix_timestamp)?;
+
     assert_metadata_valid(metadata, &token_mint.key())?;
+    assert_metadata_pin(&bid_args.metadata_pin, metadata)?;
+    assert_floor_condition(
+        bid_args.max_floor_price,
+        get_verified_collection(metadata),
+        remaining_accounts,
+        &auction_house.key(),
+    )?;
 
     open_creator_protocol::cpi::unlock(CpiContext::new_with_signer(
         ctx.accounts.ocp_program.to_account_info(),
@@ -202,6 +275,7 @@ pub fn handle<'info>(
         ]],
     ))?;
 
+    let receipt_owner = get_receipt_owner(remaining_accounts, buyer, &bid_args)?;
     if ctx.accounts.buyer_token_ata.data_is_empty() {
         open_creator_protocol::cpi::init_account(CpiContext::new(
             ctx.accounts.ocp_program.to_account_info(),
@@ -210,7 +284,7 @@ pub fn handle<'info>(
                 mint: ctx.accounts.token_mint.to_account_info(),
                 metadata: ctx.accounts.metadata.to_account_info(),
                 mint_state: ctx.accounts.ocp_mint_state.to_account_info(),
-                from: ctx.accounts.buyer.to_account_info(),
+                from: receipt_owner.clone(),
                 from_account: ctx.accounts.buyer_token_ata.to_account_info(),
                 cmt_program: ctx.accounts.cmt_program.to_account_info(),
                 instructions: ctx.accounts.instructions.to_account_info(),
@@ -222,6 +296,11 @@ pub fn handle<'info>(
             },
         ))?;
     }
+    strict_invariant!(assert_ata_derivation(
+        &ctx.accounts.buyer_token_ata,
+        receipt_owner.key,
+        &ctx.accounts.token_mint.key()
+    ));
 
     open_creator_protocol::cpi::transfer(CpiContext::new_with_signer(
         ctx.accounts.ocp_program.to_account_info(),
@@ -236,7 +315,7 @@ pub fn handle<'info>(
             instructions: ctx.accounts.instructions.to_account_info(),
             freeze_authority: ctx.accounts.ocp_freeze_authority.to_account_info(),
             token_program: ctx.accounts.token_program.to_account_info(),
-            to: ctx.accounts.buyer.to_account_info(),
+            to: receipt_owner,
             to_account: ctx.accounts.buyer_token_ata.to_account_info(),
         },
         &[&[
@@ -253,23 +332,73 @@ pub fn handle<'info>(
         &[ctx.bumps.buyer_escrow_payment_account],
     ]];
 
+    fund_escrow_from_adapter(
+        remaining_accounts,
+        auction_house,
+        &bid_args,
+        buyer_trade_state,
+        buyer,
+        buyer_escrow_payment_account,
+    )?;
+    let escrow_lamports_before = buyer_escrow_payment_account.lamports();
+
     // buyer pays creator royalties
     let metadata_parsed = &Metadata::safe_deserialize(&metadata.data.borrow()).unwrap();
+    // an all-in bid pays exactly its price, the sale is priced at what that buys after the fees and
+    // royalty on top
+    let all_in_total = if bid_args.price_is_all_in {
+        Some(bid_args.price_at(clock.unix_timestamp))
+    } else {
+        None
+    };
+    let price = match all_in_total {
+        Some(all_in_total) => {
+            let price = get_all_in_total_price(
+                all_in_total,
+                1,
+                actual_maker_fee_bp,
+                actual_taker_fee_bp,
+                payer.key == seller.key,
+                |unit_price| {
+                    Ok(get_royalty_bp(
+                        Some(&ctx.accounts.ocp_policy),
+                        metadata_parsed,
+                        unit_price,
+                    ))
+                },
+                buyer_creator_royalty_bp,
+            )?;
+            if price < args.price {
+                return Err(ErrorCode::TradeStateMismatch.into());
+            }
+            price
+        }
+        None => args.price,
+    };
     let royalty = pay_creator_fees(
-        &mut ctx.remaining_accounts.iter(),
+        &mut remaining_accounts.iter(),
         Some(&ctx.accounts.ocp_policy),
-        metadata_parsed,
+        &get_royalty_override_metadata(
+            metadata_parsed.clone(),
+            &token_mint.key(),
+            remaining_accounts,
+        )?,
         &buyer_escrow_payment_account.to_account_info(),
+        buyer_trade_state,
         buyer_escrow_signer_seeds,
-        args.price,
-        10_000,
+        price,
+        buyer_creator_royalty_bp,
         None,
+        get_pending_royalties_args(
+            &auction_house_key,
+            &token_mint.key(),
+            remaining_accounts,
+            payer,
+        ),
     )?;
 
-    let (actual_maker_fee_bp, actual_taker_fee_bp) =
-        get_actual_maker_taker_fee_bp(notary, args.maker_fee_bp, args.taker_fee_bp);
     let (maker_fee, taker_fee) = transfer_listing_payment(
-        args.price,
+        price,
         actual_maker_fee_bp,
         actual_taker_fee_bp,
         payer,
@@ -277,28 +406,108 @@ pub fn handle<'info>(
         buyer_escrow_payment_account,
         auction_house_treasury,
         None,
+        get_protocol_fee_args(auction_house, remaining_accounts, None)?,
+        get_house_metrics_args(
+            &auction_house.key(),
+            remaining_accounts,
+            &Pubkey::default(),
+            royalty,
+        ),
+        get_wsol_proceeds_args(
+            &sell_args,
+            &bid_args.payment_mint,
+            remaining_accounts,
+            payer,
+            system_program,
+            &ctx.accounts.token_program,
+        )?,
+        get_proceeds_split_args(seller.key, remaining_accounts)?,
+        None,
         buyer_escrow_signer_seeds,
     )?;
+    strict_invariant!(assert_escrow_conserved(
+        escrow_lamports_before,
+        buyer_escrow_payment_account.lamports(),
+        expected_escrow_outflow(
+            price,
+            actual_maker_fee_bp,
+            actual_taker_fee_bp,
+            payer.key == seller.key,
+            royalty,
+        )?,
+    ));
+    if let Some(all_in_total) = all_in_total {
+        pay_all_in_remainder(
+            get_all_in_remainder(
+                all_in_total,
+                price,
+                actual_maker_fee_bp,
+                actual_taker_fee_bp,
+                payer.key == seller.key,
+                royalty,
+            )?,
+            buyer_escrow_payment_account,
+            auction_house_treasury,
+            None,
+            buyer_escrow_signer_seeds,
+        )?;
+    }
 
     try_close_buyer_escrow(
         buyer_escrow_payment_account,
         buyer,
         system_program,
+        get_escrow_config(remaining_accounts, &auction_house.key(), buyer.key)?.as_ref(),
         buyer_escrow_signer_seeds,
     )?;
 
     // we don't need to zero out buyer_trade_state, just copy zero discriminator to it and then close
-    close_account_anchor(buyer_trade_state, buyer)?;
-    close_account_anchor(seller_trade_state, seller)?;
+    release_open_bid(remaining_accounts, buyer_trade_state.key, &bid_args)?;
+    release_escrow_ledger_bid(remaining_accounts, &bid_args)?;
+    release_spending_allowance_bid(
+        remaining_accounts,
+        buyer_trade_state.key,
+        &bid_args,
+        Some(buyer_escrow_payment_account),
+        escrow_lamports_before.saturating_sub(buyer_escrow_payment_account.lamports()),
+    )?;
+    release_wallet_limits_bid(
+        remaining_accounts,
+        &ctx.accounts.auction_house,
+        &bid_args,
+        Some(price),
+    )?;
+    fill_bid_group_bid(
+        remaining_accounts,
+        auction_house,
+        &bid_args,
+        buyer_trade_state.key,
+        buyer,
+    )?;
+    close_account_anchor(buyer_trade_state, buyer, true)?;
+    release_listing(
+        remaining_accounts,
+        &auction_house.key(),
+        &get_listing_index_keys(seller.key, Some(metadata.as_ref())),
+        seller_trade_state,
+    )?;
+    remove_from_mint_market(
+        remaining_accounts,
+        &auction_house.key(),
+        &token_mint.key(),
+        &[buyer_trade_state.key, seller_trade_state.key],
+    )?;
+    close_account_anchor(seller_trade_state, seller, true)?;
     msg!(
         "{{\"maker_fee\":{},\"taker_fee\":{},\"royalty\":{},\"price\":{},\"seller_expiry\":{},\"buyer_expiry\":{}}}",
         maker_fee,
         taker_fee,
         royalty,
-        args.price,
+        price,
         sell_args.expiry,
         bid_args.expiry,
     );
+    log_order_memos(&bid_args, &sell_args);
 
     Ok(())
 }
@@ -5,14 +5,20 @@ use {
     crate::constants::*,
     crate::errors::ErrorCode,
     crate::states::*,
+    crate::utils::{
+        find_remaining_account, get_listing_index_keys, get_mint_market_address,
+        get_referrer_address, get_registered_referral, record_listing,
+        split_listing_indexes_from_remaining_accounts, split_notary_set_from_remaining_accounts,
+        split_rate_limit_from_remaining_accounts, update_mint_market, RemainingAccountsTag,
+    },
     anchor_lang::{prelude::*, AnchorDeserialize},
     anchor_spl::token::{Mint, Token, TokenAccount},
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct OCPSellArgs {
-    price: u64,
-    expiry: i64,
+    pub price: u64,
+    pub expiry: i64,
 }
 
 #[derive(Accounts)]
@@ -49,7 +55,6 @@ pub struct OCPSell<'info> {
     metadata: UncheckedAccount<'info>,
     #[account(
         seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref()],
-        constraint = auction_house.notary == notary.key(),
         bump,
     )]
     auction_house: Account<'info, AuctionHouse>,
@@ -64,7 +69,7 @@ pub struct OCPSell<'info> {
             token_mint.key().as_ref(),
         ],
         constraint = args.price > 0 && args.price <= MAX_PRICE @ ErrorCode::InvalidPrice,
-        constraint = args.expiry < 0 @ ErrorCode::InvalidExpiry,
+        constraint = Expiry::from(args.expiry).is_escrowed() @ ErrorCode::InvalidExpiry,
         space=SellerTradeState::LEN,
         bump)]
     seller_trade_state: Box<Account<'info, SellerTradeState>>,
@@ -91,8 +96,26 @@ pub struct OCPSell<'info> {
     token_program: Program<'info, Token>,
     system_program: Program<'info, System>,
     rent: Sysvar<'info, Rent>,
+    // remaining accounts, in this order, or in any order with a remaining accounts schema appended to the instruction data (see RemainingAccountsTag):
+    // notary_set, then notaries (signer) (optional) - auction house's NotarySet and the notaries cosigning with it, notary may be one of them instead of auction_house.notary, placed first
+    // rate_limit (required if the auction house rate limits wallets, writable) - wallet's RateLimit, placed before the accounts below
+    // 0.. listing_index (optional) - seller's and verified collection's ListingIndex, the listing is recorded in them
+    // referrer (optional) - Referrer registration of seller_referral, the referral is only stored if it is registered
+    // mint_market (optional, writable) - token mint's MintMarket, the listing is offered as its best ask, placed before listing_index
+    // instructions sysvar (optional) - placed last if a remaining accounts schema is appended to the instruction data
 }
 
+/// Length of the ocp_sell instruction data up to the end of its args
+pub const OCP_SELL_ARGS_LEN: usize = 8 + 8 + 8;
+
+/// Where ocp_sell expects each group of remaining accounts
+pub const OCP_SELL_ORDER: [RemainingAccountsTag; 4] = [
+    RemainingAccountsTag::NotarySet,
+    RemainingAccountsTag::RateLimit,
+    RemainingAccountsTag::Keyed,
+    RemainingAccountsTag::ListingIndex,
+];
+
 pub fn handle<'info>(
     ctx: Context<'_, '_, '_, 'info, OCPSell<'info>>,
     args: OCPSellArgs,
@@ -104,12 +127,29 @@ pub fn handle<'info>(
     let token_ata = ctx.accounts.token_ata.to_account_info();
 
     let seller_trade_state = &mut ctx.accounts.seller_trade_state;
-    let seller_referral = &ctx.accounts.seller_referral;
     let auction_house = &ctx.accounts.auction_house;
 
     let wallet_key = wallet.key();
     let token_mint_key = token_mint.key();
     let token_ata_key = token_ata.key();
+    let listing_index_keys = get_listing_index_keys(&wallet_key, Some(&ctx.accounts.metadata));
+    let (remaining_accounts, notary_cosign) = split_notary_set_from_remaining_accounts(
+        ctx.remaining_accounts,
+        auction_house,
+        &ctx.accounts.notary,
+    )?;
+    notary_cosign.assert_known()?;
+    let remaining_accounts = split_rate_limit_from_remaining_accounts(
+        remaining_accounts,
+        &auction_house.key(),
+        auction_house,
+        &wallet_key,
+    )?;
+    let (_, listing_indexes) = split_listing_indexes_from_remaining_accounts(
+        remaining_accounts,
+        &auction_house.key(),
+        &listing_index_keys,
+    );
 
     // can't set the existing seller_trade_state to another auction house
     if seller_trade_state.auction_house_key.ne(&Pubkey::default())
@@ -117,7 +157,7 @@ pub fn handle<'info>(
             .auction_house_key
             .ne(&auction_house.key())
     {
-        return Err(ErrorCode::InvalidAccountState.into());
+        return Err(ErrorCode::WrongAuctionHouseForState.into());
     }
 
     match ctx.accounts.ocp_mint_state.locked_by {
@@ -154,25 +194,47 @@ pub fn handle<'info>(
             ))?;
         }
         Some(locked_by) => {
-            if locked_by.ne(&program_as_signer.key()) || seller_trade_state.token_size == 0 {
-                // if locked_by is not program_as_signer, but locked, we should return error
-
-                // if locked_by is already program_as_signer, but token_size is 0
-                // this is likely a relist from other auction house, not change sell price, we should simply block it
-                return Err(ErrorCode::InvalidAccountState.into());
+            // if locked_by is not program_as_signer, but locked, we should return error
+            if locked_by.ne(&program_as_signer.key()) {
+                return Err(ErrorCode::DelegateMismatch.into());
+            }
+            // if locked_by is already program_as_signer, but token_size is 0
+            // this is likely a relist from other auction house, not change sell price, we should simply block it
+            if seller_trade_state.token_size == 0 {
+                return Err(ErrorCode::TokenAlreadyEscrowed.into());
             }
         }
     }
 
     seller_trade_state.auction_house_key = auction_house.key();
     seller_trade_state.seller = wallet_key;
-    seller_trade_state.seller_referral = seller_referral.key();
+    seller_trade_state.seller_referral = get_registered_referral(
+        find_remaining_account(
+            remaining_accounts,
+            &get_referrer_address(&auction_house.key(), ctx.accounts.seller_referral.key),
+        ),
+        ctx.accounts.seller_referral.key,
+    )?;
     seller_trade_state.buyer_price = args.price;
     seller_trade_state.token_mint = token_mint_key;
     seller_trade_state.token_account = token_ata_key;
     seller_trade_state.token_size = 1;
     seller_trade_state.bump = ctx.bumps.seller_trade_state;
     seller_trade_state.expiry = args.expiry; // negative number means non-movable listing mode
+    record_listing(
+        &listing_indexes,
+        &ctx.accounts.wallet,
+        &auction_house.key(),
+        &seller_trade_state.to_account_info(),
+        Some(args.price),
+    )?;
+    update_mint_market(
+        find_remaining_account(
+            remaining_accounts,
+            &get_mint_market_address(&auction_house.key(), &token_mint_key),
+        ),
+        |market| market.offer_ask(&seller_trade_state.key(), Some(args.price), 1),
+    )?;
 
     msg!(
         "{{\"price\":{},\"seller_expiry\":{}}}",
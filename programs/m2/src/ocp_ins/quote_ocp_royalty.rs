@@ -0,0 +1,31 @@
+use mpl_token_metadata::accounts::Metadata;
+use open_creator_protocol::state::Policy;
+
+use {crate::utils::get_royalty_bp, anchor_lang::prelude::*};
+
+#[derive(Accounts)]
+pub struct QuoteOCPRoyalty<'info> {
+    /// CHECK: token_mint, only used to derive the metadata
+    token_mint: UncheckedAccount<'info>,
+    /// CHECK: metadata
+    #[account(
+    seeds = [
+        "metadata".as_bytes(),
+        mpl_token_metadata::ID.as_ref(),
+        token_mint.key().as_ref(),
+    ],
+    bump,
+    seeds::program = mpl_token_metadata::ID,
+    )]
+    metadata: UncheckedAccount<'info>,
+    ocp_policy: Box<Account<'info, Policy>>,
+}
+
+// View only, nothing is written: returns the royalty bp ocp_execute_sale_v2 would charge at
+// price under the policy's dynamic royalty curve, before buyer_creator_royalty_bp is applied.
+pub fn handle(ctx: Context<QuoteOCPRoyalty>, price: u64) -> Result<u16> {
+    let metadata = Metadata::safe_deserialize(&ctx.accounts.metadata.data.borrow())?;
+    let royalty_bp = get_royalty_bp(Some(&ctx.accounts.ocp_policy), &metadata, price);
+    msg!("{{\"price\":{},\"royalty_bp\":{}}}", price, royalty_bp);
+    Ok(royalty_bp)
+}
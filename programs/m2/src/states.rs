@@ -1,6 +1,19 @@
-use anchor_lang::{prelude::*, AnchorDeserialize, Discriminator};
+use anchor_lang::{prelude::*, solana_program::hash::hashv, AnchorDeserialize, Discriminator};
+use anchor_spl::token::Mint;
+use arrayref::array_ref;
+use mpl_token_metadata::{accounts::Metadata, types::Creator};
 
-use crate::{errors::ErrorCode, utils::assert_owned_by};
+use crate::{
+    constants::{
+        HOUSE_METRICS_DAYS, MAX_ALT_PRICES, MAX_BID_GROUP_BIDS, MAX_CANCEL_AUTHORITIES,
+        MAX_CONFIG_PAYMENT_MINTS, MAX_CO_OWNERS, MAX_CPI_CALLERS, MAX_ESCROW_LEDGER_MINTS,
+        MAX_INDEXED_BIDS, MAX_MAKER_FEE_BP, MAX_NOTARIES, MAX_PENDING_ROYALTIES,
+        MAX_PROCEEDS_SPLIT_RECIPIENTS, MAX_ROYALTY_RECIPIENTS, MAX_SPENDING_ALLOWANCE_LOCKS,
+        MAX_TAKER_FEE_BP, METADATA_PIN_LEN,
+    },
+    errors::ErrorCode,
+    utils::{assert_not_zombie_trade_state, assert_owned_by, get_bid_locked_cost},
+};
 
 #[account]
 #[derive(Default, Copy)]
@@ -107,6 +120,173 @@ impl SellerTradeStateV2 {
     }
 }
 
+#[account]
+#[derive(Default, Copy)]
+pub struct SellerTradeStateV3 {
+    pub auction_house_key: Pubkey,
+    pub seller: Pubkey,
+    pub seller_referral: Pubkey,
+    pub buyer_price: u64,
+    pub token_mint: Pubkey,
+    pub token_account: Pubkey,
+    pub token_size: u64,
+    pub bump: u8,
+    pub expiry: i64, // in unix timestamp in seconds
+    pub payment_mint: Pubkey,
+    pub created_at: i64,      // listed, or first updated as V3 for older listings
+    pub last_updated_at: i64, // last price/expiry/payment mint change
+    pub origin: [u8; 8],      // client tag, kept when an update isn't tagged
+    pub order_memo: [u8; 32], // seller's own order id or hash, kept when an update has none
+    pub proceeds_as_wsol: bool, // SOL proceeds are paid into the seller's wSOL ATA
+    pub auto_relist_price_bp: u16, // price increase applied by auto_relist once the listing expired, 0 to disable
+    pub auto_relist_max: u8,       // how many times the listing may be auto relisted
+    pub auto_relist_count: u8,     // auto relists so far, reset when the seller updates the listing
+    pub frozen: bool, // delegate mode listing whose token account program_as_signer froze
+    pub royalty_mode: RoyaltyMode, // whether bids must pay the creators' full royalty to fill the listing
+    pub maker_rewards: bool, // listed with the seller's MakerRewards, which accrues when the listing closes
+    pub requires_attestation: bool, // only buyers with a valid Attestation from the house's attestation_authority can fill it
+    pub auto_match: bool, // anyone can match it against a crossing auto_match bid with match_orders
+    pub royalty_lock: bool, // fills pay the lower of royalty_bp_snapshot and the metadata's current royalty
+    pub royalty_bp_snapshot: u16, // metadata's seller_fee_basis_points when a royalty locked listing was listed
+    pub hidden_until: i64, // unix timestamp before which the listing can't be filled, 0 if it's visible right away
+    pub listing_indexes: u8, // ListingIndexes the listing is counted in, bit 0 the seller's and bit 1 its collection's
+    pub repay_loan: bool, // SOL fills pay the proceeds into the seller's loan vault of auction_house.loan_program
+}
+
+/// What a listing asks of the royalty of the bids that fill it. pNFT and OCP sales always pay full
+/// royalties and an auction house can enforce them for every listing, whatever the listing says.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RoyaltyMode {
+    /// The bid's buyer_creator_royalty_bp decides, as for listings made before royalty modes
+    #[default]
+    Optional,
+    /// Only bids paying the full royalty can fill the listing
+    Full,
+}
+
+/// What the i64 expiry of a trade state, or of an instruction creating one, means. It's stored as
+/// that i64 so layouts don't change: 0 and 1 never expire, a negative expiry is an escrowed
+/// listing, where program_as_signer holds the token, and any other value is a unix timestamp, for
+/// escrowed listings negated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Expiry {
+    /// 0 or 1, the order never expires
+    Never,
+    /// The order can't be filled after the timestamp
+    AtTimestamp(i64),
+    /// -1, an escrowed listing that never expires
+    EscrowedNoExpiry,
+    /// An escrowed listing that can't be filled after the timestamp
+    EscrowedAtTimestamp(i64),
+}
+
+impl Expiry {
+    pub fn is_escrowed(&self) -> bool {
+        matches!(
+            self,
+            Expiry::EscrowedNoExpiry | Expiry::EscrowedAtTimestamp(_)
+        )
+    }
+
+    pub fn timestamp(&self) -> Option<i64> {
+        match self {
+            Expiry::AtTimestamp(ts) | Expiry::EscrowedAtTimestamp(ts) => Some(*ts),
+            Expiry::Never | Expiry::EscrowedNoExpiry => None,
+        }
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        matches!(self.timestamp(), Some(ts) if now > ts)
+    }
+
+    /// The same mode with a new timestamp, e.g. when a listing is relisted
+    pub fn with_timestamp(&self, ts: i64) -> Self {
+        match self.is_escrowed() {
+            true => Expiry::EscrowedAtTimestamp(ts),
+            false => Expiry::AtTimestamp(ts),
+        }
+    }
+}
+
+impl From<i64> for Expiry {
+    fn from(raw: i64) -> Self {
+        match raw {
+            0 | 1 => Expiry::Never,
+            -1 => Expiry::EscrowedNoExpiry,
+            ts if ts > 1 => Expiry::AtTimestamp(ts),
+            ts => Expiry::EscrowedAtTimestamp(ts.saturating_neg()),
+        }
+    }
+}
+
+impl From<Expiry> for i64 {
+    fn from(expiry: Expiry) -> Self {
+        match expiry {
+            Expiry::Never => 0,
+            Expiry::AtTimestamp(ts) => ts,
+            Expiry::EscrowedNoExpiry => -1,
+            Expiry::EscrowedAtTimestamp(ts) => -ts,
+        }
+    }
+}
+
+/// A payment mint a listing accepts besides its own, at a price in that mint. Slots with a zero
+/// price are unused, a default payment mint is SOL.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AltPrice {
+    pub payment_mint: Pubkey,
+    pub price: u64,
+}
+
+impl AltPrice {
+    pub const LEN: usize = 32 + 8;
+}
+
+impl SellerTradeStateV3 {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // auction_house_key
+        32 + // seller
+        32 + // seller_referral
+        8 + // buyer_price
+        32 + // token_mint
+        32 + // token_account
+        8 + // token_size
+        1 + // bump
+        8 + // expiry
+        32 + // payment_mint
+        8 + // created_at
+        8 + // last_updated_at
+        8 + // origin
+        32 + // order_memo
+        1 + // proceeds_as_wsol
+        2 + // auto_relist_price_bp
+        1 + // auto_relist_max
+        1 + // auto_relist_count
+        1 + // frozen
+        1 + // royalty_mode
+        1 + // maker_rewards
+        1 + // requires_attestation
+        1 + // auto_match
+        1 + // royalty_lock
+        2 + // royalty_bp_snapshot
+        8 + // hidden_until
+        1 + // listing_indexes
+        1 + // repay_loan
+        80; // padding
+
+    /// Listings with alt prices are reallocated to hold them after LEN, the others don't pay rent
+    /// for them
+    pub const ALT_PRICES_LEN: usize = AltPrice::LEN * MAX_ALT_PRICES;
+
+    /// The alt prices stored after LEN, all unused for accounts without them
+    pub fn alt_prices_from_data(data: &[u8]) -> Result<[AltPrice; MAX_ALT_PRICES]> {
+        match data.get(Self::LEN..Self::LEN + Self::ALT_PRICES_LEN) {
+            Some(mut tail) => Ok(AnchorDeserialize::deserialize(&mut tail)?),
+            None => Ok(Default::default()),
+        }
+    }
+}
+
 #[allow(dead_code)]
 pub const AUCTION_HOUSE_SIZE: usize = 8 + // key
 32 + // auction_house_treasury
@@ -121,69 +301,1804 @@ pub const AUCTION_HOUSE_SIZE: usize = 8 + // key
 2 +  // seller_referral_bp
 1 +  // requires_notary
 1 +  // nprob, notary enforce probability, 0-100
-219; // padding
+1 +  // sandwich_guard
+32 + // protocol_fee_recipient
+2 +  // protocol_fee_share_bp
+64 + // escrow_adapters
+2 +  // min_bid_bp_of_list_price
+4 +  // delist_grace_seconds
+1 +  // cancel_authority_registry
+1 +  // enforce_royalties
+8 +  // maker_rewards_lamports_per_day
+2 +  // maker_rewards_volume_bp
+32 + // attestation_authority
+1 +  // cpi_policy
+32 + // loan_program
+32 + // staking_adapter
+1 +  // max_ixs_per_slot
+1 +  // allow_sfts
+1 +  // enforce_wallet_limits
+2; // padding
+
+#[account]
+pub struct AuctionHouse {
+    pub auction_house_treasury: Pubkey,
+    pub treasury_withdrawal_destination: Pubkey,
+    pub authority: Pubkey,
+    pub creator: Pubkey,
+    pub notary: Pubkey,
+    pub bump: u8,
+    pub treasury_bump: u8,
+    pub seller_fee_basis_points: u16,
+    pub buyer_referral_bp: u16,
+    pub seller_referral_bp: u16,
+    pub requires_notary: bool,
+    pub nprob: u8,            // notary enforce probability
+    pub sandwich_guard: bool, // reject fills sharing a txn with other ixs touching the same trade states
+    pub protocol_fee_recipient: Pubkey,
+    pub protocol_fee_share_bp: u16, // share of the platform fee forwarded to protocol_fee_recipient
+    pub escrow_adapters: [Pubkey; 2], // external escrow programs bids may be funded from, default key is an empty slot
+    pub min_bid_bp_of_list_price: u16, // bids below this share of a supplied listing's price are rejected, 0 to disable
+    pub delist_grace_seconds: u32, // the seller alone can cancel this long after listing, even if the notary is required
+    pub cancel_authority_registry: bool, // only the keys enabled in the house's CancelAuthorityRegistry can cancel for others
+    pub enforce_royalties: bool, // every listing is filled with full royalties, whatever its royalty_mode
+    pub maker_rewards_lamports_per_day: u64, // maker rewards accrued per day an enrolled listing was live
+    pub maker_rewards_volume_bp: u16, // maker rewards accrued on the SOL volume of filled enrolled listings
+    pub attestation_authority: Pubkey, // issues the Attestations listings requiring one check buyers against
+    pub cpi_policy: CpiPolicy,         // which programs may CPI into sales
+    pub loan_program: Pubkey, // lending program SOL sales may repay a seller's loan through, default key to disable
+    pub staking_adapter: Pubkey, // staking program sell may unstake a frozen token through, default key to disable
+    pub max_ixs_per_slot: u8, // orders a wallet may place per slot when the notary isn't required, 0 to disable
+    pub allow_sfts: bool, // sell and buy_v2 take semi-fungible tokens, with a supply above 1 and no decimals
+    pub enforce_wallet_limits: bool, // every bid and buy passes the wallet's WalletLimits address, so the limits can't be left out
+}
+
+/// Which programs may execute sales of an auction house through CPI. Top level sale
+/// instructions are always allowed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CpiPolicy {
+    /// Any program, e.g. any aggregator
+    #[default]
+    AllowAll,
+    /// Only the programs in the house's CpiAllowlist, which has to be in remaining accounts
+    Allowlist,
+}
+
+impl AuctionHouse {
+    /// Without a notary to filter spam, wallets placing orders count them in their RateLimit
+    pub fn is_rate_limited(&self) -> bool {
+        !self.requires_notary && self.max_ixs_per_slot > 0
+    }
+
+    /// NFTs, or semi-fungible tokens if the house allows them, whose listings and bids are priced
+    /// per unit of token_size
+    pub fn is_tradable_mint(&self, mint: &Mint) -> bool {
+        mint.decimals == 0 && (mint.supply == 1 || (self.allow_sfts && mint.supply > 1))
+    }
+
+    pub fn is_escrow_adapter_allowed(&self, adapter: &Pubkey) -> bool {
+        *adapter != Pubkey::default() && self.escrow_adapters.contains(adapter)
+    }
+
+    /// Referral fees are paid out of the platform fee, so together they can't exceed it
+    pub fn assert_referral_bp(&self) -> Result<()> {
+        let referral_bp = self
+            .buyer_referral_bp
+            .checked_add(self.seller_referral_bp)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        if referral_bp > self.seller_fee_basis_points {
+            return Err(ErrorCode::InvalidBasisPoints.into());
+        }
+        Ok(())
+    }
+}
+
+/// The auction house's attestation_authority vouching for a wallet, e.g. after KYC, so it can buy
+/// listings that require an attestation. It's only valid while its issuer is still the house's
+/// attestation_authority and, if it has one, until expiry.
+#[account]
+#[derive(Default, Copy)]
+pub struct Attestation {
+    pub auction_house_key: Pubkey,
+    pub wallet: Pubkey,
+    pub issuer: Pubkey,
+    pub expiry: i64, // unix timestamp, 0 if it doesn't expire
+    pub bump: u8,
+}
+
+impl Attestation {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // wallet
+    32 + // issuer
+    8 + // expiry
+    1 + // bump
+    15; // padding to 128 bytes
+
+    pub fn is_valid(&self, auction_house: &AuctionHouse, now: i64) -> bool {
+        self.issuer != Pubkey::default()
+            && self.issuer == auction_house.attestation_authority
+            && (self.expiry == 0 || now <= self.expiry)
+    }
+}
+
+/// Fee changes the authority scheduled with schedule_house_update. Anyone can apply them to the
+/// auction house once effective_at has passed, so fills in flight never see a fee change early.
+#[account]
+#[derive(Default, Copy)]
+pub struct PendingHouseUpdate {
+    pub auction_house_key: Pubkey,
+    pub bump: u8,
+    pub effective_at: i64,
+    pub seller_fee_basis_points: Option<u16>, // None leaves the current value
+    pub buyer_referral_bp: Option<u16>,
+    pub seller_referral_bp: Option<u16>,
+    pub protocol_fee_share_bp: Option<u16>,
+}
+
+impl PendingHouseUpdate {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    1 + // bump
+    8 + // effective_at
+    3 * 4 + // fee basis points
+    35; // padding to 96 bytes
+}
+
+#[account]
+#[derive(Default, Copy)]
+pub struct BuyerTradeStateV2 {
+    pub auction_house_key: Pubkey,
+    pub buyer: Pubkey,
+    pub buyer_referral: Pubkey,
+    pub buyer_price: u64,
+    pub token_mint: Pubkey,
+    pub token_size: u64,
+    pub bump: u8,
+    pub expiry: i64,
+    pub buyer_creator_royalty_bp: u16,
+    pub payment_mint: Pubkey,
+    pub indexed: bool,                        // listed in the buyer's OpenBidIndex
+    pub escrow_adapter: Pubkey, // external escrow program funding the bid, default key for the native escrow
+    pub order_memo: [u8; 32],   // buyer's own order id or hash
+    pub receipt_owner: Pubkey,  // receives the NFT instead of the buyer, default key for the buyer
+    pub ledgered: bool,         // locked in the buyer's EscrowLedger
+    pub metadata_pin: [u8; METADATA_PIN_LEN], // see get_metadata_pin, all zeroes if the bid doesn't pin the metadata
+}
+
+impl BuyerTradeStateV2 {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // buyer
+    32 + // buyer_referral
+    8 + // buyer_price
+    32 + // token_mint
+    8 + // token_size
+    1 + // bump
+    8 + // expiry
+    2 + // buyer_creator_ryoalty_bp
+    32 + // payment_mint
+    1 + // indexed
+    32 + // escrow_adapter
+    32 + // order_memo
+    32 + // receipt_owner
+    1 + // ledgered
+    METADATA_PIN_LEN + // metadata_pin
+    3; // padding to 320 bytes
+
+    pub fn from_bid_args(args: &BidArgs) -> Self {
+        BuyerTradeStateV2 {
+            auction_house_key: args.auction_house_key,
+            buyer: args.buyer,
+            buyer_referral: args.buyer_referral,
+            buyer_price: args.buyer_price,
+            token_mint: args.token_mint,
+            token_size: args.token_size,
+            bump: args.bump,
+            expiry: args.expiry,
+            buyer_creator_royalty_bp: args.buyer_creator_royalty_bp,
+            payment_mint: args.payment_mint,
+            indexed: args.indexed,
+            escrow_adapter: args.escrow_adapter,
+            order_memo: args.order_memo,
+            receipt_owner: args.receipt_owner,
+            ledgered: args.ledgered,
+            metadata_pin: args.metadata_pin,
+        }
+    }
+}
+
+/// The current buyer trade state, older bids are migrated to it when they're updated
+#[account]
+#[derive(Default, Copy)]
+pub struct BuyerTradeStateV3 {
+    pub auction_house_key: Pubkey,
+    pub buyer: Pubkey,
+    pub buyer_referral: Pubkey,
+    pub buyer_price: u64, // the price the bid starts at when it escalates
+    pub token_mint: Pubkey,
+    pub token_size: u64,
+    pub bump: u8,
+    pub expiry: i64,
+    pub buyer_creator_royalty_bp: u16,
+    pub payment_mint: Pubkey,
+    pub indexed: bool,                        // listed in the buyer's OpenBidIndex
+    pub escrow_adapter: Pubkey, // external escrow program funding the bid, default key for the native escrow
+    pub order_memo: [u8; 32],   // buyer's own order id or hash
+    pub receipt_owner: Pubkey,  // receives the NFT instead of the buyer, default key for the buyer
+    pub ledgered: bool,         // locked in the buyer's EscrowLedger
+    pub metadata_pin: [u8; METADATA_PIN_LEN], // see get_metadata_pin, all zeroes if the bid doesn't pin the metadata
+    pub escalation: BidEscalation, // climbs from buyer_price up to a max price over time, all zeroes if the bid doesn't escalate
+    pub limited: bool,             // counted in the buyer's WalletLimits
+    pub price_is_all_in: bool, // buyer_price is the buyer's total cost, platform fee and royalty included
+    pub auto_match: bool, // anyone can match it against a crossed auto_match listing with match_orders
+    pub swap_max_slippage_bp: u16, // SPL bids only, fills SOL listings through the swap adapter spending up to this much over the bid price, 0 if the bid doesn't swap
+    pub bid_group: Pubkey,         // BidGroup the bid fills through, default key if it isn't in one
+    pub allowance_funded: bool, // drew from a SpendingAllowance, what's left of the draw goes back to its treasury when the bid closes
+    pub max_floor_price: u64, // only fills while the verified collection's ListingIndex floor is at or below this SOL price, 0 if the bid isn't floor conditional
+}
+
+/// A bid that climbs from its buyer_price to max_price, linearly between start_at and end_at, so
+/// a patient buyer can work up towards the floor without bidding again. The escrow must hold
+/// max_price, the bid fills at any price between buyer_price and its current price.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BidEscalation {
+    pub max_price: u64, // 0 if the bid doesn't escalate
+    pub start_at: i64,
+    pub end_at: i64,
+}
+
+impl BidEscalation {
+    pub const LEN: usize = 8 + 8 + 8;
+
+    pub fn is_set(&self) -> bool {
+        self.max_price != 0
+    }
+
+    /// The price of a bid starting at buyer_price at now
+    pub fn price_at(&self, buyer_price: u64, now: i64) -> u64 {
+        if !self.is_set() || self.max_price <= buyer_price || now <= self.start_at {
+            return buyer_price;
+        }
+        if now >= self.end_at {
+            return self.max_price;
+        }
+        let elapsed = (now as i128 - self.start_at as i128) as u128;
+        let duration = (self.end_at as i128 - self.start_at as i128) as u128;
+        let climbed = (self.max_price - buyer_price) as u128 * elapsed / duration;
+        buyer_price + climbed as u64
+    }
+}
+
+impl BuyerTradeStateV3 {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // buyer
+    32 + // buyer_referral
+    8 + // buyer_price
+    32 + // token_mint
+    8 + // token_size
+    1 + // bump
+    8 + // expiry
+    2 + // buyer_creator_ryoalty_bp
+    32 + // payment_mint
+    1 + // indexed
+    32 + // escrow_adapter
+    32 + // order_memo
+    32 + // receipt_owner
+    1 + // ledgered
+    METADATA_PIN_LEN + // metadata_pin
+    BidEscalation::LEN + // escalation
+    1 + // limited
+    1 + // price_is_all_in
+    1 + // auto_match
+    2 + // swap_max_slippage_bp
+    32 + // bid_group
+    1 + // allowance_funded
+    8 + // max_floor_price
+    5; // padding to 392 bytes
+}
+
+#[account]
+#[derive(Default, Copy)]
+pub struct EscrowConfig {
+    pub auction_house_key: Pubkey,
+    pub wallet: Pubkey,
+    pub keep_open: bool, // never auto-close the escrow while it is rent exempt
+    pub min_resident_balance: u64, // auto-close the escrow after a fill when it holds less than this
+    pub bump: u8,
+}
+
+impl EscrowConfig {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // wallet
+    1 + // keep_open
+    8 + // min_resident_balance
+    1 + // bump
+    46; // padding to 128 bytes
+}
+
+#[account]
+#[derive(Default)]
+pub struct OpenBidIndex {
+    pub auction_house_key: Pubkey,
+    pub wallet: Pubkey,
+    pub locked_amount: u64, // lamports backing the wallet's indexed SOL bids
+    pub open_bids: u32,
+    pub bump: u8,
+    pub tracks_all_bids: bool, // created while the wallet's escrow was empty, so no bid backed by it predates the index
+    pub bids: Vec<Pubkey>,     // buyer_trade_state keys of the indexed bids, unordered
+}
+
+impl OpenBidIndex {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // wallet
+    8 + // locked_amount
+    4 + // open_bids
+    1 + // bump
+    1 + // tracks_all_bids
+    4 + // bids length, the account is reallocated as the list grows
+    38; // padding to 128 bytes
+
+    pub fn space(bids: usize) -> usize {
+        std::cmp::max(Self::LEN, Self::LEN - 38 + 32 * bids)
+    }
+
+    pub fn add_bid(
+        &mut self,
+        buyer_trade_state: &Pubkey,
+        buyer_price: u64,
+        payment_mint: &Pubkey,
+    ) -> Result<()> {
+        if self.bids.len() >= MAX_INDEXED_BIDS {
+            return Err(ErrorCode::OpenBidIndexFull.into());
+        }
+        if *payment_mint == Pubkey::default() {
+            self.locked_amount = self
+                .locked_amount
+                .checked_add(buyer_price)
+                .ok_or(ErrorCode::NumericalOverflow)?;
+        }
+        self.bids.push(*buyer_trade_state);
+        self.open_bids = self.bids.len() as u32;
+        Ok(())
+    }
+
+    /// Removes an indexed bid and its lock, a bid that isn't indexed anymore releases nothing
+    pub fn remove_bid(
+        &mut self,
+        buyer_trade_state: &Pubkey,
+        buyer_price: u64,
+        payment_mint: &Pubkey,
+    ) {
+        if let Some(i) = self.bids.iter().position(|k| k == buyer_trade_state) {
+            self.bids.swap_remove(i);
+            if *payment_mint == Pubkey::default() {
+                self.locked_amount = self.locked_amount.saturating_sub(buyer_price);
+            }
+        }
+        self.open_bids = self.bids.len() as u32;
+    }
+}
+
+#[account]
+#[derive(Default, Copy)]
+pub struct ListingIndex {
+    pub auction_house_key: Pubkey,
+    pub key: Pubkey, // seller wallet or verified collection mint
+    pub listings: u32,
+    pub cursor: u8, // next slot of the recent ring to write
+    pub bump: u8,
+    pub recent: [Pubkey; 16], // ring of recent seller_trade_state keys, cleared when delisted
+    pub floor_price: u64,     // lowest visible SOL price listed since the floor was last cleared
+    pub floor_listing: Pubkey, // seller_trade_state at floor_price, default key if there is no floor
+}
+
+impl ListingIndex {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // key
+    4 + // listings
+    1 + // cursor
+    1 + // bump
+    32 * 16 + // recent
+    8 + // floor_price
+    32 + // floor_listing
+    10; // padding to 640 bytes
+
+    /// Records a listing, is_new if it isn't counted in the index yet. sol_price is its unit price
+    /// in SOL, None if it isn't listed in SOL or is hidden. The floor can only be lowered here,
+    /// it's cleared when its listing is delisted or repriced above it.
+    pub fn add_listing(
+        &mut self,
+        seller_trade_state: &Pubkey,
+        is_new: bool,
+        sol_price: Option<u64>,
+    ) -> Result<()> {
+        if self.floor_listing == *seller_trade_state {
+            match sol_price {
+                Some(price) if price <= self.floor_price => self.floor_price = price,
+                _ => self.clear_floor(),
+            }
+        } else if let Some(price) = sol_price {
+            if self.floor_listing == Pubkey::default() || price < self.floor_price {
+                self.floor_price = price;
+                self.floor_listing = *seller_trade_state;
+            }
+        }
+        if is_new {
+            self.listings = self
+                .listings
+                .checked_add(1)
+                .ok_or(ErrorCode::NumericalOverflow)?;
+        }
+        if !self.recent.contains(seller_trade_state) {
+            self.recent[self.cursor as usize % self.recent.len()] = *seller_trade_state;
+            self.cursor = ((self.cursor as usize + 1) % self.recent.len()) as u8;
+        }
+        Ok(())
+    }
+
+    /// Removes a listing, only one that was counted in the index lowers its count
+    pub fn remove_listing(&mut self, seller_trade_state: &Pubkey, counted: bool) {
+        if counted {
+            self.listings = self.listings.saturating_sub(1);
+        }
+        if let Some(slot) = self.recent.iter_mut().find(|k| *k == seller_trade_state) {
+            *slot = Pubkey::default();
+        }
+        if self.floor_listing == *seller_trade_state {
+            self.clear_floor();
+        }
+    }
+
+    fn clear_floor(&mut self) {
+        self.floor_price = 0;
+        self.floor_listing = Pubkey::default();
+    }
+}
+
+#[account]
+#[derive(Default, Copy)]
+pub struct AuthorizationNonce {
+    pub auction_house_key: Pubkey,
+    pub wallet: Pubkey,
+    pub last_nonce: u64, // highest nonce consumed by an off-chain signed authorization
+    pub bump: u8,
+}
+
+impl AuthorizationNonce {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // wallet
+    8 + // last_nonce
+    1 + // bump
+    47; // padding to 128 bytes
+}
+
+#[account]
+#[derive(Default, Copy)]
+pub struct EditionSaleState {
+    pub auction_house_key: Pubkey,
+    pub seller: Pubkey,
+    pub master_mint: Pubkey,
+    pub token_account: Pubkey, // holds the master edition token, owned by program_as_signer while listed
+    pub price: u64,            // paid by the buyer for each print
+    pub max_prints: u64,
+    pub printed: u64,
+    pub bump: u8,
+}
+
+impl EditionSaleState {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // seller
+    32 + // master_mint
+    32 + // token_account
+    8 + // price
+    8 + // max_prints
+    8 + // printed
+    1 + // bump
+    95; // padding to 256 bytes
+}
+
+#[account]
+#[derive(Default, Copy)]
+pub struct DepositReceipt {
+    pub auction_house_key: Pubkey,
+    pub wallet: Pubkey,
+    pub payer: Pubkey,
+    pub reference: [u8; 32], // external payment reference, e.g. a card payment id
+    pub amount: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl DepositReceipt {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // wallet
+    32 + // payer
+    32 + // reference
+    8 + // amount
+    8 + // created_at
+    1 + // bump
+    39; // padding to 192 bytes
+}
+
+/// A referral key the auction house authority registered, orders only store a referral that
+/// has one. Orders placed after the attribution window are not attributed to it.
+#[account]
+#[derive(Default, Copy)]
+pub struct Referrer {
+    pub auction_house_key: Pubkey,
+    pub referral: Pubkey, // the key passed as buyer_referral or seller_referral
+    pub payout: Pubkey,   // where the referrer's revenue share is paid out
+    pub registered_at: i64,
+    pub attribution_window_seconds: u32, // 0 for no end
+    pub bump: u8,
+}
+
+impl Referrer {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // referral
+    32 + // payout
+    8 + // registered_at
+    4 + // attribution_window_seconds
+    1 + // bump
+    75; // padding to 192 bytes
+
+    pub fn is_attributing(&self, now: i64) -> bool {
+        self.attribution_window_seconds == 0
+            || now - self.registered_at <= self.attribution_window_seconds as i64
+    }
+}
+
+/// A seller's opt-in liquidity mining account. Listings made with it passed in are enrolled, and
+/// when an enrolled listing is cancelled or filled it accrues lamports for the days it was live and,
+/// if a buyer took it, for its SOL volume, at the auction house's current rates. The rewards are
+/// claimed from the auction house's maker rewards vault.
+#[account]
+#[derive(Default, Copy)]
+pub struct MakerRewards {
+    pub auction_house_key: Pubkey,
+    pub wallet: Pubkey,
+    pub bump: u8,
+    pub unclaimed: u64, // lamports accrued and not claimed yet
+    pub claimed: u64,
+    pub listing_seconds: u64, // lifetime totals of the enrolled listings, for display
+    pub filled_volume: u64,
+}
+
+impl MakerRewards {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // wallet
+    1 + // bump
+    8 + // unclaimed
+    8 + // claimed
+    8 + // listing_seconds
+    8 + // filled_volume
+    95; // padding to 200 bytes
+
+    /// Accrues an enrolled listing that was live for listing_seconds, filled_volume is 0 unless a
+    /// buyer took it in SOL. Returns the lamports accrued.
+    pub fn accrue(
+        &mut self,
+        auction_house: &AuctionHouse,
+        listing_seconds: u64,
+        filled_volume: u64,
+    ) -> Result<u64> {
+        let accrued = (listing_seconds as u128)
+            .checked_mul(auction_house.maker_rewards_lamports_per_day as u128)
+            .ok_or(ErrorCode::NumericalOverflow)?
+            .checked_div(60 * 60 * 24)
+            .ok_or(ErrorCode::NumericalOverflow)?
+            .checked_add(
+                (filled_volume as u128)
+                    .checked_mul(auction_house.maker_rewards_volume_bp as u128)
+                    .ok_or(ErrorCode::NumericalOverflow)?
+                    / 10000,
+            )
+            .ok_or(ErrorCode::NumericalOverflow)? as u64;
+        self.unclaimed = self
+            .unclaimed
+            .checked_add(accrued)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        self.listing_seconds = self.listing_seconds.saturating_add(listing_seconds);
+        self.filled_volume = self.filled_volume.saturating_add(filled_volume);
+        Ok(accrued)
+    }
+}
+
+/// A buyer's opt-in inventory of the escrow, per payment mint: what its ledgered bids lock and what
+/// is free. Bids placed with it passed in lock their full cost until they are cancelled or filled,
+/// which requires it passed in again, and withdrawals that pass it in can't dip into the locked
+/// balance. free is as of the last deposit, withdrawal or bid that passed the ledger in.
+#[account]
+#[derive(Default, Copy)]
+pub struct EscrowLedger {
+    pub auction_house_key: Pubkey,
+    pub wallet: Pubkey,
+    pub bump: u8,
+    pub balances: [EscrowLedgerBalance; MAX_ESCROW_LEDGER_MINTS], // SOL first, then SPL mints as they're used
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EscrowLedgerBalance {
+    pub payment_mint: Pubkey, // default key for SOL in the first slot and for unused SPL slots
+    pub locked: u64,
+    pub free: u64,
+}
+
+impl EscrowLedgerBalance {
+    pub const LEN: usize = 32 + 8 + 8;
+}
+
+impl EscrowLedger {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // wallet
+    1 + // bump
+    EscrowLedgerBalance::LEN * MAX_ESCROW_LEDGER_MINTS + // balances
+    63; // padding to 520 bytes
+
+    fn balance_mut(&mut self, payment_mint: &Pubkey) -> Result<&mut EscrowLedgerBalance> {
+        if *payment_mint == Pubkey::default() {
+            return Ok(&mut self.balances[0]);
+        }
+        let slots = &mut self.balances[1..];
+        let i = slots
+            .iter()
+            .position(|b| b.payment_mint == *payment_mint)
+            .or_else(|| {
+                slots
+                    .iter()
+                    .position(|b| b.payment_mint == Pubkey::default())
+            })
+            .ok_or(ErrorCode::EscrowLedgerFull)?;
+        slots[i].payment_mint = *payment_mint;
+        Ok(&mut slots[i])
+    }
+
+    pub fn balance(&self, payment_mint: &Pubkey) -> EscrowLedgerBalance {
+        if *payment_mint == Pubkey::default() {
+            return self.balances[0];
+        }
+        self.balances[1..]
+            .iter()
+            .find(|b| b.payment_mint == *payment_mint)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn lock(&mut self, payment_mint: &Pubkey, amount: u64) -> Result<()> {
+        let balance = self.balance_mut(payment_mint)?;
+        balance.locked = balance
+            .locked
+            .checked_add(amount)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        balance.free = balance.free.saturating_sub(amount);
+        Ok(())
+    }
+
+    pub fn unlock(&mut self, payment_mint: &Pubkey, amount: u64) -> Result<()> {
+        let balance = self.balance_mut(payment_mint)?;
+        balance.locked = balance.locked.saturating_sub(amount);
+        Ok(())
+    }
+
+    /// Recomputes the free balance from the escrow's balance in payment_mint
+    pub fn sync(&mut self, payment_mint: &Pubkey, escrow_balance: u64) -> Result<()> {
+        let balance = self.balance_mut(payment_mint)?;
+        balance.free = escrow_balance.saturating_sub(balance.locked);
+        Ok(())
+    }
+
+    /// Checks that withdrawing amount out of escrow_balance leaves the locked balance in place
+    pub fn assert_withdrawable(
+        &self,
+        payment_mint: &Pubkey,
+        amount: u64,
+        escrow_balance: u64,
+    ) -> Result<()> {
+        if escrow_balance.saturating_sub(amount) < self.balance(payment_mint).locked {
+            return Err(ErrorCode::EscrowBalanceLocked.into());
+        }
+        Ok(())
+    }
+}
+
+/// A treasury wallet's approval for a spender to top up its own escrow from the treasury's escrow
+/// when it bids in SOL, up to limit lamports in total. The treasury raises limit to extend the
+/// budget, or lowers it to spent to revoke it. What each open bid drew is locked to it, the
+/// unspent part goes back to the treasury and the budget when the bid is cancelled or filled.
+#[account]
+#[derive(Default, Copy)]
+pub struct SpendingAllowance {
+    pub auction_house_key: Pubkey,
+    pub treasury: Pubkey,
+    pub spender: Pubkey,
+    pub bump: u8,
+    pub limit: u64,
+    pub spent: u64,
+    pub locks: [SpendingAllowanceLock; MAX_SPENDING_ALLOWANCE_LOCKS],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SpendingAllowanceLock {
+    pub buyer_trade_state: Pubkey, // default key for an unused slot
+    pub amount: u64,
+}
+
+impl SpendingAllowanceLock {
+    pub const LEN: usize = 32 + 8;
+}
+
+impl SpendingAllowance {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // treasury
+    32 + // spender
+    1 + // bump
+    8 + // limit
+    8 + // spent
+    SpendingAllowanceLock::LEN * MAX_SPENDING_ALLOWANCE_LOCKS + // locks
+    7; // padding to 448 bytes
+
+    pub fn has_lock(&self, buyer_trade_state: &Pubkey) -> bool {
+        self.locks
+            .iter()
+            .any(|l| l.buyer_trade_state == *buyer_trade_state)
+    }
+
+    /// Draws amount for the bid, locked to it until it's released
+    pub fn draw(&mut self, buyer_trade_state: &Pubkey, amount: u64) -> Result<()> {
+        let spent = self
+            .spent
+            .checked_add(amount)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        if spent > self.limit {
+            return Err(ErrorCode::SpendingAllowanceExceeded.into());
+        }
+        let lock = match self
+            .locks
+            .iter()
+            .position(|l| l.buyer_trade_state == *buyer_trade_state)
+            .or_else(|| {
+                self.locks
+                    .iter()
+                    .position(|l| l.buyer_trade_state == Pubkey::default())
+            }) {
+            Some(i) => &mut self.locks[i],
+            None => return Err(ErrorCode::SpendingAllowanceFull.into()),
+        };
+        lock.buyer_trade_state = *buyer_trade_state;
+        lock.amount = lock
+            .amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        self.spent = spent;
+        Ok(())
+    }
+
+    /// Releases a closing bid's lock, used being what it spent of the escrow. Returns the unspent
+    /// part of what it drew, which goes back to the treasury and the budget.
+    pub fn release(&mut self, buyer_trade_state: &Pubkey, used: u64) -> Result<u64> {
+        let lock = self
+            .locks
+            .iter_mut()
+            .find(|l| l.buyer_trade_state == *buyer_trade_state)
+            .ok_or(ErrorCode::TradeStateMismatch)?;
+        // the drawn funds are spent first, they're what topped the escrow up for the bid
+        let refund = lock.amount.saturating_sub(used);
+        *lock = SpendingAllowanceLock::default();
+        self.spent = self.spent.saturating_sub(refund);
+        Ok(refund)
+    }
+}
+
+/// Trading limits the notary sets for a wallet, e.g. for a compliance program or to cap the damage
+/// a compromised wallet can do. Bids placed with them passed in count as open bids until they are
+/// cancelled or filled, and SOL buys they are passed to count towards the day's buy volume. Houses
+/// that enforce wallet limits require them, or the wallet's uninitialized address if it has none,
+/// on every bid and buy. A limit of 0 is no limit.
+#[account]
+#[derive(Default, Copy)]
+pub struct WalletLimits {
+    pub auction_house_key: Pubkey,
+    pub wallet: Pubkey,
+    pub bump: u8,
+    pub max_daily_buy_volume: u64, // lamports
+    pub max_open_bids: u32,
+    pub day: i64, // unix day of day_buy_volume
+    pub day_buy_volume: u64,
+    pub open_bids: u32,
+}
+
+impl WalletLimits {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // wallet
+    1 + // bump
+    8 + // max_daily_buy_volume
+    4 + // max_open_bids
+    8 + // day
+    8 + // day_buy_volume
+    4 + // open_bids
+    23; // padding to 128 bytes
+
+    pub fn open_bid(&mut self) -> Result<()> {
+        let open_bids = self
+            .open_bids
+            .checked_add(1)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        if self.max_open_bids != 0 && open_bids > self.max_open_bids {
+            return Err(ErrorCode::WalletLimitExceeded.into());
+        }
+        self.open_bids = open_bids;
+        Ok(())
+    }
+
+    pub fn close_bid(&mut self) {
+        self.open_bids = self.open_bids.saturating_sub(1);
+    }
+
+    pub fn record_buy(&mut self, lamports: u64, now: i64) -> Result<()> {
+        let day = now.div_euclid(86400);
+        if day != self.day {
+            self.day = day;
+            self.day_buy_volume = 0;
+        }
+        let day_buy_volume = self
+            .day_buy_volume
+            .checked_add(lamports)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        if self.max_daily_buy_volume != 0 && day_buy_volume > self.max_daily_buy_volume {
+            return Err(ErrorCode::WalletLimitExceeded.into());
+        }
+        self.day_buy_volume = day_buy_volume;
+        Ok(())
+    }
+}
+
+/// Escrow funds reserved for an off-chain order until a slot deadline. The funds sit in the lock
+/// itself, lamports on top of its rent or its ATA for SPL payment mints, so no withdrawal can touch
+/// them and matching engines can trust the quote without watching the escrow.
+#[account]
+#[derive(Default, Copy)]
+pub struct EscrowLock {
+    pub auction_house_key: Pubkey,
+    pub wallet: Pubkey,
+    pub bump: u8,
+    pub order_hash: [u8; 32],
+    pub payment_mint: Pubkey, // default key for SOL
+    pub amount: u64,
+    pub deadline_slot: u64,
+}
+
+impl EscrowLock {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // wallet
+    1 + // bump
+    32 + // order_hash
+    32 + // payment_mint
+    8 + // amount
+    8 + // deadline_slot
+    39; // padding to 192 bytes
+
+    pub fn is_active(&self, slot: u64) -> bool {
+        slot <= self.deadline_slot
+    }
+}
+
+/// A wallet's rate limited instructions in the last slot it sent one, for auction houses that
+/// don't require the notary
+#[account]
+#[derive(Default, Copy)]
+pub struct RateLimit {
+    pub auction_house_key: Pubkey,
+    pub wallet: Pubkey,
+    pub bump: u8,
+    pub slot: u64,
+    pub count: u8, // instructions in slot
+}
+
+impl RateLimit {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // wallet
+    1 + // bump
+    8 + // slot
+    1 + // count
+    14; // padding to 96 bytes
+
+    pub fn record(&mut self, slot: u64, max_ixs_per_slot: u8) -> Result<()> {
+        if slot != self.slot {
+            self.slot = slot;
+            self.count = 0;
+        }
+        if self.count >= max_ixs_per_slot {
+            return Err(ErrorCode::RateLimitExceeded.into());
+        }
+        self.count += 1;
+        Ok(())
+    }
+}
+
+/// A seller's lamports backing their delegate listings of a token in an auction house. If a fill
+/// fails because the seller moved the token or revoked the delegation, the notary can pay them to
+/// the buyer with claim_listing_insurance.
+#[account]
+#[derive(Default, Copy)]
+pub struct ListingInsurance {
+    pub auction_house_key: Pubkey,
+    pub seller: Pubkey,
+    pub token_mint: Pubkey,
+    pub bump: u8,
+    pub amount: u64,          // lamports deposited on top of the rent
+    pub withdrawable_at: i64, // 0 until the seller starts withdrawing, claims stay open until then
+}
+
+impl ListingInsurance {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // seller
+    32 + // token_mint
+    1 + // bump
+    8 + // amount
+    8 + // withdrawable_at
+    7; // padding to 128 bytes
+}
+
+/// How a seller splits their SOL sale proceeds, e.g. between the co-owners of an NFT or into a
+/// team treasury. An execute passed the seller's ProceedsSplit pays each recipient its bps of what
+/// the seller nets in the same settlement, the seller keeps the rest.
+#[account]
+#[derive(Default, Copy)]
+pub struct ProceedsSplit {
+    pub seller: Pubkey,
+    pub bump: u8,
+    pub recipients: [ProceedsSplitRecipient; MAX_PROCEEDS_SPLIT_RECIPIENTS], // unused slots are zeroes
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProceedsSplitRecipient {
+    pub address: Pubkey,
+    pub bp: u16,
+}
+
+impl ProceedsSplitRecipient {
+    pub const LEN: usize = 32 + 2;
+}
+
+impl ProceedsSplit {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // seller
+    1 + // bump
+    ProceedsSplitRecipient::LEN * MAX_PROCEEDS_SPLIT_RECIPIENTS + // recipients
+    45; // padding to 256 bytes
+
+    /// Sets the recipients, each with a non zero bp and at most 10000 bp in total
+    pub fn set_recipients(&mut self, recipients: &[ProceedsSplitRecipient]) -> Result<()> {
+        if recipients.len() > MAX_PROCEEDS_SPLIT_RECIPIENTS
+            || recipients
+                .iter()
+                .any(|r| r.address == Pubkey::default() || r.bp == 0)
+            || recipients.iter().map(|r| r.bp as u32).sum::<u32>() > 10000
+        {
+            return Err(ErrorCode::InvalidProceedsSplit.into());
+        }
+        self.recipients = [ProceedsSplitRecipient::default(); MAX_PROCEEDS_SPLIT_RECIPIENTS];
+        self.recipients[..recipients.len()].copy_from_slice(recipients);
+        Ok(())
+    }
+
+    pub fn used_recipients(&self) -> impl Iterator<Item = &ProceedsSplitRecipient> {
+        self.recipients
+            .iter()
+            .filter(|r| r.address != Pubkey::default())
+    }
+
+    /// Each used recipient's share of proceeds, rounded down so the rounding stays with the seller.
+    /// The bps add up to at most 10000, so the shares never add up to more than proceeds.
+    pub fn shares(&self, proceeds: u64) -> Vec<u64> {
+        self.used_recipients()
+            .map(|r| (proceeds as u128 * r.bp as u128 / 10000) as u64)
+            .collect()
+    }
+}
+
+/// Notaries that can cosign for an auction house besides auction_house.notary, so the house
+/// doesn't depend on a single notary key being available. Where the notary's cosign is required,
+/// threshold of them signing anywhere in the instruction stand in for it.
+#[account]
+#[derive(Default, Copy)]
+pub struct NotarySet {
+    pub auction_house_key: Pubkey,
+    pub bump: u8,
+    pub threshold: u8,
+    pub notaries: [Pubkey; MAX_NOTARIES], // unused slots are default keys
+}
+
+impl NotarySet {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    1 + // bump
+    1 + // threshold
+    32 * MAX_NOTARIES + // notaries
+    22; // padding to 224 bytes
+
+    pub fn set_notaries(&mut self, notaries: &[Pubkey], threshold: u8) -> Result<()> {
+        if notaries.is_empty()
+            || notaries.len() > MAX_NOTARIES
+            || threshold == 0
+            || threshold as usize > notaries.len()
+            || notaries
+                .iter()
+                .enumerate()
+                .any(|(i, notary)| *notary == Pubkey::default() || notaries[..i].contains(notary))
+        {
+            return Err(ErrorCode::InvalidNotarySet.into());
+        }
+        self.notaries = [Pubkey::default(); MAX_NOTARIES];
+        self.notaries[..notaries.len()].copy_from_slice(notaries);
+        self.threshold = threshold;
+        Ok(())
+    }
+
+    /// Whether at least threshold distinct notaries are among signers
+    pub fn is_cosigned_by<'a>(&self, signers: impl Iterator<Item = &'a Pubkey>) -> bool {
+        let mut signed = [false; MAX_NOTARIES];
+        for signer in signers {
+            if let Some(i) = self
+                .notaries
+                .iter()
+                .position(|n| n == signer && *n != Pubkey::default())
+            {
+                signed[i] = true;
+            }
+        }
+        self.threshold > 0 && signed.iter().filter(|s| **s).count() >= self.threshold as usize
+    }
+}
+
+/// Bids of a buyer on different mints, e.g. "any 1 of these 5", that share one escrow balance.
+/// The group fills at most max_fills of them, the bids left can't fill once it had its max fills,
+/// so a balance funded once for the group can't be over-filled, and the fill that consumes it
+/// cancels those passed along. Bids are kept in the buyer's order of priority for matchers filling
+/// the group.
+#[account]
+#[derive(Default, Copy)]
+pub struct BidGroup {
+    pub auction_house_key: Pubkey,
+    pub buyer: Pubkey,
+    pub id: u64,
+    pub bump: u8,
+    pub max_fills: u8,
+    pub fills: u8,
+    pub bids: [Pubkey; MAX_BID_GROUP_BIDS], // buyer_trade_state keys, unused slots are default keys
+}
+
+impl BidGroup {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // buyer
+    8 + // id
+    1 + // bump
+    1 + // max_fills
+    1 + // fills
+    32 * MAX_BID_GROUP_BIDS + // bids
+    13; // padding to 352 bytes
+
+    pub fn set_bids(&mut self, bids: &[Pubkey], max_fills: u8) -> Result<()> {
+        if bids.is_empty()
+            || bids.len() > MAX_BID_GROUP_BIDS
+            || max_fills == 0
+            || max_fills as usize > bids.len()
+            || bids
+                .iter()
+                .enumerate()
+                .any(|(i, bid)| *bid == Pubkey::default() || bids[..i].contains(bid))
+        {
+            return Err(ErrorCode::InvalidBidGroup.into());
+        }
+        self.bids = [Pubkey::default(); MAX_BID_GROUP_BIDS];
+        self.bids[..bids.len()].copy_from_slice(bids);
+        self.max_fills = max_fills;
+        Ok(())
+    }
+
+    /// Counts a fill of the bid, returning its priority in the group
+    pub fn fill(&mut self, buyer_trade_state: &Pubkey) -> Result<usize> {
+        let priority = self
+            .bids
+            .iter()
+            .position(|b| b == buyer_trade_state && *b != Pubkey::default())
+            .ok_or(ErrorCode::InvalidBidGroup)?;
+        if self.fills >= self.max_fills {
+            return Err(ErrorCode::BidGroupConsumed.into());
+        }
+        self.fills += 1;
+        Ok(priority)
+    }
+}
+
+/// Top of book of a token mint on an auction house: its best SOL bid and listing, by price per
+/// token, so other programs can read them without scanning trade states. Sells, bids, cancels and
+/// executes update it whenever it is passed in. It's best effort: a cancelled, filled, hidden or
+/// worse priced best order clears its side, until an order is placed or refresh_mint_market is
+/// called, so readers still check the trade state it points to.
+#[account]
+#[derive(Default, Copy)]
+pub struct MintMarket {
+    pub auction_house_key: Pubkey,
+    pub token_mint: Pubkey,
+    pub bump: u8,
+    pub best_bid: Pubkey, // buyer_trade_state, the default key if there's none
+    pub best_bid_price: u64,
+    pub best_ask: Pubkey, // seller_trade_state, the default key if there's none
+    pub best_ask_price: u64,
+    pub last_updated_at: i64,
+}
+
+impl MintMarket {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // token_mint
+    1 + // bump
+    32 + // best_bid
+    8 + // best_bid_price
+    32 + // best_ask
+    8 + // best_ask_price
+    8 + // last_updated_at
+    31; // padding to 192 bytes
+
+    /// Takes the bid as the best bid if it beats it, or updates the best bid's own price, clearing
+    /// the side if it lowered it, as another bid may now be best. sol_price is the bid's price for
+    /// token_size tokens, None if it doesn't pay in SOL, which drops it.
+    pub fn offer_bid(
+        &mut self,
+        buyer_trade_state: &Pubkey,
+        sol_price: Option<u64>,
+        token_size: u64,
+    ) {
+        let price = match sol_price.filter(|_| token_size > 0) {
+            Some(price) => price / token_size,
+            None => return self.remove(buyer_trade_state),
+        };
+        if self.best_bid == *buyer_trade_state && price < self.best_bid_price {
+            self.remove(buyer_trade_state);
+        } else if self.best_bid == *buyer_trade_state
+            || self.best_bid == Pubkey::default()
+            || price > self.best_bid_price
+        {
+            self.best_bid = *buyer_trade_state;
+            self.best_bid_price = price;
+        }
+    }
+
+    /// Takes the listing as the best ask if it beats it, or updates the best ask's own price,
+    /// clearing the side if it raised it, as another listing may now be best. sol_price is the
+    /// listing's visible price in SOL for token_size tokens, None if it has none, which drops it.
+    pub fn offer_ask(
+        &mut self,
+        seller_trade_state: &Pubkey,
+        sol_price: Option<u64>,
+        token_size: u64,
+    ) {
+        let price = match sol_price.filter(|_| token_size > 0) {
+            Some(price) => price / token_size,
+            None => return self.remove(seller_trade_state),
+        };
+        if self.best_ask == *seller_trade_state && price > self.best_ask_price {
+            self.remove(seller_trade_state);
+        } else if self.best_ask == *seller_trade_state
+            || self.best_ask == Pubkey::default()
+            || price < self.best_ask_price
+        {
+            self.best_ask = *seller_trade_state;
+            self.best_ask_price = price;
+        }
+    }
+
+    /// Clears the side the closed or filled trade state is best on, if any
+    pub fn remove(&mut self, trade_state: &Pubkey) {
+        if self.best_bid == *trade_state {
+            self.best_bid = Pubkey::default();
+            self.best_bid_price = 0;
+        }
+        if self.best_ask == *trade_state {
+            self.best_ask = Pubkey::default();
+            self.best_ask_price = 0;
+        }
+    }
+}
+
+/// Royalty recipients a mint's update authority set in place of its metadata creators, which
+/// metadata caps at 5. A sale passed the mint's RoyaltyOverride pays its recipients the royalty
+/// instead, and in SOL, those it couldn't fit in its accounts get a claim in the PendingRoyalties.
+#[account]
+#[derive(Default, Copy)]
+pub struct RoyaltyOverride {
+    pub token_mint: Pubkey,
+    pub bump: u8,
+    pub recipients: [RoyaltyRecipient; MAX_ROYALTY_RECIPIENTS], // unused slots are zeroes
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RoyaltyRecipient {
+    pub address: Pubkey,
+    pub share: u8, // percent of the royalty, like a metadata creator's share
+}
+
+impl RoyaltyRecipient {
+    pub const LEN: usize = 32 + 1;
+}
+
+impl RoyaltyOverride {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // token_mint
+    1 + // bump
+    RoyaltyRecipient::LEN * MAX_ROYALTY_RECIPIENTS + // recipients
+    7; // padding to 576 bytes
+
+    /// Sets the recipients, distinct and non empty, with shares adding up to 100
+    pub fn set_recipients(&mut self, recipients: &[RoyaltyRecipient]) -> Result<()> {
+        if recipients.is_empty()
+            || recipients.len() > MAX_ROYALTY_RECIPIENTS
+            || recipients.iter().enumerate().any(|(i, r)| {
+                r.address == Pubkey::default()
+                    || recipients[..i].iter().any(|o| o.address == r.address)
+            })
+            || recipients.iter().map(|r| r.share as u32).sum::<u32>() != 100
+        {
+            return Err(ErrorCode::InvalidRoyaltyOverride.into());
+        }
+        self.recipients = [RoyaltyRecipient::default(); MAX_ROYALTY_RECIPIENTS];
+        self.recipients[..recipients.len()].copy_from_slice(recipients);
+        Ok(())
+    }
+
+    /// The metadata with the recipients as its creators, in the order they were set
+    pub fn apply(&self, mut metadata: Metadata) -> Metadata {
+        metadata.creators = Some(
+            self.recipients
+                .iter()
+                .filter(|r| r.address != Pubkey::default())
+                .map(|r| Creator {
+                    address: r.address,
+                    verified: false,
+                    share: r.share,
+                })
+                .collect(),
+        );
+        metadata
+    }
+}
+
+/// SOL royalties owed to creators a sale couldn't fit in its transaction, e.g. royalty overrides
+/// with more recipients than the account budget allows. The sale moves them into this account, one
+/// per auction house and mint, and distribute_royalties pays the creators later. It holds its rent
+/// plus the claims.
+#[account]
+#[derive(Default, Copy)]
+pub struct PendingRoyalties {
+    pub auction_house_key: Pubkey,
+    pub token_mint: Pubkey,
+    pub rent_payer: Pubkey, // gets the rent back once every claim is paid
+    pub bump: u8,
+    pub claims: [RoyaltyClaim; MAX_PENDING_ROYALTIES], // unused slots are zeroes
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RoyaltyClaim {
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+impl RoyaltyClaim {
+    pub const LEN: usize = 32 + 8;
+}
+
+impl PendingRoyalties {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // token_mint
+    32 + // rent_payer
+    1 + // bump
+    RoyaltyClaim::LEN * MAX_PENDING_ROYALTIES + // claims
+    23; // padding to 768 bytes
+
+    /// Adds amount to the creator's claim, taking a free slot if it has none yet
+    pub fn add_claim(&mut self, creator: &Pubkey, amount: u64) -> Result<()> {
+        let claim = match self.claims.iter().position(|c| c.creator == *creator) {
+            Some(i) => &mut self.claims[i],
+            None => self
+                .claims
+                .iter_mut()
+                .find(|c| c.creator == Pubkey::default())
+                .ok_or(ErrorCode::PendingRoyaltiesFull)?,
+        };
+        claim.creator = *creator;
+        claim.amount = claim
+            .amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        Ok(())
+    }
+
+    /// Clears the creator's claim and returns what it was owed
+    pub fn take_claim(&mut self, creator: &Pubkey) -> u64 {
+        match self.claims.iter_mut().find(|c| c.creator == *creator) {
+            Some(claim) => std::mem::take(claim).amount,
+            None => 0,
+        }
+    }
+
+    pub fn amount_owed(&self, creator: &Pubkey) -> u64 {
+        self.claims
+            .iter()
+            .find(|c| c.creator == *creator)
+            .map_or(0, |c| c.amount)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.claims.iter().all(|c| c.creator == Pubkey::default())
+    }
+}
 
+/// The co-owners of a wallet that can't sign listings itself, e.g. a multisig PDA, and their
+/// approvals for the next listing action on one token. Once threshold co-owners approved the same
+/// action, any of them can sign sell_frozen (with the approved terms) or cancel_sell as the
+/// seller_authority of the wallet. The approvals are used up by that action.
 #[account]
-pub struct AuctionHouse {
-    pub auction_house_treasury: Pubkey,
-    pub treasury_withdrawal_destination: Pubkey,
+#[derive(Default, Copy)]
+pub struct CoOwnerApproval {
+    pub seller: Pubkey,
+    pub token_mint: Pubkey,
+    pub bump: u8,
+    pub threshold: u8,
+    pub owners: [Pubkey; MAX_CO_OWNERS], // unused slots are default keys
+    pub approved: u8,                    // bitmask over owners of the approvals of action_hash
+    pub action_hash: [u8; 32],           // listing terms being approved, see action_hash
+}
+
+impl CoOwnerApproval {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // seller
+    32 + // token_mint
+    1 + // bump
+    1 + // threshold
+    32 * MAX_CO_OWNERS + // owners
+    1 + // approved
+    32 + // action_hash
+    21; // padding to 384 bytes
+
+    /// The action co-owners approve: sell_frozen at price, expiry and payment_mint with extra_args,
+    /// or a cancel at price 0. extra_args count up to SellExtraArgs::LEN, what the listing is
+    /// written with, so its alt prices, royalty mode and every other option are approved too.
+    pub fn action_hash(
+        price: u64,
+        expiry: i64,
+        payment_mint: &Pubkey,
+        extra_args: &[u8],
+    ) -> [u8; 32] {
+        let mut terms = [0u8; SellExtraArgs::LEN];
+        let len = extra_args.len().min(SellExtraArgs::LEN);
+        terms[..len].copy_from_slice(&extra_args[..len]);
+        hashv(&[
+            &price.to_le_bytes(),
+            &expiry.to_le_bytes(),
+            payment_mint.as_ref(),
+            &terms,
+        ])
+        .to_bytes()
+    }
+
+    /// The action_hash of cancel_sell
+    pub fn cancel_action_hash() -> [u8; 32] {
+        Self::action_hash(0, 0, &Pubkey::default(), &[])
+    }
+
+    /// Replaces the co-owners, which also drops every approval
+    pub fn set_owners(&mut self, owners: &[Pubkey], threshold: u8) -> Result<()> {
+        if owners.is_empty()
+            || owners.len() > MAX_CO_OWNERS
+            || threshold == 0
+            || threshold as usize > owners.len()
+            || owners
+                .iter()
+                .enumerate()
+                .any(|(i, owner)| *owner == Pubkey::default() || owners[..i].contains(owner))
+        {
+            return Err(ErrorCode::InvalidCoOwners.into());
+        }
+        self.owners = [Pubkey::default(); MAX_CO_OWNERS];
+        self.owners[..owners.len()].copy_from_slice(owners);
+        self.threshold = threshold;
+        self.approved = 0;
+        self.action_hash = [0; 32];
+        Ok(())
+    }
+
+    fn owner_bit(&self, owner: &Pubkey) -> Option<u8> {
+        if *owner == Pubkey::default() {
+            return None;
+        }
+        self.owners.iter().position(|o| o == owner).map(|i| 1 << i)
+    }
+
+    /// Records owner's approval of action_hash, approvals of another action are dropped
+    pub fn approve(&mut self, owner: &Pubkey, action_hash: [u8; 32]) -> Result<()> {
+        let bit = self
+            .owner_bit(owner)
+            .ok_or(ErrorCode::CoOwnerApprovalMissing)?;
+        if self.action_hash != action_hash {
+            self.approved = 0;
+            self.action_hash = action_hash;
+        }
+        self.approved |= bit;
+        Ok(())
+    }
+
+    /// Whether seller_authority is an approving co-owner and enough co-owners approved action_hash
+    pub fn is_approved(&self, seller_authority: &Pubkey, action_hash: &[u8; 32]) -> bool {
+        self.action_hash == *action_hash
+            && self.threshold > 0
+            && self.approved.count_ones() >= self.threshold as u32
+            && self
+                .owner_bit(seller_authority)
+                .map_or(false, |bit| self.approved & bit != 0)
+    }
+}
+
+/// A key the wallet trusts to cancel its listings and bids for it, but not to create them, e.g. a
+/// custodian's recovery key or a security team acting on a compromised wallet. Unlike
+/// CANCEL_AUTHORITY, each wallet picks its own.
+#[account]
+#[derive(Default, Copy)]
+pub struct Guardian {
+    pub wallet: Pubkey,
+    pub guardian: Pubkey,
+    pub bump: u8,
+}
+
+impl Guardian {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // wallet
+    32 + // guardian
+    1 + // bump
+    55; // padding to 128 bytes
+}
+
+/// A buyer's standing authorization for execute_sale_v2 to top up their escrow out of
+/// funding_token_account when it's short of a fill. The token account delegates to this PDA, so
+/// its delegated amount is what's left of the cap. A wSOL account tops up the SOL escrow, any
+/// other mint the escrow's token account for bids in it.
+#[account]
+#[derive(Default, Copy)]
+pub struct EscrowTopUp {
+    pub auction_house_key: Pubkey,
+    pub wallet: Pubkey,
+    pub funding_token_account: Pubkey,
+    pub bump: u8,
+}
+
+impl EscrowTopUp {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    32 + // wallet
+    32 + // funding_token_account
+    1 + // bump
+    23; // padding to 128 bytes
+}
+
+/// Per deployment configuration, so the same binary serves every cluster. It only adds to what
+/// is built in (VALID_PAYMENT_MINTS, CANCEL_AUTHORITY and the default fees), as instructions only
+/// see it when it is passed in. That includes the GatedInstructions it can disable.
+#[account]
+#[derive(Default, Copy)]
+pub struct ProgramConfig {
     pub authority: Pubkey,
-    pub creator: Pubkey,
-    pub notary: Pubkey,
+    pub payment_mints: [Pubkey; MAX_CONFIG_PAYMENT_MINTS], // default pubkey for unused slots
+    pub cancel_authority: Pubkey,
     pub bump: u8,
-    pub treasury_bump: u8,
-    pub seller_fee_basis_points: u16,
-    pub buyer_referral_bp: u16,
-    pub seller_referral_bp: u16,
-    pub requires_notary: bool,
-    pub nprob: u8, // notary enforce probability
+    pub disabled_instructions: u32, // GatedInstruction bits, those set fail with Deprecated
+    pub swap_adapter: Pubkey, // swap program cross-currency fills go through, default key if there is none
+    pub payment_mint_fees: [PaymentMintFees; MAX_CONFIG_PAYMENT_MINTS], // fee overrides by mint, default mint for unused entries
+}
+
+impl ProgramConfig {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // authority
+    32 * MAX_CONFIG_PAYMENT_MINTS + // payment_mints
+    32 + // cancel_authority
+    1 + // bump
+    4 + // disabled_instructions
+    32 + // swap_adapter
+    PaymentMintFees::LEN * MAX_CONFIG_PAYMENT_MINTS + // payment_mint_fees
+    19; // padding to 672 bytes
+
+    pub fn is_payment_mint(&self, mint: &Pubkey) -> bool {
+        *mint != Pubkey::default() && self.payment_mints.contains(mint)
+    }
+
+    /// The fee override of an SPL payment mint, built in or added by the config. SOL sales pay
+    /// the defaults.
+    pub fn fees_for(&self, mint: &Pubkey) -> Option<PaymentMintFees> {
+        if *mint == Pubkey::default() {
+            return None;
+        }
+        self.payment_mint_fees
+            .iter()
+            .find(|fees| fees.mint == *mint)
+            .copied()
+    }
+
+    /// Sets the fee overrides, each within the fee bounds and at most one per mint
+    pub fn set_payment_mint_fees(
+        &mut self,
+        payment_mint_fees: [PaymentMintFees; MAX_CONFIG_PAYMENT_MINTS],
+    ) -> Result<()> {
+        let used = payment_mint_fees
+            .iter()
+            .filter(|fees| fees.mint != Pubkey::default());
+        for (i, fees) in used.clone().enumerate() {
+            if !fees.is_valid() || used.clone().skip(i + 1).any(|f| f.mint == fees.mint) {
+                return Err(ErrorCode::InvalidPlatformFeeBp.into());
+            }
+        }
+        self.payment_mint_fees = payment_mint_fees;
+        Ok(())
+    }
+
+    pub fn is_enabled(&self, ix: GatedInstruction) -> bool {
+        self.disabled_instructions & ix.bit() == 0
+    }
+}
+
+/// The maker and taker fee bps of sales in a payment mint, e.g. higher ones for volatile tokens,
+/// in place of the defaults when the notary didn't sign for the fees the sale was given
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PaymentMintFees {
+    pub mint: Pubkey,
+    pub maker_fee_bp: i16,
+    pub taker_fee_bp: u16,
+}
+
+impl PaymentMintFees {
+    pub const LEN: usize = 32 + 2 + 2;
+
+    /// Within the bounds execute_sale_v2 puts on the fees it's given
+    pub fn is_valid(&self) -> bool {
+        self.maker_fee_bp <= MAX_MAKER_FEE_BP
+            && self.taker_fee_bp <= MAX_TAKER_FEE_BP
+            && self.maker_fee_bp >= -(self.taker_fee_bp as i16)
+    }
+}
+
+/// Entrypoints a deployment can retire once newer versions replace them
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GatedInstruction {
+    Buy,  // V1 bids, replaced by buy_v2
+    Sell, // sell, list_auto's sell flow, sell_into_bid and sell_with_authorization, replaced by mip1_sell and ocp_sell for those standards
+}
+
+impl GatedInstruction {
+    pub fn bit(self) -> u32 {
+        1 << self as u32
+    }
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy)]
+pub struct CancelAuthorityEntry {
+    pub key: Pubkey,
+    pub enabled: bool,
+}
+
+/// The keys that can cancel orders on behalf of their makers in one auction house, replacing the
+/// built-in CANCEL_AUTHORITY once the house has one
 #[account]
 #[derive(Default, Copy)]
-pub struct BuyerTradeStateV2 {
+pub struct CancelAuthorityRegistry {
     pub auction_house_key: Pubkey,
-    pub buyer: Pubkey,
-    pub buyer_referral: Pubkey,
-    pub buyer_price: u64,
-    pub token_mint: Pubkey,
-    pub token_size: u64,
     pub bump: u8,
-    pub expiry: i64,
-    pub buyer_creator_royalty_bp: u16,
+    pub entries: [CancelAuthorityEntry; MAX_CANCEL_AUTHORITIES], // default key for unused slots
+}
+
+impl CancelAuthorityRegistry {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    1 + // bump
+    33 * MAX_CANCEL_AUTHORITIES + // entries
+    95; // padding to 400 bytes
+
+    pub fn is_enabled(&self, key: &Pubkey) -> bool {
+        *key != Pubkey::default() && self.entries.iter().any(|e| e.enabled && e.key == *key)
+    }
+
+    /// Enables or disables key, taking a free slot or the slot of a disabled key for a new one
+    pub fn set(&mut self, key: Pubkey, enabled: bool) -> Result<()> {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.key == key) {
+            entry.enabled = enabled;
+            return Ok(());
+        }
+        if !enabled {
+            return Ok(());
+        }
+        let slot = self
+            .entries
+            .iter()
+            .position(|e| e.key == Pubkey::default())
+            .or_else(|| self.entries.iter().position(|e| !e.enabled))
+            .ok_or(ErrorCode::CancelAuthorityRegistryFull)?;
+        self.entries[slot] = CancelAuthorityEntry { key, enabled };
+        Ok(())
+    }
+}
+
+/// A listing's terms as the notary recorded them at taken_at, e.g. for a support case. Written once
+/// by snapshot_listing and never changed or closed.
+#[account]
+#[derive(Default, Copy)]
+pub struct ListingSnapshot {
+    pub auction_house_key: Pubkey,
+    pub seller_trade_state: Pubkey,
+    pub snapshot_id: u64,
+    pub bump: u8,
+    pub taken_at: i64,
+    pub seller: Pubkey,
+    pub token_mint: Pubkey,
     pub payment_mint: Pubkey,
+    pub price: u64,
+    pub expiry: i64,
+    pub last_updated_at: i64, // 0 for listings older than V3
 }
 
-impl BuyerTradeStateV2 {
+impl ListingSnapshot {
     pub const LEN: usize = 8 + // discriminator
     32 + // auction_house_key
-    32 + // buyer
-    32 + // buyer_referral
-    8 + // buyer_price
-    32 + // token_mint
-    8 + // token_size
+    32 + // seller_trade_state
+    8 + // snapshot_id
     1 + // bump
+    8 + // taken_at
+    32 + // seller
+    32 + // token_mint
+    32 + // payment_mint
+    8 + // price
     8 + // expiry
-    2 + // buyer_creator_ryoalty_bp
+    8 + // last_updated_at
+    31; // padding to 240 bytes
+}
+
+/// The programs that may CPI into sales of one auction house under CpiPolicy::Allowlist
+#[account]
+#[derive(Default, Copy)]
+pub struct CpiAllowlist {
+    pub auction_house_key: Pubkey,
+    pub bump: u8,
+    pub programs: [Pubkey; MAX_CPI_CALLERS], // default key for unused slots
+}
+
+impl CpiAllowlist {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
+    1 + // bump
+    32 * MAX_CPI_CALLERS + // programs
+    23; // padding to 320 bytes
+
+    pub fn is_allowed(&self, program: &Pubkey) -> bool {
+        *program != Pubkey::default() && self.programs.contains(program)
+    }
+
+    pub fn set(&mut self, program: Pubkey, allowed: bool) -> Result<()> {
+        if allowed == self.is_allowed(&program) {
+            return Ok(());
+        }
+        let (from, to) = if allowed {
+            (Pubkey::default(), program)
+        } else {
+            (program, Pubkey::default())
+        };
+        let slot = self
+            .programs
+            .iter_mut()
+            .find(|p| **p == from)
+            .ok_or(ErrorCode::CpiAllowlistFull)?;
+        *slot = to;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy)]
+pub struct MetricsTotals {
+    pub volume: u64,
+    pub maker_fees: i64, // negative when maker rebates outweigh maker fees
+    pub taker_fees: u64,
+    pub royalty: u64,
+}
+
+impl MetricsTotals {
+    pub const LEN: usize = 8 + 8 + 8 + 8;
+
+    fn add(&mut self, other: &MetricsTotals) -> Result<()> {
+        self.volume = self
+            .volume
+            .checked_add(other.volume)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        self.maker_fees = self
+            .maker_fees
+            .checked_add(other.maker_fees)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        self.taker_fees = self
+            .taker_fees
+            .checked_add(other.taker_fees)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        self.royalty = self
+            .royalty
+            .checked_add(other.royalty)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        Ok(())
+    }
+
+    fn sub(&mut self, other: &MetricsTotals) {
+        self.volume = self.volume.saturating_sub(other.volume);
+        self.maker_fees = self.maker_fees.saturating_sub(other.maker_fees);
+        self.taker_fees = self.taker_fees.saturating_sub(other.taker_fees);
+        self.royalty = self.royalty.saturating_sub(other.royalty);
+    }
+}
+
+/// Sale totals of one auction house in one payment mint (the default pubkey for SOL), kept up
+/// to date by the execute instructions whenever it is passed in remaining accounts
+#[account]
+#[derive(Default, Copy)]
+pub struct HouseMetrics {
+    pub auction_house_key: Pubkey,
+    pub payment_mint: Pubkey,
+    pub bump: u8,
+    pub last_day: i64, // unix day of the last recorded sale
+    pub lifetime: MetricsTotals,
+    // sum of the daily buckets, covers the 30 days up to last_day
+    pub rolling_30d: MetricsTotals,
+    pub daily: [MetricsTotals; HOUSE_METRICS_DAYS],
+}
+
+impl HouseMetrics {
+    pub const LEN: usize = 8 + // discriminator
+    32 + // auction_house_key
     32 + // payment_mint
-    125; // padding to 320 bytes
+    1 + // bump
+    8 + // last_day
+    MetricsTotals::LEN + // lifetime
+    MetricsTotals::LEN + // rolling_30d
+    MetricsTotals::LEN * HOUSE_METRICS_DAYS + // daily
+    47; // padding to 1152 bytes
 
-    pub fn from_bid_args(args: &BidArgs) -> Self {
-        BuyerTradeStateV2 {
-            auction_house_key: args.auction_house_key,
-            buyer: args.buyer,
-            buyer_referral: args.buyer_referral,
-            buyer_price: args.buyer_price,
-            token_mint: args.token_mint,
-            token_size: args.token_size,
-            bump: args.bump,
-            expiry: args.expiry,
-            buyer_creator_royalty_bp: args.buyer_creator_royalty_bp,
-            payment_mint: args.payment_mint,
+    pub fn record(&mut self, now: i64, sale: &MetricsTotals) -> Result<()> {
+        let days = HOUSE_METRICS_DAYS as i64;
+        let today = now.div_euclid(86400);
+        if today > self.last_day {
+            // drop the buckets of the days that fell out of the window
+            for day in self.last_day + 1..=today.min(self.last_day + days) {
+                let bucket = &mut self.daily[day.rem_euclid(days) as usize];
+                self.rolling_30d.sub(bucket);
+                *bucket = MetricsTotals::default();
+            }
+            self.last_day = today;
         }
+        self.daily[self.last_day.rem_euclid(days) as usize].add(sale)?;
+        self.rolling_30d.add(sale)?;
+        self.lifetime.add(sale)
     }
 }
 
+#[derive(Default, Clone)]
 pub struct BidArgs {
     pub auction_house_key: Pubkey,
     pub buyer: Pubkey,
@@ -195,6 +2110,20 @@ pub struct BidArgs {
     pub expiry: i64, // in unix timestamp in seconds
     pub buyer_creator_royalty_bp: u16,
     pub payment_mint: Pubkey,
+    pub indexed: bool,
+    pub escrow_adapter: Pubkey,
+    pub order_memo: [u8; 32],
+    pub receipt_owner: Pubkey,
+    pub ledgered: bool,
+    pub metadata_pin: [u8; METADATA_PIN_LEN],
+    pub escalation: BidEscalation,
+    pub limited: bool,
+    pub price_is_all_in: bool,
+    pub auto_match: bool,
+    pub swap_max_slippage_bp: u16,
+    pub bid_group: Pubkey,
+    pub allowance_funded: bool,
+    pub max_floor_price: u64,
 }
 
 impl BidArgs {
@@ -206,21 +2135,78 @@ impl BidArgs {
         token_size: u64,
         payment_mint: &Pubkey,
     ) -> Result<()> {
-        if self.buyer_referral != *buyer_referral
-            || self.buyer_price != buyer_price
+        // an escalating bid fills at any price it has climbed to
+        let price_matches = if self.escalation.is_set() {
+            buyer_price >= self.buyer_price
+                && buyer_price <= self.price_at(Clock::get()?.unix_timestamp)
+        } else {
+            self.buyer_price == buyer_price
+        };
+        // unregistered referrals are stored as the default key, the bid has no referral to match
+        if (self.buyer_referral != Pubkey::default() && self.buyer_referral != *buyer_referral)
+            || !price_matches
             || self.token_mint != *token_mint
             || self.token_size != token_size
             || self.payment_mint != *payment_mint
         {
-            Err(ErrorCode::InvalidAccountState.into())
+            Err(ErrorCode::TradeStateMismatch.into())
         } else {
             Ok(())
         }
     }
 
+    pub fn price_at(&self, now: i64) -> u64 {
+        self.escalation.price_at(self.buyer_price, now)
+    }
+
+    /// The price to check a fill at buyer_price against the bid with. An all-in bid is checked on
+    /// the fill's total_cost to the buyer instead, it fills at any price whose total cost it covers.
+    pub fn fill_price(&self, buyer_price: u64, total_cost: u64, now: i64) -> Result<u64> {
+        if !self.price_is_all_in {
+            return Ok(buyer_price);
+        }
+        if total_cost > self.price_at(now) {
+            return Err(ErrorCode::TradeStateMismatch.into());
+        }
+        Ok(self.buyer_price)
+    }
+
+    /// What the bid locks of the buyer's escrow, the most it can pay for all its units. buy_v2
+    /// rejects bids whose total doesn't fit, token_size is 0 in some V1 bids.
+    pub fn locked_price(&self) -> u64 {
+        self.buyer_price
+            .max(self.escalation.max_price)
+            .saturating_mul(self.token_size.max(1))
+    }
+
+    /// What the bid locks in the buyer's OpenBidIndex, see get_bid_locked_cost
+    pub fn locked_cost(&self) -> u64 {
+        get_bid_locked_cost(
+            self.locked_price(),
+            self.price_is_all_in,
+            self.buyer_creator_royalty_bp,
+        )
+    }
+
+    /// The most of its payment mint a swap fill at now can spend, the bid's price for all its
+    /// units plus its max slippage
+    pub fn swap_max_amount_in(&self, now: i64) -> u64 {
+        let amount = self.price_at(now).saturating_mul(self.token_size.max(1)) as u128;
+        (amount * (10_000 + self.swap_max_slippage_bp as u128) / 10_000).min(u64::MAX as u128)
+            as u64
+    }
+
     pub fn from_account_info(info: &AccountInfo) -> Result<Self> {
+        assert_not_zombie_trade_state(info)?;
         assert_owned_by(info, &crate::ID)?;
-        let mut account_data: &[u8] = &info.try_borrow_data()?;
+        Self::from_data(&info.try_borrow_data()?)
+    }
+
+    /// Decodes any buyer trade state version, also used by off-chain decoders like m2-indexer
+    pub fn from_data(mut account_data: &[u8]) -> Result<Self> {
+        if account_data.len() < 8 {
+            return Err(ErrorCode::InvalidDiscriminator.into());
+        }
         let discrimantor = &account_data[0..8];
         if discrimantor == BuyerTradeState::discriminator() {
             let bts = BuyerTradeState::try_deserialize(&mut account_data)?;
@@ -235,6 +2221,20 @@ impl BidArgs {
                 expiry: bts.expiry,
                 buyer_creator_royalty_bp: 0,
                 payment_mint: Pubkey::default(),
+                indexed: false,
+                escrow_adapter: Pubkey::default(),
+                order_memo: [0; 32],
+                receipt_owner: Pubkey::default(),
+                ledgered: false,
+                metadata_pin: [0; METADATA_PIN_LEN],
+                escalation: BidEscalation::default(),
+                limited: false,
+                price_is_all_in: false,
+                auto_match: false,
+                swap_max_slippage_bp: 0,
+                bid_group: Pubkey::default(),
+                allowance_funded: false,
+                max_floor_price: 0,
             })
         } else if discrimantor == BuyerTradeStateV2::discriminator() {
             let bts = BuyerTradeStateV2::try_deserialize(&mut account_data)?;
@@ -249,6 +2249,48 @@ impl BidArgs {
                 expiry: bts.expiry,
                 buyer_creator_royalty_bp: bts.buyer_creator_royalty_bp,
                 payment_mint: bts.payment_mint,
+                indexed: bts.indexed,
+                escrow_adapter: bts.escrow_adapter,
+                order_memo: bts.order_memo,
+                receipt_owner: bts.receipt_owner,
+                ledgered: bts.ledgered,
+                metadata_pin: bts.metadata_pin,
+                escalation: BidEscalation::default(),
+                limited: false,
+                price_is_all_in: false,
+                auto_match: false,
+                swap_max_slippage_bp: 0,
+                bid_group: Pubkey::default(),
+                allowance_funded: false,
+                max_floor_price: 0,
+            })
+        } else if discrimantor == BuyerTradeStateV3::discriminator() {
+            let bts = BuyerTradeStateV3::try_deserialize(&mut account_data)?;
+            Ok(BidArgs {
+                auction_house_key: bts.auction_house_key,
+                buyer: bts.buyer,
+                buyer_referral: bts.buyer_referral,
+                buyer_price: bts.buyer_price,
+                token_mint: bts.token_mint,
+                token_size: bts.token_size,
+                bump: bts.bump,
+                expiry: bts.expiry,
+                buyer_creator_royalty_bp: bts.buyer_creator_royalty_bp,
+                payment_mint: bts.payment_mint,
+                indexed: bts.indexed,
+                escrow_adapter: bts.escrow_adapter,
+                order_memo: bts.order_memo,
+                receipt_owner: bts.receipt_owner,
+                ledgered: bts.ledgered,
+                metadata_pin: bts.metadata_pin,
+                escalation: bts.escalation,
+                limited: bts.limited,
+                price_is_all_in: bts.price_is_all_in,
+                auto_match: bts.auto_match,
+                swap_max_slippage_bp: bts.swap_max_slippage_bp,
+                bid_group: bts.bid_group,
+                allowance_funded: bts.allowance_funded,
+                max_floor_price: bts.max_floor_price,
             })
         } else {
             Err(ErrorCode::InvalidDiscriminator.into())
@@ -268,6 +2310,19 @@ pub struct SellArgs {
     pub bump: u8,
     pub expiry: i64, // in unix timestamp in seconds
     pub payment_mint: Pubkey,
+    pub order_memo: [u8; 32],
+    pub proceeds_as_wsol: bool,
+    pub last_updated_at: i64, // 0 for listings older than V3
+    pub frozen: bool,
+    pub royalty_mode: RoyaltyMode,
+    pub created_at: i64, // 0 for listings older than V3
+    pub maker_rewards: bool,
+    pub alt_prices: [AltPrice; MAX_ALT_PRICES],
+    pub requires_attestation: bool,
+    pub auto_match: bool,
+    pub royalty_bp_snapshot: Option<u16>, // None unless the listing locked its royalty
+    pub hidden_until: i64,                // 0 for listings older than V3
+    pub repay_loan: bool,
 }
 
 impl SellArgs {
@@ -279,21 +2334,57 @@ impl SellArgs {
         token_size: &u64,
         payment_mint: &Pubkey,
     ) -> Result<()> {
-        if self.seller_referral != *seller_referral
-            || self.buyer_price != *buyer_price
+        // unregistered referrals are stored as the default key, the listing has no referral to match
+        if (self.seller_referral != Pubkey::default() && self.seller_referral != *seller_referral)
+            || self.price_for(payment_mint) != Some(*buyer_price)
             || self.token_mint != *token_mint
             || self.token_size != *token_size
-            || self.payment_mint != *payment_mint
         {
-            Err(ErrorCode::InvalidAccountState.into())
+            Err(ErrorCode::TradeStateMismatch.into())
         } else {
             Ok(())
         }
     }
 
+    /// The metadata a fill of the listing pays royalties by: a royalty locked listing pays the
+    /// lower of its list-time royalty and the current one, so raising it doesn't reprice the listing
+    pub fn royalty_locked(&self, mut metadata: Metadata) -> Metadata {
+        if let Some(snapshot) = self.royalty_bp_snapshot {
+            metadata.seller_fee_basis_points = metadata.seller_fee_basis_points.min(snapshot);
+        }
+        metadata
+    }
+
+    /// The listing's SOL price as fills see it at now, None while it's hidden
+    pub fn visible_sol_price(&self, now: i64) -> Option<u64> {
+        if now < self.hidden_until {
+            return None;
+        }
+        self.price_for(&Pubkey::default())
+    }
+
+    /// The listing's price in payment_mint, its own price or one of its alt prices
+    pub fn price_for(&self, payment_mint: &Pubkey) -> Option<u64> {
+        if self.payment_mint == *payment_mint {
+            return Some(self.buyer_price);
+        }
+        self.alt_prices
+            .iter()
+            .find(|p| p.price != 0 && p.payment_mint == *payment_mint)
+            .map(|p| p.price)
+    }
+
     pub fn from_account_info(info: &AccountInfo) -> Result<Self> {
+        assert_not_zombie_trade_state(info)?;
         assert_owned_by(info, &crate::ID)?;
-        let mut account_data: &[u8] = &info.try_borrow_data()?;
+        Self::from_data(&info.try_borrow_data()?)
+    }
+
+    /// Decodes any seller trade state version, also used by off-chain decoders like m2-indexer
+    pub fn from_data(mut account_data: &[u8]) -> Result<Self> {
+        if account_data.len() < 8 {
+            return Err(ErrorCode::InvalidDiscriminator.into());
+        }
         let discriminator = &account_data[0..8];
         if discriminator == SellerTradeState::discriminator() {
             let sts = SellerTradeState::try_deserialize(&mut account_data)?;
@@ -308,6 +2399,19 @@ impl SellArgs {
                 token_account: sts.token_account,
                 expiry: sts.expiry,
                 payment_mint: Pubkey::default(),
+                order_memo: [0; 32],
+                proceeds_as_wsol: false,
+                last_updated_at: 0,
+                frozen: false,
+                royalty_mode: RoyaltyMode::Optional,
+                created_at: 0,
+                maker_rewards: false,
+                alt_prices: Default::default(),
+                requires_attestation: false,
+                auto_match: false,
+                royalty_bp_snapshot: None,
+                hidden_until: 0,
+                repay_loan: false,
             })
         } else if discriminator == SellerTradeStateV2::discriminator() {
             let sts = SellerTradeStateV2::try_deserialize(&mut account_data)?;
@@ -322,9 +2426,144 @@ impl SellArgs {
                 token_account: sts.token_account,
                 expiry: sts.expiry,
                 payment_mint: sts.payment_mint,
+                order_memo: [0; 32],
+                proceeds_as_wsol: false,
+                last_updated_at: 0,
+                frozen: false,
+                royalty_mode: RoyaltyMode::Optional,
+                created_at: 0,
+                maker_rewards: false,
+                alt_prices: Default::default(),
+                requires_attestation: false,
+                auto_match: false,
+                royalty_bp_snapshot: None,
+                hidden_until: 0,
+                repay_loan: false,
+            })
+        } else if discriminator == SellerTradeStateV3::discriminator() {
+            let alt_prices = SellerTradeStateV3::alt_prices_from_data(account_data)?;
+            let sts = SellerTradeStateV3::try_deserialize(&mut account_data)?;
+            Ok(SellArgs {
+                auction_house_key: sts.auction_house_key,
+                seller: sts.seller,
+                seller_referral: sts.seller_referral,
+                buyer_price: sts.buyer_price,
+                token_mint: sts.token_mint,
+                token_size: sts.token_size,
+                bump: sts.bump,
+                token_account: sts.token_account,
+                expiry: sts.expiry,
+                payment_mint: sts.payment_mint,
+                order_memo: sts.order_memo,
+                proceeds_as_wsol: sts.proceeds_as_wsol,
+                last_updated_at: sts.last_updated_at,
+                frozen: sts.frozen,
+                royalty_mode: sts.royalty_mode,
+                created_at: sts.created_at,
+                maker_rewards: sts.maker_rewards,
+                alt_prices,
+                requires_attestation: sts.requires_attestation,
+                auto_match: sts.auto_match,
+                royalty_bp_snapshot: sts.royalty_lock.then_some(sts.royalty_bp_snapshot),
+                hidden_until: sts.hidden_until,
+                repay_loan: sts.repay_loan,
             })
         } else {
             Err(ErrorCode::InvalidDiscriminator.into())
         }
     }
 }
+
+/// Optional trailing listing arguments, missing bytes are treated as zeroes
+#[derive(Default, Clone, Copy)]
+pub struct SellExtraArgs {
+    pub origin: [u8; 8],
+    pub order_memo: [u8; 32],
+    pub proceeds_as_wsol: bool,
+    pub auto_relist_price_bp: u16,
+    pub auto_relist_max: u8,
+    pub royalty_mode: RoyaltyMode,
+    pub alt_prices: [AltPrice; MAX_ALT_PRICES],
+    pub requires_attestation: bool,
+    pub auto_match: bool,
+    pub royalty_lock: bool,
+    pub hidden_until: i64,
+    pub repay_loan: bool,
+}
+
+impl SellExtraArgs {
+    pub const LEN: usize =
+        8 + 32 + 1 + 2 + 1 + 1 + SellerTradeStateV3::ALT_PRICES_LEN + 1 + 1 + 1 + 8 + 1;
+
+    pub fn parse(extra_args: &[u8]) -> Self {
+        let mut buf = [0u8; Self::LEN];
+        let len = extra_args.len().min(Self::LEN);
+        buf[..len].copy_from_slice(&extra_args[..len]);
+        SellExtraArgs {
+            origin: *array_ref![buf, 0, 8],
+            order_memo: *array_ref![buf, 8, 32],
+            proceeds_as_wsol: buf[40] != 0,
+            auto_relist_price_bp: u16::from_le_bytes(*array_ref![buf, 41, 2]),
+            auto_relist_max: buf[43],
+            royalty_mode: match buf[44] {
+                0 => RoyaltyMode::Optional,
+                _ => RoyaltyMode::Full,
+            },
+            alt_prices: std::array::from_fn(|i| {
+                let offset = 45 + i * AltPrice::LEN;
+                AltPrice {
+                    payment_mint: Pubkey::new_from_array(*array_ref![buf, offset, 32]),
+                    price: u64::from_le_bytes(*array_ref![buf, offset + 32, 8]),
+                }
+            }),
+            requires_attestation: buf[45 + SellerTradeStateV3::ALT_PRICES_LEN] != 0,
+            auto_match: buf[46 + SellerTradeStateV3::ALT_PRICES_LEN] != 0,
+            royalty_lock: buf[47 + SellerTradeStateV3::ALT_PRICES_LEN] != 0,
+            hidden_until: i64::from_le_bytes(*array_ref![
+                buf,
+                48 + SellerTradeStateV3::ALT_PRICES_LEN,
+                8
+            ]),
+            repay_loan: buf[56 + SellerTradeStateV3::ALT_PRICES_LEN] != 0,
+        }
+    }
+}
+
+/// Optional trailing bid arguments, missing bytes are treated as zeroes
+#[derive(Default, Clone, Copy)]
+pub struct BuyExtraArgs {
+    pub escrow_adapter: Pubkey,
+    pub order_memo: [u8; 32],
+    pub receipt_owner: Pubkey,
+    pub metadata_pin: [u8; METADATA_PIN_LEN],
+    pub escalation: BidEscalation, // a start_at of 0 starts escalating when the bid is placed
+    pub price_is_all_in: bool,
+    pub auto_match: bool,
+    pub swap_max_slippage_bp: u16,
+    pub max_floor_price: u64, // the bid only fills while its verified collection's floor is at or below this
+}
+
+impl BuyExtraArgs {
+    pub const LEN: usize = 32 + 32 + 32 + METADATA_PIN_LEN + BidEscalation::LEN + 1 + 1 + 2 + 8;
+
+    pub fn parse(extra_args: &[u8]) -> Self {
+        let mut buf = [0u8; Self::LEN];
+        let len = extra_args.len().min(Self::LEN);
+        buf[..len].copy_from_slice(&extra_args[..len]);
+        BuyExtraArgs {
+            escrow_adapter: Pubkey::new_from_array(*array_ref![buf, 0, 32]),
+            order_memo: *array_ref![buf, 32, 32],
+            receipt_owner: Pubkey::new_from_array(*array_ref![buf, 64, 32]),
+            metadata_pin: *array_ref![buf, 96, METADATA_PIN_LEN],
+            escalation: BidEscalation {
+                max_price: u64::from_le_bytes(*array_ref![buf, 96 + METADATA_PIN_LEN, 8]),
+                start_at: i64::from_le_bytes(*array_ref![buf, 104 + METADATA_PIN_LEN, 8]),
+                end_at: i64::from_le_bytes(*array_ref![buf, 112 + METADATA_PIN_LEN, 8]),
+            },
+            price_is_all_in: buf[120 + METADATA_PIN_LEN] != 0,
+            auto_match: buf[121 + METADATA_PIN_LEN] != 0,
+            swap_max_slippage_bp: u16::from_le_bytes(*array_ref![buf, 122 + METADATA_PIN_LEN, 2]),
+            max_floor_price: u64::from_le_bytes(*array_ref![buf, 124 + METADATA_PIN_LEN, 8]),
+        }
+    }
+}
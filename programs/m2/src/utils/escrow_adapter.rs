@@ -0,0 +1,163 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        hash::hash,
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+};
+
+use crate::{
+    constants::PREFIX,
+    errors::ErrorCode,
+    states::{AuctionHouse, BidArgs},
+};
+
+/// Calls M2 makes into an external escrow program that funds bids instead of the buyer's
+/// escrow PDA. They are encoded like Anchor instructions, the first 8 bytes of
+/// sha256("global:<name>") followed by the amount as u64 LE where there is one, with accounts:
+/// 0. auction_house (signer) - proves the call comes from M2
+/// 1. buyer_trade_state - the bid the funds are locked for
+/// 2. wallet - the buyer
+/// 3. escrow_payment_account (writable, pay only) - the buyer's escrow PDA, receives the funds
+/// 4. onwards, the adapter's own accounts, as passed after the adapter program in remaining accounts
+pub enum EscrowAdapterInstruction {
+    /// Lock amount for the bid, replacing whatever was locked for it before
+    Lock { amount: u64 },
+    /// Unlock whatever is locked for the bid
+    Release,
+    /// Move amount to escrow_payment_account and unlock the bid
+    Pay { amount: u64 },
+}
+
+impl EscrowAdapterInstruction {
+    fn data(&self) -> Vec<u8> {
+        let (name, amount) = match self {
+            EscrowAdapterInstruction::Lock { amount } => ("lock", Some(*amount)),
+            EscrowAdapterInstruction::Release => ("release", None),
+            EscrowAdapterInstruction::Pay { amount } => ("pay", Some(*amount)),
+        };
+        let mut data = hash(format!("global:{}", name).as_bytes()).to_bytes()[..8].to_vec();
+        if let Some(amount) = amount {
+            data.extend_from_slice(&amount.to_le_bytes());
+        }
+        data
+    }
+}
+
+pub fn assert_escrow_adapter_allowed(
+    auction_house: &AuctionHouse,
+    escrow_adapter: &Pubkey,
+) -> Result<()> {
+    if !auction_house.is_escrow_adapter_allowed(escrow_adapter) {
+        return Err(ErrorCode::EscrowAdapterNotAllowed.into());
+    }
+    Ok(())
+}
+
+/// Returns the adapter program followed by its accounts, that is the remaining accounts from
+/// the adapter program onwards
+pub fn get_escrow_adapter_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    escrow_adapter: &Pubkey,
+) -> Result<&'a [AccountInfo<'info>]> {
+    let position = remaining_accounts
+        .iter()
+        .position(|ai| ai.key == escrow_adapter && ai.executable)
+        .ok_or(ErrorCode::MissingRemainingAccount)?;
+    Ok(&remaining_accounts[position..])
+}
+
+pub fn invoke_escrow_adapter<'info>(
+    instruction: EscrowAdapterInstruction,
+    adapter_accounts: &[AccountInfo<'info>],
+    auction_house: &AccountInfo<'info>,
+    auction_house_seeds: &[&[u8]],
+    buyer_trade_state: &AccountInfo<'info>,
+    wallet: &AccountInfo<'info>,
+    escrow_payment_account: Option<&AccountInfo<'info>>,
+) -> Result<()> {
+    let (program, accounts) = adapter_accounts
+        .split_first()
+        .ok_or(ErrorCode::MissingRemainingAccount)?;
+    let mut metas = vec![
+        AccountMeta::new_readonly(*auction_house.key, true),
+        AccountMeta::new_readonly(*buyer_trade_state.key, false),
+        AccountMeta::new_readonly(*wallet.key, false),
+    ];
+    let mut infos = vec![
+        auction_house.clone(),
+        buyer_trade_state.clone(),
+        wallet.clone(),
+    ];
+    let expected_payment = match instruction {
+        EscrowAdapterInstruction::Pay { amount } => {
+            let escrow = escrow_payment_account.ok_or(ErrorCode::MissingRemainingAccount)?;
+            metas.push(AccountMeta::new(*escrow.key, false));
+            infos.push(escrow.clone());
+            Some((escrow, escrow.lamports(), amount))
+        }
+        _ => None,
+    };
+    for ai in accounts {
+        metas.push(if ai.is_writable {
+            AccountMeta::new(*ai.key, ai.is_signer)
+        } else {
+            AccountMeta::new_readonly(*ai.key, ai.is_signer)
+        });
+        infos.push(ai.clone());
+    }
+    infos.push(program.clone());
+
+    invoke_signed(
+        &Instruction {
+            program_id: *program.key,
+            accounts: metas,
+            data: instruction.data(),
+        },
+        &infos,
+        &[auction_house_seeds],
+    )?;
+
+    // the adapter is trusted to lock and release, but a payment is checked
+    if let Some((escrow, lamports_before, amount)) = expected_payment {
+        let expected = lamports_before
+            .checked_add(amount)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        if escrow.lamports() < expected {
+            return Err(ErrorCode::EscrowAdapterUnderpaid.into());
+        }
+    }
+    Ok(())
+}
+
+/// Has the escrow adapter of an adapter funded bid pay the price into the buyer's escrow PDA,
+/// from where the sale settles like any other SOL bid
+pub fn fund_escrow_from_adapter<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    auction_house: &Account<'info, AuctionHouse>,
+    bid_args: &BidArgs,
+    buyer_trade_state: &AccountInfo<'info>,
+    buyer: &AccountInfo<'info>,
+    escrow_payment_account: &AccountInfo<'info>,
+) -> Result<()> {
+    if bid_args.escrow_adapter == Pubkey::default() {
+        return Ok(());
+    }
+    assert_escrow_adapter_allowed(auction_house, &bid_args.escrow_adapter)?;
+    invoke_escrow_adapter(
+        EscrowAdapterInstruction::Pay {
+            amount: bid_args.locked_price(),
+        },
+        get_escrow_adapter_accounts(remaining_accounts, &bid_args.escrow_adapter)?,
+        auction_house.as_ref(),
+        &[
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            &[auction_house.bump],
+        ],
+        buyer_trade_state,
+        buyer,
+        Some(escrow_payment_account),
+    )
+}
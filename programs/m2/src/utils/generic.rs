@@ -1,13 +1,21 @@
 use anchor_lang::Discriminator;
 use mpl_token_metadata::{
-    accounts::{Metadata, TokenRecord},
+    accounts::{MasterEdition, Metadata, TokenRecord},
+    instructions::{
+        FreezeDelegatedAccountCpi, FreezeDelegatedAccountCpiAccounts, ThawDelegatedAccountCpi,
+        ThawDelegatedAccountCpiAccounts,
+    },
     types::{TokenDelegateRole, TokenStandard, TokenState},
 };
+use open_creator_protocol::state::MintState;
 use spl_associated_token_account::instruction;
 
 use crate::constants::{
+    ATTESTATION, CANCEL_AUTHORITY, CANCEL_AUTHORITY_REGISTRY, CO_OWNER_APPROVAL, CPI_ALLOWLIST,
     DEFAULT_BID_EXPIRY_SECONDS_AFTER_NOW, DEFAULT_MAKER_FEE_BP, DEFAULT_TAKER_FEE_BP,
-    VALID_PAYMENT_MINTS,
+    ESCROW_LEDGER, GUARDIAN, LISTING_INDEX, MAKER_REWARDS, MAX_ALT_PRICES, MAX_PRICE,
+    METADATA_PIN_LEN, MINT_MARKET, NOTARY_SET, OPEN_BID_INDEX, PREFIX, PROGRAM_CONFIG, RATE_LIMIT,
+    REFERRER, RENT_VAULT, VALID_PAYMENT_MINTS, WALLET_LIMITS,
 };
 
 use {
@@ -16,17 +24,24 @@ use {
     anchor_lang::{
         prelude::*,
         solana_program::{
+            ed25519_program,
+            hash::hashv,
+            instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
             program::invoke,
             program::invoke_signed,
             program_option::COption,
             program_pack::{IsInitialized, Pack},
-            system_instruction,
+            system_instruction, system_program,
+            sysvar::{
+                self,
+                instructions::{load_current_index_checked, load_instruction_at_checked},
+            },
         },
     },
-    anchor_spl::token::Mint,
+    anchor_spl::{token::Mint, token_2022},
     arrayref::array_ref,
-    spl_associated_token_account::get_associated_token_address,
-    std::convert::TryInto,
+    spl_associated_token_account::get_associated_token_address_with_program_id,
+    std::{cmp, convert::TryInto},
 };
 
 pub fn get_default_buyer_state_expiry(buyer_state_expiry: i64) -> i64 {
@@ -36,35 +51,129 @@ pub fn get_default_buyer_state_expiry(buyer_state_expiry: i64) -> i64 {
     }
 }
 
+/// Fails once the order can no longer be filled, see Expiry for what a trade state's expiry means
+pub fn assert_not_expired(expiry: i64, now: i64) -> Result<()> {
+    if Expiry::from(expiry).is_expired(now) {
+        return Err(ErrorCode::InvalidExpiry.into());
+    }
+    Ok(())
+}
+
+/// Fails while a listing is hidden for a delayed reveal, fills can't see it before hidden_until
+pub fn assert_listing_visible(hidden_until: i64, now: i64) -> Result<()> {
+    if now < hidden_until {
+        return Err(ErrorCode::ListingHidden.into());
+    }
+    Ok(())
+}
+
+/// Fails unless a new listing's expiry is in the listing mode the instruction creates
+pub fn assert_expiry_mode(expiry: i64, escrowed: bool) -> Result<Expiry> {
+    let expiry = Expiry::from(expiry);
+    if expiry.is_escrowed() != escrowed {
+        return Err(ErrorCode::InvalidExpiry.into());
+    }
+    Ok(expiry)
+}
+
+/// Checks a new bid's escalation, a start_at of 0 starts it at now
+pub fn assert_bid_escalation(
+    escalation: &BidEscalation,
+    buyer_price: u64,
+    now: i64,
+) -> Result<BidEscalation> {
+    if !escalation.is_set() {
+        return Ok(BidEscalation::default());
+    }
+    let start_at = match escalation.start_at {
+        0 => now,
+        start_at => start_at,
+    };
+    if escalation.max_price <= buyer_price
+        || escalation.max_price > MAX_PRICE
+        || escalation.end_at <= start_at
+    {
+        return Err(ErrorCode::InvalidBidEscalation.into());
+    }
+    Ok(BidEscalation {
+        start_at,
+        ..*escalation
+    })
+}
+
+/// The fees a sale pays: those it was given if the notary signed for them, otherwise the payment
+/// mint's override in the ProgramConfig, see get_payment_mint_fees, or the defaults
 pub fn get_actual_maker_taker_fee_bp(
-    notary: &AccountInfo,
+    notary_cosign: &NotaryCosign,
     maker_fee_bp: i16,
     taker_fee_bp: u16,
+    payment_mint_fees: Option<PaymentMintFees>,
 ) -> (i16, u16) {
-    match notary.is_signer {
-        true => (maker_fee_bp, taker_fee_bp),
-        false => (DEFAULT_MAKER_FEE_BP, DEFAULT_TAKER_FEE_BP),
+    match (notary_cosign.signed, payment_mint_fees) {
+        (true, _) => (maker_fee_bp, taker_fee_bp),
+        (false, Some(fees)) => (fees.maker_fee_bp, fees.taker_fee_bp),
+        (false, None) => (DEFAULT_MAKER_FEE_BP, DEFAULT_TAKER_FEE_BP),
     }
 }
 
+/// The ProgramConfig's fee override for a sale's payment mint, if the config is passed in
+/// remaining accounts
+pub fn get_payment_mint_fees(
+    remaining_accounts: &[AccountInfo],
+    payment_mint: &Pubkey,
+) -> Result<Option<PaymentMintFees>> {
+    let program_config =
+        match find_remaining_account(remaining_accounts, &get_program_config_address()) {
+            Some(program_config) => program_config,
+            None => return Ok(None),
+        };
+    assert_owned_by(program_config, &crate::ID)?;
+    let config = ProgramConfig::try_deserialize(&mut &program_config.try_borrow_data()?[..])?;
+    Ok(config.fees_for(payment_mint))
+}
+
 pub fn is_token_owner(token_account: &AccountInfo, owner: &Pubkey) -> Result<bool> {
-    let acc: spl_token::state::Account = assert_initialized(token_account)?;
+    let acc = unpack_token_account(token_account)?;
     Ok(acc.owner == *owner)
 }
 
+/// Unpacks the base token account state of a legacy or Token-2022 token account.
+/// Token-2022 accounts share the legacy layout and append extensions (e.g. immutable owner) after it.
+pub fn unpack_token_account(token_account: &AccountInfo) -> Result<spl_token::state::Account> {
+    if token_account.owner == &token_2022::ID {
+        let data = token_account.data.borrow();
+        if data.len() < spl_token::state::Account::LEN {
+            return Err(ErrorCode::UninitializedAccount.into());
+        }
+        let account =
+            spl_token::state::Account::unpack_unchecked(&data[..spl_token::state::Account::LEN])?;
+        if !account.is_initialized() {
+            return Err(ErrorCode::UninitializedAccount.into());
+        }
+        Ok(account)
+    } else {
+        assert_initialized(token_account)
+    }
+}
+
 pub fn assert_is_ata(
     ata: &AccountInfo,
     wallet: &Pubkey,
     mint: &Pubkey,
     optional_owner: &Pubkey,
 ) -> Result<spl_token::state::Account> {
-    assert_owned_by(ata, &spl_token::id())?;
-    let ata_account: spl_token::state::Account = assert_initialized(ata)?;
+    if ata.owner != &token_2022::ID {
+        assert_owned_by(ata, &spl_token::id())?;
+    }
+    let ata_account = unpack_token_account(ata)?;
     if ata_account.owner != *optional_owner {
         assert_keys_equal(&ata_account.owner, wallet)?;
     }
     assert_keys_equal(&ata_account.mint, mint)?;
-    assert_keys_equal(&get_associated_token_address(wallet, mint), ata.key)?;
+    assert_keys_equal(
+        &get_associated_token_address_with_program_id(wallet, mint, ata.owner),
+        ata.key,
+    )?;
     Ok(ata_account)
 }
 
@@ -76,6 +185,7 @@ pub fn assert_bump(seeds: &[&[u8]], program_id: &Pubkey, bump: u8) -> Result<()>
     Ok(())
 }
 
+/// Creates the ATA of `wallet` for `mint` under `token_program`, no-op if it already exists
 pub fn make_ata<'a>(
     ata: AccountInfo<'a>,
     payer: AccountInfo<'a>,
@@ -85,7 +195,7 @@ pub fn make_ata<'a>(
     system_program: AccountInfo<'a>,
 ) -> Result<()> {
     invoke(
-        &instruction::create_associated_token_account(
+        &instruction::create_associated_token_account_idempotent(
             payer.key,
             wallet.key,
             mint.key,
@@ -97,7 +207,7 @@ pub fn make_ata<'a>(
     Ok(())
 }
 
-pub fn assert_metadata_valid(metadata: &UncheckedAccount, token_mint: &Pubkey) -> Result<()> {
+pub fn assert_metadata_valid(metadata: &AccountInfo, token_mint: &Pubkey) -> Result<()> {
     assert_derivation(
         &mpl_token_metadata::ID,
         &metadata.to_account_info(),
@@ -114,23 +224,153 @@ pub fn assert_metadata_valid(metadata: &UncheckedAccount, token_mint: &Pubkey) -
     Ok(())
 }
 
+/// The royalty a royalty locked listing snapshots at list time, the metadata's current
+/// seller_fee_basis_points, 0 for listings that don't lock it
+pub fn get_royalty_bp_snapshot(royalty_lock: bool, metadata: &AccountInfo) -> Result<u16> {
+    if !royalty_lock {
+        return Ok(0);
+    }
+    Ok(Metadata::safe_deserialize(&metadata.data.borrow())?.seller_fee_basis_points)
+}
+
+/// The hash a bid pins the metadata with: sha256 of the name, the uri, seller_fee_basis_points and
+/// the creators as stored on-chain, truncated to METADATA_PIN_LEN bytes. Strings are prefixed with
+/// their u32 LE length, seller_fee_basis_points is u16 LE and each creator is its address, verified
+/// flag and share.
+pub fn get_metadata_pin(metadata: &Metadata) -> [u8; METADATA_PIN_LEN] {
+    let name_len = (metadata.name.len() as u32).to_le_bytes();
+    let uri_len = (metadata.uri.len() as u32).to_le_bytes();
+    let seller_fee_basis_points = metadata.seller_fee_basis_points.to_le_bytes();
+    let creators: Vec<u8> = metadata
+        .creators
+        .iter()
+        .flatten()
+        .flat_map(|c| {
+            let mut creator = c.address.to_bytes().to_vec();
+            creator.extend([c.verified as u8, c.share]);
+            creator
+        })
+        .collect();
+    let hash = hashv(&[
+        &name_len,
+        metadata.name.as_bytes(),
+        &uri_len,
+        metadata.uri.as_bytes(),
+        &seller_fee_basis_points,
+        &creators,
+    ]);
+    let hash = hash.to_bytes();
+    *array_ref![hash, 0, METADATA_PIN_LEN]
+}
+
+/// Checks that the metadata still hashes to what a bid pinned, bids without a pin accept any
+pub fn assert_metadata_pin(
+    metadata_pin: &[u8; METADATA_PIN_LEN],
+    metadata: &AccountInfo,
+) -> Result<()> {
+    if *metadata_pin == [0; METADATA_PIN_LEN] {
+        return Ok(());
+    }
+    let metadata = Metadata::safe_deserialize(&metadata.try_borrow_data()?)
+        .map_err(|_| ErrorCode::MetadataDoesntExist)?;
+    if get_metadata_pin(&metadata) != *metadata_pin {
+        return Err(ErrorCode::MetadataPinMismatch.into());
+    }
+    Ok(())
+}
+
+pub fn get_notary_set_address(auction_house_key: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            NOTARY_SET.as_bytes(),
+            auction_house_key.as_ref(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
+
+/// How an instruction's notary account stands with the auction house, see
+/// split_notary_set_from_remaining_accounts
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NotaryCosign {
+    pub known: bool, // the notary is auction_house.notary or one of the passed NotarySet's notaries
+    pub signed: bool, // auction_house.notary signed, or threshold of the NotarySet's notaries did
+}
+
+impl NotaryCosign {
+    /// For notary accounts that only have to be the house's, signing or not
+    pub fn assert_known(&self) -> Result<()> {
+        if !self.known {
+            return Err(ErrorCode::InvalidNotary.into());
+        }
+        Ok(())
+    }
+
+    pub fn assert_signed(&self) -> Result<()> {
+        if !self.signed {
+            return Err(ErrorCode::InvalidNotary.into());
+        }
+        Ok(())
+    }
+}
+
+/// Splits the auction house's NotarySet and the notaries signing with it off the start of remaining
+/// accounts, when they are passed, and returns how the notary account stands with the house. The
+/// notary cosigns if it's auction_house.notary and signs, or if threshold of the set's notaries sign
+/// as the notary or right after the set.
+pub fn split_notary_set_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    auction_house: &Account<AuctionHouse>,
+    notary: &AccountInfo,
+) -> Result<(&'a [AccountInfo<'info>], NotaryCosign)> {
+    let house_notary = NotaryCosign {
+        known: *notary.key == auction_house.notary,
+        signed: notary.is_signer && *notary.key == auction_house.notary,
+    };
+    let (notary_set, rest) = match remaining_accounts.split_first() {
+        Some((first, rest))
+            if first.owner == &crate::ID
+                && *first.key == get_notary_set_address(&auction_house.key()) =>
+        {
+            (first, rest)
+        }
+        _ => return Ok((remaining_accounts, house_notary)),
+    };
+    let notary_set = NotarySet::try_deserialize(&mut &notary_set.try_borrow_data()?[..])?;
+    let is_set_notary =
+        |key: &Pubkey| *key != Pubkey::default() && notary_set.notaries.contains(key);
+    let cosigners = rest
+        .iter()
+        .take_while(|ai| ai.is_signer && is_set_notary(ai.key))
+        .count();
+    let (cosigners, rest) = rest.split_at(cosigners);
+    let signers = cosigners
+        .iter()
+        .map(|ai| ai.key)
+        .chain(notary.is_signer.then_some(notary.key));
+    Ok((
+        rest,
+        NotaryCosign {
+            known: house_notary.known || is_set_notary(notary.key),
+            signed: house_notary.signed || notary_set.is_cosigned_by(signers),
+        },
+    ))
+}
+
+/// Checks the notary cosigned when the auction house requires it, see
+/// split_notary_set_from_remaining_accounts
 pub fn assert_valid_notary(
-    auction_house: &AuctionHouse,
-    notary: &UncheckedAccount,
+    auction_house: &Account<AuctionHouse>,
+    notary_cosign: &NotaryCosign,
     enforce_prob: u8, // 0-100
 ) -> Result<()> {
     if auction_house.requires_notary {
         if ((Clock::get()?.unix_timestamp.abs() % 100) as u8) >= enforce_prob {
             return Ok(());
         }
-
-        if !notary.to_account_info().is_signer {
-            return Err(ErrorCode::InvalidAccountState.into());
-        }
-
-        if notary.key() != auction_house.notary {
-            return Err(ErrorCode::InvalidAccountState.into());
-        }
+        notary_cosign.assert_signed()?;
     }
 
     Ok(())
@@ -150,11 +390,11 @@ pub fn assert_valid_delegation(
         Ok(token_account) => {
             // Ensure that the delegated amount is exactly equal to the maker_size
             if token_account.delegated_amount != paysize {
-                return Err(ErrorCode::InvalidAccountState.into());
+                return Err(ErrorCode::DelegateMismatch.into());
             }
             // Ensure that authority is the delegate of this token account
             if token_account.delegate != COption::Some(*transfer_authority.key) {
-                return Err(ErrorCode::InvalidAccountState.into());
+                return Err(ErrorCode::DelegateMismatch.into());
             }
 
             assert_is_ata(src_account, src_wallet.key, &mint.key(), src_wallet.key)?;
@@ -202,8 +442,13 @@ pub fn assert_owned_by(account: &AccountInfo, owner: &Pubkey) -> Result<()> {
     }
 }
 
-pub fn assert_payment_mint(mint_ai: &AccountInfo) -> Result<()> {
-    if !VALID_PAYMENT_MINTS.contains(mint_ai.key) || mint_ai.owner != &spl_token::id() {
+pub fn assert_payment_mint(
+    mint_ai: &AccountInfo,
+    program_config: Option<&ProgramConfig>,
+) -> Result<()> {
+    let is_valid = VALID_PAYMENT_MINTS.contains(mint_ai.key)
+        || program_config.map_or(false, |c| c.is_payment_mint(mint_ai.key));
+    if !is_valid || mint_ai.owner != &spl_token::id() {
         Err(ErrorCode::InvalidTokenMint.into())
     } else {
         Ok(())
@@ -296,6 +541,196 @@ pub fn pay_auction_house_fees<'a>(
     Ok(treasury_fee)
 }
 
+pub fn get_program_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[PREFIX.as_bytes(), PROGRAM_CONFIG.as_bytes()], &crate::ID).0
+}
+
+fn load_program_config(program_config: &AccountInfo) -> Result<Option<ProgramConfig>> {
+    // deployments without a ProgramConfig can pass its uninitialized PDA
+    if program_config.data_is_empty() {
+        return Ok(None);
+    }
+    assert_owned_by(program_config, &crate::ID)?;
+    Ok(Some(ProgramConfig::try_deserialize(
+        &mut &program_config.try_borrow_data()?[..],
+    )?))
+}
+
+/// Splits the optional ProgramConfig off the front of remaining accounts, it goes before
+/// everything else when passed in
+pub fn split_program_config_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<(&'a [AccountInfo<'info>], Option<ProgramConfig>)> {
+    if let Some((first, rest)) = remaining_accounts.split_first() {
+        if *first.key == get_program_config_address() {
+            return Ok((rest, load_program_config(first)?));
+        }
+    }
+    Ok((remaining_accounts, None))
+}
+
+/// The optional ProgramConfig, wherever it is in remaining accounts
+pub fn find_program_config(remaining_accounts: &[AccountInfo]) -> Result<Option<ProgramConfig>> {
+    match find_remaining_account(remaining_accounts, &get_program_config_address()) {
+        Some(program_config) => load_program_config(program_config),
+        None => Ok(None),
+    }
+}
+
+pub fn assert_instruction_enabled(
+    program_config: Option<&ProgramConfig>,
+    ix: GatedInstruction,
+) -> Result<()> {
+    match program_config {
+        Some(config) if !config.is_enabled(ix) => Err(ErrorCode::Deprecated.into()),
+        _ => Ok(()),
+    }
+}
+
+/// assert_instruction_enabled for a ProgramConfig anywhere in remaining accounts, for entrypoints
+/// that reach a gated flow without a place of their own for the config
+pub fn assert_instruction_enabled_in(
+    remaining_accounts: &[AccountInfo],
+    ix: GatedInstruction,
+) -> Result<()> {
+    assert_instruction_enabled(find_program_config(remaining_accounts)?.as_ref(), ix)
+}
+
+/// Whether token_account still backs a listing of token_mint, escrowed by or delegated to
+/// program_as_signer. A closed or emptied token account can't back the listing any more.
+pub fn is_listing_token_live(
+    token_account: &AccountInfo,
+    token_mint: &Pubkey,
+    program_as_signer: &Pubkey,
+) -> Result<bool> {
+    if token_account.data_is_empty() {
+        return Ok(false);
+    }
+    let account = unpack_token_account(token_account)?;
+    if account.mint != *token_mint {
+        return Err(ErrorCode::InvalidTokenMint.into());
+    }
+    let is_escrowed = account.owner == *program_as_signer;
+    let is_delegated =
+        account.delegate == Some(*program_as_signer).into() && account.delegated_amount > 0;
+    Ok(account.amount > 0 && (is_escrowed || is_delegated))
+}
+
+/// Whether an escrowless listing still holds its invariant: the seller owns token_account and
+/// delegates at least the listed token_size, which it still holds, to program_as_signer. A
+/// transfer or a revoke breaks it, the listing could only fail to fill from then on.
+pub fn is_delegate_listing_intact(
+    token_account: &spl_token::state::Account,
+    sell_args: &SellArgs,
+    program_as_signer: &Pubkey,
+) -> bool {
+    token_account.owner == sell_args.seller
+        && token_account.delegate == COption::Some(*program_as_signer)
+        && token_account.delegated_amount >= sell_args.token_size
+        && token_account.amount >= sell_args.token_size
+}
+
+pub fn get_cancel_authority_registry_address(auction_house_key: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            CANCEL_AUTHORITY_REGISTRY.as_bytes(),
+            auction_house_key.as_ref(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
+
+/// Whether key can cancel orders for their makers: once the auction house has a
+/// CancelAuthorityRegistry only its enabled keys can, and it has to be in remaining accounts,
+/// before that CANCEL_AUTHORITY and the ProgramConfig cancel authority can
+pub fn is_cancel_authority(
+    key: &Pubkey,
+    auction_house: &Account<AuctionHouse>,
+    program_config: Option<&ProgramConfig>,
+    remaining_accounts: &[AccountInfo],
+) -> Result<bool> {
+    if auction_house.cancel_authority_registry {
+        let registry_key = get_cancel_authority_registry_address(&auction_house.key());
+        return match find_remaining_account(remaining_accounts, &registry_key) {
+            Some(ai) => {
+                assert_owned_by(ai, &crate::ID)?;
+                let registry =
+                    CancelAuthorityRegistry::try_deserialize(&mut &ai.try_borrow_data()?[..])?;
+                Ok(registry.is_enabled(key))
+            }
+            None => Ok(false),
+        };
+    }
+    Ok(*key == CANCEL_AUTHORITY
+        || program_config.map_or(false, |c| {
+            c.cancel_authority != Pubkey::default() && c.cancel_authority == *key
+        }))
+}
+
+pub fn get_cpi_allowlist_address(auction_house_key: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            CPI_ALLOWLIST.as_bytes(),
+            auction_house_key.as_ref(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
+
+/// Checks the auction house's CpiPolicy for a sale. When the sale is a CPI, the caller checked is
+/// the program of the transaction's top level instruction, so nested CPIs need the outermost
+/// program allowed.
+pub fn assert_cpi_caller_allowed(
+    auction_house: &Account<AuctionHouse>,
+    instructions_sysvar: Option<&AccountInfo>,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    if auction_house.cpi_policy == CpiPolicy::AllowAll
+        || get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT
+    {
+        return Ok(());
+    }
+    let instructions_sysvar = instructions_sysvar.ok_or(ErrorCode::MissingRemainingAccount)?;
+    assert_keys_equal(instructions_sysvar.key, &sysvar::instructions::id())?;
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    let caller = load_instruction_at_checked(current_index, instructions_sysvar)?.program_id;
+    let allowlist = find_remaining_account(
+        remaining_accounts,
+        &get_cpi_allowlist_address(&auction_house.key()),
+    )
+    .ok_or(ErrorCode::CpiCallerNotAllowed)?;
+    assert_owned_by(allowlist, &crate::ID)?;
+    let allowlist = CpiAllowlist::try_deserialize(&mut &allowlist.try_borrow_data()?[..])?;
+    if !allowlist.is_allowed(&caller) {
+        return Err(ErrorCode::CpiCallerNotAllowed.into());
+    }
+    Ok(())
+}
+
+// Optional extra args are appended by clients after the borsh args of a top level instruction, so
+// instructions keep their original args. Returns the bytes after `args_len` (discriminator
+// included), or none if the call is a CPI.
+pub fn get_trailing_instruction_data(
+    instructions_sysvar: &AccountInfo,
+    discriminator: &[u8],
+    args_len: usize,
+) -> Result<Vec<u8>> {
+    if get_stack_height() != TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Ok(vec![]);
+    }
+    assert_keys_equal(instructions_sysvar.key, &sysvar::instructions::id())?;
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    let ix = load_instruction_at_checked(current_index, instructions_sysvar)?;
+    if ix.program_id != crate::ID || !ix.data.starts_with(discriminator) {
+        return Err(ErrorCode::TrailingArgsMismatch.into());
+    }
+    Ok(ix.data.get(args_len..).unwrap_or_default().to_vec())
+}
+
 pub fn split_payer_from_remaining_accounts<'a, 'info>(
     remaining_accounts: &'a [AccountInfo<'info>],
 ) -> (&'a [AccountInfo<'info>], Option<&'a AccountInfo<'info>>) {
@@ -310,6 +745,150 @@ pub fn split_payer_from_remaining_accounts<'a, 'info>(
     }
 }
 
+pub struct RentVault<'a, 'info> {
+    pub info: &'a AccountInfo<'info>,
+    auction_house_key: Pubkey,
+    bump: [u8; 1],
+}
+
+impl<'a, 'info> RentVault<'a, 'info> {
+    pub fn seeds(&self) -> [&[u8]; 4] {
+        [
+            PREFIX.as_bytes(),
+            RENT_VAULT.as_bytes(),
+            self.auction_house_key.as_ref(),
+            &self.bump,
+        ]
+    }
+}
+
+/// Splits the auction house rent vault off the end of remaining accounts (before the optional payer).
+/// The vault only pays for trade state rent when the notary co-signs, so users can't drain it themselves
+pub fn split_rent_vault_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    auction_house_key: &Pubkey,
+    notary_cosign: &NotaryCosign,
+) -> (&'a [AccountInfo<'info>], Option<RentVault<'a, 'info>>) {
+    if !notary_cosign.signed {
+        return (remaining_accounts, None);
+    }
+    if let Some((last, rest)) = remaining_accounts.split_last() {
+        if last.is_signer || last.owner != &system_program::ID || !last.data_is_empty() {
+            return (remaining_accounts, None);
+        }
+        let (vault_key, bump) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                RENT_VAULT.as_bytes(),
+                auction_house_key.as_ref(),
+            ],
+            &crate::ID,
+        );
+        if *last.key == vault_key {
+            return (
+                rest,
+                Some(RentVault {
+                    info: last,
+                    auction_house_key: *auction_house_key,
+                    bump: [bump],
+                }),
+            );
+        }
+    }
+    (remaining_accounts, None)
+}
+
+/// Finds an optional account in remaining accounts by its key, so optional accounts don't
+/// interfere with the positional remaining accounts conventions of each instruction
+pub fn find_remaining_account<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    key: &Pubkey,
+) -> Option<&'a AccountInfo<'info>> {
+    remaining_accounts.iter().find(|ai| ai.key == key)
+}
+
+/// Rejects the transaction if any other top level M2 instruction writes to one of the
+/// guarded accounts, e.g. a cancel + relist of the same trade state around a stale signed fill
+pub fn assert_no_conflicting_instructions(
+    instructions_sysvar: &AccountInfo,
+    guarded_keys: &[&Pubkey],
+) -> Result<()> {
+    assert_keys_equal(instructions_sysvar.key, &sysvar::instructions::id())?;
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    let mut index = 0;
+    // load_instruction_at_checked errors once we walk past the last instruction
+    while let Ok(ix) = load_instruction_at_checked(index, instructions_sysvar) {
+        if index != current_index
+            && ix.program_id == crate::ID
+            && ix
+                .accounts
+                .iter()
+                .any(|meta| meta.is_writable && guarded_keys.contains(&&meta.pubkey))
+        {
+            return Err(ErrorCode::ConflictingInstructionInTransaction.into());
+        }
+        index += 1;
+    }
+    Ok(())
+}
+
+/// Checks that the transaction carries an ed25519 program instruction verifying `signer`'s signature over `message`.
+/// Only the self-contained layout (all offsets pointing into the ed25519 instruction itself) is accepted
+pub fn assert_ed25519_signed(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    // ed25519 instruction data: num_signatures(1), padding(1), then 7 u16 offsets per signature
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+    assert_keys_equal(instructions_sysvar.key, &sysvar::instructions::id())?;
+    let mut index = 0;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions_sysvar) {
+        index += 1;
+        if ix.program_id != ed25519_program::ID
+            || ix.data.len() < OFFSETS_START + OFFSETS_LEN
+            || ix.data[0] != 1
+        {
+            continue;
+        }
+        let offsets = &ix.data[OFFSETS_START..OFFSETS_START + OFFSETS_LEN];
+        let read_u16 = |i: usize| u16::from_le_bytes([offsets[i * 2], offsets[i * 2 + 1]]);
+        let (signature_ix, pubkey_offset, pubkey_ix) =
+            (read_u16(1), read_u16(2) as usize, read_u16(3));
+        let (message_offset, message_size, message_ix) =
+            (read_u16(4) as usize, read_u16(5) as usize, read_u16(6));
+        if signature_ix != u16::MAX || pubkey_ix != u16::MAX || message_ix != u16::MAX {
+            continue;
+        }
+        let pubkey = ix.data.get(pubkey_offset..pubkey_offset + 32);
+        let signed_message = ix.data.get(message_offset..message_offset + message_size);
+        if pubkey == Some(signer.as_ref()) && signed_message == Some(message) {
+            return Ok(());
+        }
+    }
+    Err(ErrorCode::InvalidSignedAuthorization.into())
+}
+
+/// Consumes `nonce` for the wallet, rejecting anything not above the last consumed nonce
+pub fn consume_authorization_nonce(
+    authorization_nonce: &mut AuthorizationNonce,
+    auction_house_key: &Pubkey,
+    wallet: &Pubkey,
+    bump: u8,
+    nonce: u64,
+) -> Result<()> {
+    if authorization_nonce.wallet == Pubkey::default() {
+        authorization_nonce.auction_house_key = *auction_house_key;
+        authorization_nonce.wallet = *wallet;
+        authorization_nonce.bump = bump;
+    } else if nonce <= authorization_nonce.last_nonce {
+        return Err(ErrorCode::StaleAuthorizationNonce.into());
+    }
+    authorization_nonce.last_nonce = nonce;
+    Ok(())
+}
+
 /// Cheap method to just grab mint Pubkey from token account, instead of deserializing entire thing
 #[allow(dead_code)]
 pub fn get_mint_from_token_account(token_account_info: &AccountInfo) -> Result<Pubkey> {
@@ -359,7 +938,7 @@ pub fn create_or_allocate_account_raw<'a>(
         )?;
     }
 
-    let accounts = &[new_account_info.clone()];
+    let accounts = std::slice::from_ref(new_account_info);
     invoke_signed(
         &system_instruction::allocate(new_account_info.key, (*size).try_into().unwrap()),
         accounts,
@@ -383,116 +962,1600 @@ pub fn assert_derivation(program_id: &Pubkey, account: &AccountInfo, path: &[&[u
     Ok(bump)
 }
 
-pub fn try_close_buyer_escrow<'info>(
-    escrow: &AccountInfo<'info>,
-    buyer: &AccountInfo<'info>,
-    system_program: &Program<'info, System>,
-    seeds: &[&[&[u8]]],
-) -> Result<()> {
-    let min_rent = Rent::get()?.minimum_balance(0);
-    let escrow_lamports = escrow.lamports();
-    if escrow_lamports == 0 || escrow_lamports > min_rent {
-        Ok(())
-    } else {
-        anchor_lang::solana_program::program::invoke_signed(
-            &anchor_lang::solana_program::system_instruction::transfer(
-                &escrow.key(),
-                &buyer.key(),
-                escrow_lamports,
-            ),
-            &[
-                escrow.to_account_info(),
-                buyer.to_account_info(),
-                system_program.to_account_info(),
-            ],
-            seeds,
-        )?;
-        Ok(())
+/// Finds the wallet's EscrowConfig in remaining accounts, if the client passed one in
+pub fn get_escrow_config(
+    remaining_accounts: &[AccountInfo],
+    auction_house_key: &Pubkey,
+    wallet: &Pubkey,
+) -> Result<Option<EscrowConfig>> {
+    for ai in remaining_accounts {
+        if ai.owner != &crate::ID
+            || ai.data_len() < EscrowConfig::LEN
+            || ai.try_borrow_data()?[0..8] != EscrowConfig::discriminator()
+        {
+            continue;
+        }
+        // only set_escrow_config can write this account, so the keys in it can be trusted
+        let config = EscrowConfig::try_deserialize(&mut &ai.try_borrow_data()?[..])?;
+        if config.auction_house_key == *auction_house_key && config.wallet == *wallet {
+            return Ok(Some(config));
+        }
     }
+    Ok(None)
 }
 
-pub fn check_programmable(metadata_parsed: &Metadata) -> Result<()> {
-    match metadata_parsed.token_standard {
-        None => return Err(ErrorCode::InvalidTokenStandard.into()),
-        Some(ref t) => {
-            if *t != TokenStandard::ProgrammableNonFungible {
-                return Err(ErrorCode::InvalidTokenStandard.into());
+pub fn get_open_bid_index_address(auction_house_key: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            OPEN_BID_INDEX.as_bytes(),
+            auction_house_key.as_ref(),
+            wallet.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Splits the wallet's open bid index off the end of remaining accounts (before the optional rent vault and payer)
+pub fn split_open_bid_index_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    auction_house_key: &Pubkey,
+    wallet: &Pubkey,
+) -> (
+    &'a [AccountInfo<'info>],
+    Option<(&'a AccountInfo<'info>, u8)>,
+) {
+    if let Some((last, rest)) = remaining_accounts.split_last() {
+        let (index_key, bump) = get_open_bid_index_address(auction_house_key, wallet);
+        if *last.key == index_key {
+            return (rest, Some((last, bump)));
+        }
+    }
+    (remaining_accounts, None)
+}
+
+/// Splits a seller trade state of the token off the end of remaining accounts (before the optional open
+/// bid index, rent vault and payer)
+pub fn split_listing_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    auction_house_key: &Pubkey,
+    token_mint: &Pubkey,
+) -> Result<(&'a [AccountInfo<'info>], Option<SellArgs>)> {
+    if let Some((last, rest)) = remaining_accounts.split_last() {
+        if last.owner == &crate::ID && last.data_len() >= 8 {
+            let is_listing = {
+                let discriminator = &last.try_borrow_data()?[..8];
+                discriminator == SellerTradeState::discriminator()
+                    || discriminator == SellerTradeStateV2::discriminator()
+                    || discriminator == SellerTradeStateV3::discriminator()
+            };
+            if is_listing {
+                let sell_args = SellArgs::from_account_info(last)?;
+                if sell_args.auction_house_key != *auction_house_key {
+                    return Err(ErrorCode::WrongAuctionHouseForState.into());
+                }
+                if sell_args.token_mint != *token_mint {
+                    return Err(ErrorCode::TradeStateMismatch.into());
+                }
+                return Ok((rest, Some(sell_args)));
             }
         }
     }
-    Ok(())
+    Ok((remaining_accounts, None))
 }
 
-pub fn close_account_anchor(info: &AccountInfo, dest: &AccountInfo) -> Result<()> {
-    let curr_lamp = info.lamports();
-    **info.lamports.borrow_mut() = 0;
-    **dest.lamports.borrow_mut() = dest
-        .lamports()
-        .checked_add(curr_lamp)
-        .ok_or(ErrorCode::NumericalOverflow)?;
-    info.try_borrow_mut_data()?[0..8].copy_from_slice(&[0; 8]);
-    Ok(())
+pub fn get_referrer_address(auction_house_key: &Pubkey, referral: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            REFERRER.as_bytes(),
+            auction_house_key.as_ref(),
+            referral.as_ref(),
+        ],
+        &crate::ID,
+    )
+    .0
 }
 
-pub fn get_delegate_info_and_token_state_from_token_record(
-    info: &AccountInfo,
-) -> Result<(Option<Pubkey>, Option<TokenDelegateRole>, TokenState)> {
-    let token_record = TokenRecord::safe_deserialize(&info.data.borrow())?;
-    Ok((
-        token_record.delegate,
-        token_record.delegate_role,
-        token_record.state,
-    ))
+/// Splits the Referrer registration of referral off the end of remaining accounts (before the
+/// optional rent vault and payer)
+pub fn split_referrer_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    auction_house_key: &Pubkey,
+    referral: &Pubkey,
+) -> (&'a [AccountInfo<'info>], Option<&'a AccountInfo<'info>>) {
+    if let Some((last, rest)) = remaining_accounts.split_last() {
+        if *last.key == get_referrer_address(auction_house_key, referral) {
+            return (rest, Some(last));
+        }
+    }
+    (remaining_accounts, None)
 }
 
-pub fn create_or_realloc_seller_trade_state<'a>(
-    sts: &AccountInfo<'a>,
-    payer: &AccountInfo<'a>,
-    sts_seeds: &[&[u8]],
-) -> Result<()> {
-    let rent = Rent::get()?;
-    let required_lamports = rent
-        .minimum_balance(SellerTradeStateV2::LEN)
-        .saturating_sub(sts.lamports());
-    if sts.data_is_empty() {
-        // brand new account, need to create it with correct length
-        invoke_signed(
-            &system_instruction::create_account(
-                payer.key,
-                sts.key,
-                required_lamports,
-                SellerTradeStateV2::LEN as u64,
-                &crate::ID,
-            ),
-            &[payer.clone(), sts.clone()],
-            &[sts_seeds],
-        )?;
+pub fn get_maker_rewards_address(auction_house_key: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            MAKER_REWARDS.as_bytes(),
+            auction_house_key.as_ref(),
+            wallet.as_ref(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
 
-        sts.try_borrow_mut_data()?[..8].copy_from_slice(&SellerTradeStateV2::discriminator());
-        Ok(())
-    } else if sts.data_len() == SellerTradeState::LEN {
-        // old buyer trade state that we want to migrate
-        // zero out original data
-        sts.try_borrow_mut_data()?
-            .copy_from_slice(&[0; SellerTradeState::LEN]);
-        // reallocate new space
-        sts.realloc(SellerTradeStateV2::LEN, true)?;
-        // transfer lamports so become rent exempt
+/// A CoOwnerApproval account and the co-owner acting with it
+pub type CoOwnerAuthority<'a, 'info> = (&'a AccountInfo<'info>, &'a AccountInfo<'info>);
+
+/// Splits the token's CoOwnerApproval and the co-owner signing as seller_authority off the start of
+/// remaining accounts, when the wallet doesn't sign and they are passed. The approval isn't checked
+/// yet, see use_co_owner_approval.
+pub fn split_co_owner_authority_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    wallet: &AccountInfo,
+    token_mint: &Pubkey,
+) -> Result<(
+    &'a [AccountInfo<'info>],
+    Option<CoOwnerAuthority<'a, 'info>>,
+)> {
+    if wallet.is_signer {
+        return Ok((remaining_accounts, None));
+    }
+    let (co_owner_approval_key, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            CO_OWNER_APPROVAL.as_bytes(),
+            wallet.key.as_ref(),
+            token_mint.as_ref(),
+        ],
+        &crate::ID,
+    );
+    let (co_owner_approval, seller_authority, rest) = match remaining_accounts {
+        [approval, seller_authority, rest @ ..] if *approval.key == co_owner_approval_key => {
+            (approval, seller_authority, rest)
+        }
+        _ => return Ok((remaining_accounts, None)),
+    };
+    Ok((rest, Some((co_owner_approval, seller_authority))))
+}
+
+/// Checks that seller_authority signs and enough co-owners approved action_hash in
+/// co_owner_approval, and uses up their approvals
+pub fn use_co_owner_approval(
+    co_owner_approval: &AccountInfo,
+    seller_authority: &AccountInfo,
+    action_hash: &[u8; 32],
+) -> Result<()> {
+    assert_owned_by(co_owner_approval, &crate::ID)?;
+    let mut approval =
+        CoOwnerApproval::try_deserialize(&mut &co_owner_approval.try_borrow_data()?[..])?;
+    if !seller_authority.is_signer || !approval.is_approved(seller_authority.key, action_hash) {
+        return Err(ErrorCode::CoOwnerApprovalMissing.into());
+    }
+    approval.approved = 0;
+    approval.try_serialize(&mut &mut co_owner_approval.try_borrow_mut_data()?[..])
+}
+
+/// Splits the wallet's Guardian and its guardian, which has to sign, off the start of remaining
+/// accounts, when the wallet doesn't sign and they are passed. Returns the guardian if it signed
+/// for the wallet, which only lets it cancel.
+pub fn split_guardian_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    wallet: &AccountInfo,
+) -> Result<(&'a [AccountInfo<'info>], Option<&'a AccountInfo<'info>>)> {
+    if wallet.is_signer {
+        return Ok((remaining_accounts, None));
+    }
+    let (guardian_key, _) = Pubkey::find_program_address(
+        &[PREFIX.as_bytes(), GUARDIAN.as_bytes(), wallet.key.as_ref()],
+        &crate::ID,
+    );
+    let (guardian_account, guardian, rest) = match remaining_accounts {
+        [guardian_account, guardian, rest @ ..] if *guardian_account.key == guardian_key => {
+            (guardian_account, guardian, rest)
+        }
+        _ => return Ok((remaining_accounts, None)),
+    };
+    assert_owned_by(guardian_account, &crate::ID)?;
+    let registered = Guardian::try_deserialize(&mut &guardian_account.try_borrow_data()?[..])?;
+    if !guardian.is_signer || *guardian.key != registered.guardian {
+        return Err(ErrorCode::InvalidGuardian.into());
+    }
+    Ok((rest, Some(guardian)))
+}
+
+/// Splits the seller's MakerRewards off the end of remaining accounts (before the optional referrer,
+/// rent vault and payer), a listing made with it is enrolled in maker rewards
+pub fn split_maker_rewards_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    auction_house_key: &Pubkey,
+    wallet: &Pubkey,
+) -> Result<(&'a [AccountInfo<'info>], bool)> {
+    if let Some((last, rest)) = remaining_accounts.split_last() {
+        if *last.key == get_maker_rewards_address(auction_house_key, wallet) {
+            // only init_maker_rewards creates it, so the seller has opted in
+            assert_owned_by(last, &crate::ID)?;
+            return Ok((rest, true));
+        }
+    }
+    Ok((remaining_accounts, false))
+}
+
+/// Accrues a closing listing to the seller's MakerRewards, which must be passed in if the listing
+/// is enrolled. It accrues until it closes or expires, whichever is first. filled_volume is the
+/// SOL price when a buyer took the listing, 0 otherwise.
+pub fn accrue_maker_rewards(
+    remaining_accounts: &[AccountInfo],
+    auction_house: &Account<AuctionHouse>,
+    sell_args: &SellArgs,
+    filled_volume: u64,
+) -> Result<()> {
+    if !sell_args.maker_rewards {
+        return Ok(());
+    }
+    let maker_rewards = match find_remaining_account(
+        remaining_accounts,
+        &get_maker_rewards_address(&auction_house.key(), &sell_args.seller),
+    ) {
+        Some(ai) => ai,
+        None => return Err(ErrorCode::MissingRemainingAccount.into()),
+    };
+    assert_owned_by(maker_rewards, &crate::ID)?;
+    let mut rewards = MakerRewards::try_deserialize(&mut &maker_rewards.try_borrow_data()?[..])?;
+    let now = Clock::get()?.unix_timestamp;
+    let live_until = match Expiry::from(sell_args.expiry).timestamp() {
+        Some(expiry) => now.min(expiry),
+        None => now,
+    };
+    let listing_seconds = live_until.saturating_sub(sell_args.created_at).max(0) as u64;
+    let accrued = rewards.accrue(auction_house, listing_seconds, filled_volume)?;
+    rewards.try_serialize(&mut &mut maker_rewards.try_borrow_mut_data()?[..])?;
+    msg!(
+        "{{\"maker_rewards_accrued\":{},\"listing_seconds\":{}}}",
+        accrued,
+        listing_seconds
+    );
+    Ok(())
+}
+
+/// The referral key an order stores: the referral if its Referrer registration is passed in and
+/// still attributing, the default pubkey otherwise
+pub fn get_registered_referral(
+    referrer: Option<&AccountInfo>,
+    referral: &Pubkey,
+) -> Result<Pubkey> {
+    let referrer = match referrer {
+        Some(ai) if !ai.data_is_empty() => ai,
+        _ => return Ok(Pubkey::default()),
+    };
+    assert_owned_by(referrer, &crate::ID)?;
+    let registration = Referrer::try_deserialize(&mut &referrer.try_borrow_data()?[..])?;
+    if registration.referral != *referral {
+        return Err(ErrorCode::PublicKeyMismatch.into());
+    }
+    if !registration.is_attributing(Clock::get()?.unix_timestamp) {
+        return Ok(Pubkey::default());
+    }
+    Ok(*referral)
+}
+
+pub fn load_or_create_open_bid_index<'info>(
+    open_bid_index: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    auction_house_key: &Pubkey,
+    wallet: &Pubkey,
+    bump: u8,
+    tracks_all_bids: bool,
+) -> Result<OpenBidIndex> {
+    if !open_bid_index.data_is_empty() {
+        assert_owned_by(open_bid_index, &crate::ID)?;
+        return OpenBidIndex::try_deserialize(&mut &open_bid_index.try_borrow_data()?[..]);
+    }
+    let required_lamports = Rent::get()?
+        .minimum_balance(OpenBidIndex::LEN)
+        .saturating_sub(open_bid_index.lamports());
+    create_or_allocate_account_raw(
+        &crate::ID,
+        open_bid_index,
+        payer,
+        &required_lamports,
+        &OpenBidIndex::LEN,
+        &[
+            PREFIX.as_bytes(),
+            OPEN_BID_INDEX.as_bytes(),
+            auction_house_key.as_ref(),
+            wallet.as_ref(),
+            &[bump],
+        ],
+    )?;
+    Ok(OpenBidIndex {
+        auction_house_key: *auction_house_key,
+        wallet: *wallet,
+        locked_amount: 0,
+        open_bids: 0,
+        bump,
+        tracks_all_bids,
+        bids: vec![],
+    })
+}
+
+/// Serializes the open bid index, growing the account first if the bid list no longer fits
+pub fn save_open_bid_index<'info>(
+    open_bid_index: &AccountInfo<'info>,
+    payer: Option<&AccountInfo<'info>>,
+    index: &OpenBidIndex,
+) -> Result<()> {
+    let space = OpenBidIndex::space(index.bids.len());
+    if space > open_bid_index.data_len() {
+        let payer = payer.ok_or(ErrorCode::NoPayerPresent)?;
+        let required_lamports = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(open_bid_index.lamports());
         if required_lamports > 0 {
             invoke(
+                &system_instruction::transfer(payer.key, open_bid_index.key, required_lamports),
+                &[payer.clone(), open_bid_index.clone()],
+            )?;
+        }
+        open_bid_index.realloc(space, false)?;
+    }
+    index.try_serialize(&mut &mut open_bid_index.try_borrow_mut_data()?[..])
+}
+
+/// Releases a filled or cancelled bid from the buyer's open bid index, which must be passed in for
+/// an indexed bid so its lock doesn't outlive it
+pub fn release_open_bid(
+    remaining_accounts: &[AccountInfo],
+    buyer_trade_state: &Pubkey,
+    bid_args: &BidArgs,
+) -> Result<()> {
+    if !bid_args.indexed {
+        return Ok(());
+    }
+    let (index_key, _) = get_open_bid_index_address(&bid_args.auction_house_key, &bid_args.buyer);
+    let open_bid_index = find_remaining_account(remaining_accounts, &index_key)
+        .ok_or(ErrorCode::MissingRemainingAccount)?;
+    assert_owned_by(open_bid_index, &crate::ID)?;
+    let mut index = OpenBidIndex::try_deserialize(&mut &open_bid_index.try_borrow_data()?[..])?;
+    index.remove_bid(
+        buyer_trade_state,
+        bid_args.locked_cost(),
+        &bid_args.payment_mint,
+    );
+    save_open_bid_index(open_bid_index, None, &index)
+}
+
+pub fn get_escrow_ledger_address(auction_house_key: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            ESCROW_LEDGER.as_bytes(),
+            auction_house_key.as_ref(),
+            wallet.as_ref(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
+
+/// An EscrowLedger account passed in, decoded, for the caller to update and save
+pub type LoadedEscrowLedger<'a, 'info> = (&'a AccountInfo<'info>, EscrowLedger);
+
+/// Splits the wallet's EscrowLedger off the end of remaining accounts
+pub fn split_escrow_ledger_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    auction_house_key: &Pubkey,
+    wallet: &Pubkey,
+) -> Result<(
+    &'a [AccountInfo<'info>],
+    Option<LoadedEscrowLedger<'a, 'info>>,
+)> {
+    if let Some((last, rest)) = remaining_accounts.split_last() {
+        if *last.key == get_escrow_ledger_address(auction_house_key, wallet) {
+            assert_owned_by(last, &crate::ID)?;
+            let ledger = EscrowLedger::try_deserialize(&mut &last.try_borrow_data()?[..])?;
+            return Ok((rest, Some((last, ledger))));
+        }
+    }
+    Ok((remaining_accounts, None))
+}
+
+pub fn save_escrow_ledger(escrow_ledger: &AccountInfo, ledger: &EscrowLedger) -> Result<()> {
+    ledger.try_serialize(&mut &mut escrow_ledger.try_borrow_mut_data()?[..])
+}
+
+/// Unlocks a filled or cancelled bid's full cost in the buyer's EscrowLedger, which must be passed
+/// in if the bid is ledgered
+pub fn release_escrow_ledger_bid(
+    remaining_accounts: &[AccountInfo],
+    bid_args: &BidArgs,
+) -> Result<()> {
+    if !bid_args.ledgered {
+        return Ok(());
+    }
+    let ledger_key = get_escrow_ledger_address(&bid_args.auction_house_key, &bid_args.buyer);
+    let escrow_ledger = find_remaining_account(remaining_accounts, &ledger_key)
+        .ok_or(ErrorCode::MissingRemainingAccount)?;
+    assert_owned_by(escrow_ledger, &crate::ID)?;
+    let mut ledger = EscrowLedger::try_deserialize(&mut &escrow_ledger.try_borrow_data()?[..])?;
+    ledger.unlock(&bid_args.payment_mint, bid_args.locked_cost())?;
+    save_escrow_ledger(escrow_ledger, &ledger)
+}
+
+pub fn get_wallet_limits_address(auction_house_key: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            WALLET_LIMITS.as_bytes(),
+            auction_house_key.as_ref(),
+            wallet.as_ref(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
+
+/// A WalletLimits account passed in, decoded, for the caller to update and save
+pub type LoadedWalletLimits<'a, 'info> = (&'a AccountInfo<'info>, WalletLimits);
+
+/// Decodes the wallet's WalletLimits, None for its uninitialized address, a wallet without limits
+fn load_wallet_limits<'a, 'info>(
+    wallet_limits: &'a AccountInfo<'info>,
+) -> Result<Option<LoadedWalletLimits<'a, 'info>>> {
+    if wallet_limits.data_is_empty() {
+        return Ok(None);
+    }
+    assert_owned_by(wallet_limits, &crate::ID)?;
+    let limits = WalletLimits::try_deserialize(&mut &wallet_limits.try_borrow_data()?[..])?;
+    Ok(Some((wallet_limits, limits)))
+}
+
+/// Splits the wallet's WalletLimits off the end of remaining accounts, required if the auction
+/// house enforces wallet limits
+pub fn split_wallet_limits_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    auction_house_key: &Pubkey,
+    auction_house: &AuctionHouse,
+    wallet: &Pubkey,
+) -> Result<(
+    &'a [AccountInfo<'info>],
+    Option<LoadedWalletLimits<'a, 'info>>,
+)> {
+    if let Some((last, rest)) = remaining_accounts.split_last() {
+        if *last.key == get_wallet_limits_address(auction_house_key, wallet) {
+            return Ok((rest, load_wallet_limits(last)?));
+        }
+    }
+    if auction_house.enforce_wallet_limits {
+        return Err(ErrorCode::WalletLimitsRequired.into());
+    }
+    Ok((remaining_accounts, None))
+}
+
+/// Finds the wallet's WalletLimits anywhere in remaining accounts, required if the auction house
+/// enforces wallet limits
+pub fn find_wallet_limits<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    auction_house_key: &Pubkey,
+    auction_house: &AuctionHouse,
+    wallet: &Pubkey,
+) -> Result<Option<LoadedWalletLimits<'a, 'info>>> {
+    let limits_key = get_wallet_limits_address(auction_house_key, wallet);
+    match find_remaining_account(remaining_accounts, &limits_key) {
+        Some(wallet_limits) => load_wallet_limits(wallet_limits),
+        None if auction_house.enforce_wallet_limits => Err(ErrorCode::WalletLimitsRequired.into()),
+        None => Ok(None),
+    }
+}
+
+pub fn save_wallet_limits(wallet_limits: &AccountInfo, limits: &WalletLimits) -> Result<()> {
+    limits.try_serialize(&mut &mut wallet_limits.try_borrow_mut_data()?[..])
+}
+
+/// Closes a filled or cancelled bid in the buyer's WalletLimits, a fill in SOL adds its price to
+/// the day's buy volume whether or not the bid was placed with them. Limited bids, and every bid
+/// of a house that enforces wallet limits, can't be filled without the buyer's WalletLimits, a
+/// cancel without it leaves the bid counted, which only makes the open bids limit more
+/// conservative
+pub fn release_wallet_limits_bid(
+    remaining_accounts: &[AccountInfo],
+    auction_house: &AuctionHouse,
+    bid_args: &BidArgs,
+    filled_price: Option<u64>,
+) -> Result<()> {
+    match get_released_wallet_limits(remaining_accounts, auction_house, bid_args, filled_price)? {
+        Some((wallet_limits, limits)) => save_wallet_limits(wallet_limits, &limits),
+        None => Ok(()),
+    }
+}
+
+/// The buyer's WalletLimits as release_wallet_limits_bid leaves them, without saving them, so a
+/// fill can be checked against the limits before anything is written
+pub fn get_released_wallet_limits<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    auction_house: &AuctionHouse,
+    bid_args: &BidArgs,
+    filled_price: Option<u64>,
+) -> Result<Option<LoadedWalletLimits<'a, 'info>>> {
+    let limits_key = get_wallet_limits_address(&bid_args.auction_house_key, &bid_args.buyer);
+    let wallet_limits = match find_remaining_account(remaining_accounts, &limits_key) {
+        Some(wallet_limits) if bid_args.limited || filled_price.is_some() => wallet_limits,
+        None if filled_price.is_some()
+            && (bid_args.limited || auction_house.enforce_wallet_limits) =>
+        {
+            return Err(ErrorCode::MissingRemainingAccount.into())
+        }
+        _ => return Ok(None),
+    };
+    let (wallet_limits, mut limits) = match load_wallet_limits(wallet_limits)? {
+        Some(loaded) => loaded,
+        // a limited bid was counted in an initialized WalletLimits
+        None if bid_args.limited => return Err(ErrorCode::MissingRemainingAccount.into()),
+        None => return Ok(None),
+    };
+    if bid_args.limited {
+        limits.close_bid();
+    }
+    if let Some(price) = filled_price.filter(|_| bid_args.payment_mint == Pubkey::default()) {
+        limits.record_buy(price, Clock::get()?.unix_timestamp)?;
+    }
+    Ok(Some((wallet_limits, limits)))
+}
+
+/// Counts the fill of a bid in its BidGroup, which has to be in remaining accounts. The group's
+/// other bids can't fill once it had its max fills, in the same instruction as the fill that
+/// consumed it. That fill also cancels the other bids of the group passed as writable remaining
+/// accounts: their escrow funds are unlocked and their rent goes back to the buyer.
+pub fn fill_bid_group_bid(
+    remaining_accounts: &[AccountInfo],
+    auction_house: &AuctionHouse,
+    bid_args: &BidArgs,
+    buyer_trade_state: &Pubkey,
+    buyer: &AccountInfo,
+) -> Result<()> {
+    if bid_args.bid_group == Pubkey::default() {
+        return Ok(());
+    }
+    let bid_group_info = find_remaining_account(remaining_accounts, &bid_args.bid_group)
+        .ok_or(ErrorCode::MissingRemainingAccount)?;
+    assert_owned_by(bid_group_info, &crate::ID)?;
+    let mut bid_group = BidGroup::try_deserialize(&mut &bid_group_info.try_borrow_data()?[..])?;
+    if bid_group.buyer != bid_args.buyer {
+        return Err(ErrorCode::InvalidBidGroup.into());
+    }
+    let priority = bid_group.fill(buyer_trade_state)?;
+    bid_group.try_serialize(&mut &mut bid_group_info.try_borrow_mut_data()?[..])?;
+
+    let mut cancelled = 0u32;
+    if bid_group.fills == bid_group.max_fills {
+        assert_keys_equal(buyer.key, &bid_group.buyer)?;
+        let siblings = remaining_accounts.iter().filter(|ai| {
+            ai.key != buyer_trade_state
+                && ai.is_writable
+                && ai.owner == &crate::ID
+                && !ai.data_is_empty()
+                && bid_group.bids.contains(ai.key)
+        });
+        for sibling in siblings {
+            let sibling_args = BidArgs::from_account_info(sibling)?;
+            // bids funded by an escrow adapter or a spending allowance need their accounts to be
+            // released, they're left for cancel_buy
+            if sibling_args.bid_group != *bid_group_info.key
+                || sibling_args.escrow_adapter != Pubkey::default()
+                || sibling_args.allowance_funded
+            {
+                continue;
+            }
+            release_open_bid(remaining_accounts, sibling.key, &sibling_args)?;
+            release_escrow_ledger_bid(remaining_accounts, &sibling_args)?;
+            release_wallet_limits_bid(remaining_accounts, auction_house, &sibling_args, None)?;
+            remove_from_mint_market(
+                remaining_accounts,
+                &sibling_args.auction_house_key,
+                &sibling_args.token_mint,
+                &[sibling.key],
+            )?;
+            close_account_anchor(sibling, buyer, true)?;
+            cancelled += 1;
+        }
+    }
+    msg!(
+        "{{\"bid_group\":\"{}\",\"priority\":{},\"fills\":{},\"max_fills\":{},\"cancelled\":{}}}",
+        bid_group_info.key,
+        priority,
+        bid_group.fills,
+        bid_group.max_fills,
+        cancelled
+    );
+    Ok(())
+}
+
+pub fn get_mint_market_address(auction_house_key: &Pubkey, token_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            MINT_MARKET.as_bytes(),
+            auction_house_key.as_ref(),
+            token_mint.as_ref(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
+
+pub fn split_mint_market_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    auction_house_key: &Pubkey,
+    token_mint: &Pubkey,
+) -> (&'a [AccountInfo<'info>], Option<&'a AccountInfo<'info>>) {
+    if let Some((last, rest)) = remaining_accounts.split_last() {
+        if *last.key == get_mint_market_address(auction_house_key, token_mint) {
+            return (rest, Some(last));
+        }
+    }
+    (remaining_accounts, None)
+}
+
+/// Applies update to the token mint's MintMarket, if it is passed in
+pub fn update_mint_market(
+    mint_market: Option<&AccountInfo>,
+    update: impl FnOnce(&mut MintMarket),
+) -> Result<()> {
+    if let Some(mint_market) = mint_market {
+        assert_owned_by(mint_market, &crate::ID)?;
+        let mut market = MintMarket::try_deserialize(&mut &mint_market.try_borrow_data()?[..])?;
+        update(&mut market);
+        market.last_updated_at = Clock::get()?.unix_timestamp;
+        market.try_serialize(&mut &mut mint_market.try_borrow_mut_data()?[..])?;
+    }
+    Ok(())
+}
+
+/// Drops the closed or filled trade states from their mint's MintMarket, if it is anywhere in
+/// remaining accounts
+pub fn remove_from_mint_market(
+    remaining_accounts: &[AccountInfo],
+    auction_house_key: &Pubkey,
+    token_mint: &Pubkey,
+    trade_states: &[&Pubkey],
+) -> Result<()> {
+    update_mint_market(
+        find_remaining_account(
+            remaining_accounts,
+            &get_mint_market_address(auction_house_key, token_mint),
+        ),
+        |market| {
+            for trade_state in trade_states {
+                market.remove(trade_state);
+            }
+        },
+    )
+}
+
+/// A SpendingAllowance account passed in after the treasury's escrow_payment_account, decoded, with
+/// the escrow's bump
+pub struct LoadedSpendingAllowance<'a, 'info> {
+    pub info: &'a AccountInfo<'info>,
+    pub treasury_escrow: &'a AccountInfo<'info>,
+    pub treasury_escrow_bump: u8,
+    pub allowance: SpendingAllowance,
+}
+
+pub fn get_rate_limit_address(auction_house_key: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            RATE_LIMIT.as_bytes(),
+            auction_house_key.as_ref(),
+            wallet.as_ref(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
+
+/// Splits the wallet's RateLimit off the front of remaining accounts (after the optional
+/// program_config) and counts the instruction in it. Rate limited auction houses require it.
+pub fn split_rate_limit_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    auction_house_key: &Pubkey,
+    auction_house: &AuctionHouse,
+    wallet: &Pubkey,
+) -> Result<&'a [AccountInfo<'info>]> {
+    let rate_limit_key = get_rate_limit_address(auction_house_key, wallet);
+    let (rate_limit, rest) = match remaining_accounts.split_first() {
+        Some((first, rest)) if *first.key == rate_limit_key => (Some(first), rest),
+        _ => (None, remaining_accounts),
+    };
+    if !auction_house.is_rate_limited() {
+        return Ok(rest);
+    }
+    let rate_limit = rate_limit.ok_or(ErrorCode::MissingRemainingAccount)?;
+    assert_owned_by(rate_limit, &crate::ID)?;
+    let mut state = RateLimit::try_deserialize(&mut &rate_limit.try_borrow_data()?[..])?;
+    state.record(Clock::get()?.slot, auction_house.max_ixs_per_slot)?;
+    state.try_serialize(&mut &mut rate_limit.try_borrow_mut_data()?[..])?;
+    Ok(rest)
+}
+
+/// Splits a SpendingAllowance for the spender and the escrow_payment_account of its treasury off
+/// the end of remaining accounts. Its key depends on the treasury, so it's recognized by its owner
+/// and discriminator instead.
+pub fn split_spending_allowance_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    auction_house_key: &Pubkey,
+    spender: &Pubkey,
+) -> Result<(
+    &'a [AccountInfo<'info>],
+    Option<LoadedSpendingAllowance<'a, 'info>>,
+)> {
+    let (last, rest) = match remaining_accounts.split_last() {
+        Some((last, rest))
+            if last.owner == &crate::ID
+                && last.data_len() == SpendingAllowance::LEN
+                && get_discriminator(last)? == SpendingAllowance::discriminator() =>
+        {
+            (last, rest)
+        }
+        _ => return Ok((remaining_accounts, None)),
+    };
+    let allowance = SpendingAllowance::try_deserialize(&mut &last.try_borrow_data()?[..])?;
+    if allowance.auction_house_key != *auction_house_key || allowance.spender != *spender {
+        return Err(ErrorCode::SpendingAllowanceMismatch.into());
+    }
+    let (treasury_escrow, rest) = rest
+        .split_last()
+        .ok_or(ErrorCode::MissingRemainingAccount)?;
+    let (treasury_escrow_key, treasury_escrow_bump) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            auction_house_key.as_ref(),
+            allowance.treasury.as_ref(),
+        ],
+        &crate::ID,
+    );
+    assert_keys_equal(&treasury_escrow_key, treasury_escrow.key)?;
+    Ok((
+        rest,
+        Some(LoadedSpendingAllowance {
+            info: last,
+            treasury_escrow,
+            treasury_escrow_bump,
+            allowance,
+        }),
+    ))
+}
+
+/// Tops the spender's escrow up by amount from its treasury's escrow, locked to the bid in the
+/// SpendingAllowance until the bid closes
+pub fn draw_spending_allowance<'info>(
+    loaded: &mut LoadedSpendingAllowance<'_, 'info>,
+    escrow_payment_account: &AccountInfo<'info>,
+    buyer_trade_state: &Pubkey,
+    amount: u64,
+) -> Result<()> {
+    loaded.allowance.draw(buyer_trade_state, amount)?;
+    loaded
+        .allowance
+        .try_serialize(&mut &mut loaded.info.try_borrow_mut_data()?[..])?;
+    invoke_signed(
+        &system_instruction::transfer(
+            loaded.treasury_escrow.key,
+            escrow_payment_account.key,
+            amount,
+        ),
+        &[
+            loaded.treasury_escrow.clone(),
+            escrow_payment_account.clone(),
+        ],
+        &[&[
+            PREFIX.as_bytes(),
+            loaded.allowance.auction_house_key.as_ref(),
+            loaded.allowance.treasury.as_ref(),
+            &[loaded.treasury_escrow_bump],
+        ]],
+    )?;
+    msg!("{{\"allowance_drawn\":{}}}", amount);
+    Ok(())
+}
+
+/// Sends what an allowance funded bid drew and didn't spend back to its treasury's escrow when the
+/// bid closes, used being what its fill took out of the buyer's escrow, 0 if it's cancelled. The
+/// SpendingAllowance holding the bid's lock and its treasury's escrow_payment_account must be
+/// passed in, anywhere in remaining accounts, and so must the buyer's escrow_payment_account if
+/// the instruction doesn't take it.
+pub fn release_spending_allowance_bid<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    buyer_trade_state: &Pubkey,
+    bid_args: &BidArgs,
+    escrow_payment_account: Option<&AccountInfo<'info>>,
+    used: u64,
+) -> Result<()> {
+    if !bid_args.allowance_funded {
+        return Ok(());
+    }
+    let mut found = None;
+    for ai in remaining_accounts {
+        if ai.owner == &crate::ID
+            && ai.data_len() == SpendingAllowance::LEN
+            && get_discriminator(ai)? == SpendingAllowance::discriminator()
+        {
+            let allowance = SpendingAllowance::try_deserialize(&mut &ai.try_borrow_data()?[..])?;
+            if allowance.auction_house_key == bid_args.auction_house_key
+                && allowance.spender == bid_args.buyer
+                && allowance.has_lock(buyer_trade_state)
+            {
+                found = Some((ai, allowance));
+                break;
+            }
+        }
+    }
+    let (allowance_info, mut allowance) = found.ok_or(ErrorCode::MissingRemainingAccount)?;
+    let refund = allowance.release(buyer_trade_state, used)?;
+    allowance.try_serialize(&mut &mut allowance_info.try_borrow_mut_data()?[..])?;
+    if refund == 0 {
+        return Ok(());
+    }
+    let treasury_escrow = find_remaining_account(
+        remaining_accounts,
+        &Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                bid_args.auction_house_key.as_ref(),
+                allowance.treasury.as_ref(),
+            ],
+            &crate::ID,
+        )
+        .0,
+    )
+    .ok_or(ErrorCode::MissingRemainingAccount)?;
+    let (escrow_key, escrow_bump) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            bid_args.auction_house_key.as_ref(),
+            bid_args.buyer.as_ref(),
+        ],
+        &crate::ID,
+    );
+    let escrow_payment_account = match escrow_payment_account {
+        Some(ai) => ai,
+        None => find_remaining_account(remaining_accounts, &escrow_key)
+            .ok_or(ErrorCode::MissingRemainingAccount)?,
+    };
+    assert_keys_equal(escrow_payment_account.key, &escrow_key)?;
+    // the escrow may have paid out more than the bid's draw since it was placed
+    let refund = refund.min(escrow_payment_account.lamports());
+    invoke_signed(
+        &system_instruction::transfer(escrow_payment_account.key, treasury_escrow.key, refund),
+        &[escrow_payment_account.clone(), treasury_escrow.clone()],
+        &[&[
+            PREFIX.as_bytes(),
+            bid_args.auction_house_key.as_ref(),
+            bid_args.buyer.as_ref(),
+            &[escrow_bump],
+        ]],
+    )?;
+    msg!("{{\"allowance_refunded\":{}}}", refund);
+    Ok(())
+}
+
+pub fn get_listing_index_address(auction_house_key: &Pubkey, key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            LISTING_INDEX.as_bytes(),
+            auction_house_key.as_ref(),
+            key.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Keys a listing is indexed under: the seller and, if the token has one, its verified collection
+pub fn get_listing_index_keys(seller: &Pubkey, metadata: Option<&AccountInfo>) -> Vec<Pubkey> {
+    let mut keys = vec![*seller];
+    if let Some(collection) = metadata.and_then(get_verified_collection) {
+        keys.push(collection);
+    }
+    keys
+}
+
+/// The verified collection of the metadata, None if it has none or isn't a metadata account
+pub fn get_verified_collection(metadata: &AccountInfo) -> Option<Pubkey> {
+    Metadata::safe_deserialize(&metadata.data.borrow())
+        .ok()
+        .and_then(|m| m.collection)
+        .filter(|c| c.verified)
+        .map(|c| c.key)
+}
+
+/// Checks a floor conditional bid against the floor its collection's ListingIndex recorded, the
+/// index must be in remaining accounts. An unknown floor, e.g. once the floor listing is gone,
+/// doesn't meet the condition.
+pub fn assert_floor_condition(
+    max_floor_price: u64,
+    collection: Option<Pubkey>,
+    remaining_accounts: &[AccountInfo],
+    auction_house_key: &Pubkey,
+) -> Result<()> {
+    if max_floor_price == 0 {
+        return Ok(());
+    }
+    let collection = collection.ok_or(ErrorCode::FloorConditionNotMet)?;
+    let (address, _) = get_listing_index_address(auction_house_key, &collection);
+    let listing_index = find_remaining_account(remaining_accounts, &address)
+        .ok_or(ErrorCode::MissingRemainingAccount)?;
+    assert_owned_by(listing_index, &crate::ID)?;
+    let index = ListingIndex::try_deserialize(&mut &listing_index.try_borrow_data()?[..])?;
+    if index.floor_listing == Pubkey::default() || index.floor_price > max_floor_price {
+        return Err(ErrorCode::FloorConditionNotMet.into());
+    }
+    Ok(())
+}
+
+/// Splits the listing indexes of `index_keys` off the end of remaining accounts (before the optional rent vault and payer)
+pub fn split_listing_indexes_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    auction_house_key: &Pubkey,
+    index_keys: &[Pubkey],
+) -> (
+    &'a [AccountInfo<'info>],
+    Vec<(&'a AccountInfo<'info>, Pubkey, u8)>,
+) {
+    let candidates: Vec<(Pubkey, Pubkey, u8)> = index_keys
+        .iter()
+        .map(|k| {
+            let (address, bump) = get_listing_index_address(auction_house_key, k);
+            (address, *k, bump)
+        })
+        .collect();
+    let mut remaining_accounts = remaining_accounts;
+    let mut indexes = vec![];
+    while let Some((last, rest)) = remaining_accounts.split_last() {
+        match candidates
+            .iter()
+            .find(|(address, _, _)| address == last.key)
+        {
+            Some((_, key, bump)) => {
+                indexes.push((last, *key, *bump));
+                remaining_accounts = rest;
+            }
+            None => break,
+        }
+    }
+    (remaining_accounts, indexes)
+}
+
+/// The bit of SellerTradeStateV3::listing_indexes for the ListingIndex of key
+fn listing_index_bit(key: &Pubkey, seller: &Pubkey) -> u8 {
+    if key == seller {
+        1
+    } else {
+        2
+    }
+}
+
+/// A listing written as V3, the only layout with room to say which ListingIndexes count it
+fn read_v3_listing(seller_trade_state: &AccountInfo) -> Result<Option<SellerTradeStateV3>> {
+    let data = seller_trade_state.try_borrow_data()?;
+    if data.len() < 8 || data[..8] != SellerTradeStateV3::discriminator() {
+        return Ok(None);
+    }
+    Ok(Some(SellerTradeStateV3::deserialize(&mut &data[8..])?))
+}
+
+/// Records a listing in the listing indexes passed in. A V3 listing is counted once in each index
+/// and remembers it in its listing_indexes, a legacy OCP listing only shows in the recent ring and
+/// the floor. sol_price is the listing's unit price in SOL, None if it isn't listed in SOL or is
+/// hidden.
+pub fn record_listing<'info>(
+    listing_indexes: &[(&AccountInfo<'info>, Pubkey, u8)],
+    payer: &AccountInfo<'info>,
+    auction_house_key: &Pubkey,
+    seller_trade_state: &AccountInfo<'info>,
+    sol_price: Option<u64>,
+) -> Result<()> {
+    let mut listing = read_v3_listing(seller_trade_state)?;
+    for (listing_index, key, bump) in listing_indexes {
+        let mut index = if listing_index.data_is_empty() {
+            let required_lamports = Rent::get()?
+                .minimum_balance(ListingIndex::LEN)
+                .saturating_sub(listing_index.lamports());
+            create_or_allocate_account_raw(
+                &crate::ID,
+                listing_index,
+                payer,
+                &required_lamports,
+                &ListingIndex::LEN,
+                &[
+                    PREFIX.as_bytes(),
+                    LISTING_INDEX.as_bytes(),
+                    auction_house_key.as_ref(),
+                    key.as_ref(),
+                    &[*bump],
+                ],
+            )?;
+            ListingIndex {
+                auction_house_key: *auction_house_key,
+                key: *key,
+                bump: *bump,
+                ..Default::default()
+            }
+        } else {
+            assert_owned_by(listing_index, &crate::ID)?;
+            ListingIndex::try_deserialize(&mut &listing_index.try_borrow_data()?[..])?
+        };
+        let is_new = match listing.as_mut() {
+            Some(listing) => {
+                let bit = listing_index_bit(key, &listing.seller);
+                let is_new = listing.listing_indexes & bit == 0;
+                listing.listing_indexes |= bit;
+                is_new
+            }
+            None => false,
+        };
+        index.add_listing(seller_trade_state.key, is_new, sol_price)?;
+        index.try_serialize(&mut &mut listing_index.try_borrow_mut_data()?[..])?;
+    }
+    if let Some(listing) = listing {
+        let serialized = listing.try_to_vec()?;
+        seller_trade_state.try_borrow_mut_data()?[8..8 + serialized.len()]
+            .copy_from_slice(&serialized);
+    }
+    Ok(())
+}
+
+/// Removes a filled or cancelled listing from whichever of its listing indexes were passed in
+/// remaining accounts, it only lowers the count of the indexes it was counted in
+pub fn release_listing(
+    remaining_accounts: &[AccountInfo],
+    auction_house_key: &Pubkey,
+    index_keys: &[Pubkey],
+    seller_trade_state: &AccountInfo,
+) -> Result<()> {
+    let listing = read_v3_listing(seller_trade_state)?;
+    for key in index_keys {
+        let (address, _) = get_listing_index_address(auction_house_key, key);
+        if let Some(listing_index) = find_remaining_account(remaining_accounts, &address) {
+            assert_owned_by(listing_index, &crate::ID)?;
+            let mut index =
+                ListingIndex::try_deserialize(&mut &listing_index.try_borrow_data()?[..])?;
+            let counted = listing.as_ref().map_or(false, |l| {
+                l.listing_indexes & listing_index_bit(key, &l.seller) != 0
+            });
+            index.remove_listing(seller_trade_state.key, counted);
+            index.try_serialize(&mut &mut listing_index.try_borrow_mut_data()?[..])?;
+        }
+    }
+    Ok(())
+}
+
+/// The bookkeeping of a filled listing, before its trade state is closed: it's removed from its
+/// ListingIndex and MintMarket accounts passed in remaining accounts, along with the trade states
+/// filled with it, and accrues its maker rewards with maker_volume
+#[allow(clippy::too_many_arguments)]
+pub fn settle_filled_listing(
+    remaining_accounts: &[AccountInfo],
+    auction_house: &Account<AuctionHouse>,
+    seller_trade_state: &AccountInfo,
+    sell_args: &SellArgs,
+    metadata: &AccountInfo,
+    token_mint: &Pubkey,
+    filled_with: &[&Pubkey],
+    maker_volume: u64,
+) -> Result<()> {
+    release_listing(
+        remaining_accounts,
+        &auction_house.key(),
+        &get_listing_index_keys(&sell_args.seller, Some(metadata)),
+        seller_trade_state,
+    )?;
+    let mut trade_states = filled_with.to_vec();
+    trade_states.push(seller_trade_state.key);
+    remove_from_mint_market(
+        remaining_accounts,
+        &auction_house.key(),
+        token_mint,
+        &trade_states,
+    )?;
+    accrue_maker_rewards(remaining_accounts, auction_house, sell_args, maker_volume)
+}
+
+pub fn try_close_buyer_escrow<'info>(
+    escrow: &AccountInfo<'info>,
+    buyer: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    escrow_config: Option<&EscrowConfig>,
+    seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let min_rent = Rent::get()?.minimum_balance(0);
+    let escrow_lamports = escrow.lamports();
+    // anything below min_rent has to be closed, the runtime doesn't allow non rent exempt escrows
+    let should_close = match escrow_config {
+        None => escrow_lamports <= min_rent,
+        Some(config) if config.keep_open => escrow_lamports < min_rent,
+        Some(config) => escrow_lamports < cmp::max(min_rent, config.min_resident_balance),
+    };
+    if escrow_lamports == 0 || !should_close {
+        Ok(())
+    } else {
+        anchor_lang::solana_program::program::invoke_signed(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                &escrow.key(),
+                &buyer.key(),
+                escrow_lamports,
+            ),
+            &[
+                escrow.to_account_info(),
+                buyer.to_account_info(),
+                system_program.to_account_info(),
+            ],
+            seeds,
+        )?;
+        Ok(())
+    }
+}
+
+/// The flavor of listing flow, sell/cancel_sell, mip1_* or ocp_*, a token goes through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListingFlow {
+    Legacy,
+    Mip1,
+    Ocp,
+}
+
+impl ListingFlow {
+    /// The GatedInstruction a deployment retires the flow with, if any
+    pub fn gate(self) -> Option<GatedInstruction> {
+        match self {
+            ListingFlow::Legacy => Some(GatedInstruction::Sell),
+            ListingFlow::Mip1 | ListingFlow::Ocp => None,
+        }
+    }
+}
+
+fn listing_flow(token_standard: Option<&TokenStandard>, is_ocp: bool) -> ListingFlow {
+    if is_ocp {
+        ListingFlow::Ocp
+    } else if matches!(token_standard, Some(TokenStandard::ProgrammableNonFungible)) {
+        ListingFlow::Mip1
+    } else {
+        ListingFlow::Legacy
+    }
+}
+
+/// Picks the listing flow of token_mint from its metadata, which may be empty, and the OCP mint
+/// state, an OCP token's is expected among remaining_accounts.
+pub fn get_listing_flow(
+    token_mint: &Pubkey,
+    metadata: &AccountInfo,
+    remaining_accounts: &[AccountInfo],
+) -> Result<ListingFlow> {
+    let token_standard = if metadata.data_is_empty() {
+        None
+    } else {
+        Metadata::safe_deserialize(&metadata.data.borrow())?.token_standard
+    };
+    let (ocp_mint_state, _) = Pubkey::find_program_address(
+        &[MintState::SEED.as_bytes(), token_mint.as_ref()],
+        &open_creator_protocol::id(),
+    );
+    let is_ocp = find_remaining_account(remaining_accounts, &ocp_mint_state)
+        .map_or(false, |mint_state| !mint_state.data_is_empty());
+    Ok(listing_flow(token_standard.as_ref(), is_ocp))
+}
+
+pub fn check_programmable(metadata_parsed: &Metadata) -> Result<()> {
+    match metadata_parsed.token_standard {
+        None => return Err(ErrorCode::InvalidTokenStandard.into()),
+        Some(ref t) => {
+            if *t != TokenStandard::ProgrammableNonFungible {
+                return Err(ErrorCode::InvalidTokenStandard.into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Freezes or thaws a token account program_as_signer is the delegate of, through Token Metadata
+/// as the master edition holds the mint's freeze authority
+#[allow(clippy::too_many_arguments)]
+pub fn set_delegated_account_frozen<'info>(
+    freeze: bool,
+    program_as_signer: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    edition: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    token_metadata_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    assert_keys_equal(token_metadata_program.key, &mpl_token_metadata::ID)?;
+    assert_keys_equal(edition.key, &MasterEdition::find_pda(mint.key).0)?;
+    if freeze {
+        FreezeDelegatedAccountCpi::new(
+            token_metadata_program,
+            FreezeDelegatedAccountCpiAccounts {
+                delegate: program_as_signer,
+                token_account,
+                edition,
+                mint,
+                token_program,
+            },
+        )
+        .invoke_signed(signer_seeds)?;
+    } else {
+        ThawDelegatedAccountCpi::new(
+            token_metadata_program,
+            ThawDelegatedAccountCpiAccounts {
+                delegate: program_as_signer,
+                token_account,
+                edition,
+                mint,
+                token_program,
+            },
+        )
+        .invoke_signed(signer_seeds)?;
+    }
+    Ok(())
+}
+
+/// Finds the master edition and the Token Metadata program a frozen listing is thawed with
+pub fn get_frozen_listing_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    mint: &Pubkey,
+) -> Result<(&'a AccountInfo<'info>, &'a AccountInfo<'info>)> {
+    let edition = find_remaining_account(remaining_accounts, &MasterEdition::find_pda(mint).0)
+        .ok_or(ErrorCode::MissingRemainingAccount)?;
+    let token_metadata_program =
+        find_remaining_account(remaining_accounts, &mpl_token_metadata::ID)
+            .ok_or(ErrorCode::MissingRemainingAccount)?;
+    Ok((edition, token_metadata_program))
+}
+
+/// Closes info into dest. Zeroing the discriminator is enough for the runtime to drop the account
+/// at the end of the transaction, but until then lamports sent back to it revive it with its old
+/// body. With realloc_to_zero the body goes too and the account is handed back to the system
+/// program, as Anchor's close does, so a revived account is an empty system account and Anchor
+/// doesn't write a typed account back over it on exit.
+pub fn close_account_anchor(
+    info: &AccountInfo,
+    dest: &AccountInfo,
+    realloc_to_zero: bool,
+) -> Result<()> {
+    let curr_lamp = info.lamports();
+    **info.lamports.borrow_mut() = 0;
+    **dest.lamports.borrow_mut() = dest
+        .lamports()
+        .checked_add(curr_lamp)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+    info.try_borrow_mut_data()?[0..8].copy_from_slice(&[0; 8]);
+    if realloc_to_zero {
+        info.realloc(0, false)?;
+        info.assign(&system_program::ID);
+    }
+    Ok(())
+}
+
+pub fn get_delegate_info_and_token_state_from_token_record(
+    info: &AccountInfo,
+) -> Result<(Option<Pubkey>, Option<TokenDelegateRole>, TokenState)> {
+    let token_record = TokenRecord::safe_deserialize(&info.data.borrow())?;
+    Ok((
+        token_record.delegate,
+        token_record.delegate_role,
+        token_record.state,
+    ))
+}
+
+/// The account the NFT of a fill goes to: the buyer, or the receipt owner the buyer named in
+/// the bid, e.g. the user an aggregator's PDA is buying for
+pub fn get_receipt_owner<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    buyer: &AccountInfo<'info>,
+    bid_args: &BidArgs,
+) -> Result<AccountInfo<'info>> {
+    if bid_args.receipt_owner == Pubkey::default() || bid_args.receipt_owner == *buyer.key {
+        return Ok(buyer.clone());
+    }
+    find_remaining_account(remaining_accounts, &bid_args.receipt_owner)
+        .cloned()
+        .ok_or(ErrorCode::MissingRemainingAccount.into())
+}
+
+/// Checks that the bid, the listing and, for SPL, the payment mint account passed first in
+/// remaining accounts all use the payment mint the caller expects to settle in
+pub fn assert_expected_payment_mint(
+    expected_payment_mint: &Pubkey,
+    bid_args: &BidArgs,
+    sell_args: &SellArgs,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    if bid_args.payment_mint != *expected_payment_mint
+        || sell_args.price_for(expected_payment_mint).is_none()
+    {
+        return Err(ErrorCode::PaymentMintMismatch.into());
+    }
+    if *expected_payment_mint != Pubkey::default() {
+        let payment_mint = remaining_accounts
+            .first()
+            .ok_or(ErrorCode::MissingRemainingAccount)?;
+        if payment_mint.key != expected_payment_mint {
+            return Err(ErrorCode::PaymentMintMismatch.into());
+        }
+    }
+    Ok(())
+}
+
+/// Checks that a bid paying buyer_creator_royalty_bp of the creator royalty may fill a listing
+/// with royalty_mode, which a bid paying less than all of it can only if the listing leaves the
+/// royalty to the bid and the auction house doesn't enforce it
+pub fn assert_bid_royalty_allowed(
+    auction_house: &AuctionHouse,
+    buyer_creator_royalty_bp: u16,
+    royalty_mode: RoyaltyMode,
+) -> Result<()> {
+    if buyer_creator_royalty_bp >= 10_000 {
+        return Ok(());
+    }
+    if auction_house.enforce_royalties {
+        return Err(ErrorCode::AuctionHouseEnforcesRoyalty.into());
+    }
+    if royalty_mode == RoyaltyMode::Full {
+        return Err(ErrorCode::ListingRequiresFullRoyalty.into());
+    }
+    Ok(())
+}
+
+pub fn get_attestation_address(auction_house_key: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            ATTESTATION.as_bytes(),
+            auction_house_key.as_ref(),
+            wallet.as_ref(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
+
+/// Checks that the buyer may fill a listing that requires an attestation, with its Attestation
+/// found among remaining accounts
+pub fn assert_buyer_attested(
+    auction_house: &Account<AuctionHouse>,
+    sell_args: &SellArgs,
+    buyer: &Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    if !sell_args.requires_attestation {
+        return Ok(());
+    }
+    let attestation_key = get_attestation_address(&auction_house.key(), buyer);
+    let attestation = find_remaining_account(remaining_accounts, &attestation_key)
+        .ok_or(ErrorCode::BuyerNotAttested)?;
+    assert_owned_by(attestation, &crate::ID)?;
+    let attestation = Attestation::try_deserialize(&mut &attestation.try_borrow_data()?[..])?;
+    if !attestation.is_valid(auction_house, Clock::get()?.unix_timestamp) {
+        return Err(ErrorCode::BuyerNotAttested.into());
+    }
+    Ok(())
+}
+
+/// Echoes the order memos of a fill, if either side set one, as hex
+pub fn log_order_memos(bid_args: &BidArgs, sell_args: &SellArgs) {
+    if bid_args.order_memo == [0; 32] && sell_args.order_memo == [0; 32] {
+        return;
+    }
+    let to_hex =
+        |memo: &[u8; 32]| -> String { memo.iter().map(|b| format!("{:02x}", b)).collect() };
+    msg!(
+        "{{\"buyer_order_memo\":\"{}\",\"seller_order_memo\":\"{}\"}}",
+        to_hex(&bid_args.order_memo),
+        to_hex(&sell_args.order_memo)
+    );
+}
+
+/// The first 8 bytes of an account, an account too short to hold them has no valid discriminator
+pub fn get_discriminator(ai: &AccountInfo) -> Result<[u8; 8]> {
+    let mut discriminator = [0; 8];
+    discriminator.copy_from_slice(
+        ai.try_borrow_data()?
+            .get(..8)
+            .ok_or(ErrorCode::InvalidDiscriminator)?,
+    );
+    Ok(discriminator)
+}
+
+// Makes room for a v3 listing, which write_seller_trade_state writes over it. The discriminator is
+// only written there, after the listing, so an older listing stays readable as what it was until
+// then and a new trade state isn't a listing before it's written.
+pub fn create_or_realloc_seller_trade_state<'a>(
+    sts: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    payer_seeds: Option<&[&[u8]]>,
+    sts_seeds: &[&[u8]],
+) -> Result<()> {
+    let mut signer_seeds = vec![sts_seeds];
+    if let Some(payer_seeds) = payer_seeds {
+        signer_seeds.push(payer_seeds);
+    }
+    let rent = Rent::get()?;
+    let required_lamports = rent
+        .minimum_balance(SellerTradeStateV3::LEN)
+        .saturating_sub(sts.lamports());
+    if sts.data_is_empty() {
+        // brand new account, need to create it with correct length
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                sts.key,
+                required_lamports,
+                SellerTradeStateV3::LEN as u64,
+                &crate::ID,
+            ),
+            &[payer.clone(), sts.clone()],
+            &signer_seeds,
+        )?;
+        Ok(())
+    } else if sts.data_len() == SellerTradeState::LEN {
+        // old seller trade state that we want to migrate, the v3 body written over it is longer
+        // than the old one, so nothing of it is left once write_seller_trade_state is done
+        sts.realloc(SellerTradeStateV3::LEN, true)?;
+        // transfer lamports so become rent exempt
+        if required_lamports > 0 {
+            invoke_signed(
                 &system_instruction::transfer(payer.key, sts.key, required_lamports),
                 &[payer.clone(), sts.clone()],
+                &signer_seeds[1..],
             )?;
         }
-
-        // write discriminator
-        sts.try_borrow_mut_data()?[0..8].copy_from_slice(&SellerTradeStateV2::discriminator());
         Ok(())
-    } else if sts.try_borrow_data()?[0..8] == SellerTradeStateV2::discriminator() {
+    } else if sts.data_len() != SellerTradeStateV3::LEN
+        && sts.data_len() != SellerTradeStateV3::LEN + SellerTradeStateV3::ALT_PRICES_LEN
+    {
+        // v2 and v3 have the same length, only v3 listings get the alt prices tail, anything else
+        // was not written by this program
+        Err(ErrorCode::InvalidTradeStateLength.into())
+    } else if is_half_migrated_seller_trade_state(sts)? {
+        Err(ErrorCode::HalfMigratedTradeState.into())
+    } else if get_discriminator(sts)? == SellerTradeStateV3::discriminator()
+        || get_discriminator(sts)? == SellerTradeStateV2::discriminator()
+            && sts.data_len() == SellerTradeStateV3::LEN
+    {
+        // same length, the v3 fields of a v2 listing live in its padding
         Ok(())
     } else {
-        Err(ErrorCode::InvalidAccountState.into())
+        Err(ErrorCode::InvalidDiscriminator.into())
+    }
+}
+
+/// A v3 sized seller trade state that older versions of create_or_realloc_seller_trade_state left
+/// between zeroing it and writing the listing: without a discriminator, or with the v3 one over an
+/// empty body. No instruction can use it, recover_seller_trade_state closes it.
+pub fn is_half_migrated_seller_trade_state(sts: &AccountInfo) -> Result<bool> {
+    let discriminator = get_discriminator(sts)?;
+    if discriminator == [0; 8] {
+        return Ok(true);
     }
+    if discriminator != SellerTradeStateV3::discriminator() {
+        return Ok(false);
+    }
+    let sts = SellerTradeStateV3::try_deserialize(&mut &sts.try_borrow_data()?[..])?;
+    Ok(sts.seller == Pubkey::default())
+}
+
+// Writes the listing over a trade state prepared by create_or_realloc_seller_trade_state,
+// carrying over created_at and maker rewards enrollment, the origin and order memo when an
+// update leaves them out, and the list-time royalty snapshot while the listing stays locked. Alt
+// prices are cleared, a listing only keeps those it is written with by write_seller_alt_prices.
+pub fn write_seller_trade_state(
+    sts: &AccountInfo,
+    mut state: SellerTradeStateV3,
+) -> Result<SellerTradeStateV3> {
+    let previous = match get_discriminator(sts)? {
+        // a v2 listing is read as v3, the v3 fields are zeroes in its padding
+        d if d == SellerTradeStateV3::discriminator()
+            || d == SellerTradeStateV2::discriminator() =>
+        {
+            SellerTradeStateV3::deserialize(&mut &sts.try_borrow_data()?[8..])?
+        }
+        // a new listing, or a legacy one create_or_realloc_seller_trade_state made room for
+        _ => SellerTradeStateV3::default(),
+    };
+    if state.auto_relist_price_bp > 10000 {
+        return Err(ErrorCode::InvalidBasisPoints.into());
+    }
+    // the loan program is repaid in SOL out of proceeds paid to the seller's wallet
+    if state.repay_loan && (state.payment_mint != Pubkey::default() || state.proceeds_as_wsol) {
+        return Err(ErrorCode::LoanRepaymentNotSupported.into());
+    }
+    let now = Clock::get()?.unix_timestamp;
+    state.created_at = if previous.created_at == 0 {
+        now
+    } else {
+        previous.created_at
+    };
+    state.last_updated_at = now;
+    if state.origin == [0; 8] {
+        state.origin = previous.origin;
+    }
+    if state.order_memo == [0; 32] {
+        state.order_memo = previous.order_memo;
+    }
+    state.maker_rewards |= previous.maker_rewards;
+    state.listing_indexes |= previous.listing_indexes;
+    if state.royalty_lock && previous.royalty_lock {
+        state.royalty_bp_snapshot = previous.royalty_bp_snapshot;
+    }
+    let serialized = state.try_to_vec()?;
+    let mut data = sts.try_borrow_mut_data()?;
+    data.get_mut(8..8 + serialized.len())
+        .ok_or(ErrorCode::InvalidTradeStateLength)?
+        .copy_from_slice(&serialized);
+    // clears the padding, of whatever an older version left in it, and the alt prices
+    data[8 + serialized.len()..].fill(0);
+    // the discriminator goes last, the account only reads as a v3 listing once it holds one
+    data[..8].copy_from_slice(&SellerTradeStateV3::discriminator());
+    drop(data);
+    crate::strict_invariant!(crate::utils::assert_seller_trade_state_consistent(
+        sts, &state
+    ));
+    Ok(state)
+}
+
+/// Checks the alt prices of a listing in payment_mint: each used one is a valid price in SOL or
+/// an accepted payment mint, and no payment mint is priced twice
+pub fn assert_alt_prices(
+    alt_prices: &[AltPrice],
+    payment_mint: &Pubkey,
+    program_config: Option<&ProgramConfig>,
+) -> Result<()> {
+    let used: Vec<&AltPrice> = alt_prices.iter().filter(|p| p.price != 0).collect();
+    for (i, alt_price) in used.iter().enumerate() {
+        if alt_price.price > MAX_PRICE {
+            return Err(ErrorCode::InvalidPrice.into());
+        }
+        let mint = &alt_price.payment_mint;
+        let is_valid = *mint == Pubkey::default()
+            || VALID_PAYMENT_MINTS.contains(mint)
+            || program_config.map_or(false, |c| c.is_payment_mint(mint));
+        if !is_valid || mint == payment_mint || used[..i].iter().any(|p| p.payment_mint == *mint) {
+            return Err(ErrorCode::InvalidTokenMint.into());
+        }
+    }
+    Ok(())
+}
+
+/// Stores the alt prices after a listing written by write_seller_trade_state, reallocating the
+/// trade state for them on first use. The payer funds the extra rent, like it does for the sts.
+pub fn write_seller_alt_prices<'a>(
+    sts: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    payer_seeds: Option<&[&[u8]]>,
+    alt_prices: &[AltPrice; MAX_ALT_PRICES],
+) -> Result<()> {
+    if alt_prices.iter().all(|p| p.price == 0) {
+        return Ok(());
+    }
+    let len = SellerTradeStateV3::LEN + SellerTradeStateV3::ALT_PRICES_LEN;
+    if sts.data_len() != len {
+        let required_lamports = Rent::get()?
+            .minimum_balance(len)
+            .saturating_sub(sts.lamports());
+        if required_lamports > 0 {
+            invoke_signed(
+                &system_instruction::transfer(payer.key, sts.key, required_lamports),
+                &[payer.clone(), sts.clone()],
+                &payer_seeds.into_iter().collect::<Vec<_>>(),
+            )?;
+        }
+        sts.realloc(len, true)?;
+    }
+    let serialized = alt_prices.try_to_vec()?;
+    sts.try_borrow_mut_data()?[SellerTradeStateV3::LEN..len].copy_from_slice(&serialized);
+    Ok(())
 }
 
 #[macro_export]
@@ -502,14 +2565,32 @@ macro_rules! index_ra {
     };
 }
 
+/// Runs a check from utils::invariants only in strict-invariants builds, mainnet builds don't pay
+/// its CUs
+#[macro_export]
+macro_rules! strict_invariant {
+    ($check:expr) => {
+        #[cfg(feature = "strict-invariants")]
+        $check?;
+    };
+}
+
+/// Makes bts a BuyerTradeStateV3 with its discriminator written, ready for the caller to write
+/// the body. The steps are ordered so the account is never left resized without its rent or
+/// resized without a discriminator: fund, then realloc, then write the discriminator.
 pub fn create_or_realloc_buyer_trade_state<'a>(
     bts: &AccountInfo<'a>,
     payer: &AccountInfo<'a>,
+    payer_seeds: Option<&[&[u8]]>,
     bts_seeds: &[&[u8]],
 ) -> Result<()> {
+    let mut signer_seeds = vec![bts_seeds];
+    if let Some(payer_seeds) = payer_seeds {
+        signer_seeds.push(payer_seeds);
+    }
     let rent = Rent::get()?;
     let required_lamports = rent
-        .minimum_balance(BuyerTradeStateV2::LEN)
+        .minimum_balance(BuyerTradeStateV3::LEN)
         .saturating_sub(bts.lamports());
     if bts.data_is_empty() {
         // brand new account, need to create it with correct length
@@ -518,43 +2599,106 @@ pub fn create_or_realloc_buyer_trade_state<'a>(
                 payer.key,
                 bts.key,
                 required_lamports,
-                BuyerTradeStateV2::LEN as u64,
+                BuyerTradeStateV3::LEN as u64,
                 &crate::ID,
             ),
             &[payer.clone(), bts.clone()],
-            &[bts_seeds],
+            &signer_seeds,
         )?;
-
-        bts.try_borrow_mut_data()?[..8].copy_from_slice(&BuyerTradeStateV2::discriminator());
-        Ok(())
-    } else if bts.data_len() == BuyerTradeState::LEN {
-        // old buyer trade state that we want to migrate
-        // zero out original data
-        bts.try_borrow_mut_data()?
-            .copy_from_slice(&[0; BuyerTradeState::LEN]);
-        // reallocate new space
-        bts.realloc(BuyerTradeStateV2::LEN, true)?;
-        // transfer lamports so become rent exempt
+    } else if bts.data_len() == BuyerTradeState::LEN
+        || bts.data_len() == BuyerTradeStateV2::LEN
+            && get_discriminator(bts)? == BuyerTradeStateV2::discriminator()
+    {
+        // old buyer trade state that we want to migrate, fund it before it grows
         if required_lamports > 0 {
-            invoke(
+            invoke_signed(
                 &system_instruction::transfer(payer.key, bts.key, required_lamports),
                 &[payer.clone(), bts.clone()],
+                &signer_seeds[1..],
             )?;
         }
-
-        // write discriminator
-        bts.try_borrow_mut_data()?[0..8].copy_from_slice(&BuyerTradeStateV2::discriminator());
-        Ok(())
-    } else if bts.try_borrow_data()?[0..8] == BuyerTradeStateV2::discriminator() {
-        Ok(())
+        bts.realloc(BuyerTradeStateV3::LEN, true)?;
+        // the old body is not carried over, the caller writes the new one
+        bts.try_borrow_mut_data()?.fill(0);
+    } else if get_discriminator(bts)? == BuyerTradeStateV3::discriminator() {
+        return assert_buyer_trade_state_ready(bts, &rent);
+    } else if get_discriminator(bts)? == [0; 8] {
+        return Err(ErrorCode::StrandedTradeState.into());
     } else {
-        Err(ErrorCode::InvalidAccountState.into())
+        return Err(ErrorCode::InvalidDiscriminator.into());
+    }
+
+    bts.try_borrow_mut_data()?[0..8].copy_from_slice(&BuyerTradeStateV3::discriminator());
+    assert_buyer_trade_state_ready(bts, &rent)
+}
+
+/// Whether a trade state was closed but got lamports sent back to it: closed with
+/// close_account_anchor's realloc_to_zero it's an empty system account, closed without it, or
+/// stranded, the program still owns it with a zeroed discriminator. Either way no instruction can
+/// read it, defund_zombie_state drains it.
+pub fn is_zombie_trade_state(info: &AccountInfo) -> Result<bool> {
+    if info.lamports() == 0 {
+        // closed earlier in this transaction, the runtime drops it at the end
+        return Ok(false);
+    }
+    if info.data_is_empty() {
+        return Ok(info.owner == &system_program::ID);
     }
+    Ok(info.owner == &crate::ID && get_discriminator(info)? == [0; 8])
+}
+
+pub fn assert_not_zombie_trade_state(info: &AccountInfo) -> Result<()> {
+    if is_zombie_trade_state(info)? {
+        return Err(ErrorCode::ZombieTradeState.into());
+    }
+    Ok(())
+}
+
+fn assert_buyer_trade_state_ready(bts: &AccountInfo, rent: &Rent) -> Result<()> {
+    if bts.owner != &crate::ID
+        || bts.data_len() != BuyerTradeStateV3::LEN
+        || !rent.is_exempt(bts.lamports(), bts.data_len())
+    {
+        return Err(ErrorCode::StrandedTradeState.into());
+    }
+    if get_discriminator(bts)? != BuyerTradeStateV3::discriminator() {
+        return Err(ErrorCode::InvalidDiscriminator.into());
+    }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::{
+        MAX_BID_GROUP_BIDS, MAX_CONFIG_PAYMENT_MINTS, MAX_CO_OWNERS, MAX_NOTARIES,
+    };
+    use spl_associated_token_account::get_associated_token_address;
+
+    #[test]
+    fn no_failure_returns_the_generic_invalid_account_state() {
+        let generic = concat!("ErrorCode::", "InvalidAccountState");
+        let mut dirs = vec![std::path::PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src"
+        ))];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if path.extension() == Some(std::ffi::OsStr::new("rs")) {
+                    let source = std::fs::read_to_string(&path).unwrap();
+                    assert!(
+                        !source.contains(generic),
+                        "{} returns {}, give the failure its own ErrorCode",
+                        path.display(),
+                        generic
+                    );
+                }
+            }
+        }
+    }
 
     #[test]
     fn assert_keys_equal_returns_ok_when_keys_are_equal() -> Result<()> {
@@ -588,11 +2732,48 @@ mod tests {
         let mut lamports: u64 = 1;
         let owner = Pubkey::new_unique();
         let spl_token_account = spl_token::state::Account {
-            mint: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            owner,
+            amount: 1,
+            delegate: COption::None,
+            state: spl_token::state::AccountState::Frozen,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        };
+
+        spl_token::state::Account::pack(spl_token_account, &mut buffer)
+            .expect("Could not pack SPL token account into buffer");
+
+        let account_info = AccountInfo::new(
+            &owner,
+            false,
+            false,
+            &mut lamports,
+            &mut buffer,
+            &owner,
+            false,
+            4,
+        );
+
+        match assert_initialized::<spl_token::state::Account>(&account_info) {
+            Ok(result) => assert_eq!(result, spl_token_account),
+            _ => panic!("expected Ok(spl_token_account)"),
+        }
+    }
+
+    #[test]
+    fn assert_is_ata_returns_ok_when_account_is_ata() -> Result<()> {
+        let mut buffer = vec![0; spl_token::state::Account::get_packed_len()];
+        let mut lamports: u64 = 1;
+        let owner = spl_token::id();
+        let mint = Pubkey::new_unique();
+        let spl_token_account = spl_token::state::Account {
+            mint,
             owner,
             amount: 1,
             delegate: COption::None,
-            state: spl_token::state::AccountState::Frozen,
+            state: spl_token::state::AccountState::Initialized,
             is_native: COption::None,
             delegated_amount: 0,
             close_authority: COption::None,
@@ -601,8 +2782,9 @@ mod tests {
         spl_token::state::Account::pack(spl_token_account, &mut buffer)
             .expect("Could not pack SPL token account into buffer");
 
+        let key = get_associated_token_address(&owner, &mint);
         let account_info = AccountInfo::new(
-            &owner,
+            &key,
             false,
             false,
             &mut lamports,
@@ -612,21 +2794,19 @@ mod tests {
             4,
         );
 
-        match assert_initialized::<spl_token::state::Account>(&account_info) {
-            Ok(result) => assert_eq!(result, spl_token_account),
-            _ => panic!("expected Ok(spl_token_account)"),
-        }
+        assert_is_ata(&account_info, &owner, &mint, &owner).map(|_| ())
     }
 
     #[test]
-    fn assert_is_ata_returns_ok_when_account_is_ata() -> Result<()> {
-        let mut buffer = vec![0; spl_token::state::Account::get_packed_len()];
+    fn assert_is_ata_returns_ok_when_account_is_token_2022_ata_with_extensions() -> Result<()> {
+        // base account + account type + immutable owner extension header
+        let mut buffer = vec![0; spl_token::state::Account::get_packed_len() + 5];
         let mut lamports: u64 = 1;
-        let owner = spl_token::id();
+        let wallet = Pubkey::new_unique();
         let mint = Pubkey::new_unique();
         let spl_token_account = spl_token::state::Account {
             mint,
-            owner,
+            owner: wallet,
             amount: 1,
             delegate: COption::None,
             state: spl_token::state::AccountState::Initialized,
@@ -635,22 +2815,24 @@ mod tests {
             close_authority: COption::None,
         };
 
-        spl_token::state::Account::pack(spl_token_account, &mut buffer)
-            .expect("Could not pack SPL token account into buffer");
+        spl_token::state::Account::pack_into_slice(
+            &spl_token_account,
+            &mut buffer[..spl_token::state::Account::get_packed_len()],
+        );
 
-        let key = get_associated_token_address(&owner, &mint);
+        let key = get_associated_token_address_with_program_id(&wallet, &mint, &token_2022::ID);
         let account_info = AccountInfo::new(
             &key,
             false,
             false,
             &mut lamports,
             &mut buffer,
-            &owner,
+            &token_2022::ID,
             false,
             4,
         );
 
-        assert_is_ata(&account_info, &owner, &mint, &owner).map(|_| ())
+        assert_is_ata(&account_info, &wallet, &mint, &wallet).map(|_| ())
     }
 
     #[test]
@@ -727,4 +2909,1147 @@ mod tests {
             _ => panic!("expected Ok(balance)"),
         }
     }
+
+    #[test]
+    fn assert_alt_prices_rejects_the_listing_mint_and_duplicates() {
+        let usdc = VALID_PAYMENT_MINTS[0];
+        let alt_price = |payment_mint, price| AltPrice {
+            payment_mint,
+            price,
+        };
+        let sol = Pubkey::default();
+
+        assert!(assert_alt_prices(
+            &[alt_price(usdc, 10), alt_price(usdc, 0), AltPrice::default()],
+            &sol,
+            None
+        )
+        .is_ok());
+        assert!(assert_alt_prices(&[alt_price(sol, 10)], &usdc, None).is_ok());
+        assert!(assert_alt_prices(&[alt_price(usdc, 10)], &usdc, None).is_err());
+        assert!(
+            assert_alt_prices(&[alt_price(usdc, 10), alt_price(usdc, 20)], &sol, None).is_err()
+        );
+        assert!(assert_alt_prices(&[alt_price(Pubkey::new_unique(), 10)], &sol, None).is_err());
+        assert!(assert_alt_prices(&[alt_price(usdc, MAX_PRICE + 1)], &sol, None).is_err());
+    }
+    #[test]
+    fn expiry_decodes_every_raw_expiry() {
+        assert_eq!(Expiry::from(0), Expiry::Never);
+        assert_eq!(Expiry::from(1), Expiry::Never);
+        assert_eq!(Expiry::from(2), Expiry::AtTimestamp(2));
+        assert_eq!(Expiry::from(i64::MAX), Expiry::AtTimestamp(i64::MAX));
+        assert_eq!(Expiry::from(-1), Expiry::EscrowedNoExpiry);
+        assert_eq!(Expiry::from(-2), Expiry::EscrowedAtTimestamp(2));
+        assert_eq!(
+            Expiry::from(-i64::MAX),
+            Expiry::EscrowedAtTimestamp(i64::MAX)
+        );
+        assert_eq!(
+            Expiry::from(i64::MIN),
+            Expiry::EscrowedAtTimestamp(i64::MAX)
+        );
+    }
+
+    #[test]
+    fn expiry_round_trips_through_its_raw_expiry() {
+        for raw in [
+            0,
+            -1,
+            2,
+            -2,
+            1_700_000_000,
+            -1_700_000_000,
+            i64::MAX,
+            -i64::MAX,
+        ] {
+            assert_eq!(i64::from(Expiry::from(raw)), raw);
+        }
+        // 1 predates Never being stored as 0
+        assert_eq!(i64::from(Expiry::from(1)), 0);
+    }
+
+    #[test]
+    fn expiry_modes_and_timestamps() {
+        let cases = [
+            (Expiry::Never, false, None),
+            (Expiry::AtTimestamp(100), false, Some(100)),
+            (Expiry::EscrowedNoExpiry, true, None),
+            (Expiry::EscrowedAtTimestamp(100), true, Some(100)),
+        ];
+        for (expiry, escrowed, timestamp) in cases {
+            assert_eq!(expiry.is_escrowed(), escrowed);
+            assert_eq!(expiry.timestamp(), timestamp);
+            assert_eq!(expiry.with_timestamp(200).is_escrowed(), escrowed);
+            assert_eq!(expiry.with_timestamp(200).timestamp(), Some(200));
+        }
+    }
+
+    #[test]
+    fn expiry_is_expired_only_after_its_timestamp() {
+        for raw in [0, 1, -1] {
+            assert!(!Expiry::from(raw).is_expired(i64::MAX));
+            assert!(assert_not_expired(raw, i64::MAX).is_ok());
+        }
+        for raw in [100, -100] {
+            assert!(!Expiry::from(raw).is_expired(99));
+            assert!(!Expiry::from(raw).is_expired(100));
+            assert!(Expiry::from(raw).is_expired(101));
+            assert!(assert_not_expired(raw, 100).is_ok());
+            assert!(assert_not_expired(raw, 101).is_err());
+        }
+    }
+
+    #[test]
+    fn assert_expiry_mode_matches_the_listing_mode() {
+        for raw in [0, 1, 100] {
+            assert!(assert_expiry_mode(raw, false).is_ok());
+            assert!(assert_expiry_mode(raw, true).is_err());
+        }
+        for raw in [-1, -100] {
+            assert!(assert_expiry_mode(raw, true).is_ok());
+            assert!(assert_expiry_mode(raw, false).is_err());
+        }
+    }
+
+    #[test]
+    fn bid_escalation_climbs_linearly_up_to_max_price() {
+        let escalation = BidEscalation {
+            max_price: 200,
+            start_at: 1_000,
+            end_at: 2_000,
+        };
+        assert_eq!(escalation.price_at(100, 0), 100);
+        assert_eq!(escalation.price_at(100, 1_000), 100);
+        assert_eq!(escalation.price_at(100, 1_250), 125);
+        assert_eq!(escalation.price_at(100, 1_999), 199);
+        assert_eq!(escalation.price_at(100, 2_000), 200);
+        assert_eq!(escalation.price_at(100, i64::MAX), 200);
+        assert_eq!(BidEscalation::default().price_at(100, i64::MAX), 100);
+
+        let bid_args = BidArgs {
+            buyer_price: 100,
+            escalation,
+            ..Default::default()
+        };
+        assert_eq!(bid_args.price_at(1_500), 150);
+        assert_eq!(bid_args.locked_price(), 200);
+        assert_eq!(
+            BidArgs {
+                buyer_price: 100,
+                ..Default::default()
+            }
+            .locked_price(),
+            100
+        );
+    }
+
+    #[test]
+    fn open_bid_index_locks_the_full_cost_once_per_bid() {
+        let bid_args = BidArgs {
+            buyer_price: 10_000,
+            buyer_creator_royalty_bp: 5_000,
+            ..Default::default()
+        };
+        // the price, up to 10% of platform fees and half of a royalty of up to 100%
+        assert_eq!(bid_args.locked_cost(), 16_000);
+        assert_eq!(
+            BidArgs {
+                price_is_all_in: true,
+                ..bid_args.clone()
+            }
+            .locked_cost(),
+            10_000
+        );
+
+        let bts = Pubkey::new_unique();
+        let mut index = OpenBidIndex::default();
+        index
+            .add_bid(&bts, bid_args.locked_cost(), &Pubkey::default())
+            .unwrap();
+        index
+            .add_bid(&Pubkey::new_unique(), 1_000, &Pubkey::default())
+            .unwrap();
+        assert_eq!(index.locked_amount, 17_000);
+        index.remove_bid(&bts, bid_args.locked_cost(), &Pubkey::default());
+        assert_eq!((index.locked_amount, index.open_bids), (1_000, 1));
+        // an expired bid released by withdraw_all and cancelled later only releases once
+        index.remove_bid(&bts, bid_args.locked_cost(), &Pubkey::default());
+        assert_eq!((index.locked_amount, index.open_bids), (1_000, 1));
+    }
+
+    #[test]
+    fn listing_index_counts_each_listing_once_and_keeps_a_floor() {
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut index = ListingIndex::default();
+        index.add_listing(&a, true, Some(300)).unwrap();
+        index.add_listing(&b, true, Some(200)).unwrap();
+        index.add_listing(&c, false, None).unwrap();
+        assert_eq!(
+            (index.listings, index.floor_price, index.floor_listing),
+            (2, 200, b)
+        );
+
+        // a listing that was never counted doesn't lower the count
+        index.remove_listing(&c, false);
+        assert_eq!(index.listings, 2);
+        // the floor only follows its listing down, a raise above it clears it
+        index.add_listing(&b, false, Some(150)).unwrap();
+        assert_eq!((index.floor_price, index.floor_listing), (150, b));
+        index.add_listing(&b, false, Some(400)).unwrap();
+        assert_eq!(index.floor_listing, Pubkey::default());
+        index.add_listing(&a, false, Some(300)).unwrap();
+        assert_eq!((index.floor_price, index.floor_listing), (300, a));
+        index.remove_listing(&a, true);
+        assert_eq!(
+            (index.listings, index.floor_price, index.floor_listing),
+            (1, 0, Pubkey::default())
+        );
+    }
+
+    #[test]
+    fn floor_conditional_bids_fill_only_at_or_below_the_collection_floor() {
+        let auction_house = Pubkey::new_unique();
+        let collection = Pubkey::new_unique();
+        let (key, _) = get_listing_index_address(&auction_house, &collection);
+        let mut index = ListingIndex::default();
+        index
+            .add_listing(&Pubkey::new_unique(), true, Some(200))
+            .unwrap();
+        let mut data = vec![];
+        index.try_serialize(&mut data).unwrap();
+        let mut lamports = 0;
+        let listing_index = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let accounts = std::slice::from_ref(&listing_index);
+        let check = |max_floor_price, collection, accounts| {
+            assert_floor_condition(max_floor_price, collection, accounts, &auction_house)
+        };
+
+        assert!(check(0, None, &[]).is_ok());
+        assert!(check(200, Some(collection), accounts).is_ok());
+        assert!(check(250, Some(collection), accounts).is_ok());
+        assert_eq!(
+            check(199, Some(collection), accounts).unwrap_err(),
+            ErrorCode::FloorConditionNotMet.into()
+        );
+        // the mint has no verified collection to take the floor of
+        assert_eq!(
+            check(250, None, accounts).unwrap_err(),
+            ErrorCode::FloorConditionNotMet.into()
+        );
+        // another collection's index doesn't stand in for it
+        assert_eq!(
+            check(250, Some(Pubkey::new_unique()), accounts).unwrap_err(),
+            ErrorCode::MissingRemainingAccount.into()
+        );
+
+        // no recorded floor, e.g. the floor listing was filled
+        index.remove_listing(&index.floor_listing.clone(), true);
+        index
+            .try_serialize(&mut &mut listing_index.try_borrow_mut_data().unwrap()[..])
+            .unwrap();
+        assert_eq!(
+            check(250, Some(collection), accounts).unwrap_err(),
+            ErrorCode::FloorConditionNotMet.into()
+        );
+    }
+
+    #[test]
+    fn assert_bid_escalation_rejects_schedules_that_cant_climb() {
+        let escalation = |max_price, start_at, end_at| BidEscalation {
+            max_price,
+            start_at,
+            end_at,
+        };
+        assert_eq!(
+            assert_bid_escalation(&BidEscalation::default(), 100, 1_000).unwrap(),
+            BidEscalation::default()
+        );
+        assert_eq!(
+            assert_bid_escalation(&escalation(200, 0, 2_000), 100, 1_000).unwrap(),
+            escalation(200, 1_000, 2_000)
+        );
+        assert!(assert_bid_escalation(&escalation(200, 1_500, 2_000), 100, 1_000).is_ok());
+        assert!(assert_bid_escalation(&escalation(100, 0, 2_000), 100, 1_000).is_err());
+        assert!(assert_bid_escalation(&escalation(MAX_PRICE + 1, 0, 2_000), 100, 1_000).is_err());
+        assert!(assert_bid_escalation(&escalation(200, 0, 1_000), 100, 1_000).is_err());
+        assert!(assert_bid_escalation(&escalation(200, 2_000, 1_500), 100, 1_000).is_err());
+    }
+
+    #[test]
+    fn wallet_limits_enforce_open_bids_and_daily_volume() {
+        let mut limits = WalletLimits {
+            max_daily_buy_volume: 100,
+            max_open_bids: 1,
+            ..Default::default()
+        };
+        assert!(limits.open_bid().is_ok());
+        assert!(limits.open_bid().is_err());
+        limits.close_bid();
+        assert!(limits.open_bid().is_ok());
+
+        assert!(limits.record_buy(60, 86_400).is_ok());
+        assert!(limits.record_buy(60, 86_400 + 100).is_err());
+        assert!(limits.record_buy(60, 2 * 86_400).is_ok());
+        assert_eq!(limits.day_buy_volume, 60);
+
+        limits.max_daily_buy_volume = 0;
+        assert!(limits.record_buy(u64::MAX / 2, 2 * 86_400).is_ok());
+    }
+
+    #[test]
+    fn half_migrated_seller_trade_states_are_detected() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let v3 = SellerTradeStateV3 {
+            seller: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let cases = [
+            (vec![0; SellerTradeStateV3::LEN], true),
+            (
+                [
+                    &SellerTradeStateV3::discriminator()[..],
+                    &[0; SellerTradeStateV3::LEN - 8],
+                ]
+                .concat(),
+                true,
+            ),
+            (
+                [
+                    &SellerTradeStateV3::discriminator()[..],
+                    &v3.try_to_vec().unwrap(),
+                ]
+                .concat(),
+                false,
+            ),
+            (
+                [
+                    &SellerTradeStateV2::discriminator()[..],
+                    &[0; SellerTradeStateV3::LEN - 8],
+                ]
+                .concat(),
+                false,
+            ),
+        ];
+        for (mut data, half_migrated) in cases {
+            let sts = AccountInfo::new(
+                &key,
+                false,
+                true,
+                &mut lamports,
+                &mut data,
+                &crate::ID,
+                false,
+                0,
+            );
+            assert_eq!(
+                is_half_migrated_seller_trade_state(&sts).unwrap(),
+                half_migrated
+            );
+        }
+    }
+
+    #[test]
+    fn only_royalty_locked_listings_keep_their_snapshot() {
+        let mut extra_args = vec![0; SellExtraArgs::LEN];
+        assert!(!SellExtraArgs::parse(&extra_args).royalty_lock);
+        extra_args[47 + SellerTradeStateV3::ALT_PRICES_LEN] = 1;
+        assert!(SellExtraArgs::parse(&extra_args).royalty_lock);
+
+        for (royalty_lock, snapshot) in [(false, None), (true, Some(500))] {
+            let v3 = SellerTradeStateV3 {
+                seller: Pubkey::new_unique(),
+                royalty_lock,
+                royalty_bp_snapshot: 500,
+                ..Default::default()
+            };
+            let data = [
+                &SellerTradeStateV3::discriminator()[..],
+                &v3.try_to_vec().unwrap(),
+            ]
+            .concat();
+            assert_eq!(
+                SellArgs::from_data(&data).unwrap().royalty_bp_snapshot,
+                snapshot
+            );
+        }
+    }
+
+    #[test]
+    fn hidden_listings_cant_be_filled_before_their_reveal() {
+        let mut extra_args = vec![0; SellExtraArgs::LEN];
+        let offset = 48 + SellerTradeStateV3::ALT_PRICES_LEN;
+        extra_args[offset..offset + 8].copy_from_slice(&1_700_000_000i64.to_le_bytes());
+        let hidden_until = SellExtraArgs::parse(&extra_args).hidden_until;
+        assert_eq!(hidden_until, 1_700_000_000);
+
+        let v3 = SellerTradeStateV3 {
+            seller: Pubkey::new_unique(),
+            hidden_until,
+            ..Default::default()
+        };
+        let data = [
+            &SellerTradeStateV3::discriminator()[..],
+            &v3.try_to_vec().unwrap(),
+        ]
+        .concat();
+        let sell_args = SellArgs::from_data(&data).unwrap();
+        assert!(
+            assert_listing_visible(sell_args.hidden_until, hidden_until - 1).err()
+                == Some(ErrorCode::ListingHidden.into())
+        );
+        assert!(assert_listing_visible(sell_args.hidden_until, hidden_until).is_ok());
+        // listings that don't set it, and older ones, are visible right away
+        assert!(assert_listing_visible(SellArgs::default().hidden_until, 0).is_ok());
+    }
+
+    #[test]
+    fn orders_placed_with_unregistered_referrals_can_be_filled() {
+        let referral = Pubkey::new_unique();
+        // no Referrer registration is passed, the order stores no referral
+        let stored_referral = get_registered_referral(None, &referral).unwrap();
+        assert_eq!(stored_referral, Pubkey::default());
+
+        let token_mint = Pubkey::new_unique();
+        let sell_args = SellArgs {
+            seller_referral: stored_referral,
+            buyer_price: 100,
+            token_mint,
+            token_size: 1,
+            ..Default::default()
+        };
+        let bid_args = BidArgs {
+            buyer_referral: stored_referral,
+            buyer_price: 100,
+            token_mint,
+            token_size: 1,
+            ..Default::default()
+        };
+        // the fill passes the referral accounts the orders were placed with
+        assert!(sell_args
+            .check_args(&referral, &100, &token_mint, &1, &Pubkey::default())
+            .is_ok());
+        assert!(bid_args
+            .check_args(&referral, 100, &token_mint, 1, &Pubkey::default())
+            .is_ok());
+
+        // a stored referral still has to match
+        let registered = SellArgs {
+            seller_referral: referral,
+            ..sell_args
+        };
+        assert!(
+            registered
+                .check_args(
+                    &Pubkey::new_unique(),
+                    &100,
+                    &token_mint,
+                    &1,
+                    &Pubkey::default()
+                )
+                .err()
+                == Some(ErrorCode::TradeStateMismatch.into())
+        );
+    }
+
+    /// Runs f with a trade state account laid out like the runtime serializes it, the data length
+    /// before the data and the original data length before the key, so it can be reallocated
+    fn with_serialized_seller_trade_state(f: impl FnOnce(&AccountInfo)) {
+        let v3 = SellerTradeStateV3 {
+            seller: Pubkey::new_unique(),
+            buyer_price: 1,
+            token_size: 1,
+            ..Default::default()
+        };
+        let mut body = [
+            &SellerTradeStateV3::discriminator()[..],
+            &v3.try_to_vec().unwrap(),
+        ]
+        .concat();
+        body.resize(SellerTradeStateV3::LEN, 0);
+
+        let mut key_buf = [0u8; 40];
+        key_buf[4..8].copy_from_slice(&(body.len() as u32).to_le_bytes());
+        key_buf[8..].copy_from_slice(Pubkey::new_unique().as_ref());
+        // SAFETY: Pubkey is a 32 byte array, key_buf outlives the account
+        let key = unsafe { &*(key_buf[8..].as_ptr() as *const Pubkey) };
+        let mut data_buf = [(body.len() as u64).to_le_bytes().to_vec(), body].concat();
+        let mut lamports = 10_000_000;
+        let owner = crate::ID;
+        let sts = AccountInfo::new(
+            key,
+            false,
+            true,
+            &mut lamports,
+            &mut data_buf[8..],
+            &owner,
+            false,
+            0,
+        );
+        f(&sts);
+    }
+
+    #[test]
+    fn closed_trade_states_cant_be_revived_by_a_lamport_refund() {
+        let mut dest_lamports = 0;
+        let dest_key = Pubkey::new_unique();
+        let dest = AccountInfo::new(
+            &dest_key,
+            false,
+            true,
+            &mut dest_lamports,
+            &mut [],
+            &system_program::ID,
+            false,
+            0,
+        );
+
+        with_serialized_seller_trade_state(|sts| {
+            assert!(SellArgs::from_account_info(sts).is_ok());
+            assert!(!is_zombie_trade_state(sts).unwrap());
+            close_account_anchor(sts, &dest, true).unwrap();
+            assert_eq!(dest.lamports(), 10_000_000);
+            assert!(!is_zombie_trade_state(sts).unwrap());
+            // a later instruction of the transaction sends lamports back
+            **sts.lamports.borrow_mut() = 10_000_000;
+            assert!(sts.data_is_empty());
+            assert_eq!(sts.owner, &system_program::ID);
+            assert!(is_zombie_trade_state(sts).unwrap());
+            assert!(
+                SellArgs::from_account_info(sts).err() == Some(ErrorCode::ZombieTradeState.into())
+            );
+        });
+
+        // the discriminator alone keeps the listing unreadable, but its body is still there
+        with_serialized_seller_trade_state(|sts| {
+            close_account_anchor(sts, &dest, false).unwrap();
+            **sts.lamports.borrow_mut() = 10_000_000;
+            assert_eq!(sts.data_len(), SellerTradeStateV3::LEN);
+            assert_eq!(sts.owner, &crate::ID);
+            assert!(is_zombie_trade_state(sts).unwrap());
+            assert!(
+                SellArgs::from_account_info(sts).err() == Some(ErrorCode::ZombieTradeState.into())
+            );
+        });
+    }
+
+    #[test]
+    fn rate_limit_counts_per_slot() {
+        let mut rate_limit = RateLimit::default();
+        assert!(rate_limit.record(10, 2).is_ok());
+        assert!(rate_limit.record(10, 2).is_ok());
+        assert!(rate_limit.record(10, 2).is_err());
+        assert_eq!(rate_limit.count, 2);
+        assert!(rate_limit.record(11, 2).is_ok());
+        assert_eq!((rate_limit.slot, rate_limit.count), (11, 1));
+    }
+
+    #[test]
+    fn listing_flow_follows_token_standard() {
+        assert_eq!(listing_flow(None, false), ListingFlow::Legacy);
+        assert_eq!(
+            listing_flow(Some(&TokenStandard::NonFungible), false),
+            ListingFlow::Legacy
+        );
+        assert_eq!(
+            listing_flow(Some(&TokenStandard::ProgrammableNonFungible), false),
+            ListingFlow::Mip1
+        );
+        assert_eq!(
+            listing_flow(Some(&TokenStandard::NonFungible), true),
+            ListingFlow::Ocp
+        );
+    }
+
+    #[test]
+    fn disabled_instructions_fail_with_deprecated() {
+        let mut config = ProgramConfig::default();
+        assert!(assert_instruction_enabled(None, GatedInstruction::Buy).is_ok());
+        assert!(assert_instruction_enabled(Some(&config), GatedInstruction::Buy).is_ok());
+
+        config.disabled_instructions = GatedInstruction::Buy.bit();
+        assert_eq!(
+            assert_instruction_enabled(Some(&config), GatedInstruction::Buy).unwrap_err(),
+            ErrorCode::Deprecated.into()
+        );
+        assert!(assert_instruction_enabled(Some(&config), GatedInstruction::Sell).is_ok());
+    }
+
+    /// A ProgramConfig account at its address that disabled sell
+    fn sell_disabled_config() -> Vec<u8> {
+        let mut data = vec![];
+        ProgramConfig {
+            disabled_instructions: GatedInstruction::Sell.bit(),
+            ..Default::default()
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        data
+    }
+
+    #[test]
+    fn list_auto_fails_with_deprecated_when_its_legacy_flow_is_retired() {
+        assert_eq!(ListingFlow::Legacy.gate(), Some(GatedInstruction::Sell));
+        assert_eq!(ListingFlow::Mip1.gate(), None);
+        assert_eq!(ListingFlow::Ocp.gate(), None);
+
+        let keys = [get_program_config_address(), Pubkey::new_unique()];
+        let mut lamports = [0u64; 2];
+        let [config_lamports, other_lamports] = &mut lamports;
+        let mut config_data = sell_disabled_config();
+        let mut other_data = [0u8; 0];
+        // list_auto's routed accounts, the config is among sell's remaining accounts
+        let routed = [
+            AccountInfo::new(
+                &keys[1],
+                false,
+                false,
+                other_lamports,
+                &mut other_data,
+                &system_program::ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &keys[0],
+                false,
+                false,
+                config_lamports,
+                &mut config_data,
+                &crate::ID,
+                false,
+                0,
+            ),
+        ];
+        assert_eq!(
+            assert_instruction_enabled_in(&routed, GatedInstruction::Sell).unwrap_err(),
+            ErrorCode::Deprecated.into()
+        );
+        assert!(assert_instruction_enabled_in(&routed[..1], GatedInstruction::Sell).is_ok());
+    }
+
+    #[test]
+    fn sell_into_bid_fails_with_deprecated_when_sell_is_retired() {
+        let key = get_program_config_address();
+        let mut lamports = 0;
+        let mut config_data = sell_disabled_config();
+        let program_config = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut config_data,
+            &crate::ID,
+            false,
+            0,
+        );
+        assert_eq!(
+            assert_instruction_enabled_in(
+                std::slice::from_ref(&program_config),
+                GatedInstruction::Sell
+            )
+            .unwrap_err(),
+            ErrorCode::Deprecated.into()
+        );
+        assert!(assert_instruction_enabled_in(
+            std::slice::from_ref(&program_config),
+            GatedInstruction::Buy
+        )
+        .is_ok());
+        // a config not owned by the program can't lift the gate
+        let forged = AccountInfo {
+            owner: &key,
+            ..program_config.clone()
+        };
+        assert!(assert_instruction_enabled_in(
+            std::slice::from_ref(&forged),
+            GatedInstruction::Sell
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn co_owner_approvals_need_the_threshold_for_the_same_action() -> Result<()> {
+        let owners: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let outsider = Pubkey::new_unique();
+        let mut approval = CoOwnerApproval::default();
+        let cancel = CoOwnerApproval::cancel_action_hash();
+        let listing = CoOwnerApproval::action_hash(100, 0, &Pubkey::default(), &[]);
+        assert!(!approval.is_approved(&owners[0], &cancel));
+        approval.set_owners(&owners, 2)?;
+
+        approval.approve(&owners[0], listing)?;
+        assert!(!approval.is_approved(&owners[0], &listing));
+        approval.approve(&owners[1], listing)?;
+        assert!(approval.is_approved(&owners[0], &listing));
+        assert!(approval.is_approved(&owners[1], &listing));
+        // only an approving co-owner can act, and only for the approved action
+        assert!(!approval.is_approved(&owners[2], &listing));
+        assert!(!approval.is_approved(&outsider, &listing));
+        assert!(!approval.is_approved(&owners[0], &cancel));
+        assert!(approval.approve(&outsider, listing).is_err());
+
+        // the same price with other terms is another action
+        let usdc_mint = Pubkey::new_unique();
+        let mut alt_prices = vec![0u8; 45];
+        alt_prices.extend_from_slice(usdc_mint.as_ref());
+        for other_terms in [
+            CoOwnerApproval::action_hash(100, 1_000, &Pubkey::default(), &[]),
+            CoOwnerApproval::action_hash(100, 0, &usdc_mint, &[]),
+            CoOwnerApproval::action_hash(100, 0, &Pubkey::default(), &alt_prices),
+        ] {
+            assert!(!approval.is_approved(&owners[0], &other_terms));
+        }
+        // extra args are the terms a listing is written with, trailing zeroes and what comes
+        // after them, like a remaining accounts schema, don't change them
+        assert_eq!(
+            CoOwnerApproval::action_hash(100, 0, &Pubkey::default(), &[0; 300]),
+            listing
+        );
+
+        // approving another action starts over
+        approval.approve(&owners[2], cancel)?;
+        assert!(!approval.is_approved(&owners[0], &listing));
+        assert!(!approval.is_approved(&owners[2], &cancel));
+
+        for (owners, threshold) in [
+            (vec![], 1),
+            (owners.clone(), 0),
+            (owners.clone(), 4),
+            (vec![owners[0], owners[0]], 1),
+            (vec![Pubkey::default()], 1),
+            (vec![outsider; MAX_CO_OWNERS + 1], 1),
+        ] {
+            assert_eq!(
+                approval.set_owners(&owners, threshold).unwrap_err(),
+                ErrorCode::InvalidCoOwners.into()
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn notary_sets_need_threshold_distinct_notaries() -> Result<()> {
+        let notaries: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let outsider = Pubkey::new_unique();
+        let mut notary_set = NotarySet::default();
+        assert!(!notary_set.is_cosigned_by(std::iter::empty()));
+        notary_set.set_notaries(&notaries, 2)?;
+
+        assert!(!notary_set.is_cosigned_by([notaries[0]].iter()));
+        assert!(!notary_set.is_cosigned_by([notaries[0], notaries[0], outsider].iter()));
+        assert!(notary_set.is_cosigned_by([outsider, notaries[2], notaries[0]].iter()));
+        assert!(!notary_set.is_cosigned_by([Pubkey::default(); 2].iter()));
+
+        for (notaries, threshold) in [
+            (vec![], 1),
+            (notaries.clone(), 0),
+            (notaries.clone(), 4),
+            (vec![notaries[0], notaries[0]], 1),
+            (vec![Pubkey::default()], 1),
+            (vec![outsider; MAX_NOTARIES + 1], 1),
+        ] {
+            assert_eq!(
+                notary_set.set_notaries(&notaries, threshold).unwrap_err(),
+                ErrorCode::InvalidNotarySet.into()
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn notary_signed_fees_take_precedence_over_payment_mint_overrides() {
+        let meme = Pubkey::new_unique();
+        let bonk = VALID_PAYMENT_MINTS[6];
+        let meme_fees = PaymentMintFees {
+            mint: meme,
+            maker_fee_bp: 100,
+            taker_fee_bp: 400,
+        };
+        let bonk_fees = PaymentMintFees {
+            mint: bonk,
+            ..meme_fees
+        };
+        let mut config = ProgramConfig::default();
+        let mut payment_mint_fees = [PaymentMintFees::default(); MAX_CONFIG_PAYMENT_MINTS];
+        payment_mint_fees[2..4].copy_from_slice(&[bonk_fees, meme_fees]);
+        config.set_payment_mint_fees(payment_mint_fees).unwrap();
+        // overrides are kept by mint, built in ones included, whichever payment_mints slot it has
+        config.payment_mints[0] = meme;
+        assert_eq!(config.fees_for(&bonk), Some(bonk_fees));
+        assert_eq!(config.fees_for(&meme), Some(meme_fees));
+        assert_eq!(config.fees_for(&Pubkey::new_unique()), None);
+        assert_eq!(config.fees_for(&Pubkey::default()), None);
+        payment_mint_fees[0] = meme_fees;
+        assert_eq!(
+            config.set_payment_mint_fees(payment_mint_fees).unwrap_err(),
+            ErrorCode::InvalidPlatformFeeBp.into()
+        );
+        assert_eq!(config.fees_for(&meme), Some(meme_fees));
+
+        for (signed, fees, expected) in [
+            (true, Some(meme_fees), (-10, 50)),
+            (false, Some(meme_fees), (100, 400)),
+            (false, None, (DEFAULT_MAKER_FEE_BP, DEFAULT_TAKER_FEE_BP)),
+        ] {
+            let notary_cosign = NotaryCosign {
+                known: true,
+                signed,
+            };
+            assert_eq!(
+                get_actual_maker_taker_fee_bp(&notary_cosign, -10, 50, fees),
+                expected
+            );
+        }
+
+        assert!(meme_fees.is_valid());
+        for (maker_fee_bp, taker_fee_bp) in [(-401, 400), (0, 501), (501, 0)] {
+            let fees = PaymentMintFees {
+                mint: meme,
+                maker_fee_bp,
+                taker_fee_bp,
+            };
+            assert!(!fees.is_valid());
+            let mut payment_mint_fees = [PaymentMintFees::default(); MAX_CONFIG_PAYMENT_MINTS];
+            payment_mint_fees[0] = fees;
+            assert!(config.set_payment_mint_fees(payment_mint_fees).is_err());
+        }
+    }
+
+    #[test]
+    fn bid_groups_stop_filling_after_max_fills() {
+        let bids: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let mut bid_group = BidGroup::default();
+        for (bids, max_fills) in [
+            (vec![], 1),
+            (bids.clone(), 0),
+            (bids[..2].to_vec(), 3),
+            (vec![bids[0], bids[0]], 1),
+            (vec![Pubkey::new_unique(); MAX_BID_GROUP_BIDS + 1], 1),
+        ] {
+            assert_eq!(
+                bid_group.set_bids(&bids, max_fills).unwrap_err(),
+                ErrorCode::InvalidBidGroup.into()
+            );
+        }
+
+        // "any 2 of these 5", in the buyer's order of priority
+        bid_group.set_bids(&bids, 2).unwrap();
+        assert_eq!(bid_group.fill(&bids[3]).unwrap(), 3);
+        assert_eq!(
+            bid_group.fill(&Pubkey::new_unique()).unwrap_err(),
+            ErrorCode::InvalidBidGroup.into()
+        );
+        assert_eq!(bid_group.fill(&bids[0]).unwrap(), 0);
+        for bid in &bids {
+            assert_eq!(
+                bid_group.fill(bid).unwrap_err(),
+                ErrorCode::BidGroupConsumed.into()
+            );
+        }
+        assert_eq!(bid_group.fills, 2);
+    }
+
+    #[test]
+    fn delegate_listings_break_once_the_token_is_moved_or_revoked() {
+        let seller = Pubkey::new_unique();
+        let program_as_signer = Pubkey::new_unique();
+        let sell_args = SellArgs {
+            seller,
+            token_size: 1,
+            ..Default::default()
+        };
+        let token_account = spl_token::state::Account {
+            mint: Pubkey::new_unique(),
+            owner: seller,
+            amount: 1,
+            delegate: COption::Some(program_as_signer),
+            state: spl_token::state::AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 1,
+            close_authority: COption::None,
+        };
+        assert!(is_delegate_listing_intact(
+            &token_account,
+            &sell_args,
+            &program_as_signer
+        ));
+        // a frozen delegate listing, of sell_frozen, is still intact
+        let frozen = spl_token::state::Account {
+            state: spl_token::state::AccountState::Frozen,
+            ..token_account
+        };
+        assert!(is_delegate_listing_intact(
+            &frozen,
+            &sell_args,
+            &program_as_signer
+        ));
+
+        for broken in [
+            spl_token::state::Account {
+                owner: Pubkey::new_unique(),
+                ..token_account
+            },
+            spl_token::state::Account {
+                delegate: COption::None,
+                delegated_amount: 0,
+                ..token_account
+            },
+            spl_token::state::Account {
+                delegate: COption::Some(Pubkey::new_unique()),
+                ..token_account
+            },
+            spl_token::state::Account {
+                amount: 0,
+                ..token_account
+            },
+        ] {
+            assert!(!is_delegate_listing_intact(
+                &broken,
+                &sell_args,
+                &program_as_signer
+            ));
+        }
+    }
+
+    #[test]
+    fn mint_market_keeps_the_best_sol_orders_per_token() {
+        let mut market = MintMarket::default();
+        let (bid_a, bid_b, ask_a, ask_b) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        market.offer_bid(&bid_a, Some(100), 1);
+        // ranked per token, 2 tokens for 180 bids less than 1 for 100
+        market.offer_bid(&bid_b, Some(180), 2);
+        assert_eq!((market.best_bid, market.best_bid_price), (bid_a, 100));
+        market.offer_bid(&bid_b, Some(300), 2);
+        assert_eq!((market.best_bid, market.best_bid_price), (bid_b, 150));
+        // the best bid moving to an SPL payment mint drops it
+        market.offer_bid(&bid_b, None, 2);
+        assert_eq!(market.best_bid, Pubkey::default());
+
+        market.offer_ask(&ask_a, Some(200), 1);
+        market.offer_ask(&ask_b, Some(250), 1);
+        assert_eq!((market.best_ask, market.best_ask_price), (ask_a, 200));
+        // the best ask raising its price clears the side, ask_b may be cheaper now
+        market.offer_ask(&ask_a, Some(300), 1);
+        assert_eq!(market.best_ask, Pubkey::default());
+        market.offer_ask(&ask_b, Some(250), 1);
+        market.offer_ask(&ask_a, Some(300), 1);
+        assert_eq!((market.best_ask, market.best_ask_price), (ask_b, 250));
+        // and so does the best bid lowering its price
+        market.offer_bid(&bid_a, Some(100), 1);
+        market.offer_bid(&bid_a, Some(90), 1);
+        assert_eq!(market.best_bid, Pubkey::default());
+
+        market.remove(&ask_a);
+        assert_eq!(market.best_ask, ask_b);
+        market.remove(&ask_b);
+        assert_eq!(
+            (market.best_ask, market.best_ask_price),
+            (Pubkey::default(), 0)
+        );
+    }
+
+    #[test]
+    fn only_the_registered_guardian_signs_for_the_wallet() {
+        let wallet_key = Pubkey::new_unique();
+        let guardian_key = Pubkey::new_unique();
+        let guardian_account_key = Pubkey::find_program_address(
+            &[PREFIX.as_bytes(), GUARDIAN.as_bytes(), wallet_key.as_ref()],
+            &crate::ID,
+        )
+        .0;
+        let mut data = Vec::new();
+        Guardian {
+            wallet: wallet_key,
+            guardian: guardian_key,
+            bump: 0,
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        let mut lamports = [0u64; 3];
+        let [wallet_lamports, guardian_account_lamports, guardian_lamports] = &mut lamports;
+        let mut empty = [[0u8; 0]; 2];
+        let [wallet_data, guardian_data] = &mut empty;
+        let system_program = system_program::ID;
+        let wallet = AccountInfo::new(
+            &wallet_key,
+            false,
+            true,
+            wallet_lamports,
+            wallet_data,
+            &system_program,
+            false,
+            0,
+        );
+        let guardian_account = AccountInfo::new(
+            &guardian_account_key,
+            false,
+            false,
+            guardian_account_lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let mut guardian = AccountInfo::new(
+            &guardian_key,
+            true,
+            false,
+            guardian_lamports,
+            guardian_data,
+            &system_program,
+            false,
+            0,
+        );
+
+        let accounts = [guardian_account.clone(), guardian.clone(), wallet.clone()];
+        let (rest, signed) = split_guardian_from_remaining_accounts(&accounts, &wallet).unwrap();
+        assert_eq!(signed.map(|guardian| guardian.key), Some(&guardian_key));
+        assert_eq!(rest.len(), 1);
+        // without the Guardian first there's nothing to split
+        let (rest, signed) =
+            split_guardian_from_remaining_accounts(&accounts[1..], &wallet).unwrap();
+        assert!(signed.is_none());
+        assert_eq!(rest.len(), 2);
+
+        guardian.is_signer = false;
+        let accounts = [guardian_account.clone(), guardian.clone()];
+        assert_eq!(
+            split_guardian_from_remaining_accounts(&accounts, &wallet).unwrap_err(),
+            ErrorCode::InvalidGuardian.into()
+        );
+        let accounts = [guardian_account, wallet.clone()];
+        assert!(split_guardian_from_remaining_accounts(&accounts, &wallet).is_err());
+    }
+
+    /// The instructions sysvar of a transaction that verified `signer`'s signature over `message`
+    /// with the ed25519 program, ahead of the program's own instruction
+    fn ed25519_instructions_sysvar(signer: &ed25519_dalek::Keypair, message: &[u8]) -> Vec<u8> {
+        use solana_program::sysvar::instructions::{
+            construct_instructions_data, BorrowedInstruction,
+        };
+        let verify = solana_sdk::ed25519_instruction::new_ed25519_instruction(signer, message);
+        construct_instructions_data(&[
+            BorrowedInstruction {
+                program_id: &verify.program_id,
+                accounts: vec![],
+                data: &verify.data,
+            },
+            BorrowedInstruction {
+                program_id: &crate::ID,
+                accounts: vec![],
+                data: &[],
+            },
+        ])
+    }
+
+    fn ed25519_keypair(seed: u8) -> ed25519_dalek::Keypair {
+        let secret = ed25519_dalek::SecretKey::from_bytes(&[seed; 32]).unwrap();
+        let public = ed25519_dalek::PublicKey::from(&secret);
+        ed25519_dalek::Keypair { secret, public }
+    }
+
+    fn sell_authorization(seller_referral: Pubkey, nonce: u64) -> Vec<u8> {
+        crate::m2_ins::sell_with_authorization::SellAuthorization {
+            auction_house: Pubkey::new_from_array([1; 32]),
+            token_mint: Pubkey::new_from_array([2; 32]),
+            token_account: Pubkey::new_from_array([3; 32]),
+            seller_referral,
+            price: 1_000_000_000,
+            expiry: 0,
+            nonce,
+        }
+        .try_to_vec()
+        .unwrap()
+    }
+
+    #[test]
+    fn ed25519_signed_authorization_must_match_signer_and_message() {
+        let seller = ed25519_keypair(7);
+        let forger = ed25519_keypair(8);
+        let seller_key = Pubkey::new_from_array(seller.public.to_bytes());
+        let referral = Pubkey::new_from_array([4; 32]);
+        let message = sell_authorization(referral, 1);
+        let check = |sysvar_data: &mut Vec<u8>, message: &[u8]| {
+            let key = sysvar::instructions::id();
+            let mut lamports = 0;
+            let instructions = AccountInfo::new(
+                &key,
+                false,
+                false,
+                &mut lamports,
+                sysvar_data,
+                &sysvar::ID,
+                false,
+                0,
+            );
+            assert_ed25519_signed(&instructions, &seller_key, message)
+        };
+
+        assert!(check(
+            &mut ed25519_instructions_sysvar(&seller, &message),
+            &message
+        )
+        .is_ok());
+        // forged signer: a valid signature, but not by the seller
+        assert_eq!(
+            check(
+                &mut ed25519_instructions_sysvar(&forger, &message),
+                &message
+            )
+            .unwrap_err(),
+            ErrorCode::InvalidSignedAuthorization.into()
+        );
+        // wrong message: the relayer swapped in its own seller_referral
+        let relayed = sell_authorization(Pubkey::new_from_array([5; 32]), 1);
+        assert_eq!(
+            check(
+                &mut ed25519_instructions_sysvar(&seller, &message),
+                &relayed
+            )
+            .unwrap_err(),
+            ErrorCode::InvalidSignedAuthorization.into()
+        );
+    }
+
+    #[test]
+    fn signed_authorization_nonce_cannot_be_reused() {
+        let auction_house = Pubkey::new_unique();
+        let wallet = Pubkey::new_unique();
+        let mut authorization_nonce = AuthorizationNonce::default();
+        consume_authorization_nonce(&mut authorization_nonce, &auction_house, &wallet, 254, 1)
+            .unwrap();
+        assert_eq!(
+            consume_authorization_nonce(&mut authorization_nonce, &auction_house, &wallet, 254, 1)
+                .unwrap_err(),
+            ErrorCode::StaleAuthorizationNonce.into()
+        );
+        assert_eq!(
+            consume_authorization_nonce(&mut authorization_nonce, &auction_house, &wallet, 254, 0)
+                .unwrap_err(),
+            ErrorCode::StaleAuthorizationNonce.into()
+        );
+        consume_authorization_nonce(&mut authorization_nonce, &auction_house, &wallet, 254, 2)
+            .unwrap();
+        assert_eq!(authorization_nonce.last_nonce, 2);
+    }
 }
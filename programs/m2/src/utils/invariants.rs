@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+use crate::{
+    errors::ErrorCode,
+    states::{BidArgs, BuyerTradeStateV3, SellArgs, SellerTradeStateV3},
+    utils::{get_listing_payment_amounts, ListingPaymentAmounts},
+};
+
+// Assertions only built with the strict-invariants feature, for CI and localnet. Each re-checks
+// what the handlers already rely on from a second angle, so a regression fails loudly there
+// instead of moving funds on mainnet. Call them through strict_invariant!, which compiles to
+// nothing without the feature.
+
+fn invariant(holds: bool, what: &str) -> Result<()> {
+    if !holds {
+        msg!("strict invariant violated: {}", what);
+        return Err(ErrorCode::InvariantViolated.into());
+    }
+    Ok(())
+}
+
+/// What a SOL escrow pays out for a sale at total_price: what the seller gets from the buyer,
+/// the platform fee when the buyer takes, and the royalty on top
+pub fn expected_escrow_outflow(
+    total_price: u64,
+    actual_maker_fee_bp: i16,
+    actual_taker_fee_bp: u16,
+    seller_is_taker: bool,
+    royalty: u64,
+) -> Result<u64> {
+    let ListingPaymentAmounts {
+        seller_will_get_from_buyer,
+        total_platform_fee,
+        ..
+    } = get_listing_payment_amounts(
+        total_price,
+        actual_maker_fee_bp,
+        actual_taker_fee_bp,
+        seller_is_taker,
+        0,
+    )?;
+    seller_will_get_from_buyer
+        .checked_add(if seller_is_taker {
+            0
+        } else {
+            total_platform_fee
+        })
+        .and_then(|v| v.checked_add(royalty))
+        .ok_or(ErrorCode::NumericalOverflow.into())
+}
+
+/// The SOL escrow lost exactly what the sale owed out of it, no more and no less
+pub fn assert_escrow_conserved(
+    escrow_lamports_before: u64,
+    escrow_lamports_after: u64,
+    expected_outflow: u64,
+) -> Result<()> {
+    invariant(
+        escrow_lamports_before.checked_sub(escrow_lamports_after) == Some(expected_outflow),
+        "escrow balance conservation",
+    )
+}
+
+/// A listing reads back, through the same decoder the execute paths use, as what was written
+pub fn assert_seller_trade_state_consistent(
+    sts: &AccountInfo,
+    written: &SellerTradeStateV3,
+) -> Result<()> {
+    let read = SellArgs::from_data(&sts.try_borrow_data()?)?;
+    invariant(
+        read.auction_house_key == written.auction_house_key
+            && read.seller == written.seller
+            && read.seller_referral == written.seller_referral
+            && read.buyer_price == written.buyer_price
+            && read.token_mint == written.token_mint
+            && read.token_account == written.token_account
+            && read.token_size == written.token_size
+            && read.bump == written.bump
+            && read.expiry == written.expiry
+            && read.payment_mint == written.payment_mint
+            && read.created_at == written.created_at
+            && read.last_updated_at == written.last_updated_at,
+        "seller trade state consistency",
+    )
+}
+
+/// A bid reads back, through the same decoder the execute paths use, as what was written
+pub fn assert_buyer_trade_state_consistent(
+    bts: &AccountInfo,
+    written: &BuyerTradeStateV3,
+) -> Result<()> {
+    let read = BidArgs::from_data(&bts.try_borrow_data()?)?;
+    invariant(
+        read.auction_house_key == written.auction_house_key
+            && read.buyer == written.buyer
+            && read.buyer_referral == written.buyer_referral
+            && read.buyer_price == written.buyer_price
+            && read.token_mint == written.token_mint
+            && read.token_size == written.token_size
+            && read.bump == written.bump
+            && read.expiry == written.expiry
+            && read.buyer_creator_royalty_bp == written.buyer_creator_royalty_bp
+            && read.payment_mint == written.payment_mint
+            && read.escrow_adapter == written.escrow_adapter,
+        "buyer trade state consistency",
+    )
+}
+
+/// The token account is the ATA of wallet for mint, re-derived under the program that owns it
+pub fn assert_ata_derivation(ata: &AccountInfo, wallet: &Pubkey, mint: &Pubkey) -> Result<()> {
+    invariant(
+        *ata.key == get_associated_token_address_with_program_id(wallet, mint, ata.owner),
+        "associated token account derivation",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escrow_outflow_covers_the_taker_side_only_when_the_buyer_takes() -> Result<()> {
+        // the buyer takes: price + taker fee + royalty
+        assert_eq!(
+            expected_escrow_outflow(10_000_000, 100, 200, false, 500_000)?,
+            10_700_000
+        );
+        // the seller takes and pays the platform fee itself, the bid as maker pays its maker fee
+        // to the seller: price + maker fee + royalty
+        assert_eq!(
+            expected_escrow_outflow(10_000_000, 100, 200, true, 500_000)?,
+            10_600_000
+        );
+        assert!(assert_escrow_conserved(20_000_000, 9_300_000, 10_700_000).is_ok());
+        assert!(assert_escrow_conserved(20_000_000, 9_300_001, 10_700_000).is_err());
+        assert!(assert_escrow_conserved(0, 1, 0).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        hash::hash,
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+};
+
+use crate::{constants::LOAN_VAULT, errors::ErrorCode, states::AuctionHouse};
+
+/// The auction house's loan_program and its accounts, passed in remaining accounts to have a SOL
+/// sale repay the seller's loan against the sold NFT. The seller's proceeds are paid into the
+/// loan_vault instead of the seller, then the loan program is called with `repay`, encoded like an
+/// Anchor instruction, the first 8 bytes of sha256("global:repay") followed by the proceeds as
+/// u64 LE, with accounts:
+/// 0. auction_house (signer) - proves the call comes from M2
+/// 1. token_mint - the NFT sold, the loan's collateral
+/// 2. seller (writable) - the borrower, receives what's left once the loan is repaid
+/// 3. loan_vault (writable) - holds the proceeds, owned by the loan program at its PDA of
+///    [LOAN_VAULT, seller, token_mint]
+/// 4. onwards, the loan program's own accounts, as passed after the loan vault in remaining accounts
+///
+/// The loan program is trusted to repay the seller's loan and pay out the rest, like escrow
+/// adapters it's vetted by the auction house authority. Only listings the seller made with
+/// repay_loan are routed through it, and those can't be filled without it.
+pub struct LoanRepaymentAccounts<'a, 'info> {
+    pub program: &'a AccountInfo<'info>,
+    pub loan_vault: &'a AccountInfo<'info>,
+    pub accounts: &'a [AccountInfo<'info>],
+}
+
+/// Finds the auction house's loan program in remaining accounts and splits it, with the loan vault
+/// and the loan program's own accounts after it, off the end of remaining accounts
+pub fn split_loan_repayment_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    auction_house: &AuctionHouse,
+) -> Result<(
+    &'a [AccountInfo<'info>],
+    Option<LoanRepaymentAccounts<'a, 'info>>,
+)> {
+    if auction_house.loan_program == Pubkey::default() {
+        return Ok((remaining_accounts, None));
+    }
+    let position = match remaining_accounts
+        .iter()
+        .position(|ai| *ai.key == auction_house.loan_program && ai.executable)
+    {
+        Some(position) => position,
+        None => return Ok((remaining_accounts, None)),
+    };
+    let (program, rest) = remaining_accounts[position..]
+        .split_first()
+        .ok_or(ErrorCode::MissingRemainingAccount)?;
+    let (loan_vault, accounts) = rest
+        .split_first()
+        .ok_or(ErrorCode::MissingRemainingAccount)?;
+    if !loan_vault.is_writable {
+        return Err(ErrorCode::MissingRemainingAccount.into());
+    }
+    Ok((
+        &remaining_accounts[..position],
+        Some(LoanRepaymentAccounts {
+            program,
+            loan_vault,
+            accounts,
+        }),
+    ))
+}
+
+/// Checks the loan accounts passed against the listing: a listing that repays a loan needs them,
+/// any other can't be routed through the loan program, and the loan vault must be the loan
+/// program's vault for the seller and the NFT
+pub fn assert_loan_repayment(
+    loan_repayment: Option<&LoanRepaymentAccounts>,
+    repay_loan: bool,
+    seller: &Pubkey,
+    token_mint: &Pubkey,
+) -> Result<()> {
+    match loan_repayment {
+        Some(loan_repayment) if repay_loan => {
+            let (loan_vault, _) = Pubkey::find_program_address(
+                &[LOAN_VAULT.as_bytes(), seller.as_ref(), token_mint.as_ref()],
+                loan_repayment.program.key,
+            );
+            if *loan_repayment.loan_vault.key != loan_vault
+                || loan_repayment.loan_vault.owner != loan_repayment.program.key
+            {
+                return Err(ErrorCode::LoanRepaymentMismatch.into());
+            }
+            Ok(())
+        }
+        None if !repay_loan => Ok(()),
+        _ => Err(ErrorCode::LoanRepaymentMismatch.into()),
+    }
+}
+
+/// Has the loan program repay the seller's loan out of the proceeds paid into its loan vault
+pub fn repay_loan<'info>(
+    loan_repayment: &LoanRepaymentAccounts<'_, 'info>,
+    proceeds: u64,
+    auction_house: &AccountInfo<'info>,
+    auction_house_seeds: &[&[u8]],
+    token_mint: &AccountInfo<'info>,
+    seller: &AccountInfo<'info>,
+) -> Result<()> {
+    let mut metas = vec![
+        AccountMeta::new_readonly(*auction_house.key, true),
+        AccountMeta::new_readonly(*token_mint.key, false),
+        AccountMeta::new(*seller.key, false),
+        AccountMeta::new(*loan_repayment.loan_vault.key, false),
+    ];
+    let mut infos = vec![
+        auction_house.clone(),
+        token_mint.clone(),
+        seller.clone(),
+        loan_repayment.loan_vault.clone(),
+    ];
+    for ai in loan_repayment.accounts {
+        metas.push(if ai.is_writable {
+            AccountMeta::new(*ai.key, ai.is_signer)
+        } else {
+            AccountMeta::new_readonly(*ai.key, ai.is_signer)
+        });
+        infos.push(ai.clone());
+    }
+    infos.push(loan_repayment.program.clone());
+
+    let mut data = hash(b"global:repay").to_bytes()[..8].to_vec();
+    data.extend_from_slice(&proceeds.to_le_bytes());
+
+    // the seller can only come out ahead, whatever the loan program does with the proceeds
+    let seller_lamports = seller.lamports();
+    invoke_signed(
+        &Instruction {
+            program_id: *loan_repayment.program.key,
+            accounts: metas,
+            data,
+        },
+        &infos,
+        &[auction_house_seeds],
+    )?;
+    if seller.lamports() < seller_lamports {
+        return Err(ErrorCode::LoanProgramTookFromSeller.into());
+    }
+    msg!("{{\"loan_repaid_from\":{}}}", proceeds);
+    Ok(())
+}
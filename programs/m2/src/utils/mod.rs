@@ -3,3 +3,23 @@ pub use generic::*;
 
 pub mod transfer;
 pub use transfer::*;
+
+pub mod escrow_adapter;
+pub use escrow_adapter::*;
+
+pub mod loan_repayment;
+pub use loan_repayment::*;
+
+pub mod staking_adapter;
+pub use staking_adapter::*;
+
+pub mod remaining_accounts;
+pub use remaining_accounts::*;
+
+pub mod swap_adapter;
+pub use swap_adapter::*;
+
+#[cfg(feature = "strict-invariants")]
+pub mod invariants;
+#[cfg(feature = "strict-invariants")]
+pub use invariants::*;
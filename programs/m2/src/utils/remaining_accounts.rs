@@ -0,0 +1,300 @@
+use anchor_lang::{prelude::*, Bumps};
+
+use anchor_lang::solana_program::sysvar;
+
+use crate::{
+    constants::{MAX_NOTARIES, REMAINING_ACCOUNTS_SCHEMA_OFFSET},
+    errors::ErrorCode,
+    states::{BuyExtraArgs, SellExtraArgs},
+    utils::get_trailing_instruction_data,
+};
+
+// extra args have to end before the schema, a new field can't move it
+const _: () = assert!(SellExtraArgs::LEN <= REMAINING_ACCOUNTS_SCHEMA_OFFSET);
+const _: () = assert!(BuyExtraArgs::LEN <= REMAINING_ACCOUNTS_SCHEMA_OFFSET);
+
+/// A group of optional accounts a client can name in a remaining accounts schema. Tag 0 is
+/// reserved, so zeroed bytes never read as a group.
+///
+/// A schema describes the remaining accounts as they are passed: for each group, its 1-byte tag
+/// then a 1-byte count of accounts, with each tag at most once. A handler taking a schema puts
+/// the groups back into its positional order before splitting them, so clients don't depend on
+/// where an optional group sits and a new group never shifts the accounts of the others.
+///
+/// The schema always sits at REMAINING_ACCOUNTS_SCHEMA_OFFSET of the instruction's extra args,
+/// whatever their length, zero padded up to it. Instructions without extra args take the bytes
+/// appended to their instruction data after the args as such, read through the instructions
+/// sysvar passed as the last remaining account, which isn't part of the schema.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RemainingAccountsTag {
+    ProgramConfig = 1,
+    RateLimit = 2,
+    Payment = 3,       // payment_mint, then payment_source_token_account for bids
+    EscrowAdapter = 4, // adapter program, then its accounts
+    WalletLimits = 5,
+    SpendingAllowance = 6, // spending_allowance and the escrows the handler lists with it
+    EscrowLedger = 7,
+    Listing = 8,
+    OpenBidIndex = 9,
+    Referrer = 10,
+    RentVault = 11,
+    Payer = 12,
+    StakingAdapter = 13, // adapter program, then its accounts
+    MakerRewards = 14,
+    ListingIndex = 15, // seller's and verified collection's ListingIndex
+    MintMarket = 16,
+    Guardian = 17,        // guardian_account, then guardian
+    CoOwnerApproval = 18, // co_owner_approval, then seller_authority
+    Creators = 19,        // creators, or their payment mint ATAs, in metadata order
+    Keyed = 20,           // accounts the handler finds by their address, in any order
+    LoanRepayment = 21,   // loan program, then loan_vault and its accounts
+    ProgramAsSigner = 22,
+    Recipient = 23,
+    NotarySet = 24, // notary_set, then the notaries signing with it
+}
+
+impl RemainingAccountsTag {
+    pub fn from_u8(tag: u8) -> Option<Self> {
+        use RemainingAccountsTag::*;
+        [
+            ProgramConfig,
+            RateLimit,
+            Payment,
+            EscrowAdapter,
+            WalletLimits,
+            SpendingAllowance,
+            EscrowLedger,
+            Listing,
+            OpenBidIndex,
+            Referrer,
+            RentVault,
+            Payer,
+            StakingAdapter,
+            MakerRewards,
+            ListingIndex,
+            MintMarket,
+            Guardian,
+            CoOwnerApproval,
+            Creators,
+            Keyed,
+            LoanRepayment,
+            ProgramAsSigner,
+            Recipient,
+            NotarySet,
+        ]
+        .iter()
+        .copied()
+        .find(|t| *t as u8 == tag)
+    }
+
+    /// The most accounts the group can hold
+    pub fn max_len(self) -> usize {
+        match self {
+            RemainingAccountsTag::EscrowAdapter
+            | RemainingAccountsTag::StakingAdapter
+            | RemainingAccountsTag::Keyed
+            | RemainingAccountsTag::LoanRepayment => u8::MAX as usize,
+            // a bid's mint and source, a sale's four accounts, or a transfer's mint, token accounts
+            // and programs
+            RemainingAccountsTag::Payment => 5,
+            RemainingAccountsTag::Creators => 10,
+            RemainingAccountsTag::NotarySet => 1 + MAX_NOTARIES,
+            RemainingAccountsTag::SpendingAllowance => 3,
+            RemainingAccountsTag::ListingIndex
+            | RemainingAccountsTag::Guardian
+            | RemainingAccountsTag::CoOwnerApproval => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// The schema at REMAINING_ACCOUNTS_SCHEMA_OFFSET of extra_args: a count of groups, then a tag and
+/// a count of accounts for each. Extra args that end before the offset read as no schema.
+pub fn get_remaining_accounts_schema(extra_args: &[u8]) -> Result<&[u8]> {
+    let offset = REMAINING_ACCOUNTS_SCHEMA_OFFSET;
+    let groups = match extra_args.get(offset) {
+        None | Some(0) => return Ok(&[]),
+        Some(groups) => *groups as usize,
+    };
+    extra_args
+        .get(offset + 1..offset + 1 + groups * 2)
+        .ok_or(ErrorCode::InvalidRemainingAccountsSchema.into())
+}
+
+/// Parses a schema into its groups, in the order they are passed
+pub fn parse_remaining_accounts_schema(
+    schema: &[u8],
+) -> Result<Vec<(RemainingAccountsTag, usize)>> {
+    let chunks = schema.chunks_exact(2);
+    if !chunks.remainder().is_empty() {
+        return Err(ErrorCode::InvalidRemainingAccountsSchema.into());
+    }
+    let mut groups: Vec<(RemainingAccountsTag, usize)> = Vec::with_capacity(schema.len() / 2);
+    for group in chunks {
+        let tag = RemainingAccountsTag::from_u8(group[0])
+            .ok_or(ErrorCode::InvalidRemainingAccountsSchema)?;
+        let len = group[1] as usize;
+        if len == 0 || len > tag.max_len() || groups.iter().any(|(t, _)| *t == tag) {
+            return Err(ErrorCode::InvalidRemainingAccountsSchema.into());
+        }
+        groups.push((tag, len));
+    }
+    Ok(groups)
+}
+
+/// Puts the remaining accounts a schema describes into a handler's positional order, given as
+/// the tags in the order the handler expects their groups. Every account has to be in a group
+/// the handler takes.
+pub fn order_tagged_remaining_accounts<'info>(
+    schema: &[u8],
+    remaining_accounts: &[AccountInfo<'info>],
+    order: &[RemainingAccountsTag],
+) -> Result<Vec<AccountInfo<'info>>> {
+    let groups = parse_remaining_accounts_schema(schema)?;
+    if groups.iter().map(|(_, len)| len).sum::<usize>() != remaining_accounts.len()
+        || groups.iter().any(|(tag, _)| !order.contains(tag))
+    {
+        return Err(ErrorCode::InvalidRemainingAccountsSchema.into());
+    }
+    let mut offsets = Vec::with_capacity(groups.len());
+    let mut offset = 0;
+    for (tag, len) in &groups {
+        offsets.push((*tag, offset, *len));
+        offset += len;
+    }
+    let mut ordered = Vec::with_capacity(remaining_accounts.len());
+    for tag in order {
+        if let Some((_, start, len)) = offsets.iter().find(|(t, _, _)| t == tag) {
+            ordered.extend_from_slice(&remaining_accounts[*start..start + len]);
+        }
+    }
+    Ok(ordered)
+}
+
+/// The remaining accounts in the handler's order if extra_args carries a schema, none if it
+/// doesn't and they are already passed in that order
+pub fn order_remaining_accounts<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    extra_args: &[u8],
+    order: &[RemainingAccountsTag],
+) -> Result<Option<Vec<AccountInfo<'info>>>> {
+    let schema = get_remaining_accounts_schema(extra_args)?;
+    if schema.is_empty() {
+        return Ok(None);
+    }
+    order_tagged_remaining_accounts(schema, remaining_accounts, order).map(Some)
+}
+
+/// The remaining accounts in the handler's order for an instruction without extra args, whose
+/// schema is appended to the instruction data after args_len. The instructions sysvar it's read
+/// through, passed last, is left out when there is a schema and kept otherwise, since a handler may
+/// read the sysvar itself.
+pub fn order_remaining_accounts_by_instruction_data<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    discriminator: &[u8],
+    args_len: usize,
+    order: &[RemainingAccountsTag],
+) -> Result<Option<Vec<AccountInfo<'info>>>> {
+    match remaining_accounts.split_last() {
+        Some((last, rest)) if *last.key == sysvar::instructions::id() => {
+            let trailing_data = get_trailing_instruction_data(last, discriminator, args_len)?;
+            order_remaining_accounts(rest, &trailing_data, order)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// ctx with the remaining accounts order_remaining_accounts put in order, if it did
+pub fn with_ordered_remaining_accounts<'a, 'b, 'c, 'info, T: Bumps>(
+    ctx: Context<'a, 'b, 'c, 'info, T>,
+    ordered: &'c Option<Vec<AccountInfo<'info>>>,
+) -> Context<'a, 'b, 'c, 'info, T> {
+    match ordered {
+        Some(ordered) => Context {
+            remaining_accounts: ordered,
+            ..ctx
+        },
+        None => ctx,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use RemainingAccountsTag::*;
+
+    fn tags(accounts: &[AccountInfo]) -> Vec<u8> {
+        accounts.iter().map(|ai| ai.key.to_bytes()[0]).collect()
+    }
+
+    #[test]
+    fn tagged_remaining_accounts_are_put_in_positional_order() {
+        let keys: Vec<Pubkey> = (1..=4u8).map(|i| Pubkey::new_from_array([i; 32])).collect();
+        let mut lamports = [0u64; 4];
+        let mut data = [[0u8; 0]; 4];
+        let owner = Pubkey::default();
+        let accounts: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, &owner, false, 0)
+            })
+            .collect();
+
+        // passed as payer, payment group, referrer
+        let schema = [Payer as u8, 1, Payment as u8, 2, Referrer as u8, 1];
+        let ordered = order_tagged_remaining_accounts(
+            &schema,
+            &accounts,
+            &[ProgramConfig, Payment, Referrer, Payer],
+        )
+        .unwrap();
+        assert_eq!(tags(&ordered), vec![2, 3, 4, 1]);
+
+        // extra args carry the schema at the fixed offset, without one the accounts stay as passed
+        let order = [ProgramConfig, Payment, Referrer, Payer];
+        let extra_args = [&[0; REMAINING_ACCOUNTS_SCHEMA_OFFSET][..], &[3], &schema].concat();
+        let ordered = order_remaining_accounts(&accounts, &extra_args, &order).unwrap();
+        assert_eq!(tags(&ordered.unwrap()), vec![2, 3, 4, 1]);
+        assert!(order_remaining_accounts(&accounts, &[0; 177], &order)
+            .unwrap()
+            .is_none());
+
+        // counts have to add up to the accounts, and every group has to be one the handler takes
+        assert!(
+            order_tagged_remaining_accounts(&schema[..4], &accounts, &[Payment, Payer]).is_err()
+        );
+        assert!(order_tagged_remaining_accounts(&schema, &accounts, &[Payment, Payer]).is_err());
+    }
+
+    #[test]
+    fn malformed_schemas_are_rejected() {
+        assert!(parse_remaining_accounts_schema(&[Payer as u8]).is_err());
+        assert!(parse_remaining_accounts_schema(&[0, 1]).is_err());
+        assert!(parse_remaining_accounts_schema(&[Payer as u8, 2]).is_err());
+        assert!(parse_remaining_accounts_schema(&[Payer as u8, 0]).is_err());
+        assert!(parse_remaining_accounts_schema(&[Payer as u8, 1, Payer as u8, 1]).is_err());
+        assert_eq!(
+            parse_remaining_accounts_schema(&[EscrowAdapter as u8, 5]).unwrap(),
+            vec![(EscrowAdapter, 5)]
+        );
+
+        // the schema is at a fixed offset, whatever the extra args before it
+        let extra_args = |tail: &[u8]| [&[7; REMAINING_ACCOUNTS_SCHEMA_OFFSET][..], tail].concat();
+        assert_eq!(
+            get_remaining_accounts_schema(&[7; REMAINING_ACCOUNTS_SCHEMA_OFFSET]).unwrap(),
+            &[] as &[u8]
+        );
+        assert_eq!(
+            get_remaining_accounts_schema(&extra_args(&[0, 9])).unwrap(),
+            &[] as &[u8]
+        );
+        assert_eq!(
+            get_remaining_accounts_schema(&extra_args(&[1, Payer as u8, 1, 9])).unwrap(),
+            &[Payer as u8, 1]
+        );
+        assert!(get_remaining_accounts_schema(&extra_args(&[2, Payer as u8, 1])).is_err());
+    }
+}
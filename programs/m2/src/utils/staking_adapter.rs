@@ -0,0 +1,102 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        hash::hash,
+        instruction::{AccountMeta, Instruction},
+        program::invoke,
+        program_pack::Pack,
+    },
+};
+
+use crate::{errors::ErrorCode, states::AuctionHouse};
+
+/// The auction house's staking_adapter and its accounts, passed in remaining accounts to list a
+/// token a freeze based staking program keeps frozen. Sell calls the adapter with `unstake`,
+/// encoded like an Anchor instruction, the first 8 bytes of sha256("global:unstake"), with
+/// accounts:
+/// 0. wallet (signer) - the staker, signing sell
+/// 1. token_account (writable) - the staked token account, thawed by the adapter
+/// 2. token_mint
+/// 3. onwards, the adapter's own accounts, as passed after the adapter program in remaining accounts
+///
+/// Like escrow adapters, the staking adapter is vetted by the auction house authority. Sell checks
+/// the token account is thawed once it returns.
+pub struct StakingAdapterAccounts<'a, 'info> {
+    pub program: &'a AccountInfo<'info>,
+    pub accounts: &'a [AccountInfo<'info>],
+}
+
+/// Finds the auction house's staking adapter in remaining accounts and splits it, with the
+/// adapter's own accounts after it, off the end of remaining accounts
+pub fn split_staking_adapter_from_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+    auction_house: &AuctionHouse,
+) -> (
+    &'a [AccountInfo<'info>],
+    Option<StakingAdapterAccounts<'a, 'info>>,
+) {
+    if auction_house.staking_adapter == Pubkey::default() {
+        return (remaining_accounts, None);
+    }
+    match remaining_accounts
+        .iter()
+        .position(|ai| *ai.key == auction_house.staking_adapter && ai.executable)
+    {
+        Some(position) => (
+            &remaining_accounts[..position],
+            Some(StakingAdapterAccounts {
+                program: &remaining_accounts[position],
+                accounts: &remaining_accounts[position + 1..],
+            }),
+        ),
+        None => (remaining_accounts, None),
+    }
+}
+
+fn is_frozen(token_account: &AccountInfo) -> Result<bool> {
+    Ok(spl_token::state::Account::unpack(&token_account.try_borrow_data()?)?.is_frozen())
+}
+
+/// Makes sure token_account isn't frozen, unstaking it through the staking adapter if it is and
+/// one is passed in
+pub fn assert_not_frozen_or_unstake<'info>(
+    token_account: &AccountInfo<'info>,
+    token_mint: &AccountInfo<'info>,
+    wallet: &AccountInfo<'info>,
+    staking_adapter: Option<&StakingAdapterAccounts<'_, 'info>>,
+) -> Result<()> {
+    if !is_frozen(token_account)? {
+        return Ok(());
+    }
+    let staking_adapter = staking_adapter.ok_or(ErrorCode::TokenAccountFrozen)?;
+
+    let mut metas = vec![
+        AccountMeta::new_readonly(*wallet.key, true),
+        AccountMeta::new(*token_account.key, false),
+        AccountMeta::new_readonly(*token_mint.key, false),
+    ];
+    let mut infos = vec![wallet.clone(), token_account.clone(), token_mint.clone()];
+    for ai in staking_adapter.accounts {
+        metas.push(if ai.is_writable {
+            AccountMeta::new(*ai.key, ai.is_signer)
+        } else {
+            AccountMeta::new_readonly(*ai.key, ai.is_signer)
+        });
+        infos.push(ai.clone());
+    }
+    infos.push(staking_adapter.program.clone());
+    invoke(
+        &Instruction {
+            program_id: *staking_adapter.program.key,
+            accounts: metas,
+            data: hash(b"global:unstake").to_bytes()[..8].to_vec(),
+        },
+        &infos,
+    )?;
+
+    if is_frozen(token_account)? {
+        return Err(ErrorCode::TokenAccountFrozen.into());
+    }
+    msg!("{{\"unstaked\":\"{}\"}}", token_account.key);
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        hash::hash,
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+};
+
+use crate::{
+    errors::ErrorCode,
+    states::{BidArgs, ProgramConfig, SellArgs},
+    utils::{
+        assert_is_ata, assert_keys_equal, assert_owned_by, find_remaining_account,
+        get_program_config_address, unpack_token_account,
+    },
+};
+
+/// The deployment's swap adapter, ProgramConfig.swap_adapter, converts the SPL escrow of a bid
+/// into the SOL a listing asks for when the bid fills it. Execute calls it with `swap_exact_out`,
+/// encoded like an Anchor instruction, the first 8 bytes of sha256("global:swap_exact_out")
+/// followed by amount_out and max_amount_in as u64 LE, with accounts:
+/// 0. escrow_payment_account (signer, writable) - the buyer's escrow PDA, receives amount_out lamports
+/// 1. payment_source_token_account (writable) - the escrow's token account, spends at most max_amount_in
+/// 2. payment_mint
+/// 3. onwards, the adapter's own accounts, as passed after payment_source_token_account in remaining accounts
+///
+/// The escrow signs for its token account, so the adapter is vetted by the ProgramConfig authority
+/// like escrow adapters are by the auction house's. Execute checks both balances once it returns.
+pub struct SwapAdapterAccounts<'a, 'info> {
+    pub program: &'a AccountInfo<'info>,
+    pub payment_mint: &'a AccountInfo<'info>,
+    pub payment_source_token_account: &'a AccountInfo<'info>,
+    pub accounts: &'a [AccountInfo<'info>],
+}
+
+fn swap_exact_out_data(amount_out: u64, max_amount_in: u64) -> Vec<u8> {
+    let mut data = hash(b"global:swap_exact_out").to_bytes()[..8].to_vec();
+    data.extend_from_slice(&amount_out.to_le_bytes());
+    data.extend_from_slice(&max_amount_in.to_le_bytes());
+    data
+}
+
+/// Whether a bid fills a listing by swapping its escrow into SOL: the bid opted into swaps and
+/// the listing doesn't take the bid's payment mint. The listing still has to take SOL.
+pub fn is_swap_fill(bid_args: &BidArgs, sell_args: &SellArgs) -> bool {
+    bid_args.swap_max_slippage_bp > 0
+        && bid_args.payment_mint != Pubkey::default()
+        && sell_args.price_for(&bid_args.payment_mint).is_none()
+}
+
+/// Finds the ProgramConfig and its swap adapter in remaining accounts, the adapter followed by
+/// the payment mint, the escrow's token account and the adapter's own accounts
+pub fn get_swap_adapter_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<SwapAdapterAccounts<'a, 'info>> {
+    let program_config = find_remaining_account(remaining_accounts, &get_program_config_address())
+        .ok_or(ErrorCode::MissingRemainingAccount)?;
+    assert_owned_by(program_config, &crate::ID)?;
+    let swap_adapter =
+        ProgramConfig::try_deserialize(&mut &program_config.try_borrow_data()?[..])?.swap_adapter;
+    if swap_adapter == Pubkey::default() {
+        return Err(ErrorCode::SwapAdapterNotAllowed.into());
+    }
+    let position = remaining_accounts
+        .iter()
+        .position(|ai| *ai.key == swap_adapter && ai.executable)
+        .ok_or(ErrorCode::SwapAdapterNotAllowed)?;
+    match &remaining_accounts[position..] {
+        [program, payment_mint, payment_source_token_account, accounts @ ..] => {
+            Ok(SwapAdapterAccounts {
+                program,
+                payment_mint,
+                payment_source_token_account,
+                accounts,
+            })
+        }
+        _ => Err(ErrorCode::MissingRemainingAccount.into()),
+    }
+}
+
+/// Has the swap adapter pay amount_out lamports into the buyer's escrow PDA out of the bid's
+/// escrowed payment mint, spending at most the bid's price plus its max slippage. The sale then
+/// settles like any other SOL bid. Returns the amount of the payment mint spent.
+pub fn swap_into_escrow<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    bid_args: &BidArgs,
+    amount_out: u64,
+    now: i64,
+    escrow_payment_account: &AccountInfo<'info>,
+    escrow_signer_seeds: &[&[&[u8]]],
+) -> Result<u64> {
+    let SwapAdapterAccounts {
+        program,
+        payment_mint,
+        payment_source_token_account,
+        accounts,
+    } = get_swap_adapter_accounts(remaining_accounts)?;
+    assert_keys_equal(payment_mint.key, &bid_args.payment_mint)?;
+    let amount_before = assert_is_ata(
+        payment_source_token_account,
+        escrow_payment_account.key,
+        payment_mint.key,
+        escrow_payment_account.key,
+    )?
+    .amount;
+    let lamports_before = escrow_payment_account.lamports();
+    let max_amount_in = bid_args.swap_max_amount_in(now);
+
+    let mut metas = vec![
+        AccountMeta::new(*escrow_payment_account.key, true),
+        AccountMeta::new(*payment_source_token_account.key, false),
+        AccountMeta::new_readonly(*payment_mint.key, false),
+    ];
+    let mut infos = vec![
+        escrow_payment_account.clone(),
+        payment_source_token_account.clone(),
+        payment_mint.clone(),
+    ];
+    for ai in accounts {
+        metas.push(if ai.is_writable {
+            AccountMeta::new(*ai.key, ai.is_signer)
+        } else {
+            AccountMeta::new_readonly(*ai.key, ai.is_signer)
+        });
+        infos.push(ai.clone());
+    }
+    infos.push(program.clone());
+
+    invoke_signed(
+        &Instruction {
+            program_id: *program.key,
+            accounts: metas,
+            data: swap_exact_out_data(amount_out, max_amount_in),
+        },
+        &infos,
+        escrow_signer_seeds,
+    )?;
+
+    let amount_in =
+        amount_before.saturating_sub(unpack_token_account(payment_source_token_account)?.amount);
+    let expected_lamports = lamports_before
+        .checked_add(amount_out)
+        .ok_or(ErrorCode::NumericalOverflow)?;
+    if amount_in > max_amount_in || escrow_payment_account.lamports() < expected_lamports {
+        return Err(ErrorCode::SwapSlippageExceeded.into());
+    }
+    Ok(amount_in)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_fills_are_bounded_by_the_bid_price_and_its_slippage() {
+        let usdc = Pubkey::new_unique();
+        let bid_args = BidArgs {
+            buyer_price: 150_000_000,
+            token_size: 1,
+            payment_mint: usdc,
+            swap_max_slippage_bp: 100,
+            ..Default::default()
+        };
+        assert_eq!(bid_args.swap_max_amount_in(0), 151_500_000);
+
+        // only a listing that doesn't take the bid's mint is filled through a swap
+        let sol_listing = SellArgs::default();
+        assert!(is_swap_fill(&bid_args, &sol_listing));
+        let usdc_listing = SellArgs {
+            payment_mint: usdc,
+            ..Default::default()
+        };
+        assert!(!is_swap_fill(&bid_args, &usdc_listing));
+        assert!(!is_swap_fill(
+            &BidArgs {
+                swap_max_slippage_bp: 0,
+                ..bid_args.clone()
+            },
+            &sol_listing
+        ));
+
+        let data = swap_exact_out_data(7, 9);
+        assert_eq!(data.len(), 24);
+        assert_eq!(&data[8..16], &7u64.to_le_bytes());
+        assert_eq!(&data[16..], &9u64.to_le_bytes());
+    }
+}
@@ -1,22 +1,43 @@
-use std::slice::Iter;
+use std::{convert::TryFrom, slice::Iter};
 
 use anchor_lang::prelude::*;
 use mpl_token_metadata::accounts::Metadata;
 use open_creator_protocol::state::Policy;
 use solana_program::{
     program::{invoke, invoke_signed},
-    system_instruction,
+    system_instruction, system_program,
 };
 
-use super::{assert_initialized, assert_is_ata, assert_keys_equal, is_token_owner, make_ata};
-use crate::errors::ErrorCode;
+use spl_associated_token_account::{
+    get_associated_token_address, get_associated_token_address_with_program_id,
+};
+
+use super::{
+    assert_initialized, assert_is_ata, assert_keys_equal, assert_owned_by,
+    create_or_allocate_account_raw, find_remaining_account, is_token_owner, make_ata,
+    unpack_token_account,
+};
+use crate::{
+    constants::{
+        ESCROW_TOP_UP, HOUSE_METRICS, MATCH_BOUNTY_BP, MAX_MAKER_FEE_BP, MAX_TAKER_FEE_BP,
+        PENDING_ROYALTIES, PREFIX, PROCEEDS_SPLIT, ROYALTY_OVERRIDE,
+    },
+    errors::ErrorCode,
+    states::{
+        AuctionHouse, BidArgs, EscrowTopUp, HouseMetrics, MetricsTotals, PendingRoyalties,
+        ProceedsSplit, RoyaltyOverride, SellArgs,
+    },
+};
 
 pub enum DestinationSpecifier<'refs, 'a> {
     Key(&'refs Pubkey),
     Ai(&'refs AccountInfo<'a>),
 }
 
-/// Transfers token, does some cleanup and checks
+/// Transfers token, does some cleanup and checks. wSOL is handled like any SPL token: a source
+/// owned by a signing wallet is wrapped up to amount from the wallet's SOL and synced, and a
+/// destination created here is unwrapped into its owner if the owner signed, see
+/// wrap_native_source and unwrap_native_destination.
 ///
 /// # Arguments
 /// * `amount` - Amount of token to transfer
@@ -51,6 +72,17 @@ pub fn transfer_token<'refs, 'a>(
         DestinationSpecifier::Key(key) => key,
         DestinationSpecifier::Ai(ai) => ai.key,
     };
+    let is_native = *mint.key == spl_token::native_mint::ID;
+    if is_native {
+        wrap_native_source(
+            *amount,
+            source_authority,
+            source_token_account,
+            token_program,
+            system_program,
+        )?;
+    }
+    let created_destination = destination_token_account.data_is_empty();
     // initialize destination token account if needed
     if destination_token_account.data_is_empty() {
         if let DestinationSpecifier::Ai(owner_ai) = destination_owner {
@@ -133,7 +165,7 @@ pub fn transfer_token<'refs, 'a>(
         }
     }
 
-    assert_is_ata(
+    let destination = assert_is_ata(
         destination_token_account,
         dest_owner_key,
         mint.key,
@@ -142,9 +174,109 @@ pub fn transfer_token<'refs, 'a>(
         } else {
             dest_owner_key
         },
-    )
+    )?;
+    if let (true, true, DestinationSpecifier::Ai(owner_ai)) =
+        (is_native, created_destination, destination_owner)
+    {
+        unwrap_native_destination(
+            payer,
+            owner_ai,
+            destination_token_account,
+            token_program,
+            system_program,
+        )?;
+    }
+    Ok(destination)
+}
+
+/// Tops a wSOL source up to amount out of its owner's SOL if the owner is a signing wallet, and
+/// syncs it so lamports sent to it without a sync_native count too
+fn wrap_native_source<'a>(
+    amount: u64,
+    source_authority: &AccountInfo<'a>,
+    source_token_account: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<()> {
+    let source = unpack_token_account(source_token_account)?;
+    let rent_exempt_reserve = match Option::from(source.is_native) {
+        Some(rent_exempt_reserve) => rent_exempt_reserve,
+        None => return Ok(()),
+    };
+    let wrapped = source_token_account
+        .lamports()
+        .saturating_sub(rent_exempt_reserve);
+    if wrapped < amount
+        && source.owner == *source_authority.key
+        && source_authority.is_signer
+        && source_authority.owner == &system_program::ID
+    {
+        invoke(
+            &system_instruction::transfer(
+                source_authority.key,
+                source_token_account.key,
+                amount - wrapped,
+            ),
+            &[
+                source_authority.clone(),
+                source_token_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+    if source_token_account
+        .lamports()
+        .saturating_sub(rent_exempt_reserve)
+        > source.amount
+    {
+        invoke(
+            &spl_token::instruction::sync_native(token_program.key, source_token_account.key)?,
+            std::slice::from_ref(source_token_account),
+        )?;
+    }
+    Ok(())
+}
+
+/// Closes a wSOL account created for the transfer into its owner when the owner signed, so the
+/// owner gets SOL. The payer gets the account's rent back.
+fn unwrap_native_destination<'a>(
+    payer: &AccountInfo<'a>,
+    owner: &AccountInfo<'a>,
+    destination_token_account: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<()> {
+    if !owner.is_signer || owner.owner != &system_program::ID {
+        return Ok(());
+    }
+    let rent = match Option::from(unpack_token_account(destination_token_account)?.is_native) {
+        Some(rent_exempt_reserve) => rent_exempt_reserve,
+        None => return Ok(()),
+    };
+    invoke(
+        &spl_token::instruction::close_account(
+            token_program.key,
+            destination_token_account.key,
+            owner.key,
+            owner.key,
+            &[],
+        )?,
+        &[destination_token_account.clone(), owner.clone()],
+    )?;
+    if payer.key != owner.key && rent > 0 {
+        invoke(
+            &system_instruction::transfer(owner.key, payer.key, rent),
+            &[owner.clone(), payer.clone(), system_program.clone()],
+        )?;
+    }
+    msg!(
+        "{{\"unwrapped_wsol\":\"{}\"}}",
+        destination_token_account.key
+    );
+    Ok(())
 }
 
+#[derive(Clone, Copy)]
 pub struct TransferListingPaymentSplArgs<'r, 'info> {
     pub payer: &'r AccountInfo<'info>,
     pub buyer: &'r AccountInfo<'info>,
@@ -156,8 +288,552 @@ pub struct TransferListingPaymentSplArgs<'r, 'info> {
 
     pub system_program: &'r AccountInfo<'info>,
     pub token_program: &'r AccountInfo<'info>,
+
+    // signs for payment_treasury_token_account when it pays out a maker rebate
+    pub treasury_signer_seeds: &'r [&'r [&'r [u8]]],
+}
+
+pub struct ProtocolFeeArgs<'r, 'info> {
+    pub share_bp: u16,
+    pub recipient: &'r Pubkey,
+    // the recipient itself when paying in SOL, otherwise the recipient's token account of the payment mint
+    pub destination: &'r AccountInfo<'info>,
+}
+
+/// Looks up where the protocol's share of the platform fee goes, if the auction house shares fees
+pub fn get_protocol_fee_args<'r, 'info>(
+    auction_house: &'r AuctionHouse,
+    remaining_accounts: &'r [AccountInfo<'info>],
+    payment_mint: Option<&Pubkey>,
+) -> Result<Option<ProtocolFeeArgs<'r, 'info>>> {
+    if auction_house.protocol_fee_share_bp == 0
+        || auction_house.protocol_fee_recipient == Pubkey::default()
+    {
+        return Ok(None);
+    }
+    let recipient = &auction_house.protocol_fee_recipient;
+    let destination_key = match payment_mint {
+        Some(mint) => get_associated_token_address(recipient, mint),
+        None => *recipient,
+    };
+    let destination = find_remaining_account(remaining_accounts, &destination_key)
+        .ok_or(ErrorCode::MissingRemainingAccount)?;
+    Ok(Some(ProtocolFeeArgs {
+        share_bp: auction_house.protocol_fee_share_bp,
+        recipient,
+        destination,
+    }))
+}
+
+pub struct HouseMetricsArgs<'r, 'info> {
+    pub house_metrics: &'r AccountInfo<'info>,
+    pub royalty: u64,
+}
+
+/// Looks up the auction house's metrics account for the payment mint, if the caller passed it in
+pub fn get_house_metrics_args<'r, 'info>(
+    auction_house_key: &Pubkey,
+    remaining_accounts: &'r [AccountInfo<'info>],
+    payment_mint: &Pubkey,
+    royalty: u64,
+) -> Option<HouseMetricsArgs<'r, 'info>> {
+    let (house_metrics_key, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            HOUSE_METRICS.as_bytes(),
+            auction_house_key.as_ref(),
+            payment_mint.as_ref(),
+        ],
+        &crate::ID,
+    );
+    find_remaining_account(remaining_accounts, &house_metrics_key).map(|house_metrics| {
+        HouseMetricsArgs {
+            house_metrics,
+            royalty,
+        }
+    })
+}
+
+pub struct WsolProceedsArgs<'r, 'info> {
+    pub payer: &'r AccountInfo<'info>,
+    pub seller_wsol_token_account: &'r AccountInfo<'info>,
+    pub native_mint: &'r AccountInfo<'info>,
+    pub system_program: &'r AccountInfo<'info>,
+    pub token_program: &'r AccountInfo<'info>,
+}
+
+/// Looks up the seller's wSOL ATA and the native mint if the listing asks for SOL proceeds as wSOL
+/// and the sale settles in SOL, payment_mint being the mint it settles in
+pub fn get_wsol_proceeds_args<'r, 'info>(
+    sell_args: &SellArgs,
+    payment_mint: &Pubkey,
+    remaining_accounts: &'r [AccountInfo<'info>],
+    payer: &'r AccountInfo<'info>,
+    system_program: &'r AccountInfo<'info>,
+    token_program: &'r AccountInfo<'info>,
+) -> Result<Option<WsolProceedsArgs<'r, 'info>>> {
+    if !sell_args.proceeds_as_wsol || *payment_mint != Pubkey::default() {
+        return Ok(None);
+    }
+    let seller_wsol_token_account = find_remaining_account(
+        remaining_accounts,
+        &get_associated_token_address(&sell_args.seller, &spl_token::native_mint::ID),
+    )
+    .ok_or(ErrorCode::MissingRemainingAccount)?;
+    let native_mint = find_remaining_account(remaining_accounts, &spl_token::native_mint::ID)
+        .ok_or(ErrorCode::MissingRemainingAccount)?;
+    Ok(Some(WsolProceedsArgs {
+        payer,
+        seller_wsol_token_account,
+        native_mint,
+        system_program,
+        token_program,
+    }))
+}
+
+pub struct ProceedsSplitArgs<'r, 'info> {
+    pub proceeds_split: ProceedsSplit,
+    pub recipients: Vec<&'r AccountInfo<'info>>, // in the order of the split's used recipients
+}
+
+/// Looks up the seller's proceeds split and its recipients, if the caller passed the split in
+pub fn get_proceeds_split_args<'r, 'info>(
+    seller: &Pubkey,
+    remaining_accounts: &'r [AccountInfo<'info>],
+) -> Result<Option<ProceedsSplitArgs<'r, 'info>>> {
+    let (proceeds_split_key, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            PROCEEDS_SPLIT.as_bytes(),
+            seller.as_ref(),
+        ],
+        &crate::ID,
+    );
+    let proceeds_split = match find_remaining_account(remaining_accounts, &proceeds_split_key) {
+        Some(proceeds_split) => proceeds_split,
+        None => return Ok(None),
+    };
+    assert_owned_by(proceeds_split, &crate::ID)?;
+    let proceeds_split =
+        ProceedsSplit::try_deserialize(&mut &proceeds_split.try_borrow_data()?[..])?;
+    let recipients = proceeds_split
+        .used_recipients()
+        .map(|r| {
+            find_remaining_account(remaining_accounts, &r.address)
+                .ok_or(ErrorCode::MissingRemainingAccount.into())
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Some(ProceedsSplitArgs {
+        proceeds_split,
+        recipients,
+    }))
+}
+
+pub struct EscrowTopUpArgs<'r, 'info> {
+    pub escrow_top_up: &'r AccountInfo<'info>,
+    pub bump: u8,
+    pub funding_token_account: &'r AccountInfo<'info>,
+    /// The payment mint, the native mint for SOL escrows
+    pub mint: &'r AccountInfo<'info>,
+    /// The escrow's ATA of mint, a wSOL ATA for SOL escrows is unwrapped into the escrow
+    pub escrow_token_account: &'r AccountInfo<'info>,
+}
+
+impl<'r, 'info> EscrowTopUpArgs<'r, 'info> {
+    /// Checks the funding token account still delegates enough to the escrow top-up for shortfall
+    pub fn assert_covers(&self, shortfall: u64) -> Result<()> {
+        let funding_token_account: spl_token::state::Account =
+            assert_initialized(self.funding_token_account)?;
+        assert_keys_equal(&funding_token_account.mint, self.mint.key)?;
+        if funding_token_account.delegate != Some(*self.escrow_top_up.key).into()
+            || funding_token_account.delegated_amount < shortfall
+        {
+            return Err(ErrorCode::EscrowTopUpExceeded.into());
+        }
+        Ok(())
+    }
+}
+
+/// Looks up the buyer's escrow top-up and its funding token account, if the caller passed it in,
+/// along with the payment mint and the escrow's token account for it, the native mint and the
+/// escrow's wSOL ATA for SOL escrows
+pub fn get_escrow_top_up_args<'r, 'info>(
+    auction_house_key: &Pubkey,
+    buyer: &Pubkey,
+    escrow_payment_account: &Pubkey,
+    remaining_accounts: &'r [AccountInfo<'info>],
+    payment_mint: &Pubkey,
+) -> Result<Option<EscrowTopUpArgs<'r, 'info>>> {
+    let (escrow_top_up_key, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            ESCROW_TOP_UP.as_bytes(),
+            auction_house_key.as_ref(),
+            buyer.as_ref(),
+        ],
+        &crate::ID,
+    );
+    let escrow_top_up = match find_remaining_account(remaining_accounts, &escrow_top_up_key) {
+        Some(escrow_top_up) => escrow_top_up,
+        None => return Ok(None),
+    };
+    assert_owned_by(escrow_top_up, &crate::ID)?;
+    let state = EscrowTopUp::try_deserialize(&mut &escrow_top_up.try_borrow_data()?[..])?;
+    let mint_key = if *payment_mint == Pubkey::default() {
+        spl_token::native_mint::ID
+    } else {
+        *payment_mint
+    };
+    let find = |key: &Pubkey| {
+        find_remaining_account(remaining_accounts, key).ok_or(ErrorCode::MissingRemainingAccount)
+    };
+    Ok(Some(EscrowTopUpArgs {
+        escrow_top_up,
+        bump: state.bump,
+        funding_token_account: find(&state.funding_token_account)?,
+        mint: find(&mint_key)?,
+        escrow_token_account: find(&get_associated_token_address(
+            escrow_payment_account,
+            &mint_key,
+        ))?,
+    }))
+}
+
+/// Pulls shortfall into the buyer's escrow from the escrow top-up's funding token account, the
+/// escrow top-up signing as its delegate. A SOL escrow gets it as wSOL in its wSOL ATA, which is
+/// then closed into the escrow, the payer getting back the ATA's rent if it paid for it.
+#[allow(clippy::too_many_arguments)]
+pub fn top_up_escrow<'r, 'info>(
+    args: &EscrowTopUpArgs<'r, 'info>,
+    shortfall: u64,
+    auction_house_key: &Pubkey,
+    buyer: &Pubkey,
+    escrow_payment_account: &'r AccountInfo<'info>,
+    payer: &'r AccountInfo<'info>,
+    token_program: &'r AccountInfo<'info>,
+    system_program: &'r AccountInfo<'info>,
+    escrow_signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    args.assert_covers(shortfall)?;
+    let created_escrow_token_account = args.escrow_token_account.data_is_empty();
+    transfer_token(
+        &shortfall,
+        payer,
+        args.escrow_top_up,
+        payer,
+        None,
+        DestinationSpecifier::Ai(escrow_payment_account),
+        args.mint,
+        args.funding_token_account,
+        args.escrow_token_account,
+        token_program,
+        system_program,
+        None,
+        &[&[
+            PREFIX.as_bytes(),
+            ESCROW_TOP_UP.as_bytes(),
+            auction_house_key.as_ref(),
+            buyer.as_ref(),
+            &[args.bump],
+        ]],
+    )?;
+
+    if *args.mint.key == spl_token::native_mint::ID {
+        // the rent of the ATA on top of the unwrapped shortfall
+        let rent = args
+            .escrow_token_account
+            .lamports()
+            .saturating_sub(shortfall);
+        invoke_signed(
+            &spl_token::instruction::close_account(
+                token_program.key,
+                args.escrow_token_account.key,
+                escrow_payment_account.key,
+                escrow_payment_account.key,
+                &[],
+            )?,
+            &[
+                args.escrow_token_account.clone(),
+                escrow_payment_account.clone(),
+            ],
+            escrow_signer_seeds,
+        )?;
+        if created_escrow_token_account {
+            invoke_signed(
+                &system_instruction::transfer(escrow_payment_account.key, payer.key, rent),
+                &[escrow_payment_account.clone(), payer.clone()],
+                escrow_signer_seeds,
+            )?;
+        }
+    }
+
+    msg!("{{\"escrow_top_up\":{}}}", shortfall);
+    Ok(())
+}
+
+/// What a listing sale moves between the buyer, the seller and the house
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListingPaymentAmounts {
+    /// Negative when the maker gets a rebate
+    pub maker_fee: i64,
+    pub taker_fee: u64,
+    pub seller_will_get_from_buyer: u64,
+    pub total_platform_fee: u64,
+    /// The protocol's share of total_platform_fee, the rest goes to the treasury
+    pub protocol_fee: u64,
 }
 
+/// Fees are rounded toward zero, so the buyer's escrow never pays more than the exact bp of the
+/// price. Amounts that don't fit, e.g. a maker rebate larger than the seller's proceeds, are an
+/// error instead of wrapping.
+pub fn get_listing_payment_amounts(
+    buyer_price: u64,
+    actual_maker_fee_bp: i16,
+    actual_taker_fee_bp: u16,
+    seller_is_taker: bool,
+    protocol_fee_share_bp: u16,
+) -> Result<ListingPaymentAmounts> {
+    let maker_fee = i64::try_from(
+        (buyer_price as i128)
+            .checked_mul(actual_maker_fee_bp as i128)
+            .ok_or(ErrorCode::NumericalOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::NumericalOverflow)?,
+    )
+    .map_err(|_| ErrorCode::NumericalOverflow)?;
+    let taker_fee = u64::try_from(
+        (buyer_price as u128)
+            .checked_mul(actual_taker_fee_bp as u128)
+            .ok_or(ErrorCode::NumericalOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::NumericalOverflow)?,
+    )
+    .map_err(|_| ErrorCode::NumericalOverflow)?;
+    let seller_will_get_from_buyer = u64::try_from(if seller_is_taker {
+        (buyer_price as i128)
+            .checked_add(maker_fee as i128)
+            .ok_or(ErrorCode::NumericalOverflow)?
+    } else {
+        (buyer_price as i128)
+            .checked_sub(maker_fee as i128)
+            .ok_or(ErrorCode::NumericalOverflow)?
+    })
+    .map_err(|_| ErrorCode::NumericalOverflow)?;
+    // a rebate larger than the taker fee would have the house pay out of nothing
+    let total_platform_fee = u64::try_from(
+        (maker_fee as i128)
+            .checked_add(taker_fee as i128)
+            .ok_or(ErrorCode::NumericalOverflow)?,
+    )
+    .map_err(|_| ErrorCode::InvalidPlatformFeeBp)?;
+    let protocol_fee = (total_platform_fee as u128)
+        .checked_mul(protocol_fee_share_bp as u128)
+        .ok_or(ErrorCode::NumericalOverflow)?
+        .checked_div(10000)
+        .ok_or(ErrorCode::NumericalOverflow)? as u64;
+    Ok(ListingPaymentAmounts {
+        maker_fee,
+        taker_fee,
+        seller_will_get_from_buyer,
+        total_platform_fee,
+        protocol_fee,
+    })
+}
+
+/// What token_size units cost at unit_price, listings and bids are priced per unit
+pub fn get_total_price(unit_price: u64, token_size: u64) -> Result<u64> {
+    unit_price
+        .checked_mul(token_size)
+        .ok_or(ErrorCode::NumericalOverflow.into())
+}
+
+/// What a sale at buyer_price costs the buyer's escrow in total: the price, the buyer's side of the
+/// platform fee and at most the creator royalty the bid pays. All-in bids are checked against it.
+pub fn get_buyer_total_cost(
+    buyer_price: u64,
+    actual_maker_fee_bp: i16,
+    actual_taker_fee_bp: u16,
+    seller_is_taker: bool,
+    royalty_bp: u16,
+    buyer_creator_royalty_bp: u16,
+) -> Result<u64> {
+    let ListingPaymentAmounts {
+        seller_will_get_from_buyer,
+        total_platform_fee,
+        ..
+    } = get_listing_payment_amounts(
+        buyer_price,
+        actual_maker_fee_bp,
+        actual_taker_fee_bp,
+        seller_is_taker,
+        0,
+    )?;
+    // the taker pays the platform fee, out of the escrow when it's the buyer
+    let payment = if seller_is_taker {
+        seller_will_get_from_buyer
+    } else {
+        seller_will_get_from_buyer
+            .checked_add(total_platform_fee)
+            .ok_or(ErrorCode::NumericalOverflow)?
+    };
+    payment
+        .checked_add(get_creator_royalty(
+            royalty_bp,
+            buyer_price,
+            buyer_creator_royalty_bp,
+        )?)
+        .ok_or(ErrorCode::NumericalOverflow.into())
+}
+
+/// The most a bid locking locked_price can cost its buyer's escrow once filled: the price, the
+/// highest platform fee a fill can charge the buyer and its share of a royalty of up to 100%. An
+/// all-in bid's price already is its total cost.
+pub fn get_bid_locked_cost(
+    locked_price: u64,
+    price_is_all_in: bool,
+    buyer_creator_royalty_bp: u16,
+) -> u64 {
+    if price_is_all_in {
+        return locked_price;
+    }
+    let fee_bp = MAX_TAKER_FEE_BP as u128 + MAX_MAKER_FEE_BP as u128;
+    let royalty_bp = buyer_creator_royalty_bp.min(10_000) as u128;
+    let cost = locked_price as u128 * (10_000 + fee_bp + royalty_bp) / 10_000;
+    cost.min(u64::MAX as u128) as u64
+}
+
+/// The price a fill at buyer_price is checked against the bid with, see BidArgs::fill_price.
+/// royalty_bp is only looked up for all-in bids, buyer_creator_royalty_bp is the share of it the
+/// sale charges the buyer.
+pub fn get_bid_fill_price(
+    bid_args: &BidArgs,
+    buyer_price: u64,
+    actual_maker_fee_bp: i16,
+    actual_taker_fee_bp: u16,
+    seller_is_taker: bool,
+    royalty_bp: impl FnOnce() -> Result<u16>,
+    buyer_creator_royalty_bp: u16,
+) -> Result<u64> {
+    if !bid_args.price_is_all_in {
+        return Ok(buyer_price);
+    }
+    let total_cost = get_buyer_total_cost(
+        buyer_price,
+        actual_maker_fee_bp,
+        actual_taker_fee_bp,
+        seller_is_taker,
+        royalty_bp()?,
+        buyer_creator_royalty_bp,
+    )?;
+    bid_args.fill_price(buyer_price, total_cost, Clock::get()?.unix_timestamp)
+}
+
+/// The total price an all-in bid's fill sells token_size units at, worked out backwards from the
+/// bid's all-in total: the highest price whose total cost to the buyer, see get_buyer_total_cost,
+/// fits in it. royalty_bp is looked up at each candidate unit price.
+pub fn get_all_in_total_price(
+    all_in_total: u64,
+    token_size: u64,
+    actual_maker_fee_bp: i16,
+    actual_taker_fee_bp: u16,
+    seller_is_taker: bool,
+    royalty_bp: impl Fn(u64) -> Result<u16>,
+    buyer_creator_royalty_bp: u16,
+) -> Result<u64> {
+    // a maker rebate is at most MAX_MAKER_FEE_BP, the price never gets past twice the total
+    let (mut low, mut high) = (0, all_in_total.saturating_mul(2));
+    while low < high {
+        let price = high - (high - low) / 2;
+        let total_cost = get_buyer_total_cost(
+            price,
+            actual_maker_fee_bp,
+            actual_taker_fee_bp,
+            seller_is_taker,
+            royalty_bp(price / token_size.max(1))?,
+            buyer_creator_royalty_bp,
+        )?;
+        if total_cost <= all_in_total {
+            low = price;
+        } else {
+            high = price - 1;
+        }
+    }
+    Ok(low)
+}
+
+/// What's left of an all-in bid's total once its fill paid the sale at total_price, see
+/// get_all_in_total_price, with royalty the creators were actually paid. It's the rounding of the
+/// fees and royalty, so the escrow is debited exactly the bid's price once it's paid out.
+pub fn get_all_in_remainder(
+    all_in_total: u64,
+    total_price: u64,
+    actual_maker_fee_bp: i16,
+    actual_taker_fee_bp: u16,
+    seller_is_taker: bool,
+    royalty: u64,
+) -> Result<u64> {
+    get_buyer_total_cost(
+        total_price,
+        actual_maker_fee_bp,
+        actual_taker_fee_bp,
+        seller_is_taker,
+        0,
+        0,
+    )?
+    .checked_add(royalty)
+    .and_then(|paid| all_in_total.checked_sub(paid))
+    .ok_or(ErrorCode::NumericalOverflow.into())
+}
+
+/// Pays the treasury an all-in bid's remainder, see get_all_in_remainder, out of the buyer's
+/// escrow, in the payment mint when the sale is paid in SPL
+pub fn pay_all_in_remainder<'info>(
+    remainder: u64,
+    escrow_payment_account: &AccountInfo<'info>,
+    auction_house_treasury: &AccountInfo<'info>,
+    listing_spl_args: Option<TransferListingPaymentSplArgs<'_, 'info>>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if remainder == 0 {
+        return Ok(());
+    }
+    match listing_spl_args {
+        Some(spl_args) => {
+            transfer_token(
+                &remainder,
+                spl_args.payer,
+                escrow_payment_account,
+                spl_args.buyer,
+                None,
+                DestinationSpecifier::Ai(auction_house_treasury),
+                spl_args.mint,
+                spl_args.payment_source_token_account,
+                spl_args.payment_treasury_token_account,
+                spl_args.token_program,
+                spl_args.system_program,
+                None,
+                signer_seeds,
+            )?;
+        }
+        None => {
+            invoke_signed(
+                &system_instruction::transfer(
+                    escrow_payment_account.key,
+                    auction_house_treasury.key,
+                    remainder,
+                ),
+                &[
+                    escrow_payment_account.clone(),
+                    auction_house_treasury.clone(),
+                ],
+                signer_seeds,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn transfer_listing_payment<'info>(
     buyer_price: u64,
     actual_maker_fee_bp: i16,
@@ -167,6 +843,11 @@ pub fn transfer_listing_payment<'info>(
     escrow_payment_account: &AccountInfo<'info>,
     auction_house_treasury: &AccountInfo<'info>,
     listing_spl_args: Option<TransferListingPaymentSplArgs<'_, 'info>>,
+    protocol_fee_args: Option<ProtocolFeeArgs<'_, 'info>>,
+    house_metrics_args: Option<HouseMetricsArgs<'_, 'info>>,
+    wsol_proceeds_args: Option<WsolProceedsArgs<'_, 'info>>,
+    proceeds_split_args: Option<ProceedsSplitArgs<'_, 'info>>,
+    loan_vault: Option<&AccountInfo<'info>>,
     signer_seeds: &[&[&[u8]]],
 ) -> Result<(i64, u64)> {
     // payer pays maker/taker fees
@@ -179,32 +860,56 @@ pub fn transfer_listing_payment<'info>(
     //   buyer as taker needs to pay (args.price + taker_fee + royalty)
     //   seller gets (args.price - maker_fee) from buyer
     // royalty is also paid ON TOP of the price
+    // in SPL a maker rebate (negative maker fee) isn't netted into the buyer/seller amounts, the
+    // full taker fee goes to treasury and the treasury token account pays the rebate to the maker
+    // if a protocol fee share is configured, that share of the platform fee goes to the protocol instead of the treasury
+    // a proceeds split fans out what the seller nets, after the fees it pays as taker
 
-    let maker_fee = (buyer_price as i128)
-        .checked_mul(actual_maker_fee_bp as i128)
-        .ok_or(ErrorCode::NumericalOverflow)?
-        .checked_div(10000)
-        .ok_or(ErrorCode::NumericalOverflow)? as i64;
-    let taker_fee = (buyer_price as u128)
-        .checked_mul(actual_taker_fee_bp as u128)
-        .ok_or(ErrorCode::NumericalOverflow)?
-        .checked_div(10000)
-        .ok_or(ErrorCode::NumericalOverflow)? as u64;
-    let seller_will_get_from_buyer = if taker.key.eq(seller.key) {
-        (buyer_price as i64)
-            .checked_add(maker_fee)
-            .ok_or(ErrorCode::NumericalOverflow)?
-    } else {
-        (buyer_price as i64)
-            .checked_sub(maker_fee)
-            .ok_or(ErrorCode::NumericalOverflow)?
-    } as u64;
-    let total_platform_fee = (maker_fee
-        .checked_add(taker_fee as i64)
-        .ok_or(ErrorCode::NumericalOverflow)?) as u64;
+    let ListingPaymentAmounts {
+        maker_fee,
+        taker_fee,
+        seller_will_get_from_buyer,
+        total_platform_fee,
+        mut protocol_fee,
+    } = get_listing_payment_amounts(
+        buyer_price,
+        actual_maker_fee_bp,
+        actual_taker_fee_bp,
+        taker.key == seller.key,
+        protocol_fee_args.as_ref().map_or(0, |args| args.share_bp),
+    )?;
+
+    if proceeds_split_args.is_some()
+        && (listing_spl_args.is_some() || wsol_proceeds_args.is_some() || loan_vault.is_some())
+    {
+        return Err(ErrorCode::ProceedsSplitNotSupported.into());
+    }
 
     if let Some(listing_spl_args) = &listing_spl_args {
+        if loan_vault.is_some() {
+            return Err(ErrorCode::LoanRepaymentNotSupported.into());
+        }
         // transfer SPL token
+        let maker_rebate = if maker_fee < 0 {
+            maker_fee.unsigned_abs()
+        } else {
+            0
+        };
+        let (seller_will_get_from_buyer, total_platform_fee) = if maker_rebate > 0 {
+            (buyer_price, taker_fee)
+        } else {
+            (seller_will_get_from_buyer, total_platform_fee)
+        };
+        // fees only go to the treasury's ATA for the payment mint, the payer funds it the first
+        // time a mint pays a fee, so operators don't need to create it beforehand
+        assert_keys_equal(
+            listing_spl_args.payment_treasury_token_account.key,
+            &get_associated_token_address_with_program_id(
+                auction_house_treasury.key,
+                listing_spl_args.mint.key,
+                listing_spl_args.token_program.key,
+            ),
+        )?;
 
         transfer_token(
             &seller_will_get_from_buyer,
@@ -223,85 +928,228 @@ pub fn transfer_listing_payment<'info>(
         )?;
 
         if total_platform_fee > 0 {
-            if taker.key == seller.key {
+            let (fee_source_authority, fee_close_receiver, fee_source_token_account, fee_seeds) =
+                if taker.key == seller.key {
+                    (
+                        taker,
+                        taker,
+                        listing_spl_args.payment_seller_token_account,
+                        &[] as &[&[&[u8]]],
+                    )
+                } else {
+                    (
+                        escrow_payment_account,
+                        listing_spl_args.buyer,
+                        listing_spl_args.payment_source_token_account,
+                        signer_seeds,
+                    )
+                };
+            let treasury_fee = total_platform_fee
+                .checked_sub(protocol_fee)
+                .ok_or(ErrorCode::NumericalOverflow)?;
+            if treasury_fee > 0 {
                 transfer_token(
-                    &total_platform_fee,
+                    &treasury_fee,
                     listing_spl_args.payer,
-                    taker,
-                    taker,
+                    fee_source_authority,
+                    fee_close_receiver,
                     None,
                     DestinationSpecifier::Ai(auction_house_treasury),
                     listing_spl_args.mint,
-                    listing_spl_args.payment_seller_token_account,
+                    fee_source_token_account,
                     listing_spl_args.payment_treasury_token_account,
                     listing_spl_args.token_program,
                     listing_spl_args.system_program,
                     None,
-                    &[],
+                    fee_seeds,
                 )?;
-            } else {
+            }
+            if let (Some(protocol_fee_args), true) = (&protocol_fee_args, protocol_fee > 0) {
                 transfer_token(
-                    &total_platform_fee,
+                    &protocol_fee,
                     listing_spl_args.payer,
-                    escrow_payment_account,
-                    listing_spl_args.buyer,
+                    fee_source_authority,
+                    fee_close_receiver,
                     None,
-                    DestinationSpecifier::Ai(auction_house_treasury),
+                    DestinationSpecifier::Key(protocol_fee_args.recipient),
                     listing_spl_args.mint,
-                    listing_spl_args.payment_source_token_account,
-                    listing_spl_args.payment_treasury_token_account,
+                    fee_source_token_account,
+                    protocol_fee_args.destination,
                     listing_spl_args.token_program,
                     listing_spl_args.system_program,
                     None,
-                    signer_seeds,
+                    fee_seeds,
                 )?;
             }
         }
+
+        if maker_rebate > 0 {
+            // the maker is the buyer when the seller takes, the rebate then goes back to the escrow
+            let (maker_token_account, maker_token_account_owner) = if taker.key == seller.key {
+                (
+                    listing_spl_args.payment_source_token_account,
+                    escrow_payment_account,
+                )
+            } else {
+                (listing_spl_args.payment_seller_token_account, seller)
+            };
+            transfer_token(
+                &maker_rebate,
+                listing_spl_args.payer,
+                auction_house_treasury,
+                auction_house_treasury,
+                None,
+                DestinationSpecifier::Ai(maker_token_account_owner),
+                listing_spl_args.mint,
+                listing_spl_args.payment_treasury_token_account,
+                maker_token_account,
+                listing_spl_args.token_program,
+                listing_spl_args.system_program,
+                None,
+                listing_spl_args.treasury_signer_seeds,
+            )?;
+        }
     } else {
+        // the loan program is paid in SOL, it pays the seller what's left in SOL too
+        if loan_vault.is_some() && wsol_proceeds_args.is_some() {
+            return Err(ErrorCode::LoanRepaymentNotSupported.into());
+        }
         // transfer native SOL
-        invoke_signed(
-            &system_instruction::transfer(
-                escrow_payment_account.key,
+        if let Some(wsol_proceeds_args) = &wsol_proceeds_args {
+            // into the seller's wSOL ATA, synced so the lamports show up as tokens
+            make_ata(
+                wsol_proceeds_args
+                    .seller_wsol_token_account
+                    .to_account_info(),
+                wsol_proceeds_args.payer.to_account_info(),
+                seller.to_account_info(),
+                wsol_proceeds_args.native_mint.to_account_info(),
+                wsol_proceeds_args.token_program.to_account_info(),
+                wsol_proceeds_args.system_program.to_account_info(),
+            )?;
+            assert_is_ata(
+                wsol_proceeds_args.seller_wsol_token_account,
+                seller.key,
+                &spl_token::native_mint::ID,
                 seller.key,
+            )?;
+            invoke_signed(
+                &system_instruction::transfer(
+                    escrow_payment_account.key,
+                    wsol_proceeds_args.seller_wsol_token_account.key,
+                    seller_will_get_from_buyer,
+                ),
+                &[
+                    escrow_payment_account.to_account_info(),
+                    wsol_proceeds_args
+                        .seller_wsol_token_account
+                        .to_account_info(),
+                ],
+                signer_seeds,
+            )?;
+            invoke(
+                &spl_token::instruction::sync_native(
+                    wsol_proceeds_args.token_program.key,
+                    wsol_proceeds_args.seller_wsol_token_account.key,
+                )?,
+                &[wsol_proceeds_args
+                    .seller_wsol_token_account
+                    .to_account_info()],
+            )?;
+        } else if let Some(proceeds_split_args) = &proceeds_split_args {
+            pay_proceeds_split(
+                proceeds_split_args,
+                escrow_payment_account,
+                seller,
                 seller_will_get_from_buyer,
-            ),
-            &[
-                escrow_payment_account.to_account_info(),
-                seller.to_account_info(),
-            ],
-            signer_seeds,
-        )?;
+                if taker.key == seller.key {
+                    total_platform_fee
+                } else {
+                    0
+                },
+                signer_seeds,
+            )?;
+        } else {
+            // into the loan vault when the proceeds repay the seller's loan first
+            let proceeds_destination = loan_vault.unwrap_or(seller);
+            invoke_signed(
+                &system_instruction::transfer(
+                    escrow_payment_account.key,
+                    proceeds_destination.key,
+                    seller_will_get_from_buyer,
+                ),
+                &[
+                    escrow_payment_account.to_account_info(),
+                    proceeds_destination.to_account_info(),
+                ],
+                signer_seeds,
+            )?;
+        }
 
         if total_platform_fee > 0 {
-            if taker.key == seller.key {
-                invoke(
+            if let Some(protocol_fee_args) = &protocol_fee_args {
+                // too small to make the protocol account rent exempt, the treasury keeps it
+                if protocol_fee + protocol_fee_args.destination.lamports()
+                    < Rent::get()?.minimum_balance(0)
+                {
+                    protocol_fee = 0;
+                }
+            }
+            let (fee_source, fee_seeds) = if taker.key == seller.key {
+                (taker, &[] as &[&[&[u8]]])
+            } else {
+                (escrow_payment_account, signer_seeds)
+            };
+            let treasury_fee = total_platform_fee
+                .checked_sub(protocol_fee)
+                .ok_or(ErrorCode::NumericalOverflow)?;
+            if treasury_fee > 0 {
+                invoke_signed(
                     &system_instruction::transfer(
-                        taker.key,
+                        fee_source.key,
                         auction_house_treasury.key,
-                        total_platform_fee,
+                        treasury_fee,
                     ),
                     &[
-                        taker.to_account_info(),
+                        fee_source.to_account_info(),
                         auction_house_treasury.to_account_info(),
                     ],
+                    fee_seeds,
                 )?;
-            } else {
+            }
+            if let (Some(protocol_fee_args), true) = (&protocol_fee_args, protocol_fee > 0) {
                 invoke_signed(
                     &system_instruction::transfer(
-                        escrow_payment_account.key,
-                        auction_house_treasury.key,
-                        total_platform_fee,
+                        fee_source.key,
+                        protocol_fee_args.destination.key,
+                        protocol_fee,
                     ),
                     &[
-                        escrow_payment_account.to_account_info(),
-                        auction_house_treasury.to_account_info(),
+                        fee_source.to_account_info(),
+                        protocol_fee_args.destination.to_account_info(),
                     ],
-                    signer_seeds,
+                    fee_seeds,
                 )?;
             }
         }
     }
 
+    if let Some(args) = house_metrics_args {
+        assert_owned_by(args.house_metrics, &crate::ID)?;
+        let mut house_metrics =
+            HouseMetrics::try_deserialize(&mut &args.house_metrics.try_borrow_data()?[..])?;
+        house_metrics.record(
+            Clock::get()?.unix_timestamp,
+            &MetricsTotals {
+                volume: buyer_price,
+                maker_fees: maker_fee,
+                taker_fees: taker_fee,
+                royalty: args.royalty,
+            },
+        )?;
+        house_metrics.try_serialize(&mut &mut args.house_metrics.try_borrow_mut_data()?[..])?;
+    }
+
     Ok((maker_fee, taker_fee))
 }
 
@@ -315,16 +1163,258 @@ pub struct TransferCreatorSplArgs<'r, 'info> {
     pub token_program: &'r AccountInfo<'info>,
 }
 
+pub struct PendingRoyaltiesArgs<'r, 'info> {
+    pub pending_royalties: &'r AccountInfo<'info>,
+    pub payer: &'r AccountInfo<'info>,
+    pub auction_house_key: Pubkey,
+    pub token_mint: Pubkey,
+    pub bump: u8,
+}
+
+/// Looks up the mint's PendingRoyalties, if the caller passed it in to have the sale record the
+/// SOL royalties of creators left out of its accounts instead of failing
+pub fn get_pending_royalties_args<'r, 'info>(
+    auction_house_key: &Pubkey,
+    token_mint: &Pubkey,
+    remaining_accounts: &'r [AccountInfo<'info>],
+    payer: &'r AccountInfo<'info>,
+) -> Option<PendingRoyaltiesArgs<'r, 'info>> {
+    let (pending_royalties_key, bump) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            PENDING_ROYALTIES.as_bytes(),
+            auction_house_key.as_ref(),
+            token_mint.as_ref(),
+        ],
+        &crate::ID,
+    );
+    find_remaining_account(remaining_accounts, &pending_royalties_key).map(|pending_royalties| {
+        PendingRoyaltiesArgs {
+            pending_royalties,
+            payer,
+            auction_house_key: *auction_house_key,
+            token_mint: *token_mint,
+            bump,
+        }
+    })
+}
+
+/// The metadata with the recipients of the mint's RoyaltyOverride as its creators, if the caller
+/// passed it in to have the sale pay them the royalty instead
+pub fn get_royalty_override_metadata(
+    metadata: Metadata,
+    token_mint: &Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> Result<Metadata> {
+    let royalty_override_key = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            ROYALTY_OVERRIDE.as_bytes(),
+            token_mint.as_ref(),
+        ],
+        &crate::ID,
+    )
+    .0;
+    match find_remaining_account(remaining_accounts, &royalty_override_key) {
+        Some(royalty_override) => {
+            assert_owned_by(royalty_override, &crate::ID)?;
+            let royalty_override =
+                RoyaltyOverride::try_deserialize(&mut &royalty_override.try_borrow_data()?[..])?;
+            Ok(royalty_override.apply(metadata))
+        }
+        None => Ok(metadata),
+    }
+}
+
+fn load_or_create_pending_royalties(args: &PendingRoyaltiesArgs) -> Result<PendingRoyalties> {
+    let pending_royalties = args.pending_royalties;
+    if !pending_royalties.data_is_empty() {
+        assert_owned_by(pending_royalties, &crate::ID)?;
+        return PendingRoyalties::try_deserialize(&mut &pending_royalties.try_borrow_data()?[..]);
+    }
+    let required_lamports = Rent::get()?
+        .minimum_balance(PendingRoyalties::LEN)
+        .saturating_sub(pending_royalties.lamports());
+    create_or_allocate_account_raw(
+        &crate::ID,
+        pending_royalties,
+        args.payer,
+        &required_lamports,
+        &PendingRoyalties::LEN,
+        &[
+            PREFIX.as_bytes(),
+            PENDING_ROYALTIES.as_bytes(),
+            args.auction_house_key.as_ref(),
+            args.token_mint.as_ref(),
+            &[args.bump],
+        ],
+    )?;
+    Ok(PendingRoyalties {
+        auction_house_key: args.auction_house_key,
+        token_mint: args.token_mint,
+        rent_payer: *args.payer.key,
+        bump: args.bump,
+        ..Default::default()
+    })
+}
+
+/// The royalty bp a sale at total_price pays, before buyer_creator_royalty_bp is applied: the
+/// metadata's seller_fee_basis_points, unless an OCP policy sets a dynamic royalty curve
+pub fn get_royalty_bp(policy: Option<&Policy>, metadata: &Metadata, total_price: u64) -> u16 {
+    match policy.and_then(|p| p.dynamic_royalty.as_ref()) {
+        None => metadata.seller_fee_basis_points,
+        Some(dynamic_royalty) => {
+            dynamic_royalty.get_royalty_bp(total_price, metadata.seller_fee_basis_points)
+        }
+    }
+}
+
+/// The cranker's share of a matched sale's taker fee
+pub fn get_match_bounty(taker_fee: u64) -> Result<u64> {
+    Ok((taker_fee as u128)
+        .checked_mul(MATCH_BOUNTY_BP as u128)
+        .and_then(|v| v.checked_div(10_000))
+        .ok_or(ErrorCode::NumericalOverflow)? as u64)
+}
+
+/// Pays the seller's SOL proceeds out of the escrow, fanned out by its proceeds split. The shares
+/// are of what the seller nets, seller_fee being the platform fee it pays afterwards as the taker.
+fn pay_proceeds_split<'info>(
+    proceeds_split_args: &ProceedsSplitArgs<'_, 'info>,
+    escrow_payment_account: &AccountInfo<'info>,
+    seller: &AccountInfo<'info>,
+    seller_will_get_from_buyer: u64,
+    seller_fee: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let shares = proceeds_split_args
+        .proceeds_split
+        .shares(seller_will_get_from_buyer.saturating_sub(seller_fee));
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    let mut to_seller = seller_will_get_from_buyer;
+    for (recipient, share) in proceeds_split_args.recipients.iter().zip(shares) {
+        // too small to make the recipient rent exempt, the seller keeps it
+        if share == 0 || share + recipient.lamports() < rent_exempt_minimum {
+            continue;
+        }
+        invoke_signed(
+            &system_instruction::transfer(escrow_payment_account.key, recipient.key, share),
+            &[
+                escrow_payment_account.to_account_info(),
+                recipient.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+        to_seller = to_seller
+            .checked_sub(share)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+    }
+    if to_seller > 0 {
+        invoke_signed(
+            &system_instruction::transfer(escrow_payment_account.key, seller.key, to_seller),
+            &[
+                escrow_payment_account.to_account_info(),
+                seller.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+    }
+    Ok(())
+}
+
+/// Pays the cranker of a sale matched by match_orders its bounty out of the treasury, which just
+/// received the taker fee. An SPL sale pays it in the payment mint from the treasury's ATA to
+/// cranker_token_account, the cranker's ATA of the mint, created if needed.
+pub fn pay_match_bounty<'info>(
+    taker_fee: u64,
+    auction_house_treasury: &AccountInfo<'info>,
+    cranker: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    listing_spl_args: Option<(
+        TransferListingPaymentSplArgs<'_, 'info>,
+        &AccountInfo<'info>,
+    )>,
+    treasury_signer_seeds: &[&[&[u8]]],
+) -> Result<u64> {
+    let bounty = get_match_bounty(taker_fee)?;
+    if bounty == 0 {
+        return Ok(0);
+    }
+    if let Some((spl_args, cranker_token_account)) = listing_spl_args {
+        transfer_token(
+            &bounty,
+            cranker,
+            auction_house_treasury,
+            auction_house_treasury,
+            None,
+            DestinationSpecifier::Ai(cranker),
+            spl_args.mint,
+            spl_args.payment_treasury_token_account,
+            cranker_token_account,
+            spl_args.token_program,
+            spl_args.system_program,
+            None,
+            treasury_signer_seeds,
+        )?;
+    } else {
+        invoke_signed(
+            &system_instruction::transfer(auction_house_treasury.key, cranker.key, bounty),
+            &[
+                auction_house_treasury.clone(),
+                cranker.clone(),
+                system_program.clone(),
+            ],
+            treasury_signer_seeds,
+        )?;
+    }
+    Ok(bounty)
+}
+
+/// The royalty a sale at total_price pays the creators in total. Both bps are capped at 10000,
+/// royalty_bp can come from an OCP override and not every bid path bounds the buyer's bp, so the
+/// royalty never exceeds the price.
+pub fn get_creator_royalty(
+    royalty_bp: u16,
+    total_price: u64,
+    buyer_creator_royalty_bp: u16,
+) -> Result<u64> {
+    Ok((royalty_bp.min(10000) as u128)
+        .checked_mul(total_price as u128)
+        .ok_or(ErrorCode::NumericalOverflow)?
+        .checked_div(10000)
+        .ok_or(ErrorCode::NumericalOverflow)?
+        .checked_mul(buyer_creator_royalty_bp.min(10000) as u128)
+        .ok_or(ErrorCode::NumericalOverflow)?
+        .checked_div(10000)
+        .ok_or(ErrorCode::NumericalOverflow)? as u64)
+}
+
+/// A creator's cut of the royalty, rounded down so the cuts never add up to more than it
+pub fn get_creator_fee(total_fee: u64, share: u8) -> Result<u64> {
+    Ok((share as u128)
+        .checked_mul(total_fee as u128)
+        .ok_or(ErrorCode::NumericalOverflow)?
+        .checked_div(100)
+        .ok_or(ErrorCode::NumericalOverflow)? as u64)
+}
+
+// SOL royalties are moved out of the escrow with a single transfer into royalty_conduit, a
+// writable account owned by this program that is closed later in the instruction (the buyer
+// trade state), and split from there to the creators by adjusting lamports directly. With
+// pending_royalties, a SOL creator owed royalty but left out of creator_accounts gets a claim
+// instead, its royalty moved on into the PendingRoyalties for distribute_royalties to pay.
 #[allow(clippy::too_many_arguments)]
 pub fn pay_creator_fees<'r, 'a>(
     creator_accounts: &mut Iter<'r, AccountInfo<'a>>,
     policy: Option<&Account<'a, Policy>>,
     metadata: &'r Metadata,
     escrow_payment_account: &AccountInfo<'a>,
+    royalty_conduit: &AccountInfo<'a>,
     signer_seeds: &[&[&[u8]]],
     total_price: u64,
     buyer_creator_royalty_bp: u16,
     creator_spl_args: Option<TransferCreatorSplArgs<'_, 'a>>,
+    pending_royalties: Option<PendingRoyaltiesArgs<'_, 'a>>,
 ) -> Result<u64> {
     let creators = if let Some(creators) = &metadata.creators {
         creators
@@ -336,38 +1426,77 @@ pub fn pay_creator_fees<'r, 'a>(
         return Ok(0);
     }
 
-    let royalty_bp = match policy {
-        None => metadata.seller_fee_basis_points,
-        Some(p) => match &p.dynamic_royalty {
-            None => metadata.seller_fee_basis_points,
-            Some(dynamic_royalty) => {
-                dynamic_royalty.get_royalty_bp(total_price, metadata.seller_fee_basis_points)
-            }
-        },
-    };
+    let royalty_bp = get_royalty_bp(policy.map(|p| p.as_ref()), metadata, total_price);
 
-    let total_fee = (royalty_bp as u128)
-        .checked_mul(total_price as u128)
-        .ok_or(ErrorCode::NumericalOverflow)?
-        .checked_div(10000)
-        .ok_or(ErrorCode::NumericalOverflow)?
-        .checked_mul(buyer_creator_royalty_bp as u128)
-        .ok_or(ErrorCode::NumericalOverflow)?
-        .checked_div(10000)
-        .ok_or(ErrorCode::NumericalOverflow)? as u64;
+    let total_fee = get_creator_royalty(royalty_bp, total_price, buyer_creator_royalty_bp)?;
     if total_fee == 0 {
         return Ok(0);
     }
+    // SPL sales can list the creators' wallets instead of their ATAs, the ATAs are then derived
+    // from the metadata creators and looked up anywhere after the creator wallets
+    let derive_creator_atas = creator_spl_args.is_some()
+        && creator_accounts.as_slice().first().map(|ai| ai.key) == Some(&creators[0].address);
     let mut total_fee_paid = 0u64;
+    let mut sol_payouts = Vec::with_capacity(creators.len());
+    let mut pending_claims = vec![];
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
     for creator in creators {
-        let pct = creator.share as u128;
-        let creator_fee = pct
-            .checked_mul(total_fee as u128)
-            .ok_or(ErrorCode::NumericalOverflow)?
-            .checked_div(100)
-            .ok_or(ErrorCode::NumericalOverflow)? as u64;
-        let current_creator_info = next_account_info(creator_accounts)?;
+        let creator_fee = get_creator_fee(total_fee, creator.share)?;
+        let ata_mint = creator_spl_args
+            .as_ref()
+            .filter(|_| !derive_creator_atas)
+            .map(|spl_args| spl_args.mint.key);
+        let current_creator_info =
+            match next_creator_account(creator_accounts, &creator.address, ata_mint, creator_fee) {
+                Ok(Some(ai)) => ai,
+                Ok(None) => continue,
+                Err(err)
+                    if err == ErrorCode::MissingCreatorAccount.into()
+                        && pending_royalties.is_some()
+                        && creator_spl_args.is_none() =>
+                {
+                    pending_claims.push((creator.address, creator_fee));
+                    total_fee_paid = total_fee_paid
+                        .checked_add(creator_fee)
+                        .ok_or(ErrorCode::NumericalOverflow)?;
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
         if let Some(spl_args) = &creator_spl_args {
+            if derive_creator_atas {
+                // transfer SPL token, current_creator_info should be the creator's wallet
+                assert_keys_equal(&creator.address, current_creator_info.key)?;
+                if creator_fee == 0 {
+                    continue;
+                }
+                let creator_ata = find_remaining_account(
+                    creator_accounts.as_slice(),
+                    &get_associated_token_address(&creator.address, spl_args.mint.key),
+                )
+                .ok_or(ErrorCode::MissingRemainingAccount)?;
+                transfer_token(
+                    &creator_fee,
+                    spl_args.payer,
+                    escrow_payment_account,
+                    spl_args.buyer,
+                    None,
+                    DestinationSpecifier::Ai(current_creator_info),
+                    spl_args.mint,
+                    spl_args.payment_source_token_account,
+                    creator_ata,
+                    spl_args.token_program,
+                    spl_args.system_program,
+                    None,
+                    signer_seeds,
+                )?;
+                log_royalty_paid(&creator.address, spl_args.mint.key, creator_fee);
+                total_fee_paid = total_fee_paid
+                    .checked_add(creator_fee)
+                    .ok_or(ErrorCode::NumericalOverflow)?;
+                continue;
+            }
+
             // transfer SPL token, current_creator_info should be the creator's ATA
             if creator_fee == 0 {
                 continue;
@@ -397,18 +1526,16 @@ pub fn pay_creator_fees<'r, 'a>(
                 None,
                 signer_seeds,
             )?;
+            log_royalty_paid(&creator.address, spl_args.mint.key, creator_fee);
+            total_fee_paid = total_fee_paid
+                .checked_add(creator_fee)
+                .ok_or(ErrorCode::NumericalOverflow)?;
         } else {
             assert_keys_equal(&creator.address, current_creator_info.key)?;
-            if creator_fee + current_creator_info.lamports() >= Rent::get()?.minimum_balance(0) {
-                invoke_signed(
-                    &system_instruction::transfer(
-                        escrow_payment_account.key,
-                        current_creator_info.key,
-                        creator_fee,
-                    ),
-                    &[escrow_payment_account.clone(), current_creator_info.clone()],
-                    signer_seeds,
-                )?;
+            if creator_fee > 0
+                && creator_fee + current_creator_info.lamports() >= rent_exempt_minimum
+            {
+                sol_payouts.push((current_creator_info, creator_fee));
                 total_fee_paid = total_fee_paid
                     .checked_add(creator_fee)
                     .ok_or(ErrorCode::NumericalOverflow)?;
@@ -416,5 +1543,917 @@ pub fn pay_creator_fees<'r, 'a>(
         }
     }
 
+    // created before lamports are moved around outside of CPIs
+    let pending_royalties = match pending_royalties {
+        Some(args) if !pending_claims.is_empty() => Some((
+            load_or_create_pending_royalties(&args)?,
+            args.pending_royalties,
+        )),
+        _ => None,
+    };
+
+    if !sol_payouts.is_empty() || pending_royalties.is_some() {
+        if royalty_conduit.owner != &crate::ID {
+            return Err(ErrorCode::IncorrectOwner.into());
+        }
+        invoke_signed(
+            &system_instruction::transfer(
+                escrow_payment_account.key,
+                royalty_conduit.key,
+                total_fee_paid,
+            ),
+            &[escrow_payment_account.clone(), royalty_conduit.clone()],
+            signer_seeds,
+        )?;
+        for (creator_info, creator_fee) in sol_payouts {
+            let conduit_lamports = royalty_conduit
+                .lamports()
+                .checked_sub(creator_fee)
+                .ok_or(ErrorCode::NumericalOverflow)?;
+            let creator_lamports = creator_info
+                .lamports()
+                .checked_add(creator_fee)
+                .ok_or(ErrorCode::NumericalOverflow)?;
+            **royalty_conduit.try_borrow_mut_lamports()? = conduit_lamports;
+            **creator_info.try_borrow_mut_lamports()? = creator_lamports;
+            log_royalty_paid(creator_info.key, &Pubkey::default(), creator_fee);
+        }
+    }
+
+    if let Some((mut state, pending_royalties)) = pending_royalties {
+        let mut pending_total = 0u64;
+        for (creator, creator_fee) in pending_claims {
+            state.add_claim(&creator, creator_fee)?;
+            pending_total = pending_total
+                .checked_add(creator_fee)
+                .ok_or(ErrorCode::NumericalOverflow)?;
+            msg!(
+                "{{\"royalty_pending\":{},\"creator\":\"{}\"}}",
+                creator_fee,
+                creator
+            );
+        }
+        let conduit_lamports = royalty_conduit
+            .lamports()
+            .checked_sub(pending_total)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        let pending_lamports = pending_royalties
+            .lamports()
+            .checked_add(pending_total)
+            .ok_or(ErrorCode::NumericalOverflow)?;
+        **royalty_conduit.try_borrow_mut_lamports()? = conduit_lamports;
+        **pending_royalties.try_borrow_mut_lamports()? = pending_lamports;
+        state.try_serialize(&mut &mut pending_royalties.try_borrow_mut_data()?[..])?;
+    }
+
     Ok(total_fee_paid)
 }
+
+/// Takes the next metadata creator's account off creator_accounts: the creator's wallet, or its
+/// token account if ata_mint is set. Creators owed nothing may be left out, so metadata with more
+/// creators than fit in the transaction can still be filled as long as the ones left out are owed 0.
+fn next_creator_account<'r, 'a>(
+    creator_accounts: &mut Iter<'r, AccountInfo<'a>>,
+    creator: &Pubkey,
+    ata_mint: Option<&Pubkey>,
+    creator_fee: u64,
+) -> Result<Option<&'r AccountInfo<'a>>> {
+    let is_creator_account = |ai: &AccountInfo| match ata_mint {
+        // a creator owed royalty is paid into any token account it owns, the transfer checks that
+        Some(mint) => {
+            creator_fee > 0
+                || *ai.key == get_associated_token_address(creator, mint)
+                || is_token_owner(ai, creator).unwrap_or(false)
+        }
+        None => ai.key == creator,
+    };
+    match creator_accounts.as_slice().first() {
+        Some(ai) if is_creator_account(ai) => Ok(creator_accounts.next()),
+        _ if creator_fee == 0 => Ok(None),
+        _ => Err(ErrorCode::MissingCreatorAccount.into()),
+    }
+}
+
+// One line per creator paid, so creators can account for their royalties from the sale
+// transaction itself. The default payment mint is SOL.
+pub fn log_royalty_paid(creator: &Pubkey, payment_mint: &Pubkey, amount: u64) {
+    msg!(
+        "{{\"royalty_paid\":{},\"creator\":\"{}\",\"payment_mint\":\"{}\"}}",
+        amount,
+        creator,
+        payment_mint
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{
+        MAX_MAKER_FEE_BP, MAX_PENDING_ROYALTIES, MAX_PRICE, MAX_PROCEEDS_SPLIT_RECIPIENTS,
+        MAX_ROYALTY_RECIPIENTS, MAX_TAKER_FEE_BP,
+    };
+    use crate::states::{ProceedsSplitRecipient, RoyaltyRecipient};
+    use mpl_token_metadata::types::{Creator, Key};
+    use proptest::prelude::*;
+    use solana_program::{
+        entrypoint::ProgramResult,
+        instruction::Instruction,
+        program_stubs::{set_syscall_stubs, SyscallStubs},
+    };
+    use std::{convert::TryInto, sync::Once};
+
+    /// Runs the system transfers, and the wSOL sync_native and close_account, that the code
+    /// under test invokes, so tests see real balances
+    struct TransferStubs;
+
+    impl SyscallStubs for TransferStubs {
+        fn sol_invoke_signed(
+            &self,
+            instruction: &Instruction,
+            account_infos: &[AccountInfo],
+            _signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            let find = |key: &Pubkey| {
+                account_infos
+                    .iter()
+                    .find(|ai| ai.key == key)
+                    .ok_or(ProgramError::NotEnoughAccountKeys)
+            };
+            if instruction.program_id == system_program::ID && instruction.data[..4] == [2, 0, 0, 0]
+            {
+                let lamports = u64::from_le_bytes(instruction.data[4..12].try_into().unwrap());
+                let from = find(&instruction.accounts[0].pubkey)?;
+                let to = find(&instruction.accounts[1].pubkey)?;
+                **from.try_borrow_mut_lamports()? = from
+                    .lamports()
+                    .checked_sub(lamports)
+                    .ok_or(ProgramError::InsufficientFunds)?;
+                **to.try_borrow_mut_lamports()? += lamports;
+            } else if instruction.program_id == spl_token::ID {
+                let account = find(&instruction.accounts[0].pubkey)?;
+                match spl_token::instruction::TokenInstruction::unpack(&instruction.data)? {
+                    spl_token::instruction::TokenInstruction::SyncNative => {
+                        let mut token_account = unpack_token_account(account)?;
+                        token_account.amount = account.lamports()
+                            - Option::<u64>::from(token_account.is_native).unwrap();
+                        solana_program::program_pack::Pack::pack_into_slice(
+                            &token_account,
+                            &mut account.try_borrow_mut_data()?,
+                        );
+                    }
+                    spl_token::instruction::TokenInstruction::CloseAccount => {
+                        let destination = find(&instruction.accounts[1].pubkey)?;
+                        **destination.try_borrow_mut_lamports()? += account.lamports();
+                        **account.try_borrow_mut_lamports()? = 0;
+                        account.try_borrow_mut_data()?.fill(0);
+                    }
+                    _ => {}
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn install_transfer_stubs() {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            set_syscall_stubs(Box::new(TransferStubs));
+        });
+    }
+
+    fn fee_bps() -> impl Strategy<Value = (i16, u16)> {
+        (0..=MAX_TAKER_FEE_BP).prop_flat_map(|taker_fee_bp| {
+            (
+                -(taker_fee_bp as i16)..=MAX_MAKER_FEE_BP,
+                Just(taker_fee_bp),
+            )
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn listing_payment_conserves_lamports(
+            buyer_price in 1..=MAX_PRICE,
+            (maker_fee_bp, taker_fee_bp) in fee_bps(),
+            seller_is_taker in any::<bool>(),
+        ) {
+            install_transfer_stubs();
+            let keys = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+            let (buyer_key, seller_key, escrow_key, treasury_key) = (&keys[0], &keys[1], &keys[2], &keys[3]);
+            // the seller covers its fees as the taker, the escrow the buyer's side of the trade
+            let (mut buyer_lamports, mut seller_lamports, mut escrow_lamports, mut treasury_lamports) =
+                (0, MAX_PRICE, 2 * MAX_PRICE, 0);
+            let (mut buyer_data, mut seller_data, mut escrow_data, mut treasury_data) =
+                ([0u8; 0], [0u8; 0], [0u8; 0], [0u8; 0]);
+            let owner = system_program::ID;
+            let buyer = AccountInfo::new(buyer_key, true, true, &mut buyer_lamports, &mut buyer_data, &owner, false, 0);
+            let seller = AccountInfo::new(seller_key, true, true, &mut seller_lamports, &mut seller_data, &owner, false, 0);
+            let escrow = AccountInfo::new(escrow_key, false, true, &mut escrow_lamports, &mut escrow_data, &owner, false, 0);
+            let treasury = AccountInfo::new(treasury_key, false, true, &mut treasury_lamports, &mut treasury_data, &owner, false, 0);
+            let accounts = [&buyer, &seller, &escrow, &treasury];
+            let before: Vec<u64> = accounts.iter().map(|ai| ai.lamports()).collect();
+
+            let (maker_fee, taker_fee) = transfer_listing_payment(
+                buyer_price,
+                maker_fee_bp,
+                taker_fee_bp,
+                if seller_is_taker { &seller } else { &buyer },
+                &seller,
+                &escrow,
+                &treasury,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                &[],
+            )
+            .unwrap();
+
+            let after: Vec<u64> = accounts.iter().map(|ai| ai.lamports()).collect();
+            prop_assert_eq!(before.iter().sum::<u64>(), after.iter().sum::<u64>());
+            let delta = |i: usize| after[i] as i128 - before[i] as i128;
+            let expected_maker_fee = buyer_price as i128 * maker_fee_bp as i128 / 10_000;
+            let expected_taker_fee = buyer_price as i128 * taker_fee_bp as i128 / 10_000;
+            prop_assert_eq!(maker_fee as i128, expected_maker_fee);
+            prop_assert_eq!(taker_fee as i128, expected_taker_fee);
+            let fee = expected_maker_fee + expected_taker_fee;
+            // the maker's fee moves the price the seller gets, the taker pays both fees
+            let seller_gets = if seller_is_taker {
+                buyer_price as i128 + expected_maker_fee
+            } else {
+                buyer_price as i128 - expected_maker_fee
+            };
+            prop_assert_eq!(delta(0), 0);
+            prop_assert_eq!(delta(3), fee);
+            if seller_is_taker {
+                prop_assert_eq!(delta(2), -seller_gets);
+                prop_assert_eq!(delta(1), seller_gets - fee);
+            } else {
+                prop_assert_eq!(delta(2), -(seller_gets + fee));
+                prop_assert_eq!(delta(1), seller_gets);
+            }
+        }
+
+        #[test]
+        fn listing_payment_fees_never_exceed_max_bp(
+            buyer_price in 1..=MAX_PRICE,
+            (maker_fee_bp, taker_fee_bp) in fee_bps(),
+            seller_is_taker in any::<bool>(),
+        ) {
+            let amounts = get_listing_payment_amounts(
+                buyer_price,
+                maker_fee_bp,
+                taker_fee_bp,
+                seller_is_taker,
+                0,
+            )
+            .unwrap();
+            let price = buyer_price as u128;
+            prop_assert!(amounts.taker_fee as u128 * 10000 <= price * MAX_TAKER_FEE_BP as u128);
+            prop_assert!(
+                amounts.maker_fee.unsigned_abs() as u128 * 10000
+                    <= price * MAX_MAKER_FEE_BP.unsigned_abs().max(MAX_TAKER_FEE_BP) as u128
+            );
+            prop_assert!(
+                amounts.total_platform_fee as u128 * 10000
+                    <= price * (MAX_MAKER_FEE_BP as u128 + MAX_TAKER_FEE_BP as u128)
+            );
+        }
+
+        #[test]
+        fn buyer_total_cost_is_what_leaves_the_escrow(
+            buyer_price in 1..=MAX_PRICE,
+            (maker_fee_bp, taker_fee_bp) in fee_bps(),
+            seller_is_taker in any::<bool>(),
+            royalty_bp in 0..=10000u16,
+            buyer_creator_royalty_bp in 0..=10000u16,
+        ) {
+            // the taker's fee is on top of the price, a maker fee truncates toward zero
+            let price = buyer_price as i128;
+            let fee = if seller_is_taker {
+                price * maker_fee_bp as i128 / 10000
+            } else {
+                price * taker_fee_bp as i128 / 10000
+            };
+            let royalty = price * royalty_bp as i128 / 10000 * buyer_creator_royalty_bp as i128 / 10000;
+            prop_assert_eq!(
+                get_buyer_total_cost(
+                    buyer_price,
+                    maker_fee_bp,
+                    taker_fee_bp,
+                    seller_is_taker,
+                    royalty_bp,
+                    buyer_creator_royalty_bp,
+                )
+                .unwrap() as i128,
+                price + fee + royalty
+            );
+        }
+
+        #[test]
+        fn all_in_fill_spends_exactly_the_bid(
+            all_in_total in 1..=MAX_PRICE,
+            (maker_fee_bp, taker_fee_bp) in fee_bps(),
+            seller_is_taker in any::<bool>(),
+            royalty_bp in 0..=10000u16,
+            buyer_creator_royalty_bp in 0..=10000u16,
+        ) {
+            let total_cost = |price| {
+                get_buyer_total_cost(
+                    price,
+                    maker_fee_bp,
+                    taker_fee_bp,
+                    seller_is_taker,
+                    royalty_bp,
+                    buyer_creator_royalty_bp,
+                )
+                .unwrap()
+            };
+            let total_price = get_all_in_total_price(
+                all_in_total,
+                1,
+                maker_fee_bp,
+                taker_fee_bp,
+                seller_is_taker,
+                |_| Ok(royalty_bp),
+                buyer_creator_royalty_bp,
+            )
+            .unwrap();
+            // the highest price the bid covers
+            prop_assert!(total_cost(total_price) <= all_in_total);
+            prop_assert!(total_cost(total_price + 1) > all_in_total);
+            let royalty =
+                get_creator_royalty(royalty_bp, total_price, buyer_creator_royalty_bp).unwrap();
+            let remainder = get_all_in_remainder(
+                all_in_total,
+                total_price,
+                maker_fee_bp,
+                taker_fee_bp,
+                seller_is_taker,
+                royalty,
+            )
+            .unwrap();
+            prop_assert_eq!(total_cost(total_price) + remainder, all_in_total);
+        }
+
+        #[test]
+        fn total_price_is_per_unit_or_an_error(
+            unit_price in 1..=MAX_PRICE,
+            token_size in any::<u64>(),
+        ) {
+            match get_total_price(unit_price, token_size) {
+                Ok(total) => prop_assert_eq!(total as u128, unit_price as u128 * token_size as u128),
+                Err(_) => prop_assert!(unit_price as u128 * token_size as u128 > u64::MAX as u128),
+            }
+        }
+
+        #[test]
+        fn match_bounty_never_exceeds_taker_fee(
+            buyer_price in 1..=MAX_PRICE,
+            taker_fee_bp in 0..=MAX_TAKER_FEE_BP,
+        ) {
+            let taker_fee = get_listing_payment_amounts(buyer_price, 0, taker_fee_bp, false, 0)
+                .unwrap()
+                .taker_fee;
+            let bounty = get_match_bounty(taker_fee).unwrap();
+            prop_assert!(bounty <= taker_fee);
+            prop_assert_eq!(bounty as u128, taker_fee as u128 * MATCH_BOUNTY_BP as u128 / 10000);
+        }
+
+        #[test]
+        fn negative_maker_fee_never_underflows_seller_proceeds(
+            buyer_price in 1..=MAX_PRICE,
+            maker_fee_bp in any::<i16>(),
+            taker_fee_bp in any::<u16>(),
+            seller_is_taker in any::<bool>(),
+        ) {
+            // out of range bps are rejected by the execute constraints, here they must not wrap
+            if let Ok(amounts) = get_listing_payment_amounts(
+                buyer_price,
+                maker_fee_bp,
+                taker_fee_bp,
+                seller_is_taker,
+                0,
+            ) {
+                let expected = if seller_is_taker {
+                    buyer_price as i128 + amounts.maker_fee as i128
+                } else {
+                    buyer_price as i128 - amounts.maker_fee as i128
+                };
+                prop_assert_eq!(amounts.seller_will_get_from_buyer as i128, expected);
+                prop_assert!(amounts.maker_fee as i128 + amounts.taker_fee as i128 >= 0);
+            }
+        }
+
+        #[test]
+        fn listing_payment_rounding_favors_escrow(
+            buyer_price in 1..=MAX_PRICE,
+            (maker_fee_bp, taker_fee_bp) in fee_bps(),
+            seller_is_taker in any::<bool>(),
+        ) {
+            let amounts = get_listing_payment_amounts(
+                buyer_price,
+                maker_fee_bp,
+                taker_fee_bp,
+                seller_is_taker,
+                0,
+            )
+            .unwrap();
+            let price = buyer_price as i128;
+            // what leaves the buyer's escrow, scaled by 10000, against the exact amount owed
+            let (escrow_out, exact) = if seller_is_taker {
+                (
+                    amounts.seller_will_get_from_buyer as i128,
+                    price * (10000 + maker_fee_bp.max(0) as i128),
+                )
+            } else {
+                (
+                    (amounts.seller_will_get_from_buyer + amounts.total_platform_fee) as i128,
+                    price * (10000 + taker_fee_bp as i128),
+                )
+            };
+            prop_assert!(escrow_out * 10000 <= exact);
+        }
+
+        #[test]
+        fn creator_royalty_never_exceeds_price(
+            total_price in 0..=MAX_PRICE,
+            royalty_bp in any::<u16>(),
+            buyer_creator_royalty_bp in any::<u16>(),
+            shares in proptest::collection::vec(0..=100u8, 1..=5),
+        ) {
+            let total_fee =
+                get_creator_royalty(royalty_bp, total_price, buyer_creator_royalty_bp).unwrap();
+            prop_assert!(total_fee <= total_price);
+            prop_assert!(
+                total_fee as u128 * 10000 <= total_price as u128 * royalty_bp.min(10000) as u128
+            );
+
+            // metadata creator shares add up to 100
+            let mut remaining = 100u8;
+            let mut paid = 0u64;
+            for share in shares {
+                let share = share.min(remaining);
+                remaining -= share;
+                paid += get_creator_fee(total_fee, share).unwrap();
+            }
+            paid += get_creator_fee(total_fee, remaining).unwrap();
+            prop_assert!(paid <= total_fee);
+        }
+    }
+
+    // walks SOL creator accounts the way pay_creator_fees does, returns which creators got an account
+    fn take_creator_accounts(
+        creators: &[Pubkey],
+        fees: &[u64],
+        passed: &[Pubkey],
+    ) -> Result<(Vec<Option<Pubkey>>, usize)> {
+        let owner = Pubkey::new_unique();
+        let mut lamports = vec![0u64; passed.len()];
+        let mut data = vec![[0u8; 0]; passed.len()];
+        let accounts: Vec<AccountInfo> = passed
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, true, lamports, data, &owner, false, 0)
+            })
+            .collect();
+        let mut iter = accounts.iter();
+        let mut taken = vec![];
+        for (creator, fee) in creators.iter().zip(fees) {
+            taken.push(next_creator_account(&mut iter, creator, None, *fee)?.map(|ai| *ai.key));
+        }
+        Ok((taken, iter.len()))
+    }
+
+    #[test]
+    fn next_creator_account_skips_left_out_creators_owed_nothing() {
+        for n in 0..=5 {
+            let creators: Vec<Pubkey> = (0..n).map(|_| Pubkey::new_unique()).collect();
+            let fees: Vec<u64> = (0..n).map(|i| if i % 2 == 0 { 100 } else { 0 }).collect();
+            let trailing = Pubkey::new_unique();
+
+            // every creator passed in, each one takes its account
+            let mut passed = creators.clone();
+            passed.push(trailing);
+            let (taken, left) = take_creator_accounts(&creators, &fees, &passed).unwrap();
+            assert_eq!(taken, creators.iter().map(|c| Some(*c)).collect::<Vec<_>>());
+            assert_eq!(left, 1);
+
+            // creators owed nothing left out, the accounts after the creators are untouched
+            let mut passed: Vec<Pubkey> = creators
+                .iter()
+                .zip(&fees)
+                .filter(|(_, fee)| **fee > 0)
+                .map(|(c, _)| *c)
+                .collect();
+            passed.push(trailing);
+            let (taken, left) = take_creator_accounts(&creators, &fees, &passed).unwrap();
+            let expected: Vec<Option<Pubkey>> = creators
+                .iter()
+                .zip(&fees)
+                .map(|(c, fee)| Some(*c).filter(|_| *fee > 0))
+                .collect();
+            assert_eq!(taken, expected);
+            assert_eq!(left, 1);
+        }
+    }
+
+    #[test]
+    fn next_creator_account_requires_creators_owed_royalty() {
+        for n in 1..=5 {
+            let creators: Vec<Pubkey> = (0..n).map(|_| Pubkey::new_unique()).collect();
+            let fees = vec![100u64; n];
+            for missing in 0..n {
+                let mut passed = creators.clone();
+                passed.remove(missing);
+                // running out of accounts or reaching the accounts after the creators
+                for trailing in [vec![], vec![Pubkey::new_unique()]] {
+                    let passed = [passed.clone(), trailing].concat();
+                    assert_eq!(
+                        take_creator_accounts(&creators, &fees, &passed).unwrap_err(),
+                        ErrorCode::MissingCreatorAccount.into()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn proceeds_split_shares_round_down_to_the_seller() -> Result<()> {
+        let recipient = |bp| ProceedsSplitRecipient {
+            address: Pubkey::new_unique(),
+            bp,
+        };
+        let mut split = ProceedsSplit::default();
+        split.set_recipients(&[recipient(5000), recipient(3333)])?;
+        assert_eq!(split.shares(1_000_001), vec![500_000, 333_300]);
+        assert_eq!(split.shares(0), vec![0, 0]);
+
+        // at most 10000 bp, no empty recipient and at most MAX_PROCEEDS_SPLIT_RECIPIENTS of them
+        assert!(split
+            .set_recipients(&[recipient(5000), recipient(5001)])
+            .is_err());
+        assert!(split.set_recipients(&[recipient(0)]).is_err());
+        assert!(split
+            .set_recipients(&[ProceedsSplitRecipient {
+                address: Pubkey::default(),
+                bp: 100
+            }])
+            .is_err());
+        assert!(split
+            .set_recipients(&[recipient(1); MAX_PROCEEDS_SPLIT_RECIPIENTS + 1])
+            .is_err());
+
+        // a new set replaces every recipient
+        split.set_recipients(&[recipient(10000)])?;
+        assert_eq!(split.shares(7), vec![7]);
+        split.set_recipients(&[])?;
+        assert!(split.shares(7).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn pending_royalties_merge_claims_per_creator() -> Result<()> {
+        let mut pending = PendingRoyalties::default();
+        let creators: Vec<Pubkey> = (0..MAX_PENDING_ROYALTIES)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        for creator in &creators {
+            pending.add_claim(creator, 100)?;
+        }
+        // a later sale adds to an existing claim, a new creator needs a free slot
+        pending.add_claim(&creators[3], 50)?;
+        assert_eq!(pending.amount_owed(&creators[3]), 150);
+        assert_eq!(
+            pending.add_claim(&Pubkey::new_unique(), 1).unwrap_err(),
+            ErrorCode::PendingRoyaltiesFull.into()
+        );
+
+        assert_eq!(pending.take_claim(&creators[3]), 150);
+        assert_eq!(pending.take_claim(&creators[3]), 0);
+        let late_creator = Pubkey::new_unique();
+        pending.add_claim(&late_creator, 7)?;
+        assert_eq!(pending.amount_owed(&late_creator), 7);
+
+        for creator in creators.iter().chain([&late_creator]) {
+            pending.take_claim(creator);
+        }
+        assert!(pending.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn royalty_override_pays_more_recipients_than_metadata_fits() -> Result<()> {
+        let recipient = |share| RoyaltyRecipient {
+            address: Pubkey::new_unique(),
+            share,
+        };
+        let mut royalty_override = RoyaltyOverride::default();
+        // 1 to MAX_ROYALTY_RECIPIENTS distinct, non empty recipients, with shares adding up to 100
+        for recipients in [
+            vec![],
+            vec![recipient(50), recipient(49)],
+            vec![recipient(6); MAX_ROYALTY_RECIPIENTS + 1],
+            vec![RoyaltyRecipient {
+                address: Pubkey::default(),
+                share: 100,
+            }],
+        ] {
+            assert_eq!(
+                royalty_override.set_recipients(&recipients).unwrap_err(),
+                ErrorCode::InvalidRoyaltyOverride.into()
+            );
+        }
+        let duplicate = recipient(50);
+        assert!(royalty_override
+            .set_recipients(&[duplicate, duplicate])
+            .is_err());
+
+        let mut recipients: Vec<RoyaltyRecipient> =
+            (0..MAX_ROYALTY_RECIPIENTS).map(|_| recipient(6)).collect();
+        recipients[0].share = 10;
+        royalty_override.set_recipients(&recipients)?;
+        let metadata = royalty_override.apply(Metadata {
+            key: Key::MetadataV1,
+            update_authority: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            name: String::new(),
+            symbol: String::new(),
+            uri: String::new(),
+            seller_fee_basis_points: 500,
+            creators: Some(vec![Creator {
+                address: Pubkey::new_unique(),
+                verified: true,
+                share: 100,
+            }]),
+            primary_sale_happened: true,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: None,
+            collection: None,
+            uses: None,
+            collection_details: None,
+            programmable_config: None,
+        });
+        let creators = metadata.creators.unwrap();
+        assert_eq!(
+            creators.iter().map(|c| c.address).collect::<Vec<_>>(),
+            recipients.iter().map(|r| r.address).collect::<Vec<_>>()
+        );
+        assert_eq!(metadata.seller_fee_basis_points, 500);
+        let fees = creators
+            .iter()
+            .map(|c| get_creator_fee(1_000_000, c.share))
+            .collect::<Result<Vec<u64>>>()?;
+        assert_eq!(fees[0], 100_000);
+        assert_eq!(fees.iter().sum::<u64>(), 1_000_000);
+        Ok(())
+    }
+
+    #[test]
+    fn escrow_top_up_covers_a_shortfall_up_to_what_is_still_delegated() {
+        use solana_program::{program_option::COption, program_pack::Pack};
+        use spl_token::state::{Account as TokenAccount, AccountState};
+
+        let keys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let (escrow_top_up_key, funding_key, escrow_token_account_key) =
+            (&keys[0], &keys[1], &keys[2]);
+        let mint_key = spl_token::native_mint::ID;
+        let funding = |delegate: Pubkey, mint: Pubkey| {
+            let mut data = vec![0u8; TokenAccount::LEN];
+            TokenAccount {
+                mint,
+                owner: Pubkey::new_unique(),
+                amount: 1_000,
+                delegate: COption::Some(delegate),
+                state: AccountState::Initialized,
+                delegated_amount: 100,
+                ..Default::default()
+            }
+            .pack_into_slice(&mut data);
+            data
+        };
+        let mut lamports = [0u64; 4];
+        let [top_up_lamports, funding_lamports, mint_lamports, escrow_token_account_lamports] =
+            &mut lamports;
+        let mut empty = [[0u8; 0]; 3];
+        let [top_up_data, mint_data, escrow_token_account_data] = &mut empty;
+        let mut funding_data = funding(*escrow_top_up_key, mint_key);
+        let escrow_top_up = AccountInfo::new(
+            escrow_top_up_key,
+            false,
+            false,
+            top_up_lamports,
+            top_up_data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let funding_token_account = AccountInfo::new(
+            funding_key,
+            false,
+            true,
+            funding_lamports,
+            &mut funding_data,
+            &spl_token::ID,
+            false,
+            0,
+        );
+        let mint = AccountInfo::new(
+            &mint_key,
+            false,
+            false,
+            mint_lamports,
+            mint_data,
+            &spl_token::ID,
+            false,
+            0,
+        );
+        let escrow_token_account = AccountInfo::new(
+            escrow_token_account_key,
+            false,
+            true,
+            escrow_token_account_lamports,
+            escrow_token_account_data,
+            &spl_token::ID,
+            false,
+            0,
+        );
+        let args = EscrowTopUpArgs {
+            escrow_top_up: &escrow_top_up,
+            bump: 0,
+            funding_token_account: &funding_token_account,
+            mint: &mint,
+            escrow_token_account: &escrow_token_account,
+        };
+
+        assert!(args.assert_covers(1).is_ok());
+        assert!(args.assert_covers(100).is_ok());
+        assert_eq!(
+            args.assert_covers(101).unwrap_err(),
+            ErrorCode::EscrowTopUpExceeded.into()
+        );
+
+        // a revoked or reassigned delegation covers nothing
+        funding_token_account
+            .try_borrow_mut_data()
+            .unwrap()
+            .copy_from_slice(&funding(Pubkey::new_unique(), mint_key));
+        assert_eq!(
+            args.assert_covers(1).unwrap_err(),
+            ErrorCode::EscrowTopUpExceeded.into()
+        );
+        // nor does a funding account of another mint than the escrow's
+        funding_token_account
+            .try_borrow_mut_data()
+            .unwrap()
+            .copy_from_slice(&funding(*escrow_top_up_key, Pubkey::new_unique()));
+        assert!(args.assert_covers(1).is_err());
+    }
+
+    #[test]
+    fn wsol_sources_are_topped_up_and_synced_and_created_destinations_unwrapped() -> Result<()> {
+        use solana_program::{program_option::COption, program_pack::Pack};
+        use spl_token::state::{Account as TokenAccount, AccountState};
+
+        install_transfer_stubs();
+        const RESERVE: u64 = 2_039_280;
+        let keys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            spl_token::ID,
+            system_program::ID,
+        ];
+        let (wallet_key, payer_key, wsol_key, token_program_key, system_program_key) =
+            (&keys[0], &keys[1], &keys[2], &keys[3], &keys[4]);
+        let wsol = |amount: u64| {
+            let mut data = vec![0u8; TokenAccount::LEN];
+            TokenAccount {
+                mint: spl_token::native_mint::ID,
+                owner: *wallet_key,
+                amount,
+                state: AccountState::Initialized,
+                is_native: COption::Some(RESERVE),
+                ..Default::default()
+            }
+            .pack_into_slice(&mut data);
+            data
+        };
+        let mut lamports = [1_000_000, 0, RESERVE + 100, 0, 0];
+        let [wallet_lamports, payer_lamports, wsol_lamports, token_program_lamports, system_program_lamports] =
+            &mut lamports;
+        let mut wsol_data = wsol(100);
+        let mut empty = [[0u8; 0]; 4];
+        let [wallet_data, payer_data, token_program_data, system_program_data] = &mut empty;
+        let wallet = AccountInfo::new(
+            wallet_key,
+            true,
+            true,
+            wallet_lamports,
+            wallet_data,
+            &system_program::ID,
+            false,
+            0,
+        );
+        let payer = AccountInfo::new(
+            payer_key,
+            true,
+            true,
+            payer_lamports,
+            payer_data,
+            &system_program::ID,
+            false,
+            0,
+        );
+        let wsol_account = AccountInfo::new(
+            wsol_key,
+            false,
+            true,
+            wsol_lamports,
+            &mut wsol_data,
+            &spl_token::ID,
+            false,
+            0,
+        );
+        let token_program = AccountInfo::new(
+            token_program_key,
+            false,
+            false,
+            token_program_lamports,
+            token_program_data,
+            &solana_program::bpf_loader::ID,
+            true,
+            0,
+        );
+        let system_program = AccountInfo::new(
+            system_program_key,
+            false,
+            false,
+            system_program_lamports,
+            system_program_data,
+            &solana_program::bpf_loader::ID,
+            true,
+            0,
+        );
+        let wrapped = || unpack_token_account(&wsol_account).unwrap().amount;
+
+        // a signing owner's SOL tops the source up to the amount, and the top-up is synced
+        wrap_native_source(500, &wallet, &wsol_account, &token_program, &system_program)?;
+        assert_eq!(wrapped(), 500);
+        assert_eq!(wallet.lamports(), 1_000_000 - 400);
+        assert_eq!(wsol_account.lamports(), RESERVE + 500);
+
+        // lamports sent without a sync_native count once synced, without another top-up
+        **wsol_account.try_borrow_mut_lamports()? += 200;
+        wrap_native_source(600, &wallet, &wsol_account, &token_program, &system_program)?;
+        assert_eq!(wrapped(), 700);
+        assert_eq!(wallet.lamports(), 1_000_000 - 400);
+
+        // an owner that didn't sign isn't drawn from
+        let unsigned_wallet = AccountInfo {
+            is_signer: false,
+            ..wallet.clone()
+        };
+        wrap_native_source(
+            1_000,
+            &unsigned_wallet,
+            &wsol_account,
+            &token_program,
+            &system_program,
+        )?;
+        assert_eq!(wrapped(), 700);
+        assert_eq!(wallet.lamports(), 1_000_000 - 400);
+
+        // nor is its destination unwrapped
+        unwrap_native_destination(
+            &payer,
+            &unsigned_wallet,
+            &wsol_account,
+            &token_program,
+            &system_program,
+        )?;
+        assert_eq!(wrapped(), 700);
+
+        // a signing owner gets the wrapped SOL, and the payer the rent it put up
+        unwrap_native_destination(
+            &payer,
+            &wallet,
+            &wsol_account,
+            &token_program,
+            &system_program,
+        )?;
+        assert_eq!(wsol_account.lamports(), 0);
+        assert_eq!(wallet.lamports(), 1_000_000 - 400 + 700);
+        assert_eq!(payer.lamports(), RESERVE);
+        Ok(())
+    }
+}
@@ -102,6 +102,101 @@ export type M2 = {
           "type": {
             "option": "u8"
           }
+        },
+        {
+          "name": "sandwichGuard",
+          "type": {
+            "option": "bool"
+          }
+        },
+        {
+          "name": "protocolFeeRecipient",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "protocolFeeShareBp",
+          "type": {
+            "option": "u16"
+          }
+        },
+        {
+          "name": "escrowAdapters",
+          "type": {
+            "option": {
+              "array": [
+                "publicKey",
+                2
+              ]
+            }
+          }
+        },
+        {
+          "name": "minBidBpOfListPrice",
+          "type": {
+            "option": "u16"
+          }
+        },
+        {
+          "name": "delistGraceSeconds",
+          "type": {
+            "option": "u32"
+          }
+        },
+        {
+          "name": "enforceRoyalties",
+          "type": {
+            "option": "bool"
+          }
+        },
+        {
+          "name": "makerRewardsLamportsPerDay",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "makerRewardsVolumeBp",
+          "type": {
+            "option": "u16"
+          }
+        },
+        {
+          "name": "attestationAuthority",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "loanProgram",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "stakingAdapter",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "maxIxsPerSlot",
+          "type": {
+            "option": "u8"
+          }
+        },
+        {
+          "name": "allowSfts",
+          "type": {
+            "option": "bool"
+          }
+        },
+        {
+          "name": "enforceWalletLimits",
+          "type": {
+            "option": "bool"
+          }
         }
       ]
     },
@@ -1057,6 +1152,10 @@ export type M2 = {
         {
           "name": "takerFeeBp",
           "type": "u16"
+        },
+        {
+          "name": "expectedPaymentMint",
+          "type": "publicKey"
         }
       ]
     },
@@ -1284,12 +1383,14 @@ export type M2 = {
         {
           "name": "authorizationRulesProgram",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true
         },
         {
           "name": "authorizationRules",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true
         },
         {
           "name": "ownerTokenRecord",
@@ -1342,7 +1443,7 @@ export type M2 = {
         {
           "name": "wallet",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
           "name": "notary",
@@ -1384,7 +1485,7 @@ export type M2 = {
           "isMut": true,
           "isSigner": false,
           "docs": [
-            "should always be ATA of (mint, wallet)"
+            "should always be ATA of (mint, wallet), or of (mint, recipient) when delisting into another wallet"
           ]
         },
         {
@@ -1452,670 +1553,466 @@ export type M2 = {
         }
       ],
       "args": []
-    }
-  ],
-  "accounts": [
+    },
     {
-      "name": "buyerTradeState",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "auctionHouseKey",
-            "type": "publicKey"
-          },
-          {
-            "name": "buyer",
-            "type": "publicKey"
-          },
-          {
-            "name": "buyerReferral",
-            "type": "publicKey"
-          },
-          {
-            "name": "buyerPrice",
-            "type": "u64"
-          },
-          {
-            "name": "tokenMint",
-            "type": "publicKey"
-          },
-          {
-            "name": "tokenSize",
-            "type": "u64"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "expiry",
-            "type": "i64"
-          }
-        ]
-      }
+      "name": "setEscrowConfig",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowConfig",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "keepOpen",
+          "type": "bool"
+        },
+        {
+          "name": "minResidentBalance",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "sellerTradeState",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "auctionHouseKey",
-            "type": "publicKey"
-          },
-          {
-            "name": "seller",
-            "type": "publicKey"
-          },
-          {
-            "name": "sellerReferral",
-            "type": "publicKey"
-          },
-          {
-            "name": "buyerPrice",
-            "type": "u64"
-          },
-          {
-            "name": "tokenMint",
-            "type": "publicKey"
-          },
-          {
-            "name": "tokenAccount",
-            "type": "publicKey"
-          },
-          {
-            "name": "tokenSize",
-            "type": "u64"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "expiry",
-            "type": "i64"
-          }
-        ]
-      }
+      "name": "withdrawFromRentVault",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rentVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "sellerTradeStateV2",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "auctionHouseKey",
-            "type": "publicKey"
-          },
-          {
-            "name": "seller",
-            "type": "publicKey"
-          },
-          {
-            "name": "sellerReferral",
-            "type": "publicKey"
-          },
-          {
-            "name": "buyerPrice",
-            "type": "u64"
-          },
-          {
-            "name": "tokenMint",
-            "type": "publicKey"
-          },
-          {
-            "name": "tokenAccount",
-            "type": "publicKey"
-          },
-          {
-            "name": "tokenSize",
-            "type": "u64"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "expiry",
-            "type": "i64"
-          },
-          {
-            "name": "paymentMint",
-            "type": "publicKey"
-          }
-        ]
-      }
+      "name": "withdrawAll",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "openBidIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "name": "auctionHouse",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "auctionHouseTreasury",
-            "type": "publicKey"
-          },
-          {
-            "name": "treasuryWithdrawalDestination",
-            "type": "publicKey"
-          },
-          {
-            "name": "authority",
-            "type": "publicKey"
-          },
-          {
-            "name": "creator",
-            "type": "publicKey"
-          },
-          {
-            "name": "notary",
-            "type": "publicKey"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "treasuryBump",
-            "type": "u8"
-          },
-          {
-            "name": "sellerFeeBasisPoints",
-            "type": "u16"
-          },
-          {
-            "name": "buyerReferralBp",
-            "type": "u16"
-          },
-          {
-            "name": "sellerReferralBp",
-            "type": "u16"
-          },
-          {
-            "name": "requiresNotary",
-            "type": "bool"
-          },
-          {
-            "name": "nprob",
-            "type": "u8"
-          }
-        ]
-      }
+      "name": "sellWithAuthorization",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationNonce",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "expiry",
+          "type": "i64"
+        },
+        {
+          "name": "nonce",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "buyerTradeStateV2",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "auctionHouseKey",
-            "type": "publicKey"
-          },
-          {
-            "name": "buyer",
-            "type": "publicKey"
-          },
-          {
-            "name": "buyerReferral",
-            "type": "publicKey"
-          },
-          {
-            "name": "buyerPrice",
-            "type": "u64"
-          },
-          {
-            "name": "tokenMint",
-            "type": "publicKey"
-          },
-          {
-            "name": "tokenSize",
-            "type": "u64"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "expiry",
-            "type": "i64"
-          },
-          {
-            "name": "buyerCreatorRoyaltyBp",
-            "type": "u16"
-          },
-          {
-            "name": "paymentMint",
-            "type": "publicKey"
-          }
-        ]
-      }
-    }
-  ],
-  "types": [
-    {
-      "name": "MIP1ExecuteSaleV2Args",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "price",
-            "type": "u64"
-          },
-          {
-            "name": "makerFeeBp",
-            "type": "i16"
-          },
-          {
-            "name": "takerFeeBp",
-            "type": "u16"
-          }
-        ]
-      }
-    },
-    {
-      "name": "MIP1SellArgs",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "price",
-            "type": "u64"
-          },
-          {
-            "name": "expiry",
-            "type": "i64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "OCPExecuteSaleV2Args",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "price",
-            "type": "u64"
-          },
-          {
-            "name": "makerFeeBp",
-            "type": "i16"
-          },
-          {
-            "name": "takerFeeBp",
-            "type": "u16"
-          }
-        ]
-      }
-    },
-    {
-      "name": "OCPSellArgs",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "price",
-            "type": "u64"
-          },
-          {
-            "name": "expiry",
-            "type": "i64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "DestinationSpecifier",
-      "type": {
-        "kind": "enum",
-        "variants": [
-          {
-            "name": "Key",
-            "fields": [
-              {
-                "defined": "&'refsPubkey"
-              }
-            ]
-          },
-          {
-            "name": "Ai",
-            "fields": [
-              {
-                "defined": "&'refsAccountInfo<'a>"
-              }
-            ]
-          }
-        ]
-      }
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "PublicKeyMismatch",
-      "msg": "PublicKeyMismatch"
-    },
-    {
-      "code": 6001,
-      "name": "InvalidMintAuthority",
-      "msg": "InvalidMintAuthority"
-    },
-    {
-      "code": 6002,
-      "name": "UninitializedAccount",
-      "msg": "UninitializedAccount"
-    },
-    {
-      "code": 6003,
-      "name": "IncorrectOwner",
-      "msg": "IncorrectOwner"
-    },
-    {
-      "code": 6004,
-      "name": "PublicKeysShouldBeUnique",
-      "msg": "PublicKeysShouldBeUnique"
+      "name": "buyWithAuthorization",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationNonce",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReferral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "expiry",
+          "type": "i64"
+        },
+        {
+          "name": "buyerCreatorRoyaltyBp",
+          "type": "u16"
+        },
+        {
+          "name": "nonce",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 6005,
-      "name": "StatementFalse",
-      "msg": "StatementFalse"
+      "name": "sellEdition",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "masterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "editionSaleState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "maxPrints",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "code": 6006,
-      "name": "NotRentExempt",
-      "msg": "NotRentExempt"
-    },
-    {
-      "code": 6007,
-      "name": "NumericalOverflow",
-      "msg": "NumericalOverflow"
-    },
-    {
-      "code": 6008,
-      "name": "ExpectedSolAccount",
-      "msg": "Expected a sol account but got an spl token account instead"
-    },
-    {
-      "code": 6009,
-      "name": "CannotExchangeSOLForSol",
-      "msg": "Cannot exchange sol for sol"
-    },
-    {
-      "code": 6010,
-      "name": "SOLWalletMustSign",
-      "msg": "If paying with sol, sol wallet must be signer"
-    },
-    {
-      "code": 6011,
-      "name": "CannotTakeThisActionWithoutAuctionHouseSignOff",
-      "msg": "Cannot take this action without auction house signing too"
-    },
-    {
-      "code": 6012,
-      "name": "NoPayerPresent",
-      "msg": "No payer present on this txn"
-    },
-    {
-      "code": 6013,
-      "name": "DerivedKeyInvalid",
-      "msg": "Derived key invalid"
-    },
-    {
-      "code": 6014,
-      "name": "MetadataDoesntExist",
-      "msg": "Metadata doesn't exist"
-    },
-    {
-      "code": 6015,
-      "name": "InvalidTokenAmount",
-      "msg": "Invalid token amount"
-    },
-    {
-      "code": 6016,
-      "name": "BothPartiesNeedToAgreeToSale",
-      "msg": "Both parties need to agree to this sale"
-    },
-    {
-      "code": 6017,
-      "name": "CannotMatchFreeSalesWithoutAuctionHouseOrSellerSignoff",
-      "msg": "Cannot match free sales unless the auction house or seller signs off"
-    },
-    {
-      "code": 6018,
-      "name": "SaleRequiresSigner",
-      "msg": "This sale requires a signer"
-    },
-    {
-      "code": 6019,
-      "name": "OldSellerNotInitialized",
-      "msg": "Old seller not initialized"
-    },
-    {
-      "code": 6020,
-      "name": "SellerATACannotHaveDelegate",
-      "msg": "Seller ata cannot have a delegate set"
-    },
-    {
-      "code": 6021,
-      "name": "BuyerATACannotHaveDelegate",
-      "msg": "Buyer ata cannot have a delegate set"
-    },
-    {
-      "code": 6022,
-      "name": "NoValidSignerPresent",
-      "msg": "No valid signer present"
-    },
-    {
-      "code": 6023,
-      "name": "InvalidBasisPoints",
-      "msg": "Invalid BP"
-    },
-    {
-      "code": 6024,
-      "name": "InvalidNotary",
-      "msg": "Invalid notary"
-    },
-    {
-      "code": 6025,
-      "name": "EmptyTradeState",
-      "msg": "Empty trade state"
-    },
-    {
-      "code": 6026,
-      "name": "InvalidExpiry",
-      "msg": "Invalid expiry"
-    },
-    {
-      "code": 6027,
-      "name": "InvalidPrice",
-      "msg": "Invalid price"
-    },
-    {
-      "code": 6028,
-      "name": "InvalidRemainingAccountsWithoutProgramAsSigner",
-      "msg": "Invalid remainning accounts without program_as_signer"
-    },
-    {
-      "code": 6029,
-      "name": "InvalidBump",
-      "msg": "Invalid bump"
-    },
-    {
-      "code": 6030,
-      "name": "InvalidCreateAuctionHouseNonce",
-      "msg": "Invalid create auction house nonce"
-    },
-    {
-      "code": 6031,
-      "name": "InvalidAccountState",
-      "msg": "Invalid account state"
-    },
-    {
-      "code": 6032,
-      "name": "InvalidDiscriminator",
-      "msg": "Invalid discriminator"
-    },
-    {
-      "code": 6033,
-      "name": "InvalidPlatformFeeBp",
-      "msg": "Invalid platform fee bp"
-    },
-    {
-      "code": 6034,
-      "name": "InvalidTokenMint",
-      "msg": "Invalid token mint"
-    },
-    {
-      "code": 6035,
-      "name": "InvalidTokenStandard",
-      "msg": "Invalid token standard"
-    },
-    {
-      "code": 6036,
-      "name": "Deprecated",
-      "msg": "Deprecated"
-    },
-    {
-      "code": 6037,
-      "name": "MissingRemainingAccount",
-      "msg": "Missing remaining account"
-    }
-  ]
-};
-
-export const IDL: M2 = {
-  "version": "0.1.0",
-  "name": "m2",
-  "instructions": [
-    {
-      "name": "withdrawFromTreasury",
+      "name": "executeEditionSale",
       "accounts": [
         {
-          "name": "treasuryWithdrawalDestination",
+          "name": "buyer",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "auctionHouseTreasury",
+          "name": "seller",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "auctionHouse",
+          "name": "tokenAccount",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "tokenMint",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "amount",
-          "type": "u64"
-        }
-      ]
-    },
-    {
-      "name": "updateAuctionHouse",
-      "accounts": [
+        },
         {
-          "name": "payer",
+          "name": "metadata",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "notary",
-          "isMut": false,
+          "name": "masterEdition",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "authority",
-          "isMut": false,
-          "isSigner": true
+          "name": "editionMarker",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "newAuthority",
-          "isMut": false,
+          "name": "newMint",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "treasuryWithdrawalDestination",
+          "name": "newMetadata",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "auctionHouse",
+          "name": "newEdition",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "newMetadataUpdateAuthority",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "sellerFeeBasisPoints",
-          "type": {
-            "option": "u16"
-          }
         },
         {
-          "name": "buyerReferralBp",
-          "type": {
-            "option": "u16"
-          }
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "sellerReferralBp",
-          "type": {
-            "option": "u16"
-          }
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "requiresNotary",
-          "type": {
-            "option": "bool"
-          }
+          "name": "auctionHouseTreasury",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "nprob",
-          "type": {
-            "option": "u8"
-          }
-        }
-      ]
-    },
-    {
-      "name": "withdraw",
-      "accounts": [
-        {
-          "name": "wallet",
+          "name": "editionSaleState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "notary",
+          "name": "programAsSigner",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "escrowPaymentAccount",
-          "isMut": true,
+          "name": "tokenMetadataProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "authority",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "auctionHouse",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
@@ -2127,31 +2024,27 @@ export const IDL: M2 = {
       ],
       "args": [
         {
-          "name": "escrowPaymentBump",
-          "type": "u8"
-        },
-        {
-          "name": "amount",
+          "name": "price",
           "type": "u64"
         }
       ]
     },
     {
-      "name": "deposit",
+      "name": "cancelEditionSale",
       "accounts": [
         {
           "name": "wallet",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "notary",
-          "isMut": false,
+          "name": "tokenAccount",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "escrowPaymentAccount",
-          "isMut": true,
+          "name": "tokenMint",
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -2165,129 +2058,153 @@ export const IDL: M2 = {
           "isSigner": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
+          "name": "editionSaleState",
+          "isMut": true,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "escrowPaymentBump",
-          "type": "u8"
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "sell",
+      "name": "sweepAbandonedEscrow",
       "accounts": [
         {
-          "name": "wallet",
+          "name": "cranker",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "notary",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenAccount",
+          "name": "wallet",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenAta",
+          "name": "escrowPaymentAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenMint",
+          "name": "auctionHouse",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "metadata",
+          "name": "openBidIndex",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "mip1UpdateListing",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "auctionHouse",
+          "name": "notary",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sellerTradeState",
-          "isMut": true,
+          "name": "programAsSigner",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sellerReferral",
+          "name": "tokenAta",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "tokenMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "metadata",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "ataProgram",
+          "name": "auctionHouse",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "programAsSigner",
-          "isMut": false,
+          "name": "sellerTradeState",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "sellerStateBump",
-          "type": "u8"
-        },
-        {
-          "name": "programAsSignerBump",
-          "type": "u8"
-        },
+          "name": "args",
+          "type": {
+            "defined": "MIP1UpdateListingArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "cancelBuyBatch",
+      "accounts": [
         {
-          "name": "buyerPrice",
-          "type": "u64"
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "tokenSize",
-          "type": "u64"
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "sellerStateExpiry",
-          "type": "i64"
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "cancelSell",
+      "name": "mip1BidAndExecute",
       "accounts": [
         {
-          "name": "wallet",
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "seller",
           "isMut": true,
           "isSigner": false
         },
@@ -2296,19 +2213,29 @@ export const IDL: M2 = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "tokenAccount",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "buyerReceiptTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "tokenMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "authority",
-          "isMut": false,
+          "name": "metadata",
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -2317,81 +2244,74 @@ export const IDL: M2 = {
           "isSigner": false
         },
         {
-          "name": "sellerTradeState",
+          "name": "auctionHouseTreasury",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sellerReferral",
-          "isMut": false,
+          "name": "sellerTradeState",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "buyerTradeState",
+          "isMut": true,
           "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "buyerPrice",
-          "type": "u64"
         },
         {
-          "name": "tokenSize",
-          "type": "u64"
+          "name": "buyerEscrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "sellerStateExpiry",
-          "type": "i64"
-        }
-      ]
-    },
-    {
-      "name": "buy",
-      "accounts": [
-        {
-          "name": "wallet",
+          "name": "buyerReferral",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "notary",
-          "isMut": false,
+          "name": "sellerReferral",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenMint",
+          "name": "tokenMetadataProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "metadata",
+          "name": "edition",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "escrowPaymentAccount",
-          "isMut": true,
-          "isSigner": false
+          "name": "authorizationRulesProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
         },
         {
-          "name": "authority",
+          "name": "authorizationRules",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "isOptional": true
         },
         {
-          "name": "auctionHouse",
-          "isMut": false,
+          "name": "ownerTokenRecord",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "buyerTradeState",
+          "name": "destinationTokenRecord",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "buyerReferral",
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
           "isMut": false,
           "isSigner": false
         },
@@ -2413,40 +2333,36 @@ export const IDL: M2 = {
       ],
       "args": [
         {
-          "name": "buyerStateBump",
-          "type": "u8"
-        },
-        {
-          "name": "escrowPaymentBump",
-          "type": "u8"
-        },
-        {
-          "name": "buyerPrice",
-          "type": "u64"
-        },
-        {
-          "name": "tokenSize",
-          "type": "u64"
-        },
-        {
-          "name": "buyerStateExpiry",
-          "type": "i64"
+          "name": "args",
+          "type": {
+            "defined": "MIP1ExecuteSaleV2Args"
+          }
         }
       ]
     },
     {
-      "name": "buyV2",
+      "name": "sellIntoBid",
       "accounts": [
         {
-          "name": "wallet",
+          "name": "buyer",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
+        },
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": false
         },
         {
           "name": "notary",
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "tokenMint",
           "isMut": false,
@@ -2462,6 +2378,11 @@ export const IDL: M2 = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "buyerReceiptTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "authority",
           "isMut": false,
@@ -2473,13 +2394,28 @@ export const IDL: M2 = {
           "isSigner": false
         },
         {
-          "name": "buyerTradeState",
+          "name": "auctionHouseTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
           "isMut": true,
           "isSigner": false
         },
         {
           "name": "buyerReferral",
-          "isMut": false,
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -2491,9 +2427,32 @@ export const IDL: M2 = {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "ataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
+        {
+          "name": "escrowPaymentBump",
+          "type": "u8"
+        },
+        {
+          "name": "programAsSignerBump",
+          "type": "u8"
+        },
         {
           "name": "buyerPrice",
           "type": "u64"
@@ -2507,30 +2466,43 @@ export const IDL: M2 = {
           "type": "i64"
         },
         {
-          "name": "buyerCreatorRoyaltyBp",
+          "name": "sellerStateExpiry",
+          "type": "i64"
+        },
+        {
+          "name": "makerFeeBp",
+          "type": "i16"
+        },
+        {
+          "name": "takerFeeBp",
           "type": "u16"
         },
         {
-          "name": "extraArgs",
-          "type": "bytes"
+          "name": "expectedPaymentMint",
+          "type": "publicKey"
         }
       ]
     },
     {
-      "name": "cancelBuy",
+      "name": "depositWithReference",
       "accounts": [
         {
           "name": "wallet",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
         {
           "name": "notary",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenMint",
+          "name": "escrowPaymentAccount",
           "isMut": true,
           "isSigner": false
         },
@@ -2545,51 +2517,94 @@ export const IDL: M2 = {
           "isSigner": false
         },
         {
-          "name": "buyerTradeState",
+          "name": "depositReceipt",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "buyerReferral",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "buyerPrice",
+          "name": "amount",
           "type": "u64"
         },
         {
-          "name": "tokenSize",
-          "type": "u64"
+          "name": "reference",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "initHouseMetrics",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "buyerStateExpiry",
-          "type": "i64"
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "houseMetrics",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "paymentMint",
+          "type": "publicKey"
         }
       ]
     },
     {
-      "name": "ocpSell",
+      "name": "autoRelist",
       "accounts": [
         {
-          "name": "wallet",
+          "name": "cranker",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "notary",
-          "isMut": false,
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "sellFrozen",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "programAsSigner",
+          "name": "notary",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenAta",
+          "name": "tokenAccount",
           "isMut": true,
           "isSigner": false
         },
@@ -2604,77 +2619,95 @@ export const IDL: M2 = {
           "isSigner": false
         },
         {
-          "name": "auctionHouse",
+          "name": "edition",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sellerTradeState",
-          "isMut": true,
+          "name": "authority",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sellerReferral",
+          "name": "auctionHouse",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "ocpMintState",
+          "name": "sellerTradeState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "ocpPolicy",
+          "name": "sellerReferral",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "ocpFreezeAuthority",
+          "name": "programAsSigner",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "ocpProgram",
+          "name": "tokenMetadataProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "cmtProgram",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "instructions",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
         },
         {
-          "name": "tokenProgram",
+          "name": "expiry",
+          "type": "i64"
+        },
+        {
+          "name": "extraArgs",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "quoteOcpRoyalty",
+      "accounts": [
+        {
+          "name": "tokenMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "metadata",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "ocpPolicy",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "args",
-          "type": {
-            "defined": "OCPSellArgs"
-          }
+          "name": "price",
+          "type": "u64"
         }
-      ]
+      ],
+      "returns": "u16"
     },
     {
-      "name": "ocpCancelSell",
+      "name": "repairTradeState",
       "accounts": [
         {
           "name": "wallet",
@@ -2682,29 +2715,30 @@ export const IDL: M2 = {
           "isSigner": false
         },
         {
-          "name": "notary",
+          "name": "tokenMint",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "programAsSigner",
+          "name": "auctionHouse",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenAta",
+          "name": "buyerTradeState",
           "isMut": true,
           "isSigner": false
-        },
-        {
-          "name": "tokenMint",
-          "isMut": false,
-          "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "registerReferrer",
+      "accounts": [
         {
-          "name": "metadata",
-          "isMut": false,
-          "isSigner": false
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
         },
         {
           "name": "auctionHouse",
@@ -2712,248 +2746,382 @@ export const IDL: M2 = {
           "isSigner": false
         },
         {
-          "name": "sellerTradeState",
-          "isMut": true,
+          "name": "referral",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "ocpMintState",
+          "name": "referrer",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "ocpPolicy",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "ocpFreezeAuthority",
-          "isMut": false,
-          "isSigner": false
+          "name": "payout",
+          "type": "publicKey"
         },
         {
-          "name": "ocpProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "attributionWindowSeconds",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "initProgramConfig",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "cmtProgram",
+          "name": "program",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "instructions",
+          "name": "programData",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "programConfig",
+          "isMut": true,
           "isSigner": false
         },
         {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "configAuthority",
+          "type": "publicKey"
         },
         {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
+          "name": "paymentMints",
+          "type": {
+            "array": [
+              "publicKey",
+              8
+            ]
+          }
+        },
+        {
+          "name": "cancelAuthority",
+          "type": "publicKey"
         }
-      ],
-      "args": []
+      ]
     },
     {
-      "name": "ocpExecuteSaleV2",
+      "name": "updateProgramConfig",
       "accounts": [
         {
-          "name": "payer",
-          "isMut": true,
+          "name": "authority",
+          "isMut": false,
           "isSigner": true
         },
         {
-          "name": "buyer",
+          "name": "programConfig",
           "isMut": true,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "seller",
-          "isMut": true,
-          "isSigner": false
+          "name": "newAuthority",
+          "type": {
+            "option": "publicKey"
+          }
         },
         {
-          "name": "notary",
-          "isMut": false,
-          "isSigner": false
+          "name": "paymentMints",
+          "type": {
+            "option": {
+              "array": [
+                "publicKey",
+                8
+              ]
+            }
+          }
         },
         {
-          "name": "programAsSigner",
-          "isMut": false,
-          "isSigner": false
+          "name": "cancelAuthority",
+          "type": {
+            "option": "publicKey"
+          }
         },
         {
-          "name": "sellerTokenAta",
+          "name": "disabledInstructions",
+          "type": {
+            "option": "u32"
+          }
+        },
+        {
+          "name": "swapAdapter",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "paymentMintFees",
+          "type": {
+            "option": {
+              "array": [
+                {
+                  "defined": "PaymentMintFees"
+                },
+                8
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setCancelAuthority",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "buyerTokenAta",
+          "name": "cancelAuthorityRegistry",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenMint",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "key",
+          "type": "publicKey"
         },
         {
-          "name": "metadata",
-          "isMut": false,
+          "name": "enabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "closeStaleSts",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "auctionHouse",
+          "name": "tokenAccount",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "auctionHouseTreasury",
-          "isMut": true,
+          "name": "tokenMint",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "sellerTradeState",
-          "isMut": true,
+          "name": "auctionHouse",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "buyerTradeState",
+          "name": "sellerTradeState",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "buyerEscrowPaymentAccount",
-          "isMut": true,
+          "name": "programAsSigner",
+          "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initMakerRewards",
+      "accounts": [
         {
-          "name": "buyerReferral",
+          "name": "wallet",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "sellerReferral",
-          "isMut": true,
+          "name": "auctionHouse",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "ocpMintState",
+          "name": "makerRewards",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "ocpPolicy",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimMakerRewards",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "ocpFreezeAuthority",
+          "name": "auctionHouse",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "ocpProgram",
-          "isMut": false,
+          "name": "makerRewards",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "cmtProgram",
-          "isMut": false,
+          "name": "makerRewardsVault",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "instructions",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initEscrowLedger",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "auctionHouse",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "escrowLedger",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "escrowPaymentAccount",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "OCPExecuteSaleV2Args"
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "executeSaleV2",
+      "name": "upgradeLegacySts",
       "accounts": [
         {
-          "name": "buyer",
+          "name": "payer",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true,
+          "docs": [
+            "pays the rent the bigger layout needs"
+          ]
         },
         {
-          "name": "seller",
-          "isMut": true,
-          "isSigner": false
+          "name": "notary",
+          "isMut": false,
+          "isSigner": true
         },
         {
-          "name": "notary",
+          "name": "auctionHouse",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenAccount",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setSpendingAllowance",
+      "accounts": [
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "tokenMint",
+          "name": "spender",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "metadata",
+          "name": "auctionHouse",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "escrowPaymentAccount",
+          "name": "spendingAllowance",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "buyerReceiptTokenAccount",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "limit",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "scheduleHouseUpdate",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
         },
         {
           "name": "authority",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
           "name": "auctionHouse",
@@ -2961,114 +3129,282 @@ export const IDL: M2 = {
           "isSigner": false
         },
         {
-          "name": "auctionHouseTreasury",
+          "name": "pendingHouseUpdate",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "buyerTradeState",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "sellerFeeBasisPoints",
+          "type": {
+            "option": "u16"
+          }
         },
         {
-          "name": "buyerReferral",
-          "isMut": true,
-          "isSigner": false
+          "name": "buyerReferralBp",
+          "type": {
+            "option": "u16"
+          }
         },
         {
-          "name": "sellerTradeState",
+          "name": "sellerReferralBp",
+          "type": {
+            "option": "u16"
+          }
+        },
+        {
+          "name": "protocolFeeShareBp",
+          "type": {
+            "option": "u16"
+          }
+        }
+      ]
+    },
+    {
+      "name": "applyHouseUpdate",
+      "accounts": [
+        {
+          "name": "authority",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sellerReferral",
+          "name": "auctionHouse",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "pendingHouseUpdate",
+          "isMut": true,
           "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "issueAttestation",
+      "accounts": [
+        {
+          "name": "attestationAuthority",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "systemProgram",
+          "name": "wallet",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "ataProgram",
+          "name": "auctionHouse",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "programAsSigner",
-          "isMut": false,
+          "name": "attestation",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "escrowPaymentBump",
-          "type": "u8"
-        },
+          "name": "expiry",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "revokeAttestation",
+      "accounts": [
         {
-          "name": "programAsSignerBump",
-          "type": "u8"
+          "name": "attestationAuthority",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "buyerPrice",
-          "type": "u64"
+          "name": "wallet",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "tokenSize",
-          "type": "u64"
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "buyerStateExpiry",
-          "type": "i64"
+          "name": "attestation",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setCpiPolicy",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "sellerStateExpiry",
-          "type": "i64"
+          "name": "auctionHouse",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "makerFeeBp",
-          "type": "i16"
+          "name": "cpiAllowlist",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "takerFeeBp",
-          "type": "u16"
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "cpiPolicy",
+          "type": {
+            "defined": "CpiPolicy"
+          }
+        },
+        {
+          "name": "program",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "allowed",
+          "type": "bool"
         }
       ]
     },
     {
-      "name": "mip1Sell",
+      "name": "snapshotListing",
       "accounts": [
         {
-          "name": "wallet",
+          "name": "payer",
           "isMut": true,
           "isSigner": true
         },
         {
           "name": "notary",
           "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "programAsSigner",
+          "name": "sellerTradeState",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenAccount",
+          "name": "listingSnapshot",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "snapshotId",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "getConfig",
+      "accounts": [],
+      "args": [],
+      "returns": {
+        "defined": "ProgramConstants"
+      }
+    },
+    {
+      "name": "setWalletLimits",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "wallet",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "walletLimits",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "maxDailyBuyVolume",
+          "type": "u64"
+        },
+        {
+          "name": "maxOpenBids",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "cancelListingAuto",
+      "accounts": [
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "listAuto",
+      "accounts": [
         {
           "name": "tokenMint",
           "isMut": false,
@@ -3076,6 +3412,29 @@ export const IDL: M2 = {
         },
         {
           "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "ListAutoArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "lockEscrowForQuote",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "escrowPaymentAccount",
           "isMut": true,
           "isSigner": false
         },
@@ -3085,668 +3444,11685 @@ export const IDL: M2 = {
           "isSigner": false
         },
         {
-          "name": "sellerTradeState",
+          "name": "escrowLock",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "migrationSellerTradeState",
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "orderHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "deadlineSlot",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "releaseEscrowLock",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "wallet",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "sellerReferral",
+          "name": "notary",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenAta",
+          "name": "escrowPaymentAccount",
           "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "escrow mode for init sell:        we transfer from token_account to token_ata",
-            "escrow mode for change price:     token_account is the same as token_ata",
-            "migration mode for change price:  token_ata is not used, because we only need token_account which is owned by program_as_signer"
-          ]
+          "isSigner": false
         },
         {
-          "name": "tokenMetadataProgram",
+          "name": "auctionHouse",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "edition",
+          "name": "escrowLock",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "matchOrders",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "authorizationRulesProgram",
+          "name": "seller",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "authorizationRules",
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "instructions",
+          "name": "metadata",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "ownerTokenRecord",
+          "name": "escrowPaymentAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "destinationTokenRecord",
+          "name": "buyerReceiptTokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "authority",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "auctionHouse",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
+          "name": "auctionHouseTreasury",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
-          "isMut": false,
+          "name": "buyerTradeState",
+          "isMut": true,
           "isSigner": false
-        }
+        },
+        {
+          "name": "buyerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "escrowPaymentBump",
+          "type": "u8"
+        },
+        {
+          "name": "programAsSignerBump",
+          "type": "u8"
+        },
+        {
+          "name": "buyerPrice",
+          "type": "u64"
+        },
+        {
+          "name": "tokenSize",
+          "type": "u64"
+        },
+        {
+          "name": "buyerStateExpiry",
+          "type": "i64"
+        },
+        {
+          "name": "sellerStateExpiry",
+          "type": "i64"
+        },
+        {
+          "name": "makerFeeBp",
+          "type": "i16"
+        },
+        {
+          "name": "takerFeeBp",
+          "type": "u16"
+        },
+        {
+          "name": "expectedPaymentMint",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "initRateLimit",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rateLimit",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "recoverSellerTradeState",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "quoteList",
+      "accounts": [
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "makerFeeBp",
+          "type": "i16"
+        },
+        {
+          "name": "takerFeeBp",
+          "type": "u16"
+        },
+        {
+          "name": "buyerCreatorRoyaltyBp",
+          "type": "u16"
+        }
+      ],
+      "returns": {
+        "defined": "ListQuote"
+      }
+    },
+    {
+      "name": "depositListingInsurance",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "listingInsurance",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawListingInsurance",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "listingInsurance",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimListingInsurance",
+      "accounts": [
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "listingInsurance",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setProceedsSplit",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "proceedsSplit",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "recipients",
+          "type": {
+            "vec": {
+              "defined": "ProceedsSplitRecipient"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "closeProceedsSplit",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "proceedsSplit",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setCoOwners",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "seller",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "the co-owned wallet, e.g. a multisig PDA signing through its program"
+          ]
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "coOwnerApproval",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "owners",
+          "type": {
+            "vec": "publicKey"
+          }
+        },
+        {
+          "name": "threshold",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "approveCoOwnerAction",
+      "accounts": [
+        {
+          "name": "coOwner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "seller",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "coOwnerApproval",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "expiry",
+          "type": "i64"
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey"
+        },
+        {
+          "name": "extraArgs",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "setGuardian",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "guardian",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "guardianAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "removeGuardian",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "guardianAccount",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "sweepBuy",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouseTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "maxBudget",
+          "type": "u64"
+        },
+        {
+          "name": "makerFeeBp",
+          "type": "i16"
+        },
+        {
+          "name": "takerFeeBp",
+          "type": "u16"
+        }
+      ],
+      "returns": {
+        "defined": "SweepBuyResult"
+      }
+    },
+    {
+      "name": "dryRunExecuteSale",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReceiptTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouseTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "escrowPaymentBump",
+          "type": "u8"
+        },
+        {
+          "name": "programAsSignerBump",
+          "type": "u8"
+        },
+        {
+          "name": "buyerPrice",
+          "type": "u64"
+        },
+        {
+          "name": "tokenSize",
+          "type": "u64"
+        },
+        {
+          "name": "buyerStateExpiry",
+          "type": "i64"
+        },
+        {
+          "name": "sellerStateExpiry",
+          "type": "i64"
+        },
+        {
+          "name": "makerFeeBp",
+          "type": "i16"
+        },
+        {
+          "name": "takerFeeBp",
+          "type": "u16"
+        },
+        {
+          "name": "expectedPaymentMint",
+          "type": "publicKey"
+        }
+      ],
+      "returns": {
+        "defined": "DryRunResult"
+      }
+    },
+    {
+      "name": "setEscrowTopUp",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "fundingTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowTopUp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "cap",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "removeEscrowTopUp",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "fundingTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrowTopUp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setNotarySet",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "notarySet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "notaries",
+          "type": {
+            "vec": "publicKey"
+          }
+        },
+        {
+          "name": "threshold",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "closeNotarySet",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "notarySet",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "defundZombieState",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "distributeRoyalties",
+      "accounts": [
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pendingRoyalties",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createBidGroup",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "bidGroup",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "id",
+          "type": "u64"
+        },
+        {
+          "name": "maxFills",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "closeBidGroup",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "bidGroup",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initMintMarket",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mintMarket",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "refreshMintMarket",
+      "accounts": [
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mintMarket",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "invalidateBrokenListing",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setRoyaltyOverride",
+      "accounts": [
+        {
+          "name": "updateAuthority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyOverride",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "recipients",
+          "type": {
+            "vec": {
+              "defined": "RoyaltyRecipient"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "closeRoyaltyOverride",
+      "accounts": [
+        {
+          "name": "updateAuthority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyOverride",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    }
+  ],
+  "accounts": [
+    {
+      "name": "buyerTradeState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyer",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerReferral",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerPrice",
+            "type": "u64"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenSize",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "sellerTradeState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "seller",
+            "type": "publicKey"
+          },
+          {
+            "name": "sellerReferral",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerPrice",
+            "type": "u64"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenSize",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "sellerTradeStateV2",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "seller",
+            "type": "publicKey"
+          },
+          {
+            "name": "sellerReferral",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerPrice",
+            "type": "u64"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenSize",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "sellerTradeStateV3",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "seller",
+            "type": "publicKey"
+          },
+          {
+            "name": "sellerReferral",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerPrice",
+            "type": "u64"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenSize",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "createdAt",
+            "type": "i64"
+          },
+          {
+            "name": "lastUpdatedAt",
+            "type": "i64"
+          },
+          {
+            "name": "origin",
+            "type": {
+              "array": [
+                "u8",
+                8
+              ]
+            }
+          },
+          {
+            "name": "orderMemo",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "proceedsAsWsol",
+            "type": "bool"
+          },
+          {
+            "name": "autoRelistPriceBp",
+            "type": "u16"
+          },
+          {
+            "name": "autoRelistMax",
+            "type": "u8"
+          },
+          {
+            "name": "autoRelistCount",
+            "type": "u8"
+          },
+          {
+            "name": "frozen",
+            "type": "bool"
+          },
+          {
+            "name": "royaltyMode",
+            "type": {
+              "defined": "RoyaltyMode"
+            }
+          },
+          {
+            "name": "makerRewards",
+            "type": "bool"
+          },
+          {
+            "name": "requiresAttestation",
+            "type": "bool"
+          },
+          {
+            "name": "autoMatch",
+            "type": "bool"
+          },
+          {
+            "name": "royaltyLock",
+            "type": "bool"
+          },
+          {
+            "name": "royaltyBpSnapshot",
+            "type": "u16"
+          },
+          {
+            "name": "hiddenUntil",
+            "type": "i64"
+          },
+          {
+            "name": "listingIndexes",
+            "type": "u8"
+          },
+          {
+            "name": "repayLoan",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "auctionHouse",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseTreasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "treasuryWithdrawalDestination",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "creator",
+            "type": "publicKey"
+          },
+          {
+            "name": "notary",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "treasuryBump",
+            "type": "u8"
+          },
+          {
+            "name": "sellerFeeBasisPoints",
+            "type": "u16"
+          },
+          {
+            "name": "buyerReferralBp",
+            "type": "u16"
+          },
+          {
+            "name": "sellerReferralBp",
+            "type": "u16"
+          },
+          {
+            "name": "requiresNotary",
+            "type": "bool"
+          },
+          {
+            "name": "nprob",
+            "type": "u8"
+          },
+          {
+            "name": "sandwichGuard",
+            "type": "bool"
+          },
+          {
+            "name": "protocolFeeRecipient",
+            "type": "publicKey"
+          },
+          {
+            "name": "protocolFeeShareBp",
+            "type": "u16"
+          },
+          {
+            "name": "escrowAdapters",
+            "type": {
+              "array": [
+                "publicKey",
+                2
+              ]
+            }
+          },
+          {
+            "name": "minBidBpOfListPrice",
+            "type": "u16"
+          },
+          {
+            "name": "delistGraceSeconds",
+            "type": "u32"
+          },
+          {
+            "name": "cancelAuthorityRegistry",
+            "type": "bool"
+          },
+          {
+            "name": "enforceRoyalties",
+            "type": "bool"
+          },
+          {
+            "name": "makerRewardsLamportsPerDay",
+            "type": "u64"
+          },
+          {
+            "name": "makerRewardsVolumeBp",
+            "type": "u16"
+          },
+          {
+            "name": "attestationAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "cpiPolicy",
+            "type": {
+              "defined": "CpiPolicy"
+            }
+          },
+          {
+            "name": "loanProgram",
+            "type": "publicKey"
+          },
+          {
+            "name": "stakingAdapter",
+            "type": "publicKey"
+          },
+          {
+            "name": "maxIxsPerSlot",
+            "type": "u8"
+          },
+          {
+            "name": "allowSfts",
+            "type": "bool"
+          },
+          {
+            "name": "enforceWalletLimits",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "attestation",
+      "docs": [
+        "The auction house's attestation_authority vouching for a wallet, e.g. after KYC, so it can buy",
+        "listings that require an attestation. It's only valid while its issuer is still the house's",
+        "attestation_authority and, if it has one, until expiry."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "issuer",
+            "type": "publicKey"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "pendingHouseUpdate",
+      "docs": [
+        "Fee changes the authority scheduled with schedule_house_update. Anyone can apply them to the",
+        "auction house once effective_at has passed, so fills in flight never see a fee change early."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "effectiveAt",
+            "type": "i64"
+          },
+          {
+            "name": "sellerFeeBasisPoints",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "buyerReferralBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "sellerReferralBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "protocolFeeShareBp",
+            "type": {
+              "option": "u16"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "buyerTradeStateV2",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyer",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerReferral",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerPrice",
+            "type": "u64"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenSize",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "buyerCreatorRoyaltyBp",
+            "type": "u16"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "indexed",
+            "type": "bool"
+          },
+          {
+            "name": "escrowAdapter",
+            "type": "publicKey"
+          },
+          {
+            "name": "orderMemo",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "receiptOwner",
+            "type": "publicKey"
+          },
+          {
+            "name": "ledgered",
+            "type": "bool"
+          },
+          {
+            "name": "metadataPin",
+            "type": {
+              "array": [
+                "u8",
+                24
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "buyerTradeStateV3",
+      "docs": [
+        "The current buyer trade state, older bids are migrated to it when they're updated"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyer",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerReferral",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerPrice",
+            "type": "u64"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenSize",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "buyerCreatorRoyaltyBp",
+            "type": "u16"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "indexed",
+            "type": "bool"
+          },
+          {
+            "name": "escrowAdapter",
+            "type": "publicKey"
+          },
+          {
+            "name": "orderMemo",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "receiptOwner",
+            "type": "publicKey"
+          },
+          {
+            "name": "ledgered",
+            "type": "bool"
+          },
+          {
+            "name": "metadataPin",
+            "type": {
+              "array": [
+                "u8",
+                24
+              ]
+            }
+          },
+          {
+            "name": "escalation",
+            "type": {
+              "defined": "BidEscalation"
+            }
+          },
+          {
+            "name": "limited",
+            "type": "bool"
+          },
+          {
+            "name": "priceIsAllIn",
+            "type": "bool"
+          },
+          {
+            "name": "autoMatch",
+            "type": "bool"
+          },
+          {
+            "name": "swapMaxSlippageBp",
+            "type": "u16"
+          },
+          {
+            "name": "bidGroup",
+            "type": "publicKey"
+          },
+          {
+            "name": "allowanceFunded",
+            "type": "bool"
+          },
+          {
+            "name": "maxFloorPrice",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "escrowConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "keepOpen",
+            "type": "bool"
+          },
+          {
+            "name": "minResidentBalance",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "openBidIndex",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "lockedAmount",
+            "type": "u64"
+          },
+          {
+            "name": "openBids",
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "tracksAllBids",
+            "type": "bool"
+          },
+          {
+            "name": "bids",
+            "type": {
+              "vec": "publicKey"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "listingIndex",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "key",
+            "type": "publicKey"
+          },
+          {
+            "name": "listings",
+            "type": "u32"
+          },
+          {
+            "name": "cursor",
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "recent",
+            "type": {
+              "array": [
+                "publicKey",
+                16
+              ]
+            }
+          },
+          {
+            "name": "floorPrice",
+            "type": "u64"
+          },
+          {
+            "name": "floorListing",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "authorizationNonce",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "lastNonce",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "editionSaleState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "seller",
+            "type": "publicKey"
+          },
+          {
+            "name": "masterMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "maxPrints",
+            "type": "u64"
+          },
+          {
+            "name": "printed",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "depositReceipt",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "payer",
+            "type": "publicKey"
+          },
+          {
+            "name": "reference",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "createdAt",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "referrer",
+      "docs": [
+        "A referral key the auction house authority registered, orders only store a referral that",
+        "has one. Orders placed after the attribution window are not attributed to it."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "referral",
+            "type": "publicKey"
+          },
+          {
+            "name": "payout",
+            "type": "publicKey"
+          },
+          {
+            "name": "registeredAt",
+            "type": "i64"
+          },
+          {
+            "name": "attributionWindowSeconds",
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "makerRewards",
+      "docs": [
+        "A seller's opt-in liquidity mining account. Listings made with it passed in are enrolled, and",
+        "when an enrolled listing is cancelled or filled it accrues lamports for the days it was live and,",
+        "if a buyer took it, for its SOL volume, at the auction house's current rates. The rewards are",
+        "claimed from the auction house's maker rewards vault."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "unclaimed",
+            "type": "u64"
+          },
+          {
+            "name": "claimed",
+            "type": "u64"
+          },
+          {
+            "name": "listingSeconds",
+            "type": "u64"
+          },
+          {
+            "name": "filledVolume",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "escrowLedger",
+      "docs": [
+        "A buyer's opt-in inventory of the escrow, per payment mint: what its ledgered bids lock and what",
+        "is free. Bids placed with it passed in lock their full cost until they are cancelled or filled,",
+        "which requires it passed in again, and withdrawals that pass it in can't dip into the locked",
+        "balance. free is as of the last deposit, withdrawal or bid that passed the ledger in."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "balances",
+            "type": {
+              "array": [
+                {
+                  "defined": "EscrowLedgerBalance"
+                },
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "spendingAllowance",
+      "docs": [
+        "A treasury wallet's approval for a spender to top up its own escrow from the treasury's escrow",
+        "when it bids in SOL, up to limit lamports in total. The treasury raises limit to extend the",
+        "budget, or lowers it to spent to revoke it. What each open bid drew is locked to it, the",
+        "unspent part goes back to the treasury and the budget when the bid is cancelled or filled."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "treasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "spender",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "limit",
+            "type": "u64"
+          },
+          {
+            "name": "spent",
+            "type": "u64"
+          },
+          {
+            "name": "locks",
+            "type": {
+              "array": [
+                {
+                  "defined": "SpendingAllowanceLock"
+                },
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "walletLimits",
+      "docs": [
+        "Trading limits the notary sets for a wallet, e.g. for a compliance program or to cap the damage",
+        "a compromised wallet can do. Bids placed with them passed in count as open bids until they are",
+        "cancelled or filled, and SOL buys they are passed to count towards the day's buy volume. Houses",
+        "that enforce wallet limits require them, or the wallet's uninitialized address if it has none,",
+        "on every bid and buy. A limit of 0 is no limit."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "maxDailyBuyVolume",
+            "type": "u64"
+          },
+          {
+            "name": "maxOpenBids",
+            "type": "u32"
+          },
+          {
+            "name": "day",
+            "type": "i64"
+          },
+          {
+            "name": "dayBuyVolume",
+            "type": "u64"
+          },
+          {
+            "name": "openBids",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "escrowLock",
+      "docs": [
+        "Escrow funds reserved for an off-chain order until a slot deadline. The funds sit in the lock",
+        "itself, lamports on top of its rent or its ATA for SPL payment mints, so no withdrawal can touch",
+        "them and matching engines can trust the quote without watching the escrow."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "orderHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "deadlineSlot",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rateLimit",
+      "docs": [
+        "A wallet's rate limited instructions in the last slot it sent one, for auction houses that",
+        "don't require the notary"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "slot",
+            "type": "u64"
+          },
+          {
+            "name": "count",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "listingInsurance",
+      "docs": [
+        "A seller's lamports backing their delegate listings of a token in an auction house. If a fill",
+        "fails because the seller moved the token or revoked the delegation, the notary can pay them to",
+        "the buyer with claim_listing_insurance."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "seller",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "withdrawableAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "proceedsSplit",
+      "docs": [
+        "How a seller splits their SOL sale proceeds, e.g. between the co-owners of an NFT or into a",
+        "team treasury. An execute passed the seller's ProceedsSplit pays each recipient its bps of what",
+        "the seller nets in the same settlement, the seller keeps the rest."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "seller",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "recipients",
+            "type": {
+              "array": [
+                {
+                  "defined": "ProceedsSplitRecipient"
+                },
+                5
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "notarySet",
+      "docs": [
+        "Notaries that can cosign for an auction house besides auction_house.notary, so the house",
+        "doesn't depend on a single notary key being available. Where the notary's cosign is required,",
+        "threshold of them signing anywhere in the instruction stand in for it."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "threshold",
+            "type": "u8"
+          },
+          {
+            "name": "notaries",
+            "type": {
+              "array": [
+                "publicKey",
+                5
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "bidGroup",
+      "docs": [
+        "Bids of a buyer on different mints, e.g. \"any 1 of these 5\", that share one escrow balance.",
+        "The group fills at most max_fills of them, the bids left can't fill once it had its max fills,",
+        "so a balance funded once for the group can't be over-filled, and the fill that consumes it",
+        "cancels those passed along. Bids are kept in the buyer's order of priority for matchers filling",
+        "the group."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyer",
+            "type": "publicKey"
+          },
+          {
+            "name": "id",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "maxFills",
+            "type": "u8"
+          },
+          {
+            "name": "fills",
+            "type": "u8"
+          },
+          {
+            "name": "bids",
+            "type": {
+              "array": [
+                "publicKey",
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "mintMarket",
+      "docs": [
+        "Top of book of a token mint on an auction house: its best SOL bid and listing, by price per",
+        "token, so other programs can read them without scanning trade states. Sells, bids, cancels and",
+        "executes update it whenever it is passed in. It's best effort: a cancelled, filled, hidden or",
+        "worse priced best order clears its side, until an order is placed or refresh_mint_market is",
+        "called, so readers still check the trade state it points to."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "bestBid",
+            "type": "publicKey"
+          },
+          {
+            "name": "bestBidPrice",
+            "type": "u64"
+          },
+          {
+            "name": "bestAsk",
+            "type": "publicKey"
+          },
+          {
+            "name": "bestAskPrice",
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdatedAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "royaltyOverride",
+      "docs": [
+        "Royalty recipients a mint's update authority set in place of its metadata creators, which",
+        "metadata caps at 5. A sale passed the mint's RoyaltyOverride pays its recipients the royalty",
+        "instead, and in SOL, those it couldn't fit in its accounts get a claim in the PendingRoyalties."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "recipients",
+            "type": {
+              "array": [
+                {
+                  "defined": "RoyaltyRecipient"
+                },
+                16
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "pendingRoyalties",
+      "docs": [
+        "SOL royalties owed to creators a sale couldn't fit in its transaction, e.g. royalty overrides",
+        "with more recipients than the account budget allows. The sale moves them into this account, one",
+        "per auction house and mint, and distribute_royalties pays the creators later. It holds its rent",
+        "plus the claims."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "rentPayer",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "claims",
+            "type": {
+              "array": [
+                {
+                  "defined": "RoyaltyClaim"
+                },
+                16
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "coOwnerApproval",
+      "docs": [
+        "The co-owners of a wallet that can't sign listings itself, e.g. a multisig PDA, and their",
+        "approvals for the next listing action on one token. Once threshold co-owners approved the same",
+        "action, any of them can sign sell_frozen (with the approved terms) or cancel_sell as the",
+        "seller_authority of the wallet. The approvals are used up by that action."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "seller",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "threshold",
+            "type": "u8"
+          },
+          {
+            "name": "owners",
+            "type": {
+              "array": [
+                "publicKey",
+                8
+              ]
+            }
+          },
+          {
+            "name": "approved",
+            "type": "u8"
+          },
+          {
+            "name": "actionHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "guardian",
+      "docs": [
+        "A key the wallet trusts to cancel its listings and bids for it, but not to create them, e.g. a",
+        "custodian's recovery key or a security team acting on a compromised wallet. Unlike",
+        "CANCEL_AUTHORITY, each wallet picks its own."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "guardian",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "escrowTopUp",
+      "docs": [
+        "A buyer's standing authorization for execute_sale_v2 to top up their escrow out of",
+        "funding_token_account when it's short of a fill. The token account delegates to this PDA, so",
+        "its delegated amount is what's left of the cap. A wSOL account tops up the SOL escrow, any",
+        "other mint the escrow's token account for bids in it."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "fundingTokenAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "programConfig",
+      "docs": [
+        "Per deployment configuration, so the same binary serves every cluster. It only adds to what",
+        "is built in (VALID_PAYMENT_MINTS, CANCEL_AUTHORITY and the default fees), as instructions only",
+        "see it when it is passed in. That includes the GatedInstructions it can disable."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMints",
+            "type": {
+              "array": [
+                "publicKey",
+                8
+              ]
+            }
+          },
+          {
+            "name": "cancelAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "disabledInstructions",
+            "type": "u32"
+          },
+          {
+            "name": "swapAdapter",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMintFees",
+            "type": {
+              "array": [
+                {
+                  "defined": "PaymentMintFees"
+                },
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "cancelAuthorityRegistry",
+      "docs": [
+        "The keys that can cancel orders on behalf of their makers in one auction house, replacing the",
+        "built-in CANCEL_AUTHORITY once the house has one"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "entries",
+            "type": {
+              "array": [
+                {
+                  "defined": "CancelAuthorityEntry"
+                },
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "listingSnapshot",
+      "docs": [
+        "A listing's terms as the notary recorded them at taken_at, e.g. for a support case. Written once",
+        "by snapshot_listing and never changed or closed."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "sellerTradeState",
+            "type": "publicKey"
+          },
+          {
+            "name": "snapshotId",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "takenAt",
+            "type": "i64"
+          },
+          {
+            "name": "seller",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "lastUpdatedAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "cpiAllowlist",
+      "docs": [
+        "The programs that may CPI into sales of one auction house under CpiPolicy::Allowlist"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "programs",
+            "type": {
+              "array": [
+                "publicKey",
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "houseMetrics",
+      "docs": [
+        "Sale totals of one auction house in one payment mint (the default pubkey for SOL), kept up",
+        "to date by the execute instructions whenever it is passed in remaining accounts"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "lastDay",
+            "type": "i64"
+          },
+          {
+            "name": "lifetime",
+            "type": {
+              "defined": "MetricsTotals"
+            }
+          },
+          {
+            "name": "rolling30d",
+            "type": {
+              "defined": "MetricsTotals"
+            }
+          },
+          {
+            "name": "daily",
+            "type": {
+              "array": [
+                {
+                  "defined": "MetricsTotals"
+                },
+                30
+              ]
+            }
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "BuyAuthorization",
+      "docs": [
+        "The message the buyer signs off-chain, Borsh serialized"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouse",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "buyerCreatorRoyaltyBp",
+            "type": "u16"
+          },
+          {
+            "name": "nonce",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "DryRunResult",
+      "docs": [
+        "The outcome of each check dry_run_execute_sale runs on a prospective fill, 0 if it passes,",
+        "otherwise the error code execute_sale_v2 would fail the check with. Checks that need the trade",
+        "states carry trade_states' code when they can't be read."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "signer",
+            "type": "u32"
+          },
+          {
+            "name": "tradeStates",
+            "type": "u32"
+          },
+          {
+            "name": "bid",
+            "type": "u32"
+          },
+          {
+            "name": "listing",
+            "type": "u32"
+          },
+          {
+            "name": "expiry",
+            "type": "u32"
+          },
+          {
+            "name": "escrowBalance",
+            "type": "u32"
+          },
+          {
+            "name": "delegation",
+            "type": "u32"
+          },
+          {
+            "name": "metadata",
+            "type": "u32"
+          },
+          {
+            "name": "guards",
+            "type": "u32"
+          },
+          {
+            "name": "walletLimits",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ProgramConstants",
+      "docs": [
+        "The program's compiled constants, as returned by get_config"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "maxPrice",
+            "type": "u64"
+          },
+          {
+            "name": "defaultMakerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "defaultTakerFeeBp",
+            "type": "u16"
+          },
+          {
+            "name": "maxMakerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "maxTakerFeeBp",
+            "type": "u16"
+          },
+          {
+            "name": "defaultBidExpirySeconds",
+            "type": "i64"
+          },
+          {
+            "name": "cancelAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMints",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "seeds",
+            "type": {
+              "vec": "string"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "ListAutoArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "extraArgs",
+            "type": "bytes"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ListQuote",
+      "docs": [
+        "What a listing at a price nets the seller, as returned by quote_list"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sellerProceeds",
+            "type": "u64"
+          },
+          {
+            "name": "makerFee",
+            "type": "i64"
+          },
+          {
+            "name": "takerFee",
+            "type": "u64"
+          },
+          {
+            "name": "royalty",
+            "type": "u64"
+          },
+          {
+            "name": "buyerTotalCost",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "SellAuthorization",
+      "docs": [
+        "The message the seller signs off-chain, Borsh serialized. It covers every account the relayer",
+        "could otherwise pick for the seller."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouse",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "sellerReferral",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "nonce",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "SweepBuyResult",
+      "docs": [
+        "What sweep_buy returns, one status per listing in the order they were passed"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "statuses",
+            "type": {
+              "vec": {
+                "defined": "SweepFillStatus"
+              }
+            }
+          },
+          {
+            "name": "spent",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "MIP1ExecuteSaleV2Args",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "u16"
+          },
+          {
+            "name": "expectedPaymentMint",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "MIP1SellArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "MIP1UpdateListingArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "extraArgs",
+            "type": "bytes"
+          }
+        ]
+      }
+    },
+    {
+      "name": "OCPExecuteSaleV2Args",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "OCPSellArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "AltPrice",
+      "docs": [
+        "A payment mint a listing accepts besides its own, at a price in that mint. Slots with a zero",
+        "price are unused, a default payment mint is SOL."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "BidEscalation",
+      "docs": [
+        "A bid that climbs from its buyer_price to max_price, linearly between start_at and end_at, so",
+        "a patient buyer can work up towards the floor without bidding again. The escrow must hold",
+        "max_price, the bid fills at any price between buyer_price and its current price."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "maxPrice",
+            "type": "u64"
+          },
+          {
+            "name": "startAt",
+            "type": "i64"
+          },
+          {
+            "name": "endAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "EscrowLedgerBalance",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "locked",
+            "type": "u64"
+          },
+          {
+            "name": "free",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "SpendingAllowanceLock",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "buyerTradeState",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ProceedsSplitRecipient",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "address",
+            "type": "publicKey"
+          },
+          {
+            "name": "bp",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RoyaltyRecipient",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "address",
+            "type": "publicKey"
+          },
+          {
+            "name": "share",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RoyaltyClaim",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "creator",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "PaymentMintFees",
+      "docs": [
+        "The maker and taker fee bps of sales in a payment mint, e.g. higher ones for volatile tokens,",
+        "in place of the defaults when the notary didn't sign for the fees the sale was given"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "CancelAuthorityEntry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "key",
+            "type": "publicKey"
+          },
+          {
+            "name": "enabled",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "MetricsTotals",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "volume",
+            "type": "u64"
+          },
+          {
+            "name": "makerFees",
+            "type": "i64"
+          },
+          {
+            "name": "takerFees",
+            "type": "u64"
+          },
+          {
+            "name": "royalty",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "SweepFillStatus",
+      "docs": [
+        "What sweep_buy did with a listing"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Filled"
+          },
+          {
+            "name": "AlreadyFilled"
+          },
+          {
+            "name": "OverBudget"
+          },
+          {
+            "name": "Unavailable"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RoyaltyMode",
+      "docs": [
+        "What a listing asks of the royalty of the bids that fill it. pNFT and OCP sales always pay full",
+        "royalties and an auction house can enforce them for every listing, whatever the listing says."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Optional"
+          },
+          {
+            "name": "Full"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Expiry",
+      "docs": [
+        "What the i64 expiry of a trade state, or of an instruction creating one, means. It's stored as",
+        "that i64 so layouts don't change: 0 and 1 never expire, a negative expiry is an escrowed",
+        "listing, where program_as_signer holds the token, and any other value is a unix timestamp, for",
+        "escrowed listings negated."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Never"
+          },
+          {
+            "name": "AtTimestamp",
+            "fields": [
+              "i64"
+            ]
+          },
+          {
+            "name": "EscrowedNoExpiry"
+          },
+          {
+            "name": "EscrowedAtTimestamp",
+            "fields": [
+              "i64"
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "CpiPolicy",
+      "docs": [
+        "Which programs may execute sales of an auction house through CPI. Top level sale",
+        "instructions are always allowed."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "AllowAll"
+          },
+          {
+            "name": "Allowlist"
+          }
+        ]
+      }
+    },
+    {
+      "name": "GatedInstruction",
+      "docs": [
+        "Entrypoints a deployment can retire once newer versions replace them"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Buy"
+          },
+          {
+            "name": "Sell"
+          }
+        ]
+      }
+    },
+    {
+      "name": "EscrowAdapterInstruction",
+      "docs": [
+        "Calls M2 makes into an external escrow program that funds bids instead of the buyer's",
+        "escrow PDA. They are encoded like Anchor instructions, the first 8 bytes of",
+        "sha256(\"global:<name>\") followed by the amount as u64 LE where there is one, with accounts:",
+        "0. auction_house (signer) - proves the call comes from M2",
+        "1. buyer_trade_state - the bid the funds are locked for",
+        "2. wallet - the buyer",
+        "3. escrow_payment_account (writable, pay only) - the buyer's escrow PDA, receives the funds",
+        "4. onwards, the adapter's own accounts, as passed after the adapter program in remaining accounts"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Lock",
+            "fields": [
+              {
+                "name": "amount",
+                "type": "u64"
+              }
+            ]
+          },
+          {
+            "name": "Release"
+          },
+          {
+            "name": "Pay",
+            "fields": [
+              {
+                "name": "amount",
+                "type": "u64"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "ListingFlow",
+      "docs": [
+        "The flavor of listing flow, sell/cancel_sell, mip1_* or ocp_*, a token goes through."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Legacy"
+          },
+          {
+            "name": "Mip1"
+          },
+          {
+            "name": "Ocp"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RemainingAccountsTag",
+      "docs": [
+        "A group of optional accounts a client can name in a remaining accounts schema. Tag 0 is",
+        "reserved, so zeroed bytes never read as a group.",
+        "",
+        "A schema describes the remaining accounts as they are passed: for each group, its 1-byte tag",
+        "then a 1-byte count of accounts, with each tag at most once. A handler taking a schema puts",
+        "the groups back into its positional order before splitting them, so clients don't depend on",
+        "where an optional group sits and a new group never shifts the accounts of the others.",
+        "",
+        "The schema always sits at REMAINING_ACCOUNTS_SCHEMA_OFFSET of the instruction's extra args,",
+        "whatever their length, zero padded up to it. Instructions without extra args take the bytes",
+        "appended to their instruction data after the args as such, read through the instructions",
+        "sysvar passed as the last remaining account, which isn't part of the schema."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "ProgramConfig"
+          },
+          {
+            "name": "RateLimit"
+          },
+          {
+            "name": "Payment"
+          },
+          {
+            "name": "EscrowAdapter"
+          },
+          {
+            "name": "WalletLimits"
+          },
+          {
+            "name": "SpendingAllowance"
+          },
+          {
+            "name": "EscrowLedger"
+          },
+          {
+            "name": "Listing"
+          },
+          {
+            "name": "OpenBidIndex"
+          },
+          {
+            "name": "Referrer"
+          },
+          {
+            "name": "RentVault"
+          },
+          {
+            "name": "Payer"
+          },
+          {
+            "name": "StakingAdapter"
+          },
+          {
+            "name": "MakerRewards"
+          },
+          {
+            "name": "ListingIndex"
+          },
+          {
+            "name": "MintMarket"
+          },
+          {
+            "name": "Guardian"
+          },
+          {
+            "name": "CoOwnerApproval"
+          },
+          {
+            "name": "Creators"
+          },
+          {
+            "name": "Keyed"
+          },
+          {
+            "name": "LoanRepayment"
+          },
+          {
+            "name": "ProgramAsSigner"
+          },
+          {
+            "name": "Recipient"
+          },
+          {
+            "name": "NotarySet"
+          }
+        ]
+      }
+    },
+    {
+      "name": "DestinationSpecifier",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Key",
+            "fields": [
+              {
+                "defined": "&'refsPubkey"
+              }
+            ]
+          },
+          {
+            "name": "Ai",
+            "fields": [
+              {
+                "defined": "&'refsAccountInfo<'a>"
+              }
+            ]
+          }
+        ]
+      }
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "PublicKeyMismatch",
+      "msg": "PublicKeyMismatch"
+    },
+    {
+      "code": 6001,
+      "name": "InvalidMintAuthority",
+      "msg": "InvalidMintAuthority"
+    },
+    {
+      "code": 6002,
+      "name": "UninitializedAccount",
+      "msg": "UninitializedAccount"
+    },
+    {
+      "code": 6003,
+      "name": "IncorrectOwner",
+      "msg": "IncorrectOwner"
+    },
+    {
+      "code": 6004,
+      "name": "PublicKeysShouldBeUnique",
+      "msg": "PublicKeysShouldBeUnique"
+    },
+    {
+      "code": 6005,
+      "name": "StatementFalse",
+      "msg": "StatementFalse"
+    },
+    {
+      "code": 6006,
+      "name": "NotRentExempt",
+      "msg": "NotRentExempt"
+    },
+    {
+      "code": 6007,
+      "name": "NumericalOverflow",
+      "msg": "NumericalOverflow"
+    },
+    {
+      "code": 6008,
+      "name": "ExpectedSolAccount",
+      "msg": "Expected a sol account but got an spl token account instead"
+    },
+    {
+      "code": 6009,
+      "name": "CannotExchangeSOLForSol",
+      "msg": "Cannot exchange sol for sol"
+    },
+    {
+      "code": 6010,
+      "name": "SOLWalletMustSign",
+      "msg": "If paying with sol, sol wallet must be signer"
+    },
+    {
+      "code": 6011,
+      "name": "CannotTakeThisActionWithoutAuctionHouseSignOff",
+      "msg": "Cannot take this action without auction house signing too"
+    },
+    {
+      "code": 6012,
+      "name": "NoPayerPresent",
+      "msg": "No payer present on this txn"
+    },
+    {
+      "code": 6013,
+      "name": "DerivedKeyInvalid",
+      "msg": "Derived key invalid"
+    },
+    {
+      "code": 6014,
+      "name": "MetadataDoesntExist",
+      "msg": "Metadata doesn't exist"
+    },
+    {
+      "code": 6015,
+      "name": "InvalidTokenAmount",
+      "msg": "Invalid token amount"
+    },
+    {
+      "code": 6016,
+      "name": "BothPartiesNeedToAgreeToSale",
+      "msg": "Both parties need to agree to this sale"
+    },
+    {
+      "code": 6017,
+      "name": "CannotMatchFreeSalesWithoutAuctionHouseOrSellerSignoff",
+      "msg": "Cannot match free sales unless the auction house or seller signs off"
+    },
+    {
+      "code": 6018,
+      "name": "SaleRequiresSigner",
+      "msg": "This sale requires a signer"
+    },
+    {
+      "code": 6019,
+      "name": "OldSellerNotInitialized",
+      "msg": "Old seller not initialized"
+    },
+    {
+      "code": 6020,
+      "name": "SellerATACannotHaveDelegate",
+      "msg": "Seller ata cannot have a delegate set"
+    },
+    {
+      "code": 6021,
+      "name": "BuyerATACannotHaveDelegate",
+      "msg": "Buyer ata cannot have a delegate set"
+    },
+    {
+      "code": 6022,
+      "name": "NoValidSignerPresent",
+      "msg": "No valid signer present"
+    },
+    {
+      "code": 6023,
+      "name": "InvalidBasisPoints",
+      "msg": "Invalid BP"
+    },
+    {
+      "code": 6024,
+      "name": "InvalidNotary",
+      "msg": "Invalid notary"
+    },
+    {
+      "code": 6025,
+      "name": "EmptyTradeState",
+      "msg": "Empty trade state"
+    },
+    {
+      "code": 6026,
+      "name": "InvalidExpiry",
+      "msg": "Invalid expiry"
+    },
+    {
+      "code": 6027,
+      "name": "InvalidPrice",
+      "msg": "Invalid price"
+    },
+    {
+      "code": 6028,
+      "name": "InvalidRemainingAccountsWithoutProgramAsSigner",
+      "msg": "Invalid remainning accounts without program_as_signer"
+    },
+    {
+      "code": 6029,
+      "name": "InvalidBump",
+      "msg": "Invalid bump"
+    },
+    {
+      "code": 6030,
+      "name": "InvalidCreateAuctionHouseNonce",
+      "msg": "Invalid create auction house nonce"
+    },
+    {
+      "code": 6031,
+      "name": "InvalidAccountState",
+      "msg": "Invalid account state"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidDiscriminator",
+      "msg": "Invalid discriminator"
+    },
+    {
+      "code": 6033,
+      "name": "InvalidPlatformFeeBp",
+      "msg": "Invalid platform fee bp"
+    },
+    {
+      "code": 6034,
+      "name": "InvalidTokenMint",
+      "msg": "Invalid token mint"
+    },
+    {
+      "code": 6035,
+      "name": "InvalidTokenStandard",
+      "msg": "Invalid token standard"
+    },
+    {
+      "code": 6036,
+      "name": "Deprecated",
+      "msg": "Deprecated"
+    },
+    {
+      "code": 6037,
+      "name": "MissingRemainingAccount",
+      "msg": "Missing remaining account"
+    },
+    {
+      "code": 6038,
+      "name": "ConflictingInstructionInTransaction",
+      "msg": "Another instruction in this transaction modifies the same trade state"
+    },
+    {
+      "code": 6039,
+      "name": "NoFreeEscrowBalance",
+      "msg": "No free escrow balance to withdraw"
+    },
+    {
+      "code": 6040,
+      "name": "OpenBidIndexFull",
+      "msg": "Open bid index is full"
+    },
+    {
+      "code": 6041,
+      "name": "InvalidSignedAuthorization",
+      "msg": "Missing or invalid off-chain signed authorization"
+    },
+    {
+      "code": 6042,
+      "name": "StaleAuthorizationNonce",
+      "msg": "Authorization nonce was already used"
+    },
+    {
+      "code": 6043,
+      "name": "EditionSaleSoldOut",
+      "msg": "All prints of this edition sale have been sold"
+    },
+    {
+      "code": 6044,
+      "name": "BatchTooLarge",
+      "msg": "Too many accounts in batch"
+    },
+    {
+      "code": 6045,
+      "name": "EscrowAdapterNotAllowed",
+      "msg": "Escrow adapter is not allowed by the auction house"
+    },
+    {
+      "code": 6046,
+      "name": "EscrowAdapterUnderpaid",
+      "msg": "Escrow adapter paid less than the bid price"
+    },
+    {
+      "code": 6047,
+      "name": "DelegateMismatch",
+      "msg": "Token account delegate is not the program signer"
+    },
+    {
+      "code": 6048,
+      "name": "WrongAuctionHouseForState",
+      "msg": "Trade state belongs to another auction house"
+    },
+    {
+      "code": 6049,
+      "name": "TokenNotEscrowed",
+      "msg": "Token is not escrowed by the program signer"
+    },
+    {
+      "code": 6050,
+      "name": "TokenAlreadyEscrowed",
+      "msg": "Token is already escrowed by another listing"
+    },
+    {
+      "code": 6051,
+      "name": "StaleMigrationState",
+      "msg": "Migration trade state doesn't match the token"
+    },
+    {
+      "code": 6052,
+      "name": "EscrowBalanceTooLow",
+      "msg": "Escrow balance is lower than the price"
+    },
+    {
+      "code": 6053,
+      "name": "TradeStateMismatch",
+      "msg": "Trade state doesn't match the given accounts or args"
+    },
+    {
+      "code": 6054,
+      "name": "TradeStateAlreadyExists",
+      "msg": "Trade state already exists"
+    },
+    {
+      "code": 6055,
+      "name": "EscrowAdapterMismatch",
+      "msg": "Escrow adapter doesn't match the bid"
+    },
+    {
+      "code": 6056,
+      "name": "UnexpectedRemainingAccounts",
+      "msg": "Unexpected remaining accounts"
+    },
+    {
+      "code": 6057,
+      "name": "OpenBidsRemaining",
+      "msg": "Wallet still has open bids"
+    },
+    {
+      "code": 6058,
+      "name": "PaymentMintMismatch",
+      "msg": "Payment mint doesn't match the expected payment mint"
+    },
+    {
+      "code": 6059,
+      "name": "BidTooLowForListing",
+      "msg": "Bid is below the minimum share of the listing price"
+    },
+    {
+      "code": 6060,
+      "name": "AutoRelistNotAllowed",
+      "msg": "Listing can't be auto relisted"
+    },
+    {
+      "code": 6061,
+      "name": "StrandedTradeState",
+      "msg": "Trade state was left without a discriminator, repair it with repair_trade_state"
+    },
+    {
+      "code": 6062,
+      "name": "NotUpgradeAuthority",
+      "msg": "Signer is not the program's upgrade authority"
+    },
+    {
+      "code": 6063,
+      "name": "CancelAuthorityRegistryFull",
+      "msg": "Cancel authority registry is full"
+    },
+    {
+      "code": 6064,
+      "name": "ListingRequiresFullRoyalty",
+      "msg": "Listing requires bids to pay the full creator royalty"
+    },
+    {
+      "code": 6065,
+      "name": "AuctionHouseEnforcesRoyalty",
+      "msg": "Auction house enforces the full creator royalty"
+    },
+    {
+      "code": 6066,
+      "name": "NoMakerRewardsToClaim",
+      "msg": "No maker rewards to claim, or the rewards vault is empty"
+    },
+    {
+      "code": 6067,
+      "name": "EscrowLedgerFull",
+      "msg": "Escrow ledger has no free slot for another payment mint"
+    },
+    {
+      "code": 6068,
+      "name": "EscrowBalanceLocked",
+      "msg": "Withdrawal would leave the escrow below what its open bids lock"
+    },
+    {
+      "code": 6069,
+      "name": "MetadataPinMismatch",
+      "msg": "Metadata no longer matches the hash the bid pinned"
+    },
+    {
+      "code": 6070,
+      "name": "SpendingAllowanceExceeded",
+      "msg": "Spending allowance limit exceeded"
+    },
+    {
+      "code": 6071,
+      "name": "HouseUpdateNotEffective",
+      "msg": "Scheduled auction house update is not effective yet"
+    },
+    {
+      "code": 6072,
+      "name": "BuyerNotAttested",
+      "msg": "Listing requires a valid attestation for the buyer"
+    },
+    {
+      "code": 6073,
+      "name": "MissingCreatorAccount",
+      "msg": "Missing the account of a creator owed royalty"
+    },
+    {
+      "code": 6074,
+      "name": "CpiCallerNotAllowed",
+      "msg": "Program is not allowed to CPI into sales of this auction house"
+    },
+    {
+      "code": 6075,
+      "name": "CpiAllowlistFull",
+      "msg": "CPI allowlist is full"
+    },
+    {
+      "code": 6076,
+      "name": "InvalidBidEscalation",
+      "msg": "Invalid bid escalation"
+    },
+    {
+      "code": 6077,
+      "name": "LoanRepaymentNotSupported",
+      "msg": "Loan repayment only works for SOL sales paid to the seller's wallet"
+    },
+    {
+      "code": 6078,
+      "name": "LoanProgramTookFromSeller",
+      "msg": "Loan program took from the seller"
+    },
+    {
+      "code": 6079,
+      "name": "WalletLimitExceeded",
+      "msg": "Wallet limit exceeded"
+    },
+    {
+      "code": 6080,
+      "name": "InvalidEscrowLockDeadline",
+      "msg": "Escrow lock deadline must be in the future and within MAX_ESCROW_LOCK_SLOTS"
+    },
+    {
+      "code": 6081,
+      "name": "EscrowLockActive",
+      "msg": "Escrow lock is active until its deadline, only the notary can release it early"
+    },
+    {
+      "code": 6082,
+      "name": "TokenAccountFrozen",
+      "msg": "Token account is frozen, unstake it first or pass the auction house's staking adapter"
+    },
+    {
+      "code": 6083,
+      "name": "AutoMatchNotEnabled",
+      "msg": "Both the listing and the bid must opt into auto matching"
+    },
+    {
+      "code": 6084,
+      "name": "RateLimitExceeded",
+      "msg": "Wallet exceeded the auction house's instructions per slot"
+    },
+    {
+      "code": 6085,
+      "name": "HalfMigratedTradeState",
+      "msg": "Listing was left half migrated, recover it with recover_seller_trade_state"
+    },
+    {
+      "code": 6086,
+      "name": "ListingInsuranceLocked",
+      "msg": "Listing insurance is still open to claims, withdraw again once the delay passed"
+    },
+    {
+      "code": 6087,
+      "name": "ListingNotBroken",
+      "msg": "Token is still escrowed or delegated by the seller, the listing isn't broken"
+    },
+    {
+      "code": 6088,
+      "name": "InvalidRemainingAccountsSchema",
+      "msg": "Remaining accounts schema is malformed or doesn't match the remaining accounts"
+    },
+    {
+      "code": 6089,
+      "name": "InvariantViolated",
+      "msg": "Strict invariant violated"
+    },
+    {
+      "code": 6090,
+      "name": "InvalidProceedsSplit",
+      "msg": "Proceeds split has too many recipients, an empty one or more than 10000 bp in total"
+    },
+    {
+      "code": 6091,
+      "name": "ProceedsSplitNotSupported",
+      "msg": "Proceeds split only applies to SOL proceeds paid to the seller's wallet"
+    },
+    {
+      "code": 6092,
+      "name": "InvalidCoOwners",
+      "msg": "Co-owners must be 1 to 8 distinct keys with a threshold between 1 and their count"
+    },
+    {
+      "code": 6093,
+      "name": "CoOwnerApprovalMissing",
+      "msg": "Co-owners haven't approved this listing action, or the signer isn't one of them"
+    },
+    {
+      "code": 6094,
+      "name": "InvalidGuardian",
+      "msg": "Guardian didn't sign or isn't the wallet's guardian"
+    },
+    {
+      "code": 6095,
+      "name": "SwapAdapterNotAllowed",
+      "msg": "Swap program isn't the program config's swap adapter"
+    },
+    {
+      "code": 6096,
+      "name": "SwapSlippageExceeded",
+      "msg": "Swap spent more than the bid's max slippage allows or paid less than the sale needs"
+    },
+    {
+      "code": 6097,
+      "name": "EscrowTopUpExceeded",
+      "msg": "Escrow top-up's funding token account doesn't delegate enough to cover the shortfall"
+    },
+    {
+      "code": 6098,
+      "name": "InvalidNotarySet",
+      "msg": "Notary set must be 1 to 5 distinct keys with a threshold between 1 and their count"
+    },
+    {
+      "code": 6099,
+      "name": "ZombieTradeState",
+      "msg": "Trade state was closed but holds lamports again, drain it with defund_zombie_state"
+    },
+    {
+      "code": 6100,
+      "name": "PendingRoyaltiesFull",
+      "msg": "Pending royalties have no free slot for another creator, distribute them first"
+    },
+    {
+      "code": 6101,
+      "name": "InvalidBidGroup",
+      "msg": "Bid group must have 1 to 8 distinct ungrouped bids of the buyer with max_fills between 1 and their count"
+    },
+    {
+      "code": 6102,
+      "name": "BidGroupConsumed",
+      "msg": "Bid group already had its max fills, its other bids can't fill"
+    },
+    {
+      "code": 6103,
+      "name": "ListingHidden",
+      "msg": "Listing is hidden until its reveal time"
+    },
+    {
+      "code": 6104,
+      "name": "SpendingAllowanceFull",
+      "msg": "Spending allowance has too many open bids"
+    },
+    {
+      "code": 6105,
+      "name": "FeeChangeNotScheduled",
+      "msg": "Fee changes must be scheduled with schedule_house_update"
+    },
+    {
+      "code": 6106,
+      "name": "LoanRepaymentMismatch",
+      "msg": "Loan accounts must be passed for listings that repay a loan, with the seller's loan vault, and only for them"
+    },
+    {
+      "code": 6107,
+      "name": "WalletLimitsRequired",
+      "msg": "Auction house enforces wallet limits, the wallet's WalletLimits address must be passed"
+    },
+    {
+      "code": 6108,
+      "name": "InvalidRoyaltyOverride",
+      "msg": "Royalty override must have 1 to 16 distinct recipients with shares adding up to 100"
+    },
+    {
+      "code": 6109,
+      "name": "EscrowNotSweepable",
+      "msg": "Escrow holds no dust to sweep, it's empty or above the sweepable amount"
+    },
+    {
+      "code": 6110,
+      "name": "OpenBidIndexIncomplete",
+      "msg": "Open bid index was created after the wallet's escrow backed bids, it can't vouch for all of them"
+    },
+    {
+      "code": 6111,
+      "name": "FloorConditionNotMet",
+      "msg": "Bid only fills while its collection's floor is at or below its max floor price"
+    },
+    {
+      "code": 6112,
+      "name": "InvalidMasterEdition",
+      "msg": "Master edition account can't be read as a master edition"
+    },
+    {
+      "code": 6113,
+      "name": "TreasuryBalanceTooLow",
+      "msg": "Treasury must keep its minimum leftover balance"
+    },
+    {
+      "code": 6114,
+      "name": "NotZombieTradeState",
+      "msg": "Trade state still holds a valid order, it isn't a zombie"
+    },
+    {
+      "code": 6115,
+      "name": "TrailingArgsMismatch",
+      "msg": "Current instruction isn't the one its trailing args were read for"
+    },
+    {
+      "code": 6116,
+      "name": "SpendingAllowanceMismatch",
+      "msg": "Spending allowance is for another auction house or spender"
+    },
+    {
+      "code": 6117,
+      "name": "InvalidTradeStateLength",
+      "msg": "Seller trade state has a length this program never wrote"
+    }
+  ]
+};
+
+export const IDL: M2 = {
+  "version": "0.1.0",
+  "name": "m2",
+  "instructions": [
+    {
+      "name": "withdrawFromTreasury",
+      "accounts": [
+        {
+          "name": "treasuryWithdrawalDestination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouseTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updateAuctionHouse",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "newAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "treasuryWithdrawalDestination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "sellerFeeBasisPoints",
+          "type": {
+            "option": "u16"
+          }
+        },
+        {
+          "name": "buyerReferralBp",
+          "type": {
+            "option": "u16"
+          }
+        },
+        {
+          "name": "sellerReferralBp",
+          "type": {
+            "option": "u16"
+          }
+        },
+        {
+          "name": "requiresNotary",
+          "type": {
+            "option": "bool"
+          }
+        },
+        {
+          "name": "nprob",
+          "type": {
+            "option": "u8"
+          }
+        },
+        {
+          "name": "sandwichGuard",
+          "type": {
+            "option": "bool"
+          }
+        },
+        {
+          "name": "protocolFeeRecipient",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "protocolFeeShareBp",
+          "type": {
+            "option": "u16"
+          }
+        },
+        {
+          "name": "escrowAdapters",
+          "type": {
+            "option": {
+              "array": [
+                "publicKey",
+                2
+              ]
+            }
+          }
+        },
+        {
+          "name": "minBidBpOfListPrice",
+          "type": {
+            "option": "u16"
+          }
+        },
+        {
+          "name": "delistGraceSeconds",
+          "type": {
+            "option": "u32"
+          }
+        },
+        {
+          "name": "enforceRoyalties",
+          "type": {
+            "option": "bool"
+          }
+        },
+        {
+          "name": "makerRewardsLamportsPerDay",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "makerRewardsVolumeBp",
+          "type": {
+            "option": "u16"
+          }
+        },
+        {
+          "name": "attestationAuthority",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "loanProgram",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "stakingAdapter",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "maxIxsPerSlot",
+          "type": {
+            "option": "u8"
+          }
+        },
+        {
+          "name": "allowSfts",
+          "type": {
+            "option": "bool"
+          }
+        },
+        {
+          "name": "enforceWalletLimits",
+          "type": {
+            "option": "bool"
+          }
+        }
+      ]
+    },
+    {
+      "name": "withdraw",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "escrowPaymentBump",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "deposit",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "escrowPaymentBump",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "sell",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "sellerStateBump",
+          "type": "u8"
+        },
+        {
+          "name": "programAsSignerBump",
+          "type": "u8"
+        },
+        {
+          "name": "buyerPrice",
+          "type": "u64"
+        },
+        {
+          "name": "tokenSize",
+          "type": "u64"
+        },
+        {
+          "name": "sellerStateExpiry",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "cancelSell",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "buyerPrice",
+          "type": "u64"
+        },
+        {
+          "name": "tokenSize",
+          "type": "u64"
+        },
+        {
+          "name": "sellerStateExpiry",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "buy",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReferral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "buyerStateBump",
+          "type": "u8"
+        },
+        {
+          "name": "escrowPaymentBump",
+          "type": "u8"
+        },
+        {
+          "name": "buyerPrice",
+          "type": "u64"
+        },
+        {
+          "name": "tokenSize",
+          "type": "u64"
+        },
+        {
+          "name": "buyerStateExpiry",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "buyV2",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReferral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "buyerPrice",
+          "type": "u64"
+        },
+        {
+          "name": "tokenSize",
+          "type": "u64"
+        },
+        {
+          "name": "buyerStateExpiry",
+          "type": "i64"
+        },
+        {
+          "name": "buyerCreatorRoyaltyBp",
+          "type": "u16"
+        },
+        {
+          "name": "extraArgs",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "cancelBuy",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReferral",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "buyerPrice",
+          "type": "u64"
+        },
+        {
+          "name": "tokenSize",
+          "type": "u64"
+        },
+        {
+          "name": "buyerStateExpiry",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "ocpSell",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpMintState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ocpPolicy",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpFreezeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "cmtProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "OCPSellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "ocpCancelSell",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ocpMintState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ocpPolicy",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpFreezeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "cmtProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "ocpExecuteSaleV2",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTokenAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTokenAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouseTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerEscrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ocpMintState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "ocpPolicy",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpFreezeAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "cmtProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "OCPExecuteSaleV2Args"
+          }
+        }
+      ]
+    },
+    {
+      "name": "executeSaleV2",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReceiptTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouseTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "escrowPaymentBump",
+          "type": "u8"
+        },
+        {
+          "name": "programAsSignerBump",
+          "type": "u8"
+        },
+        {
+          "name": "buyerPrice",
+          "type": "u64"
+        },
+        {
+          "name": "tokenSize",
+          "type": "u64"
+        },
+        {
+          "name": "buyerStateExpiry",
+          "type": "i64"
+        },
+        {
+          "name": "sellerStateExpiry",
+          "type": "i64"
+        },
+        {
+          "name": "makerFeeBp",
+          "type": "i16"
+        },
+        {
+          "name": "takerFeeBp",
+          "type": "u16"
+        },
+        {
+          "name": "expectedPaymentMint",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "mip1Sell",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "migrationSellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAta",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "escrow mode for init sell:        we transfer from token_account to token_ata",
+            "escrow mode for change price:     token_account is the same as token_ata",
+            "migration mode for change price:  token_ata is not used, because we only need token_account which is owned by program_as_signer"
+          ]
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "edition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRulesProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRules",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ownerTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destinationTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "MIP1SellArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "mip1ExecuteSaleV2",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReceiptTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouseTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerEscrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "edition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRulesProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "authorizationRules",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "ownerTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destinationTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "MIP1ExecuteSaleV2Args"
+          }
+        }
+      ]
+    },
+    {
+      "name": "mip1CancelSell",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAta",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "should always be ATA of (mint, wallet), or of (mint, recipient) when delisting into another wallet"
+          ]
+        },
+        {
+          "name": "tokenAccountTemp",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "should always be ATA of (mint, program_as_signer)"
+          ]
+        },
+        {
+          "name": "tempTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "edition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRulesProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRules",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ownerTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destinationTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setEscrowConfig",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowConfig",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "keepOpen",
+          "type": "bool"
+        },
+        {
+          "name": "minResidentBalance",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawFromRentVault",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rentVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawAll",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "openBidIndex",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "sellWithAuthorization",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationNonce",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "expiry",
+          "type": "i64"
+        },
+        {
+          "name": "nonce",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "buyWithAuthorization",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationNonce",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReferral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "expiry",
+          "type": "i64"
+        },
+        {
+          "name": "buyerCreatorRoyaltyBp",
+          "type": "u16"
+        },
+        {
+          "name": "nonce",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "sellEdition",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "masterEdition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "editionSaleState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "maxPrints",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "executeEditionSale",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "masterEdition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "editionMarker",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newMint",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newMetadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newEdition",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newMetadataUpdateAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouseTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "editionSaleState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "cancelEditionSale",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "editionSaleState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "sweepAbandonedEscrow",
+      "accounts": [
+        {
+          "name": "cranker",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "openBidIndex",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "mip1UpdateListing",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAta",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "MIP1UpdateListingArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "cancelBuyBatch",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "mip1BidAndExecute",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReceiptTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouseTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerEscrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "edition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authorizationRulesProgram",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "authorizationRules",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "ownerTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destinationTokenRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "MIP1ExecuteSaleV2Args"
+          }
+        }
+      ]
+    },
+    {
+      "name": "sellIntoBid",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReceiptTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouseTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "escrowPaymentBump",
+          "type": "u8"
+        },
+        {
+          "name": "programAsSignerBump",
+          "type": "u8"
+        },
+        {
+          "name": "buyerPrice",
+          "type": "u64"
+        },
+        {
+          "name": "tokenSize",
+          "type": "u64"
+        },
+        {
+          "name": "buyerStateExpiry",
+          "type": "i64"
+        },
+        {
+          "name": "sellerStateExpiry",
+          "type": "i64"
+        },
+        {
+          "name": "makerFeeBp",
+          "type": "i16"
+        },
+        {
+          "name": "takerFeeBp",
+          "type": "u16"
+        },
+        {
+          "name": "expectedPaymentMint",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "depositWithReference",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "depositReceipt",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "reference",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "initHouseMetrics",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "houseMetrics",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "paymentMint",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "autoRelist",
+      "accounts": [
+        {
+          "name": "cranker",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "sellFrozen",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "edition",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMetadataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "expiry",
+          "type": "i64"
+        },
+        {
+          "name": "extraArgs",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "quoteOcpRoyalty",
+      "accounts": [
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ocpPolicy",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        }
+      ],
+      "returns": "u16"
+    },
+    {
+      "name": "repairTradeState",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "registerReferrer",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "referral",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "referrer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "payout",
+          "type": "publicKey"
+        },
+        {
+          "name": "attributionWindowSeconds",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "initProgramConfig",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "program",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programData",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programConfig",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "configAuthority",
+          "type": "publicKey"
+        },
+        {
+          "name": "paymentMints",
+          "type": {
+            "array": [
+              "publicKey",
+              8
+            ]
+          }
+        },
+        {
+          "name": "cancelAuthority",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "updateProgramConfig",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "programConfig",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newAuthority",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "paymentMints",
+          "type": {
+            "option": {
+              "array": [
+                "publicKey",
+                8
+              ]
+            }
+          }
+        },
+        {
+          "name": "cancelAuthority",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "disabledInstructions",
+          "type": {
+            "option": "u32"
+          }
+        },
+        {
+          "name": "swapAdapter",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "paymentMintFees",
+          "type": {
+            "option": {
+              "array": [
+                {
+                  "defined": "PaymentMintFees"
+                },
+                8
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setCancelAuthority",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "cancelAuthorityRegistry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "key",
+          "type": "publicKey"
+        },
+        {
+          "name": "enabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "closeStaleSts",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initMakerRewards",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "makerRewards",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimMakerRewards",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "makerRewards",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "makerRewardsVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initEscrowLedger",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowLedger",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "upgradeLegacySts",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "pays the rent the bigger layout needs"
+          ]
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setSpendingAllowance",
+      "accounts": [
+        {
+          "name": "treasury",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "spender",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "spendingAllowance",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "limit",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "scheduleHouseUpdate",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pendingHouseUpdate",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "sellerFeeBasisPoints",
+          "type": {
+            "option": "u16"
+          }
+        },
+        {
+          "name": "buyerReferralBp",
+          "type": {
+            "option": "u16"
+          }
+        },
+        {
+          "name": "sellerReferralBp",
+          "type": {
+            "option": "u16"
+          }
+        },
+        {
+          "name": "protocolFeeShareBp",
+          "type": {
+            "option": "u16"
+          }
+        }
+      ]
+    },
+    {
+      "name": "applyHouseUpdate",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pendingHouseUpdate",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "issueAttestation",
+      "accounts": [
+        {
+          "name": "attestationAuthority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "wallet",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "attestation",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "expiry",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "revokeAttestation",
+      "accounts": [
+        {
+          "name": "attestationAuthority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "wallet",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "attestation",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setCpiPolicy",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "cpiAllowlist",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "cpiPolicy",
+          "type": {
+            "defined": "CpiPolicy"
+          }
+        },
+        {
+          "name": "program",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "allowed",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "snapshotListing",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "listingSnapshot",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "snapshotId",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "getConfig",
+      "accounts": [],
+      "args": [],
+      "returns": {
+        "defined": "ProgramConstants"
+      }
+    },
+    {
+      "name": "setWalletLimits",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "wallet",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "walletLimits",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "maxDailyBuyVolume",
+          "type": "u64"
+        },
+        {
+          "name": "maxOpenBids",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "cancelListingAuto",
+      "accounts": [
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "listAuto",
+      "accounts": [
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "args",
+          "type": {
+            "defined": "ListAutoArgs"
+          }
+        }
+      ]
+    },
+    {
+      "name": "lockEscrowForQuote",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowLock",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "orderHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "deadlineSlot",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "releaseEscrowLock",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowLock",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "matchOrders",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReceiptTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouseTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "escrowPaymentBump",
+          "type": "u8"
+        },
+        {
+          "name": "programAsSignerBump",
+          "type": "u8"
+        },
+        {
+          "name": "buyerPrice",
+          "type": "u64"
+        },
+        {
+          "name": "tokenSize",
+          "type": "u64"
+        },
+        {
+          "name": "buyerStateExpiry",
+          "type": "i64"
+        },
+        {
+          "name": "sellerStateExpiry",
+          "type": "i64"
+        },
+        {
+          "name": "makerFeeBp",
+          "type": "i16"
+        },
+        {
+          "name": "takerFeeBp",
+          "type": "u16"
+        },
+        {
+          "name": "expectedPaymentMint",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "initRateLimit",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rateLimit",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "recoverSellerTradeState",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "quoteList",
+      "accounts": [
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "makerFeeBp",
+          "type": "i16"
+        },
+        {
+          "name": "takerFeeBp",
+          "type": "u16"
+        },
+        {
+          "name": "buyerCreatorRoyaltyBp",
+          "type": "u16"
+        }
+      ],
+      "returns": {
+        "defined": "ListQuote"
+      }
+    },
+    {
+      "name": "depositListingInsurance",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "listingInsurance",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawListingInsurance",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "listingInsurance",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimListingInsurance",
+      "accounts": [
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "listingInsurance",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setProceedsSplit",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "proceedsSplit",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "recipients",
+          "type": {
+            "vec": {
+              "defined": "ProceedsSplitRecipient"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "closeProceedsSplit",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "proceedsSplit",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setCoOwners",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "seller",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "the co-owned wallet, e.g. a multisig PDA signing through its program"
+          ]
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "coOwnerApproval",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "owners",
+          "type": {
+            "vec": "publicKey"
+          }
+        },
+        {
+          "name": "threshold",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "approveCoOwnerAction",
+      "accounts": [
+        {
+          "name": "coOwner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "seller",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "coOwnerApproval",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "expiry",
+          "type": "i64"
+        },
+        {
+          "name": "paymentMint",
+          "type": "publicKey"
+        },
+        {
+          "name": "extraArgs",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "setGuardian",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "guardian",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "guardianAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "removeGuardian",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "guardianAccount",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "sweepBuy",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouseTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "maxBudget",
+          "type": "u64"
+        },
+        {
+          "name": "makerFeeBp",
+          "type": "i16"
+        },
+        {
+          "name": "takerFeeBp",
+          "type": "u16"
+        }
+      ],
+      "returns": {
+        "defined": "SweepBuyResult"
+      }
+    },
+    {
+      "name": "dryRunExecuteSale",
+      "accounts": [
+        {
+          "name": "buyer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "notary",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowPaymentAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReceiptTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouseTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "buyerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "sellerReferral",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "ataProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "escrowPaymentBump",
+          "type": "u8"
+        },
+        {
+          "name": "programAsSignerBump",
+          "type": "u8"
+        },
+        {
+          "name": "buyerPrice",
+          "type": "u64"
+        },
+        {
+          "name": "tokenSize",
+          "type": "u64"
+        },
+        {
+          "name": "buyerStateExpiry",
+          "type": "i64"
+        },
+        {
+          "name": "sellerStateExpiry",
+          "type": "i64"
+        },
+        {
+          "name": "makerFeeBp",
+          "type": "i16"
+        },
+        {
+          "name": "takerFeeBp",
+          "type": "u16"
+        },
+        {
+          "name": "expectedPaymentMint",
+          "type": "publicKey"
+        }
+      ],
+      "returns": {
+        "defined": "DryRunResult"
+      }
+    },
+    {
+      "name": "setEscrowTopUp",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "fundingTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "escrowTopUp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "cap",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "removeEscrowTopUp",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "fundingTokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "escrowTopUp",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setNotarySet",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "notarySet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "notaries",
+          "type": {
+            "vec": "publicKey"
+          }
+        },
+        {
+          "name": "threshold",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "closeNotarySet",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "notarySet",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "defundZombieState",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "distributeRoyalties",
+      "accounts": [
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pendingRoyalties",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createBidGroup",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "bidGroup",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "id",
+          "type": "u64"
+        },
+        {
+          "name": "maxFills",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "closeBidGroup",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "bidGroup",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initMintMarket",
+      "accounts": [
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mintMarket",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "refreshMintMarket",
+      "accounts": [
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mintMarket",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "invalidateBrokenListing",
+      "accounts": [
+        {
+          "name": "seller",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "auctionHouse",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "sellerTradeState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "programAsSigner",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setRoyaltyOverride",
+      "accounts": [
+        {
+          "name": "updateAuthority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyOverride",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "recipients",
+          "type": {
+            "vec": {
+              "defined": "RoyaltyRecipient"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "closeRoyaltyOverride",
+      "accounts": [
+        {
+          "name": "updateAuthority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "tokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "metadata",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "royaltyOverride",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    }
+  ],
+  "accounts": [
+    {
+      "name": "buyerTradeState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyer",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerReferral",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerPrice",
+            "type": "u64"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenSize",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "sellerTradeState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "seller",
+            "type": "publicKey"
+          },
+          {
+            "name": "sellerReferral",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerPrice",
+            "type": "u64"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenSize",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "sellerTradeStateV2",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "seller",
+            "type": "publicKey"
+          },
+          {
+            "name": "sellerReferral",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerPrice",
+            "type": "u64"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenSize",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "sellerTradeStateV3",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "seller",
+            "type": "publicKey"
+          },
+          {
+            "name": "sellerReferral",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerPrice",
+            "type": "u64"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenSize",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "createdAt",
+            "type": "i64"
+          },
+          {
+            "name": "lastUpdatedAt",
+            "type": "i64"
+          },
+          {
+            "name": "origin",
+            "type": {
+              "array": [
+                "u8",
+                8
+              ]
+            }
+          },
+          {
+            "name": "orderMemo",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "proceedsAsWsol",
+            "type": "bool"
+          },
+          {
+            "name": "autoRelistPriceBp",
+            "type": "u16"
+          },
+          {
+            "name": "autoRelistMax",
+            "type": "u8"
+          },
+          {
+            "name": "autoRelistCount",
+            "type": "u8"
+          },
+          {
+            "name": "frozen",
+            "type": "bool"
+          },
+          {
+            "name": "royaltyMode",
+            "type": {
+              "defined": "RoyaltyMode"
+            }
+          },
+          {
+            "name": "makerRewards",
+            "type": "bool"
+          },
+          {
+            "name": "requiresAttestation",
+            "type": "bool"
+          },
+          {
+            "name": "autoMatch",
+            "type": "bool"
+          },
+          {
+            "name": "royaltyLock",
+            "type": "bool"
+          },
+          {
+            "name": "royaltyBpSnapshot",
+            "type": "u16"
+          },
+          {
+            "name": "hiddenUntil",
+            "type": "i64"
+          },
+          {
+            "name": "listingIndexes",
+            "type": "u8"
+          },
+          {
+            "name": "repayLoan",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "auctionHouse",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseTreasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "treasuryWithdrawalDestination",
+            "type": "publicKey"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "creator",
+            "type": "publicKey"
+          },
+          {
+            "name": "notary",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "treasuryBump",
+            "type": "u8"
+          },
+          {
+            "name": "sellerFeeBasisPoints",
+            "type": "u16"
+          },
+          {
+            "name": "buyerReferralBp",
+            "type": "u16"
+          },
+          {
+            "name": "sellerReferralBp",
+            "type": "u16"
+          },
+          {
+            "name": "requiresNotary",
+            "type": "bool"
+          },
+          {
+            "name": "nprob",
+            "type": "u8"
+          },
+          {
+            "name": "sandwichGuard",
+            "type": "bool"
+          },
+          {
+            "name": "protocolFeeRecipient",
+            "type": "publicKey"
+          },
+          {
+            "name": "protocolFeeShareBp",
+            "type": "u16"
+          },
+          {
+            "name": "escrowAdapters",
+            "type": {
+              "array": [
+                "publicKey",
+                2
+              ]
+            }
+          },
+          {
+            "name": "minBidBpOfListPrice",
+            "type": "u16"
+          },
+          {
+            "name": "delistGraceSeconds",
+            "type": "u32"
+          },
+          {
+            "name": "cancelAuthorityRegistry",
+            "type": "bool"
+          },
+          {
+            "name": "enforceRoyalties",
+            "type": "bool"
+          },
+          {
+            "name": "makerRewardsLamportsPerDay",
+            "type": "u64"
+          },
+          {
+            "name": "makerRewardsVolumeBp",
+            "type": "u16"
+          },
+          {
+            "name": "attestationAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "cpiPolicy",
+            "type": {
+              "defined": "CpiPolicy"
+            }
+          },
+          {
+            "name": "loanProgram",
+            "type": "publicKey"
+          },
+          {
+            "name": "stakingAdapter",
+            "type": "publicKey"
+          },
+          {
+            "name": "maxIxsPerSlot",
+            "type": "u8"
+          },
+          {
+            "name": "allowSfts",
+            "type": "bool"
+          },
+          {
+            "name": "enforceWalletLimits",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "attestation",
+      "docs": [
+        "The auction house's attestation_authority vouching for a wallet, e.g. after KYC, so it can buy",
+        "listings that require an attestation. It's only valid while its issuer is still the house's",
+        "attestation_authority and, if it has one, until expiry."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "issuer",
+            "type": "publicKey"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "pendingHouseUpdate",
+      "docs": [
+        "Fee changes the authority scheduled with schedule_house_update. Anyone can apply them to the",
+        "auction house once effective_at has passed, so fills in flight never see a fee change early."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "effectiveAt",
+            "type": "i64"
+          },
+          {
+            "name": "sellerFeeBasisPoints",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "buyerReferralBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "sellerReferralBp",
+            "type": {
+              "option": "u16"
+            }
+          },
+          {
+            "name": "protocolFeeShareBp",
+            "type": {
+              "option": "u16"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "buyerTradeStateV2",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyer",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerReferral",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerPrice",
+            "type": "u64"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenSize",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "buyerCreatorRoyaltyBp",
+            "type": "u16"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "indexed",
+            "type": "bool"
+          },
+          {
+            "name": "escrowAdapter",
+            "type": "publicKey"
+          },
+          {
+            "name": "orderMemo",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "receiptOwner",
+            "type": "publicKey"
+          },
+          {
+            "name": "ledgered",
+            "type": "bool"
+          },
+          {
+            "name": "metadataPin",
+            "type": {
+              "array": [
+                "u8",
+                24
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "buyerTradeStateV3",
+      "docs": [
+        "The current buyer trade state, older bids are migrated to it when they're updated"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyer",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerReferral",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyerPrice",
+            "type": "u64"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenSize",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "buyerCreatorRoyaltyBp",
+            "type": "u16"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "indexed",
+            "type": "bool"
+          },
+          {
+            "name": "escrowAdapter",
+            "type": "publicKey"
+          },
+          {
+            "name": "orderMemo",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "receiptOwner",
+            "type": "publicKey"
+          },
+          {
+            "name": "ledgered",
+            "type": "bool"
+          },
+          {
+            "name": "metadataPin",
+            "type": {
+              "array": [
+                "u8",
+                24
+              ]
+            }
+          },
+          {
+            "name": "escalation",
+            "type": {
+              "defined": "BidEscalation"
+            }
+          },
+          {
+            "name": "limited",
+            "type": "bool"
+          },
+          {
+            "name": "priceIsAllIn",
+            "type": "bool"
+          },
+          {
+            "name": "autoMatch",
+            "type": "bool"
+          },
+          {
+            "name": "swapMaxSlippageBp",
+            "type": "u16"
+          },
+          {
+            "name": "bidGroup",
+            "type": "publicKey"
+          },
+          {
+            "name": "allowanceFunded",
+            "type": "bool"
+          },
+          {
+            "name": "maxFloorPrice",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "escrowConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "keepOpen",
+            "type": "bool"
+          },
+          {
+            "name": "minResidentBalance",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "openBidIndex",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "lockedAmount",
+            "type": "u64"
+          },
+          {
+            "name": "openBids",
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "tracksAllBids",
+            "type": "bool"
+          },
+          {
+            "name": "bids",
+            "type": {
+              "vec": "publicKey"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "listingIndex",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "key",
+            "type": "publicKey"
+          },
+          {
+            "name": "listings",
+            "type": "u32"
+          },
+          {
+            "name": "cursor",
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "recent",
+            "type": {
+              "array": [
+                "publicKey",
+                16
+              ]
+            }
+          },
+          {
+            "name": "floorPrice",
+            "type": "u64"
+          },
+          {
+            "name": "floorListing",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "authorizationNonce",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "lastNonce",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "editionSaleState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "seller",
+            "type": "publicKey"
+          },
+          {
+            "name": "masterMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "maxPrints",
+            "type": "u64"
+          },
+          {
+            "name": "printed",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "depositReceipt",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "payer",
+            "type": "publicKey"
+          },
+          {
+            "name": "reference",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "createdAt",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "referrer",
+      "docs": [
+        "A referral key the auction house authority registered, orders only store a referral that",
+        "has one. Orders placed after the attribution window are not attributed to it."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "referral",
+            "type": "publicKey"
+          },
+          {
+            "name": "payout",
+            "type": "publicKey"
+          },
+          {
+            "name": "registeredAt",
+            "type": "i64"
+          },
+          {
+            "name": "attributionWindowSeconds",
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "makerRewards",
+      "docs": [
+        "A seller's opt-in liquidity mining account. Listings made with it passed in are enrolled, and",
+        "when an enrolled listing is cancelled or filled it accrues lamports for the days it was live and,",
+        "if a buyer took it, for its SOL volume, at the auction house's current rates. The rewards are",
+        "claimed from the auction house's maker rewards vault."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "unclaimed",
+            "type": "u64"
+          },
+          {
+            "name": "claimed",
+            "type": "u64"
+          },
+          {
+            "name": "listingSeconds",
+            "type": "u64"
+          },
+          {
+            "name": "filledVolume",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "escrowLedger",
+      "docs": [
+        "A buyer's opt-in inventory of the escrow, per payment mint: what its ledgered bids lock and what",
+        "is free. Bids placed with it passed in lock their full cost until they are cancelled or filled,",
+        "which requires it passed in again, and withdrawals that pass it in can't dip into the locked",
+        "balance. free is as of the last deposit, withdrawal or bid that passed the ledger in."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "balances",
+            "type": {
+              "array": [
+                {
+                  "defined": "EscrowLedgerBalance"
+                },
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "spendingAllowance",
+      "docs": [
+        "A treasury wallet's approval for a spender to top up its own escrow from the treasury's escrow",
+        "when it bids in SOL, up to limit lamports in total. The treasury raises limit to extend the",
+        "budget, or lowers it to spent to revoke it. What each open bid drew is locked to it, the",
+        "unspent part goes back to the treasury and the budget when the bid is cancelled or filled."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "treasury",
+            "type": "publicKey"
+          },
+          {
+            "name": "spender",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "limit",
+            "type": "u64"
+          },
+          {
+            "name": "spent",
+            "type": "u64"
+          },
+          {
+            "name": "locks",
+            "type": {
+              "array": [
+                {
+                  "defined": "SpendingAllowanceLock"
+                },
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "walletLimits",
+      "docs": [
+        "Trading limits the notary sets for a wallet, e.g. for a compliance program or to cap the damage",
+        "a compromised wallet can do. Bids placed with them passed in count as open bids until they are",
+        "cancelled or filled, and SOL buys they are passed to count towards the day's buy volume. Houses",
+        "that enforce wallet limits require them, or the wallet's uninitialized address if it has none,",
+        "on every bid and buy. A limit of 0 is no limit."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "maxDailyBuyVolume",
+            "type": "u64"
+          },
+          {
+            "name": "maxOpenBids",
+            "type": "u32"
+          },
+          {
+            "name": "day",
+            "type": "i64"
+          },
+          {
+            "name": "dayBuyVolume",
+            "type": "u64"
+          },
+          {
+            "name": "openBids",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "escrowLock",
+      "docs": [
+        "Escrow funds reserved for an off-chain order until a slot deadline. The funds sit in the lock",
+        "itself, lamports on top of its rent or its ATA for SPL payment mints, so no withdrawal can touch",
+        "them and matching engines can trust the quote without watching the escrow."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "orderHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "deadlineSlot",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rateLimit",
+      "docs": [
+        "A wallet's rate limited instructions in the last slot it sent one, for auction houses that",
+        "don't require the notary"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "slot",
+            "type": "u64"
+          },
+          {
+            "name": "count",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "listingInsurance",
+      "docs": [
+        "A seller's lamports backing their delegate listings of a token in an auction house. If a fill",
+        "fails because the seller moved the token or revoked the delegation, the notary can pay them to",
+        "the buyer with claim_listing_insurance."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "seller",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "withdrawableAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "proceedsSplit",
+      "docs": [
+        "How a seller splits their SOL sale proceeds, e.g. between the co-owners of an NFT or into a",
+        "team treasury. An execute passed the seller's ProceedsSplit pays each recipient its bps of what",
+        "the seller nets in the same settlement, the seller keeps the rest."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "seller",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "recipients",
+            "type": {
+              "array": [
+                {
+                  "defined": "ProceedsSplitRecipient"
+                },
+                5
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "notarySet",
+      "docs": [
+        "Notaries that can cosign for an auction house besides auction_house.notary, so the house",
+        "doesn't depend on a single notary key being available. Where the notary's cosign is required,",
+        "threshold of them signing anywhere in the instruction stand in for it."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "threshold",
+            "type": "u8"
+          },
+          {
+            "name": "notaries",
+            "type": {
+              "array": [
+                "publicKey",
+                5
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "bidGroup",
+      "docs": [
+        "Bids of a buyer on different mints, e.g. \"any 1 of these 5\", that share one escrow balance.",
+        "The group fills at most max_fills of them, the bids left can't fill once it had its max fills,",
+        "so a balance funded once for the group can't be over-filled, and the fill that consumes it",
+        "cancels those passed along. Bids are kept in the buyer's order of priority for matchers filling",
+        "the group."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "buyer",
+            "type": "publicKey"
+          },
+          {
+            "name": "id",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "maxFills",
+            "type": "u8"
+          },
+          {
+            "name": "fills",
+            "type": "u8"
+          },
+          {
+            "name": "bids",
+            "type": {
+              "array": [
+                "publicKey",
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "mintMarket",
+      "docs": [
+        "Top of book of a token mint on an auction house: its best SOL bid and listing, by price per",
+        "token, so other programs can read them without scanning trade states. Sells, bids, cancels and",
+        "executes update it whenever it is passed in. It's best effort: a cancelled, filled, hidden or",
+        "worse priced best order clears its side, until an order is placed or refresh_mint_market is",
+        "called, so readers still check the trade state it points to."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "bestBid",
+            "type": "publicKey"
+          },
+          {
+            "name": "bestBidPrice",
+            "type": "u64"
+          },
+          {
+            "name": "bestAsk",
+            "type": "publicKey"
+          },
+          {
+            "name": "bestAskPrice",
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdatedAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "royaltyOverride",
+      "docs": [
+        "Royalty recipients a mint's update authority set in place of its metadata creators, which",
+        "metadata caps at 5. A sale passed the mint's RoyaltyOverride pays its recipients the royalty",
+        "instead, and in SOL, those it couldn't fit in its accounts get a claim in the PendingRoyalties."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "recipients",
+            "type": {
+              "array": [
+                {
+                  "defined": "RoyaltyRecipient"
+                },
+                16
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "pendingRoyalties",
+      "docs": [
+        "SOL royalties owed to creators a sale couldn't fit in its transaction, e.g. royalty overrides",
+        "with more recipients than the account budget allows. The sale moves them into this account, one",
+        "per auction house and mint, and distribute_royalties pays the creators later. It holds its rent",
+        "plus the claims."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "rentPayer",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "claims",
+            "type": {
+              "array": [
+                {
+                  "defined": "RoyaltyClaim"
+                },
+                16
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "coOwnerApproval",
+      "docs": [
+        "The co-owners of a wallet that can't sign listings itself, e.g. a multisig PDA, and their",
+        "approvals for the next listing action on one token. Once threshold co-owners approved the same",
+        "action, any of them can sign sell_frozen (with the approved terms) or cancel_sell as the",
+        "seller_authority of the wallet. The approvals are used up by that action."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "seller",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "threshold",
+            "type": "u8"
+          },
+          {
+            "name": "owners",
+            "type": {
+              "array": [
+                "publicKey",
+                8
+              ]
+            }
+          },
+          {
+            "name": "approved",
+            "type": "u8"
+          },
+          {
+            "name": "actionHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "guardian",
+      "docs": [
+        "A key the wallet trusts to cancel its listings and bids for it, but not to create them, e.g. a",
+        "custodian's recovery key or a security team acting on a compromised wallet. Unlike",
+        "CANCEL_AUTHORITY, each wallet picks its own."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "guardian",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "escrowTopUp",
+      "docs": [
+        "A buyer's standing authorization for execute_sale_v2 to top up their escrow out of",
+        "funding_token_account when it's short of a fill. The token account delegates to this PDA, so",
+        "its delegated amount is what's left of the cap. A wSOL account tops up the SOL escrow, any",
+        "other mint the escrow's token account for bids in it."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "fundingTokenAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "programConfig",
+      "docs": [
+        "Per deployment configuration, so the same binary serves every cluster. It only adds to what",
+        "is built in (VALID_PAYMENT_MINTS, CANCEL_AUTHORITY and the default fees), as instructions only",
+        "see it when it is passed in. That includes the GatedInstructions it can disable."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMints",
+            "type": {
+              "array": [
+                "publicKey",
+                8
+              ]
+            }
+          },
+          {
+            "name": "cancelAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "disabledInstructions",
+            "type": "u32"
+          },
+          {
+            "name": "swapAdapter",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMintFees",
+            "type": {
+              "array": [
+                {
+                  "defined": "PaymentMintFees"
+                },
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "cancelAuthorityRegistry",
+      "docs": [
+        "The keys that can cancel orders on behalf of their makers in one auction house, replacing the",
+        "built-in CANCEL_AUTHORITY once the house has one"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "entries",
+            "type": {
+              "array": [
+                {
+                  "defined": "CancelAuthorityEntry"
+                },
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "listingSnapshot",
+      "docs": [
+        "A listing's terms as the notary recorded them at taken_at, e.g. for a support case. Written once",
+        "by snapshot_listing and never changed or closed."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "sellerTradeState",
+            "type": "publicKey"
+          },
+          {
+            "name": "snapshotId",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "takenAt",
+            "type": "i64"
+          },
+          {
+            "name": "seller",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "lastUpdatedAt",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "cpiAllowlist",
+      "docs": [
+        "The programs that may CPI into sales of one auction house under CpiPolicy::Allowlist"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "programs",
+            "type": {
+              "array": [
+                "publicKey",
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "houseMetrics",
+      "docs": [
+        "Sale totals of one auction house in one payment mint (the default pubkey for SOL), kept up",
+        "to date by the execute instructions whenever it is passed in remaining accounts"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouseKey",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "lastDay",
+            "type": "i64"
+          },
+          {
+            "name": "lifetime",
+            "type": {
+              "defined": "MetricsTotals"
+            }
+          },
+          {
+            "name": "rolling30d",
+            "type": {
+              "defined": "MetricsTotals"
+            }
+          },
+          {
+            "name": "daily",
+            "type": {
+              "array": [
+                {
+                  "defined": "MetricsTotals"
+                },
+                30
+              ]
+            }
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "BuyAuthorization",
+      "docs": [
+        "The message the buyer signs off-chain, Borsh serialized"
       ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "MIP1SellArgs"
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouse",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "buyerCreatorRoyaltyBp",
+            "type": "u16"
+          },
+          {
+            "name": "nonce",
+            "type": "u64"
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "mip1ExecuteSaleV2",
-      "accounts": [
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "buyer",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "seller",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "notary",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "programAsSigner",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buyerReceiptTokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "metadata",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "auctionHouse",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "auctionHouseTreasury",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellerTradeState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buyerTradeState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buyerEscrowPaymentAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "buyerReferral",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "sellerReferral",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenMetadataProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "edition",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "authorizationRulesProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "authorizationRules",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "ownerTokenRecord",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "destinationTokenRecord",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "instructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
+      "name": "DryRunResult",
+      "docs": [
+        "The outcome of each check dry_run_execute_sale runs on a prospective fill, 0 if it passes,",
+        "otherwise the error code execute_sale_v2 would fail the check with. Checks that need the trade",
+        "states carry trade_states' code when they can't be read."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "signer",
+            "type": "u32"
+          },
+          {
+            "name": "tradeStates",
+            "type": "u32"
+          },
+          {
+            "name": "bid",
+            "type": "u32"
+          },
+          {
+            "name": "listing",
+            "type": "u32"
+          },
+          {
+            "name": "expiry",
+            "type": "u32"
+          },
+          {
+            "name": "escrowBalance",
+            "type": "u32"
+          },
+          {
+            "name": "delegation",
+            "type": "u32"
+          },
+          {
+            "name": "metadata",
+            "type": "u32"
+          },
+          {
+            "name": "guards",
+            "type": "u32"
+          },
+          {
+            "name": "walletLimits",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ProgramConstants",
+      "docs": [
+        "The program's compiled constants, as returned by get_config"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "maxPrice",
+            "type": "u64"
+          },
+          {
+            "name": "defaultMakerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "defaultTakerFeeBp",
+            "type": "u16"
+          },
+          {
+            "name": "maxMakerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "maxTakerFeeBp",
+            "type": "u16"
+          },
+          {
+            "name": "defaultBidExpirySeconds",
+            "type": "i64"
+          },
+          {
+            "name": "cancelAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "paymentMints",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "seeds",
+            "type": {
+              "vec": "string"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "ListAutoArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "extraArgs",
+            "type": "bytes"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ListQuote",
+      "docs": [
+        "What a listing at a price nets the seller, as returned by quote_list"
       ],
-      "args": [
-        {
-          "name": "args",
-          "type": {
-            "defined": "MIP1ExecuteSaleV2Args"
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sellerProceeds",
+            "type": "u64"
+          },
+          {
+            "name": "makerFee",
+            "type": "i64"
+          },
+          {
+            "name": "takerFee",
+            "type": "u64"
+          },
+          {
+            "name": "royalty",
+            "type": "u64"
+          },
+          {
+            "name": "buyerTotalCost",
+            "type": "u64"
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "mip1CancelSell",
-      "accounts": [
-        {
-          "name": "wallet",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "notary",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "programAsSigner",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenAta",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "metadata",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "auctionHouse",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "sellerTradeState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenAccount",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "should always be ATA of (mint, wallet)"
-          ]
-        },
-        {
-          "name": "tokenAccountTemp",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "should always be ATA of (mint, program_as_signer)"
-          ]
-        },
-        {
-          "name": "tempTokenRecord",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenMetadataProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "edition",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "authorizationRulesProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "authorizationRules",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "ownerTokenRecord",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "destinationTokenRecord",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "instructions",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        }
+      "name": "SellAuthorization",
+      "docs": [
+        "The message the seller signs off-chain, Borsh serialized. It covers every account the relayer",
+        "could otherwise pick for the seller."
       ],
-      "args": []
-    }
-  ],
-  "accounts": [
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "auctionHouse",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenAccount",
+            "type": "publicKey"
+          },
+          {
+            "name": "sellerReferral",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "nonce",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "SweepBuyResult",
+      "docs": [
+        "What sweep_buy returns, one status per listing in the order they were passed"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "statuses",
+            "type": {
+              "vec": {
+                "defined": "SweepFillStatus"
+              }
+            }
+          },
+          {
+            "name": "spent",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "MIP1ExecuteSaleV2Args",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "u16"
+          },
+          {
+            "name": "expectedPaymentMint",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "MIP1SellArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "MIP1UpdateListingArgs",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "extraArgs",
+            "type": "bytes"
+          }
+        ]
+      }
+    },
+    {
+      "name": "OCPExecuteSaleV2Args",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "makerFeeBp",
+            "type": "i16"
+          },
+          {
+            "name": "takerFeeBp",
+            "type": "u16"
+          }
+        ]
+      }
+    },
     {
-      "name": "buyerTradeState",
+      "name": "OCPSellArgs",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "auctionHouseKey",
-            "type": "publicKey"
+            "name": "price",
+            "type": "u64"
           },
           {
-            "name": "buyer",
-            "type": "publicKey"
-          },
+            "name": "expiry",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "AltPrice",
+      "docs": [
+        "A payment mint a listing accepts besides its own, at a price in that mint. Slots with a zero",
+        "price are unused, a default payment mint is SOL."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "buyerReferral",
+            "name": "paymentMint",
             "type": "publicKey"
           },
           {
-            "name": "buyerPrice",
+            "name": "price",
             "type": "u64"
-          },
-          {
-            "name": "tokenMint",
-            "type": "publicKey"
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "BidEscalation",
+      "docs": [
+        "A bid that climbs from its buyer_price to max_price, linearly between start_at and end_at, so",
+        "a patient buyer can work up towards the floor without bidding again. The escrow must hold",
+        "max_price, the bid fills at any price between buyer_price and its current price."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "tokenSize",
+            "name": "maxPrice",
             "type": "u64"
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "startAt",
+            "type": "i64"
           },
           {
-            "name": "expiry",
+            "name": "endAt",
             "type": "i64"
           }
         ]
       }
     },
     {
-      "name": "sellerTradeState",
+      "name": "EscrowLedgerBalance",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "auctionHouseKey",
+            "name": "paymentMint",
             "type": "publicKey"
           },
           {
-            "name": "seller",
-            "type": "publicKey"
+            "name": "locked",
+            "type": "u64"
           },
           {
-            "name": "sellerReferral",
+            "name": "free",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "SpendingAllowanceLock",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "buyerTradeState",
             "type": "publicKey"
           },
           {
-            "name": "buyerPrice",
+            "name": "amount",
             "type": "u64"
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "ProceedsSplitRecipient",
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "tokenMint",
+            "name": "address",
             "type": "publicKey"
           },
           {
-            "name": "tokenAccount",
-            "type": "publicKey"
-          },
+            "name": "bp",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RoyaltyRecipient",
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "tokenSize",
-            "type": "u64"
+            "name": "address",
+            "type": "publicKey"
           },
           {
-            "name": "bump",
+            "name": "share",
             "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RoyaltyClaim",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "creator",
+            "type": "publicKey"
           },
           {
-            "name": "expiry",
-            "type": "i64"
+            "name": "amount",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "sellerTradeStateV2",
+      "name": "PaymentMintFees",
+      "docs": [
+        "The maker and taker fee bps of sales in a payment mint, e.g. higher ones for volatile tokens,",
+        "in place of the defaults when the notary didn't sign for the fees the sale was given"
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "auctionHouseKey",
+            "name": "mint",
             "type": "publicKey"
           },
           {
-            "name": "seller",
-            "type": "publicKey"
+            "name": "makerFeeBp",
+            "type": "i16"
           },
           {
-            "name": "sellerReferral",
+            "name": "takerFeeBp",
+            "type": "u16"
+          }
+        ]
+      }
+    },
+    {
+      "name": "CancelAuthorityEntry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "key",
             "type": "publicKey"
           },
           {
-            "name": "buyerPrice",
+            "name": "enabled",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "MetricsTotals",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "volume",
             "type": "u64"
           },
           {
-            "name": "tokenMint",
-            "type": "publicKey"
+            "name": "makerFees",
+            "type": "i64"
           },
           {
-            "name": "tokenAccount",
-            "type": "publicKey"
+            "name": "takerFees",
+            "type": "u64"
           },
           {
-            "name": "tokenSize",
+            "name": "royalty",
             "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "SweepFillStatus",
+      "docs": [
+        "What sweep_buy did with a listing"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Filled"
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "AlreadyFilled"
           },
           {
-            "name": "expiry",
-            "type": "i64"
+            "name": "OverBudget"
           },
           {
-            "name": "paymentMint",
-            "type": "publicKey"
+            "name": "Unavailable"
           }
         ]
       }
     },
     {
-      "name": "auctionHouse",
+      "name": "RoyaltyMode",
+      "docs": [
+        "What a listing asks of the royalty of the bids that fill it. pNFT and OCP sales always pay full",
+        "royalties and an auction house can enforce them for every listing, whatever the listing says."
+      ],
       "type": {
-        "kind": "struct",
-        "fields": [
+        "kind": "enum",
+        "variants": [
           {
-            "name": "auctionHouseTreasury",
-            "type": "publicKey"
+            "name": "Optional"
           },
           {
-            "name": "treasuryWithdrawalDestination",
-            "type": "publicKey"
-          },
+            "name": "Full"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Expiry",
+      "docs": [
+        "What the i64 expiry of a trade state, or of an instruction creating one, means. It's stored as",
+        "that i64 so layouts don't change: 0 and 1 never expire, a negative expiry is an escrowed",
+        "listing, where program_as_signer holds the token, and any other value is a unix timestamp, for",
+        "escrowed listings negated."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
           {
-            "name": "authority",
-            "type": "publicKey"
+            "name": "Never"
           },
           {
-            "name": "creator",
-            "type": "publicKey"
+            "name": "AtTimestamp",
+            "fields": [
+              "i64"
+            ]
           },
           {
-            "name": "notary",
-            "type": "publicKey"
+            "name": "EscrowedNoExpiry"
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "EscrowedAtTimestamp",
+            "fields": [
+              "i64"
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "CpiPolicy",
+      "docs": [
+        "Which programs may execute sales of an auction house through CPI. Top level sale",
+        "instructions are always allowed."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "AllowAll"
           },
           {
-            "name": "treasuryBump",
-            "type": "u8"
+            "name": "Allowlist"
+          }
+        ]
+      }
+    },
+    {
+      "name": "GatedInstruction",
+      "docs": [
+        "Entrypoints a deployment can retire once newer versions replace them"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Buy"
           },
           {
-            "name": "sellerFeeBasisPoints",
-            "type": "u16"
+            "name": "Sell"
+          }
+        ]
+      }
+    },
+    {
+      "name": "EscrowAdapterInstruction",
+      "docs": [
+        "Calls M2 makes into an external escrow program that funds bids instead of the buyer's",
+        "escrow PDA. They are encoded like Anchor instructions, the first 8 bytes of",
+        "sha256(\"global:<name>\") followed by the amount as u64 LE where there is one, with accounts:",
+        "0. auction_house (signer) - proves the call comes from M2",
+        "1. buyer_trade_state - the bid the funds are locked for",
+        "2. wallet - the buyer",
+        "3. escrow_payment_account (writable, pay only) - the buyer's escrow PDA, receives the funds",
+        "4. onwards, the adapter's own accounts, as passed after the adapter program in remaining accounts"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Lock",
+            "fields": [
+              {
+                "name": "amount",
+                "type": "u64"
+              }
+            ]
           },
           {
-            "name": "buyerReferralBp",
-            "type": "u16"
+            "name": "Release"
           },
           {
-            "name": "sellerReferralBp",
-            "type": "u16"
+            "name": "Pay",
+            "fields": [
+              {
+                "name": "amount",
+                "type": "u64"
+              }
+            ]
+          }
+        ]
+      }
+    },
+    {
+      "name": "ListingFlow",
+      "docs": [
+        "The flavor of listing flow, sell/cancel_sell, mip1_* or ocp_*, a token goes through."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Legacy"
           },
           {
-            "name": "requiresNotary",
-            "type": "bool"
+            "name": "Mip1"
           },
           {
-            "name": "nprob",
-            "type": "u8"
+            "name": "Ocp"
           }
         ]
       }
     },
     {
-      "name": "buyerTradeStateV2",
+      "name": "RemainingAccountsTag",
+      "docs": [
+        "A group of optional accounts a client can name in a remaining accounts schema. Tag 0 is",
+        "reserved, so zeroed bytes never read as a group.",
+        "",
+        "A schema describes the remaining accounts as they are passed: for each group, its 1-byte tag",
+        "then a 1-byte count of accounts, with each tag at most once. A handler taking a schema puts",
+        "the groups back into its positional order before splitting them, so clients don't depend on",
+        "where an optional group sits and a new group never shifts the accounts of the others.",
+        "",
+        "The schema always sits at REMAINING_ACCOUNTS_SCHEMA_OFFSET of the instruction's extra args,",
+        "whatever their length, zero padded up to it. Instructions without extra args take the bytes",
+        "appended to their instruction data after the args as such, read through the instructions",
+        "sysvar passed as the last remaining account, which isn't part of the schema."
+      ],
       "type": {
-        "kind": "struct",
-        "fields": [
+        "kind": "enum",
+        "variants": [
           {
-            "name": "auctionHouseKey",
-            "type": "publicKey"
+            "name": "ProgramConfig"
           },
           {
-            "name": "buyer",
-            "type": "publicKey"
+            "name": "RateLimit"
           },
           {
-            "name": "buyerReferral",
-            "type": "publicKey"
+            "name": "Payment"
           },
           {
-            "name": "buyerPrice",
-            "type": "u64"
+            "name": "EscrowAdapter"
           },
           {
-            "name": "tokenMint",
-            "type": "publicKey"
+            "name": "WalletLimits"
           },
           {
-            "name": "tokenSize",
-            "type": "u64"
+            "name": "SpendingAllowance"
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "EscrowLedger"
           },
           {
-            "name": "expiry",
-            "type": "i64"
+            "name": "Listing"
           },
           {
-            "name": "buyerCreatorRoyaltyBp",
-            "type": "u16"
+            "name": "OpenBidIndex"
           },
           {
-            "name": "paymentMint",
-            "type": "publicKey"
-          }
-        ]
-      }
-    }
-  ],
-  "types": [
-    {
-      "name": "MIP1ExecuteSaleV2Args",
-      "type": {
-        "kind": "struct",
-        "fields": [
+            "name": "Referrer"
+          },
           {
-            "name": "price",
-            "type": "u64"
+            "name": "RentVault"
           },
           {
-            "name": "makerFeeBp",
-            "type": "i16"
+            "name": "Payer"
           },
           {
-            "name": "takerFeeBp",
-            "type": "u16"
-          }
-        ]
-      }
-    },
-    {
-      "name": "MIP1SellArgs",
-      "type": {
-        "kind": "struct",
-        "fields": [
+            "name": "StakingAdapter"
+          },
           {
-            "name": "price",
-            "type": "u64"
+            "name": "MakerRewards"
           },
           {
-            "name": "expiry",
-            "type": "i64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "OCPExecuteSaleV2Args",
-      "type": {
-        "kind": "struct",
-        "fields": [
+            "name": "ListingIndex"
+          },
           {
-            "name": "price",
-            "type": "u64"
+            "name": "MintMarket"
           },
           {
-            "name": "makerFeeBp",
-            "type": "i16"
+            "name": "Guardian"
           },
           {
-            "name": "takerFeeBp",
-            "type": "u16"
-          }
-        ]
-      }
-    },
-    {
-      "name": "OCPSellArgs",
-      "type": {
-        "kind": "struct",
-        "fields": [
+            "name": "CoOwnerApproval"
+          },
           {
-            "name": "price",
-            "type": "u64"
+            "name": "Creators"
           },
           {
-            "name": "expiry",
-            "type": "i64"
+            "name": "Keyed"
+          },
+          {
+            "name": "LoanRepayment"
+          },
+          {
+            "name": "ProgramAsSigner"
+          },
+          {
+            "name": "Recipient"
+          },
+          {
+            "name": "NotarySet"
           }
         ]
       }
@@ -3966,6 +15342,406 @@ export const IDL: M2 = {
       "code": 6037,
       "name": "MissingRemainingAccount",
       "msg": "Missing remaining account"
+    },
+    {
+      "code": 6038,
+      "name": "ConflictingInstructionInTransaction",
+      "msg": "Another instruction in this transaction modifies the same trade state"
+    },
+    {
+      "code": 6039,
+      "name": "NoFreeEscrowBalance",
+      "msg": "No free escrow balance to withdraw"
+    },
+    {
+      "code": 6040,
+      "name": "OpenBidIndexFull",
+      "msg": "Open bid index is full"
+    },
+    {
+      "code": 6041,
+      "name": "InvalidSignedAuthorization",
+      "msg": "Missing or invalid off-chain signed authorization"
+    },
+    {
+      "code": 6042,
+      "name": "StaleAuthorizationNonce",
+      "msg": "Authorization nonce was already used"
+    },
+    {
+      "code": 6043,
+      "name": "EditionSaleSoldOut",
+      "msg": "All prints of this edition sale have been sold"
+    },
+    {
+      "code": 6044,
+      "name": "BatchTooLarge",
+      "msg": "Too many accounts in batch"
+    },
+    {
+      "code": 6045,
+      "name": "EscrowAdapterNotAllowed",
+      "msg": "Escrow adapter is not allowed by the auction house"
+    },
+    {
+      "code": 6046,
+      "name": "EscrowAdapterUnderpaid",
+      "msg": "Escrow adapter paid less than the bid price"
+    },
+    {
+      "code": 6047,
+      "name": "DelegateMismatch",
+      "msg": "Token account delegate is not the program signer"
+    },
+    {
+      "code": 6048,
+      "name": "WrongAuctionHouseForState",
+      "msg": "Trade state belongs to another auction house"
+    },
+    {
+      "code": 6049,
+      "name": "TokenNotEscrowed",
+      "msg": "Token is not escrowed by the program signer"
+    },
+    {
+      "code": 6050,
+      "name": "TokenAlreadyEscrowed",
+      "msg": "Token is already escrowed by another listing"
+    },
+    {
+      "code": 6051,
+      "name": "StaleMigrationState",
+      "msg": "Migration trade state doesn't match the token"
+    },
+    {
+      "code": 6052,
+      "name": "EscrowBalanceTooLow",
+      "msg": "Escrow balance is lower than the price"
+    },
+    {
+      "code": 6053,
+      "name": "TradeStateMismatch",
+      "msg": "Trade state doesn't match the given accounts or args"
+    },
+    {
+      "code": 6054,
+      "name": "TradeStateAlreadyExists",
+      "msg": "Trade state already exists"
+    },
+    {
+      "code": 6055,
+      "name": "EscrowAdapterMismatch",
+      "msg": "Escrow adapter doesn't match the bid"
+    },
+    {
+      "code": 6056,
+      "name": "UnexpectedRemainingAccounts",
+      "msg": "Unexpected remaining accounts"
+    },
+    {
+      "code": 6057,
+      "name": "OpenBidsRemaining",
+      "msg": "Wallet still has open bids"
+    },
+    {
+      "code": 6058,
+      "name": "PaymentMintMismatch",
+      "msg": "Payment mint doesn't match the expected payment mint"
+    },
+    {
+      "code": 6059,
+      "name": "BidTooLowForListing",
+      "msg": "Bid is below the minimum share of the listing price"
+    },
+    {
+      "code": 6060,
+      "name": "AutoRelistNotAllowed",
+      "msg": "Listing can't be auto relisted"
+    },
+    {
+      "code": 6061,
+      "name": "StrandedTradeState",
+      "msg": "Trade state was left without a discriminator, repair it with repair_trade_state"
+    },
+    {
+      "code": 6062,
+      "name": "NotUpgradeAuthority",
+      "msg": "Signer is not the program's upgrade authority"
+    },
+    {
+      "code": 6063,
+      "name": "CancelAuthorityRegistryFull",
+      "msg": "Cancel authority registry is full"
+    },
+    {
+      "code": 6064,
+      "name": "ListingRequiresFullRoyalty",
+      "msg": "Listing requires bids to pay the full creator royalty"
+    },
+    {
+      "code": 6065,
+      "name": "AuctionHouseEnforcesRoyalty",
+      "msg": "Auction house enforces the full creator royalty"
+    },
+    {
+      "code": 6066,
+      "name": "NoMakerRewardsToClaim",
+      "msg": "No maker rewards to claim, or the rewards vault is empty"
+    },
+    {
+      "code": 6067,
+      "name": "EscrowLedgerFull",
+      "msg": "Escrow ledger has no free slot for another payment mint"
+    },
+    {
+      "code": 6068,
+      "name": "EscrowBalanceLocked",
+      "msg": "Withdrawal would leave the escrow below what its open bids lock"
+    },
+    {
+      "code": 6069,
+      "name": "MetadataPinMismatch",
+      "msg": "Metadata no longer matches the hash the bid pinned"
+    },
+    {
+      "code": 6070,
+      "name": "SpendingAllowanceExceeded",
+      "msg": "Spending allowance limit exceeded"
+    },
+    {
+      "code": 6071,
+      "name": "HouseUpdateNotEffective",
+      "msg": "Scheduled auction house update is not effective yet"
+    },
+    {
+      "code": 6072,
+      "name": "BuyerNotAttested",
+      "msg": "Listing requires a valid attestation for the buyer"
+    },
+    {
+      "code": 6073,
+      "name": "MissingCreatorAccount",
+      "msg": "Missing the account of a creator owed royalty"
+    },
+    {
+      "code": 6074,
+      "name": "CpiCallerNotAllowed",
+      "msg": "Program is not allowed to CPI into sales of this auction house"
+    },
+    {
+      "code": 6075,
+      "name": "CpiAllowlistFull",
+      "msg": "CPI allowlist is full"
+    },
+    {
+      "code": 6076,
+      "name": "InvalidBidEscalation",
+      "msg": "Invalid bid escalation"
+    },
+    {
+      "code": 6077,
+      "name": "LoanRepaymentNotSupported",
+      "msg": "Loan repayment only works for SOL sales paid to the seller's wallet"
+    },
+    {
+      "code": 6078,
+      "name": "LoanProgramTookFromSeller",
+      "msg": "Loan program took from the seller"
+    },
+    {
+      "code": 6079,
+      "name": "WalletLimitExceeded",
+      "msg": "Wallet limit exceeded"
+    },
+    {
+      "code": 6080,
+      "name": "InvalidEscrowLockDeadline",
+      "msg": "Escrow lock deadline must be in the future and within MAX_ESCROW_LOCK_SLOTS"
+    },
+    {
+      "code": 6081,
+      "name": "EscrowLockActive",
+      "msg": "Escrow lock is active until its deadline, only the notary can release it early"
+    },
+    {
+      "code": 6082,
+      "name": "TokenAccountFrozen",
+      "msg": "Token account is frozen, unstake it first or pass the auction house's staking adapter"
+    },
+    {
+      "code": 6083,
+      "name": "AutoMatchNotEnabled",
+      "msg": "Both the listing and the bid must opt into auto matching"
+    },
+    {
+      "code": 6084,
+      "name": "RateLimitExceeded",
+      "msg": "Wallet exceeded the auction house's instructions per slot"
+    },
+    {
+      "code": 6085,
+      "name": "HalfMigratedTradeState",
+      "msg": "Listing was left half migrated, recover it with recover_seller_trade_state"
+    },
+    {
+      "code": 6086,
+      "name": "ListingInsuranceLocked",
+      "msg": "Listing insurance is still open to claims, withdraw again once the delay passed"
+    },
+    {
+      "code": 6087,
+      "name": "ListingNotBroken",
+      "msg": "Token is still escrowed or delegated by the seller, the listing isn't broken"
+    },
+    {
+      "code": 6088,
+      "name": "InvalidRemainingAccountsSchema",
+      "msg": "Remaining accounts schema is malformed or doesn't match the remaining accounts"
+    },
+    {
+      "code": 6089,
+      "name": "InvariantViolated",
+      "msg": "Strict invariant violated"
+    },
+    {
+      "code": 6090,
+      "name": "InvalidProceedsSplit",
+      "msg": "Proceeds split has too many recipients, an empty one or more than 10000 bp in total"
+    },
+    {
+      "code": 6091,
+      "name": "ProceedsSplitNotSupported",
+      "msg": "Proceeds split only applies to SOL proceeds paid to the seller's wallet"
+    },
+    {
+      "code": 6092,
+      "name": "InvalidCoOwners",
+      "msg": "Co-owners must be 1 to 8 distinct keys with a threshold between 1 and their count"
+    },
+    {
+      "code": 6093,
+      "name": "CoOwnerApprovalMissing",
+      "msg": "Co-owners haven't approved this listing action, or the signer isn't one of them"
+    },
+    {
+      "code": 6094,
+      "name": "InvalidGuardian",
+      "msg": "Guardian didn't sign or isn't the wallet's guardian"
+    },
+    {
+      "code": 6095,
+      "name": "SwapAdapterNotAllowed",
+      "msg": "Swap program isn't the program config's swap adapter"
+    },
+    {
+      "code": 6096,
+      "name": "SwapSlippageExceeded",
+      "msg": "Swap spent more than the bid's max slippage allows or paid less than the sale needs"
+    },
+    {
+      "code": 6097,
+      "name": "EscrowTopUpExceeded",
+      "msg": "Escrow top-up's funding token account doesn't delegate enough to cover the shortfall"
+    },
+    {
+      "code": 6098,
+      "name": "InvalidNotarySet",
+      "msg": "Notary set must be 1 to 5 distinct keys with a threshold between 1 and their count"
+    },
+    {
+      "code": 6099,
+      "name": "ZombieTradeState",
+      "msg": "Trade state was closed but holds lamports again, drain it with defund_zombie_state"
+    },
+    {
+      "code": 6100,
+      "name": "PendingRoyaltiesFull",
+      "msg": "Pending royalties have no free slot for another creator, distribute them first"
+    },
+    {
+      "code": 6101,
+      "name": "InvalidBidGroup",
+      "msg": "Bid group must have 1 to 8 distinct ungrouped bids of the buyer with max_fills between 1 and their count"
+    },
+    {
+      "code": 6102,
+      "name": "BidGroupConsumed",
+      "msg": "Bid group already had its max fills, its other bids can't fill"
+    },
+    {
+      "code": 6103,
+      "name": "ListingHidden",
+      "msg": "Listing is hidden until its reveal time"
+    },
+    {
+      "code": 6104,
+      "name": "SpendingAllowanceFull",
+      "msg": "Spending allowance has too many open bids"
+    },
+    {
+      "code": 6105,
+      "name": "FeeChangeNotScheduled",
+      "msg": "Fee changes must be scheduled with schedule_house_update"
+    },
+    {
+      "code": 6106,
+      "name": "LoanRepaymentMismatch",
+      "msg": "Loan accounts must be passed for listings that repay a loan, with the seller's loan vault, and only for them"
+    },
+    {
+      "code": 6107,
+      "name": "WalletLimitsRequired",
+      "msg": "Auction house enforces wallet limits, the wallet's WalletLimits address must be passed"
+    },
+    {
+      "code": 6108,
+      "name": "InvalidRoyaltyOverride",
+      "msg": "Royalty override must have 1 to 16 distinct recipients with shares adding up to 100"
+    },
+    {
+      "code": 6109,
+      "name": "EscrowNotSweepable",
+      "msg": "Escrow holds no dust to sweep, it's empty or above the sweepable amount"
+    },
+    {
+      "code": 6110,
+      "name": "OpenBidIndexIncomplete",
+      "msg": "Open bid index was created after the wallet's escrow backed bids, it can't vouch for all of them"
+    },
+    {
+      "code": 6111,
+      "name": "FloorConditionNotMet",
+      "msg": "Bid only fills while its collection's floor is at or below its max floor price"
+    },
+    {
+      "code": 6112,
+      "name": "InvalidMasterEdition",
+      "msg": "Master edition account can't be read as a master edition"
+    },
+    {
+      "code": 6113,
+      "name": "TreasuryBalanceTooLow",
+      "msg": "Treasury must keep its minimum leftover balance"
+    },
+    {
+      "code": 6114,
+      "name": "NotZombieTradeState",
+      "msg": "Trade state still holds a valid order, it isn't a zombie"
+    },
+    {
+      "code": 6115,
+      "name": "TrailingArgsMismatch",
+      "msg": "Current instruction isn't the one its trailing args were read for"
+    },
+    {
+      "code": 6116,
+      "name": "SpendingAllowanceMismatch",
+      "msg": "Spending allowance is for another auction house or spender"
+    },
+    {
+      "code": 6117,
+      "name": "InvalidTradeStateLength",
+      "msg": "Seller trade state has a length this program never wrote"
     }
   ]
 };